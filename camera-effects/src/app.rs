@@ -3,27 +3,20 @@
 //! This module contains the core graphics state including the wgpu device,
 //! queue, surface, and configuration needed for rendering.
 
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::Instant;
 use winit::dpi::PhysicalSize;
 use winit::event::WindowEvent;
 use winit::window::Window;
 
-use bytemuck::{Pod, Zeroable};
-
 use crate::camera::CameraCapture;
-use crate::effects::person_particles::{PersonParticlesEffect, Particle, ParticleParams, ParticleShape, ColorMode, MAX_PARTICLES};
-use crate::effects::EffectType;
+use crate::effects::{
+    find_preset_dir, list_presets, register_builtin_effects, Blitter, EffectChain, EffectChainRuntime, EffectContext,
+    EffectFrame, EffectPreset, EffectRegistry,
+};
 use crate::ml::MlInference;
-
-/// Mask parameters for masked passthrough shader
-#[repr(C)]
-#[derive(Clone, Copy, Pod, Zeroable)]
-struct MaskParams {
-    threshold: f32,
-    fade_amount: f32,
-    _pad: [f32; 2],
-}
+use crate::ui::effect_chain::{effect_chain_panel, preset_panel, preset_path, EffectStatus, PresetCommand};
 
 #[cfg(target_os = "macos")]
 use crate::network::SyphonSharer;
@@ -53,15 +46,17 @@ pub struct App {
     ml_inference: Option<MlInference>,
 
     // Effects
-    current_effect: EffectType,
     effect_enabled: bool,
-    person_particles: PersonParticlesEffect,
+    effect_registry: EffectRegistry,
+    effect_chain: EffectChain,
+    chain_runtime: EffectChainRuntime,
+    blitter: Blitter,
+
+    // Presets
+    preset_dir: PathBuf,
+    presets: Vec<PathBuf>,
+    preset_name: String,
 
-    // Particle rendering resources
-    particle_buffer: wgpu::Buffer,
-    particle_pipeline: wgpu::RenderPipeline,
-    particle_bind_group_layout: wgpu::BindGroupLayout,
-    particle_params_buffer: wgpu::Buffer,
     /// Default white texture for particles when no camera
     default_texture: wgpu::Texture,
     default_texture_view: wgpu::TextureView,
@@ -78,18 +73,13 @@ pub struct App {
     metal_command_queue: Option<metal::CommandQueue>,
     output_enabled: bool,
 
-    // Passthrough pipeline (camera -> output when no effect)
+    // Passthrough pipeline (output -> window)
     passthrough_pipeline: wgpu::RenderPipeline,
-    passthrough_bind_group_layout: wgpu::BindGroupLayout,
-    passthrough_bind_group: Option<wgpu::BindGroup>,
     sampler: wgpu::Sampler,
 
-    // Masked passthrough pipeline (camera masked by segmentation)
-    masked_pipeline: wgpu::RenderPipeline,
-    masked_bind_group_layout: wgpu::BindGroupLayout,
+    // Segmentation mask shared with effects
     mask_texture: wgpu::Texture,
     mask_texture_view: wgpu::TextureView,
-    mask_params_buffer: wgpu::Buffer,
 
     // egui integration
     egui_ctx: egui::Context,
@@ -298,106 +288,6 @@ impl App {
             ],
         });
 
-        // Create masked passthrough pipeline (for rendering camera masked by segmentation)
-        let masked_shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
-            label: Some("Masked Passthrough Shader"),
-            source: wgpu::ShaderSource::Wgsl(include_str!("shaders/masked_passthrough.wgsl").into()),
-        });
-
-        let masked_bind_group_layout =
-            device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
-                label: Some("Masked Bind Group Layout"),
-                entries: &[
-                    // Camera texture
-                    wgpu::BindGroupLayoutEntry {
-                        binding: 0,
-                        visibility: wgpu::ShaderStages::FRAGMENT,
-                        ty: wgpu::BindingType::Texture {
-                            sample_type: wgpu::TextureSampleType::Float { filterable: true },
-                            view_dimension: wgpu::TextureViewDimension::D2,
-                            multisampled: false,
-                        },
-                        count: None,
-                    },
-                    // Sampler
-                    wgpu::BindGroupLayoutEntry {
-                        binding: 1,
-                        visibility: wgpu::ShaderStages::FRAGMENT,
-                        ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
-                        count: None,
-                    },
-                    // Mask texture (R32Float is not filterable)
-                    wgpu::BindGroupLayoutEntry {
-                        binding: 2,
-                        visibility: wgpu::ShaderStages::FRAGMENT,
-                        ty: wgpu::BindingType::Texture {
-                            sample_type: wgpu::TextureSampleType::Float { filterable: false },
-                            view_dimension: wgpu::TextureViewDimension::D2,
-                            multisampled: false,
-                        },
-                        count: None,
-                    },
-                    // Mask params
-                    wgpu::BindGroupLayoutEntry {
-                        binding: 3,
-                        visibility: wgpu::ShaderStages::FRAGMENT,
-                        ty: wgpu::BindingType::Buffer {
-                            ty: wgpu::BufferBindingType::Uniform,
-                            has_dynamic_offset: false,
-                            min_binding_size: None,
-                        },
-                        count: None,
-                    },
-                ],
-            });
-
-        let masked_pipeline_layout =
-            device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
-                label: Some("Masked Pipeline Layout"),
-                bind_group_layouts: &[&masked_bind_group_layout],
-                push_constant_ranges: &[],
-            });
-
-        let masked_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
-            label: Some("Masked Pipeline"),
-            layout: Some(&masked_pipeline_layout),
-            vertex: wgpu::VertexState {
-                module: &masked_shader,
-                entry_point: Some("vs_main"),
-                buffers: &[],
-                compilation_options: Default::default(),
-            },
-            fragment: Some(wgpu::FragmentState {
-                module: &masked_shader,
-                entry_point: Some("fs_main"),
-                targets: &[Some(wgpu::ColorTargetState {
-                    format: wgpu::TextureFormat::Bgra8UnormSrgb,
-                    blend: Some(wgpu::BlendState {
-                        color: wgpu::BlendComponent {
-                            src_factor: wgpu::BlendFactor::SrcAlpha,
-                            dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
-                            operation: wgpu::BlendOperation::Add,
-                        },
-                        alpha: wgpu::BlendComponent {
-                            src_factor: wgpu::BlendFactor::One,
-                            dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
-                            operation: wgpu::BlendOperation::Add,
-                        },
-                    }),
-                    write_mask: wgpu::ColorWrites::ALL,
-                })],
-                compilation_options: Default::default(),
-            }),
-            primitive: wgpu::PrimitiveState {
-                topology: wgpu::PrimitiveTopology::TriangleList,
-                ..Default::default()
-            },
-            depth_stencil: None,
-            multisample: wgpu::MultisampleState::default(),
-            multiview: None,
-            cache: None,
-        });
-
         // Create mask texture (256x256 to match segmentation model output)
         let mask_texture = device.create_texture(&wgpu::TextureDescriptor {
             label: Some("Mask Texture"),
@@ -415,91 +305,6 @@ impl App {
         });
         let mask_texture_view = mask_texture.create_view(&wgpu::TextureViewDescriptor::default());
 
-        // Create mask params buffer
-        let mask_params_buffer = device.create_buffer(&wgpu::BufferDescriptor {
-            label: Some("Mask Params Buffer"),
-            size: std::mem::size_of::<MaskParams>() as u64,
-            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
-            mapped_at_creation: false,
-        });
-
-        // Create particle buffer (storage buffer for particle data)
-        let particle_buffer = device.create_buffer(&wgpu::BufferDescriptor {
-            label: Some("Particle Buffer"),
-            size: (MAX_PARTICLES * std::mem::size_of::<Particle>()) as u64,
-            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
-            mapped_at_creation: false,
-        });
-
-        // Create particle params uniform buffer
-        let particle_params_buffer = device.create_buffer(&wgpu::BufferDescriptor {
-            label: Some("Particle Params Buffer"),
-            size: std::mem::size_of::<ParticleParams>() as u64,
-            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
-            mapped_at_creation: false,
-        });
-
-        // Create particle bind group layout
-        let particle_bind_group_layout =
-            device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
-                label: Some("Particle Bind Group Layout"),
-                entries: &[
-                    // Particle storage buffer
-                    wgpu::BindGroupLayoutEntry {
-                        binding: 0,
-                        visibility: wgpu::ShaderStages::VERTEX,
-                        ty: wgpu::BindingType::Buffer {
-                            ty: wgpu::BufferBindingType::Storage { read_only: true },
-                            has_dynamic_offset: false,
-                            min_binding_size: None,
-                        },
-                        count: None,
-                    },
-                    // Camera texture
-                    wgpu::BindGroupLayoutEntry {
-                        binding: 1,
-                        visibility: wgpu::ShaderStages::FRAGMENT,
-                        ty: wgpu::BindingType::Texture {
-                            sample_type: wgpu::TextureSampleType::Float { filterable: true },
-                            view_dimension: wgpu::TextureViewDimension::D2,
-                            multisampled: false,
-                        },
-                        count: None,
-                    },
-                    // Sampler
-                    wgpu::BindGroupLayoutEntry {
-                        binding: 2,
-                        visibility: wgpu::ShaderStages::FRAGMENT,
-                        ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
-                        count: None,
-                    },
-                    // Params uniform
-                    wgpu::BindGroupLayoutEntry {
-                        binding: 3,
-                        visibility: wgpu::ShaderStages::VERTEX | wgpu::ShaderStages::FRAGMENT,
-                        ty: wgpu::BindingType::Buffer {
-                            ty: wgpu::BufferBindingType::Uniform,
-                            has_dynamic_offset: false,
-                            min_binding_size: None,
-                        },
-                        count: None,
-                    },
-                ],
-            });
-
-        // Create particle pipeline
-        let particle_shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
-            label: Some("Particle Shader"),
-            source: wgpu::ShaderSource::Wgsl(include_str!("shaders/particle.wgsl").into()),
-        });
-
-        let particle_pipeline_layout =
-            device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
-                label: Some("Particle Pipeline Layout"),
-                bind_group_layouts: &[&particle_bind_group_layout],
-                push_constant_ranges: &[],
-            });
-
         // Create default white texture for particles when no camera
         let default_texture = device.create_texture(&wgpu::TextureDescriptor {
             label: Some("Default Texture"),
@@ -540,46 +345,6 @@ impl App {
 
         let default_texture_view = default_texture.create_view(&wgpu::TextureViewDescriptor::default());
 
-        let particle_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
-            label: Some("Particle Pipeline"),
-            layout: Some(&particle_pipeline_layout),
-            vertex: wgpu::VertexState {
-                module: &particle_shader,
-                entry_point: Some("vs_main"),
-                buffers: &[],
-                compilation_options: Default::default(),
-            },
-            fragment: Some(wgpu::FragmentState {
-                module: &particle_shader,
-                entry_point: Some("fs_main"),
-                targets: &[Some(wgpu::ColorTargetState {
-                    format: wgpu::TextureFormat::Bgra8UnormSrgb, // Match output texture format (sRGB)
-                    blend: Some(wgpu::BlendState {
-                        color: wgpu::BlendComponent {
-                            src_factor: wgpu::BlendFactor::SrcAlpha,
-                            dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
-                            operation: wgpu::BlendOperation::Add,
-                        },
-                        alpha: wgpu::BlendComponent {
-                            src_factor: wgpu::BlendFactor::One,
-                            dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
-                            operation: wgpu::BlendOperation::Add,
-                        },
-                    }),
-                    write_mask: wgpu::ColorWrites::ALL,
-                })],
-                compilation_options: Default::default(),
-            }),
-            primitive: wgpu::PrimitiveState {
-                topology: wgpu::PrimitiveTopology::TriangleList,
-                ..Default::default()
-            },
-            depth_stencil: None,
-            multisample: wgpu::MultisampleState::default(),
-            multiview: None,
-            cache: None,
-        });
-
         // Initialize egui
         let egui_ctx = egui::Context::default();
         let mut style = (*egui_ctx.style()).clone();
@@ -601,6 +366,19 @@ impl App {
         #[cfg(target_os = "macos")]
         let (syphon_sharer, metal_command_queue) = Self::init_syphon(&device, output_width, output_height);
 
+        // Effect chain, starting with Person to Particles
+        let mut effect_registry = EffectRegistry::new();
+        register_builtin_effects(&mut effect_registry);
+        let mut effect_chain = EffectChain::new();
+        effect_registry.add_to_chain(&mut effect_chain, "person_particles");
+        let mut chain_runtime = EffectChainRuntime::new(&device, output_width, output_height);
+        chain_runtime.sync(&effect_chain, &effect_registry, &device, &queue);
+        let blitter = Blitter::new(&device);
+
+        let preset_dir = find_preset_dir();
+        let presets = list_presets(&preset_dir);
+        log::info!("Presets: {} in {}", presets.len(), preset_dir.display());
+
         let now = Instant::now();
 
         Self {
@@ -615,13 +393,14 @@ impl App {
             camera_texture_view: None,
             last_camera_frame: 0,
             ml_inference: None,
-            current_effect: EffectType::PersonParticles,
             effect_enabled: true,
-            person_particles: PersonParticlesEffect::new(),
-            particle_buffer,
-            particle_pipeline,
-            particle_bind_group_layout,
-            particle_params_buffer,
+            effect_registry,
+            effect_chain,
+            chain_runtime,
+            blitter,
+            preset_dir,
+            presets,
+            preset_name: String::new(),
             default_texture,
             default_texture_view,
             output_texture,
@@ -633,14 +412,9 @@ impl App {
             metal_command_queue,
             output_enabled: true,
             passthrough_pipeline,
-            passthrough_bind_group_layout,
-            passthrough_bind_group: None,
             sampler,
-            masked_pipeline,
-            masked_bind_group_layout,
             mask_texture,
             mask_texture_view,
-            mask_params_buffer,
             egui_ctx,
             egui_state,
             egui_renderer,
@@ -743,15 +517,49 @@ impl App {
         log::info!("Effect enabled: {}", self.effect_enabled);
     }
 
-    /// Select an effect by index
-    pub fn select_effect(&mut self, index: usize) {
-        self.current_effect = match index {
-            0 => EffectType::PersonParticles,
-            1 => EffectType::HandInteraction,
-            2 => EffectType::PaintWarp,
-            _ => EffectType::PersonParticles,
-        };
-        log::info!("Selected effect: {:?}", self.current_effect);
+    /// Toggle bypass of the effect at a chain position
+    pub fn toggle_chain_effect(&mut self, index: usize) {
+        if let Some(effect) = self.effect_chain.effects.get_mut(index) {
+            effect.bypassed = !effect.bypassed;
+            log::info!("Effect '{}' bypassed: {}", effect.name, effect.bypassed);
+        }
+    }
+
+    /// Run an effect action (e.g., "spawn_test") on every effect offering it
+    pub fn trigger_action(&mut self, action: &str) {
+        self.chain_runtime.trigger_all(action);
+    }
+
+    /// Replace the effect chain with a preset
+    pub fn load_preset(&mut self, path: &Path) {
+        match EffectPreset::load(path).and_then(|preset| preset.to_chain(&self.effect_registry)) {
+            Ok(chain) => {
+                self.effect_chain = chain;
+                self.chain_runtime
+                    .sync(&self.effect_chain, &self.effect_registry, &self.device, &self.queue);
+                log::info!("Loaded preset {}", path.display());
+            }
+            Err(e) => {
+                log::error!("{}", e);
+            }
+        }
+    }
+
+    /// Save the effect chain as a preset in the presets directory
+    pub fn save_preset(&mut self, name: &str) {
+        let path = preset_path(&self.preset_dir, name);
+        let result = std::fs::create_dir_all(&self.preset_dir)
+            .map_err(|e| format!("Failed to create {}: {}", self.preset_dir.display(), e))
+            .and_then(|_| EffectPreset::from_chain(name, &self.effect_chain).save(&path));
+        match result {
+            Ok(()) => {
+                log::info!("Saved preset {}", path.display());
+                self.presets = list_presets(&self.preset_dir);
+            }
+            Err(e) => {
+                log::error!("{}", e);
+            }
+        }
     }
 
     /// Connect to a camera
@@ -769,7 +577,6 @@ impl App {
                 // Texture will be created lazily in update_camera when first frame arrives
                 self.camera_texture = None;
                 self.camera_texture_view = None;
-                self.passthrough_bind_group = None;
                 self.last_camera_frame = 0;
 
                 // Auto-initialize ML when camera connects
@@ -790,7 +597,6 @@ impl App {
         }
         self.camera_texture = None;
         self.camera_texture_view = None;
-        self.passthrough_bind_group = None;
         log::info!("Camera disconnected");
     }
 
@@ -837,25 +643,8 @@ impl App {
             let camera_texture_view =
                 camera_texture.create_view(&wgpu::TextureViewDescriptor::default());
 
-            // Create bind group for camera texture
-            let bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
-                label: Some("Camera Bind Group"),
-                layout: &self.passthrough_bind_group_layout,
-                entries: &[
-                    wgpu::BindGroupEntry {
-                        binding: 0,
-                        resource: wgpu::BindingResource::TextureView(&camera_texture_view),
-                    },
-                    wgpu::BindGroupEntry {
-                        binding: 1,
-                        resource: wgpu::BindingResource::Sampler(&self.sampler),
-                    },
-                ],
-            });
-
             self.camera_texture = Some(camera_texture);
             self.camera_texture_view = Some(camera_texture_view);
-            self.passthrough_bind_group = Some(bind_group);
         }
 
         // Upload frame data to GPU texture
@@ -926,108 +715,12 @@ impl App {
             return;
         }
 
-        // Get ML result for segmentation
         let ml_result = self.ml_result();
-
-        match self.current_effect {
-            EffectType::PersonParticles => {
-                let segmentation = ml_result.as_ref().and_then(|r| r.segmentation.as_ref());
-                self.person_particles.update(delta_time, segmentation);
-            }
-            EffectType::HandInteraction => {
-                // TODO: Implement hand interaction
-            }
-            EffectType::PaintWarp => {
-                // TODO: Implement paint warp
-            }
-        }
-    }
-
-    /// Get particle count for UI
-    pub fn particle_count(&self) -> usize {
-        self.person_particles.particle_count()
-    }
-
-    /// Spawn test particles for debugging
-    pub fn spawn_test_particles(&mut self, count: usize) {
-        self.person_particles.spawn_test_particles(count);
-        log::info!("Spawned {} test particles (total: {})", count, self.particle_count());
-    }
-
-    /// Render particles to the output texture
-    fn render_particles(&self, encoder: &mut wgpu::CommandEncoder) {
-        let particles = self.person_particles.particles();
-        let particle_count = particles.len();
-
-        if particle_count == 0 {
-            return;
-        }
-
-        // Get texture view for particle color sampling (camera or default white)
-        let texture_view = self
-            .camera_texture_view
-            .as_ref()
-            .unwrap_or(&self.default_texture_view);
-
-        // Upload particle data to GPU
-        self.queue.write_buffer(
-            &self.particle_buffer,
-            0,
-            bytemuck::cast_slice(particles),
-        );
-
-        // Upload params
-        self.queue.write_buffer(
-            &self.particle_params_buffer,
-            0,
-            bytemuck::bytes_of(self.person_particles.params()),
-        );
-
-        // Create bind group for this frame
-        let particle_bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
-            label: Some("Particle Bind Group"),
-            layout: &self.particle_bind_group_layout,
-            entries: &[
-                wgpu::BindGroupEntry {
-                    binding: 0,
-                    resource: self.particle_buffer.as_entire_binding(),
-                },
-                wgpu::BindGroupEntry {
-                    binding: 1,
-                    resource: wgpu::BindingResource::TextureView(texture_view),
-                },
-                wgpu::BindGroupEntry {
-                    binding: 2,
-                    resource: wgpu::BindingResource::Sampler(&self.sampler),
-                },
-                wgpu::BindGroupEntry {
-                    binding: 3,
-                    resource: self.particle_params_buffer.as_entire_binding(),
-                },
-            ],
-        });
-
-        // Render particles
-        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
-            label: Some("Particle Render Pass"),
-            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                view: &self.output_texture_view,
-                resolve_target: None,
-                ops: wgpu::Operations {
-                    load: wgpu::LoadOp::Load, // Preserve camera image
-                    store: wgpu::StoreOp::Store,
-                },
-            })],
-            depth_stencil_attachment: None,
-            timestamp_writes: None,
-            occlusion_query_set: None,
-        });
-
-        render_pass.set_pipeline(&self.particle_pipeline);
-        render_pass.set_bind_group(0, &particle_bind_group, &[]);
-
-        // Instanced rendering: 6 vertices per quad, one instance per particle
-        render_pass.draw(0..6, 0..particle_count as u32);
+        let frame = EffectFrame {
+            delta_time,
+            ml: ml_result.as_ref(),
+        };
+        self.chain_runtime.update(&self.effect_chain, &frame);
     }
 
     /// Render a frame
@@ -1039,150 +732,56 @@ impl App {
             label: Some("Render Encoder"),
         });
 
-        // Check if we should use masked rendering (PersonParticles effect with ML)
-        let use_masked = self.effect_enabled
-            && matches!(self.current_effect, EffectType::PersonParticles)
-            && self.camera_texture_view.is_some();
-
         // Upload segmentation mask if available
-        let has_mask = if use_masked {
-            if let Some(ml_result) = self.ml_result() {
-                if let Some(ref seg) = ml_result.segmentation {
-                    // Upload mask to GPU
-                    self.queue.write_texture(
-                        wgpu::TexelCopyTextureInfo {
-                            texture: &self.mask_texture,
-                            mip_level: 0,
-                            origin: wgpu::Origin3d::ZERO,
-                            aspect: wgpu::TextureAspect::All,
-                        },
-                        bytemuck::cast_slice(&seg.mask),
-                        wgpu::TexelCopyBufferLayout {
-                            offset: 0,
-                            bytes_per_row: Some(seg.width * 4), // R32Float = 4 bytes per pixel
-                            rows_per_image: Some(seg.height),
-                        },
-                        wgpu::Extent3d {
-                            width: seg.width,
-                            height: seg.height,
-                            depth_or_array_layers: 1,
-                        },
-                    );
-                    true
-                } else {
-                    false
-                }
-            } else {
-                false
-            }
-        } else {
-            false
-        };
-
-        // Clear output texture first
-        {
-            let _render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
-                label: Some("Clear Pass"),
-                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                    view: &self.output_texture_view,
-                    resolve_target: None,
-                    ops: wgpu::Operations {
-                        load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
-                        store: wgpu::StoreOp::Store,
-                    },
-                })],
-                depth_stencil_attachment: None,
-                timestamp_writes: None,
-                occlusion_query_set: None,
-            });
-        }
-
-        // Render camera (masked or unmasked)
-        if use_masked && has_mask {
-            // Use masked rendering - only show person, hide background
-            if let Some(camera_texture_view) = &self.camera_texture_view {
-                // Upload mask params
-                let fade_person = self.person_particles.params().fade_person;
-                let mask_params = MaskParams {
-                    threshold: 0.5,
-                    fade_amount: fade_person,
-                    _pad: [0.0; 2],
-                };
-                self.queue.write_buffer(
-                    &self.mask_params_buffer,
-                    0,
-                    bytemuck::bytes_of(&mask_params),
-                );
-
-                // Create masked bind group
-                let masked_bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
-                    label: Some("Masked Bind Group"),
-                    layout: &self.masked_bind_group_layout,
-                    entries: &[
-                        wgpu::BindGroupEntry {
-                            binding: 0,
-                            resource: wgpu::BindingResource::TextureView(camera_texture_view),
-                        },
-                        wgpu::BindGroupEntry {
-                            binding: 1,
-                            resource: wgpu::BindingResource::Sampler(&self.sampler),
-                        },
-                        wgpu::BindGroupEntry {
-                            binding: 2,
-                            resource: wgpu::BindingResource::TextureView(&self.mask_texture_view),
-                        },
-                        wgpu::BindGroupEntry {
-                            binding: 3,
-                            resource: self.mask_params_buffer.as_entire_binding(),
-                        },
-                    ],
-                });
-
-                let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
-                    label: Some("Masked Camera Pass"),
-                    color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                        view: &self.output_texture_view,
-                        resolve_target: None,
-                        ops: wgpu::Operations {
-                            load: wgpu::LoadOp::Load,
-                            store: wgpu::StoreOp::Store,
-                        },
-                    })],
-                    depth_stencil_attachment: None,
-                    timestamp_writes: None,
-                    occlusion_query_set: None,
-                });
-
-                render_pass.set_pipeline(&self.masked_pipeline);
-                render_pass.set_bind_group(0, &masked_bind_group, &[]);
-                render_pass.draw(0..3, 0..1);
-            }
-        } else if let Some(camera_bind_group) = &self.passthrough_bind_group {
-            // Render camera feed unmasked
-            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
-                label: Some("Camera to Output Pass"),
-                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                    view: &self.output_texture_view,
-                    resolve_target: None,
-                    ops: wgpu::Operations {
-                        load: wgpu::LoadOp::Load,
-                        store: wgpu::StoreOp::Store,
-                    },
-                })],
-                depth_stencil_attachment: None,
-                timestamp_writes: None,
-                occlusion_query_set: None,
-            });
-
-            render_pass.set_pipeline(&self.passthrough_pipeline);
-            render_pass.set_bind_group(0, camera_bind_group, &[]);
-            render_pass.draw(0..3, 0..1);
+        let ml_result = self.ml_result();
+        let segmentation = ml_result.as_ref().and_then(|r| r.segmentation.as_ref());
+        if let Some(seg) = segmentation {
+            self.queue.write_texture(
+                wgpu::TexelCopyTextureInfo {
+                    texture: &self.mask_texture,
+                    mip_level: 0,
+                    origin: wgpu::Origin3d::ZERO,
+                    aspect: wgpu::TextureAspect::All,
+                },
+                bytemuck::cast_slice(&seg.mask),
+                wgpu::TexelCopyBufferLayout {
+                    offset: 0,
+                    bytes_per_row: Some(seg.width * 4), // R32Float = 4 bytes per pixel
+                    rows_per_image: Some(seg.height),
+                },
+                wgpu::Extent3d {
+                    width: seg.width,
+                    height: seg.height,
+                    depth_or_array_layers: 1,
+                },
+            );
         }
 
-        // Render particles if effect is enabled
-        if self.effect_enabled && matches!(self.current_effect, EffectType::PersonParticles) {
-            self.render_particles(&mut encoder);
-        }
+        // Run the effect chain over the camera image into the output texture
+        let context = EffectContext {
+            device: &self.device,
+            queue: &self.queue,
+            camera: self
+                .camera_texture_view
+                .as_ref()
+                .unwrap_or(&self.default_texture_view),
+            mask: segmentation.map(|_| &self.mask_texture_view),
+            sampler: &self.sampler,
+            blitter: &self.blitter,
+        };
+        let bypass_chain = EffectChain::new();
+        let chain = if self.effect_enabled {
+            &self.effect_chain
+        } else {
+            &bypass_chain
+        };
+        self.chain_runtime.process(
+            &mut encoder,
+            chain,
+            &context,
+            self.camera_texture_view.as_ref(),
+            &self.output_texture_view,
+        );
 
         // Render output to window (use cached bind group)
         {
@@ -1227,7 +826,6 @@ impl App {
         let raw_input = self.egui_state.take_egui_input(&self.window);
 
         // Get UI state before running egui
-        let current_effect = self.current_effect;
         let effect_enabled = self.effect_enabled;
         let fps = self.fps;
         let camera_connected = self.camera.is_some();
@@ -1244,31 +842,34 @@ impl App {
         let ml_ready = self.is_ml_ready();
         let ml_initializing = self.ml_inference.is_some() && !ml_ready;
         let ml_result = self.ml_result();
-        let particle_count = self.particle_count();
-
-        // Get current particle settings
-        let mut particle_shape = self.person_particles.shape();
-        let mut particle_color_mode = self.person_particles.color_mode();
-        let mut spawn_rate = self.person_particles.params().spawn_rate;
-        let mut particle_size = self.person_particles.params().particle_size;
-        let mut particle_lifetime = self.person_particles.params().particle_lifetime;
-        let mut gravity_y = self.person_particles.params().gravity[1];
-        let mut wind_x = self.person_particles.params().wind[0];
-        let mut turbulence = self.person_particles.params().turbulence_strength;
-        let mut spawn_inside = self.person_particles.params().spawn_inside != 0;
-        let mut fade_person = self.person_particles.params().fade_person;
-        let mut solid_color = self.person_particles.params().solid_color;
-        let mut gradient_start = self.person_particles.params().gradient_start;
-        let mut gradient_end = self.person_particles.params().gradient_end;
-
-        // Run egui with a closure that doesn't borrow self
-        let mut new_effect = None;
+
+        // Edit a copy of the chain; runtimes report status and actions
+        let mut effect_chain = self.effect_chain.clone();
+        let active_effects = effect_chain.active().count();
+        let statuses: Vec<EffectStatus> = effect_chain
+            .effects
+            .iter()
+            .filter_map(|effect| {
+                let runtime = self.chain_runtime.runtime(effect.id)?;
+                Some(EffectStatus {
+                    id: effect.id,
+                    status: runtime.status(),
+                    actions: runtime.actions(),
+                })
+            })
+            .collect();
+        let registry = &self.effect_registry;
+        let presets = &self.presets;
+        let mut preset_name = self.preset_name.clone();
+
+        // Run egui with a closure that doesn't borrow self mutably
         let mut toggle_effect = false;
         let mut connect_camera_index: Option<u32> = None;
         let mut disconnect_camera = false;
         let mut init_ml = false;
-        let mut spawn_test = false;
-        let mut clear_particles = false;
+        let mut chain_changed = false;
+        let mut triggered_actions = Vec::new();
+        let mut preset_command = None;
 
         let full_output = self.egui_ctx.run(raw_input, |ctx| {
             egui::TopBottomPanel::top("top_panel").show(ctx, |ui| {
@@ -1281,7 +882,7 @@ impl App {
                         ui.label(format!("Camera frames: {}", camera_frame_count));
                         ui.separator();
                     }
-                    ui.label(format!("Effect: {:?}", current_effect));
+                    ui.label(format!("Effects: {} active", active_effects));
                     if ui.button(if effect_enabled { "Disable" } else { "Enable" }).clicked() {
                         toggle_effect = true;
                     }
@@ -1318,174 +919,15 @@ impl App {
                 ui.heading("Effects");
                 ui.separator();
 
-                if ui.selectable_label(
-                    matches!(current_effect, EffectType::PersonParticles),
-                    format!("1. Person to Particles ({})", particle_count),
-                ).clicked() {
-                    new_effect = Some(0);
-                }
-
-                // Particle settings when PersonParticles is selected
-                if matches!(current_effect, EffectType::PersonParticles) {
-                    ui.horizontal(|ui| {
-                        if ui.button("Test (T)").clicked() {
-                            spawn_test = true;
-                        }
-                        if ui.button("Clear").clicked() {
-                            clear_particles = true;
-                        }
-                    });
-
-                    ui.separator();
-                    ui.heading("Particle Settings");
-
-                    // Shape selection
-                    ui.label("Shape:");
-                    ui.horizontal(|ui| {
-                        if ui.selectable_label(particle_shape == ParticleShape::Circle, "Circle").clicked() {
-                            particle_shape = ParticleShape::Circle;
-                        }
-                        if ui.selectable_label(particle_shape == ParticleShape::Square, "Square").clicked() {
-                            particle_shape = ParticleShape::Square;
-                        }
-                        if ui.selectable_label(particle_shape == ParticleShape::Star, "Star").clicked() {
-                            particle_shape = ParticleShape::Star;
-                        }
-                    });
-                    ui.horizontal(|ui| {
-                        if ui.selectable_label(particle_shape == ParticleShape::Heart, "Heart").clicked() {
-                            particle_shape = ParticleShape::Heart;
-                        }
-                        if ui.selectable_label(particle_shape == ParticleShape::Diamond, "Diamond").clicked() {
-                            particle_shape = ParticleShape::Diamond;
-                        }
-                    });
-
-                    ui.add_space(4.0);
-
-                    // Color mode selection
-                    ui.label("Color Mode:");
-                    ui.horizontal(|ui| {
-                        if ui.selectable_label(particle_color_mode == ColorMode::Original, "Camera").clicked() {
-                            particle_color_mode = ColorMode::Original;
-                        }
-                        if ui.selectable_label(particle_color_mode == ColorMode::Solid, "Solid").clicked() {
-                            particle_color_mode = ColorMode::Solid;
-                        }
-                    });
-                    ui.horizontal(|ui| {
-                        if ui.selectable_label(particle_color_mode == ColorMode::Rainbow, "Rainbow").clicked() {
-                            particle_color_mode = ColorMode::Rainbow;
-                        }
-                        if ui.selectable_label(particle_color_mode == ColorMode::Gradient, "Gradient").clicked() {
-                            particle_color_mode = ColorMode::Gradient;
-                        }
-                    });
-
-                    // Show color picker for solid color mode
-                    if particle_color_mode == ColorMode::Solid {
-                        ui.horizontal(|ui| {
-                            ui.label("Color:");
-                            let mut color = egui::Color32::from_rgba_unmultiplied(
-                                (solid_color[0] * 255.0) as u8,
-                                (solid_color[1] * 255.0) as u8,
-                                (solid_color[2] * 255.0) as u8,
-                                (solid_color[3] * 255.0) as u8,
-                            );
-                            if ui.color_edit_button_srgba(&mut color).changed() {
-                                solid_color = [
-                                    color.r() as f32 / 255.0,
-                                    color.g() as f32 / 255.0,
-                                    color.b() as f32 / 255.0,
-                                    color.a() as f32 / 255.0,
-                                ];
-                            }
-                        });
-                    }
-
-                    // Show gradient color pickers
-                    if particle_color_mode == ColorMode::Gradient {
-                        ui.horizontal(|ui| {
-                            ui.label("Start:");
-                            let mut start_color = egui::Color32::from_rgba_unmultiplied(
-                                (gradient_start[0] * 255.0) as u8,
-                                (gradient_start[1] * 255.0) as u8,
-                                (gradient_start[2] * 255.0) as u8,
-                                (gradient_start[3] * 255.0) as u8,
-                            );
-                            if ui.color_edit_button_srgba(&mut start_color).changed() {
-                                gradient_start = [
-                                    start_color.r() as f32 / 255.0,
-                                    start_color.g() as f32 / 255.0,
-                                    start_color.b() as f32 / 255.0,
-                                    start_color.a() as f32 / 255.0,
-                                ];
-                            }
-                        });
-                        ui.horizontal(|ui| {
-                            ui.label("End:");
-                            let mut end_color = egui::Color32::from_rgba_unmultiplied(
-                                (gradient_end[0] * 255.0) as u8,
-                                (gradient_end[1] * 255.0) as u8,
-                                (gradient_end[2] * 255.0) as u8,
-                                (gradient_end[3] * 255.0) as u8,
-                            );
-                            if ui.color_edit_button_srgba(&mut end_color).changed() {
-                                gradient_end = [
-                                    end_color.r() as f32 / 255.0,
-                                    end_color.g() as f32 / 255.0,
-                                    end_color.b() as f32 / 255.0,
-                                    end_color.a() as f32 / 255.0,
-                                ];
-                            }
-                        });
-                    }
-
-                    ui.add_space(4.0);
-
-                    // Sliders for particle parameters
-                    ui.add(egui::Slider::new(&mut spawn_rate, 100.0..=10000.0)
-                        .text("Spawn Rate")
-                        .logarithmic(true));
-
-                    ui.add(egui::Slider::new(&mut particle_size, 0.005..=0.1)
-                        .text("Size"));
-
-                    ui.add(egui::Slider::new(&mut particle_lifetime, 0.5..=10.0)
-                        .text("Lifetime"));
-
-                    ui.add(egui::Slider::new(&mut gravity_y, -0.5..=0.5)
-                        .text("Gravity"));
-
-                    ui.add(egui::Slider::new(&mut wind_x, -0.2..=0.2)
-                        .text("Wind"));
-
-                    ui.add(egui::Slider::new(&mut turbulence, 0.0..=1.0)
-                        .text("Turbulence"));
-
-                    ui.add_space(4.0);
-                    ui.label("Person Visibility:");
-                    ui.add(egui::Slider::new(&mut fade_person, 0.0..=1.0)
-                        .text("Fade"));
-
-                    ui.checkbox(&mut spawn_inside, "Spawn inside silhouette");
-                }
+                egui::ScrollArea::vertical().max_height(ui.available_height() * 0.6).show(ui, |ui| {
+                    let (changed, triggered) = effect_chain_panel(ui, &mut effect_chain, registry, &statuses);
+                    chain_changed = changed;
+                    triggered_actions = triggered;
+                });
 
                 ui.separator();
-
-                if ui.selectable_label(
-                    matches!(current_effect, EffectType::HandInteraction),
-                    "2. Hand Interaction",
-                ).clicked() {
-                    new_effect = Some(1);
-                }
-
-                if ui.selectable_label(
-                    matches!(current_effect, EffectType::PaintWarp),
-                    "3. Paint Warp",
-                ).clicked() {
-                    new_effect = Some(2);
-                }
+                ui.heading("Presets");
+                preset_command = preset_panel(ui, presets, &mut preset_name);
 
                 ui.separator();
                 ui.heading("Output");
@@ -1524,9 +966,6 @@ impl App {
         if toggle_effect {
             self.toggle_effect();
         }
-        if let Some(idx) = new_effect {
-            self.select_effect(idx);
-        }
         if let Some(idx) = connect_camera_index {
             self.connect_camera(idx);
         }
@@ -1536,27 +975,24 @@ impl App {
         if init_ml {
             self.init_ml();
         }
-        if spawn_test {
-            self.spawn_test_particles(100);
+
+        // Apply effect chain edits
+        self.preset_name = preset_name;
+        if chain_changed {
+            self.effect_chain = effect_chain;
+            self.chain_runtime
+                .sync(&self.effect_chain, &self.effect_registry, &self.device, &self.queue);
+        }
+        for triggered in triggered_actions {
+            self.chain_runtime.trigger(triggered.id, triggered.action);
         }
-        if clear_particles {
-            self.person_particles.clear();
+        match preset_command {
+            Some(PresetCommand::Load(path)) => self.load_preset(&path),
+            Some(PresetCommand::Save(name)) => self.save_preset(&name),
+            Some(PresetCommand::Refresh) => self.presets = list_presets(&self.preset_dir),
+            None => {}
         }
 
-        // Apply particle settings
-        self.person_particles.set_shape(particle_shape);
-        self.person_particles.set_color_mode(particle_color_mode);
-        self.person_particles.set_spawn_rate(spawn_rate);
-        self.person_particles.set_particle_size(particle_size);
-        self.person_particles.params_mut().particle_lifetime = particle_lifetime;
-        self.person_particles.params_mut().gravity[1] = gravity_y;
-        self.person_particles.params_mut().wind[0] = wind_x;
-        self.person_particles.params_mut().turbulence_strength = turbulence;
-        self.person_particles.set_spawn_inside(spawn_inside);
-        self.person_particles.set_fade_person(fade_person);
-        self.person_particles.set_solid_color(solid_color[0], solid_color[1], solid_color[2], solid_color[3]);
-        self.person_particles.set_gradient(gradient_start, gradient_end);
-
         self.egui_state.handle_platform_output(&self.window, full_output.platform_output);
 
         let paint_jobs = self.egui_ctx.tessellate(full_output.shapes, full_output.pixels_per_point);
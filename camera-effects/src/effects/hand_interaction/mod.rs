@@ -2,6 +2,10 @@
 //!
 //! Particles respond to hand landmark positions with various interaction modes.
 
+mod runtime;
+
+pub use runtime::{HandInteractionDefinition, HandInteractionRuntime};
+
 use bytemuck::{Pod, Zeroable};
 use crate::ml::Hand;
 
@@ -14,6 +18,26 @@ pub enum InteractionMode {
     Push,
 }
 
+impl InteractionMode {
+    /// All modes, in parameter option order
+    pub const ALL: [InteractionMode; 4] = [
+        InteractionMode::Attract,
+        InteractionMode::Repel,
+        InteractionMode::Swirl,
+        InteractionMode::Push,
+    ];
+
+    /// Display name
+    pub fn name(&self) -> &'static str {
+        match self {
+            InteractionMode::Attract => "Attract",
+            InteractionMode::Repel => "Repel",
+            InteractionMode::Swirl => "Swirl",
+            InteractionMode::Push => "Push",
+        }
+    }
+}
+
 /// Hand point for GPU
 #[repr(C)]
 #[derive(Clone, Copy, Pod, Zeroable)]
@@ -138,6 +162,29 @@ impl HandInteractionEffect {
         };
     }
 
+    /// Get current mode
+    pub fn mode(&self) -> InteractionMode {
+        self.mode
+    }
+
+    /// Update the effect
+    pub fn update(&mut self, delta_time: f32) {
+        self.params.time += delta_time;
+        self.params.delta_time = delta_time;
+    }
+
+    /// Set a parameter by name
+    pub fn set_param(&mut self, name: &str, value: f32) {
+        match name {
+            "force_strength" => self.params.force_strength = value,
+            "force_radius" => self.params.force_radius = value,
+            "max_velocity" => self.params.max_velocity = value,
+            "palm_force_multiplier" => self.params.palm_force_multiplier = value,
+            "fingertip_force_multiplier" => self.params.fingertip_force_multiplier = value,
+            _ => {}
+        }
+    }
+
     /// Get effect parameters
     pub fn params(&self) -> &HandInteractionParams {
         &self.params
@@ -0,0 +1,101 @@
+//! Hand Interaction chain runtime
+
+use super::{HandInteractionEffect, HandInteractionParams, InteractionMode};
+use crate::effects::{
+    EffectContext, EffectDefinition, EffectFrame, EffectInstance, EffectRuntime, Parameter, ParameterMeta,
+    ParameterValue,
+};
+
+/// Hand Interaction effect definition
+///
+/// Placeholder until the GPU pass exists: the runtime passes its input
+/// through unchanged, and the display name says so.
+pub struct HandInteractionDefinition;
+
+impl EffectDefinition for HandInteractionDefinition {
+    fn effect_type(&self) -> &'static str {
+        "hand_interaction"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "Hand Interaction (placeholder)"
+    }
+
+    fn default_parameters(&self) -> Vec<Parameter> {
+        let defaults = HandInteractionParams::default();
+        let modes: Vec<&str> = InteractionMode::ALL.iter().map(|m| m.name()).collect();
+
+        vec![
+            Parameter::new(ParameterMeta::enumeration("mode", "Mode", &modes, defaults.mode as usize)),
+            Parameter::new(ParameterMeta::float(
+                "force_strength",
+                "Force",
+                defaults.force_strength,
+                0.0,
+                2.0,
+            )),
+            Parameter::new(ParameterMeta::float("force_radius", "Radius", defaults.force_radius, 0.01, 1.0)),
+            Parameter::new(ParameterMeta::float(
+                "max_velocity",
+                "Max Velocity",
+                defaults.max_velocity,
+                0.1,
+                5.0,
+            )),
+        ]
+    }
+
+    fn create_runtime(
+        &self,
+        _device: &wgpu::Device,
+        _queue: &wgpu::Queue,
+        _output_format: wgpu::TextureFormat,
+    ) -> Box<dyn EffectRuntime> {
+        Box::new(HandInteractionRuntime {
+            effect: HandInteractionEffect::new(),
+        })
+    }
+}
+
+/// Hand Interaction runtime
+pub struct HandInteractionRuntime {
+    effect: HandInteractionEffect,
+}
+
+impl EffectRuntime for HandInteractionRuntime {
+    fn effect_type(&self) -> &'static str {
+        "hand_interaction"
+    }
+
+    fn update(&mut self, instance: &EffectInstance, frame: &EffectFrame) {
+        for param in &instance.parameters {
+            match (param.meta.name.as_str(), &param.value) {
+                ("mode", value) => {
+                    let mode = InteractionMode::ALL
+                        .get(value.as_index())
+                        .copied()
+                        .unwrap_or(InteractionMode::Attract);
+                    self.effect.set_mode(mode);
+                }
+                (name, ParameterValue::Float(value)) => self.effect.set_param(name, *value),
+                _ => {}
+            }
+        }
+
+        self.effect.update(frame.delta_time);
+        if let Some(ml) = frame.ml {
+            self.effect.update_hands(&ml.hands);
+        }
+    }
+
+    fn process(
+        &mut self,
+        encoder: &mut wgpu::CommandEncoder,
+        context: &EffectContext,
+        input: &wgpu::TextureView,
+        output: &wgpu::TextureView,
+    ) {
+        // TODO: Run the hand_interaction compute pass over a particle buffer
+        context.blitter.blit(encoder, context.device, input, output);
+    }
+}
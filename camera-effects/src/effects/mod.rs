@@ -1,24 +1,45 @@
 //! Effects module
 //!
-//! Provides GPU-based visual effects for camera input.
+//! Provides GPU-based visual effects for camera input, stacked in a chain.
+//!
+//! # Architecture
+//!
+//! Mirrors immersive-server's effects system:
+//!
+//! - **Data types** (`types.rs`): typed parameters, `EffectInstance` and
+//!   `EffectChain`
+//! - **Traits** (`traits.rs`): `EffectDefinition` (factory and parameter
+//!   metadata) and `EffectRuntime` (per-instance state and rendering)
+//! - **Registry** (`registry.rs`): available effects by type identifier
+//! - **Runtime** (`runtime.rs`): runs a chain, each effect reading the
+//!   previous effect's output
+//! - **Presets** (`preset.rs`): chains saved as JSON
+//!
+//! # Adding an effect
+//!
+//! Implement `EffectDefinition` and `EffectRuntime` in a new module and
+//! register the definition in `register_builtin_effects`; the UI builds
+//! its controls from the parameter metadata.
+
+mod preset;
+mod registry;
+mod runtime;
+mod traits;
+mod types;
 
 pub mod person_particles;
 pub mod hand_interaction;
 pub mod paint_warp;
 
-/// Effect types
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
-pub enum EffectType {
-    /// Turn people into particles
-    PersonParticles,
-    /// Hand-driven particle interaction
-    HandInteraction,
-    /// Paint-like warping
-    PaintWarp,
-}
+pub use preset::*;
+pub use registry::*;
+pub use runtime::*;
+pub use traits::*;
+pub use types::*;
 
-impl Default for EffectType {
-    fn default() -> Self {
-        Self::PersonParticles
-    }
+/// Register all built-in effects
+pub fn register_builtin_effects(registry: &mut EffectRegistry) {
+    registry.register(person_particles::PersonParticlesDefinition);
+    registry.register(hand_interaction::HandInteractionDefinition);
+    registry.register(paint_warp::PaintWarpDefinition);
 }
@@ -3,6 +3,10 @@
 //! Image warps and smears like wet paint when touched,
 //! with persistent trails that decay over time.
 
+mod runtime;
+
+pub use runtime::{PaintWarpDefinition, PaintWarpRuntime};
+
 use bytemuck::{Pod, Zeroable};
 
 /// Touch point for input
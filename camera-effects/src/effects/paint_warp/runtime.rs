@@ -0,0 +1,119 @@
+//! Paint Warp chain runtime
+
+use super::{PaintWarpEffect, PaintWarpParams};
+use crate::effects::{
+    EffectAction, EffectContext, EffectDefinition, EffectFrame, EffectInstance, EffectRuntime, Parameter,
+    ParameterMeta,
+};
+
+/// Actions offered in the UI
+const ACTIONS: &[EffectAction] = &[EffectAction {
+    name: "clear",
+    label: "Clear",
+}];
+
+/// Paint Warp effect definition
+///
+/// Placeholder until the GPU pass exists: the runtime passes its input
+/// through unchanged, and the display name says so.
+pub struct PaintWarpDefinition;
+
+impl EffectDefinition for PaintWarpDefinition {
+    fn effect_type(&self) -> &'static str {
+        "paint_warp"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "Paint Warp (placeholder)"
+    }
+
+    fn default_parameters(&self) -> Vec<Parameter> {
+        let defaults = PaintWarpParams::default();
+
+        vec![
+            Parameter::new(ParameterMeta::float("viscosity", "Viscosity", defaults.viscosity, 0.9, 0.999)),
+            Parameter::new(ParameterMeta::float(
+                "displacement_strength",
+                "Strength",
+                defaults.displacement_strength,
+                0.0,
+                2.0,
+            )),
+            Parameter::new(ParameterMeta::float(
+                "brush_radius",
+                "Brush Radius",
+                defaults.brush_radius,
+                0.01,
+                0.5,
+            )),
+            Parameter::new(ParameterMeta::float(
+                "brush_softness",
+                "Brush Softness",
+                defaults.brush_softness,
+                0.0,
+                1.0,
+            )),
+            Parameter::new(ParameterMeta::float(
+                "smear_length",
+                "Smear Length",
+                defaults.smear_length,
+                0.0,
+                10.0,
+            )),
+        ]
+    }
+
+    fn create_runtime(
+        &self,
+        _device: &wgpu::Device,
+        _queue: &wgpu::Queue,
+        _output_format: wgpu::TextureFormat,
+    ) -> Box<dyn EffectRuntime> {
+        Box::new(PaintWarpRuntime {
+            effect: PaintWarpEffect::new(),
+        })
+    }
+}
+
+/// Paint Warp runtime
+pub struct PaintWarpRuntime {
+    effect: PaintWarpEffect,
+}
+
+impl EffectRuntime for PaintWarpRuntime {
+    fn effect_type(&self) -> &'static str {
+        "paint_warp"
+    }
+
+    fn update(&mut self, instance: &EffectInstance, frame: &EffectFrame) {
+        for param in &instance.parameters {
+            self.effect.set_param(&param.meta.name, param.value.as_f32());
+        }
+
+        self.effect.update(frame.delta_time);
+        if let Some(ml) = frame.ml {
+            self.effect.update_from_hands(&ml.hands);
+        }
+    }
+
+    fn process(
+        &mut self,
+        encoder: &mut wgpu::CommandEncoder,
+        context: &EffectContext,
+        input: &wgpu::TextureView,
+        output: &wgpu::TextureView,
+    ) {
+        // TODO: Run the paint_warp displacement pass and warp the input
+        context.blitter.blit(encoder, context.device, input, output);
+    }
+
+    fn actions(&self) -> &'static [EffectAction] {
+        ACTIONS
+    }
+
+    fn trigger(&mut self, action: &str) {
+        if action == "clear" {
+            self.effect.clear();
+        }
+    }
+}
@@ -3,6 +3,10 @@
 //! Dissolves a person's silhouette into particles that drift away,
 //! with configurable amount, size, color, and shape.
 
+mod runtime;
+
+pub use runtime::{PersonParticlesDefinition, PersonParticlesRuntime};
+
 use bytemuck::{Pod, Zeroable};
 use rand::Rng;
 
@@ -21,6 +25,28 @@ pub enum ParticleShape {
     Diamond = 4,
 }
 
+impl ParticleShape {
+    /// All shapes, in parameter option order
+    pub const ALL: [ParticleShape; 5] = [
+        ParticleShape::Circle,
+        ParticleShape::Square,
+        ParticleShape::Star,
+        ParticleShape::Heart,
+        ParticleShape::Diamond,
+    ];
+
+    /// Display name
+    pub fn name(&self) -> &'static str {
+        match self {
+            ParticleShape::Circle => "Circle",
+            ParticleShape::Square => "Square",
+            ParticleShape::Star => "Star",
+            ParticleShape::Heart => "Heart",
+            ParticleShape::Diamond => "Diamond",
+        }
+    }
+}
+
 /// Color mode for particles
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
 #[repr(u32)]
@@ -32,6 +58,26 @@ pub enum ColorMode {
     Gradient = 3,    // Gradient based on lifetime
 }
 
+impl ColorMode {
+    /// All color modes, in parameter option order
+    pub const ALL: [ColorMode; 4] = [
+        ColorMode::Original,
+        ColorMode::Solid,
+        ColorMode::Rainbow,
+        ColorMode::Gradient,
+    ];
+
+    /// Display name
+    pub fn name(&self) -> &'static str {
+        match self {
+            ColorMode::Original => "Camera",
+            ColorMode::Solid => "Solid",
+            ColorMode::Rainbow => "Rainbow",
+            ColorMode::Gradient => "Gradient",
+        }
+    }
+}
+
 /// GPU particle data (64 bytes, aligned for WGSL)
 /// Note: vec4 in WGSL requires 16-byte alignment, so we add padding
 #[repr(C)]
@@ -0,0 +1,555 @@
+//! Person to Particles chain runtime
+//!
+//! Shows the input masked to the person (faded by `fade_person`), then
+//! draws the particles on top, coloring them from the raw camera image.
+
+use bytemuck::{Pod, Zeroable};
+
+use super::{ColorMode, Particle, ParticleParams, ParticleShape, PersonParticlesEffect, MAX_PARTICLES};
+use crate::effects::{
+    EffectAction, EffectContext, EffectDefinition, EffectFrame, EffectInstance, EffectRuntime, Parameter,
+    ParameterMeta, ParameterValue,
+};
+
+/// Mask parameters for masked passthrough shader
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+struct MaskParams {
+    threshold: f32,
+    fade_amount: f32,
+    _pad: [f32; 2],
+}
+
+/// Actions offered in the UI
+const ACTIONS: &[EffectAction] = &[
+    EffectAction {
+        name: "spawn_test",
+        label: "Test (T)",
+    },
+    EffectAction {
+        name: "clear",
+        label: "Clear",
+    },
+];
+
+/// Number of particles spawned by the "spawn_test" action
+const TEST_PARTICLE_COUNT: usize = 100;
+
+/// Person to Particles effect definition
+pub struct PersonParticlesDefinition;
+
+impl EffectDefinition for PersonParticlesDefinition {
+    fn effect_type(&self) -> &'static str {
+        "person_particles"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "Person to Particles"
+    }
+
+    fn default_parameters(&self) -> Vec<Parameter> {
+        let defaults = ParticleParams::default();
+        let shapes: Vec<&str> = ParticleShape::ALL.iter().map(|s| s.name()).collect();
+        let color_modes: Vec<&str> = ColorMode::ALL.iter().map(|m| m.name()).collect();
+
+        vec![
+            Parameter::new(ParameterMeta::enumeration("shape", "Shape", &shapes, 0)),
+            Parameter::new(ParameterMeta::enumeration("color_mode", "Color Mode", &color_modes, 0)),
+            Parameter::new(ParameterMeta::color("solid_color", "Color", defaults.solid_color)),
+            Parameter::new(ParameterMeta::color("gradient_start", "Gradient Start", defaults.gradient_start)),
+            Parameter::new(ParameterMeta::color("gradient_end", "Gradient End", defaults.gradient_end)),
+            Parameter::new(
+                ParameterMeta::float("spawn_rate", "Spawn Rate", defaults.spawn_rate, 100.0, 10000.0).with_logarithmic(),
+            ),
+            Parameter::new(ParameterMeta::float("particle_size", "Size", defaults.particle_size, 0.005, 0.1)),
+            Parameter::new(ParameterMeta::float(
+                "particle_lifetime",
+                "Lifetime",
+                defaults.particle_lifetime,
+                0.5,
+                10.0,
+            )),
+            Parameter::new(ParameterMeta::float("gravity_y", "Gravity", defaults.gravity[1], -0.5, 0.5)),
+            Parameter::new(ParameterMeta::float("wind_x", "Wind", defaults.wind[0], -0.2, 0.2)),
+            Parameter::new(ParameterMeta::float(
+                "turbulence",
+                "Turbulence",
+                defaults.turbulence_strength,
+                0.0,
+                1.0,
+            )),
+            Parameter::new(ParameterMeta::float("fade_person", "Person Fade", defaults.fade_person, 0.0, 1.0)),
+            Parameter::new(ParameterMeta::bool(
+                "spawn_inside",
+                "Spawn inside silhouette",
+                defaults.spawn_inside != 0,
+            )),
+        ]
+    }
+
+    fn create_runtime(
+        &self,
+        device: &wgpu::Device,
+        _queue: &wgpu::Queue,
+        output_format: wgpu::TextureFormat,
+    ) -> Box<dyn EffectRuntime> {
+        Box::new(PersonParticlesRuntime::new(device, output_format))
+    }
+}
+
+/// Person to Particles runtime: particle simulation plus its GPU resources
+pub struct PersonParticlesRuntime {
+    effect: PersonParticlesEffect,
+
+    // Particle rendering resources
+    particle_buffer: wgpu::Buffer,
+    particle_pipeline: wgpu::RenderPipeline,
+    particle_bind_group_layout: wgpu::BindGroupLayout,
+    particle_params_buffer: wgpu::Buffer,
+
+    // Masked passthrough pipeline (input masked by segmentation)
+    masked_pipeline: wgpu::RenderPipeline,
+    masked_bind_group_layout: wgpu::BindGroupLayout,
+    mask_params_buffer: wgpu::Buffer,
+    sampler: wgpu::Sampler,
+}
+
+impl PersonParticlesRuntime {
+    /// Create the runtime and its pipelines
+    pub fn new(device: &wgpu::Device, output_format: wgpu::TextureFormat) -> Self {
+        let alpha_blend = wgpu::BlendState {
+            color: wgpu::BlendComponent {
+                src_factor: wgpu::BlendFactor::SrcAlpha,
+                dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
+                operation: wgpu::BlendOperation::Add,
+            },
+            alpha: wgpu::BlendComponent {
+                src_factor: wgpu::BlendFactor::One,
+                dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
+                operation: wgpu::BlendOperation::Add,
+            },
+        };
+
+        // Create masked passthrough pipeline (for rendering the person masked by segmentation)
+        let masked_shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Masked Passthrough Shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("../../shaders/masked_passthrough.wgsl").into()),
+        });
+
+        let masked_bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Masked Bind Group Layout"),
+            entries: &[
+                // Input texture
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                // Sampler
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                    count: None,
+                },
+                // Mask texture (R32Float is not filterable)
+                wgpu::BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Float { filterable: false },
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                // Mask params
+                wgpu::BindGroupLayoutEntry {
+                    binding: 3,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+            ],
+        });
+
+        let masked_pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Masked Pipeline Layout"),
+            bind_group_layouts: &[&masked_bind_group_layout],
+            push_constant_ranges: &[],
+        });
+
+        let masked_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Masked Pipeline"),
+            layout: Some(&masked_pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &masked_shader,
+                entry_point: Some("vs_main"),
+                buffers: &[],
+                compilation_options: Default::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &masked_shader,
+                entry_point: Some("fs_main"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: output_format,
+                    blend: Some(alpha_blend),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: Default::default(),
+            }),
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleList,
+                ..Default::default()
+            },
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            multiview: None,
+            cache: None,
+        });
+
+        // Create mask params buffer
+        let mask_params_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Mask Params Buffer"),
+            size: std::mem::size_of::<MaskParams>() as u64,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        // Create particle buffer (storage buffer for particle data)
+        let particle_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Particle Buffer"),
+            size: (MAX_PARTICLES * std::mem::size_of::<Particle>()) as u64,
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        // Create particle params uniform buffer
+        let particle_params_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Particle Params Buffer"),
+            size: std::mem::size_of::<ParticleParams>() as u64,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        // Create particle bind group layout
+        let particle_bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Particle Bind Group Layout"),
+            entries: &[
+                // Particle storage buffer
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::VERTEX,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Storage { read_only: true },
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                // Camera texture
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                // Sampler
+                wgpu::BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                    count: None,
+                },
+                // Params uniform
+                wgpu::BindGroupLayoutEntry {
+                    binding: 3,
+                    visibility: wgpu::ShaderStages::VERTEX | wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+            ],
+        });
+
+        // Create particle pipeline
+        let particle_shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Particle Shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("../../shaders/particle.wgsl").into()),
+        });
+
+        let particle_pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Particle Pipeline Layout"),
+            bind_group_layouts: &[&particle_bind_group_layout],
+            push_constant_ranges: &[],
+        });
+
+        let particle_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Particle Pipeline"),
+            layout: Some(&particle_pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &particle_shader,
+                entry_point: Some("vs_main"),
+                buffers: &[],
+                compilation_options: Default::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &particle_shader,
+                entry_point: Some("fs_main"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: output_format,
+                    blend: Some(alpha_blend),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: Default::default(),
+            }),
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleList,
+                ..Default::default()
+            },
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            multiview: None,
+            cache: None,
+        });
+
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("Particle Sampler"),
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            ..Default::default()
+        });
+
+        Self {
+            effect: PersonParticlesEffect::new(),
+            particle_buffer,
+            particle_pipeline,
+            particle_bind_group_layout,
+            particle_params_buffer,
+            masked_pipeline,
+            masked_bind_group_layout,
+            mask_params_buffer,
+            sampler,
+        }
+    }
+
+    /// Copy the instance's parameter values into the simulation
+    fn apply_parameters(&mut self, instance: &EffectInstance) {
+        for param in &instance.parameters {
+            match (param.meta.name.as_str(), &param.value) {
+                ("shape", value) => {
+                    let shape = ParticleShape::ALL.get(value.as_index()).copied().unwrap_or_default();
+                    self.effect.set_shape(shape);
+                }
+                ("color_mode", value) => {
+                    let mode = ColorMode::ALL.get(value.as_index()).copied().unwrap_or_default();
+                    self.effect.set_color_mode(mode);
+                }
+                ("solid_color", value) => {
+                    let [r, g, b, a] = value.as_color();
+                    self.effect.set_solid_color(r, g, b, a);
+                }
+                ("gradient_start", value) => self.effect.params_mut().gradient_start = value.as_color(),
+                ("gradient_end", value) => self.effect.params_mut().gradient_end = value.as_color(),
+                ("spawn_inside", value) => self.effect.set_spawn_inside(value.as_bool()),
+                ("spawn_rate", value) => self.effect.set_spawn_rate(value.as_f32()),
+                ("particle_size", value) => self.effect.set_particle_size(value.as_f32()),
+                ("fade_person", value) => self.effect.set_fade_person(value.as_f32()),
+                (name, ParameterValue::Float(value)) => self.effect.set_param(name, *value),
+                _ => {}
+            }
+        }
+    }
+
+    /// Draw the input masked to the person, or copy it unmasked without a mask
+    fn render_person(
+        &self,
+        encoder: &mut wgpu::CommandEncoder,
+        context: &EffectContext,
+        input: &wgpu::TextureView,
+        output: &wgpu::TextureView,
+    ) {
+        let Some(mask) = context.mask else {
+            context.blitter.blit(encoder, context.device, input, output);
+            return;
+        };
+
+        let params = self.effect.params();
+        let mask_params = MaskParams {
+            threshold: params.dissolve_threshold,
+            fade_amount: params.fade_person,
+            _pad: [0.0; 2],
+        };
+        context
+            .queue
+            .write_buffer(&self.mask_params_buffer, 0, bytemuck::bytes_of(&mask_params));
+
+        let masked_bind_group = context.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Masked Bind Group"),
+            layout: &self.masked_bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::TextureView(input),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::Sampler(&self.sampler),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: wgpu::BindingResource::TextureView(mask),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 3,
+                    resource: self.mask_params_buffer.as_entire_binding(),
+                },
+            ],
+        });
+
+        // Background is hidden: clear to black, then blend the person on top
+        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("Masked Person Pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: output,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+
+        render_pass.set_pipeline(&self.masked_pipeline);
+        render_pass.set_bind_group(0, &masked_bind_group, &[]);
+        render_pass.draw(0..3, 0..1);
+    }
+
+    /// Render particles on top of the output
+    fn render_particles(&self, encoder: &mut wgpu::CommandEncoder, context: &EffectContext, output: &wgpu::TextureView) {
+        let particles = self.effect.particles();
+        let particle_count = particles.len();
+
+        if particle_count == 0 {
+            return;
+        }
+
+        // Upload particle data and params to GPU
+        context
+            .queue
+            .write_buffer(&self.particle_buffer, 0, bytemuck::cast_slice(particles));
+        context
+            .queue
+            .write_buffer(&self.particle_params_buffer, 0, bytemuck::bytes_of(self.effect.params()));
+
+        // Particles sample their color from the raw camera image
+        let particle_bind_group = context.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Particle Bind Group"),
+            layout: &self.particle_bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: self.particle_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::TextureView(context.camera),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: wgpu::BindingResource::Sampler(&self.sampler),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 3,
+                    resource: self.particle_params_buffer.as_entire_binding(),
+                },
+            ],
+        });
+
+        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("Particle Render Pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: output,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Load, // Preserve the person pass
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+
+        render_pass.set_pipeline(&self.particle_pipeline);
+        render_pass.set_bind_group(0, &particle_bind_group, &[]);
+
+        // Instanced rendering: 6 vertices per quad, one instance per particle
+        render_pass.draw(0..6, 0..particle_count as u32);
+    }
+}
+
+impl EffectRuntime for PersonParticlesRuntime {
+    fn effect_type(&self) -> &'static str {
+        "person_particles"
+    }
+
+    fn update(&mut self, instance: &EffectInstance, frame: &EffectFrame) {
+        self.apply_parameters(instance);
+        let segmentation = frame.ml.and_then(|r| r.segmentation.as_ref());
+        self.effect.update(frame.delta_time, segmentation);
+    }
+
+    fn process(
+        &mut self,
+        encoder: &mut wgpu::CommandEncoder,
+        context: &EffectContext,
+        input: &wgpu::TextureView,
+        output: &wgpu::TextureView,
+    ) {
+        self.render_person(encoder, context, input, output);
+        self.render_particles(encoder, context, output);
+    }
+
+    fn actions(&self) -> &'static [EffectAction] {
+        ACTIONS
+    }
+
+    fn trigger(&mut self, action: &str) {
+        match action {
+            "spawn_test" => {
+                self.effect.spawn_test_particles(TEST_PARTICLE_COUNT);
+                log::info!(
+                    "Spawned {} test particles (total: {})",
+                    TEST_PARTICLE_COUNT,
+                    self.effect.particle_count()
+                );
+            }
+            "clear" => self.effect.clear(),
+            _ => {}
+        }
+    }
+
+    fn status(&self) -> Option<String> {
+        Some(format!("{} particles", self.effect.particle_count()))
+    }
+}
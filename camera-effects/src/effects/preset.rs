@@ -0,0 +1,409 @@
+//! JSON effect chain presets
+//!
+//! A preset lists effects in chain order with the parameters that differ
+//! from their defaults, keyed by parameter name:
+//!
+//! ```json
+//! {
+//!   "name": "Rainbow Dissolve",
+//!   "effects": [
+//!     {
+//!       "effect_type": "person_particles",
+//!       "parameters": { "shape": "Star", "color_mode": "Rainbow", "spawn_rate": 5000.0 }
+//!     }
+//!   ]
+//! }
+//! ```
+//!
+//! Floats and ints are numbers, bools are `true`/`false`, colors are
+//! `[r, g, b]` or `[r, g, b, a]`, vec2s are `[x, y]` and enums are the
+//! option name (or its index). Parameters left out keep their defaults.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::registry::EffectRegistry;
+use super::types::{EffectChain, ParameterMeta, ParameterValue};
+
+/// File extension for preset files
+pub const PRESET_EXTENSION: &str = "json";
+
+/// A saved effect chain
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EffectPreset {
+    /// Preset name shown in the UI
+    pub name: String,
+    /// Effects in chain order
+    pub effects: Vec<PresetEffect>,
+}
+
+/// One effect in a preset
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PresetEffect {
+    /// Registered effect type
+    pub effect_type: String,
+    /// Whether the effect is bypassed
+    #[serde(default)]
+    pub bypassed: bool,
+    /// Parameter values by name
+    #[serde(default)]
+    pub parameters: BTreeMap<String, Value>,
+}
+
+impl EffectPreset {
+    /// Capture a chain; only parameters that differ from their defaults are stored
+    pub fn from_chain(name: impl Into<String>, chain: &EffectChain) -> Self {
+        let effects = chain
+            .effects
+            .iter()
+            .map(|effect| PresetEffect {
+                effect_type: effect.effect_type.clone(),
+                bypassed: effect.bypassed,
+                parameters: effect
+                    .parameters
+                    .iter()
+                    .filter(|p| p.value != p.meta.default)
+                    .map(|p| (p.meta.name.clone(), value_to_json(&p.value)))
+                    .collect(),
+            })
+            .collect();
+
+        Self {
+            name: name.into(),
+            effects,
+        }
+    }
+
+    /// Build a chain from this preset
+    ///
+    /// Fails on unknown effect types or values of the wrong type; unknown
+    /// parameter names are skipped with a warning so presets survive
+    /// parameters being removed.
+    pub fn to_chain(&self, registry: &EffectRegistry) -> Result<EffectChain, String> {
+        let mut chain = EffectChain::new();
+
+        for preset_effect in &self.effects {
+            let id = registry
+                .add_to_chain(&mut chain, &preset_effect.effect_type)
+                .ok_or_else(|| format!("Unknown effect type '{}'", preset_effect.effect_type))?;
+            let effect = chain.get_mut(id).expect("effect was just added");
+            effect.bypassed = preset_effect.bypassed;
+
+            for (name, json) in &preset_effect.parameters {
+                let Some(param) = effect.parameters.iter_mut().find(|p| &p.meta.name == name) else {
+                    log::warn!(
+                        "Preset '{}': effect '{}' has no parameter '{}', skipping",
+                        self.name,
+                        preset_effect.effect_type,
+                        name
+                    );
+                    continue;
+                };
+                param.value = value_from_json(&param.meta, json).map_err(|e| {
+                    format!("Preset '{}': {}.{}: {}", self.name, preset_effect.effect_type, name, e)
+                })?;
+            }
+        }
+
+        Ok(chain)
+    }
+
+    /// Load a preset file
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read preset {}: {}", path.display(), e))?;
+        serde_json::from_str(&text).map_err(|e| format!("Invalid preset {}: {}", path.display(), e))
+    }
+
+    /// Save as pretty-printed JSON
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let text = serde_json::to_string_pretty(self).map_err(|e| e.to_string())?;
+        std::fs::write(path, text).map_err(|e| format!("Failed to write preset {}: {}", path.display(), e))
+    }
+}
+
+/// Find the presets directory (next to `models`), falling back to `./presets`
+pub fn find_preset_dir() -> PathBuf {
+    let mut candidates = Vec::new();
+    if let Ok(exe_path) = std::env::current_exe() {
+        // Next to the executable, and up to three levels up for cargo run
+        candidates.extend(exe_path.ancestors().skip(1).take(4).map(|dir| dir.join("presets")));
+    }
+    if let Ok(cwd) = std::env::current_dir() {
+        candidates.push(cwd.join("presets"));
+        candidates.push(cwd.join("camera-effects").join("presets"));
+    }
+
+    candidates
+        .into_iter()
+        .find(|dir| dir.is_dir())
+        .unwrap_or_else(|| PathBuf::from("presets"))
+}
+
+/// List preset files in a directory, sorted by file name
+pub fn list_presets(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == PRESET_EXTENSION))
+        .collect();
+    paths.sort();
+    paths
+}
+
+fn value_to_json(value: &ParameterValue) -> Value {
+    match value {
+        ParameterValue::Float(v) => float_to_json(*v),
+        ParameterValue::Int(v) => Value::from(*v),
+        ParameterValue::Bool(v) => Value::from(*v),
+        ParameterValue::Color(c) => Value::Array(c.iter().map(|v| float_to_json(*v)).collect()),
+        ParameterValue::Vec2(v) => Value::Array(v.iter().map(|v| float_to_json(*v)).collect()),
+        ParameterValue::Enum { index, options } => match options.get(*index) {
+            Some(option) => Value::from(option.as_str()),
+            None => Value::from(*index),
+        },
+    }
+}
+
+/// Store an f32 with its shortest decimal form (0.015, not 0.014999999664723873)
+fn float_to_json(value: f32) -> Value {
+    value.to_string().parse::<f64>().map(Value::from).unwrap_or(Value::Null)
+}
+
+fn value_from_json(meta: &ParameterMeta, json: &Value) -> Result<ParameterValue, String> {
+    let expected = || format!("expected {}, got {}", meta.default.type_name(), json);
+    let floats = |len: std::ops::RangeInclusive<usize>| -> Option<Vec<f32>> {
+        let items = json.as_array()?;
+        if !len.contains(&items.len()) {
+            return None;
+        }
+        items.iter().map(|v| v.as_f64().map(|f| f as f32)).collect()
+    };
+
+    match &meta.default {
+        ParameterValue::Float(_) => json
+            .as_f64()
+            .map(|v| ParameterValue::Float(meta.clamp(v as f32)))
+            .ok_or_else(expected),
+        ParameterValue::Int(_) => json
+            .as_i64()
+            .map(|v| ParameterValue::Int(meta.clamp(v as f32) as i32))
+            .ok_or_else(expected),
+        ParameterValue::Bool(_) => json.as_bool().map(ParameterValue::Bool).ok_or_else(expected),
+        ParameterValue::Color(_) => floats(3..=4)
+            .map(|c| ParameterValue::Color([c[0], c[1], c[2], c.get(3).copied().unwrap_or(1.0)]))
+            .ok_or_else(expected),
+        ParameterValue::Vec2(_) => floats(2..=2)
+            .map(|v| ParameterValue::Vec2([v[0], v[1]]))
+            .ok_or_else(expected),
+        ParameterValue::Enum { options, .. } => {
+            let index = match json {
+                Value::String(name) => options.iter().position(|o| o.eq_ignore_ascii_case(name)),
+                Value::Number(n) => n.as_u64().map(|i| i as usize).filter(|i| *i < options.len()),
+                _ => None,
+            };
+            index
+                .map(|index| ParameterValue::Enum {
+                    index,
+                    options: options.clone(),
+                })
+                .ok_or_else(|| format!("expected one of {:?}, got {}", options, json))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::effects::{register_builtin_effects, EffectDefinition, EffectRuntime, Parameter};
+    use serde_json::json;
+
+    /// Effect with one parameter of every type
+    struct TestDefinition;
+
+    impl EffectDefinition for TestDefinition {
+        fn effect_type(&self) -> &'static str {
+            "test"
+        }
+
+        fn display_name(&self) -> &'static str {
+            "Test"
+        }
+
+        fn default_parameters(&self) -> Vec<Parameter> {
+            vec![
+                Parameter::new(ParameterMeta::float("amount", "Amount", 0.5, 0.0, 1.0)),
+                Parameter::new(ParameterMeta::int("count", "Count", 4, 1, 10)),
+                Parameter::new(ParameterMeta::bool("enabled", "Enabled", true)),
+                Parameter::new(ParameterMeta::color("tint", "Tint", [1.0; 4])),
+                Parameter::new(ParameterMeta::enumeration("shape", "Shape", &["Circle", "Star"], 0)),
+            ]
+        }
+
+        fn create_runtime(
+            &self,
+            _device: &wgpu::Device,
+            _queue: &wgpu::Queue,
+            _output_format: wgpu::TextureFormat,
+        ) -> Box<dyn EffectRuntime> {
+            unreachable!("runtimes need a GPU device")
+        }
+    }
+
+    fn registry() -> EffectRegistry {
+        let mut registry = EffectRegistry::new();
+        registry.register(TestDefinition);
+        registry
+    }
+
+    fn preset(parameters: Value) -> EffectPreset {
+        serde_json::from_value(json!({
+            "name": "Test",
+            "effects": [{ "effect_type": "test", "parameters": parameters }]
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_round_trip_stores_only_changed_values() {
+        let registry = registry();
+        let mut chain = EffectChain::new();
+        let first = registry.add_to_chain(&mut chain, "test").unwrap();
+        let second = registry.add_to_chain(&mut chain, "test").unwrap();
+        let effect = chain.get_mut(first).unwrap();
+        effect.set("amount", ParameterValue::Float(0.015));
+        effect.set("tint", ParameterValue::Color([0.2, 0.4, 0.6, 1.0]));
+        effect.set(
+            "shape",
+            ParameterValue::Enum {
+                index: 1,
+                options: vec!["Circle".into(), "Star".into()],
+            },
+        );
+        chain.get_mut(second).unwrap().bypassed = true;
+
+        let saved = EffectPreset::from_chain("Round Trip", &chain);
+        let parameters = &saved.effects[0].parameters;
+        assert_eq!(parameters.len(), 3);
+        assert_eq!(parameters["amount"], json!(0.015));
+        assert_eq!(parameters["shape"], json!("Star"));
+        assert!(saved.effects[1].parameters.is_empty());
+
+        let text = serde_json::to_string(&saved).unwrap();
+        let loaded: EffectPreset = serde_json::from_str(&text).unwrap();
+        let restored = loaded.to_chain(&registry).unwrap();
+        assert_eq!(restored.len(), 2);
+        for (a, b) in chain.effects.iter().zip(&restored.effects) {
+            assert_eq!(a.effect_type, b.effect_type);
+            assert_eq!(a.bypassed, b.bypassed);
+            let values = |e: &crate::effects::EffectInstance| {
+                e.parameters.iter().map(|p| p.value.clone()).collect::<Vec<_>>()
+            };
+            assert_eq!(values(a), values(b));
+        }
+    }
+
+    #[test]
+    fn test_value_parsing() {
+        let registry = registry();
+        let chain = preset(json!({
+            "amount": 7.0,
+            "count": 3,
+            "enabled": false,
+            "tint": [0.5, 0.25, 0.0],
+            "shape": "star"
+        }))
+        .to_chain(&registry)
+        .unwrap();
+        let effect = &chain.effects[0];
+
+        // Out-of-range numbers are clamped, RGB colors get full alpha and
+        // enum names match case-insensitively
+        assert_eq!(effect.get("amount"), Some(&ParameterValue::Float(1.0)));
+        assert_eq!(effect.get("count"), Some(&ParameterValue::Int(3)));
+        assert_eq!(effect.get("enabled"), Some(&ParameterValue::Bool(false)));
+        assert_eq!(effect.get("tint"), Some(&ParameterValue::Color([0.5, 0.25, 0.0, 1.0])));
+        assert_eq!(effect.get("shape").map(|v| v.as_index()), Some(1));
+
+        // Enums also accept an index
+        let chain = preset(json!({ "shape": 1 })).to_chain(&registry).unwrap();
+        assert_eq!(chain.effects[0].get("shape").map(|v| v.as_index()), Some(1));
+    }
+
+    #[test]
+    fn test_unknown_effect_type_fails() {
+        let preset: EffectPreset = serde_json::from_value(json!({
+            "name": "Bad",
+            "effects": [{ "effect_type": "no_such_effect" }]
+        }))
+        .unwrap();
+        let error = preset.to_chain(&registry()).unwrap_err();
+        assert!(error.contains("no_such_effect"), "{}", error);
+    }
+
+    #[test]
+    fn test_wrong_value_type_fails() {
+        let registry = registry();
+        for parameters in [
+            json!({ "amount": "loud" }),
+            json!({ "count": 2.5 }),
+            json!({ "enabled": 1 }),
+            json!({ "tint": [1.0, 0.0] }),
+            json!({ "shape": "Hexagon" }),
+            json!({ "shape": 5 }),
+        ] {
+            let error = preset(parameters.clone()).to_chain(&registry).unwrap_err();
+            assert!(error.contains("test."), "{}: {}", parameters, error);
+        }
+    }
+
+    #[test]
+    fn test_unknown_parameter_is_skipped() {
+        let chain = preset(json!({ "removed_param": 1.0, "amount": 0.25 }))
+            .to_chain(&registry())
+            .unwrap();
+        let effect = &chain.effects[0];
+        assert!(effect.get("removed_param").is_none());
+        assert_eq!(effect.get("amount"), Some(&ParameterValue::Float(0.25)));
+    }
+
+    #[test]
+    fn test_save_load_and_list() {
+        let dir = std::env::temp_dir().join(format!("camera_effects_presets_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("notes.txt"), "not a preset").unwrap();
+
+        let registry = registry();
+        let mut chain = EffectChain::new();
+        registry.add_to_chain(&mut chain, "test");
+        let path = dir.join("b.json");
+        EffectPreset::from_chain("B", &chain).save(&path).unwrap();
+        EffectPreset::from_chain("A", &chain).save(&dir.join("a.json")).unwrap();
+
+        assert_eq!(list_presets(&dir), vec![dir.join("a.json"), path.clone()]);
+        assert_eq!(EffectPreset::load(&path).unwrap().name, "B");
+        assert!(EffectPreset::load(&dir.join("notes.txt")).is_err());
+        assert!(list_presets(&dir.join("missing")).is_empty());
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_bundled_presets_load() {
+        let mut registry = EffectRegistry::new();
+        register_builtin_effects(&mut registry);
+
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("presets");
+        let presets = list_presets(&dir);
+        assert!(!presets.is_empty());
+        for path in presets {
+            let preset = EffectPreset::load(&path).unwrap();
+            preset.to_chain(&registry).unwrap();
+        }
+    }
+}
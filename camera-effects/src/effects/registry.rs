@@ -0,0 +1,169 @@
+//! Effect registry for managing available effects
+//!
+//! The registry holds all registered effect definitions, in registration
+//! order for the UI, and creates chain entries and runtimes by type.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use super::traits::{EffectDefinition, EffectRuntime};
+use super::types::{EffectChain, Parameter};
+
+/// Registry of available effects
+pub struct EffectRegistry {
+    /// Effect definitions by type identifier
+    effects: HashMap<String, Arc<dyn EffectDefinition>>,
+    /// Effect types in registration order
+    order: Vec<String>,
+}
+
+impl Default for EffectRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EffectRegistry {
+    /// Create a new empty registry
+    pub fn new() -> Self {
+        Self {
+            effects: HashMap::new(),
+            order: Vec::new(),
+        }
+    }
+
+    /// Register an effect definition (replaces one with the same type)
+    pub fn register(&mut self, definition: impl EffectDefinition + 'static) {
+        let effect_type = definition.effect_type().to_string();
+        if !self.effects.contains_key(&effect_type) {
+            self.order.push(effect_type.clone());
+        }
+        self.effects.insert(effect_type, Arc::new(definition));
+    }
+
+    /// Get an effect definition by type
+    pub fn get(&self, effect_type: &str) -> Option<Arc<dyn EffectDefinition>> {
+        self.effects.get(effect_type).cloned()
+    }
+
+    /// Check if an effect type is registered
+    pub fn contains(&self, effect_type: &str) -> bool {
+        self.effects.contains_key(effect_type)
+    }
+
+    /// All effect definitions in registration order
+    pub fn effects(&self) -> impl Iterator<Item = &Arc<dyn EffectDefinition>> {
+        self.order.iter().filter_map(|t| self.effects.get(t))
+    }
+
+    /// Get the number of registered effects
+    pub fn len(&self) -> usize {
+        self.effects.len()
+    }
+
+    /// Check if the registry is empty
+    pub fn is_empty(&self) -> bool {
+        self.effects.is_empty()
+    }
+
+    /// Get default parameters for an effect type
+    pub fn default_parameters(&self, effect_type: &str) -> Option<Vec<Parameter>> {
+        self.get(effect_type).map(|def| def.default_parameters())
+    }
+
+    /// Get the display name for an effect type
+    pub fn display_name(&self, effect_type: &str) -> Option<&'static str> {
+        self.get(effect_type).map(|def| def.display_name())
+    }
+
+    /// Append an effect with default parameters to a chain
+    pub fn add_to_chain(&self, chain: &mut EffectChain, effect_type: &str) -> Option<u32> {
+        let def = self.get(effect_type)?;
+        Some(chain.add(effect_type, def.display_name(), def.default_parameters()))
+    }
+
+    /// Create a runtime for an effect type
+    pub fn create_runtime(
+        &self,
+        effect_type: &str,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        output_format: wgpu::TextureFormat,
+    ) -> Option<Box<dyn EffectRuntime>> {
+        self.get(effect_type)
+            .map(|def| def.create_runtime(device, queue, output_format))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::effects::ParameterMeta;
+
+    struct TestDefinition {
+        effect_type: &'static str,
+        display_name: &'static str,
+    }
+
+    impl EffectDefinition for TestDefinition {
+        fn effect_type(&self) -> &'static str {
+            self.effect_type
+        }
+
+        fn display_name(&self) -> &'static str {
+            self.display_name
+        }
+
+        fn default_parameters(&self) -> Vec<Parameter> {
+            vec![Parameter::new(ParameterMeta::float("amount", "Amount", 0.5, 0.0, 1.0))]
+        }
+
+        fn create_runtime(
+            &self,
+            _device: &wgpu::Device,
+            _queue: &wgpu::Queue,
+            _output_format: wgpu::TextureFormat,
+        ) -> Box<dyn EffectRuntime> {
+            unreachable!("runtimes need a GPU device")
+        }
+    }
+
+    fn definition(effect_type: &'static str, display_name: &'static str) -> TestDefinition {
+        TestDefinition {
+            effect_type,
+            display_name,
+        }
+    }
+
+    #[test]
+    fn test_register_keeps_order_and_replaces() {
+        let mut registry = EffectRegistry::new();
+        assert!(registry.is_empty());
+        registry.register(definition("b", "B"));
+        registry.register(definition("a", "A"));
+        registry.register(definition("b", "B2"));
+
+        assert_eq!(registry.len(), 2);
+        assert!(registry.contains("a"));
+        assert!(!registry.contains("c"));
+        let types: Vec<_> = registry.effects().map(|d| d.effect_type()).collect();
+        assert_eq!(types, vec!["b", "a"]);
+        assert_eq!(registry.display_name("b"), Some("B2"));
+    }
+
+    #[test]
+    fn test_add_to_chain() {
+        let mut registry = EffectRegistry::new();
+        registry.register(definition("a", "Effect A"));
+        let mut chain = EffectChain::new();
+
+        let id = registry.add_to_chain(&mut chain, "a").unwrap();
+        let effect = chain.get(id).unwrap();
+        assert_eq!(effect.name, "Effect A");
+        assert_eq!(effect.get("amount").map(|v| v.as_f32()), Some(0.5));
+
+        assert!(registry.add_to_chain(&mut chain, "unknown").is_none());
+        assert_eq!(chain.len(), 1);
+        assert!(registry.default_parameters("unknown").is_none());
+    }
+}
@@ -0,0 +1,283 @@
+//! Effect chain processing
+//!
+//! `EffectChainRuntime` keeps one `EffectRuntime` per chain entry and runs
+//! them in order, ping-ponging between two textures so each effect reads
+//! the previous effect's output.
+
+use std::collections::HashMap;
+
+use super::registry::EffectRegistry;
+use super::traits::{EffectContext, EffectFrame, EffectRuntime};
+use super::types::EffectChain;
+
+/// Texture format effects render into (matches the output texture)
+pub const CHAIN_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Bgra8UnormSrgb;
+
+/// Fullscreen texture copy into `CHAIN_FORMAT` targets
+pub struct Blitter {
+    pipeline: wgpu::RenderPipeline,
+    bind_group_layout: wgpu::BindGroupLayout,
+    sampler: wgpu::Sampler,
+}
+
+impl Blitter {
+    /// Create the copy pipeline
+    pub fn new(device: &wgpu::Device) -> Self {
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Blit Shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("../shaders/passthrough.wgsl").into()),
+        });
+
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Blit Bind Group Layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                    count: None,
+                },
+            ],
+        });
+
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Blit Pipeline Layout"),
+            bind_group_layouts: &[&bind_group_layout],
+            push_constant_ranges: &[],
+        });
+
+        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Blit Pipeline"),
+            layout: Some(&pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: Some("vs_main"),
+                buffers: &[],
+                compilation_options: Default::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs_main"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: CHAIN_FORMAT,
+                    blend: Some(wgpu::BlendState::REPLACE),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: Default::default(),
+            }),
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleList,
+                ..Default::default()
+            },
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            multiview: None,
+            cache: None,
+        });
+
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("Blit Sampler"),
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            ..Default::default()
+        });
+
+        Self {
+            pipeline,
+            bind_group_layout,
+            sampler,
+        }
+    }
+
+    /// Copy `input` over `output`, scaling to fit
+    pub fn blit(
+        &self,
+        encoder: &mut wgpu::CommandEncoder,
+        device: &wgpu::Device,
+        input: &wgpu::TextureView,
+        output: &wgpu::TextureView,
+    ) {
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Blit Bind Group"),
+            layout: &self.bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::TextureView(input),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::Sampler(&self.sampler),
+                },
+            ],
+        });
+
+        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("Blit Pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: output,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+
+        render_pass.set_pipeline(&self.pipeline);
+        render_pass.set_bind_group(0, &bind_group, &[]);
+        render_pass.draw(0..3, 0..1);
+    }
+}
+
+/// Runtimes and intermediate textures for an `EffectChain`
+pub struct EffectChainRuntime {
+    /// Runtime per chain entry, by instance ID
+    runtimes: HashMap<u32, Box<dyn EffectRuntime>>,
+    /// Ping-pong textures (kept alive for their views)
+    _textures: [wgpu::Texture; 2],
+    views: [wgpu::TextureView; 2],
+}
+
+impl EffectChainRuntime {
+    /// Create a chain runtime processing frames of the given size
+    pub fn new(device: &wgpu::Device, width: u32, height: u32) -> Self {
+        let create = |label| {
+            device.create_texture(&wgpu::TextureDescriptor {
+                label: Some(label),
+                size: wgpu::Extent3d {
+                    width,
+                    height,
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format: CHAIN_FORMAT,
+                usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
+                view_formats: &[],
+            })
+        };
+        let textures = [create("Effect Chain Texture A"), create("Effect Chain Texture B")];
+        let views = [
+            textures[0].create_view(&wgpu::TextureViewDescriptor::default()),
+            textures[1].create_view(&wgpu::TextureViewDescriptor::default()),
+        ];
+
+        Self {
+            runtimes: HashMap::new(),
+            _textures: textures,
+            views,
+        }
+    }
+
+    /// Create runtimes for new chain entries and drop those of removed ones
+    pub fn sync(
+        &mut self,
+        chain: &EffectChain,
+        registry: &EffectRegistry,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+    ) {
+        self.runtimes.retain(|id, _| chain.get(*id).is_some());
+
+        for effect in &chain.effects {
+            if self.runtimes.contains_key(&effect.id) {
+                continue;
+            }
+            match registry.create_runtime(&effect.effect_type, device, queue, CHAIN_FORMAT) {
+                Some(runtime) => {
+                    log::info!("Created runtime for effect '{}' ({})", effect.name, effect.effect_type);
+                    self.runtimes.insert(effect.id, runtime);
+                }
+                None => {
+                    log::warn!("Unknown effect type '{}', skipping", effect.effect_type);
+                }
+            }
+        }
+    }
+
+    /// Update every active effect
+    pub fn update(&mut self, chain: &EffectChain, frame: &EffectFrame) {
+        for effect in chain.active() {
+            if let Some(runtime) = self.runtimes.get_mut(&effect.id) {
+                runtime.update(effect, frame);
+            }
+        }
+    }
+
+    /// Run the active effects over `source` (black if `None`) and write the result to `output`
+    pub fn process(
+        &mut self,
+        encoder: &mut wgpu::CommandEncoder,
+        chain: &EffectChain,
+        context: &EffectContext,
+        source: Option<&wgpu::TextureView>,
+        output: &wgpu::TextureView,
+    ) {
+        match source {
+            Some(source) => context.blitter.blit(encoder, context.device, source, &self.views[0]),
+            None => {
+                encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                    label: Some("Effect Chain Clear Pass"),
+                    color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                        view: &self.views[0],
+                        resolve_target: None,
+                        ops: wgpu::Operations {
+                            load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                            store: wgpu::StoreOp::Store,
+                        },
+                    })],
+                    depth_stencil_attachment: None,
+                    timestamp_writes: None,
+                    occlusion_query_set: None,
+                });
+            }
+        }
+
+        let mut current = 0;
+        for effect in chain.active() {
+            let Some(runtime) = self.runtimes.get_mut(&effect.id) else {
+                continue;
+            };
+            runtime.process(encoder, context, &self.views[current], &self.views[1 - current]);
+            current = 1 - current;
+        }
+
+        context.blitter.blit(encoder, context.device, &self.views[current], output);
+    }
+
+    /// Get the runtime of a chain entry
+    pub fn runtime(&self, id: u32) -> Option<&dyn EffectRuntime> {
+        self.runtimes.get(&id).map(|r| r.as_ref())
+    }
+
+    /// Run an action on one chain entry
+    pub fn trigger(&mut self, id: u32, action: &str) {
+        if let Some(runtime) = self.runtimes.get_mut(&id) {
+            runtime.trigger(action);
+        }
+    }
+
+    /// Run an action on every entry that offers it
+    pub fn trigger_all(&mut self, action: &str) {
+        for runtime in self.runtimes.values_mut() {
+            if runtime.actions().iter().any(|a| a.name == action) {
+                runtime.trigger(action);
+            }
+        }
+    }
+}
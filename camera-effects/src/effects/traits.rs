@@ -0,0 +1,100 @@
+//! Effect traits
+//!
+//! An `EffectDefinition` describes an effect type (its parameters) and
+//! creates `EffectRuntime`s, which own the GPU resources and per-instance
+//! state (particles, touch trails, ...) for one effect in the chain.
+
+use super::runtime::Blitter;
+use super::types::{EffectInstance, Parameter};
+use crate::ml::MlResult;
+
+/// Trait for effect definitions (factory pattern)
+///
+/// Each effect type implements this trait and is registered with the
+/// `EffectRegistry` in `register_builtin_effects`.
+pub trait EffectDefinition: Send + Sync {
+    /// Unique identifier for this effect type (e.g., "person_particles")
+    fn effect_type(&self) -> &'static str;
+
+    /// Human-readable display name (e.g., "Person to Particles")
+    fn display_name(&self) -> &'static str;
+
+    /// Get the default parameters for this effect
+    fn default_parameters(&self) -> Vec<Parameter>;
+
+    /// Create a runtime instance rendering into `output_format` textures
+    fn create_runtime(
+        &self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        output_format: wgpu::TextureFormat,
+    ) -> Box<dyn EffectRuntime>;
+}
+
+/// Per-frame inputs for updating effects
+pub struct EffectFrame<'a> {
+    /// Seconds since the previous frame
+    pub delta_time: f32,
+    /// Latest ML result (segmentation, hands), if ML is running
+    pub ml: Option<&'a MlResult>,
+}
+
+/// GPU resources shared with effects while rendering a frame
+pub struct EffectContext<'a> {
+    pub device: &'a wgpu::Device,
+    pub queue: &'a wgpu::Queue,
+    /// Raw camera image (white placeholder when no camera is connected)
+    pub camera: &'a wgpu::TextureView,
+    /// Segmentation mask (R32Float) for this frame, if there is one
+    pub mask: Option<&'a wgpu::TextureView>,
+    /// Linear sampler
+    pub sampler: &'a wgpu::Sampler,
+    /// Fullscreen copy, for effects that leave the image unchanged
+    pub blitter: &'a Blitter,
+}
+
+/// A one-shot action an effect offers in the UI (e.g., "Clear")
+#[derive(Debug, Clone, Copy)]
+pub struct EffectAction {
+    /// Internal name passed to `EffectRuntime::trigger`
+    pub name: &'static str,
+    /// Button label
+    pub label: &'static str,
+}
+
+/// Runtime trait for effects in the chain
+///
+/// Each frame, `update` is called with the instance's current parameters,
+/// then `process` reads the previous effect's output from `input` and
+/// writes this effect's result to `output`.
+pub trait EffectRuntime {
+    /// Get the effect type identifier
+    fn effect_type(&self) -> &'static str;
+
+    /// Apply parameters and advance the simulation by one frame
+    fn update(&mut self, instance: &EffectInstance, frame: &EffectFrame);
+
+    /// Render the effect
+    fn process(
+        &mut self,
+        encoder: &mut wgpu::CommandEncoder,
+        context: &EffectContext,
+        input: &wgpu::TextureView,
+        output: &wgpu::TextureView,
+    );
+
+    /// Actions shown as buttons under the effect's parameters
+    fn actions(&self) -> &'static [EffectAction] {
+        &[]
+    }
+
+    /// Run an action by name; unknown actions are ignored
+    fn trigger(&mut self, _action: &str) {
+        // Default: no-op
+    }
+
+    /// Short status line for the UI (e.g., particle count)
+    fn status(&self) -> Option<String> {
+        None
+    }
+}
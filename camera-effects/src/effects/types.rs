@@ -0,0 +1,400 @@
+//! Core effect data types
+//!
+//! Parameters and effect chains are plain data, separate from the GPU
+//! resources in `EffectRuntime`s, so they can be edited in the UI and
+//! saved as presets.
+
+/// Parameter value types supported by effects
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParameterValue {
+    /// Floating point value
+    Float(f32),
+    /// Integer value
+    Int(i32),
+    /// Boolean value
+    Bool(bool),
+    /// RGBA color (0.0-1.0 per channel)
+    Color([f32; 4]),
+    /// 2D vector
+    Vec2([f32; 2]),
+    /// Enumeration (index into options list)
+    Enum { index: usize, options: Vec<String> },
+}
+
+impl ParameterValue {
+    /// Get the value as f32 (returns 0.0 for non-numeric types)
+    pub fn as_f32(&self) -> f32 {
+        match self {
+            ParameterValue::Float(v) => *v,
+            ParameterValue::Int(v) => *v as f32,
+            ParameterValue::Bool(true) => 1.0,
+            ParameterValue::Enum { index, .. } => *index as f32,
+            _ => 0.0,
+        }
+    }
+
+    /// Get the value as bool (non-zero numbers are true)
+    pub fn as_bool(&self) -> bool {
+        match self {
+            ParameterValue::Bool(v) => *v,
+            other => other.as_f32() != 0.0,
+        }
+    }
+
+    /// Get the selected index of an enum value (0 for other types)
+    pub fn as_index(&self) -> usize {
+        match self {
+            ParameterValue::Enum { index, .. } => *index,
+            other => other.as_f32().max(0.0) as usize,
+        }
+    }
+
+    /// Get the value as a color (white for non-color types)
+    pub fn as_color(&self) -> [f32; 4] {
+        match self {
+            ParameterValue::Color(c) => *c,
+            _ => [1.0; 4],
+        }
+    }
+
+    /// Name of the value's type, for error messages
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            ParameterValue::Float(_) => "float",
+            ParameterValue::Int(_) => "int",
+            ParameterValue::Bool(_) => "bool",
+            ParameterValue::Color(_) => "color",
+            ParameterValue::Vec2(_) => "vec2",
+            ParameterValue::Enum { .. } => "enum",
+        }
+    }
+}
+
+/// Parameter metadata: name, UI label, default and range
+#[derive(Debug, Clone)]
+pub struct ParameterMeta {
+    /// Internal name (used as key in presets)
+    pub name: String,
+    /// Display label in UI
+    pub label: String,
+    /// Default value
+    pub default: ParameterValue,
+    /// Minimum value (for numeric types)
+    pub min: Option<f32>,
+    /// Maximum value (for numeric types)
+    pub max: Option<f32>,
+    /// Show the slider on a logarithmic scale
+    pub logarithmic: bool,
+}
+
+impl ParameterMeta {
+    /// Create a float parameter
+    pub fn float(name: impl Into<String>, label: impl Into<String>, default: f32, min: f32, max: f32) -> Self {
+        Self {
+            name: name.into(),
+            label: label.into(),
+            default: ParameterValue::Float(default),
+            min: Some(min),
+            max: Some(max),
+            logarithmic: false,
+        }
+    }
+
+    /// Create an integer parameter
+    pub fn int(name: impl Into<String>, label: impl Into<String>, default: i32, min: i32, max: i32) -> Self {
+        Self {
+            name: name.into(),
+            label: label.into(),
+            default: ParameterValue::Int(default),
+            min: Some(min as f32),
+            max: Some(max as f32),
+            logarithmic: false,
+        }
+    }
+
+    /// Create a boolean parameter
+    pub fn bool(name: impl Into<String>, label: impl Into<String>, default: bool) -> Self {
+        Self {
+            name: name.into(),
+            label: label.into(),
+            default: ParameterValue::Bool(default),
+            min: None,
+            max: None,
+            logarithmic: false,
+        }
+    }
+
+    /// Create an RGBA color parameter
+    pub fn color(name: impl Into<String>, label: impl Into<String>, default: [f32; 4]) -> Self {
+        Self {
+            name: name.into(),
+            label: label.into(),
+            default: ParameterValue::Color(default),
+            min: None,
+            max: None,
+            logarithmic: false,
+        }
+    }
+
+    /// Create an enumeration parameter
+    pub fn enumeration(name: impl Into<String>, label: impl Into<String>, options: &[&str], default_index: usize) -> Self {
+        Self {
+            name: name.into(),
+            label: label.into(),
+            default: ParameterValue::Enum {
+                index: default_index,
+                options: options.iter().map(|o| o.to_string()).collect(),
+            },
+            min: None,
+            max: None,
+            logarithmic: false,
+        }
+    }
+
+    /// Show the slider on a logarithmic scale
+    pub fn with_logarithmic(mut self) -> Self {
+        self.logarithmic = true;
+        self
+    }
+
+    /// Clamp a numeric value into this parameter's range
+    pub fn clamp(&self, value: f32) -> f32 {
+        let value = self.min.map_or(value, |min| value.max(min));
+        self.max.map_or(value, |max| value.min(max))
+    }
+}
+
+/// A parameter with its current value
+#[derive(Debug, Clone)]
+pub struct Parameter {
+    /// Parameter metadata
+    pub meta: ParameterMeta,
+    /// Current value
+    pub value: ParameterValue,
+}
+
+impl Parameter {
+    /// Create a parameter at its default value
+    pub fn new(meta: ParameterMeta) -> Self {
+        Self {
+            value: meta.default.clone(),
+            meta,
+        }
+    }
+
+    /// Reset to the default value
+    pub fn reset(&mut self) {
+        self.value = self.meta.default.clone();
+    }
+}
+
+/// One effect in a chain, with its parameter values
+#[derive(Debug, Clone)]
+pub struct EffectInstance {
+    /// Unique instance ID within the chain
+    pub id: u32,
+    /// Effect type identifier (e.g., "person_particles")
+    pub effect_type: String,
+    /// Human-readable name
+    pub name: String,
+    /// Parameters with current values
+    pub parameters: Vec<Parameter>,
+    /// Whether the effect is bypassed
+    pub bypassed: bool,
+}
+
+impl EffectInstance {
+    /// Get a parameter value by name
+    pub fn get(&self, name: &str) -> Option<&ParameterValue> {
+        self.parameters.iter().find(|p| p.meta.name == name).map(|p| &p.value)
+    }
+
+    /// Set a parameter value by name, returning false if there is no such parameter
+    pub fn set(&mut self, name: &str, value: ParameterValue) -> bool {
+        match self.parameters.iter_mut().find(|p| p.meta.name == name) {
+            Some(param) => {
+                param.value = value;
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+/// Ordered chain of effects; each effect processes the output of the previous one
+#[derive(Debug, Clone)]
+pub struct EffectChain {
+    /// Effects in processing order
+    pub effects: Vec<EffectInstance>,
+    /// Next instance ID
+    next_effect_id: u32,
+}
+
+impl Default for EffectChain {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EffectChain {
+    /// Create an empty chain
+    pub fn new() -> Self {
+        Self {
+            effects: Vec::new(),
+            next_effect_id: 1,
+        }
+    }
+
+    /// Append an effect, returning its instance ID
+    pub fn add(&mut self, effect_type: impl Into<String>, name: impl Into<String>, parameters: Vec<Parameter>) -> u32 {
+        let id = self.next_effect_id;
+        self.next_effect_id += 1;
+        self.effects.push(EffectInstance {
+            id,
+            effect_type: effect_type.into(),
+            name: name.into(),
+            parameters,
+            bypassed: false,
+        });
+        id
+    }
+
+    /// Remove an effect by instance ID
+    pub fn remove(&mut self, id: u32) -> Option<EffectInstance> {
+        let index = self.effects.iter().position(|e| e.id == id)?;
+        Some(self.effects.remove(index))
+    }
+
+    /// Move an effect to a new position in the chain
+    pub fn move_to(&mut self, id: u32, new_index: usize) {
+        if let Some(index) = self.effects.iter().position(|e| e.id == id) {
+            let effect = self.effects.remove(index);
+            let new_index = new_index.min(self.effects.len());
+            self.effects.insert(new_index, effect);
+        }
+    }
+
+    /// Get an effect by instance ID
+    pub fn get(&self, id: u32) -> Option<&EffectInstance> {
+        self.effects.iter().find(|e| e.id == id)
+    }
+
+    /// Get a mutable effect by instance ID
+    pub fn get_mut(&mut self, id: u32) -> Option<&mut EffectInstance> {
+        self.effects.iter_mut().find(|e| e.id == id)
+    }
+
+    /// Effects that are not bypassed, in processing order
+    pub fn active(&self) -> impl Iterator<Item = &EffectInstance> {
+        self.effects.iter().filter(|e| !e.bypassed)
+    }
+
+    /// Number of effects in the chain
+    pub fn len(&self) -> usize {
+        self.effects.len()
+    }
+
+    /// Check if the chain is empty
+    pub fn is_empty(&self) -> bool {
+        self.effects.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mode_param() -> Parameter {
+        Parameter::new(ParameterMeta::enumeration("mode", "Mode", &["A", "B", "C"], 1))
+    }
+
+    #[test]
+    fn test_parameter_value_conversions() {
+        assert_eq!(ParameterValue::Float(0.5).as_f32(), 0.5);
+        assert_eq!(ParameterValue::Int(3).as_f32(), 3.0);
+        assert_eq!(ParameterValue::Bool(true).as_f32(), 1.0);
+        assert_eq!(ParameterValue::Bool(false).as_f32(), 0.0);
+        assert_eq!(ParameterValue::Color([0.1; 4]).as_f32(), 0.0);
+
+        assert!(ParameterValue::Float(0.1).as_bool());
+        assert!(!ParameterValue::Int(0).as_bool());
+        assert_eq!(mode_param().value.as_index(), 1);
+        assert_eq!(ParameterValue::Float(-2.0).as_index(), 0);
+        assert_eq!(ParameterValue::Float(0.5).as_color(), [1.0; 4]);
+        assert_eq!(mode_param().value.type_name(), "enum");
+    }
+
+    #[test]
+    fn test_parameter_meta_clamp() {
+        let float = ParameterMeta::float("size", "Size", 0.5, 0.1, 2.0);
+        assert_eq!(float.clamp(-1.0), 0.1);
+        assert_eq!(float.clamp(5.0), 2.0);
+        assert_eq!(float.clamp(1.0), 1.0);
+
+        // Types without a range are left alone
+        let flag = ParameterMeta::bool("on", "On", true);
+        assert_eq!(flag.clamp(42.0), 42.0);
+    }
+
+    #[test]
+    fn test_parameter_reset_and_instance_access() {
+        let mut chain = EffectChain::new();
+        let id = chain.add("test", "Test", vec![mode_param()]);
+        let effect = chain.get_mut(id).unwrap();
+
+        let b = effect.get("mode").unwrap().clone();
+        let c = ParameterValue::Enum {
+            index: 2,
+            options: vec!["A".into(), "B".into(), "C".into()],
+        };
+        assert!(effect.set("mode", c.clone()));
+        assert_eq!(effect.get("mode"), Some(&c));
+        assert!(!effect.set("missing", ParameterValue::Bool(true)));
+        assert!(effect.get("missing").is_none());
+
+        effect.parameters[0].reset();
+        assert_eq!(effect.get("mode"), Some(&b));
+    }
+
+    #[test]
+    fn test_chain_add_remove() {
+        let mut chain = EffectChain::new();
+        assert!(chain.is_empty());
+        let a = chain.add("a", "A", Vec::new());
+        let b = chain.add("b", "B", Vec::new());
+        assert_ne!(a, b);
+        assert_eq!(chain.len(), 2);
+
+        assert_eq!(chain.remove(a).map(|e| e.effect_type), Some("a".to_string()));
+        assert!(chain.remove(a).is_none());
+        assert!(chain.get(a).is_none());
+
+        // IDs are never reused after removal
+        let c = chain.add("c", "C", Vec::new());
+        assert!(c != a && c != b);
+        assert_eq!(chain.len(), 2);
+    }
+
+    #[test]
+    fn test_chain_move_to_and_active() {
+        let mut chain = EffectChain::new();
+        let a = chain.add("a", "A", Vec::new());
+        let b = chain.add("b", "B", Vec::new());
+        let c = chain.add("c", "C", Vec::new());
+        let order = |chain: &EffectChain| chain.effects.iter().map(|e| e.id).collect::<Vec<_>>();
+
+        chain.move_to(c, 0);
+        assert_eq!(order(&chain), vec![c, a, b]);
+        chain.move_to(c, 1);
+        assert_eq!(order(&chain), vec![a, c, b]);
+        // Indices past the end move to the back; unknown IDs are ignored
+        chain.move_to(a, 10);
+        assert_eq!(order(&chain), vec![c, b, a]);
+        chain.move_to(99, 0);
+        assert_eq!(order(&chain), vec![c, b, a]);
+
+        chain.get_mut(b).unwrap().bypassed = true;
+        let active: Vec<u32> = chain.active().map(|e| e.id).collect();
+        assert_eq!(active, vec![c, a]);
+    }
+}
@@ -131,16 +131,16 @@ impl ApplicationHandler for CameraEffectsApp {
                     KeyCode::Space => {
                         app.toggle_effect();
                     }
-                    // 1-3 to select effects
-                    KeyCode::Digit1 => app.select_effect(0),
-                    KeyCode::Digit2 => app.select_effect(1),
-                    KeyCode::Digit3 => app.select_effect(2),
+                    // 1-3 to bypass effects in the chain
+                    KeyCode::Digit1 => app.toggle_chain_effect(0),
+                    KeyCode::Digit2 => app.toggle_chain_effect(1),
+                    KeyCode::Digit3 => app.toggle_chain_effect(2),
                     // C to connect to camera 0
                     KeyCode::KeyC => app.connect_camera(0),
                     // D to disconnect camera
                     KeyCode::KeyD => app.disconnect_camera(),
                     // T to spawn test particles
-                    KeyCode::KeyT => app.spawn_test_particles(100),
+                    KeyCode::KeyT => app.trigger_action("spawn_test"),
                     // M to initialize ML
                     KeyCode::KeyM => app.init_ml(),
                     _ => {}
@@ -0,0 +1,237 @@
+//! Effect chain and preset panels
+//!
+//! Controls are built from each effect's parameter metadata, so new
+//! effects get a UI without any code here.
+
+use std::path::{Path, PathBuf};
+
+use crate::effects::{EffectAction, EffectChain, EffectRegistry, Parameter, ParameterValue};
+
+/// What the chain panel shows for an effect's runtime
+pub struct EffectStatus {
+    /// Chain entry the status belongs to
+    pub id: u32,
+    /// Status line (e.g., particle count)
+    pub status: Option<String>,
+    /// Action buttons
+    pub actions: &'static [EffectAction],
+}
+
+/// An action button clicked in the chain panel
+pub struct TriggeredAction {
+    /// Chain entry
+    pub id: u32,
+    /// Action name
+    pub action: &'static str,
+}
+
+/// Preset panel requests
+pub enum PresetCommand {
+    /// Load the preset file at this path
+    Load(PathBuf),
+    /// Save the current chain under this name
+    Save(String),
+    /// Re-scan the presets directory
+    Refresh,
+}
+
+/// Show the effect chain: add, remove, reorder, bypass and edit effects.
+///
+/// Returns true if the chain changed, plus any action buttons clicked.
+pub fn effect_chain_panel(
+    ui: &mut egui::Ui,
+    chain: &mut EffectChain,
+    registry: &EffectRegistry,
+    statuses: &[EffectStatus],
+) -> (bool, Vec<TriggeredAction>) {
+    let mut changed = false;
+    let mut triggered = Vec::new();
+    let mut remove = None;
+    let mut move_to = None;
+    let count = chain.len();
+
+    for (index, effect) in chain.effects.iter_mut().enumerate() {
+        let status = statuses.iter().find(|s| s.id == effect.id);
+
+        ui.push_id(effect.id, |ui| {
+            ui.horizontal(|ui| {
+                let mut enabled = !effect.bypassed;
+                if ui.checkbox(&mut enabled, "").changed() {
+                    effect.bypassed = !enabled;
+                    changed = true;
+                }
+                match status.and_then(|s| s.status.as_ref()) {
+                    Some(status) => ui.label(format!("{}. {} ({})", index + 1, effect.name, status)),
+                    None => ui.label(format!("{}. {}", index + 1, effect.name)),
+                };
+                if ui.add_enabled(index > 0, egui::Button::new("^").small()).clicked() {
+                    move_to = Some((effect.id, index - 1));
+                }
+                if ui.add_enabled(index + 1 < count, egui::Button::new("v").small()).clicked() {
+                    move_to = Some((effect.id, index + 1));
+                }
+                if ui.small_button("x").clicked() {
+                    remove = Some(effect.id);
+                }
+            });
+
+            egui::CollapsingHeader::new("Settings").show(ui, |ui| {
+                if let Some(status) = status {
+                    if !status.actions.is_empty() {
+                        ui.horizontal(|ui| {
+                            for action in status.actions {
+                                if ui.button(action.label).clicked() {
+                                    triggered.push(TriggeredAction {
+                                        id: effect.id,
+                                        action: action.name,
+                                    });
+                                }
+                            }
+                        });
+                    }
+                }
+
+                for param in &mut effect.parameters {
+                    changed |= parameter_editor(ui, param);
+                }
+
+                if ui.small_button("Reset").clicked() {
+                    effect.parameters.iter_mut().for_each(Parameter::reset);
+                    changed = true;
+                }
+            });
+        });
+        ui.add_space(4.0);
+    }
+
+    if let Some(id) = remove {
+        chain.remove(id);
+        changed = true;
+    }
+    if let Some((id, index)) = move_to {
+        chain.move_to(id, index);
+        changed = true;
+    }
+
+    ui.menu_button("Add Effect", |ui| {
+        for definition in registry.effects() {
+            if ui.button(definition.display_name()).clicked() {
+                registry.add_to_chain(chain, definition.effect_type());
+                changed = true;
+                ui.close_menu();
+            }
+        }
+    });
+
+    (changed, triggered)
+}
+
+/// Show the widget for one parameter, returning true if it changed
+pub fn parameter_editor(ui: &mut egui::Ui, param: &mut Parameter) -> bool {
+    let meta = &param.meta;
+    match &mut param.value {
+        ParameterValue::Float(value) => {
+            let min = meta.min.unwrap_or(0.0);
+            let max = meta.max.unwrap_or(1.0);
+            ui.add(
+                egui::Slider::new(value, min..=max)
+                    .text(meta.label.as_str())
+                    .logarithmic(meta.logarithmic),
+            )
+            .changed()
+        }
+        ParameterValue::Int(value) => {
+            let min = meta.min.unwrap_or(0.0) as i32;
+            let max = meta.max.unwrap_or(100.0) as i32;
+            ui.add(egui::Slider::new(value, min..=max).text(meta.label.as_str())).changed()
+        }
+        ParameterValue::Bool(value) => ui.checkbox(value, meta.label.as_str()).changed(),
+        ParameterValue::Color(color) => {
+            ui.horizontal(|ui| {
+                ui.label(format!("{}:", meta.label));
+                color_editor(ui, color)
+            })
+            .inner
+        }
+        ParameterValue::Vec2(value) => {
+            ui.horizontal(|ui| {
+                ui.label(format!("{}:", meta.label));
+                let x = ui.add(egui::DragValue::new(&mut value[0]).speed(0.01)).changed();
+                let y = ui.add(egui::DragValue::new(&mut value[1]).speed(0.01)).changed();
+                x || y
+            })
+            .inner
+        }
+        ParameterValue::Enum { index, options } => {
+            ui.label(format!("{}:", meta.label));
+            ui.horizontal_wrapped(|ui| {
+                let mut changed = false;
+                for (i, option) in options.iter().enumerate() {
+                    if ui.selectable_label(*index == i, option.as_str()).clicked() && *index != i {
+                        *index = i;
+                        changed = true;
+                    }
+                }
+                changed
+            })
+            .inner
+        }
+    }
+}
+
+/// Color picker over 0-1 RGBA, returning true if it changed
+fn color_editor(ui: &mut egui::Ui, color: &mut [f32; 4]) -> bool {
+    let mut color32 = egui::Color32::from_rgba_unmultiplied(
+        (color[0] * 255.0) as u8,
+        (color[1] * 255.0) as u8,
+        (color[2] * 255.0) as u8,
+        (color[3] * 255.0) as u8,
+    );
+    if ui.color_edit_button_srgba(&mut color32).changed() {
+        *color = [
+            color32.r() as f32 / 255.0,
+            color32.g() as f32 / 255.0,
+            color32.b() as f32 / 255.0,
+            color32.a() as f32 / 255.0,
+        ];
+        true
+    } else {
+        false
+    }
+}
+
+/// Show the preset list and a save field
+pub fn preset_panel(ui: &mut egui::Ui, presets: &[PathBuf], name: &mut String) -> Option<PresetCommand> {
+    let mut command = None;
+
+    if presets.is_empty() {
+        ui.label("No presets saved");
+    }
+    for path in presets {
+        let label = path.file_stem().map(|s| s.to_string_lossy().into_owned()).unwrap_or_default();
+        if ui.button(label).clicked() {
+            command = Some(PresetCommand::Load(path.clone()));
+        }
+    }
+
+    ui.horizontal(|ui| {
+        ui.add(egui::TextEdit::singleline(name).desired_width(120.0).hint_text("Preset name"));
+        if ui.add_enabled(!name.trim().is_empty(), egui::Button::new("Save")).clicked() {
+            command = Some(PresetCommand::Save(name.trim().to_string()));
+        }
+        if ui.button("Refresh").clicked() {
+            command = Some(PresetCommand::Refresh);
+        }
+    });
+
+    command
+}
+
+/// File path for a preset name in a directory (unsafe filename characters replaced)
+pub fn preset_path(dir: &Path, name: &str) -> PathBuf {
+    let file_name: String = name
+        .chars()
+        .map(|c| if c.is_alphanumeric() || c == '-' || c == '_' || c == ' ' { c } else { '_' })
+        .collect();
+    dir.join(file_name).with_extension(crate::effects::PRESET_EXTENSION)
+}
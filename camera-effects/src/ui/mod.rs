@@ -2,4 +2,4 @@
 //!
 //! Provides the user interface for camera effects control.
 
-// UI components will be added here as needed
+pub mod effect_chain;
@@ -14,7 +14,7 @@ pub use dashboard::run_dashboard_server;
 pub use server::{create_shared_state, run_server};
 pub use shared::{
     ApiCommand, AppSnapshot, ClipSnapshot, EffectParamInfo, EffectSnapshot, EffectTypeInfo,
-    FileSnapshot, LayerSnapshot, OutputSnapshot, PerformanceSnapshot, SharedState,
+    FileSnapshot, LayerSnapshot, OutputSnapshot, PerformanceSnapshot, ScreenSnapshot, SharedState,
     SharedStateHandle, SourceSnapshot, StreamingSnapshot, ViewportSnapshot, WsEvent, WsSnapshot,
 };
 pub use types::*;
@@ -1,7 +1,8 @@
 //! API route definitions
 
 use axum::{
-    extract::{Path, State},
+    body::Bytes,
+    extract::{DefaultBodyLimit, Path, State},
     http::StatusCode,
     response::Html,
     routing::{delete, get, post, put},
@@ -15,6 +16,9 @@ use crate::compositor::BlendMode;
 /// Embedded dashboard HTML
 const DASHBOARD_HTML: &str = include_str!("dashboard.html");
 
+/// Largest accepted calibration pattern upload (an uncompressed 8K PNG fits)
+const CALIBRATION_PATTERN_BODY_LIMIT: usize = 64 * 1024 * 1024;
+
 /// Create the API router with all endpoints
 pub fn create_router(state: SharedStateHandle) -> Router {
     Router::new()
@@ -125,6 +129,13 @@ pub fn create_router(state: SharedStateHandle) -> Router {
         .route("/api/outputs", get(list_outputs))
         .route("/api/outputs/:id", get(get_output))
         .route("/api/outputs/:id", put(update_output))
+        // Screens (calibration patterns for remote projection mapping)
+        .route("/api/screens", get(list_screens))
+        .route(
+            "/api/screens/:id/calibration-pattern",
+            put(show_calibration_pattern).layer(DefaultBodyLimit::max(CALIBRATION_PATTERN_BODY_LIMIT)),
+        )
+        .route("/api/screens/:id/calibration-pattern", delete(clear_calibration_pattern))
         // File operations
         .route("/api/files/current", get(get_current_file))
         .route("/api/files/open", post(open_file))
@@ -1039,6 +1050,72 @@ async fn update_output(
     Json(serde_json::json!({ "message": "Output update requested" }))
 }
 
+// ============================================================================
+// Screen Handlers
+// ============================================================================
+
+async fn list_screens(State(state): State<SharedStateHandle>) -> Json<ScreensResponse> {
+    let snapshot = state.get_snapshot();
+    Json(ScreensResponse {
+        screens: snapshot.screens.iter().map(|s| ScreenSummary {
+            id: s.id,
+            name: s.name.clone(),
+            width: s.width,
+            height: s.height,
+            enabled: s.enabled,
+            calibration_pattern: s.calibration_pattern,
+        }).collect(),
+    })
+}
+
+/// Show a grayscale pattern (PNG body at screen resolution) in place of the screen's slices
+async fn show_calibration_pattern(
+    State(state): State<SharedStateHandle>,
+    Path(id): Path<u32>,
+    body: Bytes,
+) -> Result<Json<serde_json::Value>, (StatusCode, Json<ApiError>)> {
+    let snapshot = state.get_snapshot();
+    let screen = snapshot.screens.iter()
+        .find(|s| s.id == id)
+        .ok_or_else(|| (StatusCode::NOT_FOUND, Json(ApiError::not_found(format!("Screen {} not found", id)))))?;
+
+    let pattern = image::load_from_memory(&body)
+        .map_err(|e| (StatusCode::BAD_REQUEST, Json(ApiError::bad_request(format!("Invalid pattern image: {}", e)))))?
+        .to_luma8();
+    if pattern.dimensions() != (screen.width, screen.height) {
+        return Err((StatusCode::BAD_REQUEST, Json(ApiError::bad_request(format!(
+            "Pattern is {}x{} but screen {} is {}x{}",
+            pattern.width(), pattern.height(), id, screen.width, screen.height
+        )))));
+    }
+
+    // Wait for the main thread so a rejected pattern is reported to the caller
+    let (reply, result) = tokio::sync::oneshot::channel();
+    let _ = state.send_command(ApiCommand::ShowCalibrationPattern {
+        screen_id: id,
+        pattern: std::sync::Arc::new(pattern),
+        reply,
+    });
+    match result.await {
+        Ok(Ok(())) => Ok(Json(serde_json::json!({ "message": "Calibration pattern shown" }))),
+        Ok(Err(e)) => Err((StatusCode::BAD_REQUEST, Json(ApiError::bad_request(e)))),
+        Err(_) => Err((StatusCode::INTERNAL_SERVER_ERROR, Json(ApiError::internal("Screen outputs are not available")))),
+    }
+}
+
+async fn clear_calibration_pattern(
+    State(state): State<SharedStateHandle>,
+    Path(id): Path<u32>,
+) -> Result<Json<serde_json::Value>, (StatusCode, Json<ApiError>)> {
+    let snapshot = state.get_snapshot();
+    if !snapshot.screens.iter().any(|s| s.id == id) {
+        return Err((StatusCode::NOT_FOUND, Json(ApiError::not_found(format!("Screen {} not found", id)))));
+    }
+
+    let _ = state.send_command(ApiCommand::ClearCalibrationPattern { screen_id: id });
+    Ok(Json(serde_json::json!({ "message": "Calibration pattern cleared" })))
+}
+
 // ============================================================================
 // File Handlers
 // ============================================================================
@@ -4,13 +4,13 @@
 //! The state is a snapshot that gets updated by the main thread each frame.
 
 use std::sync::{Arc, RwLock};
-use tokio::sync::{broadcast, mpsc};
+use tokio::sync::{broadcast, mpsc, oneshot};
 
 use super::types::*;
 use crate::compositor::{BlendMode, ClipTransition};
 
 /// Commands that can be sent from API handlers to the main application
-#[derive(Debug, Clone)]
+#[derive(Debug)]
 pub enum ApiCommand {
     // Environment commands
     SetEnvironmentSize { width: u32, height: u32 },
@@ -103,6 +103,15 @@ pub enum ApiCommand {
     RemoveClipEffect { layer_id: u32, slot: usize, effect_id: String },
     UpdateClipEffect { layer_id: u32, slot: usize, effect_id: String, parameters: serde_json::Value },
     BypassClipEffect { layer_id: u32, slot: usize, effect_id: String },
+
+    // Calibration patterns (remote projection mapping calibration)
+    /// `reply` receives whether the pattern could be shown on the screen
+    ShowCalibrationPattern {
+        screen_id: u32,
+        pattern: Arc<image::GrayImage>,
+        reply: oneshot::Sender<Result<(), String>>,
+    },
+    ClearCalibrationPattern { screen_id: u32 },
 }
 
 /// Snapshot of layer state for API reads
@@ -192,6 +201,18 @@ pub struct OutputSnapshot {
     pub refresh_rate_hz: Option<u32>,
 }
 
+/// Snapshot of an output screen
+#[derive(Debug, Clone)]
+pub struct ScreenSnapshot {
+    pub id: u32,
+    pub name: String,
+    pub width: u32,
+    pub height: u32,
+    pub enabled: bool,
+    /// Whether a calibration pattern replaces the screen's slices
+    pub calibration_pattern: bool,
+}
+
 /// Performance metrics snapshot
 #[derive(Debug, Clone, Default)]
 pub struct PerformanceSnapshot {
@@ -259,6 +280,8 @@ pub struct AppSnapshot {
     pub clip_columns: usize,
     /// Available output displays
     pub outputs: Vec<OutputSnapshot>,
+    /// Configured output screens
+    pub screens: Vec<ScreenSnapshot>,
     /// Performance metrics
     pub performance: PerformanceSnapshot,
     /// Available effect types from registry
@@ -300,6 +323,7 @@ impl Default for AppSnapshot {
             environment_effects: Vec::new(),
             clip_columns: 8,
             outputs: Vec::new(),
+            screens: Vec::new(),
             performance: PerformanceSnapshot::default(),
             effect_types: Vec::new(),
             effect_categories: Vec::new(),
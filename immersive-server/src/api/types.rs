@@ -421,6 +421,23 @@ pub struct UpdateOutputRequest {
     pub enabled: Option<bool>,
 }
 
+/// Output screen summary
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScreenSummary {
+    pub id: u32,
+    pub name: String,
+    pub width: u32,
+    pub height: u32,
+    pub enabled: bool,
+    pub calibration_pattern: bool,
+}
+
+/// Screens list response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScreensResponse {
+    pub screens: Vec<ScreenSummary>,
+}
+
 // ============================================================================
 // File Types
 // ============================================================================
@@ -60,6 +60,12 @@ const FPS_UPDATE_INTERVAL_SECS: f64 = 1.0;
 /// Refresh source discovery every 2 seconds
 const DISCOVERY_REFRESH_INTERVAL_SECS: f64 = 2.0;
 
+/// Parameter moves further apart than this start a new curve recording pass
+const CURVE_RECORD_PASS_GAP_SECS: f32 = 0.25;
+
+/// Tolerance (normalized value) for thinning recorded curve keyframes on disarm
+const CURVE_SIMPLIFY_TOLERANCE: f32 = 0.005;
+
 /// Main application state holding all wgpu resources
 pub struct App {
     /// Reference to the window
@@ -211,6 +217,10 @@ pub struct App {
     pub converter_window: crate::converter::ConverterWindow,
     /// Preferences window for environment settings
     pub preferences_window: crate::ui::PreferencesWindow,
+    /// Curve editor window for keyframed parameter automation
+    pub curve_editor_window: crate::ui::CurveEditorWindow,
+    /// Last recorded curve position per armed parameter, for overwriting within a recording pass
+    curve_record_passes: HashMap<crate::ui::CurveTarget, (f32, Instant)>,
     /// Advanced Output window for multi-screen configuration
     pub advanced_output_window: crate::ui::AdvancedOutputWindow,
     /// Available displays for output selection (updated from DisplayManager)
@@ -234,6 +244,11 @@ pub struct App {
     pending_runtimes: HashMap<u32, LayerRuntime>,
     /// Pending transitions for layers (stored when clip is triggered, applied when ready)
     pending_transition: HashMap<u32, crate::compositor::ClipTransition>,
+    /// Ableton Link session driving the BPM clock (when enabled in settings)
+    link_session: Option<crate::network::LinkSession>,
+    /// Quantized clip triggers waiting for a beat boundary
+    /// Key is layer ID, value is (slot, beat at which the trigger fires)
+    pending_clip_triggers: HashMap<u32, (usize, f64)>,
     /// Scrub state: tracks which layers were playing before scrubbing started
     /// Key is layer ID, value is true if layer was playing (not paused) before scrub
     scrub_was_playing: HashMap<u32, bool>,
@@ -709,6 +724,8 @@ impl App {
             thumbnail_cache: crate::ui::ThumbnailCache::new(),
             converter_window: crate::converter::ConverterWindow::new(),
             preferences_window: crate::ui::PreferencesWindow::new(),
+            curve_editor_window: crate::ui::CurveEditorWindow::new(),
+            curve_record_passes: HashMap::new(),
             advanced_output_window: crate::ui::AdvancedOutputWindow::new(),
             available_displays: Vec::new(),
             layout_preset_manager: {
@@ -723,6 +740,8 @@ impl App {
             layer_runtimes: HashMap::new(),
             pending_runtimes: HashMap::new(),
             pending_transition: HashMap::new(),
+            pending_clip_triggers: HashMap::new(),
+            link_session: None,
             scrub_was_playing: HashMap::new(),
             scrub_was_playing_preview: false,
             last_upload_layer: 0,
@@ -1868,6 +1887,21 @@ impl App {
         }
     }
 
+    /// Sync FFT gain and band configuration from settings (after loading)
+    pub fn sync_audio_analysis_from_settings(&mut self) {
+        self.audio_manager.set_master_sensitivity(self.settings.fft_gain);
+        self.audio_manager.set_band_count(self.settings.fft_band_count);
+        for index in 0..self.audio_manager.band_count() {
+            let envelope = self
+                .settings
+                .fft_band_envelopes
+                .get(index)
+                .copied()
+                .unwrap_or_default();
+            self.audio_manager.set_band_envelope(index, envelope);
+        }
+    }
+
     /// Sync OMT broadcast state from settings (after loading)
     pub fn sync_omt_broadcast_from_settings(&mut self) {
         let should_broadcast = self.settings.omt_broadcast_enabled;
@@ -1956,9 +1990,23 @@ impl App {
         // Update effect manager timing (BPM clock, frame time)
         self.effect_manager.update();
 
+        // Follow the Ableton Link session (overrides the free-running clock)
+        self.sync_link_session();
+
+        // Fire quantized clip triggers that reached their beat boundary
+        self.process_pending_clip_triggers();
+        self.sync_bpm_clip_speeds();
+
         // Update audio manager (FFT analysis for audio-reactive effects)
         self.audio_manager.update();
 
+        // Follow tempo detected in the audio (when enabled and Link is off)
+        self.sync_audio_tempo();
+
+        // Upload spectrum/waveform for audio-reactive shaders
+        self.effect_manager
+            .update_audio_texture(&self.device, &self.queue, &self.audio_manager);
+
         // Poll for shader hot-reload (no-op in release builds)
         self.poll_shader_reload();
 
@@ -2149,7 +2197,9 @@ impl App {
                             // 4. Process preview effects
                             let effect_params = self.effect_manager.build_params();
                             let bpm_clock = self.effect_manager.bpm_clock().clone();
+                            let clip_time = self.preview_player.video_info().map(|info| info.position as f32);
                             if let Some(preview_runtime) = self.effect_manager.get_preview_runtime_mut() {
+                                preview_runtime.set_clip_time(clip_time);
                                 if let (Some(input_view), Some(output_view)) = (
                                     preview_runtime.input_view().map(|v| v as *const _),
                                     preview_runtime.output_view(active_effect_count).map(|v| v as *const _),
@@ -2288,7 +2338,9 @@ impl App {
                                 effect_params.params[27] = video_h as f32 / env_h as f32;
                                 let bpm_clock = self.effect_manager.bpm_clock().clone();
                                 let combined_effect_count = combined_effects.active_effects().count();
+                                let clip_time = layer_runtime.video_info().map(|info| info.position as f32);
                                 if let Some(preview_runtime) = self.effect_manager.get_preview_runtime_mut() {
+                                    preview_runtime.set_clip_time(clip_time);
                                     if let (Some(input_view), Some(output_view)) = (
                                         preview_runtime.input_view().map(|v| v as *const _),
                                         preview_runtime.output_view(combined_effect_count).map(|v| v as *const _),
@@ -2429,7 +2481,8 @@ impl App {
                 bpm: clock.bpm(),
                 beats_per_bar: clock.beats_per_bar(),
                 beat_phase: clock.beat_phase(),
-                current_beat_in_bar: (clock.current_beat() % clock.beats_per_bar() as f32).floor() as u32,
+                current_beat_in_bar: clock.current_beat().rem_euclid(clock.beats_per_bar() as f64).floor() as u32,
+                link_peers: self.link_session.as_ref().map(|link| link.num_peers()),
             }
         };
 
@@ -2513,18 +2566,21 @@ impl App {
                     }
                 }
                 crate::ui::menu_bar::MenuAction::SetBpm { bpm } => {
-                    self.effect_manager.bpm_clock_mut().set_bpm(bpm);
+                    self.set_bpm(bpm);
                     tracing::debug!("Set BPM to {:.1}", bpm);
                 }
                 crate::ui::menu_bar::MenuAction::TapTempo => {
-                    self.effect_manager.bpm_clock_mut().tap();
+                    self.tap_tempo();
                     let new_bpm = self.effect_manager.bpm_clock().bpm();
                     tracing::debug!("Tap tempo: BPM now {:.1}", new_bpm);
                 }
                 crate::ui::menu_bar::MenuAction::ResyncBpm => {
-                    self.effect_manager.bpm_clock_mut().resync_to_bar();
+                    self.resync_bpm();
                     tracing::debug!("Resync to bar start");
                 }
+                crate::ui::menu_bar::MenuAction::ToggleLink => {
+                    self.settings.link_enabled = !self.settings.link_enabled;
+                }
                 crate::ui::menu_bar::MenuAction::BreakoutEnvironment => {
                     // Calculate a reasonable default position and size for the environment window
                     let pos = (100.0, 100.0);
@@ -2591,6 +2647,22 @@ impl App {
             self.handle_properties_action(action);
         }
 
+        // Render Curve Editor window
+        let curve_clip_time = self
+            .curve_editor_window
+            .target()
+            .map(|target| self.curve_clip_time(target))
+            .unwrap_or_else(|| self.effect_manager.time());
+        let curve_actions = self.curve_editor_window.render(
+            &self.egui_ctx,
+            &self.environment,
+            self.effect_manager.bpm_clock(),
+            curve_clip_time,
+        );
+        for action in curve_actions {
+            self.handle_properties_action(action);
+        }
+
         // Render Advanced Output window
         let layer_count = self.environment.layers().len();
         let env_dimensions = (self.environment.width(), self.environment.height());
@@ -3665,7 +3737,10 @@ impl App {
                                             effect_params.params[26] = runtime.video_width as f32 / self.environment.width() as f32;
                                             effect_params.params[27] = runtime.video_height as f32 / self.environment.height() as f32;
                                             let bpm_clock = self.effect_manager.bpm_clock().clone();
+                                            // Clip playback position drives clip-time automation curves
+                                            let clip_time = runtime.video_info().map(|info| info.position as f32);
                                             if let Some(clip_runtime) = self.effect_manager.get_clip_runtime_mut(layer.id, slot) {
+                                                clip_runtime.set_clip_time(clip_time);
                                                 if let (Some(input_view), Some(output_view)) = (
                                                     clip_runtime.input_view().map(|v| v as *const _),
                                                     clip_runtime.output_view(clip_active_effect_count).map(|v| v as *const _),
@@ -3766,7 +3841,9 @@ impl App {
                                         effect_params.params[26] = runtime.video_width as f32 / self.environment.width() as f32;
                                         effect_params.params[27] = runtime.video_height as f32 / self.environment.height() as f32;
                                         let bpm_clock = self.effect_manager.bpm_clock().clone();
+                                        let clip_time = runtime.video_info().map(|info| info.position as f32);
                                         if let Some(layer_runtime) = self.effect_manager.get_layer_runtime_mut(layer.id) {
+                                            layer_runtime.set_clip_time(clip_time);
                                             if let (Some(input_view), Some(output_view)) = (
                                                 layer_runtime.input_view().map(|v| v as *const _),
                                                 layer_runtime.output_view(layer_active_effect_count).map(|v| v as *const _),
@@ -4273,7 +4350,8 @@ impl App {
                 bpm: clock.bpm(),
                 beats_per_bar: clock.beats_per_bar(),
                 beat_phase: clock.beat_phase(),
-                current_beat_in_bar: (clock.current_beat() % clock.beats_per_bar() as f32).floor() as u32,
+                current_beat_in_bar: clock.current_beat().rem_euclid(clock.beats_per_bar() as f64).floor() as u32,
+                link_peers: self.link_session.as_ref().map(|link| link.num_peers()),
             }
         };
 
@@ -4356,7 +4434,7 @@ impl App {
                             );
 
                             if response.changed() {
-                                self.effect_manager.bpm_clock_mut().set_bpm(bpm);
+                                self.set_bpm(bpm);
                             }
 
                             // Tap tempo button
@@ -4365,7 +4443,7 @@ impl App {
                                 .on_hover_text("Tap to set tempo")
                                 .clicked()
                             {
-                                self.effect_manager.bpm_clock_mut().tap();
+                                self.tap_tempo();
                             }
 
                             // Resync button
@@ -4374,7 +4452,12 @@ impl App {
                                 .on_hover_text("Resync to bar start")
                                 .clicked()
                             {
-                                self.effect_manager.bpm_clock_mut().resync_to_bar();
+                                self.resync_bpm();
+                            }
+
+                            // Ableton Link toggle
+                            if crate::ui::menu_bar::link_button(ui, bpm_info.link_peers).clicked() {
+                                self.settings.link_enabled = !self.settings.link_enabled;
                             }
 
                             ui.separator();
@@ -5067,10 +5150,28 @@ impl App {
     }
 
     /// Toggle pause for all layers
+    ///
+    /// With Link start/stop sync enabled the new play state is also proposed
+    /// to the Link session.
     pub fn toggle_all_pause(&self) {
         for runtime in self.layer_runtimes.values() {
             runtime.toggle_pause();
         }
+        self.propose_link_playing();
+    }
+
+    /// Send the local transport state to the Link session when start/stop sync is on
+    fn propose_link_playing(&self) {
+        if !self.settings.link_start_stop_sync {
+            return;
+        }
+        if let Some(link) = &self.link_session {
+            let playing = self
+                .layer_runtimes
+                .values()
+                .any(|r| r.has_video() && !r.is_paused());
+            link.set_playing(playing);
+        }
     }
 
     /// Restart all layer videos
@@ -5107,6 +5208,190 @@ impl App {
     /// Returns `Ok(())` if successful, or an error message if the clip
     /// couldn't be loaded.
     pub fn trigger_clip(&mut self, layer_id: u32, slot: usize) -> Result<(), String> {
+        let quantize = {
+            let layer = self.environment.get_layer(layer_id)
+                .ok_or_else(|| format!("Layer {} not found", layer_id))?;
+            layer.get_clip(slot)
+                .ok_or_else(|| format!("No clip at slot {}", slot))?;
+            layer.trigger_quantize.unwrap_or(self.settings.trigger_quantize)
+        };
+
+        // Quantized triggers wait for the next boundary on the BPM clock.
+        // A stopped clock never reaches the boundary, so trigger immediately.
+        let clock = self.effect_manager.bpm_clock();
+        let fire_at = quantize.next_boundary(clock.current_beat(), clock.beats_per_bar());
+        match fire_at {
+            Some(beat) if clock.is_running() => {
+                tracing::info!("⏳ Queued clip {} on layer {} for beat {:.0} ({})",
+                    slot, layer_id, beat, quantize.name());
+                self.pending_clip_triggers.insert(layer_id, (slot, beat));
+                if let Some(layer) = self.environment.get_layer_mut(layer_id) {
+                    layer.pending_clip = Some(slot);
+                }
+                Ok(())
+            }
+            _ => {
+                self.cancel_pending_clip_trigger(layer_id);
+                self.trigger_clip_now(layer_id, slot)
+            }
+        }
+    }
+
+    /// Set the BPM (proposed to the Link session when Link is enabled)
+    pub fn set_bpm(&mut self, bpm: f32) {
+        self.effect_manager.bpm_clock_mut().set_bpm(bpm);
+        if let Some(link) = &self.link_session {
+            link.set_tempo(bpm as f64);
+        }
+    }
+
+    /// Register a tap-tempo tap (proposed to the Link session when Link is enabled)
+    pub fn tap_tempo(&mut self) {
+        self.effect_manager.bpm_clock_mut().tap();
+        if let Some(link) = &self.link_session {
+            link.set_tempo(self.effect_manager.bpm_clock().bpm() as f64);
+        }
+    }
+
+    /// Resync the BPM clock to the start of the current bar
+    ///
+    /// Ignored while Link is active: beat phase is owned by the Link session.
+    pub fn resync_bpm(&mut self) {
+        if self.link_session.is_some() {
+            self.menu_bar.set_status("Beat phase follows the Link session");
+            return;
+        }
+        self.effect_manager.bpm_clock_mut().resync_to_bar();
+    }
+
+    /// Start/stop the Link session to match settings and drive the BPM clock from it
+    fn sync_link_session(&mut self) {
+        if self.settings.link_enabled != self.link_session.is_some() {
+            if self.settings.link_enabled {
+                let bpm = self.effect_manager.bpm_clock().bpm() as f64;
+                match crate::network::LinkSession::start(bpm) {
+                    Ok(link) => self.link_session = Some(link),
+                    Err(e) => {
+                        tracing::warn!("Failed to start Ableton Link: {}", e);
+                        self.menu_bar.set_status(format!("Link unavailable: {}", e));
+                        self.settings.link_enabled = false;
+                    }
+                }
+            } else {
+                self.link_session = None;
+            }
+        }
+
+        if let Some(link) = &self.link_session {
+            let state = link.capture();
+            // Without start/stop sync the clock keeps running regardless of transport
+            let running = !self.settings.link_start_stop_sync || state.is_playing;
+            self.effect_manager
+                .bpm_clock_mut()
+                .sync_external(state.bpm as f32, state.beat, running);
+        }
+    }
+
+    /// Drive the BPM clock from the tempo detected on the primary audio source
+    ///
+    /// Link takes precedence. Estimates from audio without a clear pulse are
+    /// ignored so the clock keeps its last good tempo.
+    fn sync_audio_tempo(&mut self) {
+        const MIN_CONFIDENCE: f32 = 0.3;
+
+        if !self.settings.audio_beat_sync
+            || self.link_session.is_some()
+            || !self.settings.audio_source.is_enabled()
+        {
+            return;
+        }
+
+        if let Some(estimate) = self.audio_manager.tempo_estimate() {
+            if estimate.confidence >= MIN_CONFIDENCE {
+                self.effect_manager
+                    .bpm_clock_mut()
+                    .follow_tempo(estimate.bpm, estimate.beat_phase);
+            }
+        }
+    }
+
+    /// Fire queued clip triggers whose beat boundary has been reached
+    ///
+    /// If the clock jumps backwards (reset/nudge), pending triggers are
+    /// re-aligned to the next boundary so they don't wait indefinitely.
+    fn process_pending_clip_triggers(&mut self) {
+        if self.pending_clip_triggers.is_empty() {
+            return;
+        }
+
+        let clock = self.effect_manager.bpm_clock();
+        let current_beat = clock.current_beat();
+        let beats_per_bar = clock.beats_per_bar();
+        let running = clock.is_running();
+
+        let mut due = Vec::new();
+        for (&layer_id, (slot, fire_at)) in self.pending_clip_triggers.iter_mut() {
+            if !running || current_beat >= *fire_at {
+                due.push((layer_id, *slot));
+                continue;
+            }
+            let quantum = self.environment.get_layer(layer_id)
+                .and_then(|l| l.trigger_quantize.unwrap_or(self.settings.trigger_quantize).quantum_beats(beats_per_bar));
+            match quantum {
+                Some(quantum) if *fire_at - current_beat > quantum => {
+                    *fire_at = ((current_beat / quantum).floor() + 1.0) * quantum;
+                }
+                Some(_) => {}
+                // Quantization was switched off while waiting
+                None => due.push((layer_id, *slot)),
+            }
+        }
+
+        for (layer_id, slot) in due {
+            self.pending_clip_triggers.remove(&layer_id);
+            if let Some(layer) = self.environment.get_layer_mut(layer_id) {
+                layer.pending_clip = None;
+            }
+            if let Err(e) = self.trigger_clip_now(layer_id, slot) {
+                tracing::warn!("Quantized trigger failed on layer {}: {}", layer_id, e);
+            }
+        }
+    }
+
+    /// Cancel a queued (quantized) clip trigger on a layer
+    fn cancel_pending_clip_trigger(&mut self, layer_id: u32) {
+        if self.pending_clip_triggers.remove(&layer_id).is_some() {
+            if let Some(layer) = self.environment.get_layer_mut(layer_id) {
+                layer.pending_clip = None;
+            }
+        }
+    }
+
+    /// Keep BPM-synced clips stretched to a whole number of bars
+    ///
+    /// Runs every frame so tempo changes are picked up immediately.
+    fn sync_bpm_clip_speeds(&mut self) {
+        let seconds_per_bar = self.effect_manager.bpm_clock().seconds_per_bar();
+        for layer in self.environment.layers() {
+            let Some(clip) = layer.active_clip.and_then(|slot| layer.get_clip(slot)) else {
+                continue;
+            };
+            let Some(player) = self.layer_runtimes.get(&layer.id).and_then(|r| r.player.as_ref()) else {
+                continue;
+            };
+            let speed = if clip.bpm_sync {
+                crate::compositor::bpm_sync_speed(player.duration(), seconds_per_bar)
+            } else {
+                1.0
+            };
+            if (player.speed() - speed).abs() > 1e-6 {
+                player.set_speed(speed);
+            }
+        }
+    }
+
+    /// Trigger a clip immediately, ignoring trigger quantization
+    pub fn trigger_clip_now(&mut self, layer_id: u32, slot: usize) -> Result<(), String> {
         // Get the clip source and layer transition
         let (clip_source, transition) = {
             let layer = self.environment.get_layer(layer_id)
@@ -5259,8 +5544,9 @@ impl App {
                             "🔄 Floor sync: triggering clip {} on floor layer {} (index {})",
                             slot, floor_layer_id, floor_layer_idx
                         );
+                        // Fire in lockstep with the source layer (already quantized)
                         // Ignore errors - it's okay if the floor layer doesn't have a clip
-                        let _ = self.trigger_clip(floor_layer_id, slot);
+                        let _ = self.trigger_clip_now(floor_layer_id, slot);
                     }
                 }
             }
@@ -5273,6 +5559,8 @@ impl App {
     ///
     /// Clears the video player and resets the active clip indicator.
     pub fn stop_clip(&mut self, layer_id: u32) {
+        self.cancel_pending_clip_trigger(layer_id);
+
         // Clear the runtime video resources
         if let Some(runtime) = self.layer_runtimes.get_mut(&layer_id) {
             runtime.clear();
@@ -5291,6 +5579,8 @@ impl App {
     ///
     /// Starts a fade-out animation; the actual clear happens when fade completes.
     pub fn stop_clip_with_fade(&mut self, layer_id: u32) {
+        self.cancel_pending_clip_trigger(layer_id);
+
         // Get the transition duration from the layer
         let fade_duration = self.environment
             .get_layer(layer_id)
@@ -5659,6 +5949,19 @@ impl App {
             }
         }).collect();
 
+        // Build output screens from the output manager
+        let screens: Vec<crate::api::ScreenSnapshot> = self.output_manager.as_ref().map(|manager| {
+            manager.screens().map(|screen| crate::api::ScreenSnapshot {
+                id: screen.id.0,
+                name: screen.name.clone(),
+                width: screen.width,
+                height: screen.height,
+                enabled: screen.enabled,
+                calibration_pattern: manager.get_runtime(screen.id)
+                    .is_some_and(|runtime| runtime.has_calibration_pattern()),
+            }).collect()
+        }).unwrap_or_default();
+
         // Build performance metrics from frame profiler
         let frame_stats = self.frame_profiler.stats();
         let performance = PerformanceSnapshot {
@@ -5748,6 +6051,7 @@ impl App {
                 .collect(),
             clip_columns: self.settings.global_clip_count,
             outputs,
+            screens,
             performance,
             effect_types,
             effect_categories,
@@ -6035,6 +6339,7 @@ impl App {
                         Ok(settings) => {
                             self.settings = settings;
                             self.current_file = Some(path.clone());
+                            self.sync_audio_analysis_from_settings();
                             tracing::info!("🌐 API: Opened file {:?}", path);
                         }
                         Err(e) => {
@@ -6239,6 +6544,31 @@ impl App {
                     }
                 }
 
+                // Calibration patterns
+                ApiCommand::ShowCalibrationPattern { screen_id, pattern, reply } => {
+                    // Without an output manager the reply is dropped, which the
+                    // handler reports as outputs being unavailable
+                    if let Some(manager) = &mut self.output_manager {
+                        let result = manager.set_calibration_pattern(
+                            &self.device,
+                            &self.queue,
+                            crate::output::ScreenId(screen_id),
+                            &pattern,
+                        );
+                        match &result {
+                            Ok(()) => tracing::debug!("🌐 API: Showing calibration pattern on screen {}", screen_id),
+                            Err(e) => tracing::warn!("🌐 API: Calibration pattern rejected: {}", e),
+                        }
+                        let _ = reply.send(result);
+                    }
+                }
+                ApiCommand::ClearCalibrationPattern { screen_id } => {
+                    if let Some(manager) = &mut self.output_manager {
+                        manager.clear_calibration_pattern(crate::output::ScreenId(screen_id));
+                        tracing::debug!("🌐 API: Cleared calibration pattern on screen {}", screen_id);
+                    }
+                }
+
                 // Catch-all for unimplemented commands
                 _ => {
                     tracing::warn!("🌐 API: Unimplemented command received: {:?}", cmd);
@@ -6635,6 +6965,7 @@ impl App {
             // Reset runtime state (source and active_clip are runtime, not saved)
             cloned.source = crate::compositor::LayerSource::None;
             cloned.active_clip = None;
+            cloned.pending_clip = None;
 
             // If the source has an active clip, get its path so we can load it independently
             let active_path = if let Some(active_slot) = source_layer.active_clip {
@@ -6694,6 +7025,22 @@ impl App {
         }
     }
 
+    /// Set the trigger quantization override for a layer (None = use global setting)
+    pub fn set_layer_trigger_quantize(
+        &mut self,
+        layer_id: u32,
+        quantize: Option<crate::compositor::TriggerQuantize>,
+    ) {
+        if let Some(layer) = self.environment.get_layer_mut(layer_id) {
+            layer.trigger_quantize = quantize;
+            tracing::info!(
+                "Set trigger quantize for layer {} to {}",
+                layer_id,
+                quantize.map(|q| q.name()).unwrap_or_else(|| "Global".to_string())
+            );
+        }
+    }
+
     /// Check if a clip is active on a layer at the given slot
     pub fn is_clip_active(&self, layer_id: u32, slot: usize) -> bool {
         self.environment
@@ -6752,6 +7099,9 @@ impl App {
             ClipGridAction::SetLayerTransition { layer_id, transition } => {
                 self.set_layer_transition(layer_id, transition);
             }
+            ClipGridAction::SetLayerTriggerQuantize { layer_id, quantize } => {
+                self.set_layer_trigger_quantize(layer_id, quantize);
+            }
             ClipGridAction::AddLayer => {
                 self.add_layer();
             }
@@ -6980,6 +7330,9 @@ impl App {
             PropertiesAction::SetLayerTransition { layer_id, transition } => {
                 self.set_layer_transition(layer_id, transition);
             }
+            PropertiesAction::SetLayerTriggerQuantize { layer_id, quantize } => {
+                self.set_layer_trigger_quantize(layer_id, quantize);
+            }
             PropertiesAction::SetOmtBroadcast { enabled } => {
                 self.settings.omt_broadcast_enabled = enabled;
                 self.omt_broadcast_enabled = enabled;
@@ -7107,11 +7460,14 @@ impl App {
                 }
             }
             PropertiesAction::SetLayerEffectParameter { layer_id, effect_id, param_name, value } => {
+                let raw_value = value.as_f32();
                 if let Some(layer) = self.environment.get_layer_mut(layer_id) {
                     if let Some(effect) = layer.effects.get_mut(effect_id) {
                         effect.set_parameter(&param_name, value);
                     }
                 }
+                let context = crate::ui::EffectContext::Layer { layer_id };
+                self.record_curve_value(crate::ui::CurveTarget::new(context, effect_id, &param_name), raw_value);
             }
             PropertiesAction::ReorderLayerEffect { layer_id, effect_id, new_index } => {
                 if let Some(layer) = self.environment.get_layer_mut(layer_id) {
@@ -7163,6 +7519,7 @@ impl App {
                 }
             }
             PropertiesAction::SetClipEffectParameter { layer_id, slot, effect_id, param_name, value } => {
+                let raw_value = value.as_f32();
                 if let Some(layer) = self.environment.get_layer_mut(layer_id) {
                     if let Some(clip) = layer.get_clip_mut(slot) {
                         if let Some(effect) = clip.effects.get_mut(effect_id) {
@@ -7170,6 +7527,8 @@ impl App {
                         }
                     }
                 }
+                let context = crate::ui::EffectContext::Clip { layer_id, slot };
+                self.record_curve_value(crate::ui::CurveTarget::new(context, effect_id, &param_name), raw_value);
             }
             PropertiesAction::ReorderClipEffect { layer_id, slot, effect_id, new_index } => {
                 if let Some(layer) = self.environment.get_layer_mut(layer_id) {
@@ -7207,9 +7566,12 @@ impl App {
                 }
             }
             PropertiesAction::SetEnvironmentEffectParameter { effect_id, param_name, value } => {
+                let raw_value = value.as_f32();
                 if let Some(effect) = self.environment.effects_mut().get_mut(effect_id) {
                     effect.set_parameter(&param_name, value);
                 }
+                let context = crate::ui::EffectContext::Environment;
+                self.record_curve_value(crate::ui::CurveTarget::new(context, effect_id, &param_name), raw_value);
             }
             PropertiesAction::ReorderEnvironmentEffect { effect_id, new_index } => {
                 self.environment.effects_mut().move_effect(effect_id, new_index);
@@ -7334,9 +7696,27 @@ impl App {
                     }
                 }
             }
+            PropertiesAction::SetClipBpmSync { layer_id, slot, enabled } => {
+                // Playback speed is picked up by sync_bpm_clip_speeds() next frame
+                if let Some(layer) = self.environment.get_layer_mut(layer_id) {
+                    if let Some(Some(clip)) = layer.clips.get_mut(slot) {
+                        clip.bpm_sync = enabled;
+                    }
+                }
+            }
             PropertiesAction::SetFloorSyncEnabled { enabled } => {
                 self.settings.floor_sync_enabled = enabled;
             }
+            PropertiesAction::SetTriggerQuantize { quantize } => {
+                self.settings.trigger_quantize = quantize;
+            }
+            PropertiesAction::SetLinkEnabled { enabled } => {
+                // Session is started/stopped by sync_link_session() next frame
+                self.settings.link_enabled = enabled;
+            }
+            PropertiesAction::SetLinkStartStopSync { enabled } => {
+                self.settings.link_start_stop_sync = enabled;
+            }
             PropertiesAction::SetFloorLayerIndex { index } => {
                 self.settings.floor_layer_index = index;
             }
@@ -7459,7 +7839,84 @@ impl App {
                 self.audio_manager.set_master_sensitivity(gain);
                 tracing::debug!("[AUDIO] FFT gain set to {:.2}x", gain);
             }
+            PropertiesAction::SetAudioBeatSync { enabled } => {
+                self.settings.audio_beat_sync = enabled;
+                tracing::info!("🥁 Audio BPM detection {}", if enabled { "enabled" } else { "disabled" });
+            }
+            PropertiesAction::SetFftBandCount { count } => {
+                self.settings.fft_band_count = count;
+                self.settings.fft_band_envelopes.truncate(count);
+                self.sync_audio_analysis_from_settings();
+                tracing::debug!("[AUDIO] FFT band count set to {}", count);
+            }
+            PropertiesAction::SetFftBandEnvelope { index, envelope } => {
+                let envelopes = &mut self.settings.fft_band_envelopes;
+                if envelopes.len() <= index {
+                    envelopes.resize(index + 1, crate::audio::BandEnvelope::default());
+                }
+                envelopes[index] = envelope;
+                self.audio_manager.set_band_envelope(index, envelope);
+            }
+            PropertiesAction::OpenCurveEditor { target } => {
+                self.curve_editor_window.open_for(target);
+            }
+            PropertiesAction::SetCurveRecordArm { target, armed } => {
+                if let Some(param) = target.parameter_mut(&mut self.environment) {
+                    if let Some(crate::effects::AutomationSource::Curve(curve)) = &mut param.automation {
+                        curve.armed = armed;
+                        if !armed {
+                            // Recording adds a keyframe per UI update; keep only the shape
+                            curve.simplify(CURVE_SIMPLIFY_TOLERANCE);
+                        }
+                        tracing::info!("⏺ Curve recording {} for '{}'", if armed { "armed" } else { "disarmed" }, target.param_name);
+                    }
+                }
+                self.curve_record_passes.remove(&target);
+            }
+        }
+    }
+
+    /// Playback position for a curve target's clip-time automation
+    ///
+    /// Matches what the render loop hands the effect runtimes: the layer's
+    /// video position, or the effect time when there is none.
+    fn curve_clip_time(&self, target: &crate::ui::CurveTarget) -> f32 {
+        target
+            .layer_id()
+            .and_then(|layer_id| self.layer_runtimes.get(&layer_id))
+            .and_then(|runtime| runtime.video_info())
+            .map(|info| info.position as f32)
+            .unwrap_or_else(|| self.effect_manager.time())
+    }
+
+    /// Record a live parameter change into the parameter's curve if recording is armed
+    fn record_curve_value(&mut self, target: crate::ui::CurveTarget, value: f32) {
+        let clip_time = self.curve_clip_time(&target);
+        let bpm_clock = self.effect_manager.bpm_clock();
+        let Some(param) = target.parameter_mut(&mut self.environment) else { return };
+        let min = param.meta.min.unwrap_or(0.0);
+        let max = param.meta.max.unwrap_or(1.0);
+        let Some(crate::effects::AutomationSource::Curve(curve)) = &mut param.automation else { return };
+        if !curve.armed {
+            return;
         }
+
+        let position = curve.playhead(clip_time, bpm_clock);
+        let normalized = if max > min { ((value - min) / (max - min)).clamp(0.0, 1.0) } else { 0.0 };
+
+        // Moves in quick succession form one pass that replaces the keyframes it sweeps over
+        // (ignoring jumps backwards, e.g. when the clip is restarted)
+        let now = Instant::now();
+        let previous = self
+            .curve_record_passes
+            .get(&target)
+            .filter(|(last, at)| {
+                now.duration_since(*at).as_secs_f32() < CURVE_RECORD_PASS_GAP_SECS
+                    && curve.wrap(position - last) < curve.length * 0.5
+            })
+            .map(|(last, _)| *last);
+        curve.record(position, curve.range.unmap(normalized), previous);
+        self.curve_record_passes.insert(target, (position, now));
     }
 
     /// Handle an advanced output window action from the UI
@@ -7690,6 +8147,127 @@ impl App {
                 self.menu_bar.set_status("Created new output configuration");
                 tracing::info!("Created new output configuration");
             }
+            AdvancedOutputAction::ImportCalibration { path } => {
+                match crate::output::import_calibration(&path) {
+                    Ok(import) => {
+                        for warning in &import.warnings {
+                            tracing::warn!("Calibration import: {}", warning);
+                        }
+                        let screen_count = import.screens.len();
+                        self.apply_output_preset_screens(import.screens);
+
+                        // Calibrated warps address the whole environment, so undo the
+                        // pixel-accurate input_rect applied to full-coverage slices on add
+                        if let Some(manager) = self.output_manager.as_mut() {
+                            let screen_ids: Vec<_> = manager.screens().map(|s| s.id).collect();
+                            for screen_id in &screen_ids {
+                                if let Some(screen) = manager.get_screen_mut(*screen_id) {
+                                    for slice in &mut screen.slices {
+                                        slice.input_rect = crate::output::Rect::full();
+                                    }
+                                }
+                            }
+                            let target_fps = self.settings.target_fps as f32;
+                            let tokio_handle = self.tokio_runtime.as_ref().map(|rt| rt.handle());
+                            for screen_id in screen_ids {
+                                manager.sync_runtime(&self.device, screen_id, target_fps, tokio_handle);
+                            }
+                        }
+
+                        self.advanced_output_window.set_current_preset(None);
+                        self.advanced_output_window.mark_dirty();
+                        let mut status = format!(
+                            "Imported calibration '{}' ({} screens)",
+                            import.name, screen_count
+                        );
+                        if !import.warnings.is_empty() {
+                            status.push_str(&format!(", {} warnings", import.warnings.len()));
+                        }
+                        self.menu_bar.set_status(status);
+                        tracing::info!("📐 Imported calibration from {}", path.display());
+                    }
+                    Err(e) => {
+                        self.menu_bar.set_status(format!("Failed to import calibration: {}", e));
+                        tracing::error!("Failed to import calibration {}: {}", path.display(), e);
+                    }
+                }
+            }
+            AdvancedOutputAction::ImportMpcdi { path } => {
+                match self.output_preset_manager.import_mpcdi(&path) {
+                    Ok(import) => {
+                        for warning in &import.warnings {
+                            tracing::warn!("MPCDI import: {}", warning);
+                        }
+
+                        // Regions covering the whole buffer get shrunk to pixel-accurate
+                        // input rects on add; restore the region rects by screen name
+                        let region_rects: std::collections::HashMap<_, _> = import
+                            .screens
+                            .iter()
+                            .map(|s| {
+                                let rects: Vec<_> = s.slices.iter().map(|slice| slice.input_rect).collect();
+                                (s.name.clone(), rects)
+                            })
+                            .collect();
+                        let screen_count = import.screens.len();
+                        self.apply_output_preset_screens(import.screens);
+                        if let Some(manager) = self.output_manager.as_mut() {
+                            let screen_ids: Vec<_> = manager.screens().map(|s| s.id).collect();
+                            for screen_id in &screen_ids {
+                                if let Some(screen) = manager.get_screen_mut(*screen_id) {
+                                    if let Some(rects) = region_rects.get(&screen.name) {
+                                        for (slice, rect) in screen.slices.iter_mut().zip(rects) {
+                                            slice.input_rect = *rect;
+                                        }
+                                    }
+                                }
+                            }
+                            let target_fps = self.settings.target_fps as f32;
+                            let tokio_handle = self.tokio_runtime.as_ref().map(|rt| rt.handle());
+                            for screen_id in screen_ids {
+                                manager.sync_runtime(&self.device, screen_id, target_fps, tokio_handle);
+                            }
+                        }
+
+                        self.advanced_output_window.set_current_preset(Some(import.name.clone()));
+                        self.advanced_output_window.clear_dirty();
+                        let mut status = format!(
+                            "Imported MPCDI '{}' ({} screens)",
+                            import.name, screen_count
+                        );
+                        if !import.warnings.is_empty() {
+                            status.push_str(&format!(", {} warnings", import.warnings.len()));
+                        }
+                        self.menu_bar.set_status(status);
+                        tracing::info!("📐 Imported MPCDI from {}", path.display());
+                    }
+                    Err(e) => {
+                        self.menu_bar.set_status(format!("Failed to import MPCDI: {}", e));
+                        tracing::error!("Failed to import MPCDI {}: {}", path.display(), e);
+                    }
+                }
+            }
+            AdvancedOutputAction::ExportMpcdi { path } => {
+                let Some(manager) = &self.output_manager else {
+                    self.menu_bar.set_status("No output configuration to export");
+                    return;
+                };
+                let screens: Vec<_> = manager.screens().cloned().collect();
+                let buffer_size = (self.environment.width(), self.environment.height());
+                match crate::output::export_mpcdi(&screens, &path, buffer_size) {
+                    Ok(warnings) => {
+                        for warning in &warnings {
+                            tracing::warn!("MPCDI export: {}", warning);
+                        }
+                        self.menu_bar.set_status(format!("Exported MPCDI to {}", path.display()));
+                        tracing::info!("Exported MPCDI to {}", path.display());
+                    }
+                    Err(e) => {
+                        self.menu_bar.set_status(format!("Failed to export MPCDI: {}", e));
+                        tracing::error!("Failed to export MPCDI {}: {}", path.display(), e);
+                    }
+                }
+            }
         }
     }
 
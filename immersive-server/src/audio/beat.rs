@@ -0,0 +1,529 @@
+//! Onset detection and tempo estimation for audio-driven BPM sync
+//!
+//! Onsets are detected from the spectral flux of short overlapping frames,
+//! measured over third-octave bands so broadband hi-hats don't drown out
+//! kicks and bass. The flux history doubles as the onset strength envelope
+//! used for tempo estimation: its autocorrelation gives the beat period, and
+//! a comb filter over the most recent beats gives the beat phase.
+
+use rustfft::{num_complex::Complex, Fft, FftPlanner};
+use std::collections::VecDeque;
+use std::ops::Range;
+use std::sync::Arc;
+
+/// Lowest band edge for the spectral flux (Hz)
+const MIN_BAND_HZ: f32 = 30.0;
+
+/// Seconds of onset strength history kept for tempo estimation
+const HISTORY_SECS: f32 = 8.0;
+
+/// Minimum history before the first tempo estimate (seconds)
+const MIN_HISTORY_SECS: f32 = 3.0;
+
+/// How often the tempo estimate is refreshed (seconds)
+const ESTIMATE_INTERVAL_SECS: f32 = 0.5;
+
+/// Window for the adaptive onset threshold (seconds)
+const THRESHOLD_WINDOW_SECS: f32 = 1.0;
+
+/// Standard deviations above the local mean a flux peak must reach
+const THRESHOLD_K: f32 = 1.5;
+
+/// Minimum time between two onsets (seconds)
+const MIN_ONSET_INTERVAL_SECS: f32 = 0.08;
+
+/// Tempo search range in BPM
+const MIN_BPM: f32 = 60.0;
+const MAX_BPM: f32 = 200.0;
+
+/// Centre and width (in octaves) of the tempo prior
+const PRIOR_BPM: f32 = 120.0;
+const PRIOR_OCTAVES: f32 = 1.0;
+
+/// Number of past beats the phase comb looks at
+const PHASE_COMB_BEATS: usize = 8;
+
+/// Estimates closer than this ratio to the previous one are smoothed together
+const TEMPO_SMOOTHING_TOLERANCE: f32 = 0.04;
+
+/// Weight of a new estimate when smoothing
+const TEMPO_SMOOTHING: f32 = 0.3;
+
+/// Result of tempo estimation
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TempoEstimate {
+    /// Estimated tempo in beats per minute
+    pub bpm: f32,
+    /// Phase within the current beat at the latest analyzed sample (0.0-1.0)
+    pub beat_phase: f32,
+    /// How periodic the onset envelope is (0.0-1.0)
+    pub confidence: f32,
+}
+
+/// Streaming onset detector and tempo estimator
+pub struct BeatDetector {
+    /// Sample rate of the incoming audio
+    sample_rate: u32,
+    /// Samples between consecutive analysis frames
+    hop_size: usize,
+    /// Samples per analysis frame
+    frame_size: usize,
+    /// Forward FFT for the frame size
+    fft: Arc<dyn Fft<f32>>,
+    /// Hann window coefficients
+    window: Vec<f32>,
+    /// FFT scratch buffer
+    spectrum: Vec<Complex<f32>>,
+    /// FFT bin ranges of the third-octave bands
+    bands: Vec<Range<usize>>,
+    /// Log band magnitudes of the previous frame
+    prev_log_magnitudes: Vec<f32>,
+    /// Mono samples not yet consumed by a full frame
+    pending: Vec<f32>,
+    /// Total mono samples received
+    samples_received: u64,
+    /// Frames analyzed so far
+    frames_analyzed: u64,
+    /// Spectral flux per frame (the onset strength envelope)
+    flux_history: VecDeque<f32>,
+    /// Frames since the last detected onset
+    frames_since_onset: usize,
+    /// Number of onsets detected so far
+    onset_count: u64,
+    /// Normalized strength of the last onset (0.0-1.0)
+    onset_strength: f32,
+    /// Frames since the tempo estimate was refreshed
+    frames_since_estimate: usize,
+    /// Current tempo (BPM) and confidence
+    tempo: Option<(f32, f32)>,
+    /// Sample position of the most recent beat
+    last_beat_sample: f64,
+}
+
+impl BeatDetector {
+    /// Create a new beat detector
+    pub fn new(sample_rate: u32) -> Self {
+        let sample_rate = sample_rate.max(1000);
+        // ~10ms hop regardless of sample rate, 50% frame overlap
+        let hop_size = (sample_rate as usize / 100).next_power_of_two();
+        let frame_size = hop_size * 2;
+
+        let fft = FftPlanner::new().plan_fft_forward(frame_size);
+        let window = (0..frame_size)
+            .map(|i| 0.5 * (1.0 - (2.0 * std::f32::consts::PI * i as f32 / frame_size as f32).cos()))
+            .collect();
+        let bands = third_octave_bands(sample_rate, frame_size);
+
+        Self {
+            sample_rate,
+            hop_size,
+            frame_size,
+            fft,
+            window,
+            spectrum: vec![Complex::new(0.0, 0.0); frame_size],
+            prev_log_magnitudes: vec![0.0; bands.len()],
+            bands,
+            pending: Vec::with_capacity(frame_size * 4),
+            samples_received: 0,
+            frames_analyzed: 0,
+            flux_history: VecDeque::new(),
+            frames_since_onset: usize::MAX,
+            onset_count: 0,
+            onset_strength: 0.0,
+            frames_since_estimate: 0,
+            tempo: None,
+            last_beat_sample: 0.0,
+        }
+    }
+
+    /// Get the sample rate the detector is configured for
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    /// Change the sample rate (discards all history if it differs)
+    pub fn set_sample_rate(&mut self, sample_rate: u32) {
+        if sample_rate != self.sample_rate {
+            *self = Self::new(sample_rate);
+        }
+    }
+
+    /// Discard all history and estimates
+    pub fn reset(&mut self) {
+        *self = Self::new(self.sample_rate);
+    }
+
+    /// Feed mono samples (any chunk size)
+    pub fn process(&mut self, samples: &[f32]) {
+        self.samples_received += samples.len() as u64;
+        self.pending.extend_from_slice(samples);
+
+        let mut consumed = 0;
+        while self.pending.len() - consumed >= self.frame_size {
+            self.analyze_frame(consumed);
+            consumed += self.hop_size;
+        }
+        self.pending.drain(..consumed);
+    }
+
+    /// Number of onsets detected so far (increments on every onset)
+    pub fn onset_count(&self) -> u64 {
+        self.onset_count
+    }
+
+    /// Normalized strength of the most recent onset (0.0-1.0)
+    pub fn onset_strength(&self) -> f32 {
+        self.onset_strength
+    }
+
+    /// Current tempo estimate, with the beat phase extrapolated to the latest sample
+    pub fn estimate(&self) -> Option<TempoEstimate> {
+        let (bpm, confidence) = self.tempo?;
+        let beat_samples = 60.0 * self.sample_rate as f64 / bpm as f64;
+        let elapsed = self.samples_received as f64 - self.last_beat_sample;
+        Some(TempoEstimate {
+            bpm,
+            beat_phase: (elapsed / beat_samples).rem_euclid(1.0) as f32,
+            confidence,
+        })
+    }
+
+    /// Frames of onset strength per second
+    fn frame_rate(&self) -> f32 {
+        self.sample_rate as f32 / self.hop_size as f32
+    }
+
+    /// Sample position an onset in the given frame is attributed to
+    fn frame_time(&self, frame: u64) -> f64 {
+        (frame * self.hop_size as u64) as f64 + self.frame_size as f64 * 0.5
+    }
+
+    /// Analyze the frame starting at `offset` in the pending buffer
+    fn analyze_frame(&mut self, offset: usize) {
+        let frame = &self.pending[offset..offset + self.frame_size];
+        for (i, (&sample, &w)) in frame.iter().zip(&self.window).enumerate() {
+            self.spectrum[i] = Complex::new(sample * w, 0.0);
+        }
+        self.fft.process(&mut self.spectrum);
+
+        // Half-wave rectified difference of log-compressed band magnitudes
+        let mut flux = 0.0;
+        for (band, prev) in self.bands.iter().zip(self.prev_log_magnitudes.iter_mut()) {
+            let power = self.spectrum[band.clone()].iter().map(|c| c.norm_sqr()).sum::<f32>()
+                / band.len() as f32;
+            let log_mag = (1.0 + 100.0 * power.sqrt()).ln();
+            flux += (log_mag - *prev).max(0.0);
+            *prev = log_mag;
+        }
+        flux /= self.bands.len().max(1) as f32;
+
+        // The first frame has no predecessor to compare against
+        if self.frames_analyzed == 0 {
+            flux = 0.0;
+        }
+
+        self.push_flux(flux);
+        self.frames_analyzed += 1;
+
+        self.frames_since_estimate += 1;
+        let history_secs = self.flux_history.len() as f32 / self.frame_rate();
+        if history_secs >= MIN_HISTORY_SECS
+            && self.frames_since_estimate as f32 >= ESTIMATE_INTERVAL_SECS * self.frame_rate()
+        {
+            self.frames_since_estimate = 0;
+            self.update_tempo();
+        }
+    }
+
+    /// Append a flux value and run peak picking on the previous frame
+    fn push_flux(&mut self, flux: f32) {
+        let capacity = (HISTORY_SECS * self.frame_rate()) as usize;
+        if self.flux_history.len() >= capacity {
+            self.flux_history.pop_front();
+        }
+        self.flux_history.push_back(flux);
+        self.frames_since_onset = self.frames_since_onset.saturating_add(1);
+
+        // A peak at frame n-1 is confirmed once frame n is lower
+        let len = self.flux_history.len();
+        if len < 3 {
+            return;
+        }
+        let candidate = self.flux_history[len - 2];
+        if candidate <= self.flux_history[len - 3] || candidate < self.flux_history[len - 1] {
+            return;
+        }
+
+        let window = ((THRESHOLD_WINDOW_SECS * self.frame_rate()) as usize).min(len);
+        let recent = self.flux_history.range(len - window..);
+        let mean = recent.clone().sum::<f32>() / window as f32;
+        let variance = recent.map(|f| (f - mean) * (f - mean)).sum::<f32>() / window as f32;
+        let std_dev = variance.sqrt();
+
+        let min_interval = (MIN_ONSET_INTERVAL_SECS * self.frame_rate()) as usize;
+        // Absolute floor keeps background noise from triggering in silence
+        if candidate > mean + THRESHOLD_K * std_dev
+            && candidate > 1e-3
+            && self.frames_since_onset > min_interval
+        {
+            self.onset_count += 1;
+            self.onset_strength = ((candidate - mean) / (4.0 * std_dev + f32::EPSILON)).clamp(0.0, 1.0);
+            self.frames_since_onset = 0;
+        }
+    }
+
+    /// Re-estimate tempo and beat phase from the onset strength history
+    fn update_tempo(&mut self) {
+        let frame_rate = self.frame_rate();
+        let envelope: Vec<f32> = self.flux_history.iter().copied().collect();
+        let n = envelope.len();
+        let mean = envelope.iter().sum::<f32>() / n as f32;
+        let centered: Vec<f32> = envelope.iter().map(|f| f - mean).collect();
+
+        let autocorrelation = |lag: usize| -> f32 {
+            centered[..n - lag]
+                .iter()
+                .zip(&centered[lag..])
+                .map(|(a, b)| a * b)
+                .sum::<f32>()
+                / (n - lag) as f32
+        };
+
+        let energy = autocorrelation(0);
+        if energy <= f32::EPSILON {
+            self.tempo = None;
+            return;
+        }
+
+        let min_lag = (60.0 * frame_rate / MAX_BPM).floor().max(1.0) as usize;
+        let max_lag = ((60.0 * frame_rate / MIN_BPM).ceil() as usize).min(n / 2);
+        if min_lag + 2 > max_lag {
+            return;
+        }
+
+        // Autocorrelation weighted by a log-Gaussian tempo prior
+        let scores: Vec<f32> = (min_lag - 1..=max_lag + 1)
+            .map(|lag| {
+                let bpm = 60.0 * frame_rate / lag as f32;
+                let octaves = (bpm / PRIOR_BPM).log2() / PRIOR_OCTAVES;
+                autocorrelation(lag) * (-0.5 * octaves * octaves).exp()
+            })
+            .collect();
+
+        let (best_index, _) = scores[1..scores.len() - 1]
+            .iter()
+            .enumerate()
+            .fold((0, f32::MIN), |best, (i, &s)| if s > best.1 { (i, s) } else { best });
+        let best_index = best_index + 1;
+        let best_lag = min_lag - 1 + best_index;
+
+        // Parabolic interpolation for a sub-frame period
+        let (a, b, c) = (scores[best_index - 1], scores[best_index], scores[best_index + 1]);
+        let denominator = a - 2.0 * b + c;
+        let shift = if denominator.abs() > f32::EPSILON {
+            (0.5 * (a - c) / denominator).clamp(-0.5, 0.5)
+        } else {
+            0.0
+        };
+        let period = best_lag as f32 + shift;
+
+        let confidence = (autocorrelation(best_lag) / energy).clamp(0.0, 1.0);
+        let mut bpm = 60.0 * frame_rate / period;
+
+        if let Some((previous, _)) = self.tempo {
+            if ((bpm - previous) / previous).abs() < TEMPO_SMOOTHING_TOLERANCE {
+                bpm = previous + (bpm - previous) * TEMPO_SMOOTHING;
+            }
+        }
+
+        // Comb filter over the last few beats to find the beat offset
+        let period = 60.0 * frame_rate / bpm;
+        let beats = PHASE_COMB_BEATS.min(((n as f32 - 1.0) / period) as usize).max(1);
+        let best_offset = (0..period.ceil() as usize)
+            .map(|offset| {
+                let score: f32 = (0..beats)
+                    .filter_map(|k| {
+                        let back = offset + (k as f32 * period).round() as usize;
+                        (back < n).then(|| envelope[n - 1 - back])
+                    })
+                    .sum();
+                (offset, score)
+            })
+            .fold((0, f32::MIN), |best, (offset, s)| if s > best.1 { (offset, s) } else { best })
+            .0;
+
+        let last_frame = self.frames_analyzed - 1 - best_offset as u64;
+        self.last_beat_sample = self.frame_time(last_frame);
+        self.tempo = Some((bpm, confidence));
+    }
+}
+
+/// Split the spectrum into third-octave bands of FFT bins
+///
+/// Bands narrower than one bin are merged into the next band up.
+fn third_octave_bands(sample_rate: u32, frame_size: usize) -> Vec<Range<usize>> {
+    let bin_hz = sample_rate as f32 / frame_size as f32;
+    let nyquist_bin = frame_size / 2;
+    let mut bands = Vec::new();
+    let mut start = ((MIN_BAND_HZ / bin_hz).round() as usize).max(1);
+    let mut upper_hz = MIN_BAND_HZ;
+    while start < nyquist_bin {
+        upper_hz *= 2f32.powf(1.0 / 3.0);
+        let end = ((upper_hz / bin_hz).round() as usize).min(nyquist_bin);
+        if end > start {
+            bands.push(start..end);
+            start = end;
+        }
+    }
+    bands
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Decode a 16-bit WAV fixture from tests/fixtures/audio (synthetic, see its README)
+    fn load_fixture(name: &str) -> (Vec<f32>, u32) {
+        let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures/audio")
+            .join(name);
+        let mut reader = hound::WavReader::open(&path).expect("open fixture");
+        let spec = reader.spec();
+        let channels = spec.channels as usize;
+        let samples: Vec<f32> = reader
+            .samples::<i16>()
+            .map(|s| s.expect("read sample") as f32 / i16::MAX as f32)
+            .collect();
+        let mono = samples
+            .chunks(channels)
+            .map(|frame| frame.iter().sum::<f32>() / channels as f32)
+            .collect();
+        (mono, spec.sample_rate)
+    }
+
+    /// Stream a signal through a detector in small chunks like a live source
+    fn run(samples: &[f32], sample_rate: u32) -> BeatDetector {
+        let mut detector = BeatDetector::new(sample_rate);
+        for chunk in samples.chunks(735) {
+            detector.process(chunk);
+        }
+        detector
+    }
+
+    /// Expected phase at the end of a fixture whose first beat is at `offset` seconds
+    fn expected_phase(samples: usize, sample_rate: u32, bpm: f32, offset: f32) -> f32 {
+        let t = samples as f32 / sample_rate as f32 - offset;
+        (t * bpm / 60.0).rem_euclid(1.0)
+    }
+
+    fn phase_error(a: f32, b: f32) -> f32 {
+        let d = (a - b).rem_euclid(1.0);
+        d.min(1.0 - d)
+    }
+
+    fn assert_fixture(name: &str, bpm: f32, offset: f32) {
+        let (samples, sample_rate) = load_fixture(name);
+        let detector = run(&samples, sample_rate);
+        let estimate = detector.estimate().expect("tempo estimate");
+
+        assert!(
+            (estimate.bpm - bpm).abs() < 1.5,
+            "{}: estimated {:.2} BPM, expected {}",
+            name, estimate.bpm, bpm
+        );
+        let expected = expected_phase(samples.len(), sample_rate, bpm, offset);
+        assert!(
+            phase_error(estimate.beat_phase, expected) < 0.1,
+            "{}: beat phase {:.3}, expected {:.3}",
+            name, estimate.beat_phase, expected
+        );
+        assert!(estimate.confidence > 0.5, "{}: confidence {:.3}", name, estimate.confidence);
+
+        // At least one onset per beat
+        let beats = ((samples.len() as f32 / sample_rate as f32 - offset) * bpm / 60.0) as u64;
+        assert!(
+            detector.onset_count() >= beats,
+            "{}: {} onsets for {} beats",
+            name, detector.onset_count(), beats
+        );
+    }
+
+    #[test]
+    fn test_hop_size_scales_with_sample_rate() {
+        assert_eq!(BeatDetector::new(48000).hop_size, 512);
+        assert_eq!(BeatDetector::new(44100).hop_size, 512);
+        assert_eq!(BeatDetector::new(22050).hop_size, 256);
+    }
+
+    #[test]
+    fn test_silence_has_no_onsets_or_tempo() {
+        let detector = run(&vec![0.0; 48000 * 5], 48000);
+        assert_eq!(detector.onset_count(), 0);
+        assert!(detector.estimate().is_none());
+    }
+
+    #[test]
+    fn test_noise_has_low_confidence() {
+        // Deterministic white noise from a linear congruential generator
+        let mut seed: u32 = 12345;
+        let samples: Vec<f32> = (0..48000 * 6)
+            .map(|_| {
+                seed = seed.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+                (seed >> 8) as f32 / (1 << 24) as f32 * 0.5 - 0.25
+            })
+            .collect();
+        let detector = run(&samples, 48000);
+        if let Some(estimate) = detector.estimate() {
+            assert!(estimate.confidence < 0.3, "noise confidence {:.3}", estimate.confidence);
+        }
+    }
+
+    #[test]
+    fn test_click_train_onsets() {
+        // 10 clicks, 0.5s apart
+        let sample_rate = 48000;
+        let mut samples = vec![0.0; sample_rate as usize * 5 + 4800];
+        for click in 0..10 {
+            let start = 4800 + click * sample_rate as usize / 2;
+            for i in 0..200 {
+                samples[start + i] = (1.0 - i as f32 / 200.0) * if i % 2 == 0 { 0.8 } else { -0.8 };
+            }
+        }
+        let detector = run(&samples, sample_rate);
+        assert_eq!(detector.onset_count(), 10);
+        assert!(detector.onset_strength() > 0.0);
+    }
+
+    #[test]
+    fn test_set_sample_rate_resets() {
+        let (samples, sample_rate) = load_fixture("four_on_floor_120bpm.wav");
+        let mut detector = run(&samples, sample_rate);
+        assert!(detector.estimate().is_some());
+
+        detector.set_sample_rate(sample_rate);
+        assert!(detector.estimate().is_some(), "same rate keeps history");
+
+        detector.set_sample_rate(48000);
+        assert!(detector.estimate().is_none());
+        assert_eq!(detector.onset_count(), 0);
+    }
+
+    #[test]
+    fn test_fixture_four_on_floor_120bpm() {
+        assert_fixture("four_on_floor_120bpm.wav", 120.0, 0.25);
+    }
+
+    #[test]
+    fn test_fixture_backbeat_128bpm() {
+        assert_fixture("backbeat_128bpm.wav", 128.0, 0.1);
+    }
+
+    #[test]
+    fn test_fixture_boombap_90bpm() {
+        assert_fixture("boombap_90bpm.wav", 90.0, 0.4);
+    }
+}
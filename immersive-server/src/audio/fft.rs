@@ -1,6 +1,13 @@
 //! FFT analysis for audio frequency band extraction
-
-use super::types::{AudioBand, AudioBuffer, FftData};
+//!
+//! Besides the fixed Low/Mid/High/Full bands, the analyzer produces N
+//! log-spaced bands with per-band attack/release, a smoothed log-frequency
+//! spectrum and a decimated waveform for visualizers.
+
+use super::types::{
+    log_band_range, log_frequency, AudioBand, AudioBuffer, BandEnvelope, FftData, DEFAULT_BAND_COUNT,
+    MAX_BAND_COUNT, SPECTRUM_SIZE, WAVEFORM_SIZE,
+};
 use rustfft::{num_complex::Complex, FftPlanner};
 use std::time::Instant;
 
@@ -10,6 +17,9 @@ const FFT_SIZE: usize = 2048;
 /// Default smoothing factor (0 = no smoothing, 1 = full smoothing)
 const DEFAULT_SMOOTHING: f32 = 0.0;
 
+/// Dynamic range of the normalized spectrum (dB below full scale)
+const SPECTRUM_DB_RANGE: f32 = 72.0;
+
 /// FFT analyzer with frequency band extraction
 pub struct FftAnalyzer {
     /// FFT planner (reusable)
@@ -26,6 +36,10 @@ pub struct FftAnalyzer {
     magnitudes: Vec<f32>,
     /// Smoothing factor
     smoothing: f32,
+    /// Attack/release per log-spaced band (length = band count)
+    band_envelopes: Vec<BandEnvelope>,
+    /// Attack/release applied to the spectrum bins
+    spectrum_envelope: BandEnvelope,
 }
 
 impl FftAnalyzer {
@@ -46,9 +60,39 @@ impl FftAnalyzer {
             prev_data: FftData::new(),
             magnitudes: vec![0.0; FFT_SIZE / 2],
             smoothing: DEFAULT_SMOOTHING,
+            band_envelopes: vec![BandEnvelope::default(); DEFAULT_BAND_COUNT],
+            spectrum_envelope: BandEnvelope::default(),
+        }
+    }
+
+    /// Set the number of log-spaced bands (1 to 64)
+    ///
+    /// New bands get the default envelope; existing band levels are reset.
+    pub fn set_band_count(&mut self, count: usize) {
+        let count = count.clamp(1, MAX_BAND_COUNT);
+        if count != self.band_envelopes.len() {
+            self.band_envelopes.resize(count, BandEnvelope::default());
+            self.prev_data.bands.clear();
+        }
+    }
+
+    /// Get the number of log-spaced bands
+    pub fn band_count(&self) -> usize {
+        self.band_envelopes.len()
+    }
+
+    /// Set the attack/release of a single log-spaced band
+    pub fn set_band_envelope(&mut self, index: usize, envelope: BandEnvelope) {
+        if let Some(env) = self.band_envelopes.get_mut(index) {
+            *env = envelope;
         }
     }
 
+    /// Get the attack/release of a log-spaced band
+    pub fn band_envelope(&self, index: usize) -> Option<BandEnvelope> {
+        self.band_envelopes.get(index).copied()
+    }
+
     /// Set sample rate (if source changes)
     pub fn set_sample_rate(&mut self, sample_rate: u32) {
         self.sample_rate = sample_rate;
@@ -66,6 +110,14 @@ impl FftAnalyzer {
 
     /// Analyze audio buffer and return FFT band data
     pub fn analyze(&mut self, buffer: &AudioBuffer) -> FftData {
+        self.analyze_at(buffer, Instant::now())
+    }
+
+    /// Analyze a buffer captured at `timestamp`
+    ///
+    /// Band and spectrum envelopes advance by the time since the previous
+    /// analysis.
+    fn analyze_at(&mut self, buffer: &AudioBuffer, timestamp: Instant) -> FftData {
         // Downmix to mono
         let mono = buffer.to_mono();
 
@@ -78,6 +130,14 @@ impl FftAnalyzer {
         let start = mono.len().saturating_sub(FFT_SIZE);
         let samples = &mono[start..start + FFT_SIZE];
 
+        // Decimated waveform snapshot (before windowing)
+        let waveform: Vec<f32> = samples
+            .iter()
+            .step_by(FFT_SIZE / WAVEFORM_SIZE)
+            .take(WAVEFORM_SIZE)
+            .copied()
+            .collect();
+
         // Apply window and copy to input buffer
         for (i, &sample) in samples.iter().enumerate() {
             self.input_buffer[i] = Complex::new(sample * self.window[i], 0.0);
@@ -106,21 +166,38 @@ impl FftAnalyzer {
         // Microphone input has low amplitude, so use gentle normalization
         let normalize = |v: f32| (v / 4.0).min(1.0);
 
-        let raw_data = FftData {
-            low: normalize(low),
-            mid: normalize(mid),
-            high: normalize(high),
-            full: normalize(full),
-            timestamp: Instant::now(),
-        };
+        let delta_time = timestamp.duration_since(self.prev_data.timestamp).as_secs_f32();
+
+        // Log-spaced bands, each with its own attack/release
+        let band_count = self.band_envelopes.len();
+        let bands: Vec<f32> = (0..band_count)
+            .map(|i| {
+                let (min_freq, max_freq) = log_band_range(i, band_count);
+                let target = normalize(self.calculate_range_energy(min_freq, max_freq));
+                let current = self.prev_data.bands.get(i).copied().unwrap_or(0.0);
+                self.band_envelopes[i].apply(current, target, delta_time)
+            })
+            .collect();
+
+        // Log-frequency spectrum in dB
+        let spectrum: Vec<f32> = (0..SPECTRUM_SIZE)
+            .map(|i| {
+                let target = self.spectrum_bin_level(i);
+                let current = self.prev_data.spectrum.get(i).copied().unwrap_or(0.0);
+                self.spectrum_envelope.apply(current, target, delta_time)
+            })
+            .collect();
 
         // Apply smoothing
         let smoothed = FftData {
-            low: lerp(self.prev_data.low, raw_data.low, 1.0 - self.smoothing),
-            mid: lerp(self.prev_data.mid, raw_data.mid, 1.0 - self.smoothing),
-            high: lerp(self.prev_data.high, raw_data.high, 1.0 - self.smoothing),
-            full: lerp(self.prev_data.full, raw_data.full, 1.0 - self.smoothing),
-            timestamp: raw_data.timestamp,
+            low: lerp(self.prev_data.low, normalize(low), 1.0 - self.smoothing),
+            mid: lerp(self.prev_data.mid, normalize(mid), 1.0 - self.smoothing),
+            high: lerp(self.prev_data.high, normalize(high), 1.0 - self.smoothing),
+            full: lerp(self.prev_data.full, normalize(full), 1.0 - self.smoothing),
+            bands,
+            spectrum,
+            waveform,
+            timestamp,
         };
 
         self.prev_data = smoothed.clone();
@@ -148,6 +225,53 @@ impl FftAnalyzer {
         (sum / (max_bin - min_bin + 1) as f32).sqrt()
     }
 
+    /// Calculate RMS energy between two frequencies (at least one bin)
+    fn calculate_range_energy(&self, min_freq: f32, max_freq: f32) -> f32 {
+        let bin_width = self.sample_rate as f32 / FFT_SIZE as f32;
+        let last_bin = self.magnitudes.len() - 1;
+
+        let min_bin = (min_freq / bin_width).round() as usize;
+        if min_bin > last_bin {
+            // Above Nyquist for this sample rate
+            return 0.0;
+        }
+        let max_bin = ((max_freq / bin_width).round() as usize).clamp(min_bin, last_bin);
+
+        let sum: f32 = self.magnitudes[min_bin..=max_bin]
+            .iter()
+            .map(|m| m * m)
+            .sum();
+
+        (sum / (max_bin - min_bin + 1) as f32).sqrt()
+    }
+
+    /// Normalized dB level (0-1) of a log-frequency spectrum bin
+    ///
+    /// Takes the peak FFT magnitude between the bin's edges so narrow
+    /// tones stay visible at high frequencies where bins are wide.
+    fn spectrum_bin_level(&self, index: usize) -> f32 {
+        let bin_width = self.sample_rate as f32 / FFT_SIZE as f32;
+        let last_bin = self.magnitudes.len() - 1;
+        let step = 1.0 / (SPECTRUM_SIZE - 1) as f32;
+
+        let center = log_frequency(index as f32 * step);
+        let min_bin = (log_frequency((index as f32 - 0.5) * step) / bin_width).round() as usize;
+        if (center / bin_width).round() as usize > last_bin {
+            return 0.0;
+        }
+        let max_bin = ((log_frequency((index as f32 + 0.5) * step) / bin_width).round() as usize)
+            .clamp(min_bin.min(last_bin), last_bin);
+        let min_bin = min_bin.min(max_bin);
+
+        let peak = self.magnitudes[min_bin..=max_bin]
+            .iter()
+            .fold(0.0f32, |a, &b| a.max(b));
+
+        // A full-scale sine peaks at FFT_SIZE / 4 with a Hann window
+        let db = 20.0 * (peak / (FFT_SIZE as f32 / 4.0)).max(1e-9).log10();
+        ((db + SPECTRUM_DB_RANGE) / SPECTRUM_DB_RANGE).clamp(0.0, 1.0)
+    }
+
     /// Get the raw magnitudes from the last analysis (for visualization)
     pub fn magnitudes(&self) -> &[f32] {
         &self.magnitudes
@@ -227,4 +351,95 @@ mod tests {
         // Mid and high should be lower
         assert!(fft.mid < fft.low);
     }
+
+    fn sine_buffer(frequency: f32, amplitude: f32) -> AudioBuffer {
+        AudioBuffer {
+            samples: (0..4096)
+                .map(|i| amplitude * (2.0 * std::f32::consts::PI * frequency * i as f32 / 48000.0).sin())
+                .collect(),
+            sample_rate: 48000,
+            channels: 1,
+        }
+    }
+
+    #[test]
+    fn test_log_band_ranges_are_contiguous() {
+        for count in [8, 16, 32] {
+            let (first, _) = log_band_range(0, count);
+            let (_, last) = log_band_range(count - 1, count);
+            assert!((first - 20.0).abs() < 1e-3);
+            assert!((last - 20000.0).abs() < 1.0);
+            for i in 1..count {
+                assert!((log_band_range(i - 1, count).1 - log_band_range(i, count).0).abs() < 1e-3);
+            }
+        }
+    }
+
+    #[test]
+    fn test_band_count() {
+        let mut analyzer = FftAnalyzer::new(48000);
+        assert_eq!(analyzer.band_count(), DEFAULT_BAND_COUNT);
+
+        analyzer.set_band_count(32);
+        let fft = analyzer.analyze(&sine_buffer(1000.0, 0.5));
+        assert_eq!(fft.bands.len(), 32);
+
+        analyzer.set_band_count(1000);
+        assert_eq!(analyzer.band_count(), MAX_BAND_COUNT);
+    }
+
+    #[test]
+    fn test_sine_peaks_in_matching_band() {
+        let mut analyzer = FftAnalyzer::new(48000);
+        analyzer.set_band_count(8);
+        for i in 0..8 {
+            // Instant response so a single analysis reaches the target
+            analyzer.set_band_envelope(i, BandEnvelope { attack_ms: 0.0, release_ms: 0.0 });
+        }
+
+        let fft = analyzer.analyze(&sine_buffer(1000.0, 0.5));
+        let expected = (0..8)
+            .find(|&i| {
+                let (lo, hi) = log_band_range(i, 8);
+                (lo..hi).contains(&1000.0)
+            })
+            .unwrap();
+        let loudest = (0..8)
+            .max_by(|&a, &b| fft.bands[a].total_cmp(&fft.bands[b]))
+            .unwrap();
+        assert_eq!(loudest, expected);
+    }
+
+    #[test]
+    fn test_spectrum_and_waveform() {
+        let mut analyzer = FftAnalyzer::new(48000);
+        let fft = analyzer.analyze(&sine_buffer(1000.0, 1.0));
+        assert_eq!(fft.spectrum.len(), SPECTRUM_SIZE);
+        assert_eq!(fft.waveform.len(), WAVEFORM_SIZE);
+        assert!(fft.waveform.iter().all(|s| s.abs() <= 1.0));
+
+        // The default attack is 10ms, so let the spectrum settle over 100ms
+        let mut fft = fft;
+        for _ in 0..5 {
+            let timestamp = fft.timestamp + std::time::Duration::from_millis(20);
+            fft = analyzer.analyze_at(&sine_buffer(1000.0, 1.0), timestamp);
+        }
+        let around_tone = fft.get_frequency_range(900.0, 1100.0);
+        let far_away = fft.get_frequency_range(5000.0, 8000.0);
+        assert!(around_tone > 0.6, "tone level {}", around_tone);
+        assert!(far_away < around_tone * 0.5, "far level {}", far_away);
+    }
+
+    #[test]
+    fn test_band_envelope_attack_release() {
+        let env = BandEnvelope { attack_ms: 100.0, release_ms: 1000.0 };
+        // One time constant reaches ~63% of the step
+        let up = env.apply(0.0, 1.0, 0.1);
+        assert!((up - 0.632).abs() < 0.01);
+        let down = env.apply(1.0, 0.0, 0.1);
+        assert!((down - 0.905).abs() < 0.01);
+
+        let instant = BandEnvelope { attack_ms: 0.0, release_ms: 0.0 };
+        assert_eq!(instant.apply(0.2, 0.7, 0.016), 0.7);
+    }
 }
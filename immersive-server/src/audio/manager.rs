@@ -1,9 +1,10 @@
 //! Central audio manager coordinating all audio sources and FFT analysis
 
+use super::beat::{BeatDetector, TempoEstimate};
 use super::fft::FftAnalyzer;
 use super::source::{AudioSource, AudioSourceState};
 use super::system_input::SystemAudioInput;
-use super::types::{AudioBand, AudioSourceId, FftData};
+use super::types::{AudioBand, AudioSourceId, BandEnvelope, FftData, DEFAULT_BAND_COUNT, MAX_BAND_COUNT};
 use std::collections::HashMap;
 use std::fs::File;
 use std::io::Write;
@@ -20,12 +21,20 @@ pub struct AudioManager {
     fft_data: Arc<RwLock<HashMap<AudioSourceId, FftData>>>,
     /// Currently selected primary source for automation
     primary_source: Option<AudioSourceId>,
+    /// Onset and tempo detection on the primary source
+    beat_detector: BeatDetector,
+    /// Source the beat detector's history belongs to
+    beat_detector_source: Option<AudioSourceId>,
+    /// When the beat detector last received samples
+    beat_detector_updated: Instant,
     /// Last update time
     last_update: Instant,
     /// Master sensitivity (0.0 - 2.0)
     master_sensitivity: f32,
     /// Per-band sensitivity multipliers
     band_sensitivity: HashMap<AudioBand, f32>,
+    /// Attack/release per log-spaced band (length = band count)
+    band_envelopes: Vec<BandEnvelope>,
     /// Whether system audio is initialized
     system_audio_initialized: bool,
     /// Debug log file for FFT values
@@ -61,9 +70,13 @@ impl AudioManager {
             analyzers: HashMap::new(),
             fft_data: Arc::new(RwLock::new(HashMap::new())),
             primary_source: None,
+            beat_detector: BeatDetector::new(48000),
+            beat_detector_source: None,
+            beat_detector_updated: Instant::now(),
             last_update: Instant::now(),
             master_sensitivity: 1.0,
             band_sensitivity,
+            band_envelopes: vec![BandEnvelope::default(); DEFAULT_BAND_COUNT],
             system_audio_initialized: false,
             fft_log_file,
             last_log_time: Instant::now(),
@@ -82,9 +95,9 @@ impl AudioManager {
                 let sample_rate = input.sample_rate();
                 let id = input.id().clone();
 
+                let analyzer = self.create_analyzer(sample_rate);
                 self.sources.insert(id.clone(), Box::new(input));
-                self.analyzers
-                    .insert(id.clone(), FftAnalyzer::new(sample_rate));
+                self.analyzers.insert(id.clone(), analyzer);
 
                 // Set as primary source if none selected
                 if self.primary_source.is_none() {
@@ -102,6 +115,16 @@ impl AudioManager {
         }
     }
 
+    /// Create an FFT analyzer using the current band configuration
+    fn create_analyzer(&self, sample_rate: u32) -> FftAnalyzer {
+        let mut analyzer = FftAnalyzer::new(sample_rate);
+        analyzer.set_band_count(self.band_envelopes.len());
+        for (i, envelope) in self.band_envelopes.iter().enumerate() {
+            analyzer.set_band_envelope(i, *envelope);
+        }
+        analyzer
+    }
+
     /// Check if system audio is available
     pub fn has_system_audio(&self) -> bool {
         self.system_audio_initialized
@@ -112,8 +135,9 @@ impl AudioManager {
         let id = source.id().clone();
         let sample_rate = source.sample_rate();
 
+        let analyzer = self.create_analyzer(sample_rate);
         self.sources.insert(id.clone(), source);
-        self.analyzers.insert(id.clone(), FftAnalyzer::new(sample_rate));
+        self.analyzers.insert(id.clone(), analyzer);
 
         // Auto-select first source as primary
         if self.primary_source.is_none() {
@@ -136,6 +160,10 @@ impl AudioManager {
             self.primary_source = self.sources.keys().next().cloned();
         }
 
+        if self.beat_detector_source.as_ref() == Some(id) {
+            self.beat_detector_source = None;
+        }
+
         if id == &AudioSourceId::SystemInput {
             self.system_audio_initialized = false;
         }
@@ -200,6 +228,17 @@ impl AudioManager {
                             data.insert(id.clone(), fft);
                         }
                     }
+
+                    // Onset/tempo detection runs on the primary source only
+                    if self.primary_source.as_ref() == Some(&id) {
+                        if self.beat_detector_source.as_ref() != Some(&id) {
+                            self.beat_detector.reset();
+                            self.beat_detector_source = Some(id.clone());
+                        }
+                        self.beat_detector.set_sample_rate(buffer.sample_rate);
+                        self.beat_detector.process(&buffer.to_mono());
+                        self.beat_detector_updated = now;
+                    }
                 }
                 None => {
                     tracing::debug!("[AUDIO] Source {:?}: no samples available", id);
@@ -239,6 +278,30 @@ impl AudioManager {
         self.get_fft_data(id)
     }
 
+    /// Number of onsets detected on the primary source so far
+    ///
+    /// Increments on every detected onset; compare against a previous
+    /// value to find new onsets.
+    pub fn onset_count(&self) -> u64 {
+        self.beat_detector.onset_count()
+    }
+
+    /// Normalized strength of the most recent onset (0.0-1.0)
+    pub fn onset_strength(&self) -> f32 {
+        self.beat_detector.onset_strength()
+    }
+
+    /// Current tempo and beat phase estimated from the primary source
+    ///
+    /// The beat phase is extrapolated from the last analyzed samples to now,
+    /// since sources deliver audio in chunks.
+    pub fn tempo_estimate(&self) -> Option<TempoEstimate> {
+        let mut estimate = self.beat_detector.estimate()?;
+        let elapsed_beats = self.beat_detector_updated.elapsed().as_secs_f32() * estimate.bpm / 60.0;
+        estimate.beat_phase = (estimate.beat_phase + elapsed_beats).fract();
+        Some(estimate)
+    }
+
     /// Get a specific band value from primary source, with sensitivity applied
     pub fn get_band_value(&self, band: AudioBand) -> f32 {
         let fft = match self.get_primary_fft_data() {
@@ -252,6 +315,30 @@ impl AudioManager {
         (raw * self.master_sensitivity * band_sens).min(1.0)
     }
 
+    /// Run a closure on the primary source's FFT data without cloning it
+    pub fn with_primary_fft_data<R>(&self, f: impl FnOnce(&FftData) -> R) -> Option<R> {
+        let id = self.primary_source.as_ref()?;
+        let data = self.fft_data.read().ok()?;
+        data.get(id).map(f)
+    }
+
+    /// Get a log-spaced band value from primary source, with master sensitivity applied
+    pub fn get_band_index_value(&self, index: usize) -> f32 {
+        let raw = self
+            .with_primary_fft_data(|fft| fft.get_band_index(index))
+            .unwrap_or(0.0);
+        (raw * self.master_sensitivity).min(1.0)
+    }
+
+    /// Get the level of a custom frequency range from primary source,
+    /// with master sensitivity applied
+    pub fn get_frequency_range_value(&self, min_hz: f32, max_hz: f32) -> f32 {
+        let raw = self
+            .with_primary_fft_data(|fft| fft.get_frequency_range(min_hz, max_hz))
+            .unwrap_or(0.0);
+        (raw * self.master_sensitivity).min(1.0)
+    }
+
     /// Get a specific band value from a specific source
     pub fn get_band_value_from_source(&self, source_id: &AudioSourceId, band: AudioBand) -> f32 {
         let fft = match self.get_fft_data(source_id) {
@@ -285,6 +372,38 @@ impl AudioManager {
         self.band_sensitivity.get(&band).copied().unwrap_or(1.0)
     }
 
+    /// Set the number of log-spaced FFT bands (1 to 64)
+    pub fn set_band_count(&mut self, count: usize) {
+        let count = count.clamp(1, MAX_BAND_COUNT);
+        if count == self.band_envelopes.len() {
+            return;
+        }
+        self.band_envelopes.resize(count, BandEnvelope::default());
+        for analyzer in self.analyzers.values_mut() {
+            analyzer.set_band_count(count);
+        }
+    }
+
+    /// Get the number of log-spaced FFT bands
+    pub fn band_count(&self) -> usize {
+        self.band_envelopes.len()
+    }
+
+    /// Set the attack/release of a log-spaced band
+    pub fn set_band_envelope(&mut self, index: usize, envelope: BandEnvelope) {
+        if let Some(env) = self.band_envelopes.get_mut(index) {
+            *env = envelope;
+            for analyzer in self.analyzers.values_mut() {
+                analyzer.set_band_envelope(index, envelope);
+            }
+        }
+    }
+
+    /// Get the attack/release of a log-spaced band
+    pub fn band_envelope(&self, index: usize) -> BandEnvelope {
+        self.band_envelopes.get(index).copied().unwrap_or_default()
+    }
+
     /// Get list of all source IDs
     pub fn source_ids(&self) -> Vec<AudioSourceId> {
         self.sources.keys().cloned().collect()
@@ -351,9 +470,9 @@ impl AudioManager {
                 let sample_rate = input.sample_rate();
                 let id = input.id().clone();
 
+                let analyzer = self.create_analyzer(sample_rate);
                 self.sources.insert(id.clone(), Box::new(input));
-                self.analyzers
-                    .insert(id.clone(), FftAnalyzer::new(sample_rate));
+                self.analyzers.insert(id.clone(), analyzer);
 
                 // Set as primary source
                 self.primary_source = Some(id);
@@ -387,8 +506,9 @@ impl AudioManager {
         let id = source.id().clone();
 
         let _ = source.start();
+        let analyzer = self.create_analyzer(sample_rate);
         self.sources.insert(id.clone(), Box::new(source));
-        self.analyzers.insert(id.clone(), FftAnalyzer::new(sample_rate));
+        self.analyzers.insert(id.clone(), analyzer);
 
         // Set as primary source
         self.primary_source = Some(id);
@@ -411,8 +531,9 @@ impl AudioManager {
         let id = source.id().clone();
 
         let _ = source.start();
+        let analyzer = self.create_analyzer(sample_rate);
         self.sources.insert(id.clone(), Box::new(source));
-        self.analyzers.insert(id.clone(), FftAnalyzer::new(sample_rate));
+        self.analyzers.insert(id.clone(), analyzer);
 
         // Set as primary source
         self.primary_source = Some(id);
@@ -481,6 +602,8 @@ impl AudioManager {
 
         self.primary_source = None;
         self.system_audio_initialized = false;
+        self.beat_detector.reset();
+        self.beat_detector_source = None;
 
         tracing::debug!("[AUDIO] clear_sources() total took {:?}", total_start.elapsed());
         tracing::info!("Cleared all audio sources");
@@ -1,8 +1,10 @@
 //! Audio input and FFT analysis module
 //!
-//! Provides audio capture from multiple sources and real-time frequency
-//! band analysis for audio-reactive effects.
+//! Provides audio capture from multiple sources, real-time frequency
+//! band analysis for audio-reactive effects, and onset/tempo detection
+//! for driving the BPM clock.
 
+mod beat;
 mod fft;
 mod manager;
 mod ndi_source;
@@ -12,10 +14,14 @@ mod system_input;
 mod types;
 
 // Re-export public API
+pub use beat::{BeatDetector, TempoEstimate};
 pub use fft::FftAnalyzer;
 pub use manager::AudioManager;
 pub use ndi_source::{push_ndi_audio_to_state, NdiAudioSource};
 pub use omt_source::{push_omt_audio_to_state, OmtAudioSource};
 pub use source::{AudioRingBuffer, AudioSource, AudioSourceState, BaseAudioSource};
 pub use system_input::SystemAudioInput;
-pub use types::{AudioBand, AudioBuffer, AudioSourceId, FftData};
+pub use types::{
+    format_frequency, log_band_range, log_frequency, AudioBand, AudioBuffer, AudioSourceId, BandEnvelope, FftData,
+    ANALYSIS_MAX_HZ, ANALYSIS_MIN_HZ, DEFAULT_BAND_COUNT, MAX_BAND_COUNT, SPECTRUM_SIZE, WAVEFORM_SIZE,
+};
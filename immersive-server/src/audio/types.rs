@@ -54,6 +54,83 @@ impl AudioBand {
     }
 }
 
+/// Default number of log-spaced analysis bands
+pub const DEFAULT_BAND_COUNT: usize = 16;
+
+/// Maximum number of log-spaced analysis bands
+pub const MAX_BAND_COUNT: usize = 64;
+
+/// Number of log-spaced bins in the smoothed spectrum
+pub const SPECTRUM_SIZE: usize = 256;
+
+/// Number of samples in the waveform snapshot
+pub const WAVEFORM_SIZE: usize = 256;
+
+/// Frequency range covered by the log-spaced bands and spectrum (Hz)
+pub const ANALYSIS_MIN_HZ: f32 = 20.0;
+pub const ANALYSIS_MAX_HZ: f32 = 20000.0;
+
+/// Frequency at a normalized position (0.0-1.0) on the log analysis axis
+pub fn log_frequency(position: f32) -> f32 {
+    ANALYSIS_MIN_HZ * (ANALYSIS_MAX_HZ / ANALYSIS_MIN_HZ).powf(position)
+}
+
+/// Frequency range (min_hz, max_hz) of one of `band_count` log-spaced bands
+pub fn log_band_range(index: usize, band_count: usize) -> (f32, f32) {
+    let count = band_count.max(1) as f32;
+    (
+        log_frequency(index as f32 / count),
+        log_frequency((index + 1) as f32 / count),
+    )
+}
+
+/// Compact frequency label, e.g. "80 Hz" or "2.5k Hz"
+pub fn format_frequency(hz: f32) -> String {
+    if hz >= 1000.0 {
+        format!("{:.1}k Hz", hz / 1000.0)
+    } else {
+        format!("{:.0} Hz", hz)
+    }
+}
+
+/// Attack/release envelope for a single analysis band
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct BandEnvelope {
+    /// Attack time in ms (how fast the value rises)
+    #[serde(rename = "attackMs")]
+    pub attack_ms: f32,
+    /// Release time in ms (how fast the value falls)
+    #[serde(rename = "releaseMs")]
+    pub release_ms: f32,
+}
+
+impl Default for BandEnvelope {
+    fn default() -> Self {
+        Self {
+            attack_ms: 10.0,
+            release_ms: 150.0,
+        }
+    }
+}
+
+impl BandEnvelope {
+    /// Move `current` towards `target` over `delta_time` seconds
+    pub fn apply(&self, current: f32, target: f32, delta_time: f32) -> f32 {
+        let time_constant = if target > current {
+            self.attack_ms / 1000.0
+        } else {
+            self.release_ms / 1000.0
+        };
+
+        if time_constant > 0.001 {
+            // Exponential smoothing
+            current + (target - current) * (1.0 - (-delta_time / time_constant).exp())
+        } else {
+            target
+        }
+    }
+}
+
 /// FFT analysis results for a single audio source
 #[derive(Debug, Clone)]
 pub struct FftData {
@@ -65,19 +142,19 @@ pub struct FftData {
     pub high: f32,
     /// Combined/RMS level across all bands (0.0 - 1.0)
     pub full: f32,
+    /// Log-spaced band levels with per-band attack/release (0.0 - 1.0)
+    pub bands: Vec<f32>,
+    /// Smoothed log-frequency spectrum in dB, normalized (0.0 - 1.0)
+    pub spectrum: Vec<f32>,
+    /// Most recent mono samples, decimated (-1.0 - 1.0)
+    pub waveform: Vec<f32>,
     /// Timestamp of this analysis
     pub timestamp: Instant,
 }
 
 impl Default for FftData {
     fn default() -> Self {
-        Self {
-            low: 0.0,
-            mid: 0.0,
-            high: 0.0,
-            full: 0.0,
-            timestamp: Instant::now(),
-        }
+        Self::new()
     }
 }
 
@@ -89,10 +166,38 @@ impl FftData {
             mid: 0.0,
             high: 0.0,
             full: 0.0,
+            bands: Vec::new(),
+            spectrum: Vec::new(),
+            waveform: Vec::new(),
             timestamp: Instant::now(),
         }
     }
 
+    /// Get a log-spaced band value by index (0.0 if out of range)
+    pub fn get_band_index(&self, index: usize) -> f32 {
+        self.bands.get(index).copied().unwrap_or(0.0)
+    }
+
+    /// Get the RMS spectrum level across a custom frequency range
+    ///
+    /// Ranges narrower than one spectrum bin read the nearest bin.
+    pub fn get_frequency_range(&self, min_hz: f32, max_hz: f32) -> f32 {
+        if self.spectrum.is_empty() {
+            return 0.0;
+        }
+
+        let (min_hz, max_hz) = (min_hz.min(max_hz), min_hz.max(max_hz));
+        let last = self.spectrum.len() - 1;
+        let position = |hz: f32| {
+            (hz.max(ANALYSIS_MIN_HZ) / ANALYSIS_MIN_HZ).ln() / (ANALYSIS_MAX_HZ / ANALYSIS_MIN_HZ).ln()
+        };
+        let first_bin = ((position(min_hz) * last as f32).round() as usize).min(last);
+        let last_bin = ((position(max_hz) * last as f32).round() as usize).clamp(first_bin, last);
+
+        let bins = &self.spectrum[first_bin..=last_bin];
+        (bins.iter().map(|v| v * v).sum::<f32>() / bins.len() as f32).sqrt()
+    }
+
     /// Get band value by enum
     pub fn get_band(&self, band: AudioBand) -> f32 {
         match band {
@@ -9,6 +9,7 @@ use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 use crate::compositor::layer::Transform2D;
 use crate::effects::EffectStack;
+use crate::video::{MAX_PLAYBACK_SPEED, MIN_PLAYBACK_SPEED};
 
 /// Default number of clip slots per layer
 pub const DEFAULT_CLIP_SLOTS: usize = 8;
@@ -300,6 +301,116 @@ impl ClipTransition {
     }
 }
 
+/// Quantization applied to clip triggers, measured on the global BPM clock
+///
+/// When quantization is active, a triggered clip waits for the next boundary
+/// before switching instead of switching immediately.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TriggerQuantize {
+    /// Switch immediately (no quantization)
+    #[default]
+    Off,
+    /// Wait for the next beat
+    Beat,
+    /// Wait for the next bar
+    Bar,
+    /// Wait for the next multiple of N bars
+    Bars(u32),
+}
+
+// Custom serialization as simple strings: "Off", "Beat", "Bar" or "Bars:4"
+impl Serialize for TriggerQuantize {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            TriggerQuantize::Off => serializer.serialize_str("Off"),
+            TriggerQuantize::Beat => serializer.serialize_str("Beat"),
+            TriggerQuantize::Bar => serializer.serialize_str("Bar"),
+            TriggerQuantize::Bars(n) => serializer.serialize_str(&format!("Bars:{}", n)),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for TriggerQuantize {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        let quantize = match s.as_str() {
+            "Beat" => TriggerQuantize::Beat,
+            "Bar" => TriggerQuantize::Bar,
+            _ => match s.strip_prefix("Bars:").and_then(|n| n.parse::<u32>().ok()) {
+                Some(n) if n > 1 => TriggerQuantize::Bars(n),
+                Some(_) => TriggerQuantize::Bar,
+                // Unknown format - default to Off
+                None => TriggerQuantize::Off,
+            },
+        };
+        Ok(quantize)
+    }
+}
+
+impl TriggerQuantize {
+    /// Options offered in the UI (Bars variants use common phrase lengths)
+    pub const PRESETS: [TriggerQuantize; 6] = [
+        TriggerQuantize::Off,
+        TriggerQuantize::Beat,
+        TriggerQuantize::Bar,
+        TriggerQuantize::Bars(2),
+        TriggerQuantize::Bars(4),
+        TriggerQuantize::Bars(8),
+    ];
+
+    /// Get display name for the quantization
+    pub fn name(&self) -> String {
+        match self {
+            TriggerQuantize::Off => "Off".to_string(),
+            TriggerQuantize::Beat => "Beat".to_string(),
+            TriggerQuantize::Bar => "Bar".to_string(),
+            TriggerQuantize::Bars(n) => format!("{} Bars", n),
+        }
+    }
+
+    /// Length of one quantization step in beats (None when off)
+    pub fn quantum_beats(&self, beats_per_bar: u32) -> Option<f64> {
+        let beats_per_bar = beats_per_bar.max(1) as f64;
+        match self {
+            TriggerQuantize::Off => None,
+            TriggerQuantize::Beat => Some(1.0),
+            TriggerQuantize::Bar => Some(beats_per_bar),
+            TriggerQuantize::Bars(n) => Some(beats_per_bar * (*n).max(1) as f64),
+        }
+    }
+
+    /// Beat position of the next quantization boundary after `current_beat`
+    ///
+    /// Returns None when quantization is off (trigger immediately).
+    pub fn next_boundary(&self, current_beat: f64, beats_per_bar: u32) -> Option<f64> {
+        let quantum = self.quantum_beats(beats_per_bar)?;
+        Some(((current_beat / quantum).floor() + 1.0) * quantum)
+    }
+}
+
+/// Playback speed that stretches a clip's loop to a whole number of bars
+///
+/// The clip length is rounded to the nearest bar count (at least one bar) and
+/// the speed is chosen so one loop lasts exactly that many bars. The bar count
+/// is limited so the speed stays within the player's supported range; clips
+/// too short to fill one bar even at the slowest speed play at that speed.
+pub fn bpm_sync_speed(duration_secs: f64, seconds_per_bar: f64) -> f64 {
+    if duration_secs <= 0.0 || seconds_per_bar <= 0.0 {
+        return 1.0;
+    }
+    let length_in_bars = duration_secs / seconds_per_bar;
+    let min_bars = (length_in_bars / MAX_PLAYBACK_SPEED).ceil().max(1.0);
+    let max_bars = (length_in_bars / MIN_PLAYBACK_SPEED).floor().max(min_bars);
+    let bars = length_in_bars.round().clamp(min_bars, max_bars);
+    (length_in_bars / bars).clamp(MIN_PLAYBACK_SPEED, MAX_PLAYBACK_SPEED)
+}
+
 /// A single clip cell containing a video source
 #[derive(Debug, Clone, Serialize)]
 pub struct ClipCell {
@@ -325,6 +436,10 @@ pub struct ClipCell {
     /// How this clip loops (default: Loop continuously)
     #[serde(default)]
     pub loop_mode: LoopMode,
+
+    /// Time-stretch playback so the loop lasts a whole number of bars
+    #[serde(default)]
+    pub bpm_sync: bool,
 }
 
 /// Helper struct for deserializing ClipCell with backwards compatibility
@@ -342,6 +457,8 @@ struct ClipCellRaw {
     transform: Transform2D,
     #[serde(default)]
     loop_mode: LoopMode,
+    #[serde(default)]
+    bpm_sync: bool,
 }
 
 impl<'de> Deserialize<'de> for ClipCell {
@@ -380,6 +497,7 @@ impl<'de> Deserialize<'de> for ClipCell {
             effects: raw.effects,
             transform: raw.transform,
             loop_mode: raw.loop_mode,
+            bpm_sync: raw.bpm_sync,
         })
     }
 }
@@ -406,6 +524,7 @@ impl ClipCell {
             effects: EffectStack::new(),
             transform: Transform2D::default(),
             loop_mode: LoopMode::default(),
+            bpm_sync: false,
         }
     }
 
@@ -419,6 +538,7 @@ impl ClipCell {
             effects: EffectStack::new(),
             transform: Transform2D::default(),
             loop_mode: LoopMode::default(),
+            bpm_sync: false,
         }
     }
 
@@ -435,6 +555,7 @@ impl ClipCell {
             effects: EffectStack::new(),
             transform: Transform2D::default(),
             loop_mode: LoopMode::default(),
+            bpm_sync: false,
         }
     }
 
@@ -459,6 +580,7 @@ impl ClipCell {
             effects: EffectStack::new(),
             transform: Transform2D::default(),
             loop_mode: LoopMode::default(),
+            bpm_sync: false,
         }
     }
 
@@ -581,4 +703,65 @@ mod tests {
         assert!(cell.is_valid());
     }
 
+    #[test]
+    fn test_trigger_quantize_next_boundary() {
+        assert_eq!(TriggerQuantize::Off.next_boundary(3.2, 4), None);
+        assert_eq!(TriggerQuantize::Beat.next_boundary(3.2, 4), Some(4.0));
+        assert_eq!(TriggerQuantize::Bar.next_boundary(3.2, 4), Some(4.0));
+        assert_eq!(TriggerQuantize::Bar.next_boundary(4.0, 4), Some(8.0));
+        assert_eq!(TriggerQuantize::Bars(4).next_boundary(5.0, 4), Some(16.0));
+        assert_eq!(TriggerQuantize::Bar.next_boundary(1.5, 3), Some(3.0));
+    }
+
+    #[test]
+    fn test_trigger_quantize_serialization_roundtrip() {
+        for quantize in TriggerQuantize::PRESETS {
+            let json = serde_json::to_string(&quantize).unwrap();
+            let parsed: TriggerQuantize = serde_json::from_str(&json).unwrap();
+            assert_eq!(parsed, quantize);
+        }
+        let parsed: TriggerQuantize = serde_json::from_str("\"garbage\"").unwrap();
+        assert_eq!(parsed, TriggerQuantize::Off);
+    }
+
+    #[test]
+    fn test_bpm_sync_speed() {
+        // 120 BPM, 4/4 -> 2 seconds per bar
+        // 7.8s rounds to 4 bars (8s), so playback slows slightly
+        let speed = bpm_sync_speed(7.8, 2.0);
+        assert!((speed - 7.8 / 8.0).abs() < 1e-9);
+
+        // Very short clips stretch to a single bar
+        let speed = bpm_sync_speed(0.5, 2.0);
+        assert!((speed - 0.25).abs() < 1e-9);
+
+        // Invalid input leaves speed unchanged
+        assert_eq!(bpm_sync_speed(0.0, 2.0), 1.0);
+    }
+
+    #[test]
+    fn test_bpm_sync_speed_stays_in_player_range() {
+        // At 20 BPM a bar lasts 12s; a 1.5s clip would need 0.125x for one
+        // bar, which is still in range
+        let speed = bpm_sync_speed(1.5, 12.0);
+        assert!((speed - 0.125).abs() < 1e-9);
+
+        // A 0.6s clip can't fill a bar at 0.1x; it plays at the minimum
+        // instead of a speed the player would clamp every frame
+        assert_eq!(bpm_sync_speed(0.6, 12.0), MIN_PLAYBACK_SPEED);
+
+        // Speeds are always within the player's range, for any length and tempo
+        for duration in [0.01, 0.3, 1.0, 7.8, 60.0, 3600.0] {
+            for seconds_per_bar in [0.24, 0.8, 2.0, 12.0] {
+                let speed = bpm_sync_speed(duration, seconds_per_bar);
+                assert!(
+                    (MIN_PLAYBACK_SPEED..=MAX_PLAYBACK_SPEED).contains(&speed),
+                    "{}s clip, {}s bars: {}",
+                    duration,
+                    seconds_per_bar,
+                    speed
+                );
+            }
+        }
+    }
 }
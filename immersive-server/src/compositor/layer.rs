@@ -137,9 +137,16 @@ pub struct Layer {
     /// This is runtime state, not saved - on load, no clip is active
     #[serde(skip)]
     pub active_clip: Option<usize>,
+    /// Clip slot waiting for a quantized trigger to fire, if any
+    /// This is runtime state, not saved - shown as blinking in the clip grid
+    #[serde(skip)]
+    pub pending_clip: Option<usize>,
     /// Transition mode for clips on this layer
     #[serde(default)]
     pub transition: crate::compositor::ClipTransition,
+    /// Trigger quantization override for this layer (None = use global setting)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trigger_quantize: Option<crate::compositor::TriggerQuantize>,
     /// Effect stack for this layer
     #[serde(default)]
     pub effects: EffectStack,
@@ -157,7 +164,9 @@ impl Default for Layer {
             visible: true,
             clips: Vec::new(),
             active_clip: None,
+            pending_clip: None,
             transition: crate::compositor::ClipTransition::Cut,
+            trigger_quantize: None,
             effects: EffectStack::default(),
         }
     }
@@ -176,7 +185,9 @@ impl Layer {
             visible: true,
             clips: vec![None; DEFAULT_CLIP_SLOTS],
             active_clip: None,
+            pending_clip: None,
             transition: crate::compositor::ClipTransition::Cut,
+            trigger_quantize: None,
             effects: EffectStack::new(),
         }
     }
@@ -193,7 +204,9 @@ impl Layer {
             visible: true,
             clips: vec![None; DEFAULT_CLIP_SLOTS],
             active_clip: None,
+            pending_clip: None,
             transition: crate::compositor::ClipTransition::Cut,
+            trigger_quantize: None,
             effects: EffectStack::new(),
         }
     }
@@ -248,6 +261,11 @@ impl Layer {
         self.active_clip = None;
     }
 
+    /// Check if a quantized trigger is waiting on this layer
+    pub fn has_pending_clip(&self) -> bool {
+        self.pending_clip.is_some()
+    }
+
     /// Set the layer's source
     pub fn set_source(&mut self, source: LayerSource) {
         self.source = source;
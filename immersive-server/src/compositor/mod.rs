@@ -17,7 +17,7 @@ pub mod layer;
 pub mod viewport;
 
 pub use blend::BlendMode;
-pub use clip::{ClipCell, ClipSource, ClipTransition, LoopMode, DEFAULT_CLIP_SLOTS};
+pub use clip::{bpm_sync_speed, ClipCell, ClipSource, ClipTransition, LoopMode, TriggerQuantize, DEFAULT_CLIP_SLOTS};
 pub use environment::Environment;
 pub use layer::{Layer, LayerSource, Transform2D};
 pub use viewport::Viewport;
@@ -0,0 +1,123 @@
+//! Audio spectrum texture for GPU effects
+//!
+//! Uploads the primary audio source's smoothed spectrum and waveform into a
+//! 1D texture once per frame so shaders can draw visualizers or react to
+//! individual frequencies.
+//!
+//! Layout: `SPECTRUM_SIZE` texels of `Rg32Float`
+//! - `r` = spectrum level (0-1, log frequency 20 Hz - 20 kHz left to right)
+//! - `g` = waveform sample (-1 to 1)
+//!
+//! The format is not filterable, so shaders read it with `textureLoad` on a
+//! `texture_1d<f32>`.
+
+use crate::audio::{AudioManager, SPECTRUM_SIZE, WAVEFORM_SIZE};
+
+/// Texture format of the audio texture
+pub const AUDIO_TEXTURE_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rg32Float;
+
+/// Bind group layout entry for sampling the audio texture at `binding`
+pub fn audio_texture_layout_entry(binding: u32) -> wgpu::BindGroupLayoutEntry {
+    wgpu::BindGroupLayoutEntry {
+        binding,
+        visibility: wgpu::ShaderStages::FRAGMENT,
+        ty: wgpu::BindingType::Texture {
+            sample_type: wgpu::TextureSampleType::Float { filterable: false },
+            view_dimension: wgpu::TextureViewDimension::D1,
+            multisampled: false,
+        },
+        count: None,
+    }
+}
+
+/// 1D texture holding the current spectrum and waveform
+pub struct AudioTexture {
+    texture: wgpu::Texture,
+    view: wgpu::TextureView,
+    width: u32,
+    /// Interleaved rg texels staged for upload
+    texels: Vec<f32>,
+}
+
+impl AudioTexture {
+    /// Create a silent audio texture with `width` texels
+    pub fn new(device: &wgpu::Device, queue: &wgpu::Queue, width: u32) -> Self {
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("Audio Texture"),
+            size: wgpu::Extent3d {
+                width,
+                height: 1,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D1,
+            format: AUDIO_TEXTURE_FORMAT,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+            view_formats: &[],
+        });
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+        let audio_texture = Self {
+            texture,
+            view,
+            width,
+            texels: vec![0.0; width as usize * 2],
+        };
+        audio_texture.upload(queue);
+        audio_texture
+    }
+
+    /// Create an audio texture sized for the analyzer's spectrum
+    pub fn for_spectrum(device: &wgpu::Device, queue: &wgpu::Queue) -> Self {
+        Self::new(device, queue, SPECTRUM_SIZE.max(WAVEFORM_SIZE) as u32)
+    }
+
+    /// Upload new spectrum and waveform values
+    ///
+    /// Missing values (e.g. no audio yet) are written as silence.
+    pub fn update(&mut self, queue: &wgpu::Queue, spectrum: &[f32], waveform: &[f32]) {
+        for (i, texel) in self.texels.chunks_exact_mut(2).enumerate() {
+            texel[0] = spectrum.get(i).copied().unwrap_or(0.0);
+            texel[1] = waveform.get(i).copied().unwrap_or(0.0);
+        }
+        self.upload(queue);
+    }
+
+    /// Upload the primary source's analysis from the audio manager
+    pub fn update_from_manager(&mut self, queue: &wgpu::Queue, audio_manager: &AudioManager) {
+        let uploaded = audio_manager.with_primary_fft_data(|fft| {
+            self.update(queue, &fft.spectrum, &fft.waveform);
+        });
+        if uploaded.is_none() {
+            self.update(queue, &[], &[]);
+        }
+    }
+
+    /// Texture view for binding in effect shaders
+    pub fn view(&self) -> &wgpu::TextureView {
+        &self.view
+    }
+
+    fn upload(&self, queue: &wgpu::Queue) {
+        queue.write_texture(
+            wgpu::TexelCopyTextureInfo {
+                texture: &self.texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d::ZERO,
+                aspect: wgpu::TextureAspect::All,
+            },
+            bytemuck::cast_slice(&self.texels),
+            wgpu::TexelCopyBufferLayout {
+                offset: 0,
+                bytes_per_row: Some(self.width * 8),
+                rows_per_image: Some(1),
+            },
+            wgpu::Extent3d {
+                width: self.width,
+                height: 1,
+                depth_or_array_layers: 1,
+            },
+        );
+    }
+}
@@ -1,13 +1,19 @@
 //! Automation system for effect parameters
 //!
 //! Provides BPM clock, LFO generation, beat-triggered envelopes,
-//! and FFT audio-reactive automation for effect parameters.
+//! and FFT/onset audio-reactive automation for effect parameters.
 
 use std::time::Instant;
 
-use super::types::{AutomationRange, AutomationSource, BeatSource, BeatTrigger, FftSource, LfoShape, LfoSource, Parameter, TimelineSource, TimelineMode, TimelineDirection};
+use super::types::{AutomationRange, AutomationSource, BeatSource, BeatTrigger, CurveSource, CurveTimeBase, FftSource, LfoShape, LfoSource, OnsetSource, Parameter, TimelineSource, TimelineMode, TimelineDirection};
 use crate::audio::AudioManager;
 
+/// Fraction of the phase error corrected per update when following audio tempo
+const AUDIO_PHASE_CORRECTION: f32 = 0.1;
+
+/// Tempo range accepted from external sources (matches Ableton Link's 20-999 BPM)
+const EXTERNAL_BPM_RANGE: (f32, f32) = (20.0, 999.0);
+
 /// Global BPM clock for synchronizing automation
 #[derive(Debug, Clone)]
 pub struct BpmClock {
@@ -15,8 +21,8 @@ pub struct BpmClock {
     bpm: f32,
     /// Beats per bar (time signature numerator)
     beats_per_bar: u32,
-    /// Total beats elapsed since start/reset
-    current_beat: f32,
+    /// Total beats elapsed since start/reset (negative during a Link count-in)
+    current_beat: f64,
     /// Phase within current beat (0.0-1.0)
     beat_phase: f32,
     /// Phase within current bar (0.0-1.0)
@@ -58,16 +64,21 @@ impl BpmClock {
         }
 
         let now = Instant::now();
-        let delta = now.duration_since(self.last_update).as_secs_f32();
+        let delta = now.duration_since(self.last_update).as_secs_f64();
         self.last_update = now;
 
         // Calculate beats elapsed
-        let beats_per_second = self.bpm / 60.0;
+        let beats_per_second = self.bpm as f64 / 60.0;
         let beats_delta = delta * beats_per_second;
 
         self.current_beat += beats_delta;
-        self.beat_phase = self.current_beat.fract();
-        self.bar_phase = (self.current_beat / self.beats_per_bar as f32).fract();
+        self.update_phases();
+    }
+
+    /// Derive beat and bar phase from the current beat
+    fn update_phases(&mut self) {
+        self.beat_phase = self.current_beat.rem_euclid(1.0) as f32;
+        self.bar_phase = (self.current_beat / self.beats_per_bar as f64).rem_euclid(1.0) as f32;
     }
 
     /// Get the current BPM
@@ -90,8 +101,15 @@ impl BpmClock {
         self.beats_per_bar = beats.clamp(1, 16);
     }
 
+    /// Get the duration of one bar in seconds at the current tempo
+    pub fn seconds_per_bar(&self) -> f64 {
+        self.beats_per_bar as f64 * 60.0 / self.bpm as f64
+    }
+
     /// Get the total beats elapsed
-    pub fn current_beat(&self) -> f32 {
+    ///
+    /// Negative while an external session is counting in.
+    pub fn current_beat(&self) -> f64 {
         self.current_beat
     }
 
@@ -168,22 +186,56 @@ impl BpmClock {
 
     /// Nudge the beat phase forward (for manual sync)
     pub fn nudge_forward(&mut self, amount: f32) {
-        self.current_beat += amount;
-        self.beat_phase = self.current_beat.fract();
-        self.bar_phase = (self.current_beat / self.beats_per_bar as f32).fract();
+        self.current_beat += amount as f64;
+        self.update_phases();
     }
 
     /// Nudge the beat phase backward (for manual sync)
     pub fn nudge_backward(&mut self, amount: f32) {
-        self.current_beat = (self.current_beat - amount).max(0.0);
-        self.beat_phase = self.current_beat.fract();
-        self.bar_phase = (self.current_beat / self.beats_per_bar as f32).fract();
+        self.current_beat = (self.current_beat - amount as f64).max(0.0);
+        self.update_phases();
+    }
+
+    /// Follow an external tempo source (e.g. an Ableton Link session)
+    ///
+    /// Tempo and beat position are taken directly from the source each frame,
+    /// so the clock never drifts from it. The beat is kept at full precision
+    /// so phases stay exact over long sessions, and may be negative while the
+    /// session counts in to a start. The tempo is
+    /// limited to Link's range rather than the manual 20-300 BPM, so `bpm()`
+    /// always matches the session the beat comes from.
+    pub fn sync_external(&mut self, bpm: f32, beat: f64, running: bool) {
+        self.bpm = bpm.clamp(EXTERNAL_BPM_RANGE.0, EXTERNAL_BPM_RANGE.1);
+        self.last_update = Instant::now();
+        if !running {
+            self.running = false;
+            return;
+        }
+        self.running = true;
+        self.current_beat = beat;
+        self.update_phases();
+    }
+
+    /// Follow a tempo and beat phase detected from audio
+    ///
+    /// Tempo is taken as-is. The phase is pulled towards the detected one a
+    /// little on every call, so noisy estimates don't make the beat jump.
+    pub fn follow_tempo(&mut self, bpm: f32, beat_phase: f32) {
+        self.set_bpm(bpm);
+        if !self.running {
+            return;
+        }
+
+        // Shortest signed distance between the two phases (-0.5..0.5)
+        let error = (beat_phase - self.beat_phase + 0.5).rem_euclid(1.0) - 0.5;
+        self.current_beat = (self.current_beat + (error * AUDIO_PHASE_CORRECTION) as f64).max(0.0);
+        self.update_phases();
     }
 
     /// Resync to the start of the current bar
     pub fn resync_to_bar(&mut self) {
-        let bars = (self.current_beat / self.beats_per_bar as f32).floor();
-        self.current_beat = bars * self.beats_per_bar as f32;
+        let bars = (self.current_beat / self.beats_per_bar as f64).floor();
+        self.current_beat = bars * self.beats_per_bar as f64;
         self.beat_phase = 0.0;
         self.bar_phase = 0.0;
     }
@@ -197,8 +249,8 @@ impl LfoSource {
     pub fn evaluate(&self, clock: &BpmClock, time: f32) -> f32 {
         let phase = if self.sync_to_bpm {
             // Phase based on beat count
-            let beats_per_cycle = self.beats.max(0.001);
-            ((clock.current_beat() / beats_per_cycle) + self.phase).fract()
+            let beats_per_cycle = self.beats.max(0.001) as f64;
+            ((clock.current_beat() / beats_per_cycle) + self.phase as f64).rem_euclid(1.0) as f32
         } else {
             // Phase based on time
             ((time * self.frequency) + self.phase).fract()
@@ -295,9 +347,9 @@ impl BeatEnvelopeState {
         // Check for trigger
         let trigger_beat = match source.trigger_on {
             BeatTrigger::Beat => clock.current_beat().floor() as u32,
-            BeatTrigger::Bar => (clock.current_beat() / clock.beats_per_bar() as f32).floor() as u32,
-            BeatTrigger::TwoBars => (clock.current_beat() / (clock.beats_per_bar() * 2) as f32).floor() as u32,
-            BeatTrigger::FourBars => (clock.current_beat() / (clock.beats_per_bar() * 4) as f32).floor() as u32,
+            BeatTrigger::Bar => (clock.current_beat() / clock.beats_per_bar() as f64).floor() as u32,
+            BeatTrigger::TwoBars => (clock.current_beat() / (clock.beats_per_bar() * 2) as f64).floor() as u32,
+            BeatTrigger::FourBars => (clock.current_beat() / (clock.beats_per_bar() * 4) as f64).floor() as u32,
         };
 
         if trigger_beat > self.last_trigger {
@@ -369,6 +421,36 @@ impl BeatEnvelopeState {
     }
 }
 
+impl FftSource {
+    /// Read the raw level of the tracked band or range from the audio manager
+    ///
+    /// A custom frequency range takes precedence over a band index, which
+    /// takes precedence over the fixed Low/Mid/High/Full band.
+    pub fn sample(&self, manager: &AudioManager) -> f32 {
+        if let Some((min_hz, max_hz)) = self.frequency_range {
+            manager.get_frequency_range_value(min_hz, max_hz)
+        } else if let Some(index) = self.band_index {
+            manager.get_band_index_value(index)
+        } else {
+            manager.get_band_value(self.band)
+        }
+    }
+}
+
+impl CurveSource {
+    /// Get the curve position for the current clip time or BPM clock
+    ///
+    /// `clip_time` is the playback position of the clip in seconds; it is
+    /// only used by clip-time curves.
+    pub fn playhead(&self, clip_time: f32, clock: &BpmClock) -> f32 {
+        let position = match self.time_base {
+            CurveTimeBase::ClipTime => clip_time,
+            CurveTimeBase::Bars => (clock.current_beat() / clock.beats_per_bar().max(1) as f64) as f32,
+        };
+        self.wrap(position)
+    }
+}
+
 /// FFT envelope state for attack/release smoothing
 #[derive(Debug, Clone, Default)]
 pub struct FftEnvelopeState {
@@ -420,6 +502,67 @@ impl FftEnvelopeState {
     }
 }
 
+/// Onset envelope state for transient-triggered pulses
+#[derive(Debug, Clone, Default)]
+pub struct OnsetEnvelopeState {
+    /// Current envelope value (0-1)
+    current_value: f32,
+    /// Onset count seen on the previous update
+    last_onset: Option<u64>,
+    /// Whether the envelope is rising towards its peak
+    attacking: bool,
+}
+
+impl OnsetEnvelopeState {
+    /// Create new onset envelope state
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Update the envelope from the audio manager's onset counter
+    pub fn update(&mut self, source: &OnsetSource, onset_count: u64, onset_strength: f32, delta_time: f32) {
+        // Onsets from before the first update don't fire; a lower count means
+        // the detector was reset
+        let last = *self.last_onset.get_or_insert(onset_count);
+        if onset_count != last {
+            self.last_onset = Some(onset_count);
+            if onset_count > last && onset_strength >= source.threshold {
+                self.attacking = true;
+            }
+        }
+
+        if self.attacking {
+            let attack_time = source.attack_ms / 1000.0;
+            if attack_time > 0.0 {
+                self.current_value += delta_time / attack_time;
+            } else {
+                self.current_value = 1.0;
+            }
+            if self.current_value >= 1.0 {
+                self.current_value = 1.0;
+                self.attacking = false;
+            }
+        } else {
+            let release_time = source.release_ms / 1000.0;
+            if release_time > 0.001 {
+                self.current_value *= (-delta_time / release_time).exp();
+            } else {
+                self.current_value = 0.0;
+            }
+        }
+    }
+
+    /// Get the current envelope value (0-1)
+    pub fn value(&self) -> f32 {
+        self.current_value
+    }
+
+    /// Reset the envelope state
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
 /// Timeline envelope state for time-based ramps
 #[derive(Debug, Clone)]
 pub struct TimelineEnvelopeState {
@@ -542,6 +685,18 @@ pub fn evaluate_parameter(
             // Timeline automation requires timeline envelope state
             base_value
         }
+        Some(AutomationSource::Onset(_)) => {
+            // Onset automation requires audio manager and onset envelope state
+            base_value
+        }
+        Some(AutomationSource::Curve(curve)) => {
+            let min = param.meta.min.unwrap_or(0.0);
+            let max = param.meta.max.unwrap_or(1.0);
+            curve
+                .evaluate(curve.playhead(time, clock))
+                .map(|value| min + curve.range.remap(value) * (max - min))
+                .unwrap_or(base_value)
+        }
     }
 }
 
@@ -583,7 +738,7 @@ pub fn evaluate_parameter_with_audio(
         Some(AutomationSource::Fft(fft)) => {
             if let (Some(state), Some(manager)) = (fft_envelope_state, audio_manager) {
                 // Get raw FFT value from audio manager
-                let raw_value = manager.get_band_value(fft.band);
+                let raw_value = fft.sample(manager);
 
                 // Update envelope with smoothing
                 state.update(fft, raw_value, delta_time);
@@ -596,11 +751,19 @@ pub fn evaluate_parameter_with_audio(
                 base_value
             }
         }
-        Some(AutomationSource::Timeline(_)) => {
-            // Timeline automation requires timeline envelope state
+        Some(AutomationSource::Timeline(_)) | Some(AutomationSource::Onset(_)) => {
+            // Timeline and onset automation require their envelope states
             // Use pack_parameters_with_automation_and_envelopes for full support
             base_value
         }
+        Some(AutomationSource::Curve(curve)) => {
+            let min = param.meta.min.unwrap_or(0.0);
+            let max = param.meta.max.unwrap_or(1.0);
+            curve
+                .evaluate(curve.playhead(time, clock))
+                .map(|value| min + curve.range.remap(value) * (max - min))
+                .unwrap_or(base_value)
+        }
     }
 }
 
@@ -629,6 +792,95 @@ mod tests {
         assert_eq!(clock.bpm(), 300.0);
     }
 
+    #[test]
+    fn test_bpm_clock_sync_external() {
+        let mut clock = BpmClock::new(120.0);
+        clock.sync_external(128.0, 1_000_005.25, true);
+        assert_eq!(clock.bpm(), 128.0);
+        assert!((clock.beat_phase() - 0.25).abs() < 1e-6);
+        assert!((clock.bar_phase() - (1.25 / 4.0)).abs() < 1e-6);
+        // The beat itself keeps sub-beat precision
+        assert_eq!(clock.current_beat(), 1_000_005.25);
+
+        // Count-in beats before the session start stay negative
+        clock.sync_external(128.0, -1.75, true);
+        assert_eq!(clock.current_beat(), -1.75);
+        assert!((clock.beat_phase() - 0.25).abs() < 1e-6);
+        assert!((clock.bar_phase() - (2.25 / 4.0)).abs() < 1e-6);
+
+        // Stopped session pauses the clock but keeps the tempo
+        clock.sync_external(100.0, 12.0, false);
+        assert!(!clock.is_running());
+        assert_eq!(clock.bpm(), 100.0);
+
+        // Link tempos above the manual range are followed as-is
+        clock.sync_external(480.0, 12.0, true);
+        assert_eq!(clock.bpm(), 480.0);
+        clock.sync_external(1500.0, 12.0, true);
+        assert_eq!(clock.bpm(), 999.0);
+    }
+
+    #[test]
+    fn test_bpm_clock_follow_tempo() {
+        let mut clock = BpmClock::new(120.0);
+        clock.nudge_forward(8.9);
+
+        // Phase converges on the detected phase across the beat boundary
+        for _ in 0..100 {
+            clock.follow_tempo(126.0, 0.1);
+        }
+        assert_eq!(clock.bpm(), 126.0);
+        assert!((clock.beat_phase() - 0.1).abs() < 0.01);
+        assert!((clock.current_beat() - 9.1).abs() < 0.01);
+
+        // A stopped clock takes the tempo but keeps its position
+        clock.stop();
+        clock.follow_tempo(90.0, 0.6);
+        assert_eq!(clock.bpm(), 90.0);
+        assert!((clock.beat_phase() - 0.1).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_onset_envelope() {
+        let source = OnsetSource {
+            threshold: 0.3,
+            attack_ms: 10.0,
+            release_ms: 100.0,
+            ..Default::default()
+        };
+        let mut state = OnsetEnvelopeState::new();
+
+        // Onsets detected before the first update are ignored
+        state.update(&source, 5, 1.0, 0.016);
+        assert_eq!(state.value(), 0.0);
+
+        // A strong onset fires the envelope
+        state.update(&source, 6, 0.8, 0.005);
+        assert!((state.value() - 0.5).abs() < 1e-4);
+        state.update(&source, 6, 0.8, 0.005);
+        assert_eq!(state.value(), 1.0);
+
+        // Then it releases
+        state.update(&source, 6, 0.8, 0.1);
+        assert!(state.value() < 0.4);
+
+        // Weak onsets stay below the threshold
+        state.reset();
+        state.update(&source, 6, 0.8, 0.016);
+        state.update(&source, 7, 0.1, 0.016);
+        assert_eq!(state.value(), 0.0);
+    }
+
+    #[test]
+    fn test_bpm_clock_seconds_per_bar() {
+        let mut clock = BpmClock::new(120.0);
+        assert!((clock.seconds_per_bar() - 2.0).abs() < 1e-9);
+
+        clock.set_beats_per_bar(3);
+        clock.set_bpm(90.0);
+        assert!((clock.seconds_per_bar() - 2.0).abs() < 1e-9);
+    }
+
     #[test]
     fn test_lfo_sine() {
         let lfo = LfoSource {
@@ -701,4 +953,21 @@ mod tests {
         // Should be in attack phase
         assert!(state.value() > 0.0);
     }
+
+    #[test]
+    fn test_curve_playhead() {
+        let mut clock = BpmClock::new(120.0);
+        clock.current_beat = 18.0;
+
+        let bars = CurveSource::default();
+        // 18 beats = 4.5 bars, wrapped into the 4 bar loop
+        assert!((bars.playhead(0.0, &clock) - 0.5).abs() < 1e-5);
+
+        let clip = CurveSource {
+            time_base: CurveTimeBase::ClipTime,
+            length: 10.0,
+            ..Default::default()
+        };
+        assert!((clip.playhead(12.5, &clock) - 2.5).abs() < 1e-5);
+    }
 }
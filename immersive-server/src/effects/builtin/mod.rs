@@ -10,6 +10,7 @@ mod invert;
 mod multiplex;
 mod poop_rain;
 mod slide;
+mod spectrum;
 
 pub use auto_mask::{AutoMaskDefinition, AutoMaskRuntime};
 pub use color_correction::{ColorCorrectionDefinition, ColorCorrectionRuntime};
@@ -20,6 +21,7 @@ pub use multiplex::{MultiplexDefinition, MultiplexRuntime};
 #[allow(unused_imports)]
 pub use poop_rain::{PoopRainDefinition, PoopRainRuntime};
 pub use slide::{SlideDefinition, SlideRuntime};
+pub use spectrum::{SpectrumDefinition, SpectrumRuntime};
 
 use super::EffectRegistry;
 
@@ -32,4 +34,5 @@ pub fn register_builtin_effects(registry: &mut EffectRegistry) {
     registry.register(InvertDefinition);
     registry.register(MultiplexDefinition);
     registry.register(SlideDefinition);
+    registry.register(SpectrumDefinition);
 }
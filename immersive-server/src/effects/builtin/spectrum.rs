@@ -0,0 +1,278 @@
+//! Spectrum Effect
+//!
+//! Draws the audio spectrum as bars or the waveform as a line over the input,
+//! reading the shared audio texture.
+
+use crate::effects::audio_texture::{audio_texture_layout_entry, AudioTexture};
+use crate::effects::traits::{
+    CpuEffectRuntime, EffectDefinition, EffectParams, EffectProcessor, GpuEffectRuntime, ParamBuilder,
+};
+use crate::effects::types::{Parameter, ParameterMeta};
+
+/// Spectrum effect definition
+pub struct SpectrumDefinition;
+
+impl EffectDefinition for SpectrumDefinition {
+    fn effect_type(&self) -> &'static str {
+        "spectrum"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "Spectrum"
+    }
+
+    fn category(&self) -> &'static str {
+        "Generate"
+    }
+
+    fn processor(&self) -> EffectProcessor {
+        EffectProcessor::Gpu
+    }
+
+    fn default_parameters(&self) -> Vec<Parameter> {
+        vec![
+            Parameter::new(ParamBuilder::amount()),
+            Parameter::new(ParameterMeta::enumeration(
+                "mode",
+                "Mode",
+                vec!["Bars".to_string(), "Waveform".to_string()],
+                0,
+            )),
+            Parameter::new(ParameterMeta::color("color", "Color", [0.2, 0.9, 1.0, 1.0])),
+            Parameter::new(ParameterMeta::float("height", "Height", 0.5, 0.05, 1.0)),
+            Parameter::new(ParameterMeta::float("bar_count", "Bars", 64.0, 8.0, 256.0)),
+            Parameter::new(ParameterMeta::bool("mirror", "Mirror", false)),
+        ]
+    }
+
+    fn create_gpu_runtime(
+        &self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        output_format: wgpu::TextureFormat,
+    ) -> Option<Box<dyn GpuEffectRuntime>> {
+        Some(Box::new(SpectrumRuntime::new(device, queue, output_format)))
+    }
+
+    fn create_cpu_runtime(&self) -> Option<Box<dyn CpuEffectRuntime>> {
+        None
+    }
+}
+
+/// GPU runtime for Spectrum effect
+pub struct SpectrumRuntime {
+    pipeline: wgpu::RenderPipeline,
+    bind_group_layout: wgpu::BindGroupLayout,
+    params_buffer: wgpu::Buffer,
+    sampler: wgpu::Sampler,
+    /// Silent texture used until the shared audio texture is provided
+    silent_texture: AudioTexture,
+    /// Shared audio texture from the effect manager
+    audio_texture: Option<wgpu::TextureView>,
+}
+
+impl SpectrumRuntime {
+    /// Create a new spectrum runtime
+    pub fn new(device: &wgpu::Device, queue: &wgpu::Queue, output_format: wgpu::TextureFormat) -> Self {
+        let shader_source = include_str!("../../shaders/effects/spectrum.wgsl");
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Spectrum Shader"),
+            source: wgpu::ShaderSource::Wgsl(shader_source.into()),
+        });
+
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Spectrum Bind Group Layout"),
+            entries: &[
+                // Input texture
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                // Sampler
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                    count: None,
+                },
+                // Parameters uniform
+                wgpu::BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                // Audio spectrum/waveform texture
+                audio_texture_layout_entry(3),
+            ],
+        });
+
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Spectrum Pipeline Layout"),
+            bind_group_layouts: &[&bind_group_layout],
+            push_constant_ranges: &[],
+        });
+
+        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Spectrum Pipeline"),
+            layout: Some(&pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: Some("vs_main"),
+                buffers: &[],
+                compilation_options: Default::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs_main"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: output_format,
+                    blend: None,
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: Default::default(),
+            }),
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleList,
+                ..Default::default()
+            },
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            multiview: None,
+            cache: None,
+        });
+
+        // Create uniform buffer for parameters
+        // Layout: time, delta_time, beat_phase, bar_phase, amount, mode, height, bar_count,
+        //         mirror, _pad[3], color[4]
+        // Total: 16 floats = 64 bytes
+        let params_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Spectrum Params Buffer"),
+            size: 64,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("Spectrum Sampler"),
+            address_mode_u: wgpu::AddressMode::ClampToEdge,
+            address_mode_v: wgpu::AddressMode::ClampToEdge,
+            address_mode_w: wgpu::AddressMode::ClampToEdge,
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            mipmap_filter: wgpu::FilterMode::Nearest,
+            ..Default::default()
+        });
+
+        Self {
+            pipeline,
+            bind_group_layout,
+            params_buffer,
+            sampler,
+            silent_texture: AudioTexture::new(device, queue, 1),
+            audio_texture: None,
+        }
+    }
+}
+
+impl GpuEffectRuntime for SpectrumRuntime {
+    fn process(
+        &mut self,
+        encoder: &mut wgpu::CommandEncoder,
+        device: &wgpu::Device,
+        input: &wgpu::TextureView,
+        output: &wgpu::TextureView,
+        params: &EffectParams,
+        queue: &wgpu::Queue,
+    ) {
+        // Packed parameter order:
+        // [amount, mode, color.r, color.g, color.b, color.a, height, bar_count, mirror]
+        let uniform_data: [f32; 16] = [
+            params.time,
+            params.delta_time,
+            params.beat_phase,
+            params.bar_phase,
+            params.params[0], // amount
+            params.params[1], // mode
+            params.params[6], // height
+            params.params[7], // bar_count
+            params.params[8], // mirror
+            0.0,              // padding
+            0.0,              // padding
+            0.0,              // padding
+            params.params[2], // color.r
+            params.params[3], // color.g
+            params.params[4], // color.b
+            params.params[5], // color.a
+        ];
+
+        queue.write_buffer(&self.params_buffer, 0, bytemuck::cast_slice(&uniform_data));
+
+        let audio_view = self.audio_texture.as_ref().unwrap_or_else(|| self.silent_texture.view());
+
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Spectrum Bind Group"),
+            layout: &self.bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::TextureView(input),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::Sampler(&self.sampler),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: self.params_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 3,
+                    resource: wgpu::BindingResource::TextureView(audio_view),
+                },
+            ],
+        });
+
+        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("Spectrum Pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: output,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT),
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+
+        render_pass.set_pipeline(&self.pipeline);
+        render_pass.set_bind_group(0, &bind_group, &[]);
+        render_pass.draw(0..3, 0..1);
+    }
+
+    fn rebuild(&mut self, _device: &wgpu::Device, _shader_source: &str) -> Result<(), String> {
+        // TODO: Implement hot-reload
+        Ok(())
+    }
+
+    fn effect_type(&self) -> &'static str {
+        "spectrum"
+    }
+
+    fn set_audio_texture(&mut self, view: &wgpu::TextureView) {
+        self.audio_texture = Some(view.clone());
+    }
+}
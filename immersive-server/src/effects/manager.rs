@@ -6,6 +6,7 @@
 use std::collections::HashMap;
 use std::time::Instant;
 
+use super::audio_texture::AudioTexture;
 use super::automation::BpmClock;
 use super::builtin::register_builtin_effects;
 use super::runtime::EffectStackRuntime;
@@ -37,6 +38,8 @@ pub struct EffectManager {
     last_frame: Instant,
     /// Whether effects are globally enabled
     enabled: bool,
+    /// Spectrum/waveform texture shared by audio-reactive effects
+    audio_texture: Option<AudioTexture>,
 }
 
 impl Default for EffectManager {
@@ -63,6 +66,7 @@ impl EffectManager {
             delta_time: 0.0,
             last_frame: Instant::now(),
             enabled: true,
+            audio_texture: None,
         }
     }
 
@@ -117,6 +121,26 @@ impl EffectManager {
         self.bpm_clock.update();
     }
 
+    /// Upload the primary audio source's spectrum and waveform for effects
+    ///
+    /// Creates the audio texture on first use. Call once per frame after
+    /// the audio manager has been updated.
+    pub fn update_audio_texture(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        audio_manager: &crate::audio::AudioManager,
+    ) {
+        self.audio_texture
+            .get_or_insert_with(|| AudioTexture::for_spectrum(device, queue))
+            .update_from_manager(queue, audio_manager);
+    }
+
+    /// Get the audio spectrum/waveform texture view, if created
+    pub fn audio_texture_view(&self) -> Option<&wgpu::TextureView> {
+        self.audio_texture.as_ref().map(AudioTexture::view)
+    }
+
     // ========== Envelope Value Accessors for UI ==========
 
     /// Get the current FFT envelope value for a parameter (for UI display)
@@ -198,6 +222,47 @@ impl EffectManager {
         }
     }
 
+    /// Get the current onset envelope value for a parameter (for UI display)
+    ///
+    /// Looks up the onset envelope state from the appropriate runtime (clip, layer, or environment).
+    /// Returns the transient pulse value (0-1).
+    /// Returns None if no envelope state exists for this parameter.
+    pub fn get_onset_envelope_value(
+        &self,
+        layer_id: Option<u32>,
+        clip_slot: Option<(u32, usize)>,
+        effect_id: u32,
+        param_name: &str,
+    ) -> Option<f32> {
+        if let Some((lid, slot)) = clip_slot {
+            self.clip_runtimes
+                .get(&(lid, slot))
+                .and_then(|r| r.get_onset_envelope_value(effect_id, param_name))
+        } else if let Some(lid) = layer_id {
+            self.layer_runtimes
+                .get(&lid)
+                .and_then(|r| r.get_onset_envelope_value(effect_id, param_name))
+        } else {
+            self.environment_runtime
+                .as_ref()
+                .and_then(|r| r.get_onset_envelope_value(effect_id, param_name))
+        }
+    }
+
+    /// Get the clip playback position last used for curve automation (for UI display)
+    ///
+    /// Returns None for environment effects and live sources, where curves
+    /// play back against the effect time instead.
+    pub fn get_clip_time(&self, layer_id: Option<u32>, clip_slot: Option<(u32, usize)>) -> Option<f32> {
+        if let Some((lid, slot)) = clip_slot {
+            self.clip_runtimes.get(&(lid, slot)).and_then(|r| r.clip_time())
+        } else if let Some(lid) = layer_id {
+            self.layer_runtimes.get(&lid).and_then(|r| r.clip_time())
+        } else {
+            None
+        }
+    }
+
     /// Initialize GPU resources for a layer's effects
     pub fn init_layer_effects(
         &mut self,
@@ -241,7 +306,7 @@ impl EffectManager {
         format: wgpu::TextureFormat,
     ) {
         if let Some(runtime) = self.clip_runtimes.get_mut(&(layer_id, slot)) {
-            runtime.sync_with_stack(stack, &self.registry, device, queue, format);
+            runtime.sync_with_stack(stack, &self.registry, device, queue, format, self.audio_texture.as_ref().map(AudioTexture::view));
         }
     }
 
@@ -394,7 +459,7 @@ impl EffectManager {
         format: wgpu::TextureFormat,
     ) {
         if let Some(runtime) = self.preview_runtime.as_mut() {
-            runtime.sync_with_stack(stack, &self.registry, device, queue, format);
+            runtime.sync_with_stack(stack, &self.registry, device, queue, format, self.audio_texture.as_ref().map(AudioTexture::view));
         }
     }
 
@@ -427,7 +492,7 @@ impl EffectManager {
         format: wgpu::TextureFormat,
     ) {
         if let Some(runtime) = self.layer_runtimes.get_mut(&layer_id) {
-            runtime.sync_with_stack(stack, &self.registry, device, queue, format);
+            runtime.sync_with_stack(stack, &self.registry, device, queue, format, self.audio_texture.as_ref().map(AudioTexture::view));
         }
     }
 
@@ -440,7 +505,7 @@ impl EffectManager {
         format: wgpu::TextureFormat,
     ) {
         if let Some(runtime) = &mut self.environment_runtime {
-            runtime.sync_with_stack(stack, &self.registry, device, queue, format);
+            runtime.sync_with_stack(stack, &self.registry, device, queue, format, self.audio_texture.as_ref().map(AudioTexture::view));
         }
     }
 
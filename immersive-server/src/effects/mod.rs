@@ -17,6 +17,7 @@
 //!   GpuEffectRuntime/CpuEffectRuntime traits for processing
 //! - **Registry** (`registry.rs`): Central registry of available effects
 //! - **Runtime** (`runtime.rs`): GPU resources and effect chain processing
+//! - **Audio texture** (`audio_texture.rs`): Spectrum/waveform texture effects can sample
 //! - **Builtin** (`builtin/`): Built-in effects (color_correction, invert, etc.)
 //!
 //! # Usage
@@ -47,6 +48,7 @@ mod registry;
 mod runtime;
 mod automation;
 mod manager;
+mod audio_texture;
 pub mod builtin;
 
 pub use types::*;
@@ -55,3 +57,4 @@ pub use registry::*;
 pub use runtime::*;
 pub use automation::*;
 pub use manager::*;
+pub use audio_texture::*;
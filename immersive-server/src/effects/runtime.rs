@@ -6,7 +6,7 @@
 
 use std::collections::HashMap;
 
-use super::automation::{BeatEnvelopeState, FftEnvelopeState, TimelineEnvelopeState};
+use super::automation::{BeatEnvelopeState, FftEnvelopeState, OnsetEnvelopeState, TimelineEnvelopeState};
 use super::traits::{EffectParams, GpuEffectRuntime};
 use super::{EffectInstance, EffectRegistry, EffectStack};
 
@@ -162,6 +162,12 @@ pub struct EffectStackRuntime {
     beat_envelope_states: HashMap<(u32, String), BeatEnvelopeState>,
     /// Timeline envelope states keyed by (effect_id, param_name) for time-based ramps
     timeline_envelope_states: HashMap<(u32, String), TimelineEnvelopeState>,
+    /// Onset envelope states keyed by (effect_id, param_name) for transient pulses
+    onset_envelope_states: HashMap<(u32, String), OnsetEnvelopeState>,
+    /// Shared audio spectrum/waveform texture handed to effect runtimes
+    audio_texture: Option<wgpu::TextureView>,
+    /// Playback position of the owning clip in seconds, for clip-time curves
+    clip_time: Option<f32>,
 }
 
 impl Default for EffectStackRuntime {
@@ -183,9 +189,25 @@ impl EffectStackRuntime {
             fft_envelope_states: HashMap::new(),
             beat_envelope_states: HashMap::new(),
             timeline_envelope_states: HashMap::new(),
+            onset_envelope_states: HashMap::new(),
+            audio_texture: None,
+            clip_time: None,
         }
     }
 
+    /// Set the playback position of the owning clip (seconds)
+    ///
+    /// Clip-time curves play back against this position; `None` (live
+    /// sources, environment effects) falls back to the effect time.
+    pub fn set_clip_time(&mut self, clip_time: Option<f32>) {
+        self.clip_time = clip_time;
+    }
+
+    /// Get the clip playback position set by `set_clip_time`
+    pub fn clip_time(&self) -> Option<f32> {
+        self.clip_time
+    }
+
     /// Get the current FFT envelope value for a parameter (for UI display)
     pub fn get_fft_envelope_value(&self, effect_id: u32, param_name: &str) -> Option<f32> {
         self.fft_envelope_states
@@ -207,6 +229,13 @@ impl EffectStackRuntime {
             .map(|e| e.value())
     }
 
+    /// Get the current onset envelope value for a parameter (for UI display)
+    pub fn get_onset_envelope_value(&self, effect_id: u32, param_name: &str) -> Option<f32> {
+        self.onset_envelope_states
+            .get(&(effect_id, param_name.to_string()))
+            .map(|e| e.value())
+    }
+
     /// Initialize GPU resources
     pub fn init(
         &mut self,
@@ -329,7 +358,8 @@ impl EffectStackRuntime {
     /// Sync effect runtimes with the effect stack
     ///
     /// Creates runtimes for new effects, removes runtimes for deleted effects.
-    /// Also cleans up orphaned envelope states.
+    /// Also cleans up orphaned envelope states and picks up the current
+    /// audio texture.
     pub fn sync_with_stack(
         &mut self,
         stack: &EffectStack,
@@ -337,7 +367,10 @@ impl EffectStackRuntime {
         device: &wgpu::Device,
         queue: &wgpu::Queue,
         format: wgpu::TextureFormat,
+        audio_texture: Option<&wgpu::TextureView>,
     ) {
+        self.audio_texture = audio_texture.cloned();
+
         // Remove runtimes for effects that no longer exist
         let effect_ids: std::collections::HashSet<_> = stack.effects.iter().map(|e| e.id).collect();
         self.effect_runtimes.retain(|id, _| effect_ids.contains(id));
@@ -346,6 +379,7 @@ impl EffectStackRuntime {
         self.fft_envelope_states.retain(|(eid, _), _| effect_ids.contains(eid));
         self.beat_envelope_states.retain(|(eid, _), _| effect_ids.contains(eid));
         self.timeline_envelope_states.retain(|(eid, _), _| effect_ids.contains(eid));
+        self.onset_envelope_states.retain(|(eid, _), _| effect_ids.contains(eid));
 
         // Create runtimes for new effects
         for effect in &stack.effects {
@@ -543,7 +577,9 @@ impl EffectStackRuntime {
                     &mut self.fft_envelope_states,
                     &mut self.beat_envelope_states,
                     &mut self.timeline_envelope_states,
+                    &mut self.onset_envelope_states,
                     effect.id,
+                    self.clip_time,
                 );
             } else {
                 params.pack_parameters(&effect.parameters);
@@ -554,6 +590,9 @@ impl EffectStackRuntime {
                 if let Some(gpu) = &mut entry.gpu {
                     // Update runtime with non-numeric params (like strings)
                     gpu.update_from_instance(effect, device, queue);
+                    if let Some(audio_texture) = &self.audio_texture {
+                        gpu.set_audio_texture(audio_texture);
+                    }
                     gpu.process(encoder, device, effect_input, effect_output, &params, queue);
                 }
             }
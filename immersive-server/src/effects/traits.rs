@@ -7,7 +7,7 @@
 
 use std::collections::HashMap;
 
-use super::automation::{BeatEnvelopeState, FftEnvelopeState, TimelineEnvelopeState};
+use super::automation::{BeatEnvelopeState, FftEnvelopeState, OnsetEnvelopeState, TimelineEnvelopeState};
 use super::{Parameter, ParameterMeta, ParameterValue, EffectInstance};
 
 /// The type of effect processor
@@ -253,7 +253,7 @@ impl EffectParams {
             Some(AutomationSource::Fft(fft)) => {
                 if let Some(manager) = audio_manager {
                     // Get raw FFT value with gain applied
-                    let raw = manager.get_band_value(fft.band);
+                    let raw = fft.sample(manager);
                     let gained = (raw * fft.gain).min(1.0);
 
                     // Map FFT value to parameter range
@@ -267,6 +267,18 @@ impl EffectParams {
                 // For now, return base value (full envelope support requires state)
                 base_value
             }
+            Some(AutomationSource::Onset(_onset)) => {
+                // Onset automation needs envelope state tracking
+                // For now, return base value (full envelope support requires state)
+                base_value
+            }
+            Some(AutomationSource::Curve(curve)) => {
+                // Curve: evaluate at the effect time (no clip position available here)
+                curve
+                    .evaluate(curve.playhead(self.time, clock))
+                    .map(|value| min + curve.range.remap(value) * range)
+                    .unwrap_or(base_value)
+            }
         }
     }
 
@@ -283,7 +295,10 @@ impl EffectParams {
     /// * `fft_envelopes` - Mutable map of FFT envelope states
     /// * `beat_envelopes` - Mutable map of Beat envelope states
     /// * `timeline_envelopes` - Mutable map of Timeline envelope states
+    /// * `onset_envelopes` - Mutable map of Onset envelope states
     /// * `effect_id` - Effect instance ID for envelope state keying
+    /// * `clip_time` - Clip playback position in seconds for clip-time curves
+    ///   (falls back to the effect time when `None`)
     pub fn pack_parameters_with_automation_and_envelopes(
         &mut self,
         parameters: &[Parameter],
@@ -293,7 +308,9 @@ impl EffectParams {
         fft_envelopes: &mut HashMap<(u32, String), FftEnvelopeState>,
         beat_envelopes: &mut HashMap<(u32, String), BeatEnvelopeState>,
         timeline_envelopes: &mut HashMap<(u32, String), TimelineEnvelopeState>,
+        onset_envelopes: &mut HashMap<(u32, String), OnsetEnvelopeState>,
         effect_id: u32,
+        clip_time: Option<f32>,
     ) -> usize {
         let mut offset = 0;
         for param in parameters {
@@ -306,7 +323,9 @@ impl EffectParams {
                 fft_envelopes,
                 beat_envelopes,
                 timeline_envelopes,
+                onset_envelopes,
                 effect_id,
+                clip_time,
             );
 
             match &param.value {
@@ -370,7 +389,9 @@ impl EffectParams {
         fft_envelopes: &mut HashMap<(u32, String), FftEnvelopeState>,
         beat_envelopes: &mut HashMap<(u32, String), BeatEnvelopeState>,
         timeline_envelopes: &mut HashMap<(u32, String), TimelineEnvelopeState>,
+        onset_envelopes: &mut HashMap<(u32, String), OnsetEnvelopeState>,
         effect_id: u32,
+        clip_time: Option<f32>,
     ) -> f32 {
         use super::types::AutomationSource;
 
@@ -405,7 +426,7 @@ impl EffectParams {
                     // FFT: get or create envelope state, update with raw value, use smoothed result
                     let key = (effect_id, param.meta.name.clone());
                     let envelope = fft_envelopes.entry(key).or_default();
-                    let raw = manager.get_band_value(fft.band);
+                    let raw = fft.sample(manager);
                     envelope.update(fft, raw, delta_time);
                     // Apply range limits to envelope value (0-1 after gain)
                     let ranged = fft.range.remap(envelope.value());
@@ -425,6 +446,30 @@ impl EffectParams {
                 // Map to parameter range
                 min + ranged * range
             }
+            Some(AutomationSource::Onset(onset)) => {
+                if let Some(manager) = audio_manager {
+                    // Onset: get or create envelope state, fire it on new onsets
+                    let key = (effect_id, param.meta.name.clone());
+                    let envelope = onset_envelopes.entry(key).or_default();
+                    envelope.update(onset, manager.onset_count(), manager.onset_strength(), delta_time);
+                    // Apply range limits to envelope value (0-1)
+                    let ranged = onset.range.remap(envelope.value());
+                    // Map to parameter range
+                    min + ranged * range
+                } else {
+                    base_value
+                }
+            }
+            Some(AutomationSource::Curve(curve)) => {
+                // Curve: stateless lookup at the clip position (or bar position)
+                let playhead = curve.playhead(clip_time.unwrap_or(self.time), clock);
+                match curve.evaluate(playhead) {
+                    // Apply range limits to the normalized curve value, then map to parameter range
+                    Some(value) => min + curve.range.remap(value) * range,
+                    // Empty curve: leave the parameter at its base value
+                    None => base_value,
+                }
+            }
         }
     }
 }
@@ -512,6 +557,16 @@ pub trait GpuEffectRuntime: Send {
     ) {
         // Default: no-op
     }
+
+    /// Provide the shared audio spectrum/waveform texture.
+    ///
+    /// Called before process() on every frame once audio analysis is
+    /// running. Effects that visualize audio keep a clone of the view.
+    ///
+    /// Default implementation is a no-op.
+    fn set_audio_texture(&mut self, _view: &wgpu::TextureView) {
+        // Default: no-op
+    }
 }
 
 /// Runtime trait for CPU-based effects
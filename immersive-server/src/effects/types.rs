@@ -4,7 +4,7 @@
 //! They are separate from runtime GPU resources, following the
 //! Layer/LayerRuntime pattern used elsewhere in the codebase.
 
-use crate::audio::AudioBand;
+use crate::audio::{format_frequency, AudioBand};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 /// Parameter value types supported by effects
@@ -539,6 +539,12 @@ pub struct FftSource {
     /// Output range limits
     #[serde(default)]
     pub range: AutomationRange,
+    /// Track one of the log-spaced analysis bands instead of `band`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub band_index: Option<usize>,
+    /// Track a custom frequency range (min_hz, max_hz); overrides `band_index`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub frequency_range: Option<(f32, f32)>,
 }
 
 impl Default for FftSource {
@@ -550,6 +556,8 @@ impl Default for FftSource {
             attack_ms: 10.0,
             release_ms: 100.0,
             range: AutomationRange::default(),
+            band_index: None,
+            frequency_range: None,
         }
     }
 }
@@ -562,6 +570,58 @@ impl FftSource {
             ..Default::default()
         }
     }
+
+    /// Create tracking a log-spaced analysis band
+    pub fn with_band_index(index: usize) -> Self {
+        Self {
+            band_index: Some(index),
+            ..Default::default()
+        }
+    }
+
+    /// Create tracking a custom frequency range
+    pub fn with_frequency_range(min_hz: f32, max_hz: f32) -> Self {
+        Self {
+            frequency_range: Some((min_hz, max_hz)),
+            ..Default::default()
+        }
+    }
+
+    /// Short display name of the tracked band or range
+    pub fn selection_name(&self) -> String {
+        if let Some((min_hz, max_hz)) = self.frequency_range {
+            format!("{} - {}", format_frequency(min_hz), format_frequency(max_hz))
+        } else if let Some(index) = self.band_index {
+            format!("Band {}", index + 1)
+        } else {
+            self.band.name().to_string()
+        }
+    }
+}
+
+/// Audio onset automation source (envelope fired by detected transients)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OnsetSource {
+    /// Minimum onset strength that fires the envelope (0.0-1.0)
+    pub threshold: f32,
+    /// Attack time in ms (how fast value rises to peak)
+    pub attack_ms: f32,
+    /// Release time in ms (how fast value falls back to zero)
+    pub release_ms: f32,
+    /// Output range limits
+    #[serde(default)]
+    pub range: AutomationRange,
+}
+
+impl Default for OnsetSource {
+    fn default() -> Self {
+        Self {
+            threshold: 0.0,
+            attack_ms: 5.0,
+            release_ms: 150.0,
+            range: AutomationRange::default(),
+        }
+    }
 }
 
 /// Range limits for automation output (normalized 0.0-1.0)
@@ -591,6 +651,16 @@ impl AutomationRange {
     pub fn remap(&self, value: f32) -> f32 {
         self.min_limit + value * (self.max_limit - self.min_limit)
     }
+
+    /// Inverse of `remap`: map a 0-1 value back into this range's 0-1 span
+    pub fn unmap(&self, value: f32) -> f32 {
+        let span = self.max_limit - self.min_limit;
+        if span.abs() > f32::EPSILON {
+            ((value - self.min_limit) / span).clamp(0.0, 1.0)
+        } else {
+            0.0
+        }
+    }
 }
 
 /// Direction of timeline ramp
@@ -718,6 +788,320 @@ impl Default for TimelineSource {
     }
 }
 
+/// Interpolation from a keyframe to the next one
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Default)]
+pub enum KeyframeEasing {
+    /// Keep the keyframe's value until the next keyframe
+    Hold,
+    /// Straight line to the next keyframe
+    #[default]
+    Linear,
+    /// Cubic bezier with control points (x1, y1) and (x2, y2), as in CSS `cubic-bezier()`
+    Bezier { x1: f32, y1: f32, x2: f32, y2: f32 },
+}
+
+impl KeyframeEasing {
+    /// Slow start, fast end
+    pub const EASE_IN: Self = Self::Bezier { x1: 0.42, y1: 0.0, x2: 1.0, y2: 1.0 };
+    /// Fast start, slow end
+    pub const EASE_OUT: Self = Self::Bezier { x1: 0.0, y1: 0.0, x2: 0.58, y2: 1.0 };
+    /// Slow start and end
+    pub const EASE_IN_OUT: Self = Self::Bezier { x1: 0.42, y1: 0.0, x2: 0.58, y2: 1.0 };
+
+    /// Get the preset easings with display names
+    pub fn presets() -> &'static [(&'static str, Self)] {
+        &[
+            ("Hold", Self::Hold),
+            ("Linear", Self::Linear),
+            ("Ease In", Self::EASE_IN),
+            ("Ease Out", Self::EASE_OUT),
+            ("Ease In/Out", Self::EASE_IN_OUT),
+        ]
+    }
+
+    /// Get display name
+    pub fn name(&self) -> &'static str {
+        Self::presets()
+            .iter()
+            .find(|(_, easing)| easing == self)
+            .map(|(name, _)| *name)
+            .unwrap_or("Bezier")
+    }
+
+    /// Apply easing to a 0-1 progress value
+    pub fn apply(&self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match *self {
+            Self::Hold => 0.0,
+            Self::Linear => t,
+            Self::Bezier { x1, y1, x2, y2 } => {
+                let x1 = x1.clamp(0.0, 1.0);
+                let x2 = x2.clamp(0.0, 1.0);
+                let s = solve_bezier_parameter(t, x1, x2);
+                cubic_bezier(s, y1, y2)
+            }
+        }
+    }
+}
+
+/// One coordinate of a cubic bezier from (0,0) to (1,1) with control values `c1`, `c2`
+fn cubic_bezier(s: f32, c1: f32, c2: f32) -> f32 {
+    let inv = 1.0 - s;
+    3.0 * inv * inv * s * c1 + 3.0 * inv * s * s * c2 + s * s * s
+}
+
+/// Find the bezier parameter whose x coordinate is `x` (Newton, then bisection)
+fn solve_bezier_parameter(x: f32, x1: f32, x2: f32) -> f32 {
+    let mut s = x;
+    for _ in 0..8 {
+        let error = cubic_bezier(s, x1, x2) - x;
+        if error.abs() < 1e-5 {
+            return s;
+        }
+        let inv = 1.0 - s;
+        let slope = 3.0 * inv * inv * x1 + 6.0 * inv * s * (x2 - x1) + 3.0 * s * s * (1.0 - x2);
+        if slope.abs() < 1e-6 {
+            break;
+        }
+        s = (s - error / slope).clamp(0.0, 1.0);
+    }
+
+    // x(s) is monotonic for control points within 0-1, so bisection always converges
+    let (mut low, mut high) = (0.0f32, 1.0f32);
+    s = x;
+    for _ in 0..32 {
+        let value = cubic_bezier(s, x1, x2);
+        if (value - x).abs() < 1e-5 {
+            break;
+        }
+        if value < x {
+            low = s;
+        } else {
+            high = s;
+        }
+        s = (low + high) / 2.0;
+    }
+    s
+}
+
+/// A point on an automation curve
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Keyframe {
+    /// Position in curve units (seconds or bars, see `CurveTimeBase`)
+    pub position: f32,
+    /// Normalized value (0.0-1.0 of the parameter range)
+    pub value: f32,
+    /// Interpolation towards the next keyframe
+    #[serde(default)]
+    pub easing: KeyframeEasing,
+}
+
+impl Keyframe {
+    /// Create a linear keyframe
+    pub fn new(position: f32, value: f32) -> Self {
+        Self {
+            position,
+            value,
+            easing: KeyframeEasing::Linear,
+        }
+    }
+}
+
+/// What a curve's positions are measured in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum CurveTimeBase {
+    /// Seconds of clip playback (effect time for live sources and environment effects)
+    #[default]
+    ClipTime,
+    /// Bars of the BPM clock
+    Bars,
+}
+
+impl CurveTimeBase {
+    /// Get all available time bases
+    pub fn all() -> &'static [Self] {
+        &[Self::ClipTime, Self::Bars]
+    }
+
+    /// Get display name
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::ClipTime => "Clip Time",
+            Self::Bars => "Bars",
+        }
+    }
+
+    /// Get the unit suffix for positions and lengths
+    pub fn unit(&self) -> &'static str {
+        match self {
+            Self::ClipTime => "s",
+            Self::Bars => " bars",
+        }
+    }
+}
+
+/// Keyframed automation curve, recorded live or drawn in the curve editor
+///
+/// The curve loops over `length`. Before the first keyframe it holds the
+/// first value, after the last keyframe it holds the last value.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CurveSource {
+    /// What keyframe positions are measured in
+    #[serde(default)]
+    pub time_base: CurveTimeBase,
+    /// Loop length in seconds or bars
+    pub length: f32,
+    /// Keyframes sorted by position
+    #[serde(default)]
+    pub keyframes: Vec<Keyframe>,
+    /// Output range limits
+    #[serde(default)]
+    pub range: AutomationRange,
+    /// Whether live parameter changes are recorded into the curve (not saved)
+    #[serde(skip)]
+    pub armed: bool,
+}
+
+impl Default for CurveSource {
+    fn default() -> Self {
+        Self {
+            time_base: CurveTimeBase::Bars,
+            length: 4.0,
+            keyframes: Vec::new(),
+            range: AutomationRange::default(),
+            armed: false,
+        }
+    }
+}
+
+impl CurveSource {
+    /// Minimum distance between keyframes (in curve units)
+    pub const MIN_KEYFRAME_SPACING: f32 = 0.001;
+
+    /// Wrap a position into the loop (0 to length)
+    pub fn wrap(&self, position: f32) -> f32 {
+        if self.length > 0.0 {
+            position.rem_euclid(self.length)
+        } else {
+            0.0
+        }
+    }
+
+    /// Evaluate the normalized value (0-1) at a position, or None without keyframes
+    pub fn evaluate(&self, position: f32) -> Option<f32> {
+        let first = self.keyframes.first()?;
+        let position = self.wrap(position);
+
+        if position <= first.position {
+            return Some(first.value);
+        }
+
+        // Index of the first keyframe after the position
+        let next = self.keyframes.partition_point(|k| k.position <= position);
+        let Some(to) = self.keyframes.get(next) else {
+            return self.keyframes.last().map(|k| k.value);
+        };
+        let from = &self.keyframes[next - 1];
+
+        let span = to.position - from.position;
+        let t = if span > 0.0 { (position - from.position) / span } else { 1.0 };
+        let eased = from.easing.apply(t);
+        Some(from.value + (to.value - from.value) * eased)
+    }
+
+    /// Insert a keyframe, replacing any keyframe at (nearly) the same position
+    ///
+    /// Returns the index of the inserted keyframe.
+    pub fn insert_keyframe(&mut self, keyframe: Keyframe) -> usize {
+        let keyframe = Keyframe {
+            position: self.wrap(keyframe.position),
+            value: keyframe.value.clamp(0.0, 1.0),
+            ..keyframe
+        };
+
+        if let Some(index) = self
+            .keyframes
+            .iter()
+            .position(|k| (k.position - keyframe.position).abs() < Self::MIN_KEYFRAME_SPACING)
+        {
+            self.keyframes[index] = keyframe;
+            return index;
+        }
+
+        let index = self.keyframes.partition_point(|k| k.position < keyframe.position);
+        self.keyframes.insert(index, keyframe);
+        index
+    }
+
+    /// Remove keyframes after `from` up to and including `to`, wrapping around the loop
+    pub fn clear_range(&mut self, from: f32, to: f32) {
+        let (from, to) = (self.wrap(from), self.wrap(to));
+        if from <= to {
+            self.keyframes.retain(|k| k.position <= from || k.position > to);
+        } else {
+            self.keyframes.retain(|k| k.position <= from && k.position > to);
+        }
+    }
+
+    /// Record a live value at `position`
+    ///
+    /// `previous` is the position of the previous recorded value in the
+    /// same pass; keyframes in between are overwritten.
+    pub fn record(&mut self, position: f32, value: f32, previous: Option<f32>) {
+        if let Some(previous) = previous {
+            self.clear_range(previous, position);
+        }
+        self.insert_keyframe(Keyframe::new(position, value));
+    }
+
+    /// Move a keyframe, keeping the list sorted
+    ///
+    /// Returns the keyframe's new index.
+    pub fn move_keyframe(&mut self, index: usize, position: f32, value: f32) -> usize {
+        if index >= self.keyframes.len() {
+            return index;
+        }
+        let mut keyframe = self.keyframes.remove(index);
+        keyframe.position = position.clamp(0.0, self.length.max(0.0));
+        keyframe.value = value.clamp(0.0, 1.0);
+        let index = self.keyframes.partition_point(|k| k.position < keyframe.position);
+        self.keyframes.insert(index, keyframe);
+        index
+    }
+
+    /// Remove linear keyframes that their neighbours already describe
+    ///
+    /// Recording produces a keyframe per UI update; this thins runs that
+    /// lie within `tolerance` of a straight line.
+    pub fn simplify(&mut self, tolerance: f32) {
+        if self.keyframes.len() < 3 {
+            return;
+        }
+
+        let mut kept: Vec<Keyframe> = Vec::with_capacity(self.keyframes.len());
+        kept.push(self.keyframes[0]);
+        for i in 1..self.keyframes.len() - 1 {
+            let previous = *kept.last().unwrap();
+            let current = self.keyframes[i];
+            let next = self.keyframes[i + 1];
+
+            let removable = previous.easing == KeyframeEasing::Linear
+                && current.easing == KeyframeEasing::Linear
+                && next.position > previous.position
+                && {
+                    let t = (current.position - previous.position) / (next.position - previous.position);
+                    let predicted = previous.value + (next.value - previous.value) * t;
+                    (predicted - current.value).abs() <= tolerance
+                };
+            if !removable {
+                kept.push(current);
+            }
+        }
+        kept.push(*self.keyframes.last().unwrap());
+        self.keyframes = kept;
+    }
+}
+
 /// All automation source types
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type", content = "data")]
@@ -730,6 +1114,10 @@ pub enum AutomationSource {
     Fft(FftSource),
     /// Time-based ramp (up/down, loop/hold)
     Timeline(TimelineSource),
+    /// Envelope triggered by audio onsets
+    Onset(OnsetSource),
+    /// Keyframed curve recorded live or drawn in the curve editor
+    Curve(CurveSource),
 }
 
 /// Effect instance in the stack
@@ -1009,4 +1397,81 @@ mod tests {
         assert_eq!(stack.active_effects().count(), 1);
         assert_eq!(stack.active_effects().next().unwrap().id, id1);
     }
+
+    #[test]
+    fn test_fft_source_selection() {
+        // Sources saved before band selection existed still load
+        let json = r#"{"band":"Mid","gain":1.0,"smoothing":0.3,"attack_ms":10.0,"release_ms":100.0}"#;
+        let fft: FftSource = serde_json::from_str(json).unwrap();
+        assert_eq!(fft.band, AudioBand::Mid);
+        assert!(fft.band_index.is_none() && fft.frequency_range.is_none());
+        assert!(!serde_json::to_string(&fft).unwrap().contains("band_index"));
+
+        assert_eq!(FftSource::with_band_index(3).selection_name(), "Band 4");
+        let range = FftSource::with_frequency_range(60.0, 2500.0);
+        assert_eq!(range.selection_name(), "60 Hz - 2.5k Hz");
+        let roundtrip: FftSource = serde_json::from_str(&serde_json::to_string(&range).unwrap()).unwrap();
+        assert_eq!(roundtrip.frequency_range, Some((60.0, 2500.0)));
+    }
+
+    #[test]
+    fn test_keyframe_easing() {
+        for easing in [KeyframeEasing::Linear, KeyframeEasing::EASE_IN, KeyframeEasing::EASE_IN_OUT] {
+            assert!(easing.apply(0.0).abs() < 1e-4);
+            assert!((easing.apply(1.0) - 1.0).abs() < 1e-4);
+        }
+        assert_eq!(KeyframeEasing::Hold.apply(0.9), 0.0);
+        assert!(KeyframeEasing::EASE_IN.apply(0.25) < 0.25);
+        assert!(KeyframeEasing::EASE_OUT.apply(0.25) > 0.25);
+        assert!((KeyframeEasing::EASE_IN_OUT.apply(0.5) - 0.5).abs() < 1e-3);
+        assert_eq!(KeyframeEasing::EASE_OUT.name(), "Ease Out");
+    }
+
+    #[test]
+    fn test_curve_evaluate_and_record() {
+        let mut curve = CurveSource::default();
+        assert!(curve.evaluate(1.0).is_none());
+
+        curve.insert_keyframe(Keyframe::new(1.0, 0.2));
+        curve.insert_keyframe(Keyframe::new(3.0, 0.6));
+        assert_eq!(curve.evaluate(0.5), Some(0.2));
+        assert!((curve.evaluate(2.0).unwrap() - 0.4).abs() < 1e-5);
+        assert_eq!(curve.evaluate(3.5), Some(0.6));
+        // Loops over the length
+        assert!((curve.evaluate(6.0).unwrap() - 0.4).abs() < 1e-5);
+
+        // A recording pass overwrites keyframes it sweeps over
+        curve.record(0.5, 0.9, None);
+        curve.record(2.0, 0.9, Some(0.5));
+        let positions: Vec<f32> = curve.keyframes.iter().map(|k| k.position).collect();
+        assert_eq!(positions, vec![0.5, 2.0, 3.0]);
+
+        // Wrapping pass clears the end and the start of the loop
+        curve.record(3.5, 0.1, None);
+        curve.record(0.6, 0.1, Some(3.5));
+        let positions: Vec<f32> = curve.keyframes.iter().map(|k| k.position).collect();
+        assert_eq!(positions, vec![0.6, 2.0, 3.0, 3.5]);
+    }
+
+    #[test]
+    fn test_curve_simplify_and_serialization() {
+        let mut curve = CurveSource::default();
+        for i in 0..=10 {
+            curve.record(i as f32 * 0.1, i as f32 * 0.05, None);
+        }
+        curve.simplify(0.001);
+        assert_eq!(curve.keyframes.len(), 2);
+
+        curve.keyframes[0].easing = KeyframeEasing::EASE_IN;
+        curve.armed = true;
+        let source = AutomationSource::Curve(curve);
+        let json = serde_json::to_string(&source).unwrap();
+        assert!(!json.contains("armed"));
+        let AutomationSource::Curve(loaded) = serde_json::from_str(&json).unwrap() else {
+            panic!("expected curve");
+        };
+        assert_eq!(loaded.time_base, CurveTimeBase::Bars);
+        assert_eq!(loaded.keyframes[0].easing, KeyframeEasing::EASE_IN);
+        assert!(!loaded.armed);
+    }
 }
@@ -37,8 +37,8 @@ pub use audio::{AudioBand, AudioManager, FftData};
 pub use previs::{OrbitCamera, PrevisMesh, PrevisRenderer, PrevisSettings, PrevisVertex, SurfaceType};
 pub use gpu_context::{GpuContext, WindowGpuContext};
 pub use output::{
-    EdgeBlendConfig, EdgeBlendRegion, MaskShape, OutputColorCorrection, OutputDevice, OutputManager,
-    Point2D, Rect, Screen, ScreenId, ScreenRuntime, Slice, SliceColorCorrection, SliceId,
+    EdgeBlendConfig, EdgeBlendCurve, EdgeBlendRegion, LutInterpolation, MaskShape, OutputColorCorrection, OutputDevice,
+    OutputLut, OutputManager, Point2D, Rect, Screen, ScreenId, ScreenRuntime, Slice, SliceColorCorrection, SliceId,
     SliceInput, SliceMask, SliceOutput, SliceParams, SliceRuntime, WarpInterpolation, WarpMesh,
     WarpPoint,
 };
@@ -904,6 +904,9 @@ impl ApplicationHandler for ImmersiveApp {
             // Sync OMT broadcast state from settings
             app.sync_omt_broadcast_from_settings();
 
+            // Apply FFT gain and band configuration from settings
+            app.sync_audio_analysis_from_settings();
+
             // Start API server if enabled
             if app.settings.api_server_enabled {
                 app.start_api_server();
@@ -1484,6 +1487,7 @@ impl ApplicationHandler for ImmersiveApp {
                                         app.restore_layers_from_settings();
                                         app.sync_output_manager_from_settings();
                                         app.sync_omt_broadcast_from_settings();
+                                        app.sync_audio_analysis_from_settings();
                                         // Check for layout mismatch (will show dialog if different)
                                         app.check_layout_mismatch();
                                         preferences.set_last_opened(&path);
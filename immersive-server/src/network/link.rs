@@ -0,0 +1,977 @@
+//! Ableton Link session support.
+//!
+//! A native Rust peer for the Link protocol so the BPM clock can follow the
+//! tempo, beat phase and start/stop state of a Link session on the local
+//! network (Ableton Live, Bitwig, Traktor, other Link-enabled apps).
+//!
+//! # Protocol overview
+//!
+//! - **Discovery**: peers multicast "alive" messages to `224.76.78.75:20808`
+//!   containing their session id, timeline, start/stop state and the unicast
+//!   endpoint used for clock measurement.
+//! - **Measurement**: to join another session a peer pings one of its members
+//!   and derives the offset between its own host clock and that session's
+//!   shared "ghost time" from the round trips.
+//! - **Session selection**: when two sessions meet, the older one (larger ghost
+//!   time) wins, so a newcomer never disturbs an established session.
+//! - **Timeline**: `(tempo, beat_origin, time_origin)` in ghost time. Every
+//!   peer evaluates the same timeline, which keeps beat phase identical
+//!   everywhere without any drift. The timeline with the latest `time_origin`
+//!   wins, so the last tempo change propagates to all peers.
+
+use std::collections::{HashMap, HashSet};
+use std::net::{Ipv4Addr, SocketAddr, SocketAddrV4, UdpSocket};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use socket2::{Domain, Protocol, Socket, Type};
+
+/// Link discovery multicast group
+pub const LINK_MULTICAST_ADDR: Ipv4Addr = Ipv4Addr::new(224, 76, 78, 75);
+/// Link discovery port
+pub const LINK_PORT: u16 = 20808;
+
+/// Discovery message header ("_asdp_v" + protocol version 1)
+const DISCOVERY_HEADER: [u8; 8] = *b"_asdp_v\x01";
+/// Measurement message header ("_link_v" + protocol version 1)
+const MEASUREMENT_HEADER: [u8; 8] = *b"_link_v\x01";
+
+/// Discovery message types
+const MSG_ALIVE: u8 = 1;
+const MSG_RESPONSE: u8 = 2;
+const MSG_BYEBYE: u8 = 3;
+
+/// Measurement message types
+const MSG_PING: u8 = 1;
+const MSG_PONG: u8 = 2;
+
+/// Payload entry keys (big-endian FourCC)
+const KEY_TIMELINE: u32 = u32::from_be_bytes(*b"tmln");
+const KEY_SESSION: u32 = u32::from_be_bytes(*b"sess");
+const KEY_START_STOP: u32 = u32::from_be_bytes(*b"stst");
+const KEY_ENDPOINT_V4: u32 = u32::from_be_bytes(*b"mep4");
+const KEY_HOST_TIME: u32 = u32::from_be_bytes(*b"__ht");
+const KEY_GHOST_TIME: u32 = u32::from_be_bytes(*b"__gt");
+const KEY_PREV_GHOST_TIME: u32 = u32::from_be_bytes(*b"_pgt");
+
+/// Time-to-live advertised in alive messages (seconds)
+const PEER_TTL_SECS: u8 = 5;
+/// Interval between alive broadcasts
+const BROADCAST_INTERVAL: Duration = Duration::from_millis(250);
+/// Sessions whose ghost times differ by less than this are considered equal
+const SESSION_EPS_MICROS: i64 = 500_000;
+/// Number of ping/pong round trips per measurement
+const MEASUREMENT_ROUNDS: usize = 8;
+/// How long to wait for a single pong
+const MEASUREMENT_TIMEOUT: Duration = Duration::from_millis(50);
+/// Tempo range supported by Link
+const MIN_BPM: f64 = 20.0;
+const MAX_BPM: f64 = 999.0;
+
+/// Error type for Link operations.
+#[derive(Debug, Clone)]
+pub struct LinkError(pub String);
+
+impl std::fmt::Display for LinkError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Link error: {}", self.0)
+    }
+}
+
+impl std::error::Error for LinkError {}
+
+/// Monotonic host time in microseconds (shared epoch for the whole process)
+fn host_micros() -> i64 {
+    static EPOCH: OnceLock<Instant> = OnceLock::new();
+    EPOCH.get_or_init(Instant::now).elapsed().as_micros() as i64
+}
+
+/// 8-byte identifier used for both peers and sessions
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct NodeId(pub [u8; 8]);
+
+impl NodeId {
+    /// Generate a random printable node id (matches Link's alphanumeric ids)
+    pub fn random() -> Self {
+        use rand::Rng;
+        const CHARS: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789";
+        let mut rng = rand::rng();
+        let mut id = [0u8; 8];
+        for byte in id.iter_mut() {
+            *byte = CHARS[rng.random_range(0..CHARS.len())];
+        }
+        Self(id)
+    }
+}
+
+/// Shared session timeline: maps ghost time to beats
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Timeline {
+    /// Tempo expressed as microseconds per beat
+    pub micros_per_beat: i64,
+    /// Beat at `time_origin`, in micro-beats
+    pub beat_origin: i64,
+    /// Ghost time (microseconds) at which `beat_origin` occurs
+    pub time_origin: i64,
+}
+
+impl Timeline {
+    /// Create a timeline at the given tempo starting at beat 0 at `time_origin`
+    pub fn new(bpm: f64, time_origin: i64) -> Self {
+        Self {
+            micros_per_beat: bpm_to_micros_per_beat(bpm),
+            beat_origin: 0,
+            time_origin,
+        }
+    }
+
+    /// Tempo in beats per minute
+    pub fn bpm(&self) -> f64 {
+        60_000_000.0 / self.micros_per_beat as f64
+    }
+
+    /// Beat position at the given ghost time
+    pub fn beats_at(&self, ghost_micros: i64) -> f64 {
+        self.beat_origin as f64 / 1_000_000.0
+            + (ghost_micros - self.time_origin) as f64 / self.micros_per_beat as f64
+    }
+
+    /// Ghost time at which the given beat occurs
+    pub fn time_at(&self, beats: f64) -> i64 {
+        let delta_beats = beats - self.beat_origin as f64 / 1_000_000.0;
+        self.time_origin + (delta_beats * self.micros_per_beat as f64).round() as i64
+    }
+
+    /// Change tempo at `ghost_micros` while keeping the beat position continuous
+    pub fn with_tempo(&self, bpm: f64, ghost_micros: i64) -> Self {
+        Self {
+            micros_per_beat: bpm_to_micros_per_beat(bpm),
+            beat_origin: (self.beats_at(ghost_micros) * 1_000_000.0).round() as i64,
+            time_origin: ghost_micros,
+        }
+    }
+}
+
+fn bpm_to_micros_per_beat(bpm: f64) -> i64 {
+    (60_000_000.0 / bpm.clamp(MIN_BPM, MAX_BPM)).round() as i64
+}
+
+/// Shared transport (start/stop) state
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct StartStopState {
+    /// Whether the session transport is playing
+    pub is_playing: bool,
+    /// Beat at which the state changed, in micro-beats
+    pub beats: i64,
+    /// Ghost time of the change (later changes win)
+    pub timestamp: i64,
+}
+
+/// Decoded payload of a discovery or measurement message
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct Payload {
+    timeline: Option<Timeline>,
+    session: Option<NodeId>,
+    start_stop: Option<StartStopState>,
+    endpoint: Option<SocketAddrV4>,
+    host_time: Option<i64>,
+    ghost_time: Option<i64>,
+    prev_ghost_time: Option<i64>,
+}
+
+impl Payload {
+    fn encode(&self, out: &mut Vec<u8>) {
+        fn entry(out: &mut Vec<u8>, key: u32, value: &[u8]) {
+            out.extend_from_slice(&key.to_be_bytes());
+            out.extend_from_slice(&(value.len() as u32).to_be_bytes());
+            out.extend_from_slice(value);
+        }
+
+        if let Some(t) = self.timeline {
+            let mut v = Vec::with_capacity(24);
+            v.extend_from_slice(&t.micros_per_beat.to_be_bytes());
+            v.extend_from_slice(&t.beat_origin.to_be_bytes());
+            v.extend_from_slice(&t.time_origin.to_be_bytes());
+            entry(out, KEY_TIMELINE, &v);
+        }
+        if let Some(id) = self.session {
+            entry(out, KEY_SESSION, &id.0);
+        }
+        if let Some(s) = self.start_stop {
+            let mut v = Vec::with_capacity(17);
+            v.push(s.is_playing as u8);
+            v.extend_from_slice(&s.beats.to_be_bytes());
+            v.extend_from_slice(&s.timestamp.to_be_bytes());
+            entry(out, KEY_START_STOP, &v);
+        }
+        if let Some(ep) = self.endpoint {
+            let mut v = Vec::with_capacity(6);
+            v.extend_from_slice(&ep.ip().octets());
+            v.extend_from_slice(&ep.port().to_be_bytes());
+            entry(out, KEY_ENDPOINT_V4, &v);
+        }
+        if let Some(t) = self.host_time {
+            entry(out, KEY_HOST_TIME, &t.to_be_bytes());
+        }
+        if let Some(t) = self.ghost_time {
+            entry(out, KEY_GHOST_TIME, &t.to_be_bytes());
+        }
+        if let Some(t) = self.prev_ghost_time {
+            entry(out, KEY_PREV_GHOST_TIME, &t.to_be_bytes());
+        }
+    }
+
+    /// Decode payload entries, skipping unknown keys (forward compatible)
+    fn decode(mut data: &[u8]) -> Option<Self> {
+        fn i64_at(v: &[u8], offset: usize) -> Option<i64> {
+            v.get(offset..offset + 8)
+                .map(|b| i64::from_be_bytes(b.try_into().unwrap()))
+        }
+
+        let mut payload = Payload::default();
+        while !data.is_empty() {
+            if data.len() < 8 {
+                return None;
+            }
+            let key = u32::from_be_bytes(data[0..4].try_into().unwrap());
+            let size = u32::from_be_bytes(data[4..8].try_into().unwrap()) as usize;
+            let value = data.get(8..8 + size)?;
+            data = &data[8 + size..];
+
+            match key {
+                KEY_TIMELINE => {
+                    payload.timeline = Some(Timeline {
+                        micros_per_beat: i64_at(value, 0)?.max(1),
+                        beat_origin: i64_at(value, 8)?,
+                        time_origin: i64_at(value, 16)?,
+                    });
+                }
+                KEY_SESSION => {
+                    payload.session = Some(NodeId(value.get(0..8)?.try_into().unwrap()));
+                }
+                KEY_START_STOP => {
+                    payload.start_stop = Some(StartStopState {
+                        is_playing: *value.first()? != 0,
+                        beats: i64_at(value, 1)?,
+                        timestamp: i64_at(value, 9)?,
+                    });
+                }
+                KEY_ENDPOINT_V4 => {
+                    let ip: [u8; 4] = value.get(0..4)?.try_into().unwrap();
+                    let port = u16::from_be_bytes(value.get(4..6)?.try_into().unwrap());
+                    payload.endpoint = Some(SocketAddrV4::new(Ipv4Addr::from(ip), port));
+                }
+                KEY_HOST_TIME => payload.host_time = Some(i64_at(value, 0)?),
+                KEY_GHOST_TIME => payload.ghost_time = Some(i64_at(value, 0)?),
+                KEY_PREV_GHOST_TIME => payload.prev_ghost_time = Some(i64_at(value, 0)?),
+                _ => {}
+            }
+        }
+        Some(payload)
+    }
+}
+
+/// Discovery message (alive / response / byebye)
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct DiscoveryMessage {
+    message_type: u8,
+    ttl: u8,
+    group_id: u16,
+    node_id: NodeId,
+    payload: Payload,
+}
+
+impl DiscoveryMessage {
+    fn encode(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(128);
+        out.extend_from_slice(&DISCOVERY_HEADER);
+        out.push(self.message_type);
+        out.push(self.ttl);
+        out.extend_from_slice(&self.group_id.to_be_bytes());
+        out.extend_from_slice(&self.node_id.0);
+        self.payload.encode(&mut out);
+        out
+    }
+
+    fn decode(data: &[u8]) -> Option<Self> {
+        if data.len() < 20 || data[0..8] != DISCOVERY_HEADER {
+            return None;
+        }
+        Some(Self {
+            message_type: data[8],
+            ttl: data[9],
+            group_id: u16::from_be_bytes([data[10], data[11]]),
+            node_id: NodeId(data[12..20].try_into().unwrap()),
+            payload: Payload::decode(&data[20..])?,
+        })
+    }
+}
+
+fn encode_measurement(message_type: u8, payload: &Payload) -> Vec<u8> {
+    let mut out = Vec::with_capacity(64);
+    out.extend_from_slice(&MEASUREMENT_HEADER);
+    out.push(message_type);
+    payload.encode(&mut out);
+    out
+}
+
+fn decode_measurement(data: &[u8]) -> Option<(u8, Payload)> {
+    if data.len() < 9 || data[0..8] != MEASUREMENT_HEADER {
+        return None;
+    }
+    Some((data[8], Payload::decode(&data[9..])?))
+}
+
+/// Snapshot of the Link session, captured once per frame by the app
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LinkState {
+    /// Session tempo
+    pub bpm: f64,
+    /// Session beat position at capture time
+    pub beat: f64,
+    /// Whether the session transport is playing
+    pub is_playing: bool,
+    /// Number of other peers in our session
+    pub num_peers: usize,
+}
+
+/// Information about a remote peer learned from discovery
+#[derive(Debug, Clone)]
+struct PeerInfo {
+    session_id: NodeId,
+    timeline: Timeline,
+    start_stop: Option<StartStopState>,
+    expires: Instant,
+}
+
+/// Mutable session state shared between the app and the network threads
+struct SessionState {
+    node_id: NodeId,
+    session_id: NodeId,
+    /// ghost_time = host_time + ghost_offset
+    ghost_offset: i64,
+    timeline: Timeline,
+    start_stop: StartStopState,
+    peers: HashMap<NodeId, PeerInfo>,
+    /// Sessions currently being measured (avoid duplicate measurements)
+    measuring: HashSet<NodeId>,
+    /// Local state changed; broadcast before the next interval
+    dirty: bool,
+}
+
+impl SessionState {
+    fn ghost_now(&self) -> i64 {
+        host_micros() + self.ghost_offset
+    }
+
+    fn num_peers(&self) -> usize {
+        self.peers
+            .values()
+            .filter(|p| p.session_id == self.session_id)
+            .count()
+    }
+
+    /// Apply a timeline from a member of our session (latest change wins)
+    fn merge_timeline(&mut self, timeline: Timeline) {
+        if timeline.time_origin > self.timeline.time_origin {
+            self.timeline = timeline;
+        }
+    }
+
+    /// Apply a start/stop state from a member of our session (latest change wins)
+    fn merge_start_stop(&mut self, state: StartStopState) {
+        if state.timestamp > self.start_stop.timestamp {
+            self.start_stop = state;
+        }
+    }
+}
+
+/// A Link peer participating in a session on the local network.
+///
+/// Network work happens on background threads; the app calls `capture()` once
+/// per frame and drives the BPM clock from the returned state.
+pub struct LinkSession {
+    state: Arc<Mutex<SessionState>>,
+    running: Arc<AtomicBool>,
+    threads: Vec<JoinHandle<()>>,
+}
+
+impl LinkSession {
+    /// Join (or found) a Link session at the given initial tempo.
+    pub fn start(bpm: f64) -> Result<Self, LinkError> {
+        Self::start_with_transport(bpm, DiscoveryTransport::multicast()?)
+    }
+
+    /// Start a peer that discovers others over the given transport
+    fn start_with_transport(bpm: f64, transport: DiscoveryTransport) -> Result<Self, LinkError> {
+        let measurement = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0))
+            .map_err(|e| LinkError(format!("Failed to bind measurement socket: {}", e)))?;
+        measurement
+            .set_read_timeout(Some(Duration::from_millis(100)))
+            .map_err(|e| LinkError(e.to_string()))?;
+        let measurement_port = measurement
+            .local_addr()
+            .map_err(|e| LinkError(e.to_string()))?
+            .port();
+
+        let node_id = NodeId::random();
+        // A new session starts its ghost time at zero, so older sessions
+        // always have larger ghost times and win when sessions meet.
+        let ghost_offset = -host_micros();
+        let state = Arc::new(Mutex::new(SessionState {
+            node_id,
+            session_id: node_id,
+            ghost_offset,
+            timeline: Timeline::new(bpm, 0),
+            start_stop: StartStopState::default(),
+            peers: HashMap::new(),
+            measuring: HashSet::new(),
+            dirty: true,
+        }));
+        let running = Arc::new(AtomicBool::new(true));
+
+        let endpoint = SocketAddrV4::new(transport.local_ip, measurement_port);
+
+        let discovery_thread = {
+            let state = Arc::clone(&state);
+            let running = Arc::clone(&running);
+            thread::Builder::new()
+                .name("link-discovery".to_string())
+                .spawn(move || discovery_loop(transport, state, running, endpoint))
+                .map_err(|e| LinkError(e.to_string()))?
+        };
+        let measurement_thread = {
+            let state = Arc::clone(&state);
+            let running = Arc::clone(&running);
+            thread::Builder::new()
+                .name("link-measurement".to_string())
+                .spawn(move || measurement_responder_loop(measurement, state, running))
+                .map_err(|e| LinkError(e.to_string()))?
+        };
+
+        tracing::info!(
+            "🔗 Link enabled (node {}, measurement port {})",
+            String::from_utf8_lossy(&node_id.0),
+            measurement_port
+        );
+
+        Ok(Self {
+            state,
+            running,
+            threads: vec![discovery_thread, measurement_thread],
+        })
+    }
+
+    /// Capture the current session state
+    pub fn capture(&self) -> LinkState {
+        let state = self.state.lock().unwrap();
+        let ghost = state.ghost_now();
+        LinkState {
+            bpm: state.timeline.bpm(),
+            beat: state.timeline.beats_at(ghost),
+            is_playing: state.start_stop.is_playing,
+            num_peers: state.num_peers(),
+        }
+    }
+
+    /// Propose a new tempo to the session
+    pub fn set_tempo(&self, bpm: f64) {
+        let mut state = self.state.lock().unwrap();
+        let ghost = state.ghost_now();
+        let timeline = state.timeline.with_tempo(bpm, ghost);
+        if timeline.micros_per_beat != state.timeline.micros_per_beat {
+            state.timeline = timeline;
+            state.dirty = true;
+        }
+    }
+
+    /// Propose a transport start/stop to the session
+    pub fn set_playing(&self, is_playing: bool) {
+        let mut state = self.state.lock().unwrap();
+        if state.start_stop.is_playing == is_playing {
+            return;
+        }
+        let ghost = state.ghost_now();
+        state.start_stop = StartStopState {
+            is_playing,
+            beats: (state.timeline.beats_at(ghost) * 1_000_000.0).round() as i64,
+            timestamp: ghost,
+        };
+        state.dirty = true;
+    }
+
+    /// Number of other peers in our session
+    pub fn num_peers(&self) -> usize {
+        self.state.lock().unwrap().num_peers()
+    }
+}
+
+impl Drop for LinkSession {
+    fn drop(&mut self) {
+        self.running.store(false, Ordering::Release);
+        for handle in self.threads.drain(..) {
+            let _ = handle.join();
+        }
+        tracing::info!("🔗 Link disabled");
+    }
+}
+
+/// Socket and destinations used for discovery messages
+struct DiscoveryTransport {
+    socket: UdpSocket,
+    /// Where alive and byebye messages are sent
+    targets: Vec<SocketAddrV4>,
+    /// Address advertised for measurement
+    local_ip: Ipv4Addr,
+}
+
+impl DiscoveryTransport {
+    /// The standard Link multicast group (shared port, loopback enabled)
+    fn multicast() -> Result<Self, LinkError> {
+        let err = |e: std::io::Error| LinkError(format!("Discovery socket: {}", e));
+        let socket = Socket::new(Domain::IPV4, Type::DGRAM, Some(Protocol::UDP)).map_err(err)?;
+        socket.set_reuse_address(true).map_err(err)?;
+        #[cfg(unix)]
+        socket.set_reuse_port(true).map_err(err)?;
+        let bind_addr = SocketAddrV4::new(Ipv4Addr::UNSPECIFIED, LINK_PORT);
+        socket.bind(&bind_addr.into()).map_err(err)?;
+        socket
+            .join_multicast_v4(&LINK_MULTICAST_ADDR, &Ipv4Addr::UNSPECIFIED)
+            .map_err(err)?;
+        socket.set_multicast_loop_v4(true).map_err(err)?;
+        socket.set_multicast_ttl_v4(1).map_err(err)?;
+        socket
+            .set_read_timeout(Some(Duration::from_millis(50)))
+            .map_err(err)?;
+
+        Ok(Self {
+            socket: socket.into(),
+            targets: vec![SocketAddrV4::new(LINK_MULTICAST_ADDR, LINK_PORT)],
+            local_ip: local_ipv4(),
+        })
+    }
+}
+
+/// Best-effort local IPv4 address advertised for measurement
+fn local_ipv4() -> Ipv4Addr {
+    UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0))
+        .and_then(|s| {
+            s.connect((LINK_MULTICAST_ADDR, LINK_PORT))?;
+            s.local_addr()
+        })
+        .ok()
+        .and_then(|addr| match addr {
+            SocketAddr::V4(v4) if !v4.ip().is_unspecified() => Some(*v4.ip()),
+            _ => None,
+        })
+        .unwrap_or(Ipv4Addr::LOCALHOST)
+}
+
+fn build_discovery_message(state: &SessionState, message_type: u8, endpoint: SocketAddrV4) -> Vec<u8> {
+    DiscoveryMessage {
+        message_type,
+        ttl: PEER_TTL_SECS,
+        group_id: 0,
+        node_id: state.node_id,
+        payload: Payload {
+            timeline: Some(state.timeline),
+            session: Some(state.session_id),
+            start_stop: Some(state.start_stop),
+            endpoint: Some(endpoint),
+            ..Default::default()
+        },
+    }
+    .encode()
+}
+
+/// Broadcast our state, receive peers, and start measurements of foreign sessions
+fn discovery_loop(
+    transport: DiscoveryTransport,
+    state: Arc<Mutex<SessionState>>,
+    running: Arc<AtomicBool>,
+    endpoint: SocketAddrV4,
+) {
+    let socket = &transport.socket;
+    let mut last_broadcast = Instant::now() - BROADCAST_INTERVAL;
+    let mut buf = [0u8; 512];
+
+    while running.load(Ordering::Acquire) {
+        // Broadcast on interval or immediately after local changes
+        {
+            let mut s = state.lock().unwrap();
+            if s.dirty || last_broadcast.elapsed() >= BROADCAST_INTERVAL {
+                let msg = build_discovery_message(&s, MSG_ALIVE, endpoint);
+                for target in &transport.targets {
+                    if let Err(e) = socket.send_to(&msg, target) {
+                        tracing::debug!("Link: broadcast failed: {}", e);
+                    }
+                }
+                s.dirty = false;
+                last_broadcast = Instant::now();
+            }
+
+            // Expire peers that stopped announcing themselves
+            let now = Instant::now();
+            s.peers.retain(|_, p| p.expires > now);
+        }
+
+        let (len, from) = match socket.recv_from(&mut buf) {
+            Ok(r) => r,
+            Err(_) => continue, // Timeout - loop around to broadcast/expire
+        };
+        let Some(msg) = DiscoveryMessage::decode(&buf[..len]) else {
+            continue;
+        };
+
+        let mut s = state.lock().unwrap();
+        if msg.node_id == s.node_id {
+            continue;
+        }
+
+        if msg.message_type == MSG_BYEBYE {
+            s.peers.remove(&msg.node_id);
+            continue;
+        }
+
+        let (Some(session_id), Some(timeline)) = (msg.payload.session, msg.payload.timeline) else {
+            continue;
+        };
+        // Prefer the advertised endpoint; fall back to the sender's address
+        let endpoint_addr = match (msg.payload.endpoint, from) {
+            (Some(ep), SocketAddr::V4(src)) if ep.ip().is_unspecified() => SocketAddrV4::new(*src.ip(), ep.port()),
+            (Some(ep), _) => ep,
+            (None, _) => continue,
+        };
+
+        let is_new_peer = !s.peers.contains_key(&msg.node_id);
+        s.peers.insert(
+            msg.node_id,
+            PeerInfo {
+                session_id,
+                timeline,
+                start_stop: msg.payload.start_stop,
+                expires: Instant::now() + Duration::from_secs(msg.ttl.max(1) as u64),
+            },
+        );
+
+        if session_id == s.session_id {
+            s.merge_timeline(timeline);
+            if let Some(start_stop) = msg.payload.start_stop {
+                s.merge_start_stop(start_stop);
+            }
+        } else if !s.measuring.contains(&session_id) {
+            // Foreign session - measure it to decide whether to join
+            s.measuring.insert(session_id);
+            let state = Arc::clone(&state);
+            let _ = thread::Builder::new()
+                .name("link-measure".to_string())
+                .spawn(move || measure_and_maybe_join(state, session_id, endpoint_addr));
+        }
+
+        // Answer newcomers right away so they learn about us quickly
+        if is_new_peer && msg.message_type == MSG_ALIVE {
+            let response = build_discovery_message(&s, MSG_RESPONSE, endpoint);
+            let _ = socket.send_to(&response, from);
+        }
+    }
+
+    // Say goodbye so peers drop us immediately
+    let s = state.lock().unwrap();
+    let msg = build_discovery_message(&s, MSG_BYEBYE, endpoint);
+    for target in &transport.targets {
+        let _ = socket.send_to(&msg, target);
+    }
+}
+
+/// Answer pings with our session's ghost time
+fn measurement_responder_loop(socket: UdpSocket, state: Arc<Mutex<SessionState>>, running: Arc<AtomicBool>) {
+    let mut buf = [0u8; 256];
+    while running.load(Ordering::Acquire) {
+        let Ok((len, from)) = socket.recv_from(&mut buf) else {
+            continue;
+        };
+        let Some((MSG_PING, ping)) = decode_measurement(&buf[..len]) else {
+            continue;
+        };
+
+        let (session_id, ghost) = {
+            let s = state.lock().unwrap();
+            (s.session_id, s.ghost_now())
+        };
+        let pong = Payload {
+            session: Some(session_id),
+            ghost_time: Some(ghost),
+            host_time: ping.host_time,
+            prev_ghost_time: ping.prev_ghost_time,
+            ..Default::default()
+        };
+        let _ = socket.send_to(&encode_measurement(MSG_PONG, &pong), from);
+    }
+}
+
+/// Measure the ghost time offset of another session via ping/pong round trips.
+///
+/// Each round trip gives `offset = ghost - (send + receive) / 2`; the median of
+/// all rounds rejects outliers caused by scheduling hiccups.
+fn measure_session_offset(endpoint: SocketAddrV4, session_id: NodeId) -> Option<i64> {
+    let socket = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0)).ok()?;
+    socket.set_read_timeout(Some(MEASUREMENT_TIMEOUT)).ok()?;
+    let mut buf = [0u8; 256];
+    let mut samples = Vec::with_capacity(MEASUREMENT_ROUNDS);
+    let mut prev_ghost = None;
+
+    for _ in 0..MEASUREMENT_ROUNDS * 2 {
+        if samples.len() >= MEASUREMENT_ROUNDS {
+            break;
+        }
+        let sent = host_micros();
+        let ping = Payload {
+            host_time: Some(sent),
+            prev_ghost_time: prev_ghost,
+            ..Default::default()
+        };
+        socket.send_to(&encode_measurement(MSG_PING, &ping), endpoint).ok()?;
+
+        let Ok(len) = socket.recv(&mut buf) else {
+            continue;
+        };
+        let received = host_micros();
+        let Some((MSG_PONG, pong)) = decode_measurement(&buf[..len]) else {
+            continue;
+        };
+        if pong.session != Some(session_id) || pong.host_time != Some(sent) {
+            continue;
+        }
+        if let Some(ghost) = pong.ghost_time {
+            samples.push(ghost - (sent + received) / 2);
+            prev_ghost = Some(ghost);
+        }
+    }
+
+    if samples.is_empty() {
+        return None;
+    }
+    samples.sort_unstable();
+    Some(samples[samples.len() / 2])
+}
+
+/// Decide whether a newly measured session should replace ours
+fn should_join(our_ghost: i64, their_ghost: i64, our_session: NodeId, their_session: NodeId) -> bool {
+    let diff = their_ghost - our_ghost;
+    diff > SESSION_EPS_MICROS || (diff.abs() < SESSION_EPS_MICROS && their_session < our_session)
+}
+
+fn measure_and_maybe_join(state: Arc<Mutex<SessionState>>, session_id: NodeId, endpoint: SocketAddrV4) {
+    let offset = measure_session_offset(endpoint, session_id);
+
+    let mut s = state.lock().unwrap();
+    s.measuring.remove(&session_id);
+    let Some(their_offset) = offset else {
+        tracing::debug!("Link: measurement of session {} failed", String::from_utf8_lossy(&session_id.0));
+        return;
+    };
+    if s.session_id == session_id {
+        return;
+    }
+
+    let host = host_micros();
+    if !should_join(host + s.ghost_offset, host + their_offset, s.session_id, session_id) {
+        return;
+    }
+
+    // Adopt the session's clock and its most recent timeline / transport state
+    s.session_id = session_id;
+    s.ghost_offset = their_offset;
+    let members: Vec<PeerInfo> = s
+        .peers
+        .values()
+        .filter(|p| p.session_id == session_id)
+        .cloned()
+        .collect();
+    if let Some(latest) = members.iter().map(|p| p.timeline).max_by_key(|t| t.time_origin) {
+        s.timeline = latest;
+    }
+    s.start_stop = members
+        .iter()
+        .filter_map(|p| p.start_stop)
+        .max_by_key(|st| st.timestamp)
+        .unwrap_or_default();
+    s.dirty = true;
+
+    tracing::info!(
+        "🔗 Joined Link session {} ({:.1} BPM, {} peers)",
+        String::from_utf8_lossy(&session_id.0),
+        s.timeline.bpm(),
+        s.num_peers()
+    );
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_timeline_beats_and_time() {
+        let timeline = Timeline::new(120.0, 1_000_000);
+        assert_eq!(timeline.micros_per_beat, 500_000);
+        assert!((timeline.bpm() - 120.0).abs() < 1e-9);
+        assert!((timeline.beats_at(1_000_000)).abs() < 1e-9);
+        assert!((timeline.beats_at(3_000_000) - 4.0).abs() < 1e-9);
+        assert_eq!(timeline.time_at(4.0), 3_000_000);
+    }
+
+    #[test]
+    fn test_timeline_tempo_change_is_continuous() {
+        let timeline = Timeline::new(120.0, 0);
+        let changed = timeline.with_tempo(60.0, 2_000_000);
+        // Beat position is unchanged at the moment of the change...
+        assert!((changed.beats_at(2_000_000) - timeline.beats_at(2_000_000)).abs() < 1e-6);
+        // ...and advances at the new tempo afterwards
+        assert!((changed.beats_at(3_000_000) - 5.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_discovery_message_roundtrip() {
+        let msg = DiscoveryMessage {
+            message_type: MSG_ALIVE,
+            ttl: PEER_TTL_SECS,
+            group_id: 0,
+            node_id: NodeId(*b"abcdefgh"),
+            payload: Payload {
+                timeline: Some(Timeline { micros_per_beat: 500_000, beat_origin: -3, time_origin: 42 }),
+                session: Some(NodeId(*b"sessionA")),
+                start_stop: Some(StartStopState { is_playing: true, beats: 4_000_000, timestamp: 99 }),
+                endpoint: Some(SocketAddrV4::new(Ipv4Addr::new(192, 168, 1, 20), 54321)),
+                ..Default::default()
+            },
+        };
+        let bytes = msg.encode();
+        assert_eq!(&bytes[0..8], b"_asdp_v\x01");
+        assert_eq!(DiscoveryMessage::decode(&bytes), Some(msg));
+    }
+
+    #[test]
+    fn test_payload_skips_unknown_entries() {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(b"xxxx");
+        bytes.extend_from_slice(&3u32.to_be_bytes());
+        bytes.extend_from_slice(&[1, 2, 3]);
+        Payload { host_time: Some(7), ..Default::default() }.encode(&mut bytes);
+
+        let payload = Payload::decode(&bytes).unwrap();
+        assert_eq!(payload.host_time, Some(7));
+
+        // Truncated entries are rejected
+        assert!(Payload::decode(&bytes[..bytes.len() - 1]).is_none());
+    }
+
+    #[test]
+    fn test_measurement_roundtrip() {
+        let ping = Payload { host_time: Some(123), prev_ghost_time: Some(456), ..Default::default() };
+        let bytes = encode_measurement(MSG_PING, &ping);
+        assert_eq!(decode_measurement(&bytes), Some((MSG_PING, ping)));
+        assert!(decode_measurement(b"_asdp_v\x01\x01").is_none());
+    }
+
+    #[test]
+    fn test_should_join_older_session() {
+        let a = NodeId(*b"aaaaaaaa");
+        let b = NodeId(*b"bbbbbbbb");
+        // Their session is older (larger ghost time)
+        assert!(should_join(0, 10_000_000, b, a));
+        // Our session is older
+        assert!(!should_join(10_000_000, 0, a, b));
+        // Near-equal ghost times: lower session id wins
+        assert!(should_join(0, 100, b, a));
+        assert!(!should_join(0, 100, a, b));
+    }
+
+    /// Two discovery transports that talk to each other over unicast loopback
+    fn loopback_pair() -> (DiscoveryTransport, DiscoveryTransport) {
+        let bind = || {
+            let socket = UdpSocket::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+            socket.set_read_timeout(Some(Duration::from_millis(50))).unwrap();
+            let addr = match socket.local_addr().unwrap() {
+                SocketAddr::V4(addr) => addr,
+                SocketAddr::V6(_) => unreachable!(),
+            };
+            (socket, addr)
+        };
+        let (a, a_addr) = bind();
+        let (b, b_addr) = bind();
+        let transport = |socket, target| DiscoveryTransport {
+            socket,
+            targets: vec![target],
+            local_ip: Ipv4Addr::LOCALHOST,
+        };
+        (transport(a, b_addr), transport(b, a_addr))
+    }
+
+    /// Poll until `done` holds or a few seconds pass
+    fn wait_until(mut done: impl FnMut() -> bool) -> bool {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while !done() {
+            if Instant::now() > deadline {
+                return false;
+            }
+            thread::sleep(Duration::from_millis(20));
+        }
+        true
+    }
+
+    /// Start two peers, the second joining the first's (older) session
+    fn connected_pair(first_bpm: f64, second_bpm: f64) -> (LinkSession, LinkSession) {
+        let (a, b) = loopback_pair();
+        let first = LinkSession::start_with_transport(first_bpm, a).unwrap();
+        // Start far enough apart that the first session is unambiguously older
+        thread::sleep(Duration::from_micros(2 * SESSION_EPS_MICROS as u64));
+        let second = LinkSession::start_with_transport(second_bpm, b).unwrap();
+
+        assert!(
+            wait_until(|| first.num_peers() == 1 && second.num_peers() == 1),
+            "peers did not join one session"
+        );
+        (first, second)
+    }
+
+    #[test]
+    fn test_two_peers_on_localhost_share_tempo_and_phase() {
+        let (first, second) = connected_pair(128.0, 90.0);
+
+        // The newcomer adopts the established session's tempo
+        let a = first.capture();
+        let b = second.capture();
+        assert!((a.bpm - 128.0).abs() < 0.01);
+        assert!((b.bpm - 128.0).abs() < 0.01);
+        assert!((a.beat - b.beat).abs() < 0.05, "beats differ: {} vs {}", a.beat, b.beat);
+
+        // Tempo changes propagate
+        second.set_tempo(100.0);
+        assert!(wait_until(|| (first.capture().bpm - 100.0).abs() < 0.01));
+    }
+
+    #[test]
+    fn test_two_peers_sync_start_stop_both_ways() {
+        let (first, second) = connected_pair(120.0, 120.0);
+        assert!(!first.capture().is_playing);
+        assert!(!second.capture().is_playing);
+
+        first.set_playing(true);
+        assert!(wait_until(|| second.capture().is_playing), "start did not reach second peer");
+
+        second.set_playing(false);
+        assert!(wait_until(|| !first.capture().is_playing), "stop did not reach first peer");
+
+        second.set_playing(true);
+        assert!(wait_until(|| first.capture().is_playing), "start did not reach first peer");
+
+        first.set_playing(false);
+        assert!(wait_until(|| !second.capture().is_playing), "stop did not reach second peer");
+    }
+}
@@ -6,8 +6,10 @@
 //! - NDI input/output via official NDI SDK
 //! - Syphon (macOS) / Spout (Windows) GPU texture sharing
 //! - Source discovery on the local network
+//! - Ableton Link tempo/beat synchronization
 
 pub mod discovery;
+pub mod link;
 pub mod ndi;
 pub mod ndi_capture;
 pub mod ndi_ffi;
@@ -29,6 +31,7 @@ pub mod spout_ffi;
 pub mod spout_capture;
 
 pub use discovery::{DiscoveredSource, SourceDiscovery, SourceType};
+pub use link::{LinkError, LinkSession, LinkState};
 pub use ndi::{NdiFrame, NdiReceiver, NdiSender};
 pub use ndi_capture::NdiCapture;
 pub use omt::{OmtFrame, OmtReceiver, OmtSender};
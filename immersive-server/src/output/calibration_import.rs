@@ -0,0 +1,628 @@
+//! Import of projmap-calibrator results into output screens
+//!
+//! The calibrator exports a project file (JSON or XML) with one entry per
+//! projector, plus an optional dense correspondence map and per-pixel UV map
+//! per projector. Each projector becomes a screen with a single composition
+//! slice whose warp maps the projector's framebuffer back onto the content.
+//! When a UV map is present it drives the warp, with the mesh kept as fallback,
+//! and a blend mask exported with it becomes the UV map's attenuation image in
+//! place of the edge blend ramps.
+//! Color matching results become the screen's color correction and 3D LUT.
+//!
+//! The calibration camera's image is treated as the content space: the whole
+//! environment is stretched over the camera view, so content lands on the
+//! surface where the camera saw it.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use projmap_schema::{
+    invert_homography, project_point, CorrespondenceMap, ProjectConfig,
+    ProjectorConfig, SchemaError,
+};
+
+use super::color::OutputColorCorrection;
+use super::edge_blend::{EdgeBlendConfig, EdgeBlendCurve, EdgeBlendRegion};
+use super::lut::OutputLut;
+use super::screen::{OutputDevice, Screen, ScreenId};
+use super::slice::{Point2D, Rect, Slice, SliceId};
+use super::uv_map::UvWarpMap;
+use super::warp::WarpMesh;
+
+/// Grid size used when a homography is sampled into a mesh
+const HOMOGRAPHY_MESH_SIZE: usize = 17;
+
+/// Max error (camera pixels) before bilinear corners are replaced by a mesh
+///
+/// The slice shader interpolates perspective corners bilinearly, which only
+/// matches a homography exactly when it has no projective component.
+const BILINEAR_TOLERANCE_PX: f64 = 1.0;
+
+/// Result of importing a calibration
+#[derive(Debug, Clone)]
+pub struct CalibrationImport {
+    /// Project name from the calibration file
+    pub name: String,
+    /// One screen per calibrated projector
+    pub screens: Vec<Screen>,
+    /// Non-fatal issues (missing maps, unwarped slices, ...)
+    pub warnings: Vec<String>,
+}
+
+/// Errors that can occur when importing a calibration
+#[derive(Debug)]
+pub enum CalibrationImportError {
+    Io(std::io::Error),
+    JsonParse(serde_json::Error),
+    XmlParse(quick_xml::DeError),
+    Schema(SchemaError),
+    NoProjectors,
+}
+
+impl std::fmt::Display for CalibrationImportError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CalibrationImportError::Io(e) => write!(f, "IO error: {}", e),
+            CalibrationImportError::JsonParse(e) => write!(f, "JSON parse error: {}", e),
+            CalibrationImportError::XmlParse(e) => write!(f, "XML parse error: {}", e),
+            CalibrationImportError::Schema(e) => write!(f, "Schema error: {}", e),
+            CalibrationImportError::NoProjectors => {
+                write!(f, "Calibration contains no projectors")
+            }
+        }
+    }
+}
+
+impl std::error::Error for CalibrationImportError {}
+
+/// Load a calibrator project file and its correspondence maps
+///
+/// Files with a `.json` extension are read as JSON, everything else as XML.
+/// Correspondence map paths are resolved relative to the project file; a map
+/// that fails to load is reported as a warning and the homography is used.
+pub fn import_calibration(path: &Path) -> Result<CalibrationImport, CalibrationImportError> {
+    let contents = std::fs::read_to_string(path).map_err(CalibrationImportError::Io)?;
+    let project: ProjectConfig = if path.extension().is_some_and(|e| e == "json") {
+        serde_json::from_str(&contents).map_err(CalibrationImportError::JsonParse)?
+    } else {
+        quick_xml::de::from_str(&contents).map_err(CalibrationImportError::XmlParse)?
+    };
+    project.check_version().map_err(CalibrationImportError::Schema)?;
+
+    let dir = path.parent().unwrap_or_else(|| Path::new("."));
+    let mut maps = HashMap::new();
+    let mut warnings = Vec::new();
+    for projector in &project.projectors {
+        let Some(file) = &projector.correspondence_file else {
+            continue;
+        };
+        match load_correspondence_map(&dir.join(file)) {
+            Ok(map) => {
+                maps.insert(projector.id, map);
+            }
+            Err(e) => warnings.push(format!(
+                "{}: could not load correspondence map '{}' ({})",
+                projector.name, file, e
+            )),
+        }
+    }
+
+    let mut import = screens_from_project(&project, &maps)?;
+    attach_uv_maps(&mut import, &project, dir);
+    attach_blend_masks(&mut import, &project, dir);
+    attach_luts(&mut import, &project, dir);
+    warnings.append(&mut import.warnings);
+    import.warnings = warnings;
+    Ok(import)
+}
+
+/// Point each projector's slice at its exported UV map, if the file exists
+fn attach_uv_maps(import: &mut CalibrationImport, project: &ProjectConfig, dir: &Path) {
+    for (screen, projector) in import.screens.iter_mut().zip(&project.projectors) {
+        let Some(file) = &projector.uv_map_file else {
+            continue;
+        };
+        let path = dir.join(file);
+        if !path.is_file() {
+            import
+                .warnings
+                .push(format!("{}: UV map '{}' not found", projector.name, file));
+            continue;
+        }
+        for slice in &mut screen.slices {
+            slice.output.uv_map = Some(UvWarpMap::new(path.to_string_lossy()));
+        }
+    }
+}
+
+/// Use each projector's blend mask as its UV map attenuation, if the file exists
+///
+/// The mask already holds the full overlap blend, so the edge ramps are turned off.
+fn attach_blend_masks(import: &mut CalibrationImport, project: &ProjectConfig, dir: &Path) {
+    for (screen, projector) in import.screens.iter_mut().zip(&project.projectors) {
+        let Some(file) = &projector.blend_mask_file else {
+            continue;
+        };
+        let path = dir.join(file);
+        if !path.is_file() {
+            import
+                .warnings
+                .push(format!("{}: blend mask '{}' not found", projector.name, file));
+            continue;
+        }
+        for slice in &mut screen.slices {
+            let Some(uv_map) = &mut slice.output.uv_map else {
+                import.warnings.push(format!(
+                    "{}: blend mask '{}' needs a UV map, using edge blend",
+                    projector.name, file
+                ));
+                continue;
+            };
+            uv_map.attenuation_path = Some(path.to_string_lossy().into_owned());
+            slice.output.edge_blend = EdgeBlendConfig::default();
+        }
+    }
+}
+
+/// Load each projector's color matching LUT on its screen, if the file exists
+fn attach_luts(import: &mut CalibrationImport, project: &ProjectConfig, dir: &Path) {
+    for (screen, projector) in import.screens.iter_mut().zip(&project.projectors) {
+        let Some(file) = &projector.lut_file else {
+            continue;
+        };
+        let path = dir.join(file);
+        if !path.is_file() {
+            import
+                .warnings
+                .push(format!("{}: color LUT '{}' not found", projector.name, file));
+            continue;
+        }
+        screen.lut = Some(OutputLut::new(path.to_string_lossy()));
+    }
+}
+
+fn load_correspondence_map(path: &Path) -> Result<CorrespondenceMap, CalibrationImportError> {
+    let contents = std::fs::read_to_string(path).map_err(CalibrationImportError::Io)?;
+    let map: CorrespondenceMap =
+        serde_json::from_str(&contents).map_err(CalibrationImportError::JsonParse)?;
+    map.validate().map_err(CalibrationImportError::Schema)?;
+    Ok(map)
+}
+
+/// Build screens from an already-loaded project and its correspondence maps
+pub fn screens_from_project(
+    project: &ProjectConfig,
+    maps: &HashMap<u32, CorrespondenceMap>,
+) -> Result<CalibrationImport, CalibrationImportError> {
+    if project.projectors.is_empty() {
+        return Err(CalibrationImportError::NoProjectors);
+    }
+
+    let content_size = project.content_size();
+    let mut warnings = Vec::new();
+    let screens = project
+        .projectors
+        .iter()
+        .enumerate()
+        .map(|(index, projector)| {
+            let id = index as u32 + 1;
+            let mut slice = Slice::new_full_composition(SliceId(id), "Calibrated");
+            slice.input_rect = Rect::full();
+
+            let map = maps.get(&projector.id);
+            if let Some(mesh) = map.and_then(|m| mesh_from_map(m, projector, &mut warnings)) {
+                slice.output.mesh = Some(mesh);
+            } else if let Some(h) = &projector.homography {
+                match warp_from_homography(h, projector, content_size) {
+                    Some(HomographyWarp::Perspective(corners)) => {
+                        slice.output.perspective = Some(corners)
+                    }
+                    Some(HomographyWarp::Mesh(mesh)) => slice.output.mesh = Some(mesh),
+                    None => warnings.push(format!(
+                        "{}: homography is singular, slice left unwarped",
+                        projector.name
+                    )),
+                }
+            } else {
+                warnings.push(format!(
+                    "{}: no homography or correspondence map, slice left unwarped",
+                    projector.name
+                ));
+            }
+
+            slice.output.edge_blend = edge_blend_from_config(projector);
+
+            let mut screen = Screen::new(ScreenId(id), projector.name.clone());
+            screen.device = OutputDevice::Virtual;
+            screen.width = projector.width.max(1);
+            screen.height = projector.height.max(1);
+            if let Some(color) = &projector.color_correction {
+                screen.color = OutputColorCorrection {
+                    brightness: color.brightness,
+                    contrast: color.contrast,
+                    gamma: color.gamma,
+                    red: color.red,
+                    green: color.green,
+                    blue: color.blue,
+                    saturation: color.saturation,
+                };
+            }
+            screen.add_slice(slice);
+            screen
+        })
+        .collect();
+
+    Ok(CalibrationImport {
+        name: project.name.clone(),
+        screens,
+        warnings,
+    })
+}
+
+/// Warp derived from a homography
+enum HomographyWarp {
+    Perspective([Point2D; 4]),
+    Mesh(WarpMesh),
+}
+
+/// Map a projector pixel back to normalized content UV through the inverse homography
+fn content_uv(inverse: &[f64; 9], x: f64, y: f64, content_size: (u32, u32)) -> Option<[f32; 2]> {
+    let (cx, cy) = project_point(inverse, x, y)?;
+    Some([
+        (cx / content_size.0.max(1) as f64) as f32,
+        (cy / content_size.1.max(1) as f64) as f32,
+    ])
+}
+
+fn warp_from_homography(
+    homography: &[f64; 9],
+    projector: &ProjectorConfig,
+    content_size: (u32, u32),
+) -> Option<HomographyWarp> {
+    let inverse = invert_homography(homography)?;
+    let w = projector.width.max(1) as f64;
+    let h = projector.height.max(1) as f64;
+
+    // TL, TR, BR, BL in camera pixels
+    let mut corners = [(0.0, 0.0); 4];
+    for (corner, (x, y)) in corners.iter_mut().zip([(0.0, 0.0), (w, 0.0), (w, h), (0.0, h)]) {
+        *corner = project_point(&inverse, x, y)?;
+    }
+
+    // Compare bilinear interpolation of the corners against the true mapping
+    let steps = 8;
+    let mut max_error: f64 = 0.0;
+    for j in 0..=steps {
+        for i in 0..=steps {
+            let u = i as f64 / steps as f64;
+            let v = j as f64 / steps as f64;
+            let (ex, ey) = project_point(&inverse, u * w, v * h)?;
+            let top = lerp2(corners[0], corners[1], u);
+            let bottom = lerp2(corners[3], corners[2], u);
+            let (bx, by) = lerp2(top, bottom, v);
+            max_error = max_error.max(((ex - bx).powi(2) + (ey - by).powi(2)).sqrt());
+        }
+    }
+
+    if max_error <= BILINEAR_TOLERANCE_PX {
+        let sx = content_size.0.max(1) as f64;
+        let sy = content_size.1.max(1) as f64;
+        let points = corners.map(|(x, y)| Point2D::new((x / sx) as f32, (y / sy) as f32));
+        return Some(HomographyWarp::Perspective(points));
+    }
+
+    let mut mesh = WarpMesh::new(HOMOGRAPHY_MESH_SIZE, HOMOGRAPHY_MESH_SIZE);
+    for point in &mut mesh.points {
+        let [u, v] = point.uv;
+        point.position = content_uv(&inverse, u as f64 * w, v as f64 * h, content_size)?;
+    }
+    Some(HomographyWarp::Mesh(mesh))
+}
+
+fn lerp2(a: (f64, f64), b: (f64, f64), t: f64) -> (f64, f64) {
+    (a.0 + (b.0 - a.0) * t, a.1 + (b.1 - a.1) * t)
+}
+
+/// Build a warp mesh from a dense correspondence map
+///
+/// Undecoded nodes are filled from the homography when there is one,
+/// otherwise by repeatedly averaging decoded neighbors. Returns `None` when
+/// the map has no decoded nodes at all.
+fn mesh_from_map(
+    map: &CorrespondenceMap,
+    projector: &ProjectorConfig,
+    warnings: &mut Vec<String>,
+) -> Option<WarpMesh> {
+    let columns = map.columns as usize;
+    let rows = map.rows as usize;
+    let camera_size = (map.camera_width.max(1), map.camera_height.max(1));
+
+    if map.valid_count() == 0 {
+        warnings.push(format!(
+            "{}: correspondence map has no decoded points",
+            projector.name
+        ));
+        return None;
+    }
+
+    // Row-major camera UVs, None where undecoded
+    let mut grid: Vec<Option<[f32; 2]>> = map
+        .points
+        .iter()
+        .map(|p| p.map(|[x, y]| [x / camera_size.0 as f32, y / camera_size.1 as f32]))
+        .collect();
+
+    let missing = grid.iter().filter(|p| p.is_none()).count();
+    if missing > 0 {
+        let inverse = projector.homography.as_ref().and_then(invert_homography);
+        if let Some(inverse) = inverse {
+            for row in 0..rows {
+                for col in 0..columns {
+                    let cell = &mut grid[row * columns + col];
+                    if cell.is_none() {
+                        let [px, py] = map.node_position(col as u32, row as u32);
+                        *cell = content_uv(&inverse, px as f64, py as f64, camera_size);
+                    }
+                }
+            }
+        }
+        fill_from_neighbors(&mut grid, columns, rows);
+        warnings.push(format!(
+            "{}: {} of {} mesh points were not decoded and have been interpolated",
+            projector.name,
+            missing,
+            grid.len()
+        ));
+    }
+
+    let mut mesh = WarpMesh::new(columns, rows);
+    for col in 0..columns {
+        for row in 0..rows {
+            if let Some(position) = grid[row * columns + col] {
+                mesh.set_point_position(col, row, position[0], position[1]);
+            }
+        }
+    }
+    Some(mesh)
+}
+
+/// Fill holes by averaging decoded 4-neighbors until the grid is complete
+fn fill_from_neighbors(grid: &mut [Option<[f32; 2]>], columns: usize, rows: usize) {
+    loop {
+        let snapshot = grid.to_vec();
+        let mut changed = false;
+        for row in 0..rows {
+            for col in 0..columns {
+                if snapshot[row * columns + col].is_some() {
+                    continue;
+                }
+                let neighbors = [
+                    (col > 0).then(|| snapshot[row * columns + col - 1]),
+                    (col + 1 < columns).then(|| snapshot[row * columns + col + 1]),
+                    (row > 0).then(|| snapshot[(row - 1) * columns + col]),
+                    (row + 1 < rows).then(|| snapshot[(row + 1) * columns + col]),
+                ];
+                let known: Vec<[f32; 2]> = neighbors.into_iter().flatten().flatten().collect();
+                if !known.is_empty() {
+                    let n = known.len() as f32;
+                    let sum = known.iter().fold([0.0, 0.0], |a, p| [a[0] + p[0], a[1] + p[1]]);
+                    grid[row * columns + col] = Some([sum[0] / n, sum[1] / n]);
+                    changed = true;
+                }
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+}
+
+/// Convert pixel blend widths into slice edge blend fractions
+fn edge_blend_from_config(projector: &ProjectorConfig) -> EdgeBlendConfig {
+    let blend = &projector.blend;
+    let curve = EdgeBlendCurve::from(blend.curve);
+    // Only the gamma curve uses the exponent
+    let gamma = if curve == EdgeBlendCurve::Gamma { blend.gamma } else { 1.0 };
+
+    let region = |pixels: u32, extent: u32| {
+        if pixels == 0 {
+            EdgeBlendRegion::default()
+        } else {
+            EdgeBlendRegion {
+                curve,
+                ..EdgeBlendRegion::new(pixels as f32 / extent.max(1) as f32, gamma)
+            }
+        }
+    };
+
+    EdgeBlendConfig {
+        left: region(blend.left_width, projector.width),
+        right: region(blend.right_width, projector.width),
+        top: region(blend.top_width, projector.height),
+        bottom: region(blend.bottom_width, projector.height),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use projmap_schema::{BlendConfig, BlendCurve, ColorCorrection};
+
+    fn projector(id: u32, homography: Option<[f64; 9]>) -> ProjectorConfig {
+        ProjectorConfig {
+            id,
+            name: format!("Projector {}", id),
+            width: 1920,
+            height: 1080,
+            homography,
+            ..Default::default()
+        }
+    }
+
+    fn project(projectors: Vec<ProjectorConfig>) -> ProjectConfig {
+        ProjectConfig {
+            camera_width: 1920,
+            camera_height: 1080,
+            projectors,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_affine_homography_gives_perspective_corners() {
+        // Camera -> projector: scale by 2, shift left by 960
+        let h = [2.0, 0.0, -960.0, 0.0, 2.0, 0.0, 0.0, 0.0, 1.0];
+        let import = screens_from_project(&project(vec![projector(0, Some(h))]), &HashMap::new())
+            .unwrap();
+        assert_eq!(import.screens.len(), 1);
+        let slice = &import.screens[0].slices[0];
+        assert!(slice.output.mesh.is_none());
+        let corners = slice.output.perspective.unwrap();
+        assert!((corners[0].x - 0.25).abs() < 1e-5 && corners[0].y.abs() < 1e-5);
+        assert!((corners[2].x - 0.75).abs() < 1e-5 && (corners[2].y - 0.5).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_projective_homography_gives_mesh() {
+        let h = [1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0002, 0.0, 1.0];
+        let import = screens_from_project(&project(vec![projector(0, Some(h))]), &HashMap::new())
+            .unwrap();
+        let slice = &import.screens[0].slices[0];
+        assert!(slice.output.perspective.is_none());
+        let mesh = slice.output.mesh.as_ref().unwrap();
+        assert_eq!(mesh.columns, HOMOGRAPHY_MESH_SIZE);
+        assert_eq!(mesh.get_point(0, 0).unwrap().position, [0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_correspondence_map_fills_holes() {
+        let mut map = CorrespondenceMap::new(3, (1920, 1080), (1920, 1080), 3, 3);
+        for row in 0..3 {
+            for col in 0..3 {
+                let [x, y] = map.node_position(col, row);
+                map.set(col, row, Some([x, y]));
+            }
+        }
+        map.set(1, 1, None);
+        let maps = HashMap::from([(3, map)]);
+
+        let import = screens_from_project(&project(vec![projector(3, None)]), &maps).unwrap();
+        let mesh = import.screens[0].slices[0].output.mesh.as_ref().unwrap();
+        let center = mesh.get_point(1, 1).unwrap().position;
+        assert!((center[0] - 0.5).abs() < 1e-3 && (center[1] - 0.5).abs() < 1e-3);
+        assert_eq!(import.warnings.len(), 1);
+    }
+
+    #[test]
+    fn test_blend_widths_become_fractions() {
+        let mut p = projector(0, None);
+        p.blend = BlendConfig {
+            right_width: 384,
+            bottom_width: 108,
+            gamma: 2.2,
+            curve: BlendCurve::Linear,
+            ..Default::default()
+        };
+        let import = screens_from_project(&project(vec![p]), &HashMap::new()).unwrap();
+        let blend = &import.screens[0].slices[0].output.edge_blend;
+        assert!(!blend.left.enabled && !blend.top.enabled);
+        assert!(blend.right.enabled && (blend.right.width - 0.2).abs() < 1e-6);
+        assert!(blend.bottom.enabled && (blend.bottom.width - 0.1).abs() < 1e-6);
+        assert_eq!(blend.right.gamma, 1.0);
+        assert_eq!(blend.right.curve, EdgeBlendCurve::Linear);
+    }
+
+    #[test]
+    fn test_uv_map_files_attached() {
+        let dir = std::env::temp_dir().join(format!("calibration_import_test_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("p0.pmuv"), b"").unwrap();
+
+        let mut with_map = projector(0, None);
+        with_map.uv_map_file = Some("p0.pmuv".to_string());
+        let mut missing = projector(1, None);
+        missing.uv_map_file = Some("p1.pmuv".to_string());
+        let project = project(vec![with_map, missing]);
+
+        let mut import = screens_from_project(&project, &HashMap::new()).unwrap();
+        let unwarped_warnings = import.warnings.len();
+        attach_uv_maps(&mut import, &project, &dir);
+        let uv_map = import.screens[0].slices[0].output.uv_map.as_ref().unwrap();
+        assert!(uv_map.enabled && uv_map.path.ends_with("p0.pmuv"));
+        assert!(import.screens[1].slices[0].output.uv_map.is_none());
+        assert_eq!(import.warnings.len(), unwarped_warnings + 1);
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_blend_masks_replace_edge_blend() {
+        let dir = std::env::temp_dir().join(format!("calibration_import_blend_test_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("p0.pmuv"), b"").unwrap();
+        std::fs::write(dir.join("p0_blend.png"), b"").unwrap();
+        std::fs::write(dir.join("p1_blend.png"), b"").unwrap();
+
+        let mut masked = projector(0, None);
+        masked.uv_map_file = Some("p0.pmuv".to_string());
+        masked.blend_mask_file = Some("p0_blend.png".to_string());
+        masked.blend.right_width = 384;
+        let mut unmapped = projector(1, None);
+        unmapped.blend_mask_file = Some("p1_blend.png".to_string());
+        unmapped.blend.left_width = 384;
+        let project = project(vec![masked, unmapped]);
+
+        let mut import = screens_from_project(&project, &HashMap::new()).unwrap();
+        attach_uv_maps(&mut import, &project, &dir);
+        let warnings = import.warnings.len();
+        attach_blend_masks(&mut import, &project, &dir);
+
+        let output = &import.screens[0].slices[0].output;
+        let uv_map = output.uv_map.as_ref().unwrap();
+        assert!(uv_map.attenuation_path.as_ref().unwrap().ends_with("p0_blend.png"));
+        assert!(!output.edge_blend.right.enabled);
+        // Without a UV map the edge blend stays
+        assert!(import.screens[1].slices[0].output.edge_blend.left.enabled);
+        assert_eq!(import.warnings.len(), warnings + 1);
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_color_matching_applied() {
+        let dir = std::env::temp_dir().join(format!("calibration_import_lut_test_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("p0.cube"), b"LUT_3D_SIZE 2\n").unwrap();
+
+        let mut matched = projector(0, None);
+        matched.color_correction = Some(ColorCorrection {
+            gamma: 0.9,
+            red: 0.8,
+            ..Default::default()
+        });
+        matched.lut_file = Some("p0.cube".to_string());
+        let mut missing = projector(1, None);
+        missing.lut_file = Some("p1.cube".to_string());
+        let project = project(vec![matched, missing]);
+
+        let mut import = screens_from_project(&project, &HashMap::new()).unwrap();
+        let color = &import.screens[0].color;
+        assert_eq!((color.gamma, color.red, color.green), (0.9, 0.8, 1.0));
+        assert!(import.screens[1].color.is_identity());
+
+        let warnings = import.warnings.len();
+        attach_luts(&mut import, &project, &dir);
+        let lut = import.screens[0].lut.as_ref().unwrap();
+        assert!(lut.path.as_ref().unwrap().ends_with("p0.cube"));
+        assert!(import.screens[1].lut.is_none());
+        assert_eq!(import.warnings.len(), warnings + 1);
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_empty_project_rejected() {
+        let result = screens_from_project(&project(Vec::new()), &HashMap::new());
+        assert!(matches!(result, Err(CalibrationImportError::NoProjectors)));
+    }
+}
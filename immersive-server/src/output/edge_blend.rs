@@ -31,6 +31,51 @@ impl EdgeBlendConfig {
         self.left.enabled || self.right.enabled || self.top.enabled || self.bottom.enabled
     }
 
+    /// Check if any enabled edge lifts the black level outside its overlap
+    pub fn has_uplift(&self) -> bool {
+        [&self.left, &self.right, &self.top, &self.bottom]
+            .iter()
+            .any(|r| r.enabled && r.uplift > 0.0)
+    }
+
+    /// Per-channel (RGB) blend factor at a slice output UV (matches the slice shader)
+    pub fn alpha_at(&self, uv: [f32; 2]) -> [f32; 3] {
+        let edges = [
+            self.left.falloff(uv[0]),
+            self.right.falloff(1.0 - uv[0]),
+            self.top.falloff(uv[1]),
+            self.bottom.falloff(1.0 - uv[1]),
+        ];
+        edges
+            .iter()
+            .fold([1.0; 3], |acc, f| [acc[0] * f[0], acc[1] * f[1], acc[2] * f[2]])
+    }
+
+    /// Black-level uplift at a slice output UV (matches the slice shader)
+    ///
+    /// Only the area outside every enabled blend zone is lifted, since the
+    /// overlap already receives black from both projectors. The largest
+    /// uplift of the enabled edges is used.
+    pub fn uplift_at(&self, uv: [f32; 2]) -> f32 {
+        let edges = [
+            (&self.left, uv[0]),
+            (&self.right, 1.0 - uv[0]),
+            (&self.top, uv[1]),
+            (&self.bottom, 1.0 - uv[1]),
+        ];
+        let mut uplift = 0.0_f32;
+        for (region, distance) in edges {
+            if !region.enabled {
+                continue;
+            }
+            if distance < region.width {
+                return 0.0;
+            }
+            uplift = uplift.max(region.uplift);
+        }
+        uplift
+    }
+
     /// Enable blending on left and right edges (horizontal overlap)
     pub fn horizontal(width: f32, gamma: f32) -> Self {
         Self {
@@ -90,6 +135,28 @@ pub struct EdgeBlendRegion {
     /// Black level compensation (reduces visible "halo" in overlap)
     #[serde(rename = "blackLevel", default)]
     pub black_level: f32,
+
+    /// Shape of the blend ramp
+    #[serde(rename = "curve", default)]
+    pub curve: EdgeBlendCurve,
+
+    /// Per-channel (R, G, B) exponent applied on top of the curve;
+    /// for the gamma curve it scales `gamma`
+    #[serde(rename = "channelGamma", default = "default_channel_gamma")]
+    pub channel_gamma: [f32; 3],
+
+    /// Shift of the ramp's 50% point (-0.4 to 0.4, 0 = centered)
+    #[serde(rename = "center", default)]
+    pub center: f32,
+
+    /// Black-level uplift for the non-overlap area (0.0-0.5), matching
+    /// the doubled black inside the overlap
+    #[serde(rename = "uplift", default)]
+    pub uplift: f32,
+}
+
+fn default_channel_gamma() -> [f32; 3] {
+    [1.0; 3]
 }
 
 impl Default for EdgeBlendRegion {
@@ -99,6 +166,10 @@ impl Default for EdgeBlendRegion {
             width: 0.15,      // 15% overlap is typical
             gamma: 2.2,       // Standard gamma
             black_level: 0.0, // No black level compensation
+            curve: EdgeBlendCurve::Gamma,
+            channel_gamma: default_channel_gamma(),
+            center: 0.0,
+            uplift: 0.0,
         }
     }
 }
@@ -110,7 +181,7 @@ impl EdgeBlendRegion {
             enabled: true,
             width: width.clamp(0.0, 0.5),
             gamma: gamma.clamp(0.1, 4.0),
-            black_level: 0.0,
+            ..Default::default()
         }
     }
 
@@ -133,6 +204,125 @@ impl EdgeBlendRegion {
     pub fn set_black_level(&mut self, level: f32) {
         self.black_level = level.clamp(0.0, 0.5);
     }
+
+    /// Set the center shift (clamped to -0.4-0.4)
+    pub fn set_center(&mut self, center: f32) {
+        self.center = center.clamp(-0.4, 0.4);
+    }
+
+    /// Set the black-level uplift (clamped to 0.0-0.5)
+    pub fn set_uplift(&mut self, uplift: f32) {
+        self.uplift = uplift.clamp(0.0, 0.5);
+    }
+
+    /// Exponent remapping the ramp so its 50% point moves by `center`
+    pub fn center_exponent(&self) -> f32 {
+        let center = self.center.clamp(-0.4, 0.4);
+        0.5_f32.ln() / (0.5 + center).ln()
+    }
+
+    /// Effective per-channel exponents applied after the curve
+    pub fn channel_exponents(&self) -> [f32; 3] {
+        let base = if self.curve == EdgeBlendCurve::Gamma {
+            self.gamma
+        } else {
+            1.0
+        };
+        self.channel_gamma.map(|g| (base * g).max(0.01))
+    }
+
+    /// Per-channel blend factor at a distance from this edge (0.0 = on the edge)
+    fn falloff(&self, distance: f32) -> [f32; 3] {
+        if !self.enabled || distance >= self.width {
+            return [1.0; 3];
+        }
+        let t = (distance / self.width).max(0.0).powf(self.center_exponent());
+        let shaped = self.curve.apply(t);
+        self.channel_exponents()
+            .map(|e| shaped.powf(e) * (1.0 - self.black_level) + self.black_level)
+    }
+}
+
+/// Shape of an edge blend ramp
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum EdgeBlendCurve {
+    /// Straight ramp
+    Linear,
+
+    /// Power curve using the region's gamma
+    #[default]
+    Gamma,
+
+    /// Raised cosine (smooth at both ends)
+    Cosine,
+
+    /// Hermite smoothstep
+    Smoothstep,
+}
+
+impl EdgeBlendCurve {
+    /// All curves, in UI order
+    pub const ALL: [EdgeBlendCurve; 4] = [
+        EdgeBlendCurve::Linear,
+        EdgeBlendCurve::Gamma,
+        EdgeBlendCurve::Cosine,
+        EdgeBlendCurve::Smoothstep,
+    ];
+
+    /// Get display name
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            EdgeBlendCurve::Linear => "Linear",
+            EdgeBlendCurve::Gamma => "Gamma",
+            EdgeBlendCurve::Cosine => "Cosine",
+            EdgeBlendCurve::Smoothstep => "Smoothstep",
+        }
+    }
+
+    /// Index passed to the slice shader (0 is reserved for "disabled")
+    pub fn shader_id(&self) -> f32 {
+        match self {
+            EdgeBlendCurve::Linear => 1.0,
+            EdgeBlendCurve::Gamma => 2.0,
+            EdgeBlendCurve::Cosine => 3.0,
+            EdgeBlendCurve::Smoothstep => 4.0,
+        }
+    }
+
+    /// Shape a normalized ramp position (0-1); the gamma curve's exponent is
+    /// applied separately per channel
+    pub fn apply(&self, t: f32) -> f32 {
+        match self {
+            EdgeBlendCurve::Linear | EdgeBlendCurve::Gamma => t,
+            EdgeBlendCurve::Cosine => 0.5 - 0.5 * (std::f32::consts::PI * t).cos(),
+            EdgeBlendCurve::Smoothstep => t * t * (3.0 - 2.0 * t),
+        }
+    }
+}
+
+/// Curves from calibration project files (projmap-schema)
+impl From<projmap_schema::BlendCurve> for EdgeBlendCurve {
+    fn from(curve: projmap_schema::BlendCurve) -> Self {
+        use projmap_schema::BlendCurve;
+        match curve {
+            BlendCurve::Linear => EdgeBlendCurve::Linear,
+            BlendCurve::Gamma => EdgeBlendCurve::Gamma,
+            BlendCurve::Cosine => EdgeBlendCurve::Cosine,
+            BlendCurve::Smoothstep => EdgeBlendCurve::Smoothstep,
+        }
+    }
+}
+
+impl From<EdgeBlendCurve> for projmap_schema::BlendCurve {
+    fn from(curve: EdgeBlendCurve) -> Self {
+        use projmap_schema::BlendCurve;
+        match curve {
+            EdgeBlendCurve::Linear => BlendCurve::Linear,
+            EdgeBlendCurve::Gamma => BlendCurve::Gamma,
+            EdgeBlendCurve::Cosine => BlendCurve::Cosine,
+            EdgeBlendCurve::Smoothstep => BlendCurve::Smoothstep,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -167,4 +357,59 @@ mod tests {
         region.set_gamma(10.0);
         assert_eq!(region.gamma, 4.0);
     }
+
+    #[test]
+    fn test_alpha_at() {
+        let config = EdgeBlendConfig::horizontal(0.2, 1.0);
+        assert_eq!(config.alpha_at([0.5, 0.5]), [1.0; 3]);
+        assert_eq!(config.alpha_at([0.0, 0.5]), [0.0; 3]);
+        assert!(config.alpha_at([0.1, 0.5]).iter().all(|a| (a - 0.5).abs() < 1e-6));
+        assert!(config.alpha_at([0.95, 0.0]).iter().all(|a| (a - 0.25).abs() < 1e-6));
+    }
+
+    #[test]
+    fn test_curves_and_channels() {
+        let mut region = EdgeBlendRegion::new(0.2, 2.0);
+        region.channel_gamma = [1.0, 0.5, 1.5];
+        let [r, g, b] = region.falloff(0.1);
+        assert!((r - 0.25).abs() < 1e-6);
+        assert!((g - 0.5).abs() < 1e-6);
+        assert!((b - 0.125).abs() < 1e-6);
+
+        // Symmetric curves pass through 50% at the middle of the ramp
+        for curve in [EdgeBlendCurve::Cosine, EdgeBlendCurve::Smoothstep] {
+            region.curve = curve;
+            region.channel_gamma = [1.0; 3];
+            assert!((region.falloff(0.1)[0] - 0.5).abs() < 1e-6);
+            assert!(region.falloff(0.05)[0] < 0.25);
+        }
+
+        // The center shift moves the 50% point
+        region.curve = EdgeBlendCurve::Linear;
+        region.set_center(0.2);
+        assert!((region.falloff(0.2 * 0.7)[0] - 0.5).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_curve_matches_schema() {
+        // Every curve survives a round trip through the calibration schema
+        for curve in EdgeBlendCurve::ALL {
+            let schema: projmap_schema::BlendCurve = curve.into();
+            assert_eq!(EdgeBlendCurve::from(schema), curve);
+            // Same serialized name, so project files and presets agree
+            assert_eq!(serde_json::to_value(schema).unwrap(), serde_json::to_value(curve).unwrap());
+        }
+        assert_eq!(EdgeBlendCurve::from(projmap_schema::BlendCurve::default()), EdgeBlendCurve::default());
+    }
+
+    #[test]
+    fn test_uplift_at() {
+        let mut config = EdgeBlendConfig::horizontal(0.2, 2.2);
+        config.left.set_uplift(0.05);
+        config.right.set_uplift(0.02);
+        assert_eq!(config.uplift_at([0.5, 0.5]), 0.05);
+        assert_eq!(config.uplift_at([0.1, 0.5]), 0.0);
+        assert_eq!(config.uplift_at([0.9, 0.5]), 0.0);
+        assert_eq!(EdgeBlendConfig::default().uplift_at([0.5, 0.5]), 0.0);
+    }
 }
@@ -0,0 +1,565 @@
+//! 3D LUT color correction for screens and slices
+//!
+//! Used to match projectors of different models. Supported sources:
+//! - `.cube` (Resolve/Adobe): 3D tables, 1D tables (expanded to 3D) and
+//!   `DOMAIN_MIN`/`DOMAIN_MAX` (resampled to the 0-1 domain on load)
+//! - `.3dl` (Autodesk/Lustre): integer tables with an optional breakpoint
+//!   line and `Mesh` header, output depth inferred from the values
+//!
+//! LUTs are referenced by path or embedded in presets as `.cube` text.
+
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use super::uv_map::f32_to_f16_bits;
+
+/// Largest supported LUT edge length (wgpu guarantees 3D textures of 256³)
+pub const MAX_LUT_SIZE: u32 = 256;
+
+/// Edge length used when expanding a 1D LUT into a 3D table
+const EXPANDED_1D_SIZE: u32 = 33;
+
+/// LUT configuration for a screen or slice
+///
+/// The table is loaded by the output runtime; `embedded` takes precedence
+/// over `path` so presets stay self-contained when the file moves.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OutputLut {
+    /// Whether the LUT is applied
+    #[serde(rename = "enabled")]
+    pub enabled: bool,
+
+    /// Path to the `.cube` or `.3dl` file
+    #[serde(rename = "path", default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+
+    /// Embedded table as `.cube` text
+    #[serde(rename = "embedded", default, skip_serializing_if = "Option::is_none")]
+    pub embedded: Option<String>,
+
+    /// Interpolation between table entries
+    #[serde(rename = "interpolation", default)]
+    pub interpolation: LutInterpolation,
+
+    /// Mix between the original (0.0) and the LUT result (1.0)
+    #[serde(rename = "intensity", default = "default_intensity")]
+    pub intensity: f32,
+}
+
+fn default_intensity() -> f32 {
+    1.0
+}
+
+impl OutputLut {
+    /// Create an enabled LUT referencing a file
+    pub fn new(path: impl Into<String>) -> Self {
+        Self {
+            enabled: true,
+            path: Some(path.into()),
+            embedded: None,
+            interpolation: LutInterpolation::default(),
+            intensity: 1.0,
+        }
+    }
+
+    /// Load the table, preferring the embedded copy
+    pub fn load(&self) -> Result<Lut3d, LutError> {
+        match (&self.embedded, &self.path) {
+            (Some(text), _) => Lut3d::parse_cube(text),
+            (None, Some(path)) => load_lut(Path::new(path)),
+            (None, None) => Err(LutError::NoSource),
+        }
+    }
+
+    /// Embed the referenced file so the preset no longer depends on it
+    pub fn embed(&mut self) -> Result<(), LutError> {
+        let lut = self.load()?;
+        self.embedded = Some(lut.to_cube_string());
+        Ok(())
+    }
+
+    /// Set the intensity (clamped to 0.0-1.0)
+    pub fn set_intensity(&mut self, intensity: f32) {
+        self.intensity = intensity.clamp(0.0, 1.0);
+    }
+
+    /// Source name, for display
+    pub fn display_name(&self) -> String {
+        let name = self.path.as_deref().map(|p| {
+            Path::new(p)
+                .file_name()
+                .map(|n| n.to_string_lossy().into_owned())
+                .unwrap_or_else(|| p.to_string())
+        });
+        match (name, self.embedded.is_some()) {
+            (Some(name), true) => format!("{} (embedded)", name),
+            (Some(name), false) => name,
+            (None, true) => "(embedded)".to_string(),
+            (None, false) => "(none)".to_string(),
+        }
+    }
+}
+
+/// Interpolation between LUT entries
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum LutInterpolation {
+    /// Blend the 8 surrounding entries
+    #[default]
+    Trilinear,
+
+    /// Blend the 4 entries of the enclosing tetrahedron (preserves neutrals)
+    Tetrahedral,
+}
+
+impl LutInterpolation {
+    /// Get display name
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            LutInterpolation::Trilinear => "Trilinear",
+            LutInterpolation::Tetrahedral => "Tetrahedral",
+        }
+    }
+}
+
+/// Error loading a LUT
+#[derive(Debug)]
+pub enum LutError {
+    Io(std::io::Error),
+    Parse { line: usize, message: String },
+    UnsupportedFormat(String),
+    InvalidSize(u32),
+    NoSource,
+}
+
+impl std::fmt::Display for LutError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LutError::Io(e) => write!(f, "IO error: {}", e),
+            LutError::Parse { line, message } => write!(f, "Line {}: {}", line, message),
+            LutError::UnsupportedFormat(ext) => write!(f, "Unsupported LUT format: {}", ext),
+            LutError::InvalidSize(size) => {
+                write!(f, "Invalid LUT size {} (must be 2-{})", size, MAX_LUT_SIZE)
+            }
+            LutError::NoSource => write!(f, "LUT has no file or embedded data"),
+        }
+    }
+}
+
+impl std::error::Error for LutError {}
+
+/// A decoded 3D LUT on the 0-1 input domain
+#[derive(Debug, Clone, PartialEq)]
+pub struct Lut3d {
+    /// Entries per axis
+    pub size: u32,
+    /// Output RGB per entry, red varying fastest: index = r + g * size + b * size²
+    pub data: Vec<[f32; 3]>,
+    /// Title from the source file, if any
+    pub title: Option<String>,
+}
+
+impl Lut3d {
+    /// Create an identity LUT
+    pub fn identity(size: u32) -> Self {
+        let size = size.clamp(2, MAX_LUT_SIZE);
+        let scale = 1.0 / (size - 1) as f32;
+        let mut data = Vec::with_capacity((size * size * size) as usize);
+        for b in 0..size {
+            for g in 0..size {
+                for r in 0..size {
+                    data.push([r as f32 * scale, g as f32 * scale, b as f32 * scale]);
+                }
+            }
+        }
+        Self {
+            size,
+            data,
+            title: None,
+        }
+    }
+
+    /// Parse `.cube` text
+    pub fn parse_cube(text: &str) -> Result<Self, LutError> {
+        let mut title = None;
+        let mut size_3d = None;
+        let mut size_1d = None;
+        let mut domain_min = [0.0; 3];
+        let mut domain_max = [1.0; 3];
+        let mut entries = Vec::new();
+
+        for (index, raw) in text.lines().enumerate() {
+            let line_number = index + 1;
+            let line = raw.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let parse_error = |message: &str| LutError::Parse {
+                line: line_number,
+                message: message.to_string(),
+            };
+            let mut tokens = line.split_whitespace();
+            let keyword = tokens.next().unwrap_or_default();
+            let rest: Vec<&str> = tokens.collect();
+
+            match keyword {
+                "TITLE" => {
+                    let value = line["TITLE".len()..].trim().trim_matches('"');
+                    title = Some(value.to_string());
+                }
+                "LUT_3D_SIZE" | "LUT_1D_SIZE" => {
+                    let size: u32 = rest
+                        .first()
+                        .and_then(|s| s.parse().ok())
+                        .ok_or_else(|| parse_error("invalid size"))?;
+                    if keyword == "LUT_3D_SIZE" {
+                        if !(2..=MAX_LUT_SIZE).contains(&size) {
+                            return Err(LutError::InvalidSize(size));
+                        }
+                        size_3d = Some(size);
+                    } else {
+                        if size < 2 {
+                            return Err(LutError::InvalidSize(size));
+                        }
+                        size_1d = Some(size);
+                    }
+                }
+                "DOMAIN_MIN" | "DOMAIN_MAX" => {
+                    let values = parse_floats(&rest).ok_or_else(|| parse_error("invalid domain"))?;
+                    let [r, g, b] = <[f32; 3]>::try_from(values.as_slice())
+                        .map_err(|_| parse_error("domain needs 3 values"))?;
+                    if keyword == "DOMAIN_MIN" {
+                        domain_min = [r, g, b];
+                    } else {
+                        domain_max = [r, g, b];
+                    }
+                }
+                "LUT_1D_INPUT_RANGE" | "LUT_3D_INPUT_RANGE" => {
+                    let values = parse_floats(&rest).ok_or_else(|| parse_error("invalid range"))?;
+                    let [min, max] = <[f32; 2]>::try_from(values.as_slice())
+                        .map_err(|_| parse_error("range needs 2 values"))?;
+                    domain_min = [min; 3];
+                    domain_max = [max; 3];
+                }
+                _ if keyword.starts_with(|c: char| c.is_ascii_alphabetic()) => {
+                    // Unknown keywords are allowed by the format
+                }
+                _ => {
+                    let values: Vec<&str> = std::iter::once(keyword).chain(rest).collect();
+                    let values = parse_floats(&values).ok_or_else(|| parse_error("invalid number"))?;
+                    let entry = <[f32; 3]>::try_from(values.as_slice())
+                        .map_err(|_| parse_error("expected 3 values"))?;
+                    entries.push(entry);
+                }
+            }
+        }
+
+        if domain_min.iter().zip(&domain_max).any(|(min, max)| max <= min) {
+            return Err(LutError::Parse {
+                line: 0,
+                message: "DOMAIN_MAX must be greater than DOMAIN_MIN".to_string(),
+            });
+        }
+
+        let mut lut = match (size_3d, size_1d) {
+            (Some(size), _) => {
+                let expected = (size * size * size) as usize;
+                if entries.len() != expected {
+                    return Err(LutError::Parse {
+                        line: 0,
+                        message: format!("expected {} entries, found {}", expected, entries.len()),
+                    });
+                }
+                Self {
+                    size,
+                    data: entries,
+                    title: None,
+                }
+            }
+            (None, Some(size)) => {
+                if entries.len() != size as usize {
+                    return Err(LutError::Parse {
+                        line: 0,
+                        message: format!("expected {} entries, found {}", size, entries.len()),
+                    });
+                }
+                Self::from_1d(&entries)
+            }
+            (None, None) => {
+                return Err(LutError::Parse {
+                    line: 0,
+                    message: "missing LUT_3D_SIZE or LUT_1D_SIZE".to_string(),
+                })
+            }
+        };
+
+        lut = lut.rebased(domain_min, domain_max);
+        lut.title = title;
+        Ok(lut)
+    }
+
+    /// Parse `.3dl` text
+    pub fn parse_3dl(text: &str) -> Result<Self, LutError> {
+        let mut breakpoints: Option<usize> = None;
+        let mut output_bits: Option<u32> = None;
+        let mut triples = Vec::new();
+
+        for (index, raw) in text.lines().enumerate() {
+            let line_number = index + 1;
+            let line = raw.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let tokens: Vec<&str> = line.split_whitespace().collect();
+            if tokens[0].starts_with(|c: char| c.is_ascii_alphabetic()) {
+                // Lustre header, e.g. "Mesh 4 12" (input bits, output bits)
+                if tokens[0].eq_ignore_ascii_case("mesh") {
+                    output_bits = tokens.get(2).and_then(|s| s.parse().ok());
+                }
+                continue;
+            }
+            let values = parse_floats(&tokens).ok_or_else(|| LutError::Parse {
+                line: line_number,
+                message: "invalid number".to_string(),
+            })?;
+            if breakpoints.is_none() && triples.is_empty() && values.len() != 3 {
+                breakpoints = Some(values.len());
+                continue;
+            }
+            let triple = <[f32; 3]>::try_from(values.as_slice()).map_err(|_| LutError::Parse {
+                line: line_number,
+                message: "expected 3 values".to_string(),
+            })?;
+            triples.push(triple);
+        }
+
+        let size = match breakpoints {
+            Some(size) => size as u32,
+            None => (triples.len() as f64).cbrt().round() as u32,
+        };
+        if !(2..=MAX_LUT_SIZE).contains(&size) {
+            return Err(LutError::InvalidSize(size));
+        }
+        let expected = (size * size * size) as usize;
+        if triples.len() != expected {
+            return Err(LutError::Parse {
+                line: 0,
+                message: format!("expected {} entries, found {}", expected, triples.len()),
+            });
+        }
+
+        let max_code = match output_bits {
+            Some(bits) if (1..=32).contains(&bits) => ((1u64 << bits) - 1) as f32,
+            _ => {
+                let max = triples.iter().flatten().fold(0.0_f32, |m, v| m.max(*v));
+                [1023.0, 4095.0, 65535.0]
+                    .into_iter()
+                    .find(|depth| max <= *depth)
+                    .unwrap_or(max.max(1.0))
+            }
+        };
+
+        // Blue varies fastest in .3dl files
+        let n = size as usize;
+        let mut data = vec![[0.0; 3]; expected];
+        for (i, triple) in triples.iter().enumerate() {
+            let b = i % n;
+            let g = (i / n) % n;
+            let r = i / (n * n);
+            data[r + g * n + b * n * n] = triple.map(|v| v / max_code);
+        }
+        Ok(Self {
+            size,
+            data,
+            title: None,
+        })
+    }
+
+    /// Serialize as `.cube` text
+    pub fn to_cube_string(&self) -> String {
+        let mut out = String::with_capacity(self.data.len() * 28 + 64);
+        if let Some(title) = &self.title {
+            out.push_str(&format!("TITLE \"{}\"\n", title));
+        }
+        out.push_str(&format!("LUT_3D_SIZE {}\n", self.size));
+        for [r, g, b] in &self.data {
+            out.push_str(&format!("{:.6} {:.6} {:.6}\n", r, g, b));
+        }
+        out
+    }
+
+    /// Entry at grid coordinates (clamped to the table)
+    pub fn get(&self, r: u32, g: u32, b: u32) -> [f32; 3] {
+        let max = self.size - 1;
+        let (r, g, b) = (r.min(max), g.min(max), b.min(max));
+        self.data[(r + g * self.size + b * self.size * self.size) as usize]
+    }
+
+    /// Look up a color (matches the output shaders)
+    pub fn sample(&self, rgb: [f32; 3], interpolation: LutInterpolation) -> [f32; 3] {
+        let max = (self.size - 1) as f32;
+        let scaled = rgb.map(|c| c.clamp(0.0, 1.0) * max);
+        let base = scaled.map(|c| (c.floor() as u32).min(self.size - 2));
+        let [fr, fg, fb] = [0, 1, 2].map(|i| scaled[i] - base[i] as f32);
+        let corner = |dr: u32, dg: u32, db: u32| self.get(base[0] + dr, base[1] + dg, base[2] + db);
+        let lerp = |a: [f32; 3], b: [f32; 3], t: f32| {
+            [a[0] + (b[0] - a[0]) * t, a[1] + (b[1] - a[1]) * t, a[2] + (b[2] - a[2]) * t]
+        };
+
+        match interpolation {
+            LutInterpolation::Trilinear => {
+                let c00 = lerp(corner(0, 0, 0), corner(1, 0, 0), fr);
+                let c10 = lerp(corner(0, 1, 0), corner(1, 1, 0), fr);
+                let c01 = lerp(corner(0, 0, 1), corner(1, 0, 1), fr);
+                let c11 = lerp(corner(0, 1, 1), corner(1, 1, 1), fr);
+                lerp(lerp(c00, c10, fg), lerp(c01, c11, fg), fb)
+            }
+            LutInterpolation::Tetrahedral => {
+                // Walk from c000 to c111 along the axes in order of decreasing fraction
+                let mut steps = [(fr, [1, 0, 0]), (fg, [0, 1, 0]), (fb, [0, 0, 1])];
+                steps.sort_by(|a, b| b.0.total_cmp(&a.0));
+                let mut offset = [0, 0, 0];
+                let mut previous = corner(0, 0, 0);
+                let mut result = previous;
+                for (weight, axis) in steps {
+                    offset = [offset[0] + axis[0], offset[1] + axis[1], offset[2] + axis[2]];
+                    let next = corner(offset[0], offset[1], offset[2]);
+                    for c in 0..3 {
+                        result[c] += weight * (next[c] - previous[c]);
+                    }
+                    previous = next;
+                }
+                result
+            }
+        }
+    }
+
+    /// Table as Rgba16Float texels (x = red, y = green, z = blue)
+    pub fn to_rgba_f16_bytes(&self) -> Vec<u8> {
+        self.data
+            .iter()
+            .flat_map(|[r, g, b]| [*r, *g, *b, 1.0])
+            .flat_map(|c| f32_to_f16_bits(c).to_le_bytes())
+            .collect()
+    }
+
+    /// Expand per-channel 1D curves into a 3D table
+    fn from_1d(curves: &[[f32; 3]]) -> Self {
+        let size = EXPANDED_1D_SIZE;
+        let last = (curves.len() - 1) as f32;
+        let curve = |channel: usize, x: f32| {
+            let position = x * last;
+            let i = (position.floor() as usize).min(curves.len() - 2);
+            let t = position - i as f32;
+            curves[i][channel] + (curves[i + 1][channel] - curves[i][channel]) * t
+        };
+        let mut lut = Self::identity(size);
+        for entry in &mut lut.data {
+            *entry = [curve(0, entry[0]), curve(1, entry[1]), curve(2, entry[2])];
+        }
+        lut
+    }
+
+    /// Resample a table defined on [min, max] onto the 0-1 domain
+    ///
+    /// Inputs outside the source domain clamp to its edge, as in the shader.
+    fn rebased(self, min: [f32; 3], max: [f32; 3]) -> Self {
+        if min == [0.0; 3] && max == [1.0; 3] {
+            return self;
+        }
+        let mut out = Self::identity(self.size);
+        for entry in &mut out.data {
+            let source = [0, 1, 2].map(|c| (entry[c] - min[c]) / (max[c] - min[c]));
+            *entry = self.sample(source, LutInterpolation::Trilinear);
+        }
+        out.title = self.title;
+        out
+    }
+}
+
+fn parse_floats(tokens: &[&str]) -> Option<Vec<f32>> {
+    tokens.iter().map(|t| t.parse::<f32>().ok()).collect()
+}
+
+/// Load a `.cube` or `.3dl` file
+pub fn load_lut(path: &Path) -> Result<Lut3d, LutError> {
+    let extension = path
+        .extension()
+        .map(|e| e.to_string_lossy().to_ascii_lowercase())
+        .unwrap_or_default();
+    let text = || std::fs::read_to_string(path).map_err(LutError::Io);
+    match extension.as_str() {
+        "cube" => Lut3d::parse_cube(&text()?),
+        "3dl" => Lut3d::parse_3dl(&text()?),
+        _ => Err(LutError::UnsupportedFormat(extension)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(a: [f32; 3], b: [f32; 3]) {
+        for c in 0..3 {
+            assert!((a[c] - b[c]).abs() < 1e-4, "{:?} != {:?}", a, b);
+        }
+    }
+
+    #[test]
+    fn test_parse_cube() {
+        let text = "# comment\nTITLE \"Swap\"\nLUT_3D_SIZE 2\n\
+            0 0 0\n0 0 1\n0 1 0\n0 1 1\n1 0 0\n1 0 1\n1 1 0\n1 1 1\n";
+        let lut = Lut3d::parse_cube(text).unwrap();
+        assert_eq!(lut.size, 2);
+        assert_eq!(lut.title.as_deref(), Some("Swap"));
+        // Red input drives the blue output
+        assert_close(lut.sample([1.0, 0.0, 0.0], LutInterpolation::Trilinear), [0.0, 0.0, 1.0]);
+        assert_close(lut.sample([0.5, 0.25, 0.0], LutInterpolation::Tetrahedral), [0.0, 0.25, 0.5]);
+
+        let round_trip = Lut3d::parse_cube(&lut.to_cube_string()).unwrap();
+        assert_eq!(round_trip, lut);
+
+        assert!(matches!(
+            Lut3d::parse_cube("LUT_3D_SIZE 2\n0 0 0\n"),
+            Err(LutError::Parse { .. })
+        ));
+    }
+
+    #[test]
+    fn test_cube_1d_and_domain() {
+        let lut = Lut3d::parse_cube("LUT_1D_SIZE 2\n0 0 0\n0.5 1 1\n").unwrap();
+        assert_close(lut.sample([1.0, 0.5, 0.0], LutInterpolation::Trilinear), [0.5, 0.5, 0.0]);
+
+        let text = "LUT_3D_SIZE 2\nDOMAIN_MIN 0 0 0\nDOMAIN_MAX 2 2 2\n\
+            0 0 0\n1 0 0\n0 1 0\n1 1 0\n0 0 1\n1 0 1\n0 1 1\n1 1 1\n";
+        let lut = Lut3d::parse_cube(text).unwrap();
+        assert_close(lut.sample([1.0, 0.5, 0.0], LutInterpolation::Trilinear), [0.5, 0.25, 0.0]);
+    }
+
+    #[test]
+    fn test_parse_3dl() {
+        let mut text = String::from("0 1023\n");
+        for r in [0, 1023] {
+            for g in [0, 1023] {
+                for b in [0, 1023] {
+                    text.push_str(&format!("{} {} {}\n", r, g, b));
+                }
+            }
+        }
+        let lut = Lut3d::parse_3dl(&text).unwrap();
+        assert_eq!(lut.size, 2);
+        assert_eq!(lut, Lut3d::identity(2));
+
+        let lustre = format!("Mesh 1 12\n{}", &text[7..]).replace("1023", "4095");
+        assert_eq!(Lut3d::parse_3dl(&lustre).unwrap(), Lut3d::identity(2));
+    }
+
+    #[test]
+    fn test_interpolation_on_identity() {
+        let lut = Lut3d::identity(17);
+        for interpolation in [LutInterpolation::Trilinear, LutInterpolation::Tetrahedral] {
+            assert_close(lut.sample([0.3, 0.71, 0.05], interpolation), [0.3, 0.71, 0.05]);
+        }
+        assert_eq!(lut.to_rgba_f16_bytes().len(), 17 * 17 * 17 * 8);
+    }
+}
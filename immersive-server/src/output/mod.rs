@@ -3,27 +3,41 @@
 //! This module provides support for:
 //! - Multiple output screens with independent configurations
 //! - Slice-based input selection (crop/position from composition or layers)
-//! - Output transformations (perspective warp, mesh deformation)
+//! - Output transformations (perspective warp, mesh deformation, per-pixel UV maps)
 //! - Edge blending for seamless projector overlap
-//! - Per-output masking and color correction
+//! - Per-output masking and color correction, including 3D LUTs
+//! - Importing projmap-calibrator results as calibrated screens
+//! - MPCDI import/export for exchanging calibrations with other systems
 //! - Display enumeration and multi-monitor output
 
+mod calibration_import;
 mod color;
 pub mod display;
 mod edge_blend;
+mod lut;
 mod mask;
+mod mpcdi;
 mod preset;
 pub mod runtime;
 mod screen;
 pub mod slice;
+mod uv_map;
 mod warp;
 
+pub use calibration_import::{
+    import_calibration, screens_from_project, CalibrationImport, CalibrationImportError,
+};
 pub use color::{OutputColorCorrection, SliceColorCorrection};
 pub use display::{DisplayEvent, DisplayInfo, DisplayManager, DisplayStatus};
-pub use edge_blend::{EdgeBlendConfig, EdgeBlendRegion};
+pub use edge_blend::{EdgeBlendConfig, EdgeBlendCurve, EdgeBlendRegion};
+pub use lut::{load_lut, Lut3d, LutError, LutInterpolation, OutputLut};
 pub use mask::{BezierSegment, MaskShape, Point2D, SliceMask};
+pub use mpcdi::{export_mpcdi, import_mpcdi, MpcdiError, MpcdiImport, MpcdiProfile};
 pub use preset::{OutputPreset, OutputPresetError, OutputPresetManager, OutputPresetReference};
 pub use runtime::{OutputManager, ScreenRuntime, SliceParams, SliceRuntime};
 pub use screen::{OutputDevice, Screen, ScreenId};
 pub use slice::{Rect, Slice, SliceId, SliceInput, SliceOutput};
+pub use uv_map::{
+    load_attenuation, load_uv_map, AttenuationImage, UvMapError, UvMapImage, UvWarpMap,
+};
 pub use warp::{WarpInterpolation, WarpMesh, WarpPoint};
@@ -0,0 +1,1288 @@
+//! MPCDI (Multiple Projection Common Data Interchange) import and export
+//!
+//! An MPCDI profile is an XML description (`mpcdi.xml`) of display buffers
+//! split into projector regions, plus per-region data files: a PFM geometry
+//! warp and optional alpha (blend) and beta (black level) maps. `.mpcdi`
+//! packages are zip archives of exactly these files and can be imported
+//! directly, as can an already unpacked `mpcdi.xml`.
+//!
+//! On import every region becomes a screen at the region's resolution with a
+//! single slice. The buffer is treated as the composition, so the slice's
+//! input rect is the region's rectangle, and the warp, alpha and beta maps
+//! become the slice's UV map, attenuation and black level images.
+//!
+//! 2D profile warps hold region-normalized (x, y) coordinates per projector
+//! pixel (origin top-left) and are loaded directly. 3D and shader lamps warps
+//! hold 3D points; they are projected through the region's frustum (after the
+//! coordinate frame, for shader lamps) and saved as `.pmuv`.
+//!
+//! Alpha values are multiplied directly into the output signal, i.e. with an
+//! embedded gamma of 2.2. Alpha maps declaring a different `gammaEmbedded`
+//! are re-encoded on import, and export always writes 2.2.
+//!
+//! Imported packages are never modified: unpacked archives, projected warps
+//! and re-encoded alpha maps are written to a separate data directory.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use projmap_schema::UvMap;
+use serde::{Deserialize, Serialize};
+
+use super::screen::{OutputDevice, Screen, ScreenId};
+use super::slice::{Rect, Slice, SliceId, SliceOutput};
+use super::uv_map::{load_attenuation, load_uv_map, UvMapImage, UvWarpMap};
+
+/// Highest geometry/color level this importer understands
+const SUPPORTED_LEVEL: u32 = 2;
+
+/// Output pixels between exported warp grid nodes
+const EXPORT_WARP_SPACING: u32 = 4;
+
+/// Gamma of alpha maps as applied to the output signal (and recorded on export)
+const SIGNAL_ALPHA_GAMMA: f32 = 2.2;
+
+/// Embedded gammas closer than this to the signal gamma are used as-is
+const GAMMA_EPSILON: f32 = 0.01;
+
+/// File extension of MPCDI packages
+const MPCDI_PACKAGE_EXTENSION: &str = "mpcdi";
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// PUBLIC API
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// MPCDI profile type
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MpcdiProfile {
+    /// 2D media (`2d`)
+    TwoD,
+    /// 3D simulation (`3d`)
+    ThreeD,
+    /// Advanced 3D (`a3`)
+    AdvancedThreeD,
+    /// Shader lamps (`sl`)
+    ShaderLamps,
+}
+
+impl MpcdiProfile {
+    /// Parse the `profile` attribute
+    pub fn from_attribute(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "2d" => Some(Self::TwoD),
+            "3d" => Some(Self::ThreeD),
+            "a3" => Some(Self::AdvancedThreeD),
+            "sl" => Some(Self::ShaderLamps),
+            _ => None,
+        }
+    }
+
+    /// Value of the `profile` attribute
+    pub fn attribute(&self) -> &'static str {
+        match self {
+            Self::TwoD => "2d",
+            Self::ThreeD => "3d",
+            Self::AdvancedThreeD => "a3",
+            Self::ShaderLamps => "sl",
+        }
+    }
+}
+
+/// Result of importing an MPCDI profile
+#[derive(Debug, Clone)]
+pub struct MpcdiImport {
+    /// Name derived from the file (or its folder for `mpcdi.xml`)
+    pub name: String,
+    /// Profile declared by the file
+    pub profile: MpcdiProfile,
+    /// One screen per region
+    pub screens: Vec<Screen>,
+    /// Non-fatal issues (missing files, extra buffers, ...)
+    pub warnings: Vec<String>,
+}
+
+/// Errors that can occur during MPCDI import/export
+#[derive(Debug)]
+pub enum MpcdiError {
+    Io(std::io::Error),
+    XmlParse(quick_xml::DeError),
+    XmlWrite(quick_xml::SeError),
+    Image(image::ImageError),
+    Archive(zip::result::ZipError),
+    InvalidData(&'static str),
+    UnsupportedProfile(String),
+    NoRegions,
+}
+
+impl std::fmt::Display for MpcdiError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MpcdiError::Io(e) => write!(f, "IO error: {}", e),
+            MpcdiError::XmlParse(e) => write!(f, "XML parse error: {}", e),
+            MpcdiError::XmlWrite(e) => write!(f, "XML write error: {}", e),
+            MpcdiError::Image(e) => write!(f, "Image error: {}", e),
+            MpcdiError::Archive(e) => write!(f, "MPCDI package error: {}", e),
+            MpcdiError::InvalidData(reason) => write!(f, "Invalid MPCDI data: {}", reason),
+            MpcdiError::UnsupportedProfile(p) => write!(f, "Unsupported MPCDI profile '{}'", p),
+            MpcdiError::NoRegions => write!(f, "MPCDI file contains no regions"),
+        }
+    }
+}
+
+impl std::error::Error for MpcdiError {}
+
+/// Load an MPCDI package or XML file and build one screen per region
+///
+/// `path` is a `.mpcdi` archive or an unpacked `mpcdi.xml`. Archives are
+/// unpacked into `data_dir`, which also receives projected 3D warps and
+/// re-encoded alpha maps. Data file paths are resolved relative to the XML
+/// file. Files that are missing or fail to convert are reported as warnings.
+pub fn import_mpcdi(path: &Path, data_dir: &Path) -> Result<MpcdiImport, MpcdiError> {
+    let xml_path = if is_package(path) {
+        unpack_package(path, data_dir)?
+    } else {
+        path.to_path_buf()
+    };
+    let contents = std::fs::read_to_string(&xml_path).map_err(MpcdiError::Io)?;
+    let document: MpcdiDocument =
+        quick_xml::de::from_str(&contents).map_err(MpcdiError::XmlParse)?;
+    let profile = MpcdiProfile::from_attribute(&document.profile)
+        .ok_or_else(|| MpcdiError::UnsupportedProfile(document.profile.clone()))?;
+
+    let mut warnings = Vec::new();
+    if document.geometry > SUPPORTED_LEVEL {
+        warnings.push(format!(
+            "geometry level {} is above {}, warps are used as-is",
+            document.geometry, SUPPORTED_LEVEL
+        ));
+    }
+    if document.display.buffers.len() > 1 {
+        warnings.push(format!(
+            "{} buffers found, all are mapped onto the composition",
+            document.display.buffers.len()
+        ));
+    }
+
+    let filesets: HashMap<&str, &FilesetElement> = document
+        .files
+        .filesets
+        .iter()
+        .map(|f| (f.region.as_str(), f))
+        .collect();
+    let regions: Vec<&RegionElement> = document
+        .display
+        .buffers
+        .iter()
+        .flat_map(|b| b.regions.iter())
+        .collect();
+    if regions.is_empty() {
+        return Err(MpcdiError::NoRegions);
+    }
+
+    let dir = xml_path.parent().unwrap_or_else(|| Path::new("."));
+    let screens = regions
+        .iter()
+        .enumerate()
+        .map(|(index, region)| {
+            let id = index as u32 + 1;
+            let mut slice = Slice::new_full_composition(SliceId(id), "MPCDI");
+            slice.input_rect = Rect::new(region.x, region.y, region.xsize, region.ysize);
+            match filesets.get(region.id.as_str()) {
+                Some(fileset) => {
+                    slice.output.uv_map = uv_map_from_fileset(
+                        fileset,
+                        region,
+                        profile,
+                        dir,
+                        data_dir,
+                        index as u32,
+                        &mut warnings,
+                    );
+                }
+                None => warnings.push(format!("{}: no fileset, slice left unwarped", region.id)),
+            }
+
+            let mut screen = Screen::new(ScreenId(id), region.id.clone());
+            screen.device = OutputDevice::Virtual;
+            screen.width = region.x_resolution.max(1);
+            screen.height = region.y_resolution.max(1);
+            screen.add_slice(slice);
+            screen
+        })
+        .collect();
+
+    Ok(MpcdiImport {
+        name: import_name(path),
+        profile,
+        screens,
+        warnings,
+    })
+}
+
+/// Write screens as an MPCDI 2D profile
+///
+/// `path` is the XML file to write; warp, alpha and beta files are written
+/// next to it. Each screen becomes a region of a single buffer of
+/// `buffer_size` pixels (the composition). Only the first slice of a screen
+/// is exported. Returns non-fatal warnings.
+pub fn export_mpcdi(
+    screens: &[Screen],
+    path: &Path,
+    buffer_size: (u32, u32),
+) -> Result<Vec<String>, MpcdiError> {
+    let dir = path.parent().unwrap_or_else(|| Path::new("."));
+    let mut warnings = Vec::new();
+    let mut regions = Vec::new();
+    let mut filesets = Vec::new();
+
+    for screen in screens {
+        let Some(slice) = screen.slices.first() else {
+            warnings.push(format!("{}: no slices, skipped", screen.name));
+            continue;
+        };
+        if screen.slices.len() > 1 {
+            warnings.push(format!(
+                "{}: only the first of {} slices is exported",
+                screen.name,
+                screen.slices.len()
+            ));
+        }
+
+        let region_id = format!("screen{}", screen.id.0);
+        let width = screen.width.max(1);
+        let height = screen.height.max(1);
+        regions.push(RegionElement {
+            id: region_id.clone(),
+            x_resolution: width,
+            y_resolution: height,
+            x: slice.input_rect.x,
+            y: slice.input_rect.y,
+            xsize: slice.input_rect.width,
+            ysize: slice.input_rect.height,
+            frustum: None,
+            coordinate_frame: None,
+        });
+
+        // Slice images the warp and blend depend on
+        let uv_map = slice.output.uv_map.as_ref().filter(|m| m.enabled);
+        let uv_image = uv_map.filter(|m| !m.path.is_empty()).and_then(|m| {
+            load_uv_map(Path::new(&m.path), m.invert_v)
+                .map_err(|e| warnings.push(format!("{}: UV map not exported ({})", screen.name, e)))
+                .ok()
+        });
+        let attenuation = uv_map
+            .and_then(|m| m.attenuation_path.as_deref())
+            .and_then(|p| {
+                load_attenuation(Path::new(p))
+                    .map_err(|e| {
+                        warnings.push(format!("{}: attenuation not exported ({})", screen.name, e))
+                    })
+                    .ok()
+            });
+        let black_level = uv_map
+            .and_then(|m| m.black_level_path.as_deref())
+            .and_then(|p| {
+                load_attenuation(Path::new(p))
+                    .map_err(|e| {
+                        warnings.push(format!("{}: black level not exported ({})", screen.name, e))
+                    })
+                    .ok()
+            });
+
+        // Geometry warp, sampled on a corner-aligned grid over the screen
+        let columns = ((width - 1).div_ceil(EXPORT_WARP_SPACING) + 1).max(2);
+        let rows = ((height - 1).div_ceil(EXPORT_WARP_SPACING) + 1).max(2);
+        let mut warp = PfmImage::new(columns, rows, 3);
+        for row in 0..rows {
+            for col in 0..columns {
+                let uv = [
+                    col as f32 / (columns - 1) as f32,
+                    row as f32 / (rows - 1) as f32,
+                ];
+                let value = slice_warp(&slice.output, uv_image.as_ref(), uv)
+                    .map(|[x, y]| [x, y, 0.0])
+                    .unwrap_or([f32::NAN; 3]);
+                warp.set((row * columns + col) as usize, &value);
+            }
+        }
+        let warp_file = format!("{}_warp.pfm", region_id);
+        std::fs::write(dir.join(&warp_file), write_pfm(&warp)).map_err(MpcdiError::Io)?;
+
+        // Alpha map: edge blend times attenuation, zero outside the slice
+        let mut alpha_map = None;
+        if slice.output.edge_blend.is_any_enabled() || attenuation.is_some() {
+            let file = format!("{}_alpha.png", region_id);
+            let pixels = rasterize(width, height, &slice.output, |local| {
+                let blend = slice.output.edge_blend.alpha_at(local);
+                let gain = attenuation
+                    .as_ref()
+                    .map(|a| a.sample(local))
+                    .unwrap_or([1.0; 3]);
+                [gain[0] * blend[0], gain[1] * blend[1], gain[2] * blend[2]]
+            });
+            save_rgb16(&dir.join(&file), width, height, &pixels)?;
+            alpha_map = Some(MapFileElement {
+                path: file,
+                component_depth: Some(3),
+                bit_depth: Some(16),
+                gamma_embedded: Some(SIGNAL_ALPHA_GAMMA),
+            });
+        }
+
+        // Beta map: black level lift plus edge blend uplift, zero outside the slice
+        let mut beta_map = None;
+        if black_level.is_some() || slice.output.edge_blend.has_uplift() {
+            let file = format!("{}_beta.png", region_id);
+            let pixels = rasterize(width, height, &slice.output, |local| {
+                let uplift = slice.output.edge_blend.uplift_at(local);
+                let lift = black_level
+                    .as_ref()
+                    .map(|b| b.sample(local))
+                    .unwrap_or([0.0; 3]);
+                lift.map(|l| 1.0 - (1.0 - l) * (1.0 - uplift))
+            });
+            save_rgb16(&dir.join(&file), width, height, &pixels)?;
+            beta_map = Some(MapFileElement {
+                path: file,
+                component_depth: Some(3),
+                bit_depth: Some(16),
+                gamma_embedded: None,
+            });
+        }
+
+        filesets.push(FilesetElement {
+            region: region_id,
+            geometry_warp_file: Some(WarpFileElement {
+                path: warp_file,
+                interpolation: Some("linear".to_string()),
+            }),
+            alpha_map,
+            beta_map,
+        });
+    }
+
+    let document = MpcdiDocument {
+        profile: MpcdiProfile::TwoD.attribute().to_string(),
+        geometry: 1,
+        color: Some(1),
+        version: "2.0".to_string(),
+        display: DisplayElement {
+            buffers: vec![BufferElement {
+                id: "buffer0".to_string(),
+                x_resolution: buffer_size.0.max(1),
+                y_resolution: buffer_size.1.max(1),
+                regions,
+            }],
+        },
+        files: FilesElement { filesets },
+    };
+    let xml = quick_xml::se::to_string(&document).map_err(MpcdiError::XmlWrite)?;
+    let formatted = format!("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n{}", xml);
+    std::fs::write(path, formatted).map_err(MpcdiError::Io)?;
+    Ok(warnings)
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// IMPORT HELPERS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Preset name for an imported file: the folder name for `mpcdi.xml`, else the file stem
+pub(crate) fn import_name(path: &Path) -> String {
+    let stem = path.file_stem().map(|s| s.to_string_lossy().into_owned());
+    let folder = path
+        .parent()
+        .and_then(|p| p.file_name())
+        .map(|s| s.to_string_lossy().into_owned());
+    match (stem, folder) {
+        (Some(stem), Some(folder)) if stem.eq_ignore_ascii_case("mpcdi") && !is_package(path) => folder,
+        (Some(stem), _) => stem,
+        _ => "MPCDI".to_string(),
+    }
+}
+
+/// Whether a path is a `.mpcdi` package rather than an XML file
+fn is_package(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case(MPCDI_PACKAGE_EXTENSION))
+}
+
+/// Unpack a `.mpcdi` archive into `dir`, returning the path of its `mpcdi.xml`
+fn unpack_package(path: &Path, dir: &Path) -> Result<PathBuf, MpcdiError> {
+    let file = std::fs::File::open(path).map_err(MpcdiError::Io)?;
+    let mut archive = zip::ZipArchive::new(file).map_err(MpcdiError::Archive)?;
+    std::fs::create_dir_all(dir).map_err(MpcdiError::Io)?;
+
+    let mut xml_path = None;
+    for i in 0..archive.len() {
+        let mut entry = archive.by_index(i).map_err(MpcdiError::Archive)?;
+        // Skip directories and entries that would escape `dir`
+        let Some(name) = entry.enclosed_name() else {
+            continue;
+        };
+        if entry.is_dir() {
+            continue;
+        }
+        let output = dir.join(&name);
+        if let Some(parent) = output.parent() {
+            std::fs::create_dir_all(parent).map_err(MpcdiError::Io)?;
+        }
+        let mut writer = std::fs::File::create(&output).map_err(MpcdiError::Io)?;
+        std::io::copy(&mut entry, &mut writer).map_err(MpcdiError::Io)?;
+
+        let is_xml = name
+            .file_name()
+            .is_some_and(|n| n.to_string_lossy().eq_ignore_ascii_case("mpcdi.xml"));
+        // Prefer the top-level mpcdi.xml if the archive has several
+        if is_xml && (xml_path.is_none() || name.components().count() == 1) {
+            xml_path = Some(output);
+        }
+    }
+
+    xml_path.ok_or(MpcdiError::InvalidData("package contains no mpcdi.xml"))
+}
+
+/// Build the slice UV map from a region's files
+fn uv_map_from_fileset(
+    fileset: &FilesetElement,
+    region: &RegionElement,
+    profile: MpcdiProfile,
+    dir: &Path,
+    data_dir: &Path,
+    index: u32,
+    warnings: &mut Vec<String>,
+) -> Option<UvWarpMap> {
+    let existing = |file: &str, kind: &str, warnings: &mut Vec<String>| {
+        let path = dir.join(file);
+        if path.is_file() {
+            Some(path.to_string_lossy().into_owned())
+        } else {
+            warnings.push(format!("{}: {} '{}' not found", region.id, kind, file));
+            None
+        }
+    };
+
+    let warp_path = match &fileset.geometry_warp_file {
+        Some(warp) => existing(&warp.path, "geometry warp", warnings),
+        None => {
+            warnings.push(format!(
+                "{}: no geometry warp, slice left unwarped",
+                region.id
+            ));
+            None
+        }
+    };
+    let warp_path = match (warp_path, profile) {
+        (Some(path), MpcdiProfile::TwoD) => Some(path),
+        (Some(path), _) => match convert_3d_warp(Path::new(&path), data_dir, region, profile, index) {
+            Ok(converted) => Some(converted),
+            Err(e) => {
+                warnings.push(format!("{}: could not project 3D warp ({})", region.id, e));
+                None
+            }
+        },
+        (None, _) => None,
+    };
+    let attenuation_path = fileset.alpha_map.as_ref().and_then(|map| {
+        let path = existing(&map.path, "alpha map", warnings)?;
+        let gamma = map.gamma_embedded.unwrap_or(SIGNAL_ALPHA_GAMMA);
+        if (gamma - SIGNAL_ALPHA_GAMMA).abs() < GAMMA_EPSILON {
+            return Some(path);
+        }
+        match convert_alpha_gamma(Path::new(&path), data_dir, gamma) {
+            Ok(converted) => Some(converted),
+            Err(e) => {
+                warnings.push(format!(
+                    "{}: could not convert alpha map from gamma {} ({}), used as-is",
+                    region.id, gamma, e
+                ));
+                Some(path)
+            }
+        }
+    });
+    let black_level_path = fileset
+        .beta_map
+        .as_ref()
+        .and_then(|m| existing(&m.path, "beta map", warnings));
+
+    if warp_path.is_none() && attenuation_path.is_none() && black_level_path.is_none() {
+        return None;
+    }
+    let mut uv_map = UvWarpMap::new(warp_path.unwrap_or_default());
+    uv_map.attenuation_path = attenuation_path;
+    uv_map.black_level_path = black_level_path;
+    Some(uv_map)
+}
+
+/// Re-encode an alpha map with embedded gamma `gamma` for the output signal
+///
+/// A value `a` stored with gamma `g` is the linear alpha `a^g`, which the
+/// signal (gamma 2.2) needs as `a^(g / 2.2)`. The result is saved as a 16-bit
+/// PNG in `data_dir`; returns its path.
+fn convert_alpha_gamma(path: &Path, data_dir: &Path, gamma: f32) -> Result<String, MpcdiError> {
+    if !(gamma.is_finite() && gamma > 0.0) {
+        return Err(MpcdiError::InvalidData("gammaEmbedded must be positive"));
+    }
+    let alpha = image::open(path).map_err(MpcdiError::Image)?.to_rgb32f();
+    let exponent = gamma / SIGNAL_ALPHA_GAMMA;
+    let pixels: Vec<[f32; 3]> = alpha
+        .pixels()
+        .map(|p| p.0.map(|c| c.clamp(0.0, 1.0).powf(exponent)))
+        .collect();
+
+    std::fs::create_dir_all(data_dir).map_err(MpcdiError::Io)?;
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    let output = data_dir.join(format!("{}_gamma{}.png", stem, SIGNAL_ALPHA_GAMMA));
+    save_rgb16(&output, alpha.width(), alpha.height(), &pixels)?;
+    Ok(output.to_string_lossy().into_owned())
+}
+
+/// Project a 3D warp through the region frustum and save it as `.pmuv`
+///
+/// The map is written to `data_dir`; returns its path.
+fn convert_3d_warp(
+    path: &Path,
+    data_dir: &Path,
+    region: &RegionElement,
+    profile: MpcdiProfile,
+    index: u32,
+) -> Result<String, MpcdiError> {
+    let frustum = region.frustum.as_ref().ok_or(MpcdiError::InvalidData(
+        "region has no frustum for a 3D warp",
+    ))?;
+    let frame = match profile {
+        MpcdiProfile::ShaderLamps => region.coordinate_frame.as_ref(),
+        _ => None,
+    };
+
+    let bytes = std::fs::read(path).map_err(MpcdiError::Io)?;
+    let pfm = read_pfm(&bytes)?;
+    if pfm.channels < 3 {
+        return Err(MpcdiError::InvalidData("3D warps need three channels"));
+    }
+    let data = (0..pfm.width as usize * pfm.height as usize)
+        .map(|i| {
+            let point = [pfm.value(i, 0), pfm.value(i, 1), pfm.value(i, 2)];
+            let point = frame.map(|f| f.to_display(point)).unwrap_or(point);
+            frustum.project(point).unwrap_or([f32::NAN; 2])
+        })
+        .collect();
+    let map = UvMap {
+        projector_id: index,
+        width: pfm.width,
+        height: pfm.height,
+        data,
+    };
+
+    std::fs::create_dir_all(data_dir).map_err(MpcdiError::Io)?;
+    let stem = path.file_stem().unwrap_or_default();
+    let output = data_dir.join(stem).with_extension("pmuv");
+    std::fs::write(&output, map.to_bytes()).map_err(MpcdiError::Io)?;
+    Ok(output.to_string_lossy().into_owned())
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// EXPORT HELPERS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Map a screen UV to the slice's input UV on the CPU, matching the slice shader
+///
+/// Returns `None` outside the slice's output rect or where the UV map is unmapped.
+fn slice_warp(output: &SliceOutput, uv_map: Option<&UvMapImage>, uv: [f32; 2]) -> Option<[f32; 2]> {
+    let rect = &output.rect;
+    let local = local_uv(rect, uv)?;
+
+    let mut warped = match uv_map {
+        Some(map) => map.sample(local)?,
+        None => {
+            let mut warped = local;
+            if let Some(corners) = &output.perspective {
+                let lerp = |a: f32, b: f32, t: f32| a + (b - a) * t;
+                let top = [
+                    lerp(corners[0].x, corners[1].x, warped[0]),
+                    lerp(corners[0].y, corners[1].y, warped[0]),
+                ];
+                let bottom = [
+                    lerp(corners[3].x, corners[2].x, warped[0]),
+                    lerp(corners[3].y, corners[2].y, warped[0]),
+                ];
+                warped = [
+                    lerp(top[0], bottom[0], warped[1]),
+                    lerp(top[1], bottom[1], warped[1]),
+                ];
+            }
+            if let Some(mesh) = &output.mesh {
+                warped = mesh.sample(warped);
+            }
+            warped
+        }
+    };
+
+    if output.rotation.abs() > 0.0001 {
+        let aspect = rect.width / rect.height.max(0.0001);
+        let (sin, cos) = (-output.rotation.to_radians()).sin_cos();
+        let x = (warped[0] - 0.5) * aspect;
+        let y = warped[1] - 0.5;
+        warped = [(x * cos - y * sin) / aspect + 0.5, x * sin + y * cos + 0.5];
+    }
+    if output.flip_h {
+        warped[0] = 1.0 - warped[0];
+    }
+    if output.flip_v {
+        warped[1] = 1.0 - warped[1];
+    }
+    Some(warped)
+}
+
+/// Screen UV to slice-local UV, `None` outside the output rect
+fn local_uv(rect: &Rect, uv: [f32; 2]) -> Option<[f32; 2]> {
+    if uv[0] < rect.x
+        || uv[0] > rect.x + rect.width
+        || uv[1] < rect.y
+        || uv[1] > rect.y + rect.height
+    {
+        return None;
+    }
+    Some([
+        (uv[0] - rect.x) / rect.width.max(f32::EPSILON),
+        (uv[1] - rect.y) / rect.height.max(f32::EPSILON),
+    ])
+}
+
+/// Evaluate an RGB value at every screen pixel inside the slice, black elsewhere
+fn rasterize(
+    width: u32,
+    height: u32,
+    output: &SliceOutput,
+    value: impl Fn([f32; 2]) -> [f32; 3],
+) -> Vec<[f32; 3]> {
+    let mut pixels = Vec::with_capacity((width * height) as usize);
+    for y in 0..height {
+        for x in 0..width {
+            let uv = [
+                (x as f32 + 0.5) / width as f32,
+                (y as f32 + 0.5) / height as f32,
+            ];
+            pixels.push(local_uv(&output.rect, uv).map(&value).unwrap_or([0.0; 3]));
+        }
+    }
+    pixels
+}
+
+fn save_rgb16(path: &Path, width: u32, height: u32, pixels: &[[f32; 3]]) -> Result<(), MpcdiError> {
+    let data = pixels
+        .iter()
+        .flat_map(|p| p.map(|c| (c.clamp(0.0, 1.0) * 65535.0).round() as u16))
+        .collect();
+    let image = image::ImageBuffer::<image::Rgb<u16>, Vec<u16>>::from_raw(width, height, data)
+        .ok_or(MpcdiError::InvalidData("image size mismatch"))?;
+    image.save(path).map_err(MpcdiError::Image)
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// 3D PROJECTION
+// ═══════════════════════════════════════════════════════════════════════════════
+
+impl FrustumElement {
+    /// Project a point in the display coordinate system to frustum UV
+    ///
+    /// The default view looks down -Z with +Y up. Yaw rotates about Y, pitch
+    /// about X and roll about the view axis, applied in that order. Angles are
+    /// in degrees; left and down angles are normally negative.
+    fn project(&self, point: [f32; 3]) -> Option<[f32; 2]> {
+        if !point.iter().all(|c| c.is_finite()) {
+            return None;
+        }
+        // Undo roll, pitch and yaw (inverse of R = Ry * Rx * Rz)
+        let (sy, cy) = self.yaw.to_radians().sin_cos();
+        let (sp, cp) = self.pitch.to_radians().sin_cos();
+        let (sr, cr) = self.roll.to_radians().sin_cos();
+        let [x, y, z] = point;
+        let (x, z) = (x * cy - z * sy, x * sy + z * cy);
+        let (y, z) = (y * cp + z * sp, -y * sp + z * cp);
+        let (x, y) = (x * cr + y * sr, -x * sr + y * cr);
+
+        let depth = -z;
+        if depth <= f32::EPSILON {
+            return None;
+        }
+        let left = self.left_angle.to_radians().tan();
+        let right = self.right_angle.to_radians().tan();
+        let up = self.up_angle.to_radians().tan();
+        let down = self.down_angle.to_radians().tan();
+        if (right - left).abs() < f32::EPSILON || (up - down).abs() < f32::EPSILON {
+            return None;
+        }
+        Some([
+            (x / depth - left) / (right - left),
+            (up - y / depth) / (up - down),
+        ])
+    }
+}
+
+impl CoordinateFrameElement {
+    /// Transform a shader lamps model point into the display coordinate system
+    ///
+    /// The pitch, yaw and roll vectors are the display X, Y and Z axes
+    /// expressed in model coordinates, with the display origin at `pos`.
+    fn to_display(&self, point: [f32; 3]) -> [f32; 3] {
+        let d = [
+            point[0] - self.posx,
+            point[1] - self.posy,
+            point[2] - self.posz,
+        ];
+        let dot = |a: [f32; 3]| a[0] * d[0] + a[1] * d[1] + a[2] * d[2];
+        [
+            dot([self.pitchx, self.pitchy, self.pitchz]),
+            dot([self.yawx, self.yawy, self.yawz]),
+            dot([self.rollx, self.rolly, self.rollz]),
+        ]
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// PFM IMAGES
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Decoded PFM image, rows stored top to bottom
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct PfmImage {
+    pub width: u32,
+    pub height: u32,
+    /// 1 (`Pf`) or 3 (`PF`)
+    pub channels: usize,
+    pub data: Vec<f32>,
+}
+
+impl PfmImage {
+    fn new(width: u32, height: u32, channels: usize) -> Self {
+        Self {
+            width,
+            height,
+            channels,
+            data: vec![0.0; width as usize * height as usize * channels],
+        }
+    }
+
+    /// Channel value of a pixel (row-major index), NaN for a missing channel
+    pub fn value(&self, index: usize, channel: usize) -> f32 {
+        if channel >= self.channels {
+            return f32::NAN;
+        }
+        self.data[index * self.channels + channel]
+    }
+
+    fn set(&mut self, index: usize, values: &[f32]) {
+        let start = index * self.channels;
+        self.data[start..start + self.channels].copy_from_slice(&values[..self.channels]);
+    }
+}
+
+/// Decode a PFM image (`PF` color or `Pf` grayscale, either byte order)
+pub(crate) fn read_pfm(bytes: &[u8]) -> Result<PfmImage, MpcdiError> {
+    // Header: magic, width, height, scale - whitespace separated, then one whitespace byte
+    let mut tokens = Vec::with_capacity(4);
+    let mut pos = 0;
+    while tokens.len() < 4 {
+        while pos < bytes.len() && bytes[pos].is_ascii_whitespace() {
+            pos += 1;
+        }
+        let start = pos;
+        while pos < bytes.len() && !bytes[pos].is_ascii_whitespace() {
+            pos += 1;
+        }
+        if start == pos {
+            return Err(MpcdiError::InvalidData("truncated header"));
+        }
+        tokens.push(
+            std::str::from_utf8(&bytes[start..pos])
+                .map_err(|_| MpcdiError::InvalidData("bad header"))?,
+        );
+    }
+    let data_start = pos + 1;
+
+    let channels = match tokens[0] {
+        "PF" => 3,
+        "Pf" => 1,
+        _ => return Err(MpcdiError::InvalidData("missing PF/Pf magic")),
+    };
+    let width: u32 = tokens[1]
+        .parse()
+        .map_err(|_| MpcdiError::InvalidData("bad width"))?;
+    let height: u32 = tokens[2]
+        .parse()
+        .map_err(|_| MpcdiError::InvalidData("bad height"))?;
+    let scale: f32 = tokens[3]
+        .parse()
+        .map_err(|_| MpcdiError::InvalidData("bad scale"))?;
+    if width == 0 || height == 0 {
+        return Err(MpcdiError::InvalidData("empty image"));
+    }
+    let little_endian = scale < 0.0;
+
+    let row_len = width as usize * channels;
+    let expected = row_len * height as usize * 4;
+    let payload = bytes
+        .get(data_start..)
+        .filter(|p| p.len() >= expected)
+        .ok_or(MpcdiError::InvalidData("truncated pixel data"))?;
+
+    let floats: Vec<f32> = payload[..expected]
+        .chunks_exact(4)
+        .map(|c| {
+            let b = [c[0], c[1], c[2], c[3]];
+            if little_endian {
+                f32::from_le_bytes(b)
+            } else {
+                f32::from_be_bytes(b)
+            }
+        })
+        .collect();
+
+    // PFM stores the bottom row first
+    let data = floats
+        .chunks_exact(row_len)
+        .rev()
+        .flatten()
+        .copied()
+        .collect();
+    Ok(PfmImage {
+        width,
+        height,
+        channels,
+        data,
+    })
+}
+
+/// Encode a PFM image (little endian)
+pub(crate) fn write_pfm(image: &PfmImage) -> Vec<u8> {
+    let magic = if image.channels == 1 { "Pf" } else { "PF" };
+    let mut bytes = format!("{}\n{} {}\n-1.0\n", magic, image.width, image.height).into_bytes();
+    let row_len = image.width as usize * image.channels;
+    for row in image.data.chunks_exact(row_len).rev() {
+        for value in row {
+            bytes.extend_from_slice(&value.to_le_bytes());
+        }
+    }
+    bytes
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// XML SCHEMA
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename = "MPCDI")]
+struct MpcdiDocument {
+    #[serde(rename = "@profile")]
+    profile: String,
+    #[serde(rename = "@geometry", default = "default_level")]
+    geometry: u32,
+    #[serde(rename = "@color", default, skip_serializing_if = "Option::is_none")]
+    color: Option<u32>,
+    #[serde(rename = "@version", default)]
+    version: String,
+    #[serde(rename = "display")]
+    display: DisplayElement,
+    #[serde(rename = "files", default)]
+    files: FilesElement,
+}
+
+fn default_level() -> u32 {
+    1
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct DisplayElement {
+    #[serde(rename = "buffer", default)]
+    buffers: Vec<BufferElement>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct BufferElement {
+    #[serde(rename = "@id")]
+    id: String,
+    #[serde(rename = "@Xresolution")]
+    x_resolution: u32,
+    #[serde(rename = "@Yresolution")]
+    y_resolution: u32,
+    #[serde(rename = "region", default)]
+    regions: Vec<RegionElement>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct RegionElement {
+    #[serde(rename = "@id")]
+    id: String,
+    #[serde(rename = "@Xresolution")]
+    x_resolution: u32,
+    #[serde(rename = "@Yresolution")]
+    y_resolution: u32,
+    #[serde(rename = "@x")]
+    x: f32,
+    #[serde(rename = "@y")]
+    y: f32,
+    #[serde(rename = "@xsize")]
+    xsize: f32,
+    #[serde(rename = "@ysize")]
+    ysize: f32,
+    #[serde(rename = "frustum", default, skip_serializing_if = "Option::is_none")]
+    frustum: Option<FrustumElement>,
+    #[serde(
+        rename = "coordinateFrame",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    coordinate_frame: Option<CoordinateFrameElement>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct FrustumElement {
+    yaw: f32,
+    pitch: f32,
+    roll: f32,
+    #[serde(rename = "rightAngle")]
+    right_angle: f32,
+    #[serde(rename = "leftAngle")]
+    left_angle: f32,
+    #[serde(rename = "upAngle")]
+    up_angle: f32,
+    #[serde(rename = "downAngle")]
+    down_angle: f32,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CoordinateFrameElement {
+    posx: f32,
+    posy: f32,
+    posz: f32,
+    yawx: f32,
+    yawy: f32,
+    yawz: f32,
+    pitchx: f32,
+    pitchy: f32,
+    pitchz: f32,
+    rollx: f32,
+    rolly: f32,
+    rollz: f32,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct FilesElement {
+    #[serde(rename = "fileset", default)]
+    filesets: Vec<FilesetElement>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct FilesetElement {
+    #[serde(rename = "@region")]
+    region: String,
+    #[serde(
+        rename = "geometryWarpFile",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    geometry_warp_file: Option<WarpFileElement>,
+    #[serde(rename = "alphaMap", default, skip_serializing_if = "Option::is_none")]
+    alpha_map: Option<MapFileElement>,
+    #[serde(rename = "betaMap", default, skip_serializing_if = "Option::is_none")]
+    beta_map: Option<MapFileElement>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct WarpFileElement {
+    path: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    interpolation: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct MapFileElement {
+    path: String,
+    #[serde(
+        rename = "componentDepth",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    component_depth: Option<u32>,
+    #[serde(rename = "bitDepth", default, skip_serializing_if = "Option::is_none")]
+    bit_depth: Option<u32>,
+    #[serde(
+        rename = "gammaEmbedded",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    gamma_embedded: Option<f32>,
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// TESTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::EdgeBlendConfig;
+
+    fn temp_dir(name: &str) -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(format!("mpcdi_test_{}_{}", name, std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_pfm_round_trip_and_byte_order() {
+        let mut image = PfmImage::new(2, 2, 3);
+        image.set(0, &[0.0, 0.0, 0.0]);
+        image.set(1, &[1.0, 0.0, 0.0]);
+        image.set(2, &[0.0, 1.0, 0.0]);
+        image.set(3, &[f32::NAN, f32::NAN, 0.0]);
+        let decoded = read_pfm(&write_pfm(&image)).unwrap();
+        assert_eq!((decoded.width, decoded.height, decoded.channels), (2, 2, 3));
+        assert_eq!(decoded.value(1, 0), 1.0);
+        assert_eq!(decoded.value(2, 1), 1.0);
+        assert!(decoded.value(3, 0).is_nan());
+
+        // Big-endian grayscale, bottom row first
+        let mut bytes = b"Pf 1 2 1.0\n".to_vec();
+        bytes.extend_from_slice(&2.0f32.to_be_bytes());
+        bytes.extend_from_slice(&1.0f32.to_be_bytes());
+        let gray = read_pfm(&bytes).unwrap();
+        assert_eq!(gray.data, vec![1.0, 2.0]);
+        assert!(gray.value(0, 1).is_nan());
+
+        assert!(read_pfm(b"PF 4 4 -1.0\n").is_err());
+        assert!(read_pfm(b"P6 1 1 255\n").is_err());
+    }
+
+    #[test]
+    fn test_import_2d_profile() {
+        let dir = temp_dir("import_2d");
+        let xml = r#"<?xml version="1.0" encoding="utf-8"?>
+<MPCDI profile="2d" geometry="1" color="1" version="2.0">
+  <display>
+    <buffer id="buffer0" Xresolution="3840" Yresolution="1080">
+      <region id="left" Xresolution="1920" Yresolution="1080" x="0.0" y="0.0" xsize="0.55" ysize="1.0"/>
+      <region id="right" Xresolution="1920" Yresolution="1080" x="0.45" y="0.0" xsize="0.55" ysize="1.0"/>
+    </buffer>
+  </display>
+  <files>
+    <fileset region="left">
+      <geometryWarpFile>
+        <path>left_warp.pfm</path>
+        <interpolation>linear</interpolation>
+      </geometryWarpFile>
+      <alphaMap>
+        <path>left_alpha.png</path>
+        <componentDepth>1</componentDepth>
+        <bitDepth>8</bitDepth>
+        <gammaEmbedded>2.2</gammaEmbedded>
+      </alphaMap>
+    </fileset>
+    <fileset region="right">
+      <geometryWarpFile>
+        <path>right_warp.pfm</path>
+      </geometryWarpFile>
+    </fileset>
+  </files>
+</MPCDI>"#;
+        let path = dir.join("mpcdi.xml");
+        std::fs::write(&path, xml).unwrap();
+        std::fs::write(
+            dir.join("left_warp.pfm"),
+            write_pfm(&PfmImage::new(2, 2, 3)),
+        )
+        .unwrap();
+        image::GrayImage::new(4, 4)
+            .save(dir.join("left_alpha.png"))
+            .unwrap();
+
+        let import = import_mpcdi(&path, &dir.join("data")).unwrap();
+        assert_eq!(import.profile, MpcdiProfile::TwoD);
+        assert_eq!(import.name, dir.file_name().unwrap().to_string_lossy());
+        assert_eq!(import.screens.len(), 2);
+
+        let left = &import.screens[0];
+        assert_eq!(
+            (left.name.as_str(), left.width, left.height),
+            ("left", 1920, 1080)
+        );
+        let slice = &left.slices[0];
+        assert_eq!(slice.input_rect.width, 0.55);
+        let uv_map = slice.output.uv_map.as_ref().unwrap();
+        assert!(uv_map.path.ends_with("left_warp.pfm"));
+        assert!(uv_map
+            .attenuation_path
+            .as_ref()
+            .unwrap()
+            .ends_with("left_alpha.png"));
+
+        // Right warp is missing: warned and left unwarped
+        assert_eq!(import.screens[1].slices[0].input_rect.x, 0.45);
+        assert!(import.screens[1].slices[0].output.uv_map.is_none());
+        assert_eq!(import.warnings.len(), 1);
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_import_3d_profile_projects_through_frustum() {
+        let dir = temp_dir("import_3d");
+        let xml = r#"<MPCDI profile="3d" geometry="2" version="2.0">
+  <display>
+    <buffer id="b" Xresolution="1920" Yresolution="1080">
+      <region id="r" Xresolution="1920" Yresolution="1080" x="0" y="0" xsize="1" ysize="1">
+        <frustum>
+          <yaw>0</yaw><pitch>0</pitch><roll>0</roll>
+          <rightAngle>45</rightAngle><leftAngle>-45</leftAngle>
+          <upAngle>45</upAngle><downAngle>-45</downAngle>
+        </frustum>
+      </region>
+    </buffer>
+  </display>
+  <files>
+    <fileset region="r">
+      <geometryWarpFile><path>r.pfm</path></geometryWarpFile>
+    </fileset>
+  </files>
+</MPCDI>"#;
+        let path = dir.join("dome.xml");
+        std::fs::write(&path, xml).unwrap();
+        let mut warp = PfmImage::new(2, 1, 3);
+        warp.set(0, &[-1.0, 1.0, -1.0]); // top-left corner of the frustum
+        warp.set(1, &[0.0, 0.0, 1.0]); // behind the viewer
+        std::fs::write(dir.join("r.pfm"), write_pfm(&warp)).unwrap();
+
+        let data_dir = dir.join("data");
+        let import = import_mpcdi(&path, &data_dir).unwrap();
+        assert_eq!(import.name, "dome");
+        let uv_map = import.screens[0].slices[0].output.uv_map.as_ref().unwrap();
+        // Written to the data directory, not into the package
+        assert_eq!(Path::new(&uv_map.path), data_dir.join("r.pmuv"));
+        assert!(!dir.join("r.pmuv").exists());
+
+        let loaded = load_uv_map(Path::new(&uv_map.path), false).unwrap();
+        assert!(loaded.uv[0][0].abs() < 1e-5 && loaded.uv[0][1].abs() < 1e-5);
+        assert!(loaded.uv[1][0].is_nan());
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_export_then_import() {
+        let dir = temp_dir("export");
+        let mut screen = Screen::new(ScreenId(7), "Projector");
+        screen.width = 64;
+        screen.height = 32;
+        let mut slice = Slice::new_full_composition(SliceId(1), "Slice");
+        slice.input_rect = Rect::new(0.5, 0.0, 0.5, 1.0);
+        slice.output.flip_h = true;
+        slice.output.edge_blend = EdgeBlendConfig::horizontal(0.25, 1.0);
+        screen.add_slice(slice);
+
+        let path = dir.join("mpcdi.xml");
+        let warnings = export_mpcdi(&[screen], &path, (128, 32)).unwrap();
+        assert!(warnings.is_empty());
+        assert!(dir.join("screen7_alpha.png").is_file());
+
+        let import = import_mpcdi(&path, &dir.join("data")).unwrap();
+        let imported = &import.screens[0];
+        assert_eq!((imported.width, imported.height), (64, 32));
+        assert_eq!(imported.slices[0].input_rect.x, 0.5);
+
+        let uv_map = imported.slices[0].output.uv_map.as_ref().unwrap();
+        let warp = load_uv_map(Path::new(&uv_map.path), false).unwrap();
+        assert_eq!(warp.uv[0], [1.0, 0.0]);
+        let last = warp.uv[warp.uv.len() - 1];
+        assert_eq!(last, [0.0, 1.0]);
+
+        let alpha = load_attenuation(Path::new(uv_map.attenuation_path.as_ref().unwrap())).unwrap();
+        assert_eq!(alpha.sample([0.5, 0.5]), [1.0; 3]);
+        assert!(alpha.sample([0.0, 0.5])[0] < 0.05);
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    /// Single-region 2D profile whose alpha map declares the given embedded gamma
+    fn alpha_gamma_profile(gamma: &str) -> String {
+        format!(
+            r#"<MPCDI profile="2d" geometry="1" version="2.0">
+  <display>
+    <buffer id="b" Xresolution="4" Yresolution="1">
+      <region id="r" Xresolution="4" Yresolution="1" x="0" y="0" xsize="1" ysize="1"/>
+    </buffer>
+  </display>
+  <files>
+    <fileset region="r">
+      <alphaMap><path>alpha.png</path><gammaEmbedded>{}</gammaEmbedded></alphaMap>
+    </fileset>
+  </files>
+</MPCDI>"#,
+            gamma
+        )
+    }
+
+    #[test]
+    fn test_import_linear_alpha_map_is_gamma_encoded() {
+        let dir = temp_dir("alpha_gamma");
+        let path = dir.join("mpcdi.xml");
+        std::fs::write(&path, alpha_gamma_profile("1.0")).unwrap();
+        let alpha = image::GrayImage::from_raw(4, 1, vec![0, 64, 128, 255]).unwrap();
+        alpha.save(dir.join("alpha.png")).unwrap();
+
+        let data_dir = dir.join("data");
+        let import = import_mpcdi(&path, &data_dir).unwrap();
+        assert!(!import.warnings.iter().any(|w| w.contains("alpha")), "{:?}", import.warnings);
+        let uv_map = import.screens[0].slices[0].output.uv_map.as_ref().unwrap();
+        let converted = Path::new(uv_map.attenuation_path.as_ref().unwrap());
+        assert!(converted.starts_with(&data_dir));
+
+        // Linear alpha a becomes the signal multiplier a^(1/2.2)
+        let loaded = load_attenuation(converted).unwrap();
+        for (i, value) in [0u8, 64, 128, 255].into_iter().enumerate() {
+            let expected = (value as f32 / 255.0).powf(1.0 / 2.2);
+            assert!((loaded.rgba[i][0] - expected).abs() < 1e-3, "texel {}", i);
+        }
+
+        // Alpha maps already at gamma 2.2 are used in place
+        std::fs::write(&path, alpha_gamma_profile("2.2")).unwrap();
+        let import = import_mpcdi(&path, &data_dir).unwrap();
+        let uv_map = import.screens[0].slices[0].output.uv_map.as_ref().unwrap();
+        assert_eq!(Path::new(uv_map.attenuation_path.as_ref().unwrap()), dir.join("alpha.png"));
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_import_mpcdi_package() {
+        use std::io::Write;
+
+        let dir = temp_dir("package");
+        let package = dir.join("Partner Show.mpcdi");
+        {
+            let mut zip = zip::ZipWriter::new(std::fs::File::create(&package).unwrap());
+            let options = zip::write::SimpleFileOptions::default();
+            zip.start_file("mpcdi.xml", options).unwrap();
+            zip.write_all(alpha_gamma_profile("2.2").as_bytes()).unwrap();
+            zip.start_file("alpha.png", options).unwrap();
+            let mut png = std::io::Cursor::new(Vec::new());
+            image::GrayImage::new(4, 1)
+                .write_to(&mut png, image::ImageFormat::Png)
+                .unwrap();
+            zip.write_all(png.get_ref()).unwrap();
+            zip.finish().unwrap();
+        }
+
+        let data_dir = dir.join("data");
+        let import = import_mpcdi(&package, &data_dir).unwrap();
+        assert_eq!(import.name, "Partner Show");
+        assert!(!import.warnings.iter().any(|w| w.contains("alpha")), "{:?}", import.warnings);
+        let uv_map = import.screens[0].slices[0].output.uv_map.as_ref().unwrap();
+        assert_eq!(Path::new(uv_map.attenuation_path.as_ref().unwrap()), data_dir.join("alpha.png"));
+
+        // Not a zip archive
+        std::fs::write(&package, b"not a zip").unwrap();
+        assert!(matches!(import_mpcdi(&package, &data_dir), Err(MpcdiError::Archive(_))));
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -5,13 +5,15 @@
 //! - Warp mesh, edge blending, color correction
 //! - Built-in preset configurations
 //! - System-wide preset storage
+//! - MPCDI import
 
 use quick_xml::de::from_str;
 use quick_xml::se::to_string;
 use serde::{Deserialize, Serialize};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
+use super::mpcdi::{import_mpcdi, import_name, MpcdiError, MpcdiImport};
 use super::screen::Screen;
 use super::slice::SliceId;
 use super::ScreenId;
@@ -301,6 +303,21 @@ impl OutputPresetManager {
         Ok(())
     }
 
+    /// Import an MPCDI package or XML file as a user preset and make it active
+    ///
+    /// The preset is named after the file (see [`MpcdiImport::name`]) and
+    /// saved like any other user preset. Files generated on import are kept
+    /// in `mpcdi/<name>` under the presets directory, so the partner's
+    /// package is never written to.
+    pub fn import_mpcdi(&mut self, path: &Path) -> Result<MpcdiImport, OutputPresetError> {
+        let data_dir = Self::ensure_presets_dir()?
+            .join("mpcdi")
+            .join(Self::sanitize_filename(&import_name(path)));
+        let import = import_mpcdi(path, &data_dir).map_err(OutputPresetError::Mpcdi)?;
+        self.save_as_preset(&import.name, import.screens.clone())?;
+        Ok(import)
+    }
+
     /// Delete a preset by name (cannot delete built-in presets)
     pub fn delete_preset(&mut self, name: &str) -> Result<(), OutputPresetError> {
         // Find the preset
@@ -414,6 +431,7 @@ impl OutputPresetManager {
                     width: 0.1,
                     gamma: 2.2,
                     black_level: 0.0,
+                    ..Default::default()
                 },
                 ..Default::default()
             },
@@ -442,6 +460,7 @@ impl OutputPresetManager {
                     width: 0.1,
                     gamma: 2.2,
                     black_level: 0.0,
+                    ..Default::default()
                 },
                 ..Default::default()
             },
@@ -470,6 +489,7 @@ pub enum OutputPresetError {
     Io(std::io::Error),
     XmlParse(quick_xml::DeError),
     XmlWrite(quick_xml::SeError),
+    Mpcdi(MpcdiError),
     NoConfigDir,
     NotFound,
     CannotDeleteBuiltin,
@@ -482,6 +502,7 @@ impl std::fmt::Display for OutputPresetError {
             OutputPresetError::Io(e) => write!(f, "IO error: {}", e),
             OutputPresetError::XmlParse(e) => write!(f, "XML parse error: {}", e),
             OutputPresetError::XmlWrite(e) => write!(f, "XML write error: {}", e),
+            OutputPresetError::Mpcdi(e) => write!(f, "MPCDI error: {}", e),
             OutputPresetError::NoConfigDir => write!(f, "Could not find config directory"),
             OutputPresetError::NotFound => write!(f, "Preset not found"),
             OutputPresetError::CannotDeleteBuiltin => {
@@ -8,7 +8,10 @@ use std::collections::HashMap;
 
 use winit::window::WindowId;
 
-use super::{MaskShape, OutputDevice, Rect, Screen, ScreenId, Slice, SliceId, SliceInput, SliceMask, WarpMesh};
+use super::{
+    load_attenuation, load_uv_map, EdgeBlendRegion, LutInterpolation, MaskShape, OutputDevice, OutputLut, Rect, Screen, ScreenId,
+    Slice, SliceId, SliceInput, SliceMask, UvWarpMap, WarpInterpolation, WarpMesh,
+};
 use crate::network::NdiCapture;
 
 /// Screen-level color correction parameters (matches shader uniform)
@@ -19,6 +22,8 @@ pub struct ScreenParams {
     pub color_adjust: [f32; 4],
     /// RGB channel multipliers + padding
     pub color_rgb: [f32; 4],
+    /// 3D LUT: enabled, intensity, tetrahedral, padding
+    pub lut: [f32; 4],
 }
 
 impl Default for ScreenParams {
@@ -26,6 +31,7 @@ impl Default for ScreenParams {
         Self {
             color_adjust: [0.0, 1.0, 1.0, 1.0], // brightness=0, contrast=1, gamma=1, saturation=1
             color_rgb: [1.0, 1.0, 1.0, 0.0],    // R=1, G=1, B=1, padding
+            lut: [0.0, 1.0, 0.0, 0.0],          // disabled
         }
     }
 }
@@ -41,9 +47,16 @@ impl ScreenParams {
         Self {
             color_adjust: [color.brightness, color.contrast, color.gamma, color.saturation],
             color_rgb: [color.red, color.green, color.blue, 0.0],
+            lut: [0.0, 1.0, 0.0, 0.0],
         }
     }
 
+    /// Set the LUT parameters (`loaded` = a LUT texture is bound)
+    pub fn with_lut(mut self, lut: Option<&OutputLut>, loaded: bool) -> Self {
+        self.lut = lut_params(lut, loaded);
+        self
+    }
+
     /// Check if color correction is at identity (no correction needed)
     pub fn is_identity(&self) -> bool {
         (self.color_adjust[0] - 0.0).abs() < f32::EPSILON  // brightness
@@ -53,6 +66,7 @@ impl ScreenParams {
             && (self.color_rgb[0] - 1.0).abs() < f32::EPSILON     // red
             && (self.color_rgb[1] - 1.0).abs() < f32::EPSILON     // green
             && (self.color_rgb[2] - 1.0).abs() < f32::EPSILON     // blue
+            && self.lut[0] < 0.5                                  // no LUT
     }
 }
 
@@ -60,7 +74,7 @@ impl ScreenParams {
 ///
 /// IMPORTANT: This struct must match the WGSL SliceParams layout exactly.
 /// WGSL alignment rules: vec2 needs 8-byte alignment, vec4 needs 16-byte alignment.
-/// Total size: 240 bytes
+/// Total size: 320 bytes
 #[repr(C)]
 #[derive(Debug, Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
 pub struct SliceParams {
@@ -93,8 +107,10 @@ pub struct SliceParams {
     pub perspective_bl: [f32; 2],    // offset 120, size 8
     /// Perspective enabled flag (1.0 = enabled, 0.0 = disabled)
     pub perspective_enabled: f32,    // offset 128, size 4
+    /// Mesh interpolation (1.0 = Catmull-Rom spline, 0.0 = bilinear)
+    pub mesh_interpolation: f32,     // offset 132, size 4
     /// Padding for alignment
-    pub _pad2: [f32; 3],             // offset 132, size 12
+    pub _pad2: [f32; 2],             // offset 136, size 8
     // --- Mesh warp fields (offset 144) ---
     /// Mesh grid columns
     pub mesh_columns: u32,           // offset 144, size 4
@@ -102,16 +118,16 @@ pub struct SliceParams {
     pub mesh_rows: u32,              // offset 148, size 4
     /// Mesh warp enabled flag (1.0 = enabled, 0.0 = disabled)
     pub mesh_enabled: f32,           // offset 152, size 4
-    /// Padding for alignment
-    pub _pad3: f32,                  // offset 156, size 4
+    /// UV map warp enabled flag (1.0 = lookup texture bound, overrides mesh/perspective)
+    pub uv_map_enabled: f32,         // offset 156, size 4
     // --- Edge blend fields (offset 160) ---
-    /// Edge blend left: [enabled, width, gamma, black_level]
+    /// Edge blend left: [curve (0 = disabled), width, center exponent, black_level]
     pub edge_left: [f32; 4],         // offset 160, size 16
-    /// Edge blend right: [enabled, width, gamma, black_level]
+    /// Edge blend right: [curve (0 = disabled), width, center exponent, black_level]
     pub edge_right: [f32; 4],        // offset 176, size 16
-    /// Edge blend top: [enabled, width, gamma, black_level]
+    /// Edge blend top: [curve (0 = disabled), width, center exponent, black_level]
     pub edge_top: [f32; 4],          // offset 192, size 16
-    /// Edge blend bottom: [enabled, width, gamma, black_level]
+    /// Edge blend bottom: [curve (0 = disabled), width, center exponent, black_level]
     pub edge_bottom: [f32; 4],       // offset 208, size 16
     // --- Mask fields (offset 224) ---
     /// Mask enabled flag (1.0 = enabled, 0.0 = disabled)
@@ -120,9 +136,21 @@ pub struct SliceParams {
     pub mask_inverted: f32,          // offset 228, size 4
     /// Mask feather amount (0.0-0.5)
     pub mask_feather: f32,           // offset 232, size 4
-    /// Padding for alignment
-    pub _pad4: f32,                  // offset 236, size 4
-}                                    // Total: 240 bytes
+    /// Attenuation enabled flag (1.0 = attenuation texture bound)
+    pub attenuation_enabled: f32,    // offset 236, size 4
+    // --- Edge blend channel fields (offset 240) ---
+    /// Edge blend left: [red exponent, green exponent, blue exponent, uplift]
+    pub edge_left_channels: [f32; 4],   // offset 240, size 16
+    /// Edge blend right: [red exponent, green exponent, blue exponent, uplift]
+    pub edge_right_channels: [f32; 4],  // offset 256, size 16
+    /// Edge blend top: [red exponent, green exponent, blue exponent, uplift]
+    pub edge_top_channels: [f32; 4],    // offset 272, size 16
+    /// Edge blend bottom: [red exponent, green exponent, blue exponent, uplift]
+    pub edge_bottom_channels: [f32; 4], // offset 288, size 16
+    // --- LUT fields (offset 304) ---
+    /// 3D LUT: enabled, intensity, tetrahedral, padding
+    pub lut: [f32; 4],                  // offset 304, size 16
+}                                       // Total: 320 bytes
 
 impl Default for SliceParams {
     fn default() -> Self {
@@ -142,22 +170,28 @@ impl Default for SliceParams {
             perspective_br: [1.0, 1.0],
             perspective_bl: [0.0, 1.0],
             perspective_enabled: 0.0,
-            _pad2: [0.0; 3],
+            mesh_interpolation: 0.0,
+            _pad2: [0.0; 2],
             // Mesh warp defaults
             mesh_columns: 0,
             mesh_rows: 0,
             mesh_enabled: 0.0,
-            _pad3: 0.0,
+            uv_map_enabled: 0.0,
             // Edge blend defaults (all disabled)
-            edge_left: [0.0, 0.15, 2.2, 0.0],   // enabled, width, gamma, black_level
-            edge_right: [0.0, 0.15, 2.2, 0.0],
-            edge_top: [0.0, 0.15, 2.2, 0.0],
-            edge_bottom: [0.0, 0.15, 2.2, 0.0],
+            edge_left: [0.0, 0.15, 1.0, 0.0],   // curve, width, center exponent, black_level
+            edge_right: [0.0, 0.15, 1.0, 0.0],
+            edge_top: [0.0, 0.15, 1.0, 0.0],
+            edge_bottom: [0.0, 0.15, 1.0, 0.0],
             // Mask defaults (disabled)
             mask_enabled: 0.0,
             mask_inverted: 0.0,
             mask_feather: 0.0,
-            _pad4: 0.0,
+            attenuation_enabled: 0.0,
+            edge_left_channels: [2.2, 2.2, 2.2, 0.0], // R, G, B exponents, uplift
+            edge_right_channels: [2.2, 2.2, 2.2, 0.0],
+            edge_top_channels: [2.2, 2.2, 2.2, 0.0],
+            edge_bottom_channels: [2.2, 2.2, 2.2, 0.0],
+            lut: [0.0, 1.0, 0.0, 0.0], // enabled, intensity, tetrahedral
         }
     }
 }
@@ -184,38 +218,20 @@ impl SliceParams {
             };
 
         // Extract mesh warp data
-        let (mesh_columns, mesh_rows, mesh_enabled) = if let Some(mesh) = &slice.output.mesh {
-            (mesh.columns as u32, mesh.rows as u32, 1.0)
-        } else {
-            (0, 0, 0.0)
-        };
+        let (mesh_columns, mesh_rows, mesh_enabled, mesh_interpolation) =
+            if let Some(mesh) = &slice.output.mesh {
+                let smooth = mesh.interpolation == WarpInterpolation::CatmullRom;
+                (mesh.columns as u32, mesh.rows as u32, 1.0, if smooth { 1.0 } else { 0.0 })
+            } else {
+                (0, 0, 0.0, 0.0)
+            };
 
         // Extract edge blend config
         let edge = &slice.output.edge_blend;
-        let edge_left = [
-            if edge.left.enabled { 1.0 } else { 0.0 },
-            edge.left.width,
-            edge.left.gamma,
-            edge.left.black_level,
-        ];
-        let edge_right = [
-            if edge.right.enabled { 1.0 } else { 0.0 },
-            edge.right.width,
-            edge.right.gamma,
-            edge.right.black_level,
-        ];
-        let edge_top = [
-            if edge.top.enabled { 1.0 } else { 0.0 },
-            edge.top.width,
-            edge.top.gamma,
-            edge.top.black_level,
-        ];
-        let edge_bottom = [
-            if edge.bottom.enabled { 1.0 } else { 0.0 },
-            edge.bottom.width,
-            edge.bottom.gamma,
-            edge.bottom.black_level,
-        ];
+        let (edge_left, edge_left_channels) = edge_blend_params(&edge.left);
+        let (edge_right, edge_right_channels) = edge_blend_params(&edge.right);
+        let (edge_top, edge_top_channels) = edge_blend_params(&edge.top);
+        let (edge_bottom, edge_bottom_channels) = edge_blend_params(&edge.bottom);
 
         Self {
             input_rect: [
@@ -247,11 +263,13 @@ impl SliceParams {
             perspective_br,
             perspective_bl,
             perspective_enabled,
-            _pad2: [0.0; 3],
+            mesh_interpolation,
+            _pad2: [0.0; 2],
             mesh_columns,
             mesh_rows,
             mesh_enabled,
-            _pad3: 0.0,
+            // UV map flags are set by the runtime once the textures are loaded
+            uv_map_enabled: 0.0,
             edge_left,
             edge_right,
             edge_top,
@@ -260,11 +278,40 @@ impl SliceParams {
             mask_enabled: if slice.mask.as_ref().is_some_and(|m| m.enabled) { 1.0 } else { 0.0 },
             mask_inverted: if slice.mask.as_ref().is_some_and(|m| m.inverted) { 1.0 } else { 0.0 },
             mask_feather: slice.mask.as_ref().map(|m| m.feather).unwrap_or(0.0),
-            _pad4: 0.0,
+            attenuation_enabled: 0.0,
+            edge_left_channels,
+            edge_right_channels,
+            edge_top_channels,
+            edge_bottom_channels,
+            // LUT enabled flag is set by the runtime once the texture is loaded
+            lut: lut_params(slice.lut.as_ref(), false),
         }
     }
 }
 
+/// Pack one edge blend region into its two shader vec4s
+fn edge_blend_params(region: &EdgeBlendRegion) -> ([f32; 4], [f32; 4]) {
+    let curve = if region.enabled { region.curve.shader_id() } else { 0.0 };
+    let [r, g, b] = region.channel_exponents();
+    (
+        [curve, region.width, region.center_exponent(), region.black_level],
+        [r, g, b, region.uplift],
+    )
+}
+
+/// Pack a LUT configuration into its shader vec4
+fn lut_params(lut: Option<&OutputLut>, loaded: bool) -> [f32; 4] {
+    match lut {
+        Some(lut) => [
+            if loaded && lut.enabled { 1.0 } else { 0.0 },
+            lut.intensity,
+            if lut.interpolation == LutInterpolation::Tetrahedral { 1.0 } else { 0.0 },
+            0.0,
+        ],
+        None => [0.0, 1.0, 0.0, 0.0],
+    }
+}
+
 /// GPU data layout for a single warp point in the storage buffer
 /// Each point stores: [uv.x, uv.y, position.x, position.y]
 #[repr(C)]
@@ -311,6 +358,39 @@ pub struct SliceRuntime {
     /// Flag to track if mask needs re-rasterization
     pub mask_dirty: bool,
 
+    /// UV lookup texture (optional, Rg32Float)
+    pub uv_map_texture: Option<wgpu::Texture>,
+
+    /// UV lookup texture view for binding
+    pub uv_map_texture_view: Option<wgpu::TextureView>,
+
+    /// Attenuation texture (optional, Rgba16Float)
+    pub attenuation_texture: Option<wgpu::Texture>,
+
+    /// Attenuation texture view for binding
+    pub attenuation_texture_view: Option<wgpu::TextureView>,
+
+    /// Black level texture (optional, Rgba16Float)
+    pub black_level_texture: Option<wgpu::Texture>,
+
+    /// Black level texture view for binding
+    pub black_level_texture_view: Option<wgpu::TextureView>,
+
+    /// Bind group for UV map data (optional)
+    pub uv_map_bind_group: Option<wgpu::BindGroup>,
+
+    /// UV map configuration the textures were loaded from (avoids reloading files every frame)
+    uv_map_source: Option<UvWarpMap>,
+
+    /// 3D LUT texture (optional, Rgba16Float)
+    pub lut_texture: Option<wgpu::Texture>,
+
+    /// 3D LUT texture view for binding
+    pub lut_texture_view: Option<wgpu::TextureView>,
+
+    /// LUT configuration the texture was loaded from
+    lut_source: Option<OutputLut>,
+
     /// Cached slice dimensions
     pub width: u32,
     pub height: u32,
@@ -363,6 +443,17 @@ impl SliceRuntime {
             mask_texture_view: None,
             mask_bind_group: None,
             mask_dirty: false,
+            uv_map_texture: None,
+            uv_map_texture_view: None,
+            attenuation_texture: None,
+            attenuation_texture_view: None,
+            black_level_texture: None,
+            black_level_texture_view: None,
+            uv_map_bind_group: None,
+            uv_map_source: None,
+            lut_texture: None,
+            lut_texture_view: None,
+            lut_source: None,
             width,
             height,
         }
@@ -370,7 +461,10 @@ impl SliceRuntime {
 
     /// Update the params buffer with new slice configuration
     pub fn update_params(&self, queue: &wgpu::Queue, slice: &Slice) {
-        let params = SliceParams::from_slice(slice);
+        let mut params = SliceParams::from_slice(slice);
+        params.uv_map_enabled = if self.uv_map_texture.is_some() { 1.0 } else { 0.0 };
+        params.attenuation_enabled = if self.attenuation_texture.is_some() { 1.0 } else { 0.0 };
+        params.lut = lut_params(slice.lut.as_ref(), self.lut_texture.is_some());
         queue.write_buffer(&self.params_buffer, 0, bytemuck::bytes_of(&params));
     }
 
@@ -511,6 +605,204 @@ impl SliceRuntime {
         }
     }
 
+    /// Check if a UV map warp is active (has a lookup texture)
+    pub fn has_uv_map(&self) -> bool {
+        self.uv_map_texture.is_some()
+    }
+
+    /// Update the UV lookup, attenuation and black level textures
+    ///
+    /// Files are only (re)loaded when the configuration changes. Load failures
+    /// are logged and leave the slice on its perspective/mesh warp. An empty
+    /// lookup path applies only the attenuation and black level images.
+    pub fn update_uv_map(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        uv_map: Option<&UvWarpMap>,
+    ) {
+        let wanted = uv_map.filter(|m| m.enabled);
+        if wanted == self.uv_map_source.as_ref() {
+            return;
+        }
+        self.uv_map_source = wanted.cloned();
+        self.uv_map_texture = None;
+        self.uv_map_texture_view = None;
+        self.attenuation_texture = None;
+        self.attenuation_texture_view = None;
+        self.black_level_texture = None;
+        self.black_level_texture_view = None;
+        self.uv_map_bind_group = None;
+
+        let Some(config) = wanted else {
+            return;
+        };
+        let max_dimension = device.limits().max_texture_dimension_2d;
+
+        let lookup = (!config.path.is_empty())
+            .then(|| load_uv_map(std::path::Path::new(&config.path), config.invert_v));
+        match lookup {
+            None => {}
+            Some(Ok(image)) if image.width > max_dimension || image.height > max_dimension => {
+                tracing::warn!(
+                    "Slice {}: UV map '{}' is {}x{}, larger than the GPU limit of {}",
+                    self.slice_id.0,
+                    config.path,
+                    image.width,
+                    image.height,
+                    max_dimension
+                );
+            }
+            Some(Ok(image)) => {
+                // Unmapped texels get a sentinel the shader can test without relying on NaN
+                let texels: Vec<[f32; 2]> = image
+                    .uv
+                    .iter()
+                    .map(|uv| if uv[0].is_finite() && uv[1].is_finite() { *uv } else { [-1.0e9, -1.0e9] })
+                    .collect();
+                let texture = self.create_lookup_texture(
+                    device,
+                    queue,
+                    "UV Map",
+                    (image.width, image.height),
+                    wgpu::TextureFormat::Rg32Float,
+                    bytemuck::cast_slice(&texels),
+                    8,
+                );
+                self.uv_map_texture_view = Some(texture.create_view(&wgpu::TextureViewDescriptor::default()));
+                self.uv_map_texture = Some(texture);
+            }
+            Some(Err(e)) => {
+                tracing::warn!("Slice {}: failed to load UV map '{}': {}", self.slice_id.0, config.path, e);
+            }
+        }
+
+        if let Some(path) = &config.attenuation_path {
+            if let Some(texture) = self.load_gain_texture(device, queue, "Attenuation", path, max_dimension) {
+                self.attenuation_texture_view = Some(texture.create_view(&wgpu::TextureViewDescriptor::default()));
+                self.attenuation_texture = Some(texture);
+            }
+        }
+        if let Some(path) = &config.black_level_path {
+            if let Some(texture) = self.load_gain_texture(device, queue, "Black Level", path, max_dimension) {
+                self.black_level_texture_view = Some(texture.create_view(&wgpu::TextureViewDescriptor::default()));
+                self.black_level_texture = Some(texture);
+            }
+        }
+    }
+
+    /// Check if a 3D LUT is loaded
+    pub fn has_lut(&self) -> bool {
+        self.lut_texture.is_some()
+    }
+
+    /// Update the 3D LUT texture
+    ///
+    /// The table is only (re)loaded when its source changes; intensity and
+    /// interpolation are uniforms. Load failures are logged and disable the LUT.
+    pub fn update_lut(&mut self, device: &wgpu::Device, queue: &wgpu::Queue, lut: Option<&OutputLut>) {
+        let wanted = lut.filter(|l| l.enabled);
+        if same_lut_source(wanted, self.lut_source.as_ref()) {
+            return;
+        }
+        self.lut_source = wanted.cloned();
+        self.uv_map_bind_group = None;
+        let owner = format!("Slice {}", self.slice_id.0);
+        match wanted.and_then(|lut| load_lut_texture(device, queue, &owner, lut)) {
+            Some((texture, view)) => {
+                self.lut_texture = Some(texture);
+                self.lut_texture_view = Some(view);
+            }
+            None => {
+                self.lut_texture = None;
+                self.lut_texture_view = None;
+            }
+        }
+    }
+
+    /// Load an attenuation-style RGB image into an Rgba16Float texture, logging failures
+    fn load_gain_texture(
+        &self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        name: &str,
+        path: &str,
+        max_dimension: u32,
+    ) -> Option<wgpu::Texture> {
+        match load_attenuation(std::path::Path::new(path)) {
+            Ok(image) if image.width > max_dimension || image.height > max_dimension => {
+                tracing::warn!(
+                    "Slice {}: {} '{}' is {}x{}, larger than the GPU limit of {}",
+                    self.slice_id.0,
+                    name,
+                    path,
+                    image.width,
+                    image.height,
+                    max_dimension
+                );
+                None
+            }
+            Ok(image) => Some(self.create_lookup_texture(
+                device,
+                queue,
+                name,
+                (image.width, image.height),
+                wgpu::TextureFormat::Rgba16Float,
+                &image.to_f16_bytes(),
+                8,
+            )),
+            Err(e) => {
+                tracing::warn!("Slice {}: failed to load {} '{}': {}", self.slice_id.0, name, path, e);
+                None
+            }
+        }
+    }
+
+    /// Create and fill a sampled texture for UV map data
+    #[allow(clippy::too_many_arguments)]
+    fn create_lookup_texture(
+        &self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        name: &str,
+        (width, height): (u32, u32),
+        format: wgpu::TextureFormat,
+        data: &[u8],
+        bytes_per_texel: u32,
+    ) -> wgpu::Texture {
+        let size = wgpu::Extent3d {
+            width,
+            height,
+            depth_or_array_layers: 1,
+        };
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some(&format!("Slice {} {} Texture", self.slice_id.0, name)),
+            size,
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+            view_formats: &[],
+        });
+        queue.write_texture(
+            wgpu::ImageCopyTexture {
+                texture: &texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d::ZERO,
+                aspect: wgpu::TextureAspect::All,
+            },
+            data,
+            wgpu::ImageDataLayout {
+                offset: 0,
+                bytes_per_row: Some(width * bytes_per_texel),
+                rows_per_image: Some(height),
+            },
+            size,
+        );
+        texture
+    }
+
     /// Resize the slice texture if needed
     pub fn resize(&mut self, device: &wgpu::Device, width: u32, height: u32, format: wgpu::TextureFormat) {
         if self.width == width && self.height == height {
@@ -541,6 +833,78 @@ impl SliceRuntime {
     }
 }
 
+/// Whether two LUT configurations load the same table
+fn same_lut_source(a: Option<&OutputLut>, b: Option<&OutputLut>) -> bool {
+    match (a, b) {
+        (Some(a), Some(b)) => a.path == b.path && a.embedded == b.embedded,
+        (None, None) => true,
+        _ => false,
+    }
+}
+
+/// Load a LUT into a 3D texture, logging failures
+fn load_lut_texture(
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    owner: &str,
+    lut: &OutputLut,
+) -> Option<(wgpu::Texture, wgpu::TextureView)> {
+    let table = match lut.load() {
+        Ok(table) => table,
+        Err(e) => {
+            tracing::warn!("{}: failed to load LUT '{}': {}", owner, lut.display_name(), e);
+            return None;
+        }
+    };
+    let max_dimension = device.limits().max_texture_dimension_3d;
+    if table.size > max_dimension {
+        tracing::warn!(
+            "{}: LUT '{}' has size {}, larger than the GPU limit of {}",
+            owner,
+            lut.display_name(),
+            table.size,
+            max_dimension
+        );
+        return None;
+    }
+    let texture = create_lut_texture(device, &format!("{} LUT Texture", owner), table.size);
+    queue.write_texture(
+        wgpu::TexelCopyTextureInfo {
+            texture: &texture,
+            mip_level: 0,
+            origin: wgpu::Origin3d::ZERO,
+            aspect: wgpu::TextureAspect::All,
+        },
+        &table.to_rgba_f16_bytes(),
+        wgpu::TexelCopyBufferLayout {
+            offset: 0,
+            bytes_per_row: Some(table.size * 8),
+            rows_per_image: Some(table.size),
+        },
+        texture.size(),
+    );
+    let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+    Some((texture, view))
+}
+
+/// Create an Rgba16Float 3D texture for a LUT (x = red, y = green, z = blue)
+fn create_lut_texture(device: &wgpu::Device, label: &str, size: u32) -> wgpu::Texture {
+    device.create_texture(&wgpu::TextureDescriptor {
+        label: Some(label),
+        size: wgpu::Extent3d {
+            width: size,
+            height: size,
+            depth_or_array_layers: size,
+        },
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D3,
+        format: wgpu::TextureFormat::Rgba16Float,
+        usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+        view_formats: &[],
+    })
+}
+
 /// Rasterize a mask shape to an RGBA pixel buffer
 ///
 /// Returns a Vec<u8> with size * size * 4 bytes (RGBA format).
@@ -954,6 +1318,22 @@ impl FrameDelayBuffer {
 // SCREEN RUNTIME — GPU resources for a screen
 // ═══════════════════════════════════════════════════════════════════════════════
 
+/// Expand a grayscale calibration pattern to an 8-bit RGBA/BGRA output format.
+///
+/// Gray pixels read the same in either channel order, and sRGB formats keep
+/// the raw byte so the projector receives exactly the pattern level.
+/// Returns `None` for formats that are not 8 bits per channel.
+fn calibration_pattern_pixels(pattern: &image::GrayImage, format: wgpu::TextureFormat) -> Option<Vec<u8>> {
+    match format {
+        wgpu::TextureFormat::Rgba8Unorm
+        | wgpu::TextureFormat::Rgba8UnormSrgb
+        | wgpu::TextureFormat::Bgra8Unorm
+        | wgpu::TextureFormat::Bgra8UnormSrgb => {}
+        _ => return None,
+    }
+    Some(pattern.as_raw().iter().flat_map(|&v| [v, v, v, 255]).collect())
+}
+
 /// Runtime GPU resources for a screen
 pub struct ScreenRuntime {
     /// The screen ID this runtime belongs to
@@ -974,6 +1354,18 @@ pub struct ScreenRuntime {
     /// Bind group for color correction (samples from output_texture)
     pub color_bind_group: Option<wgpu::BindGroup>,
 
+    /// Uniform buffer for color correction and LUT parameters
+    pub params_buffer: wgpu::Buffer,
+
+    /// 3D LUT texture (optional, Rgba16Float)
+    pub lut_texture: Option<wgpu::Texture>,
+
+    /// 3D LUT texture view for binding
+    pub lut_view: Option<wgpu::TextureView>,
+
+    /// LUT configuration the texture was loaded from
+    lut_source: Option<OutputLut>,
+
     /// Frame delay buffer for projector sync timing
     pub delay_buffer: FrameDelayBuffer,
 
@@ -992,6 +1384,9 @@ pub struct ScreenRuntime {
 
     /// OMT capture for screens with OMT output device
     pub omt_capture: Option<crate::network::OmtCapture>,
+
+    /// Calibration pattern shown instead of the slices (screen resolution, output format)
+    calibration_pattern: Option<wgpu::Texture>,
 }
 
 impl ScreenRuntime {
@@ -1046,6 +1441,14 @@ impl ScreenRuntime {
         // Create delay buffer (initially empty - will be allocated when delay_ms > 0)
         let delay_buffer = FrameDelayBuffer::new(width, height, format);
 
+        // Per-screen so each screen's write survives until the shared encoder is submitted
+        let params_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some(&format!("Screen {} Params", screen_id.0)),
+            size: std::mem::size_of::<ScreenParams>() as u64,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
         Self {
             screen_id,
             output_texture,
@@ -1053,6 +1456,10 @@ impl ScreenRuntime {
             color_temp_texture,
             color_temp_view,
             color_bind_group: None,
+            params_buffer,
+            lut_texture: None,
+            lut_view: None,
+            lut_source: None,
             delay_buffer,
             slices: HashMap::new(),
             width,
@@ -1060,6 +1467,7 @@ impl ScreenRuntime {
             format,
             ndi_capture: None,
             omt_capture: None,
+            calibration_pattern: None,
         }
     }
 
@@ -1115,10 +1523,110 @@ impl ScreenRuntime {
         // Resize delay buffer if active
         self.delay_buffer.resize(device, width, height);
 
+        // A pattern only fits the resolution it was rendered for
+        if self.calibration_pattern.take().is_some() {
+            tracing::info!("Screen {}: calibration pattern cleared by resize", self.screen_id.0);
+        }
+
         self.width = width;
         self.height = height;
     }
 
+    /// Show a calibration pattern instead of the slices until it is cleared.
+    ///
+    /// The pattern must match the screen resolution. It is displayed as-is:
+    /// slices and color correction are bypassed, while the frame delay and
+    /// the output device still apply.
+    pub fn set_calibration_pattern(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        pattern: &image::GrayImage,
+    ) -> Result<(), String> {
+        if pattern.dimensions() != (self.width, self.height) {
+            return Err(format!(
+                "Pattern is {}x{} but screen {} is {}x{}",
+                pattern.width(),
+                pattern.height(),
+                self.screen_id.0,
+                self.width,
+                self.height
+            ));
+        }
+        let pixels = calibration_pattern_pixels(pattern, self.format)
+            .ok_or_else(|| format!("Calibration patterns are not supported for {:?} output", self.format))?;
+
+        let size = wgpu::Extent3d {
+            width: self.width.max(1),
+            height: self.height.max(1),
+            depth_or_array_layers: 1,
+        };
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some(&format!("Screen {} Calibration Pattern", self.screen_id.0)),
+            size,
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: self.format,
+            usage: wgpu::TextureUsages::COPY_SRC | wgpu::TextureUsages::COPY_DST,
+            view_formats: &[],
+        });
+        queue.write_texture(
+            wgpu::ImageCopyTexture {
+                texture: &texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d::ZERO,
+                aspect: wgpu::TextureAspect::All,
+            },
+            &pixels,
+            wgpu::ImageDataLayout {
+                offset: 0,
+                bytes_per_row: Some(self.width * 4),
+                rows_per_image: Some(self.height),
+            },
+            size,
+        );
+
+        self.calibration_pattern = Some(texture);
+        Ok(())
+    }
+
+    /// Return to rendering slices.
+    pub fn clear_calibration_pattern(&mut self) {
+        self.calibration_pattern = None;
+    }
+
+    /// Check if a calibration pattern is being shown
+    pub fn has_calibration_pattern(&self) -> bool {
+        self.calibration_pattern.is_some()
+    }
+
+    /// Check if a 3D LUT is loaded
+    pub fn has_lut(&self) -> bool {
+        self.lut_texture.is_some()
+    }
+
+    /// Update the 3D LUT texture (only reloads when the LUT source changes)
+    pub fn update_lut(&mut self, device: &wgpu::Device, queue: &wgpu::Queue, lut: Option<&OutputLut>) {
+        let wanted = lut.filter(|l| l.enabled);
+        if same_lut_source(wanted, self.lut_source.as_ref()) {
+            return;
+        }
+        self.lut_source = wanted.cloned();
+        self.color_bind_group = None;
+        let owner = format!("Screen {}", self.screen_id.0);
+        match wanted.and_then(|lut| load_lut_texture(device, queue, &owner, lut)) {
+            Some((texture, view)) => {
+                self.lut_texture = Some(texture);
+                self.lut_view = Some(view);
+            }
+            None => {
+                self.lut_texture = None;
+                self.lut_view = None;
+            }
+        }
+    }
+
     /// Ensure slice runtime exists for a slice
     pub fn ensure_slice(&mut self, device: &wgpu::Device, slice: &Slice) {
         if !self.slices.contains_key(&slice.id) {
@@ -1343,9 +1851,6 @@ pub struct OutputManager {
     /// Shared sampler for texture filtering
     sampler: Option<wgpu::Sampler>,
 
-    /// Uniform buffer for screen params
-    screen_params_buffer: Option<wgpu::Buffer>,
-
     /// Dummy warp buffer for slices without mesh warp
     dummy_warp_buffer: Option<wgpu::Buffer>,
 
@@ -1364,6 +1869,24 @@ pub struct OutputManager {
     /// Dummy mask bind group for slices without masking
     dummy_mask_bind_group: Option<wgpu::BindGroup>,
 
+    /// Bind group layout for UV map data (UV, attenuation, black level and LUT textures + sampler)
+    uv_map_bind_group_layout: Option<wgpu::BindGroupLayout>,
+
+    /// Dummy UV lookup texture view for slices without a UV map (1x1 Rg32Float)
+    dummy_uv_map_texture_view: Option<wgpu::TextureView>,
+
+    /// Dummy attenuation texture view for slices without attenuation (1x1, unused while disabled)
+    dummy_attenuation_texture_view: Option<wgpu::TextureView>,
+
+    /// Dummy black level texture view for slices without a black level (1x1 zero = no lift)
+    dummy_black_level_texture_view: Option<wgpu::TextureView>,
+
+    /// Dummy 3D LUT texture view for slices and screens without a LUT (1x1x1, unused while disabled)
+    dummy_lut_texture_view: Option<wgpu::TextureView>,
+
+    /// Dummy UV map bind group for slices without a UV map
+    dummy_uv_map_bind_group: Option<wgpu::BindGroup>,
+
     // =========================================
     // Blit Pipeline (for presenting to surfaces)
     // =========================================
@@ -1391,13 +1914,18 @@ impl OutputManager {
             warp_bind_group_layout: None,
             screen_bind_group_layout: None,
             sampler: None,
-            screen_params_buffer: None,
             dummy_warp_buffer: None,
             dummy_warp_bind_group: None,
             mask_bind_group_layout: None,
             dummy_mask_texture: None,
             dummy_mask_texture_view: None,
             dummy_mask_bind_group: None,
+            uv_map_bind_group_layout: None,
+            dummy_uv_map_texture_view: None,
+            dummy_attenuation_texture_view: None,
+            dummy_black_level_texture_view: None,
+            dummy_lut_texture_view: None,
+            dummy_uv_map_bind_group: None,
             blit_pipeline: None,
             blit_bind_group_layout: None,
         }
@@ -1466,15 +1994,6 @@ impl OutputManager {
         });
         self.sampler = Some(sampler);
 
-        // Create screen params buffer
-        let screen_params_buffer = device.create_buffer(&wgpu::BufferDescriptor {
-            label: Some("Screen Params Buffer"),
-            size: std::mem::size_of::<ScreenParams>() as u64,
-            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
-            mapped_at_creation: false,
-        });
-        self.screen_params_buffer = Some(screen_params_buffer);
-
         // Create slice bind group layout
         let slice_bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
             label: Some("Slice Bind Group Layout"),
@@ -1614,6 +2133,129 @@ impl OutputManager {
         self.dummy_mask_texture_view = Some(dummy_mask_texture_view);
         self.dummy_mask_bind_group = Some(dummy_mask_bind_group);
 
+        // Create UV map bind group layout (UV lookup + attenuation + black level + sampler + LUT)
+        let uv_map_bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("UV Map Bind Group Layout"),
+            entries: &[
+                // UV lookup texture (Rg32Float is not filterable; the shader interpolates manually)
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Float { filterable: false },
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                // Attenuation texture
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                // Attenuation / black level sampler
+                wgpu::BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                    count: None,
+                },
+                // Black level texture
+                wgpu::BindGroupLayoutEntry {
+                    binding: 3,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                // 3D LUT texture (read with textureLoad, interpolated in the shader)
+                wgpu::BindGroupLayoutEntry {
+                    binding: 4,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Float { filterable: false },
+                        view_dimension: wgpu::TextureViewDimension::D3,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+            ],
+        });
+
+        // Create dummy UV map textures (lookup/attenuation are never sampled while their
+        // flags are 0; the zero-initialized black level adds no lift)
+        let dummy_texture = |label: &str, format: wgpu::TextureFormat| {
+            device.create_texture(&wgpu::TextureDescriptor {
+                label: Some(label),
+                size: wgpu::Extent3d {
+                    width: 1,
+                    height: 1,
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format,
+                usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+                view_formats: &[],
+            })
+        };
+        let dummy_uv_map_texture_view = dummy_texture("Dummy UV Map Texture", wgpu::TextureFormat::Rg32Float)
+            .create_view(&wgpu::TextureViewDescriptor::default());
+        let dummy_attenuation_texture_view =
+            dummy_texture("Dummy Attenuation Texture", wgpu::TextureFormat::Rgba16Float)
+                .create_view(&wgpu::TextureViewDescriptor::default());
+        let dummy_black_level_texture_view =
+            dummy_texture("Dummy Black Level Texture", wgpu::TextureFormat::Rgba16Float)
+                .create_view(&wgpu::TextureViewDescriptor::default());
+        // Never read while the LUT flag is 0 (shared by slices and screens)
+        let dummy_lut_texture_view = create_lut_texture(device, "Dummy LUT Texture", 1)
+            .create_view(&wgpu::TextureViewDescriptor::default());
+
+        // Create dummy UV map bind group
+        let dummy_uv_map_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Dummy UV Map Bind Group"),
+            layout: &uv_map_bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::TextureView(&dummy_uv_map_texture_view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::TextureView(&dummy_attenuation_texture_view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: wgpu::BindingResource::Sampler(self.sampler.as_ref().unwrap()),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 3,
+                    resource: wgpu::BindingResource::TextureView(&dummy_black_level_texture_view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 4,
+                    resource: wgpu::BindingResource::TextureView(&dummy_lut_texture_view),
+                },
+            ],
+        });
+
+        self.uv_map_bind_group_layout = Some(uv_map_bind_group_layout);
+        self.dummy_uv_map_texture_view = Some(dummy_uv_map_texture_view);
+        self.dummy_attenuation_texture_view = Some(dummy_attenuation_texture_view);
+        self.dummy_black_level_texture_view = Some(dummy_black_level_texture_view);
+        self.dummy_lut_texture_view = Some(dummy_lut_texture_view);
+        self.dummy_uv_map_bind_group = Some(dummy_uv_map_bind_group);
+
         // Create screen bind group layout
         let screen_bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
             label: Some("Screen Bind Group Layout"),
@@ -1647,6 +2289,17 @@ impl OutputManager {
                     },
                     count: None,
                 },
+                // 3D LUT texture
+                wgpu::BindGroupLayoutEntry {
+                    binding: 3,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Float { filterable: false },
+                        view_dimension: wgpu::TextureViewDimension::D3,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
             ],
         });
 
@@ -1663,6 +2316,7 @@ impl OutputManager {
                 &slice_bind_group_layout,
                 self.warp_bind_group_layout.as_ref().unwrap(),
                 self.mask_bind_group_layout.as_ref().unwrap(),
+                self.uv_map_bind_group_layout.as_ref().unwrap(),
             ],
             push_constant_ranges: &[],
         });
@@ -1935,6 +2589,28 @@ impl OutputManager {
         self.runtimes.get_mut(&screen_id)
     }
 
+    /// Show a calibration pattern on a screen, replacing its slices
+    pub fn set_calibration_pattern(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        screen_id: ScreenId,
+        pattern: &image::GrayImage,
+    ) -> Result<(), String> {
+        let runtime = self
+            .runtimes
+            .get_mut(&screen_id)
+            .ok_or_else(|| format!("Screen {} not found", screen_id.0))?;
+        runtime.set_calibration_pattern(device, queue, pattern)
+    }
+
+    /// Stop showing a calibration pattern on a screen
+    pub fn clear_calibration_pattern(&mut self, screen_id: ScreenId) {
+        if let Some(runtime) = self.runtimes.get_mut(&screen_id) {
+            runtime.clear_calibration_pattern();
+        }
+    }
+
     /// Get all screens
     pub fn screens(&self) -> impl Iterator<Item = &Screen> {
         self.screens.values()
@@ -2319,6 +2995,25 @@ impl OutputManager {
         let Some(dummy_mask_bind_group) = &self.dummy_mask_bind_group else {
             return;
         };
+        let Some(uv_map_bind_group_layout) = &self.uv_map_bind_group_layout else {
+            return;
+        };
+        let (
+            Some(dummy_uv_map_view),
+            Some(dummy_attenuation_view),
+            Some(dummy_black_level_view),
+            Some(dummy_lut_view),
+        ) = (
+            &self.dummy_uv_map_texture_view,
+            &self.dummy_attenuation_texture_view,
+            &self.dummy_black_level_texture_view,
+            &self.dummy_lut_texture_view,
+        ) else {
+            return;
+        };
+        let Some(dummy_uv_map_bind_group) = &self.dummy_uv_map_bind_group else {
+            return;
+        };
 
         // Get screen config and runtime
         let Some(screen) = self.screens.get(&screen_id) else {
@@ -2332,6 +3027,30 @@ impl OutputManager {
             return;
         }
 
+        // A calibration pattern replaces the slices until it is cleared
+        if let Some(pattern) = &runtime.calibration_pattern {
+            encoder.copy_texture_to_texture(
+                wgpu::ImageCopyTexture {
+                    texture: pattern,
+                    mip_level: 0,
+                    origin: wgpu::Origin3d::ZERO,
+                    aspect: wgpu::TextureAspect::All,
+                },
+                wgpu::ImageCopyTexture {
+                    texture: &runtime.output_texture,
+                    mip_level: 0,
+                    origin: wgpu::Origin3d::ZERO,
+                    aspect: wgpu::TextureAspect::All,
+                },
+                wgpu::Extent3d {
+                    width: runtime.width.max(1),
+                    height: runtime.height.max(1),
+                    depth_or_array_layers: 1,
+                },
+            );
+            return;
+        }
+
         // Clone slices to avoid borrow issues
         let slices: Vec<Slice> = screen.slices.clone();
 
@@ -2375,15 +3094,21 @@ impl OutputManager {
                 continue;
             };
 
-            // Update slice params buffer
-            slice_runtime.update_params(queue, slice);
-
             // Update warp buffer if mesh warp is enabled
             slice_runtime.update_warp_buffer(device, queue, slice.output.mesh.as_ref());
 
             // Update mask texture if mask is enabled (before borrowing bind groups)
             slice_runtime.update_mask_texture(device, queue, slice.mask.as_ref());
 
+            // Load UV map textures when the configuration changed
+            slice_runtime.update_uv_map(device, queue, slice.output.uv_map.as_ref());
+
+            // Load the 3D LUT when its source changed
+            slice_runtime.update_lut(device, queue, slice.lut.as_ref());
+
+            // Update slice params buffer (after UV map and LUT so its flags reflect loaded textures)
+            slice_runtime.update_params(queue, slice);
+
             // Create warp bind group if needed (must be done before borrowing)
             if slice_runtime.warp_buffer.is_some() && slice_runtime.warp_bind_group.is_none() {
                 slice_runtime.warp_bind_group = Some(device.create_bind_group(&wgpu::BindGroupDescriptor {
@@ -2414,9 +3139,52 @@ impl OutputManager {
                 }));
             }
 
+            // Create UV map bind group if needed (missing textures use the dummies)
+            let has_uv_data = slice_runtime.uv_map_texture_view.is_some()
+                || slice_runtime.attenuation_texture_view.is_some()
+                || slice_runtime.black_level_texture_view.is_some()
+                || slice_runtime.lut_texture_view.is_some();
+            if has_uv_data && slice_runtime.uv_map_bind_group.is_none() {
+                slice_runtime.uv_map_bind_group = Some(device.create_bind_group(&wgpu::BindGroupDescriptor {
+                    label: Some(&format!("Slice {} UV Map Bind Group", slice.id.0)),
+                    layout: uv_map_bind_group_layout,
+                    entries: &[
+                        wgpu::BindGroupEntry {
+                            binding: 0,
+                            resource: wgpu::BindingResource::TextureView(
+                                slice_runtime.uv_map_texture_view.as_ref().unwrap_or(dummy_uv_map_view),
+                            ),
+                        },
+                        wgpu::BindGroupEntry {
+                            binding: 1,
+                            resource: wgpu::BindingResource::TextureView(
+                                slice_runtime.attenuation_texture_view.as_ref().unwrap_or(dummy_attenuation_view),
+                            ),
+                        },
+                        wgpu::BindGroupEntry {
+                            binding: 2,
+                            resource: wgpu::BindingResource::Sampler(sampler),
+                        },
+                        wgpu::BindGroupEntry {
+                            binding: 3,
+                            resource: wgpu::BindingResource::TextureView(
+                                slice_runtime.black_level_texture_view.as_ref().unwrap_or(dummy_black_level_view),
+                            ),
+                        },
+                        wgpu::BindGroupEntry {
+                            binding: 4,
+                            resource: wgpu::BindingResource::TextureView(
+                                slice_runtime.lut_texture_view.as_ref().unwrap_or(dummy_lut_view),
+                            ),
+                        },
+                    ],
+                }));
+            }
+
             // Get bind groups (immutable borrows after mutable work is done)
             let warp_bind_group = slice_runtime.warp_bind_group.as_ref().unwrap_or(dummy_warp_bind_group);
             let mask_bind_group = slice_runtime.mask_bind_group.as_ref().unwrap_or(dummy_mask_bind_group);
+            let uv_map_bind_group = slice_runtime.uv_map_bind_group.as_ref().unwrap_or(dummy_uv_map_bind_group);
 
             // Create bind group for this slice
             let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
@@ -2461,6 +3229,7 @@ impl OutputManager {
                 render_pass.set_bind_group(0, &bind_group, &[]);
                 render_pass.set_bind_group(1, warp_bind_group, &[]);
                 render_pass.set_bind_group(2, mask_bind_group, &[]);
+                render_pass.set_bind_group(3, uv_map_bind_group, &[]);
                 render_pass.draw(0..3, 0..1); // Fullscreen triangle
             }
         }
@@ -2476,13 +3245,13 @@ impl OutputManager {
         self.sampler.as_ref()
     }
 
-    /// Apply screen-level color correction
+    /// Apply screen-level color correction and 3D LUT
     ///
     /// This should be called after render_screen() to apply per-screen color correction.
     /// Uses a ping-pong approach: renders from output_texture to color_temp_texture with
     /// color correction, then copies back to output_texture.
     ///
-    /// If the screen's color correction is at identity, this is a no-op.
+    /// If the screen's color correction is at identity and no LUT is loaded, this is a no-op.
     pub fn apply_screen_color(
         &mut self,
         device: &wgpu::Device,
@@ -2490,13 +3259,25 @@ impl OutputManager {
         encoder: &mut wgpu::CommandEncoder,
         screen_id: ScreenId,
     ) {
-        // Get screen config
+        // Get screen config and runtime
         let Some(screen) = self.screens.get(&screen_id) else {
             return;
         };
+        let Some(runtime) = self.runtimes.get_mut(&screen_id) else {
+            return;
+        };
+
+        // Calibration patterns must reach the projector unaltered
+        if runtime.has_calibration_pattern() {
+            return;
+        }
 
-        // Skip if color correction is at identity
-        if screen.color.is_identity() {
+        // Load the 3D LUT when its source changed
+        runtime.update_lut(device, queue, screen.lut.as_ref());
+
+        // Skip if color correction is at identity and no LUT is active
+        let screen_params = ScreenParams::from_color(&screen.color).with_lut(screen.lut.as_ref(), runtime.has_lut());
+        if screen_params.is_identity() {
             return;
         }
 
@@ -2511,18 +3292,12 @@ impl OutputManager {
         let Some(sampler) = &self.sampler else {
             return;
         };
-        let Some(screen_params_buffer) = &self.screen_params_buffer else {
-            return;
-        };
-
-        // Get screen runtime
-        let Some(runtime) = self.runtimes.get_mut(&screen_id) else {
+        let Some(dummy_lut_view) = &self.dummy_lut_texture_view else {
             return;
         };
 
-        // Update screen params buffer with color correction values
-        let screen_params = ScreenParams::from_color(&screen.color);
-        queue.write_buffer(screen_params_buffer, 0, bytemuck::bytes_of(&screen_params));
+        // Update screen params buffer with color correction and LUT values
+        queue.write_buffer(&runtime.params_buffer, 0, bytemuck::bytes_of(&screen_params));
 
         // Create or get color bind group (samples from output_texture)
         if runtime.color_bind_group.is_none() {
@@ -2540,7 +3315,13 @@ impl OutputManager {
                     },
                     wgpu::BindGroupEntry {
                         binding: 2,
-                        resource: screen_params_buffer.as_entire_binding(),
+                        resource: runtime.params_buffer.as_entire_binding(),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 3,
+                        resource: wgpu::BindingResource::TextureView(
+                            runtime.lut_view.as_ref().unwrap_or(dummy_lut_view),
+                        ),
                     },
                 ],
             }));
@@ -2615,6 +3396,31 @@ mod tests {
         assert_eq!(params.opacity, 0.5);
     }
 
+    #[test]
+    fn test_lut_params() {
+        assert_eq!(std::mem::size_of::<SliceParams>(), 320);
+        assert_eq!(std::mem::size_of::<ScreenParams>(), 48);
+
+        let mut lut = OutputLut::new("grade.cube");
+        lut.interpolation = LutInterpolation::Tetrahedral;
+        lut.intensity = 0.5;
+
+        // Not applied until the texture is loaded
+        let params = ScreenParams::identity().with_lut(Some(&lut), false);
+        assert!(params.is_identity());
+        let params = ScreenParams::identity().with_lut(Some(&lut), true);
+        assert!(!params.is_identity());
+        assert_eq!(params.lut, [1.0, 0.5, 1.0, 0.0]);
+    }
+
+    #[test]
+    fn test_calibration_pattern_pixels() {
+        let pattern = image::GrayImage::from_raw(2, 1, vec![0, 200]).unwrap();
+        let pixels = calibration_pattern_pixels(&pattern, wgpu::TextureFormat::Bgra8UnormSrgb).unwrap();
+        assert_eq!(pixels, vec![0, 0, 0, 255, 200, 200, 200, 255]);
+        assert!(calibration_pattern_pixels(&pattern, wgpu::TextureFormat::Rgba16Float).is_none());
+    }
+
     #[test]
     fn test_frame_delay_buffer_new() {
         let buffer = FrameDelayBuffer::new(1920, 1080, wgpu::TextureFormat::Rgba8Unorm);
@@ -6,6 +6,7 @@
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 use super::color::OutputColorCorrection;
+use super::lut::OutputLut;
 use super::slice::Slice;
 
 /// Unique identifier for a screen
@@ -224,6 +225,10 @@ pub struct Screen {
     #[serde(rename = "colorCorrection", default)]
     pub color: OutputColorCorrection,
 
+    /// Per-screen 3D LUT (applied after color correction)
+    #[serde(rename = "lut", default, skip_serializing_if = "Option::is_none")]
+    pub lut: Option<OutputLut>,
+
     /// Output timing delay in milliseconds (for projector sync)
     #[serde(rename = "delayMs", default)]
     pub delay_ms: u32,
@@ -240,6 +245,7 @@ impl Default for Screen {
             slices: Vec::new(),
             enabled: true,
             color: OutputColorCorrection::default(),
+            lut: None,
             delay_ms: 0,
         }
     }
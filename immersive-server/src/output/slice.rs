@@ -7,7 +7,9 @@ use serde::{Deserialize, Serialize};
 
 use super::color::SliceColorCorrection;
 use super::edge_blend::EdgeBlendConfig;
+use super::lut::OutputLut;
 use super::mask::SliceMask;
+use super::uv_map::UvWarpMap;
 use super::warp::WarpMesh;
 
 /// Unique identifier for a slice
@@ -229,6 +231,10 @@ pub struct SliceOutput {
     #[serde(rename = "mesh", skip_serializing_if = "Option::is_none")]
     pub mesh: Option<WarpMesh>,
 
+    /// Dense per-pixel UV lookup (optional, overrides mesh and perspective)
+    #[serde(rename = "uvMap", default, skip_serializing_if = "Option::is_none")]
+    pub uv_map: Option<UvWarpMap>,
+
     /// Edge blending configuration
     #[serde(rename = "edgeBlend", default)]
     pub edge_blend: EdgeBlendConfig,
@@ -243,6 +249,7 @@ impl Default for SliceOutput {
             flip_v: false,
             perspective: None,
             mesh: None,
+            uv_map: None,
             edge_blend: EdgeBlendConfig::default(),
         }
     }
@@ -257,6 +264,7 @@ impl SliceOutput {
             || self.flip_v
             || self.perspective.is_some()
             || self.mesh.is_some()
+            || self.uv_map.as_ref().is_some_and(|m| m.enabled)
     }
 }
 
@@ -292,6 +300,10 @@ pub struct Slice {
     #[serde(rename = "colorCorrection", default)]
     pub color: SliceColorCorrection,
 
+    /// Per-slice 3D LUT (applied after color correction, before edge blending)
+    #[serde(rename = "lut", default, skip_serializing_if = "Option::is_none")]
+    pub lut: Option<OutputLut>,
+
     /// Whether this slice is enabled
     #[serde(rename = "enabled")]
     pub enabled: bool,
@@ -315,6 +327,7 @@ impl Default for Slice {
             output: SliceOutput::default(),
             mask: None,
             color: SliceColorCorrection::default(),
+            lut: None,
             enabled: true,
             is_key: false,
             black_bg: false,
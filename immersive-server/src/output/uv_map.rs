@@ -0,0 +1,355 @@
+//! Dense per-pixel UV warp maps for slices
+//!
+//! A UV map is a lookup texture that stores, for every output pixel, the input
+//! UV to sample. It represents warps a control-point mesh cannot, such as the
+//! per-pixel correspondences produced by camera-based calibration. An optional
+//! attenuation image multiplies the output color (blend or luminance masks),
+//! and an optional black level image lifts it (MPCDI beta maps).
+//!
+//! Supported sources:
+//! - Float images (EXR) and 16-bit PNG: R = u, G = v, alpha 0 = unmapped
+//! - projmap-calibrator binary maps (`.pmuv`)
+//! - MPCDI 2D geometry warp files (`.pfm`): x = u, y = v, NaN = unmapped
+//!
+//! Lookup texels are corner-aligned: the first and last texel of each row sit
+//! on the output's left and right edges, so coarse grids interpolate exactly.
+
+use std::path::Path;
+
+use projmap_schema::{SchemaError, UvMap};
+use serde::{Deserialize, Serialize};
+
+use super::mpcdi::{read_pfm, MpcdiError};
+
+/// UV map warp configuration for a slice
+///
+/// Only file references are stored; the lookup data is loaded by the output
+/// runtime when the slice is rendered. When enabled and loaded, the UV map
+/// replaces both the perspective and mesh warps.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UvWarpMap {
+    /// Whether the UV map is applied
+    #[serde(rename = "enabled")]
+    pub enabled: bool,
+
+    /// Path to the UV lookup image or `.pmuv` file
+    #[serde(rename = "path")]
+    pub path: String,
+
+    /// Optional attenuation image multiplied into the output color
+    #[serde(rename = "attenuationPath", default, skip_serializing_if = "Option::is_none")]
+    pub attenuation_path: Option<String>,
+
+    /// Optional black level image: output = color * (1 - level) + level
+    #[serde(rename = "blackLevelPath", default, skip_serializing_if = "Option::is_none")]
+    pub black_level_path: Option<String>,
+
+    /// The V channel increases upward (STMap convention) rather than downward
+    #[serde(rename = "invertV", default)]
+    pub invert_v: bool,
+}
+
+impl UvWarpMap {
+    /// Create an enabled UV map referencing a lookup file
+    pub fn new(path: impl Into<String>) -> Self {
+        Self {
+            enabled: true,
+            path: path.into(),
+            attenuation_path: None,
+            black_level_path: None,
+            invert_v: false,
+        }
+    }
+
+    /// File name of the lookup source, for display
+    pub fn file_name(&self) -> String {
+        file_name(&self.path)
+    }
+
+    /// File name of the attenuation source, for display
+    pub fn attenuation_file_name(&self) -> Option<String> {
+        self.attenuation_path.as_deref().map(file_name)
+    }
+
+    /// File name of the black level source, for display
+    pub fn black_level_file_name(&self) -> Option<String> {
+        self.black_level_path.as_deref().map(file_name)
+    }
+}
+
+fn file_name(path: &str) -> String {
+    Path::new(path)
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_else(|| path.to_string())
+}
+
+/// Decoded UV lookup data ready for upload
+#[derive(Debug, Clone)]
+pub struct UvMapImage {
+    pub width: u32,
+    pub height: u32,
+    /// Input UV per pixel, row-major; NaN where unmapped
+    pub uv: Vec<[f32; 2]>,
+}
+
+impl UvMapImage {
+    /// Look up the input UV for an output UV, matching the slice shader
+    ///
+    /// Interpolates bilinearly between corner-aligned texels, falling back to
+    /// the nearest texel next to holes. Returns `None` where unmapped.
+    pub fn sample(&self, uv: [f32; 2]) -> Option<[f32; 2]> {
+        let max_x = self.width.saturating_sub(1);
+        let max_y = self.height.saturating_sub(1);
+        let px = (uv[0] * max_x as f32).clamp(0.0, max_x as f32);
+        let py = (uv[1] * max_y as f32).clamp(0.0, max_y as f32);
+        let x0 = (px.floor() as u32).min(max_x);
+        let y0 = (py.floor() as u32).min(max_y);
+        let x1 = (x0 + 1).min(max_x);
+        let y1 = (y0 + 1).min(max_y);
+        let fx = px - x0 as f32;
+        let fy = py - y0 as f32;
+
+        let texel = |x: u32, y: u32| {
+            let value = self.uv[(y * self.width + x) as usize];
+            (value[0].is_finite() && value[1].is_finite()).then_some(value)
+        };
+        match (texel(x0, y0), texel(x1, y0), texel(x0, y1), texel(x1, y1)) {
+            (Some(a), Some(b), Some(c), Some(d)) => {
+                let top = [a[0] + (b[0] - a[0]) * fx, a[1] + (b[1] - a[1]) * fx];
+                let bottom = [c[0] + (d[0] - c[0]) * fx, c[1] + (d[1] - c[1]) * fx];
+                Some([
+                    top[0] + (bottom[0] - top[0]) * fy,
+                    top[1] + (bottom[1] - top[1]) * fy,
+                ])
+            }
+            _ => texel(px.round() as u32, py.round() as u32),
+        }
+    }
+}
+
+/// Decoded attenuation data ready for upload
+#[derive(Debug, Clone)]
+pub struct AttenuationImage {
+    pub width: u32,
+    pub height: u32,
+    /// RGB gain per pixel (alpha is always 1.0), row-major
+    pub rgba: Vec<[f32; 4]>,
+}
+
+impl AttenuationImage {
+    /// Pack as `Rgba16Float` texel data
+    pub fn to_f16_bytes(&self) -> Vec<u8> {
+        self.rgba
+            .iter()
+            .flat_map(|p| p.iter().flat_map(|c| f32_to_f16_bits(*c).to_le_bytes()))
+            .collect()
+    }
+
+    /// Bilinearly sample the RGB gain at an output UV (texel-centered, like the GPU sampler)
+    pub fn sample(&self, uv: [f32; 2]) -> [f32; 3] {
+        let max_x = self.width.saturating_sub(1) as f32;
+        let max_y = self.height.saturating_sub(1) as f32;
+        let px = (uv[0] * self.width as f32 - 0.5).clamp(0.0, max_x);
+        let py = (uv[1] * self.height as f32 - 0.5).clamp(0.0, max_y);
+        let x0 = px.floor() as u32;
+        let y0 = py.floor() as u32;
+        let x1 = (x0 + 1).min(self.width - 1);
+        let y1 = (y0 + 1).min(self.height - 1);
+        let fx = px - x0 as f32;
+        let fy = py - y0 as f32;
+
+        let texel = |x: u32, y: u32| self.rgba[(y * self.width + x) as usize];
+        let (a, b, c, d) = (texel(x0, y0), texel(x1, y0), texel(x0, y1), texel(x1, y1));
+        std::array::from_fn(|i| {
+            let top = a[i] + (b[i] - a[i]) * fx;
+            let bottom = c[i] + (d[i] - c[i]) * fx;
+            top + (bottom - top) * fy
+        })
+    }
+}
+
+/// Errors that can occur when loading UV map sources
+#[derive(Debug)]
+pub enum UvMapError {
+    Io(std::io::Error),
+    Image(image::ImageError),
+    Schema(SchemaError),
+    Pfm(MpcdiError),
+    Empty,
+}
+
+impl std::fmt::Display for UvMapError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            UvMapError::Io(e) => write!(f, "IO error: {}", e),
+            UvMapError::Image(e) => write!(f, "Image error: {}", e),
+            UvMapError::Schema(e) => write!(f, "UV map error: {}", e),
+            UvMapError::Pfm(e) => write!(f, "PFM error: {}", e),
+            UvMapError::Empty => write!(f, "UV map has no pixels"),
+        }
+    }
+}
+
+impl std::error::Error for UvMapError {}
+
+/// Load a UV lookup from a `.pmuv` file, an MPCDI `.pfm` warp or an image
+pub fn load_uv_map(path: &Path, invert_v: bool) -> Result<UvMapImage, UvMapError> {
+    let extension = path
+        .extension()
+        .map(|e| e.to_string_lossy().to_ascii_lowercase())
+        .unwrap_or_default();
+    let mut image = if extension == "pmuv" {
+        let bytes = std::fs::read(path).map_err(UvMapError::Io)?;
+        let map = UvMap::from_bytes(&bytes).map_err(UvMapError::Schema)?;
+        UvMapImage {
+            width: map.width,
+            height: map.height,
+            uv: map.data,
+        }
+    } else if extension == "pfm" {
+        let bytes = std::fs::read(path).map_err(UvMapError::Io)?;
+        let pfm = read_pfm(&bytes).map_err(UvMapError::Pfm)?;
+        let uv = (0..pfm.width as usize * pfm.height as usize)
+            .map(|i| [pfm.value(i, 0), pfm.value(i, 1)])
+            .collect();
+        UvMapImage {
+            width: pfm.width,
+            height: pfm.height,
+            uv,
+        }
+    } else {
+        let rgba = image::open(path).map_err(UvMapError::Image)?.to_rgba32f();
+        let (width, height) = rgba.dimensions();
+        let uv = rgba
+            .pixels()
+            .map(|p| {
+                if p[3] <= 0.0 {
+                    [f32::NAN, f32::NAN]
+                } else {
+                    [p[0], p[1]]
+                }
+            })
+            .collect();
+        UvMapImage { width, height, uv }
+    };
+
+    if image.width == 0 || image.height == 0 {
+        return Err(UvMapError::Empty);
+    }
+    if invert_v {
+        for uv in &mut image.uv {
+            uv[1] = 1.0 - uv[1];
+        }
+    }
+    Ok(image)
+}
+
+/// Load an attenuation or black level image (8/16-bit PNG, EXR, ...)
+///
+/// Grayscale images affect all channels equally.
+pub fn load_attenuation(path: &Path) -> Result<AttenuationImage, UvMapError> {
+    let rgba = image::open(path).map_err(UvMapError::Image)?.to_rgba32f();
+    let (width, height) = rgba.dimensions();
+    if width == 0 || height == 0 {
+        return Err(UvMapError::Empty);
+    }
+    let rgba = rgba.pixels().map(|p| [p[0], p[1], p[2], 1.0]).collect();
+    Ok(AttenuationImage {
+        width,
+        height,
+        rgba,
+    })
+}
+
+/// Convert an f32 to IEEE 754 half-precision bits (round to nearest even)
+pub(crate) fn f32_to_f16_bits(value: f32) -> u16 {
+    let bits = value.to_bits();
+    let sign = ((bits >> 16) & 0x8000) as u16;
+    let exponent = ((bits >> 23) & 0xff) as i32;
+    let mantissa = bits & 0x007f_ffff;
+
+    if exponent == 0xff {
+        // Inf / NaN
+        let nan = if mantissa != 0 { 0x0200 } else { 0 };
+        return sign | 0x7c00 | nan;
+    }
+
+    let half_exponent = exponent - 127 + 15;
+    if half_exponent >= 0x1f {
+        return sign | 0x7c00;
+    }
+    if half_exponent <= 0 {
+        // Subnormal or underflow to zero
+        if half_exponent < -10 {
+            return sign;
+        }
+        let mantissa = mantissa | 0x0080_0000;
+        let shift = (14 - half_exponent) as u32;
+        let half_mantissa = mantissa >> shift;
+        let round = (mantissa >> (shift - 1)) & 1;
+        let sticky = mantissa & ((1 << (shift - 1)) - 1);
+        let rounded = half_mantissa + (round & ((sticky != 0) as u32 | (half_mantissa & 1)));
+        return sign | rounded as u16;
+    }
+
+    let half = ((half_exponent as u32) << 10) | (mantissa >> 13);
+    let round = (mantissa >> 12) & 1;
+    let sticky = mantissa & 0x0fff;
+    // Carry from rounding may bump the exponent, which is the correct result
+    let rounded = half + (round & ((sticky != 0) as u32 | (half & 1)));
+    sign | rounded as u16
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_f32_to_f16_bits() {
+        assert_eq!(f32_to_f16_bits(0.0), 0x0000);
+        assert_eq!(f32_to_f16_bits(1.0), 0x3c00);
+        assert_eq!(f32_to_f16_bits(0.5), 0x3800);
+        assert_eq!(f32_to_f16_bits(-2.0), 0xc000);
+        assert_eq!(f32_to_f16_bits(65504.0), 0x7bff);
+        assert_eq!(f32_to_f16_bits(1.0e6), 0x7c00);
+        // Smallest subnormal
+        assert_eq!(f32_to_f16_bits(5.960_464_5e-8), 0x0001);
+        assert_eq!(f32_to_f16_bits(f32::NAN) & 0x7c00, 0x7c00);
+    }
+
+    #[test]
+    fn test_load_pmuv_and_png() {
+        let dir = std::env::temp_dir().join(format!("uv_map_test_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let map = UvMap {
+            projector_id: 0,
+            width: 2,
+            height: 1,
+            data: vec![[0.25, 0.75], [f32::NAN, f32::NAN]],
+        };
+        let pmuv = dir.join("map.pmuv");
+        std::fs::write(&pmuv, map.to_bytes()).unwrap();
+        let loaded = load_uv_map(&pmuv, true).unwrap();
+        assert_eq!((loaded.width, loaded.height), (2, 1));
+        assert_eq!(loaded.uv[0], [0.25, 0.25]);
+        assert!(loaded.uv[1][0].is_nan());
+
+        // 16-bit PNG: second pixel fully transparent = unmapped
+        let png = dir.join("map.png");
+        let mut img = image::ImageBuffer::<image::Rgba<u16>, Vec<u16>>::new(2, 1);
+        img.put_pixel(0, 0, image::Rgba([65535, 32768, 0, 65535]));
+        img.put_pixel(1, 0, image::Rgba([0, 0, 0, 0]));
+        img.save(&png).unwrap();
+        let loaded = load_uv_map(&png, false).unwrap();
+        assert_eq!(loaded.uv[0][0], 1.0);
+        assert!((loaded.uv[0][1] - 0.5).abs() < 1e-4);
+        assert!(loaded.uv[1][1].is_nan());
+
+        let attenuation = load_attenuation(&png).unwrap();
+        assert_eq!(attenuation.to_f16_bytes().len(), 2 * 8);
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -1,6 +1,10 @@
 //! Warp mesh definitions for projection mapping
 //!
 //! Supports grid-based surface deformation for mapping onto non-planar surfaces.
+//! The slice shader evaluates the mesh per fragment, so smooth (Catmull-Rom)
+//! meshes are effectively subdivided to the render resolution.
+
+use std::ops::RangeInclusive;
 
 use serde::{Deserialize, Serialize};
 
@@ -90,12 +94,8 @@ impl WarpMesh {
 
     /// Reset mesh to identity (no deformation)
     pub fn reset(&mut self) {
-        for col in 0..self.columns {
-            for row in 0..self.rows {
-                let u = col as f32 / (self.columns - 1) as f32;
-                let v = row as f32 / (self.rows - 1) as f32;
-                self.points[col * self.rows + row].position = [u, v];
-            }
+        for point in &mut self.points {
+            point.position = point.uv;
         }
     }
 
@@ -121,6 +121,182 @@ impl WarpMesh {
         ]
     }
 
+    /// Original U coordinate of each grid column
+    pub fn column_knots(&self) -> Vec<f32> {
+        (0..self.columns).map(|col| self.points[col * self.rows].uv[0]).collect()
+    }
+
+    /// Original V coordinate of each grid row
+    pub fn row_knots(&self) -> Vec<f32> {
+        (0..self.rows).map(|row| self.points[row].uv[1]).collect()
+    }
+
+    /// Map a UV through the mesh (matches the slice shader)
+    ///
+    /// Cells are located by the points' original UVs, so grids with inserted
+    /// rows or columns need not be uniform. Catmull-Rom meshes are evaluated
+    /// as a C1-continuous cubic spline over the whole grid; every other mode
+    /// is bilinear per cell.
+    pub fn sample(&self, uv: [f32; 2]) -> [f32; 2] {
+        if self.columns < 2 || self.rows < 2 || self.points.len() < self.columns * self.rows {
+            return uv;
+        }
+        let smooth = self.interpolation == WarpInterpolation::CatmullRom;
+        let (col, wx) = spline_weights(&self.column_knots(), uv[0], smooth);
+        let (row, wy) = spline_weights(&self.row_knots(), uv[1], smooth);
+
+        let mut result = [0.0, 0.0];
+        for (j, weight_y) in wy.iter().enumerate() {
+            let Some(r) = (row + j).checked_sub(1).filter(|&r| r < self.rows) else {
+                continue;
+            };
+            for (i, weight_x) in wx.iter().enumerate() {
+                let Some(c) = (col + i).checked_sub(1).filter(|&c| c < self.columns) else {
+                    continue;
+                };
+                let weight = weight_x * weight_y;
+                if weight != 0.0 {
+                    let p = self.points[c * self.rows + r].position;
+                    result[0] += p[0] * weight;
+                    result[1] += p[1] * weight;
+                }
+            }
+        }
+        result
+    }
+
+    /// Insert a column halfway between `after` and the next column
+    ///
+    /// The new points are placed on the current surface, so the deformation
+    /// is kept (exactly for linear meshes). Returns the new column index.
+    pub fn insert_column(&mut self, after: usize) -> Option<usize> {
+        if after + 1 >= self.columns {
+            return None;
+        }
+        let knots = self.column_knots();
+        let u = (knots[after] + knots[after + 1]) * 0.5;
+        let column: Vec<WarpPoint> = self
+            .row_knots()
+            .into_iter()
+            .map(|v| WarpPoint {
+                uv: [u, v],
+                position: self.sample([u, v]),
+            })
+            .collect();
+
+        let at = (after + 1) * self.rows;
+        self.points.splice(at..at, column);
+        self.columns += 1;
+        Some(after + 1)
+    }
+
+    /// Insert a row halfway between `after` and the next row
+    ///
+    /// The new points are placed on the current surface, so the deformation
+    /// is kept (exactly for linear meshes). Returns the new row index.
+    pub fn insert_row(&mut self, after: usize) -> Option<usize> {
+        if after + 1 >= self.rows {
+            return None;
+        }
+        let knots = self.row_knots();
+        let v = (knots[after] + knots[after + 1]) * 0.5;
+        let row: Vec<WarpPoint> = self
+            .column_knots()
+            .into_iter()
+            .map(|u| WarpPoint {
+                uv: [u, v],
+                position: self.sample([u, v]),
+            })
+            .collect();
+
+        let old_rows = self.rows;
+        let mut points = Vec::with_capacity(self.points.len() + row.len());
+        for (col, new_point) in row.into_iter().enumerate() {
+            let column = &self.points[col * old_rows..(col + 1) * old_rows];
+            points.extend_from_slice(&column[..=after]);
+            points.push(new_point);
+            points.extend_from_slice(&column[after + 1..]);
+        }
+        self.points = points;
+        self.rows += 1;
+        Some(after + 1)
+    }
+
+    /// Remove an interior column (border columns are kept)
+    pub fn remove_column(&mut self, col: usize) -> bool {
+        if col == 0 || col + 1 >= self.columns {
+            return false;
+        }
+        let start = col * self.rows;
+        self.points.drain(start..start + self.rows);
+        self.columns -= 1;
+        true
+    }
+
+    /// Remove an interior row (border rows are kept)
+    pub fn remove_row(&mut self, row: usize) -> bool {
+        if row == 0 || row + 1 >= self.rows {
+            return false;
+        }
+        let rows = self.rows;
+        let mut index = 0;
+        self.points.retain(|_| {
+            let keep = index % rows != row;
+            index += 1;
+            keep
+        });
+        self.rows -= 1;
+        true
+    }
+
+    /// Reset a rectangular block of points to their original positions
+    pub fn reset_region(&mut self, cols: RangeInclusive<usize>, rows: RangeInclusive<usize>) {
+        for col in cols {
+            for row in rows.clone() {
+                if let Some(point) = self.get_point_mut(col, row) {
+                    point.position = point.uv;
+                }
+            }
+        }
+    }
+
+    /// Copy a point's displacement, mirrored, onto its counterparts across
+    /// the vertical (`horizontal`) and/or horizontal (`vertical`) center axis
+    ///
+    /// Counterparts are found by original UV, so points without an exact
+    /// mirror position are left untouched.
+    pub fn mirror_point(&mut self, col: usize, row: usize, horizontal: bool, vertical: bool) {
+        let Some(point) = self.get_point(col, row).copied() else {
+            return;
+        };
+        let [dx, dy] = point.displacement();
+        let mirror_col = horizontal
+            .then(|| find_knot(&self.column_knots(), 1.0 - point.uv[0]))
+            .flatten();
+        let mirror_row = vertical
+            .then(|| find_knot(&self.row_knots(), 1.0 - point.uv[1]))
+            .flatten();
+
+        let mut targets = Vec::with_capacity(3);
+        if let Some(c) = mirror_col {
+            targets.push((c, row, [-dx, dy]));
+        }
+        if let Some(r) = mirror_row {
+            targets.push((col, r, [dx, -dy]));
+        }
+        if let (Some(c), Some(r)) = (mirror_col, mirror_row) {
+            targets.push((c, r, [-dx, -dy]));
+        }
+        for (c, r, [mx, my]) in targets {
+            if (c, r) == (col, row) {
+                continue;
+            }
+            if let Some(target) = self.get_point_mut(c, r) {
+                target.position = [target.uv[0] + mx, target.uv[1] + my];
+            }
+        }
+    }
+
     /// Resize the mesh while preserving corner positions
     pub fn resize(&mut self, new_columns: usize, new_rows: usize) {
         let new_columns = new_columns.max(2);
@@ -206,8 +382,12 @@ pub enum WarpInterpolation {
     #[default]
     Linear,
 
-    /// Bezier curve interpolation for smooth surfaces
+    /// Bezier curve interpolation for smooth surfaces (currently rendered linearly)
     Bezier,
+
+    /// Catmull-Rom (bicubic) spline through all control points, C1-continuous
+    /// across cells; suited to curved screens
+    CatmullRom,
 }
 
 impl WarpInterpolation {
@@ -216,10 +396,50 @@ impl WarpInterpolation {
         match self {
             WarpInterpolation::Linear => "Linear",
             WarpInterpolation::Bezier => "Bezier (Smooth)",
+            WarpInterpolation::CatmullRom => "Catmull-Rom (Smooth)",
         }
     }
 }
 
+/// Find the grid line whose original coordinate matches `value`
+fn find_knot(knots: &[f32], value: f32) -> Option<usize> {
+    knots.iter().position(|k| (k - value).abs() < 1e-4)
+}
+
+/// Locate the cell containing `u` and compute the weights of the four
+/// neighbouring grid lines (cell - 1 ..= cell + 2)
+///
+/// Smooth weights form a cubic Hermite spline whose tangents are central
+/// differences over the knots (one-sided at the border), which reduces to
+/// Catmull-Rom on uniform grids. Mirrors `mesh_weights` in slice_render.wgsl.
+fn spline_weights(knots: &[f32], u: f32, smooth: bool) -> (usize, [f32; 4]) {
+    let count = knots.len();
+    let cell = (1..count - 1).rev().find(|&i| knots[i] <= u).unwrap_or(0);
+    let h = (knots[cell + 1] - knots[cell]).max(1e-6);
+    let t = (u - knots[cell]) / h;
+
+    let mut weights = [0.0; 4];
+    if !smooth {
+        weights[1] = 1.0 - t;
+        weights[2] = t;
+        return (cell, weights);
+    }
+
+    let t2 = t * t;
+    let t3 = t2 * t;
+    weights[1] += 2.0 * t3 - 3.0 * t2 + 1.0;
+    weights[2] += -2.0 * t3 + 3.0 * t2;
+    for (offset, basis) in [(0, t3 - 2.0 * t2 + t), (1, t3 - t2)] {
+        let i = cell + offset;
+        let a = i.saturating_sub(1);
+        let b = (i + 1).min(count - 1);
+        let scale = basis * h / (knots[b] - knots[a]).max(1e-6);
+        weights[b + 1 - cell] += scale;
+        weights[a + 1 - cell] -= scale;
+    }
+    (cell, weights)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -274,4 +494,83 @@ mod tests {
         assert_eq!(mesh.get_point(0, 0).unwrap().position, [0.1, 0.1]);
         assert_eq!(mesh.get_point(7, 7).unwrap().position, [0.9, 0.9]);
     }
+
+    #[test]
+    fn test_mesh_sample() {
+        let mut mesh = WarpMesh::new(3, 3);
+        assert_eq!(mesh.sample([0.25, 0.75]), [0.25, 0.75]);
+
+        mesh.set_point_position(1, 1, 0.6, 0.5);
+        assert_eq!(mesh.sample([0.5, 0.5]), [0.6, 0.5]);
+        assert!((mesh.sample([0.25, 0.5])[0] - 0.3).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_catmull_rom_sample() {
+        let mut mesh = WarpMesh::new(4, 4);
+        mesh.interpolation = WarpInterpolation::CatmullRom;
+        // Identity meshes stay identity (the spline reproduces linear functions)
+        let p = mesh.sample([0.3, 0.8]);
+        assert!((p[0] - 0.3).abs() < 1e-5 && (p[1] - 0.8).abs() < 1e-5);
+
+        // Control points are interpolated exactly
+        mesh.set_point_position(1, 1, 0.4, 0.3);
+        let p = mesh.sample([1.0 / 3.0, 1.0 / 3.0]);
+        assert!((p[0] - 0.4).abs() < 1e-5 && (p[1] - 0.3).abs() < 1e-5);
+
+        // The spline is smooth: the displacement also bends the neighbouring cell
+        let linear = WarpMesh {
+            interpolation: WarpInterpolation::Linear,
+            ..mesh.clone()
+        };
+        assert!((linear.sample([0.8, 1.0 / 3.0])[0] - 0.8).abs() < 1e-6);
+        assert!((mesh.sample([0.8, 1.0 / 3.0])[0] - 0.8).abs() > 1e-4);
+    }
+
+    #[test]
+    fn test_insert_and_remove_lines() {
+        let mut mesh = WarpMesh::new(3, 3);
+        mesh.set_point_position(1, 1, 0.6, 0.55);
+        let before = [mesh.sample([0.3, 0.4]), mesh.sample([0.7, 0.9])];
+
+        assert_eq!(mesh.insert_column(0), Some(1));
+        assert_eq!(mesh.insert_row(1), Some(2));
+        assert_eq!(mesh.columns, 4);
+        assert_eq!(mesh.rows, 4);
+        assert_eq!(mesh.points.len(), 16);
+        assert_eq!(mesh.column_knots(), vec![0.0, 0.25, 0.5, 1.0]);
+        assert_eq!(mesh.row_knots(), vec![0.0, 0.5, 0.75, 1.0]);
+        // The moved point shifted to (2, 1) and the deformation is unchanged
+        assert_eq!(mesh.get_point(2, 1).unwrap().position, [0.6, 0.55]);
+        for (uv, expected) in [[0.3, 0.4], [0.7, 0.9]].into_iter().zip(before) {
+            let p = mesh.sample(uv);
+            assert!((p[0] - expected[0]).abs() < 1e-6 && (p[1] - expected[1]).abs() < 1e-6);
+        }
+
+        assert!(!mesh.remove_column(0));
+        assert!(!mesh.remove_row(3));
+        assert!(mesh.remove_column(1));
+        assert!(mesh.remove_row(2));
+        assert_eq!(mesh.points.len(), 9);
+        assert_eq!(mesh.get_point(1, 1).unwrap().position, [0.6, 0.55]);
+        assert_eq!(mesh.insert_column(2), None);
+    }
+
+    #[test]
+    fn test_mirror_and_reset_region() {
+        let mut mesh = WarpMesh::new(5, 5);
+        mesh.set_point_position(1, 1, 0.3, 0.2);
+        mesh.mirror_point(1, 1, true, true);
+        let p = mesh.get_point(3, 1).unwrap().position;
+        assert!((p[0] - 0.7).abs() < 1e-6 && (p[1] - 0.2).abs() < 1e-6);
+        let p = mesh.get_point(1, 3).unwrap().position;
+        assert!((p[0] - 0.3).abs() < 1e-6 && (p[1] - 0.8).abs() < 1e-6);
+        let p = mesh.get_point(3, 3).unwrap().position;
+        assert!((p[0] - 0.7).abs() < 1e-6 && (p[1] - 0.8).abs() < 1e-6);
+
+        mesh.reset_region(0..=2, 0..=4);
+        assert!(mesh.get_point(1, 1).unwrap().is_identity());
+        assert!(mesh.get_point(1, 3).unwrap().is_identity());
+        assert!(!mesh.get_point(3, 1).unwrap().is_identity());
+    }
 }
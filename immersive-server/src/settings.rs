@@ -8,7 +8,7 @@ use serde::{Deserialize, Deserializer, Serialize};
 use std::fs;
 use std::path::PathBuf;
 
-use crate::compositor::Layer;
+use crate::compositor::{Layer, TriggerQuantize};
 use crate::effects::EffectStack;
 use crate::output::{OutputPresetReference, Screen, ScreenId, SliceId};
 use crate::previs::PrevisSettings;
@@ -202,6 +202,23 @@ pub struct EnvironmentSettings {
     #[serde(rename = "floorLayerIndex", default)]
     pub floor_layer_index: usize,
 
+    /// Global clip trigger quantization (layers can override this)
+    #[serde(rename = "triggerQuantize", default)]
+    pub trigger_quantize: TriggerQuantize,
+
+    /// Whether the BPM clock follows an Ableton Link session
+    #[serde(rename = "linkEnabled", default)]
+    pub link_enabled: bool,
+
+    /// Whether Link start/stop is synced with the BPM clock's running state
+    #[serde(rename = "linkStartStopSync", default)]
+    pub link_start_stop_sync: bool,
+
+    /// Whether the BPM clock follows tempo detected on the audio source
+    /// (ignored while Link is enabled)
+    #[serde(rename = "audioBeatSync", default)]
+    pub audio_beat_sync: bool,
+
     /// Low latency mode: trades stability for reduced input lag
     /// - true:  1 frame in flight (~16ms less latency, may stutter under load)
     /// - false: 2 frames in flight (smoother, but ~16ms more latency)
@@ -228,6 +245,14 @@ pub struct EnvironmentSettings {
     #[serde(rename = "fftGain", default = "default_fft_gain")]
     pub fft_gain: f32,
 
+    /// Number of log-spaced FFT bands available to automation (8, 16 or 32)
+    #[serde(rename = "fftBandCount", default = "default_fft_band_count")]
+    pub fft_band_count: usize,
+
+    /// Attack/release per log-spaced FFT band (missing bands use defaults)
+    #[serde(rename = "fftBandEnvelopes", default, skip_serializing_if = "Vec::is_empty")]
+    pub fft_band_envelopes: Vec<crate::audio::BandEnvelope>,
+
     /// Tiled layout configuration (UI panel arrangement)
     /// Optional - if not present, uses app preferences or default layout
     #[serde(rename = "tiledLayout", default, skip_serializing_if = "Option::is_none")]
@@ -279,6 +304,11 @@ fn default_fft_gain() -> f32 {
     1.0
 }
 
+/// Default number of log-spaced FFT bands
+fn default_fft_band_count() -> usize {
+    crate::audio::DEFAULT_BAND_COUNT
+}
+
 impl Default for EnvironmentSettings {
     fn default() -> Self {
         Self {
@@ -309,11 +339,17 @@ impl Default for EnvironmentSettings {
             previs_settings: PrevisSettings::default(),
             floor_sync_enabled: false,
             floor_layer_index: 0,
+            trigger_quantize: TriggerQuantize::Off,
+            link_enabled: false,
+            link_start_stop_sync: false,
+            audio_beat_sync: false,
             low_latency_mode: false, // Default to stability (2 frames in flight)
             test_pattern_enabled: false,
             bgra_pipeline_enabled: false, // Default to RGBA for compatibility
             audio_source: AudioSourceType::default(),
             fft_gain: default_fft_gain(),
+            fft_band_count: default_fft_band_count(),
+            fft_band_envelopes: Vec::new(),
             tiled_layout: None,
         }
     }
@@ -0,0 +1,110 @@
+// Spectrum Effect Shader
+//
+// Draws the audio spectrum (bars) or waveform (line) over the input.
+//
+// Parameters:
+//   params[0]    = amount (0 to 1, default 1) - opacity of the overlay
+//   params[1]    = mode (0 = bars, 1 = waveform)
+//   params[2..6] = color (RGBA)
+//   params[6]    = height (0.05 to 1, default 0.5) - fraction of the frame height
+//   params[7]    = bar_count (8 to 256, default 64)
+//   params[8]    = mirror (0 or 1) - grow bars from the vertical center
+//
+// The audio texture holds SPECTRUM_SIZE texels: r = spectrum level (log
+// frequency, 20 Hz left to 20 kHz right), g = waveform sample (-1 to 1).
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+}
+
+struct EffectParams {
+    time: f32,
+    delta_time: f32,
+    beat_phase: f32,
+    bar_phase: f32,
+    amount: f32,      // params[0]
+    mode: f32,        // params[1]
+    height: f32,      // params[6]
+    bar_count: f32,   // params[7]
+    mirror: f32,      // params[8]
+    _pad0: f32,       // Padding for alignment
+    _pad1: f32,
+    _pad2: f32,
+    color: vec4<f32>, // params[2..6]
+}
+
+@group(0) @binding(0) var t_input: texture_2d<f32>;
+@group(0) @binding(1) var s_input: sampler;
+@group(0) @binding(2) var<uniform> params: EffectParams;
+@group(0) @binding(3) var t_audio: texture_1d<f32>;
+
+// ============================================================================
+// Vertex Shader
+// ============================================================================
+
+@vertex
+fn vs_main(@builtin(vertex_index) vertex_index: u32) -> VertexOutput {
+    var out: VertexOutput;
+    // Fullscreen triangle
+    let x = f32((vertex_index << 1u) & 2u);
+    let y = f32(vertex_index & 2u);
+    out.position = vec4<f32>(x * 2.0 - 1.0, 1.0 - y * 2.0, 0.0, 1.0);
+    out.uv = vec2<f32>(x, y);
+    return out;
+}
+
+// ============================================================================
+// Audio Texture Access
+// ============================================================================
+
+// Read the audio texel at a normalized position (linear interpolation by hand,
+// since the float32 texture is not filterable)
+fn audio_at(position: f32) -> vec2<f32> {
+    let width = i32(textureDimensions(t_audio));
+    let x = clamp(position, 0.0, 1.0) * f32(width - 1);
+    let i0 = i32(floor(x));
+    let i1 = min(i0 + 1, width - 1);
+    return mix(textureLoad(t_audio, i0, 0).rg, textureLoad(t_audio, i1, 0).rg, fract(x));
+}
+
+// ============================================================================
+// Fragment Shader
+// ============================================================================
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let color = textureSample(t_input, s_input, in.uv);
+    let mirror = params.mirror > 0.5;
+
+    // Distance from the baseline (bottom edge, or vertical center when mirrored)
+    var y = 1.0 - in.uv.y;
+    if (mirror) {
+        y = abs(in.uv.y - 0.5) * 2.0;
+    }
+
+    var coverage = 0.0;
+    if (params.mode < 0.5) {
+        // Bars: one spectrum reading per bar with a small gap
+        let bars = max(params.bar_count, 1.0);
+        let cell = in.uv.x * bars;
+        let bar_center = (floor(cell) + 0.5) / bars;
+        let level = audio_at(bar_center).r;
+        let in_bar = step(0.1, fract(cell)) * step(fract(cell), 0.9);
+        coverage = in_bar * step(y, level * params.height);
+    } else {
+        // Waveform: antialiased line around the baseline
+        let wave = audio_at(in.uv.x).g * params.height;
+        var offset = (0.5 - in.uv.y) * 2.0;
+        if (mirror) {
+            offset = abs(offset);
+        }
+        let target_y = select(wave, abs(wave), mirror);
+        let thickness = 0.01;
+        coverage = 1.0 - smoothstep(thickness, thickness * 2.0, abs(offset - target_y));
+    }
+
+    let alpha = coverage * params.color.a * params.amount;
+    let final_rgb = mix(color.rgb, params.color.rgb, alpha);
+    return vec4<f32>(final_rgb, max(color.a, alpha));
+}
@@ -1,6 +1,7 @@
 // Screen Composite Shader
 //
-// Composites a slice texture to the screen output with optional color correction.
+// Composites a slice texture to the screen output with optional color correction
+// and 3D LUT.
 // Used for the final output stage before sending to displays/streams.
 
 struct VertexOutput {
@@ -14,11 +15,15 @@ struct ScreenParams {
     color_adjust: vec4<f32>,
     // RGB channel multipliers + padding
     color_rgb: vec4<f32>,
+    // 3D LUT: enabled, intensity, tetrahedral (1.0) or trilinear (0.0), padding
+    lut: vec4<f32>,
 }
 
 @group(0) @binding(0) var t_slice: texture_2d<f32>;
 @group(0) @binding(1) var s_slice: sampler;
 @group(0) @binding(2) var<uniform> params: ScreenParams;
+// 3D LUT (1x1x1 dummy when no LUT is loaded)
+@group(0) @binding(3) var t_lut: texture_3d<f32>;
 
 // Vertex shader - generates fullscreen triangle
 @vertex
@@ -129,7 +134,82 @@ fn apply_screen_color_correction(color: vec3<f32>, params: ScreenParams) -> vec3
     return clamp(c, vec3<f32>(0.0), vec3<f32>(1.0));
 }
 
-// Fragment shader with color correction
+// Fetch a LUT entry (x = red, y = green, z = blue)
+fn lut_fetch(p: vec3<i32>) -> vec3<f32> {
+    return textureLoad(t_lut, p, 0).rgb;
+}
+
+// Look up a color in the 3D LUT
+// The LUT is Rgba16Float loaded without a sampler, so interpolation is manual:
+// trilinear blends 8 entries, tetrahedral blends the 4 corners of the enclosing
+// tetrahedron (keeps the neutral axis exact).
+fn sample_lut(color: vec3<f32>, tetrahedral: bool) -> vec3<f32> {
+    let size = i32(textureDimensions(t_lut).x);
+    let scaled = clamp(color, vec3<f32>(0.0), vec3<f32>(1.0)) * f32(size - 1);
+    let base = min(vec3<i32>(floor(scaled)), vec3<i32>(size - 2));
+    let f = scaled - vec3<f32>(base);
+
+    let c000 = lut_fetch(base);
+    let c111 = lut_fetch(base + vec3<i32>(1, 1, 1));
+
+    if (tetrahedral) {
+        // Walk c000 -> c1 -> c2 -> c111 along the axes by decreasing fraction
+        var o1 = vec3<i32>(1, 0, 0);
+        var o2 = vec3<i32>(1, 1, 0);
+        var w = f.rgb;
+        if (f.r >= f.g) {
+            if (f.g >= f.b) {
+                w = f.rgb;
+            } else if (f.r >= f.b) {
+                o2 = vec3<i32>(1, 0, 1);
+                w = f.rbg;
+            } else {
+                o1 = vec3<i32>(0, 0, 1);
+                o2 = vec3<i32>(1, 0, 1);
+                w = f.brg;
+            }
+        } else {
+            if (f.b >= f.g) {
+                o1 = vec3<i32>(0, 0, 1);
+                o2 = vec3<i32>(0, 1, 1);
+                w = f.bgr;
+            } else if (f.b >= f.r) {
+                o1 = vec3<i32>(0, 1, 0);
+                o2 = vec3<i32>(0, 1, 1);
+                w = f.gbr;
+            } else {
+                o1 = vec3<i32>(0, 1, 0);
+                w = f.grb;
+            }
+        }
+        let c1 = lut_fetch(base + o1);
+        let c2 = lut_fetch(base + o2);
+        return c000 + w.x * (c1 - c000) + w.y * (c2 - c1) + w.z * (c111 - c2);
+    }
+
+    let c100 = lut_fetch(base + vec3<i32>(1, 0, 0));
+    let c010 = lut_fetch(base + vec3<i32>(0, 1, 0));
+    let c110 = lut_fetch(base + vec3<i32>(1, 1, 0));
+    let c001 = lut_fetch(base + vec3<i32>(0, 0, 1));
+    let c101 = lut_fetch(base + vec3<i32>(1, 0, 1));
+    let c011 = lut_fetch(base + vec3<i32>(0, 1, 1));
+    let c00 = mix(c000, c100, f.r);
+    let c10 = mix(c010, c110, f.r);
+    let c01 = mix(c001, c101, f.r);
+    let c11 = mix(c011, c111, f.r);
+    return mix(mix(c00, c10, f.g), mix(c01, c11, f.g), f.b);
+}
+
+// Apply the 3D LUT: [enabled, intensity, tetrahedral, unused]
+fn apply_lut(color: vec3<f32>, lut: vec4<f32>) -> vec3<f32> {
+    if (lut.x < 0.5) {
+        return color;
+    }
+    let graded = sample_lut(color, lut.z > 0.5);
+    return clamp(mix(color, graded, lut.y), vec3<f32>(0.0), vec3<f32>(1.0));
+}
+
+// Fragment shader with color correction and LUT
 @fragment
 fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
     let color = textureSample(t_slice, s_slice, in.uv);
@@ -142,14 +222,15 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
         abs(params.color_adjust.w - 1.0) < 0.001 && // saturation == 1
         abs(params.color_rgb.x - 1.0) < 0.001 &&   // red == 1
         abs(params.color_rgb.y - 1.0) < 0.001 &&   // green == 1
-        abs(params.color_rgb.z - 1.0) < 0.001;     // blue == 1
+        abs(params.color_rgb.z - 1.0) < 0.001 &&   // blue == 1
+        params.lut.x < 0.5;                        // no LUT
 
     if (is_identity) {
         return color;
     }
 
     let corrected = apply_screen_color_correction(color.rgb, params);
-    return vec4<f32>(corrected, color.a);
+    return vec4<f32>(apply_lut(corrected, params.lut), color.a);
 }
 
 // Simple passthrough fragment shader (no color correction)
@@ -9,7 +9,7 @@ struct VertexOutput {
 }
 
 // Slice parameters uniform
-// IMPORTANT: This struct must match the Rust SliceParams layout exactly (240 bytes)
+// IMPORTANT: This struct must match the Rust SliceParams layout exactly (320 bytes)
 struct SliceParams {
     // Input rect (x, y, width, height) - normalized 0.0-1.0
     input_rect: vec4<f32>,
@@ -32,16 +32,18 @@ struct SliceParams {
     perspective_bl: vec2<f32>,  // Bottom-left
     // Perspective enabled flag (1.0 = enabled, 0.0 = disabled)
     perspective_enabled: f32,
-    // Padding for perspective (3x f32 instead of vec3 to avoid 16-byte alignment)
-    _pad0a: f32,
+    // Mesh interpolation (1.0 = Catmull-Rom spline, 0.0 = bilinear)
+    mesh_interpolation: f32,
+    // Padding (2x f32 instead of vec2 to keep the mesh fields at offset 144)
     _pad0b: f32,
     _pad0c: f32,
     // Mesh warp parameters
     mesh_columns: u32,
     mesh_rows: u32,
     mesh_enabled: f32,
-    _pad1: f32,
-    // Edge blend: [enabled, width, gamma, black_level] for each edge
+    // UV map warp enabled flag (1.0 = lookup texture bound, overrides mesh/perspective)
+    uv_map_enabled: f32,
+    // Edge blend: [curve (0 = disabled), width, center exponent, black_level] for each edge
     edge_left: vec4<f32>,
     edge_right: vec4<f32>,
     edge_top: vec4<f32>,
@@ -50,7 +52,15 @@ struct SliceParams {
     mask_enabled: f32,    // 1.0 = enabled, 0.0 = disabled
     mask_inverted: f32,   // 1.0 = show outside, 0.0 = show inside
     mask_feather: f32,    // Feather amount (0.0-0.5)
-    _pad2: f32,
+    // Attenuation enabled flag (1.0 = attenuation texture bound)
+    attenuation_enabled: f32,
+    // Edge blend per channel: [red exponent, green exponent, blue exponent, uplift]
+    edge_left_channels: vec4<f32>,
+    edge_right_channels: vec4<f32>,
+    edge_top_channels: vec4<f32>,
+    edge_bottom_channels: vec4<f32>,
+    // 3D LUT: enabled, intensity, tetrahedral (1.0) or trilinear (0.0), padding
+    lut: vec4<f32>,
 }
 
 // Warp point in storage buffer: [uv.x, uv.y, position.x, position.y]
@@ -72,6 +82,18 @@ struct WarpPoint {
 @group(2) @binding(0) var t_mask: texture_2d<f32>;
 @group(2) @binding(1) var s_mask: sampler;
 
+// Optional: UV lookup + attenuation + black level + 3D LUT textures (bind group 3)
+// Only bound when a UV map or LUT is loaded. The lookup is Rg32Float (not filterable),
+// so it is interpolated manually; unmapped texels hold UV_MAP_UNMAPPED.
+// The dummy black level texture is zero, so it is always safe to apply.
+@group(3) @binding(0) var t_uv_map: texture_2d<f32>;
+@group(3) @binding(1) var t_attenuation: texture_2d<f32>;
+@group(3) @binding(2) var s_attenuation: sampler;
+@group(3) @binding(3) var t_black_level: texture_2d<f32>;
+@group(3) @binding(4) var t_lut: texture_3d<f32>;
+
+const UV_MAP_UNMAPPED: f32 = -1.0e9;
+
 // Vertex shader - generates fullscreen triangle
 @vertex
 fn vs_main(@builtin(vertex_index) vertex_index: u32) -> VertexOutput {
@@ -110,40 +132,149 @@ fn get_warp_point(col: u32, row: u32) -> WarpPoint {
     return warp_points[idx];
 }
 
-// Apply mesh warp using bilinear interpolation between grid cells
-// Maps UV coordinates through the warp mesh grid
-fn apply_mesh_warp(uv: vec2<f32>) -> vec2<f32> {
-    if (params.mesh_enabled < 0.5 || params.mesh_columns < 2u || params.mesh_rows < 2u) {
-        return uv;
+// Original grid coordinate of a mesh line (axis 0 = column U, axis 1 = row V)
+// Knots need not be uniform once rows or columns have been inserted
+fn mesh_knot(axis: u32, i: u32) -> f32 {
+    if (axis == 0u) {
+        return get_warp_point(i, 0u).uv.x;
     }
+    return get_warp_point(0u, i).uv.y;
+}
 
-    // Scale UV to grid space (0 to columns-1, 0 to rows-1)
-    let grid_uv = uv * vec2<f32>(f32(params.mesh_columns - 1u), f32(params.mesh_rows - 1u));
+// Find the cell containing u: the last knot <= u (clamped to the interior cells)
+fn mesh_cell(axis: u32, count: u32, u: f32) -> u32 {
+    var cell = 0u;
+    for (var i = 1u; i < count - 1u; i = i + 1u) {
+        if (mesh_knot(axis, i) <= u) {
+            cell = i;
+        }
+    }
+    return cell;
+}
 
-    // Find which cell we're in
-    let cell = floor(grid_uv);
-    let local = fract(grid_uv);
+// Weights of grid lines cell-1 .. cell+2 at parameter t within the cell.
+// Smooth weights form a cubic Hermite spline with central-difference tangents
+// (one-sided at the border): Catmull-Rom on uniform grids, C1 across cells.
+// Must match spline_weights in warp.rs.
+fn mesh_weights(axis: u32, count: u32, cell: u32, t: f32, spline: bool) -> vec4<f32> {
+    var w = vec4<f32>(0.0);
+    if (!spline) {
+        w[1] = 1.0 - t;
+        w[2] = t;
+        return w;
+    }
 
-    // Clamp cell indices to valid range
-    let col = u32(clamp(cell.x, 0.0, f32(params.mesh_columns - 2u)));
-    let row = u32(clamp(cell.y, 0.0, f32(params.mesh_rows - 2u)));
+    let t2 = t * t;
+    let t3 = t2 * t;
+    let h = max(mesh_knot(axis, cell + 1u) - mesh_knot(axis, cell), 1e-6);
+    w[1] = 2.0 * t3 - 3.0 * t2 + 1.0;
+    w[2] = -2.0 * t3 + 3.0 * t2;
+    for (var e = 0u; e < 2u; e = e + 1u) {
+        let i = cell + e;
+        let a = select(i - 1u, 0u, i == 0u);
+        let b = min(i + 1u, count - 1u);
+        let basis = select(t3 - 2.0 * t2 + t, t3 - t2, e == 1u);
+        let scale = basis * h / max(mesh_knot(axis, b) - mesh_knot(axis, a), 1e-6);
+        w[b + 1u - cell] = w[b + 1u - cell] + scale;
+        w[a + 1u - cell] = w[a + 1u - cell] - scale;
+    }
+    return w;
+}
 
-    // Get the 4 corners of the grid cell
-    let p00 = get_warp_point(col, row);         // Top-left
-    let p10 = get_warp_point(col + 1u, row);    // Top-right
-    let p01 = get_warp_point(col, row + 1u);    // Bottom-left
-    let p11 = get_warp_point(col + 1u, row + 1u); // Bottom-right
+// Apply mesh warp by interpolating between grid points
+// Bilinear per cell, or a Catmull-Rom spline over the whole grid. Evaluating
+// per fragment subdivides the mesh to the render resolution.
+fn apply_mesh_warp(uv: vec2<f32>) -> vec2<f32> {
+    if (params.mesh_enabled < 0.5 || params.mesh_columns < 2u || params.mesh_rows < 2u) {
+        return uv;
+    }
 
-    // Bilinear interpolation of warped positions
-    let top = mix(p00.position, p10.position, local.x);
-    let bottom = mix(p01.position, p11.position, local.x);
-    return mix(top, bottom, local.y);
+    let spline = params.mesh_interpolation > 0.5;
+    let cols = params.mesh_columns;
+    let rows = params.mesh_rows;
+
+    // Find which cell we're in and the position inside it
+    let col = mesh_cell(0u, cols, uv.x);
+    let row = mesh_cell(1u, rows, uv.y);
+    let x0 = mesh_knot(0u, col);
+    let y0 = mesh_knot(1u, row);
+    let tx = (uv.x - x0) / max(mesh_knot(0u, col + 1u) - x0, 1e-6);
+    let ty = (uv.y - y0) / max(mesh_knot(1u, row + 1u) - y0, 1e-6);
+
+    let wx = mesh_weights(0u, cols, col, tx, spline);
+    let wy = mesh_weights(1u, rows, row, ty, spline);
+
+    // Weighted sum over the 4x4 neighbourhood (out-of-range lines have zero weight)
+    var result = vec2<f32>(0.0);
+    for (var j = 0u; j < 4u; j = j + 1u) {
+        let r = row + j;
+        if (r < 1u || r > rows || wy[j] == 0.0) {
+            continue;
+        }
+        for (var i = 0u; i < 4u; i = i + 1u) {
+            let c = col + i;
+            if (c < 1u || c > cols || wx[i] == 0.0) {
+                continue;
+            }
+            result = result + wx[i] * wy[j] * get_warp_point(c - 1u, r - 1u).position;
+        }
+    }
+    return result;
 }
 
 // ═══════════════════════════════════════════════════════════════════════════
 // Color Correction Functions
 // ═══════════════════════════════════════════════════════════════════════════
 
+fn load_uv_texel(coord: vec2<i32>, size: vec2<i32>) -> vec2<f32> {
+    let c = clamp(coord, vec2<i32>(0), size - vec2<i32>(1));
+    return textureLoad(t_uv_map, c, 0).xy;
+}
+
+fn is_uv_mapped(value: vec2<f32>) -> bool {
+    return value.x > UV_MAP_UNMAPPED * 0.5;
+}
+
+// Look up the input UV for an output UV in the dense UV map
+// Texels are corner-aligned (first/last texel on the output edges)
+// Returns (u, v, mapped) - mapped is 0.0 where the map has no data
+fn apply_uv_map(uv: vec2<f32>) -> vec3<f32> {
+    let size = vec2<i32>(textureDimensions(t_uv_map));
+    let pos = clamp(uv, vec2<f32>(0.0), vec2<f32>(1.0)) * vec2<f32>(size - vec2<i32>(1));
+    let base = vec2<i32>(floor(pos));
+    let f = pos - floor(pos);
+
+    let a = load_uv_texel(base, size);
+    let b = load_uv_texel(base + vec2<i32>(1, 0), size);
+    let c = load_uv_texel(base + vec2<i32>(0, 1), size);
+    let d = load_uv_texel(base + vec2<i32>(1, 1), size);
+
+    if (is_uv_mapped(a) && is_uv_mapped(b) && is_uv_mapped(c) && is_uv_mapped(d)) {
+        return vec3<f32>(mix(mix(a, b, f.x), mix(c, d, f.x), f.y), 1.0);
+    }
+
+    // Near a hole: fall back to the nearest texel so edges stay sharp
+    let nearest = load_uv_texel(vec2<i32>(round(pos)), size);
+    if (is_uv_mapped(nearest)) {
+        return vec3<f32>(nearest, 1.0);
+    }
+    return vec3<f32>(0.0, 0.0, 0.0);
+}
+
+// Multiply by the per-pixel attenuation texture (blend/luminance correction)
+fn apply_attenuation(color: vec3<f32>, uv: vec2<f32>) -> vec3<f32> {
+    if (params.attenuation_enabled < 0.5) {
+        return color;
+    }
+    return color * textureSampleLevel(t_attenuation, s_attenuation, uv, 0.0).rgb;
+}
+
+// Lift toward white by the per-pixel black level texture (MPCDI beta map)
+fn apply_black_level(color: vec3<f32>, uv: vec2<f32>) -> vec3<f32> {
+    let level = textureSampleLevel(t_black_level, s_attenuation, uv, 0.0).rgb;
+    return color * (vec3<f32>(1.0) - level) + level;
+}
+
 // Convert RGB to HSL color space
 // Returns vec3(hue, saturation, lightness) where h is 0-1
 fn rgb_to_hsl(rgb: vec3<f32>) -> vec3<f32> {
@@ -240,45 +371,136 @@ fn apply_color_correction(color: vec3<f32>, params: SliceParams) -> vec3<f32> {
     return clamp(c, vec3<f32>(0.0), vec3<f32>(1.0));
 }
 
-// Apply edge blending with gamma-corrected falloff
-// Edge blend vec4 format: [enabled, width, gamma, black_level]
-// uv is in 0-1 range (output rect normalized coordinates)
-fn apply_edge_blend(color: vec3<f32>, uv: vec2<f32>) -> vec3<f32> {
-    var alpha = 1.0;
-
-    // Left edge (uv.x goes from 0 at left to 1 at right)
-    if (params.edge_left.x > 0.5 && uv.x < params.edge_left.y) {
-        let t = uv.x / params.edge_left.y;
-        let gamma = params.edge_left.z;
-        let black = params.edge_left.w;
-        alpha *= pow(t, gamma) * (1.0 - black) + black;
+// Fetch a LUT entry (x = red, y = green, z = blue)
+fn lut_fetch(p: vec3<i32>) -> vec3<f32> {
+    return textureLoad(t_lut, p, 0).rgb;
+}
+
+// Look up a color in the 3D LUT
+// The LUT is Rgba16Float loaded without a sampler, so interpolation is manual:
+// trilinear blends 8 entries, tetrahedral blends the 4 corners of the enclosing
+// tetrahedron (keeps the neutral axis exact).
+fn sample_lut(color: vec3<f32>, tetrahedral: bool) -> vec3<f32> {
+    let size = i32(textureDimensions(t_lut).x);
+    let scaled = clamp(color, vec3<f32>(0.0), vec3<f32>(1.0)) * f32(size - 1);
+    let base = min(vec3<i32>(floor(scaled)), vec3<i32>(size - 2));
+    let f = scaled - vec3<f32>(base);
+
+    let c000 = lut_fetch(base);
+    let c111 = lut_fetch(base + vec3<i32>(1, 1, 1));
+
+    if (tetrahedral) {
+        // Walk c000 -> c1 -> c2 -> c111 along the axes by decreasing fraction
+        var o1 = vec3<i32>(1, 0, 0);
+        var o2 = vec3<i32>(1, 1, 0);
+        var w = f.rgb;
+        if (f.r >= f.g) {
+            if (f.g >= f.b) {
+                w = f.rgb;
+            } else if (f.r >= f.b) {
+                o2 = vec3<i32>(1, 0, 1);
+                w = f.rbg;
+            } else {
+                o1 = vec3<i32>(0, 0, 1);
+                o2 = vec3<i32>(1, 0, 1);
+                w = f.brg;
+            }
+        } else {
+            if (f.b >= f.g) {
+                o1 = vec3<i32>(0, 0, 1);
+                o2 = vec3<i32>(0, 1, 1);
+                w = f.bgr;
+            } else if (f.b >= f.r) {
+                o1 = vec3<i32>(0, 1, 0);
+                o2 = vec3<i32>(0, 1, 1);
+                w = f.gbr;
+            } else {
+                o1 = vec3<i32>(0, 1, 0);
+                w = f.grb;
+            }
+        }
+        let c1 = lut_fetch(base + o1);
+        let c2 = lut_fetch(base + o2);
+        return c000 + w.x * (c1 - c000) + w.y * (c2 - c1) + w.z * (c111 - c2);
     }
 
-    // Right edge (1.0 - uv.x gives distance from right)
-    if (params.edge_right.x > 0.5 && uv.x > (1.0 - params.edge_right.y)) {
-        let t = (1.0 - uv.x) / params.edge_right.y;
-        let gamma = params.edge_right.z;
-        let black = params.edge_right.w;
-        alpha *= pow(t, gamma) * (1.0 - black) + black;
+    let c100 = lut_fetch(base + vec3<i32>(1, 0, 0));
+    let c010 = lut_fetch(base + vec3<i32>(0, 1, 0));
+    let c110 = lut_fetch(base + vec3<i32>(1, 1, 0));
+    let c001 = lut_fetch(base + vec3<i32>(0, 0, 1));
+    let c101 = lut_fetch(base + vec3<i32>(1, 0, 1));
+    let c011 = lut_fetch(base + vec3<i32>(0, 1, 1));
+    let c00 = mix(c000, c100, f.r);
+    let c10 = mix(c010, c110, f.r);
+    let c01 = mix(c001, c101, f.r);
+    let c11 = mix(c011, c111, f.r);
+    return mix(mix(c00, c10, f.g), mix(c01, c11, f.g), f.b);
+}
+
+// Apply the 3D LUT: [enabled, intensity, tetrahedral, unused]
+fn apply_lut(color: vec3<f32>, lut: vec4<f32>) -> vec3<f32> {
+    if (lut.x < 0.5) {
+        return color;
     }
+    let graded = sample_lut(color, lut.z > 0.5);
+    return clamp(mix(color, graded, lut.y), vec3<f32>(0.0), vec3<f32>(1.0));
+}
 
-    // Top edge (uv.y goes from 0 at top to 1 at bottom)
-    if (params.edge_top.x > 0.5 && uv.y < params.edge_top.y) {
-        let t = uv.y / params.edge_top.y;
-        let gamma = params.edge_top.z;
-        let black = params.edge_top.w;
-        alpha *= pow(t, gamma) * (1.0 - black) + black;
+// Shape a normalized ramp position; the gamma curve's exponent is applied per channel
+// Curve ids: 1 = linear, 2 = gamma, 3 = cosine, 4 = smoothstep
+fn blend_curve(t: f32, curve: f32) -> f32 {
+    if (curve > 3.5) {
+        return t * t * (3.0 - 2.0 * t);
+    }
+    if (curve > 2.5) {
+        return 0.5 - 0.5 * cos(3.14159265 * t);
     }
+    return t;
+}
+
+// Per-channel blend factor for one edge at a distance from it
+// edge: [curve, width, center exponent, black_level]
+// channels: [red exponent, green exponent, blue exponent, uplift]
+fn edge_falloff(distance: f32, edge: vec4<f32>, channels: vec4<f32>) -> vec3<f32> {
+    if (edge.x < 0.5 || distance >= edge.y) {
+        return vec3<f32>(1.0);
+    }
+    let t = pow(max(distance / edge.y, 0.0), edge.z);
+    let shaped = vec3<f32>(blend_curve(t, edge.x));
+    return pow(shaped, channels.xyz) * (1.0 - edge.w) + edge.w;
+}
+
+// Uplift of one edge, or -1.0 when inside its (enabled) blend zone
+fn edge_uplift(distance: f32, edge: vec4<f32>, channels: vec4<f32>) -> f32 {
+    if (edge.x < 0.5) {
+        return 0.0;
+    }
+    return select(channels.w, -1.0, distance < edge.y);
+}
 
-    // Bottom edge (1.0 - uv.y gives distance from bottom)
-    if (params.edge_bottom.x > 0.5 && uv.y > (1.0 - params.edge_bottom.y)) {
-        let t = (1.0 - uv.y) / params.edge_bottom.y;
-        let gamma = params.edge_bottom.z;
-        let black = params.edge_bottom.w;
-        alpha *= pow(t, gamma) * (1.0 - black) + black;
+// Apply edge blending with per-channel curves, then lift the black level of
+// the area outside every blend zone to match the doubled black in the overlap
+// uv is in 0-1 range (output rect normalized coordinates)
+fn apply_edge_blend(color: vec3<f32>, uv: vec2<f32>) -> vec3<f32> {
+    // Distances from the left, right, top and bottom edges
+    let alpha = edge_falloff(uv.x, params.edge_left, params.edge_left_channels)
+        * edge_falloff(1.0 - uv.x, params.edge_right, params.edge_right_channels)
+        * edge_falloff(uv.y, params.edge_top, params.edge_top_channels)
+        * edge_falloff(1.0 - uv.y, params.edge_bottom, params.edge_bottom_channels);
+
+    let u = vec4<f32>(
+        edge_uplift(uv.x, params.edge_left, params.edge_left_channels),
+        edge_uplift(1.0 - uv.x, params.edge_right, params.edge_right_channels),
+        edge_uplift(uv.y, params.edge_top, params.edge_top_channels),
+        edge_uplift(1.0 - uv.y, params.edge_bottom, params.edge_bottom_channels),
+    );
+    var uplift = max(max(u.x, u.y), max(u.z, u.w));
+    if (min(min(u.x, u.y), min(u.z, u.w)) < 0.0) {
+        uplift = 0.0;
     }
 
-    return color * alpha;
+    let blended = color * alpha;
+    return blended * (1.0 - uplift) + uplift;
 }
 
 // Apply mask from rasterized mask texture
@@ -323,12 +545,21 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
     // Save output UV for edge blending (before warp transformations)
     let output_uv = uv;
 
-    // Apply perspective warp (keystone correction)
-    uv = apply_perspective_warp(uv);
+    if (params.uv_map_enabled > 0.5) {
+        // Dense UV map overrides perspective and mesh warps
+        let mapped = apply_uv_map(uv);
+        if (mapped.z < 0.5) {
+            return vec4<f32>(0.0, 0.0, 0.0, 0.0);
+        }
+        uv = mapped.xy;
+    } else {
+        // Apply perspective warp (keystone correction)
+        uv = apply_perspective_warp(uv);
 
-    // Apply mesh warp (grid-based deformation)
-    // Note: mesh warp overrides perspective when enabled
-    uv = apply_mesh_warp(uv);
+        // Apply mesh warp (grid-based deformation)
+        // Note: mesh warp overrides perspective when enabled
+        uv = apply_mesh_warp(uv);
+    }
 
     // Apply rotation around center
     if (abs(params.rotation) > 0.0001) {
@@ -374,14 +605,20 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
     // Sample input texture
     let color = textureSample(t_input, s_input, uv);
 
-    // Apply color correction
-    let corrected = apply_color_correction(color.rgb, params);
+    // Apply color correction, then the 3D LUT
+    let corrected = apply_lut(apply_color_correction(color.rgb, params), params.lut);
 
     // Apply edge blending (using output UV, not warped/sampled UV)
     let blended = apply_edge_blend(corrected, output_uv);
 
+    // Apply attenuation texture (also in output UV space)
+    let attenuated = apply_attenuation(blended, output_uv);
+
+    // Apply black level lift (also in output UV space)
+    let lifted = apply_black_level(attenuated, output_uv);
+
     // Apply opacity
-    let with_opacity = vec4<f32>(blended, color.a * params.opacity);
+    let with_opacity = vec4<f32>(lifted, color.a * params.opacity);
 
     // Apply mask (using output UV for mask sampling)
     return apply_mask(with_opacity, output_uv);
@@ -6,7 +6,7 @@
 use std::collections::HashSet;
 
 use crate::compositor::Viewport;
-use crate::output::{DisplayInfo, EdgeBlendConfig, MaskShape, OutputDevice, OutputManager, OutputPresetManager, Point2D as MaskPoint2D, Screen, ScreenId, Slice, SliceId, SliceInput, SliceMask, WarpMesh};
+use crate::output::{DisplayInfo, EdgeBlendConfig, EdgeBlendCurve, EdgeBlendRegion, LutInterpolation, MaskShape, OutputDevice, OutputLut, OutputManager, OutputPresetManager, Point2D as MaskPoint2D, Screen, ScreenId, Slice, SliceId, SliceInput, SliceMask, UvWarpMap, WarpInterpolation, WarpMesh};
 use crate::output::slice::{Point2D, Rect};
 use egui::PointerButton;
 use super::viewport_widget::{self, ViewportConfig};
@@ -130,6 +130,12 @@ pub enum AdvancedOutputAction {
     DeletePreset { name: String },
     /// Create a new configuration with a single virtual screen
     NewConfiguration,
+    /// Replace the configuration with screens from a projmap-calibrator export
+    ImportCalibration { path: std::path::PathBuf },
+    /// Import an MPCDI file as a preset and load it
+    ImportMpcdi { path: std::path::PathBuf },
+    /// Export the current configuration as an MPCDI 2D profile
+    ExportMpcdi { path: std::path::PathBuf },
 }
 
 /// Pending action when the user has unsaved changes and tries to switch presets
@@ -141,6 +147,10 @@ pub enum PendingPresetAction {
     CloseWindow,
     /// Create new configuration (after confirming discard)
     NewConfiguration,
+    /// Import a calibration (after confirming discard)
+    ImportCalibration { path: std::path::PathBuf },
+    /// Import an MPCDI file (after confirming discard)
+    ImportMpcdi { path: std::path::PathBuf },
 }
 
 /// Advanced Output window for configuring multi-screen outputs
@@ -162,6 +172,12 @@ pub struct AdvancedOutputWindow {
     pub preview_texture_id: Option<egui::TextureId>,
     /// Currently dragged warp point (col, row)
     dragging_warp_point: Option<(usize, usize)>,
+    /// Last picked warp point (col, row), target of row/column/point operations
+    selected_warp_point: Option<(usize, usize)>,
+    /// Mirror warp point edits across the vertical center axis
+    warp_mirror_x: bool,
+    /// Mirror warp point edits across the horizontal center axis
+    warp_mirror_y: bool,
     /// Currently dragged mask vertex index
     dragging_mask_vertex: Option<usize>,
     /// Temporary device name for streaming outputs
@@ -221,6 +237,9 @@ impl AdvancedOutputWindow {
             temp_height: String::new(),
             preview_texture_id: None,
             dragging_warp_point: None,
+            selected_warp_point: None,
+            warp_mirror_x: false,
+            warp_mirror_y: false,
             dragging_mask_vertex: None,
             temp_device_name: String::new(),
             temp_omt_port: "5960".to_string(),
@@ -424,6 +443,59 @@ impl AdvancedOutputWindow {
                 }
             }
 
+            // Import calibration button
+            if ui
+                .button("Import Calibration...")
+                .on_hover_text("Create one calibrated screen per projector from a projmap-calibrator export")
+                .clicked()
+            {
+                if let Some(path) = rfd::FileDialog::new()
+                    .set_title("Import Calibration")
+                    .add_filter("Calibration", &["json", "projmap", "xml"])
+                    .add_filter("All Files", &["*"])
+                    .pick_file()
+                {
+                    if self.is_dirty {
+                        self.pending_action = Some(PendingPresetAction::ImportCalibration { path });
+                    } else {
+                        actions.push(AdvancedOutputAction::ImportCalibration { path });
+                    }
+                }
+            }
+
+            // MPCDI import/export buttons
+            if ui
+                .button("Import MPCDI...")
+                .on_hover_text("Import an MPCDI package (.mpcdi) or unpacked mpcdi.xml as a preset")
+                .clicked()
+            {
+                if let Some(path) = rfd::FileDialog::new()
+                    .set_title("Import MPCDI")
+                    .add_filter("MPCDI", &["mpcdi", "xml"])
+                    .pick_file()
+                {
+                    if self.is_dirty {
+                        self.pending_action = Some(PendingPresetAction::ImportMpcdi { path });
+                    } else {
+                        actions.push(AdvancedOutputAction::ImportMpcdi { path });
+                    }
+                }
+            }
+            if ui
+                .button("Export MPCDI...")
+                .on_hover_text("Export the current screens as an MPCDI 2D profile with warp and blend maps")
+                .clicked()
+            {
+                if let Some(path) = rfd::FileDialog::new()
+                    .set_title("Export MPCDI")
+                    .add_filter("MPCDI XML", &["xml"])
+                    .set_file_name("mpcdi.xml")
+                    .save_file()
+                {
+                    actions.push(AdvancedOutputAction::ExportMpcdi { path });
+                }
+            }
+
             // Save button
             if ui.button("Save...").clicked() {
                 // Pre-fill with current preset name if saving over existing
@@ -631,6 +703,12 @@ impl AdvancedOutputWindow {
                 PendingPresetAction::NewConfiguration => {
                     actions.push(AdvancedOutputAction::NewConfiguration);
                 }
+                PendingPresetAction::ImportCalibration { path } => {
+                    actions.push(AdvancedOutputAction::ImportCalibration { path });
+                }
+                PendingPresetAction::ImportMpcdi { path } => {
+                    actions.push(AdvancedOutputAction::ImportMpcdi { path });
+                }
             }
         }
 
@@ -2218,6 +2296,15 @@ impl AdvancedOutputWindow {
         ui.separator();
         ui.add_space(4.0);
 
+        // 3D LUT section (applied after color correction)
+        if Self::render_lut_controls(ui, "screen_lut", &mut screen_copy.lut) {
+            changed = true;
+        }
+
+        ui.add_space(8.0);
+        ui.separator();
+        ui.add_space(4.0);
+
         // Edge Blend section (for first slice) - dynamic based on overlaps
         if let Some(first_slice) = screen.slices.first() {
             let mut slice_copy = first_slice.clone();
@@ -2338,6 +2425,10 @@ impl AdvancedOutputWindow {
                 let mut gamma = ref_edge.gamma;
                 let mut luminance = ref_edge.black_level;
                 let mut power = ref_edge.width;
+                let mut curve = ref_edge.curve;
+                let mut center = ref_edge.center;
+                let mut channel_gamma = ref_edge.channel_gamma;
+                let mut uplift = ref_edge.uplift;
 
                 // Gamma slider
                 ui.horizontal(|ui| {
@@ -2401,6 +2492,101 @@ impl AdvancedOutputWindow {
                         blend_changed = true;
                     }
                 });
+
+                // Curve shape
+                ui.horizontal(|ui| {
+                    ui.label("Curve:");
+                    egui::ComboBox::from_id_salt("edge_blend_curve")
+                        .selected_text(curve.display_name())
+                        .show_ui(ui, |ui| {
+                            for option in EdgeBlendCurve::ALL {
+                                if ui.selectable_value(&mut curve, option, option.display_name()).changed() {
+                                    edge.left.curve = curve;
+                                    edge.right.curve = curve;
+                                    edge.top.curve = curve;
+                                    edge.bottom.curve = curve;
+                                    blend_changed = true;
+                                }
+                            }
+                        });
+                });
+
+                // Center shift of the ramp's 50% point
+                ui.horizontal(|ui| {
+                    ui.label("Center:");
+                    let response = ui.add(egui::Slider::new(&mut center, -0.4..=0.4).max_decimals(2));
+                    if response.changed() {
+                        edge.left.set_center(center);
+                        edge.right.set_center(center);
+                        edge.top.set_center(center);
+                        edge.bottom.set_center(center);
+                        blend_changed = true;
+                    }
+                    // Right-click instantly resets to 0
+                    if response.clicked_by(PointerButton::Secondary) {
+                        edge.left.center = 0.0;
+                        edge.right.center = 0.0;
+                        edge.top.center = 0.0;
+                        edge.bottom.center = 0.0;
+                        blend_changed = true;
+                    }
+                });
+
+                // Per-channel gamma (multiplies the curve exponent)
+                ui.horizontal(|ui| {
+                    ui.label("RGB Gamma:");
+                    let mut rgb_changed = false;
+                    for (value, prefix) in channel_gamma.iter_mut().zip(["R:", "G:", "B:"]) {
+                        rgb_changed |= ui
+                            .add(egui::DragValue::new(value).range(0.25..=4.0).speed(0.01).prefix(prefix))
+                            .changed();
+                    }
+                    if rgb_changed {
+                        edge.left.channel_gamma = channel_gamma;
+                        edge.right.channel_gamma = channel_gamma;
+                        edge.top.channel_gamma = channel_gamma;
+                        edge.bottom.channel_gamma = channel_gamma;
+                        blend_changed = true;
+                    }
+                });
+
+                // Black-level uplift outside the overlap
+                ui.horizontal(|ui| {
+                    ui.label("Black Uplift:");
+                    let response = ui.add(egui::Slider::new(&mut uplift, 0.0..=0.5).max_decimals(3))
+                        .on_hover_text("Lift black outside the overlap to match the doubled black inside it");
+                    if response.changed() {
+                        edge.left.set_uplift(uplift);
+                        edge.right.set_uplift(uplift);
+                        edge.top.set_uplift(uplift);
+                        edge.bottom.set_uplift(uplift);
+                        blend_changed = true;
+                    }
+                    // Right-click instantly resets to 0
+                    if response.clicked_by(PointerButton::Secondary) {
+                        edge.left.uplift = 0.0;
+                        edge.right.uplift = 0.0;
+                        edge.top.uplift = 0.0;
+                        edge.bottom.uplift = 0.0;
+                        blend_changed = true;
+                    }
+                });
+
+                // Individual edge overrides
+                egui::CollapsingHeader::new("Per Edge")
+                    .id_salt("edge_blend_per_edge")
+                    .show(ui, |ui| {
+                        for (name, region) in [
+                            ("Left", &mut edge.left),
+                            ("Right", &mut edge.right),
+                            ("Top", &mut edge.top),
+                            ("Bottom", &mut edge.bottom),
+                        ] {
+                            if Self::render_edge_blend_region(ui, name, region) {
+                                blend_changed = true;
+                            }
+                        }
+                    });
             }
 
             if blend_changed {
@@ -2431,6 +2617,165 @@ impl AdvancedOutputWindow {
         }
     }
 
+    /// Render the controls for a single edge blend region
+    ///
+    /// Returns true if the region was modified.
+    fn render_edge_blend_region(ui: &mut egui::Ui, name: &str, region: &mut EdgeBlendRegion) -> bool {
+        let mut changed = false;
+        ui.horizontal(|ui| {
+            changed |= ui.checkbox(&mut region.enabled, name).changed();
+            if !region.enabled {
+                return;
+            }
+            egui::ComboBox::from_id_salt(("edge_blend_region_curve", name))
+                .width(90.0)
+                .selected_text(region.curve.display_name())
+                .show_ui(ui, |ui| {
+                    for option in EdgeBlendCurve::ALL {
+                        changed |= ui
+                            .selectable_value(&mut region.curve, option, option.display_name())
+                            .changed();
+                    }
+                });
+        });
+        if !region.enabled {
+            return changed;
+        }
+
+        ui.indent(("edge_blend_region", name), |ui| {
+            egui::Grid::new(("edge_blend_region_grid", name)).num_columns(2).show(ui, |ui| {
+                ui.label("Width:");
+                changed |= ui.add(egui::Slider::new(&mut region.width, 0.0..=0.5).max_decimals(2)).changed();
+                ui.end_row();
+
+                if region.curve == EdgeBlendCurve::Gamma {
+                    ui.label("Gamma:");
+                    changed |= ui.add(egui::Slider::new(&mut region.gamma, 0.1..=4.0).max_decimals(1)).changed();
+                    ui.end_row();
+                }
+
+                ui.label("RGB Gamma:");
+                ui.horizontal(|ui| {
+                    for (value, prefix) in region.channel_gamma.iter_mut().zip(["R:", "G:", "B:"]) {
+                        changed |= ui
+                            .add(egui::DragValue::new(value).range(0.25..=4.0).speed(0.01).prefix(prefix))
+                            .changed();
+                    }
+                });
+                ui.end_row();
+
+                ui.label("Center:");
+                changed |= ui.add(egui::Slider::new(&mut region.center, -0.4..=0.4).max_decimals(2)).changed();
+                ui.end_row();
+
+                ui.label("Luminance:");
+                changed |= ui.add(egui::Slider::new(&mut region.black_level, 0.0..=0.5).max_decimals(2)).changed();
+                ui.end_row();
+
+                ui.label("Uplift:");
+                changed |= ui.add(egui::Slider::new(&mut region.uplift, 0.0..=0.5).max_decimals(3)).changed();
+                ui.end_row();
+            });
+        });
+        changed
+    }
+
+    /// Render the 3D LUT controls (load/remove, interpolation, intensity, embedding)
+    ///
+    /// Returns true if the LUT was modified.
+    fn render_lut_controls(ui: &mut egui::Ui, id: &str, lut: &mut Option<OutputLut>) -> bool {
+        let mut changed = false;
+        ui.horizontal(|ui| {
+            ui.label("3D LUT");
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                if lut.is_some() && ui.small_button("Remove").on_hover_text("Remove LUT").clicked() {
+                    *lut = None;
+                    changed = true;
+                }
+            });
+        });
+        ui.add_space(4.0);
+
+        let Some(config) = lut else {
+            if ui
+                .small_button("Load...")
+                .on_hover_text("Load a 3D LUT (.cube or .3dl) to match this output to other projectors")
+                .clicked()
+            {
+                if let Some(path) = rfd::FileDialog::new()
+                    .set_title("Load 3D LUT")
+                    .add_filter("3D LUT", &["cube", "3dl"])
+                    .pick_file()
+                {
+                    let loaded = OutputLut::new(path.to_string_lossy());
+                    match loaded.load() {
+                        Ok(_) => {
+                            *lut = Some(loaded);
+                            changed = true;
+                        }
+                        Err(e) => tracing::warn!("Failed to load LUT '{}': {}", path.display(), e),
+                    }
+                }
+            }
+            return changed;
+        };
+
+        ui.horizontal(|ui| {
+            changed |= ui.checkbox(&mut config.enabled, "Enabled").changed();
+            egui::ComboBox::from_id_salt((id, "interpolation"))
+                .width(90.0)
+                .selected_text(config.interpolation.display_name())
+                .show_ui(ui, |ui| {
+                    for option in [LutInterpolation::Trilinear, LutInterpolation::Tetrahedral] {
+                        changed |= ui
+                            .selectable_value(&mut config.interpolation, option, option.display_name())
+                            .changed();
+                    }
+                });
+        });
+
+        ui.horizontal(|ui| {
+            ui.label("Intensity:");
+            let response = ui.add(egui::Slider::new(&mut config.intensity, 0.0..=1.0).max_decimals(2));
+            if response.changed() {
+                changed = true;
+            }
+            // Right-click instantly resets to 1
+            if response.clicked_by(PointerButton::Secondary) {
+                config.intensity = 1.0;
+                changed = true;
+            }
+        });
+
+        ui.horizontal(|ui| {
+            let label = ui.label(egui::RichText::new(config.display_name()).small());
+            if let Some(path) = &config.path {
+                label.on_hover_text(path);
+            }
+            if config.embedded.is_some() {
+                if config.path.is_some()
+                    && ui
+                        .small_button("Reference")
+                        .on_hover_text("Drop the embedded copy and load from the file")
+                        .clicked()
+                {
+                    config.embedded = None;
+                    changed = true;
+                }
+            } else if ui
+                .small_button("Embed")
+                .on_hover_text("Store the LUT in the preset so it no longer depends on the file")
+                .clicked()
+            {
+                match config.embed() {
+                    Ok(()) => changed = true,
+                    Err(e) => tracing::warn!("Failed to embed LUT '{}': {}", config.display_name(), e),
+                }
+            }
+        });
+        changed
+    }
+
     /// Render the Output Transformation tab (existing functionality)
     fn render_output_transformation_tab(
         &mut self,
@@ -2670,53 +3015,65 @@ impl AdvancedOutputWindow {
                                         let grid_color = egui::Color32::from_rgba_unmultiplied(255, 200, 100, 150);
                                         let point_color = egui::Color32::from_rgb(255, 200, 100);
 
-                                        // Draw grid lines (horizontal)
-                                        for row in 0..mesh.rows {
-                                            for col in 0..(mesh.columns - 1) {
-                                                if let (Some(p1), Some(p2)) = (mesh.get_point(col, row), mesh.get_point(col + 1, row)) {
-                                                    let pos1 = slice_rect.min + egui::vec2(
-                                                        p1.position[0] * slice_rect.width(),
-                                                        p1.position[1] * slice_rect.height(),
-                                                    );
-                                                    let pos2 = slice_rect.min + egui::vec2(
-                                                        p2.position[0] * slice_rect.width(),
-                                                        p2.position[1] * slice_rect.height(),
-                                                    );
-                                                    painter.line_segment(
-                                                        [pos1, pos2],
-                                                        egui::Stroke::new(1.0, grid_color),
-                                                    );
-                                                }
+                                        // Draw grid lines through the interpolated surface. Spline meshes are
+                                        // subdivided adaptively so curved lines stay smooth at any zoom.
+                                        let to_screen = |p: [f32; 2]| {
+                                            slice_rect.min + egui::vec2(
+                                                p[0] * slice_rect.width(),
+                                                p[1] * slice_rect.height(),
+                                            )
+                                        };
+                                        let curved = mesh.interpolation == WarpInterpolation::CatmullRom;
+                                        let column_knots = mesh.column_knots();
+                                        let row_knots = mesh.row_knots();
+                                        let draw_segment = |a: [f32; 2], b: [f32; 2]| {
+                                            let start = to_screen(mesh.sample(a));
+                                            let end = to_screen(mesh.sample(b));
+                                            let steps = if curved {
+                                                ((start.distance(end) / 8.0).ceil() as usize).clamp(1, 32)
+                                            } else {
+                                                1
+                                            };
+                                            let mut prev = start;
+                                            for step in 1..=steps {
+                                                let t = step as f32 / steps as f32;
+                                                let next = to_screen(mesh.sample([
+                                                    a[0] + (b[0] - a[0]) * t,
+                                                    a[1] + (b[1] - a[1]) * t,
+                                                ]));
+                                                painter.line_segment(
+                                                    [prev, next],
+                                                    egui::Stroke::new(1.0, grid_color),
+                                                );
+                                                prev = next;
+                                            }
+                                        };
+
+                                        // Horizontal lines
+                                        for &v in &row_knots {
+                                            for pair in column_knots.windows(2) {
+                                                draw_segment([pair[0], v], [pair[1], v]);
                                             }
                                         }
 
-                                        // Draw grid lines (vertical)
-                                        for col in 0..mesh.columns {
-                                            for row in 0..(mesh.rows - 1) {
-                                                if let (Some(p1), Some(p2)) = (mesh.get_point(col, row), mesh.get_point(col, row + 1)) {
-                                                    let pos1 = slice_rect.min + egui::vec2(
-                                                        p1.position[0] * slice_rect.width(),
-                                                        p1.position[1] * slice_rect.height(),
-                                                    );
-                                                    let pos2 = slice_rect.min + egui::vec2(
-                                                        p2.position[0] * slice_rect.width(),
-                                                        p2.position[1] * slice_rect.height(),
-                                                    );
-                                                    painter.line_segment(
-                                                        [pos1, pos2],
-                                                        egui::Stroke::new(1.0, grid_color),
-                                                    );
-                                                }
+                                        // Vertical lines
+                                        for &u in &column_knots {
+                                            for pair in row_knots.windows(2) {
+                                                draw_segment([u, pair[0]], [u, pair[1]]);
                                             }
                                         }
 
-                                        // Draw control points
-                                        for point in &mesh.points {
-                                            let pos = slice_rect.min + egui::vec2(
-                                                point.position[0] * slice_rect.width(),
-                                                point.position[1] * slice_rect.height(),
-                                            );
-                                            painter.circle_filled(pos, 3.0, point_color);
+                                        // Draw control points (selected point highlighted)
+                                        for col in 0..mesh.columns {
+                                            for row in 0..mesh.rows {
+                                                if let Some(point) = mesh.get_point(col, row) {
+                                                    let pos = to_screen(point.position);
+                                                    if self.selected_warp_point == Some((col, row)) {
+                                                        painter.circle_filled(pos, 5.0, egui::Color32::WHITE);
+                                                    }
+                                                    painter.circle_filled(pos, 3.0, point_color);
+                                                }
+                                            }
                                         }
                                     }
                                 }
@@ -3005,6 +3362,9 @@ impl AdvancedOutputWindow {
                                             }
 
                                             self.dragging_warp_point = best_point;
+                                            if best_point.is_some() {
+                                                self.selected_warp_point = best_point;
+                                            }
                                         }
                                     }
 
@@ -3021,6 +3381,9 @@ impl AdvancedOutputWindow {
                                                 let mut updated_slice = slice.clone();
                                                 if let Some(mesh) = &mut updated_slice.output.mesh {
                                                     mesh.set_point_position(col, row, norm_x, norm_y);
+                                                    if self.warp_mirror_x || self.warp_mirror_y {
+                                                        mesh.mirror_point(col, row, self.warp_mirror_x, self.warp_mirror_y);
+                                                    }
                                                     actions.push(AdvancedOutputAction::UpdateSlice {
                                                         screen_id,
                                                         slice_id,
@@ -3064,6 +3427,9 @@ impl AdvancedOutputWindow {
                                                 if let Some(mesh) = &mut updated_slice.output.mesh {
                                                     if let Some(point) = mesh.get_point(col, row) {
                                                         mesh.set_point_position(col, row, point.uv[0], point.uv[1]);
+                                                        if self.warp_mirror_x || self.warp_mirror_y {
+                                                            mesh.mirror_point(col, row, self.warp_mirror_x, self.warp_mirror_y);
+                                                        }
                                                         actions.push(AdvancedOutputAction::UpdateSlice {
                                                             screen_id,
                                                             slice_id,
@@ -3546,6 +3912,92 @@ impl AdvancedOutputWindow {
                     });
             });
 
+            ui.horizontal(|ui| {
+                ui.label("Interpolation:");
+                if let Some(mesh) = &mut slice_copy.output.mesh {
+                    egui::ComboBox::from_id_salt("mesh_interpolation")
+                        .selected_text(mesh.interpolation.display_name())
+                        .show_ui(ui, |ui| {
+                            for mode in [WarpInterpolation::Linear, WarpInterpolation::CatmullRom] {
+                                if ui.selectable_value(&mut mesh.interpolation, mode, mode.display_name()).changed() {
+                                    changed = true;
+                                }
+                            }
+                        });
+                }
+            });
+
+            ui.horizontal(|ui| {
+                ui.label("Symmetry:");
+                ui.toggle_value(&mut self.warp_mirror_x, "Mirror X")
+                    .on_hover_text("Mirror point edits across the vertical center axis");
+                ui.toggle_value(&mut self.warp_mirror_y, "Mirror Y")
+                    .on_hover_text("Mirror point edits across the horizontal center axis");
+            });
+
+            // Operations on the last picked point, its row and its column
+            let selected = self.selected_warp_point.filter(|&(col, row)| {
+                slice_copy.output.mesh.as_ref().is_some_and(|m| col < m.columns && row < m.rows)
+            });
+            if let (Some((col, row)), Some(mesh)) = (selected, &mut slice_copy.output.mesh) {
+                ui.add_space(4.0);
+                ui.label(format!("Point: column {}, row {}", col, row));
+                let interior_col = col > 0 && col + 1 < mesh.columns;
+                let interior_row = row > 0 && row + 1 < mesh.rows;
+
+                ui.horizontal(|ui| {
+                    if ui.add_enabled(col + 1 < mesh.columns, egui::Button::new("+ Column"))
+                        .on_hover_text("Insert a column after this point, keeping the deformation")
+                        .clicked()
+                    {
+                        mesh.insert_column(col);
+                        changed = true;
+                    }
+                    if ui.add_enabled(row + 1 < mesh.rows, egui::Button::new("+ Row"))
+                        .on_hover_text("Insert a row below this point, keeping the deformation")
+                        .clicked()
+                    {
+                        mesh.insert_row(row);
+                        changed = true;
+                    }
+                    if ui.add_enabled(interior_col, egui::Button::new("- Column"))
+                        .on_hover_text("Remove this column (border columns are kept)")
+                        .clicked()
+                        && mesh.remove_column(col)
+                    {
+                        self.selected_warp_point = None;
+                        changed = true;
+                    }
+                    if ui.add_enabled(interior_row, egui::Button::new("- Row"))
+                        .on_hover_text("Remove this row (border rows are kept)")
+                        .clicked()
+                        && mesh.remove_row(row)
+                    {
+                        self.selected_warp_point = None;
+                        changed = true;
+                    }
+                });
+
+                ui.horizontal(|ui| {
+                    ui.label("Reset:");
+                    if ui.small_button("Point").clicked() {
+                        mesh.reset_region(col..=col, row..=row);
+                        changed = true;
+                    }
+                    if ui.small_button("Row").clicked() {
+                        mesh.reset_region(0..=mesh.columns - 1, row..=row);
+                        changed = true;
+                    }
+                    if ui.small_button("Column").clicked() {
+                        mesh.reset_region(col..=col, 0..=mesh.rows - 1);
+                        changed = true;
+                    }
+                });
+            } else {
+                ui.add_space(4.0);
+                ui.colored_label(egui::Color32::GRAY, "Click a point to edit rows/columns");
+            }
+
             // Show grid info
             if let Some(mesh) = &slice_copy.output.mesh {
                 ui.add_space(4.0);
@@ -3565,6 +4017,118 @@ impl AdvancedOutputWindow {
         ui.separator();
         ui.add_space(4.0);
 
+        // UV Map section (dense per-pixel warp, overrides perspective and mesh)
+        ui.horizontal(|ui| {
+            ui.label("UV Map");
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                if slice_copy.output.uv_map.is_some() {
+                    if ui.small_button("Remove").on_hover_text("Remove UV map").clicked() {
+                        slice_copy.output.uv_map = None;
+                        changed = true;
+                    }
+                }
+            });
+        });
+        ui.add_space(4.0);
+
+        if slice_copy.output.uv_map.is_none() {
+            if ui
+                .small_button("Load...")
+                .on_hover_text("Load a UV lookup (EXR, 16-bit PNG, MPCDI .pfm or calibrator .pmuv)")
+                .clicked()
+            {
+                if let Some(path) = rfd::FileDialog::new()
+                    .set_title("Load UV Map")
+                    .add_filter("UV Map", &["exr", "png", "pmuv", "pfm"])
+                    .pick_file()
+                {
+                    slice_copy.output.uv_map = Some(UvWarpMap::new(path.to_string_lossy()));
+                    changed = true;
+                }
+            }
+        } else if let Some(uv_map) = &mut slice_copy.output.uv_map {
+            ui.horizontal(|ui| {
+                if ui.checkbox(&mut uv_map.enabled, "Enabled").changed() {
+                    changed = true;
+                }
+                if ui
+                    .checkbox(&mut uv_map.invert_v, "Invert V")
+                    .on_hover_text("V increases upward (STMap convention)")
+                    .changed()
+                {
+                    changed = true;
+                }
+            });
+            if uv_map.path.is_empty() {
+                ui.colored_label(egui::Color32::GRAY, "(no lookup, blend images only)");
+            } else {
+                ui.label(egui::RichText::new(uv_map.file_name()).small())
+                    .on_hover_text(&uv_map.path);
+            }
+
+            ui.horizontal(|ui| {
+                ui.label("Attenuation:");
+                match uv_map.attenuation_file_name() {
+                    Some(name) => {
+                        ui.label(egui::RichText::new(name).small());
+                        if ui.small_button("×").on_hover_text("Remove attenuation").clicked() {
+                            uv_map.attenuation_path = None;
+                            changed = true;
+                        }
+                    }
+                    None => {
+                        if ui
+                            .small_button("Load...")
+                            .on_hover_text("Per-pixel blend/luminance gain multiplied into the output")
+                            .clicked()
+                        {
+                            if let Some(path) = rfd::FileDialog::new()
+                                .set_title("Load Attenuation")
+                                .add_filter("Image", &["png", "exr", "tif", "tiff"])
+                                .pick_file()
+                            {
+                                uv_map.attenuation_path = Some(path.to_string_lossy().into_owned());
+                                changed = true;
+                            }
+                        }
+                    }
+                }
+            });
+
+            ui.horizontal(|ui| {
+                ui.label("Black level:");
+                match uv_map.black_level_file_name() {
+                    Some(name) => {
+                        ui.label(egui::RichText::new(name).small());
+                        if ui.small_button("×").on_hover_text("Remove black level").clicked() {
+                            uv_map.black_level_path = None;
+                            changed = true;
+                        }
+                    }
+                    None => {
+                        if ui
+                            .small_button("Load...")
+                            .on_hover_text("Per-pixel black level lift (MPCDI beta map)")
+                            .clicked()
+                        {
+                            if let Some(path) = rfd::FileDialog::new()
+                                .set_title("Load Black Level")
+                                .add_filter("Image", &["png", "exr", "tif", "tiff"])
+                                .pick_file()
+                            {
+                                uv_map.black_level_path = Some(path.to_string_lossy().into_owned());
+                                changed = true;
+                            }
+                        }
+                    }
+                }
+            });
+        }
+
+        ui.add_space(8.0);
+        ui.separator();
+        ui.add_space(4.0);
+
         // Mask section
         ui.horizontal(|ui| {
             ui.label("Mask");
@@ -3893,6 +4457,15 @@ impl AdvancedOutputWindow {
         ui.separator();
         ui.add_space(4.0);
 
+        // 3D LUT section (applied after color correction, before edge blending)
+        if Self::render_lut_controls(ui, "slice_lut", &mut slice_copy.lut) {
+            changed = true;
+        }
+
+        ui.add_space(8.0);
+        ui.separator();
+        ui.add_space(4.0);
+
         // Enabled toggle
         let mut enabled = slice_copy.enabled;
         if ui.checkbox(&mut enabled, "Enabled").changed() {
@@ -70,6 +70,11 @@ pub enum ClipGridAction {
         layer_id: u32,
         transition: crate::compositor::ClipTransition,
     },
+    /// User wants to set the trigger quantization for a layer (None = global)
+    SetLayerTriggerQuantize {
+        layer_id: u32,
+        quantize: Option<crate::compositor::TriggerQuantize>,
+    },
     /// Add a new layer
     AddLayer,
     /// Delete a layer
@@ -413,6 +418,7 @@ impl ClipGridPanel {
                     
                     // Right-click context menu directly on the label
                     let current_transition = layer.transition;
+                    let current_quantize = layer.trigger_quantize;
                     response.context_menu(|ui| {
                         if has_active {
                             if ui.button("⏹ Stop Clip").clicked() {
@@ -451,6 +457,30 @@ impl ClipGridPanel {
                                 ui.close_menu();
                             }
                         });
+
+                        // Quantize submenu
+                        let quantize_label = current_quantize
+                            .map(|q| q.name())
+                            .unwrap_or_else(|| "Global".to_string());
+                        ui.menu_button(format!("Quantize: {}", quantize_label), |ui| {
+                            if ui.selectable_label(current_quantize.is_none(), "Global").clicked() {
+                                actions.push(ClipGridAction::SetLayerTriggerQuantize {
+                                    layer_id,
+                                    quantize: None,
+                                });
+                                ui.close_menu();
+                            }
+                            for quantize in crate::compositor::TriggerQuantize::PRESETS {
+                                let selected = current_quantize == Some(quantize);
+                                if ui.selectable_label(selected, quantize.name()).clicked() {
+                                    actions.push(ClipGridAction::SetLayerTriggerQuantize {
+                                        layer_id,
+                                        quantize: Some(quantize),
+                                    });
+                                    ui.close_menu();
+                                }
+                            }
+                        });
                         
                         ui.separator();
                         if ui.button("🗑 Delete Layer").clicked() {
@@ -497,7 +527,8 @@ impl ClipGridPanel {
             for slot in 0..max_clips {
                 let cell = layer.get_clip(slot);
                 let is_active = layer.active_clip == Some(slot);
-                let cell_actions = self.render_cell(ui, layer.id, slot, cell, is_active, layer_has_active_clip, is_dragging, thumbnail_cache);
+                let is_pending = layer.pending_clip == Some(slot);
+                let cell_actions = self.render_cell(ui, layer.id, slot, cell, is_active, is_pending, layer_has_active_clip, is_dragging, thumbnail_cache);
                 actions.extend(cell_actions);
             }
         });
@@ -515,6 +546,7 @@ impl ClipGridPanel {
         slot: usize,
         cell: Option<&ClipCell>,
         is_active: bool,
+        is_pending: bool,
         layer_has_active_clip: bool,
         is_dragging: bool,
         thumbnail_cache: &mut ThumbnailCache,
@@ -523,6 +555,15 @@ impl ClipGridPanel {
 
         let size = egui::vec2(CELL_SIZE, CELL_SIZE);
 
+        // Pending (quantized) clips blink until their trigger fires
+        let blink_on = if is_pending {
+            ui.ctx().request_repaint();
+            (ui.input(|i| i.time) * 4.0) as i64 % 2 == 0
+        } else {
+            false
+        };
+        let pending_color = egui::Color32::from_rgb(200, 160, 40); // Amber for pending
+
         // Check if this cell is being hovered during drag (for drop indicator)
         let is_drag_hover = self.drag_hover_cell == Some((layer_id, slot));
 
@@ -534,7 +575,9 @@ impl ClipGridPanel {
             let label = "⬇".to_string(); // Drop indicator
             (bg, text, label, None)
         } else if let Some(clip) = cell {
-            let bg = if is_active {
+            let bg = if is_pending && blink_on {
+                pending_color
+            } else if is_active {
                 egui::Color32::from_rgb(40, 160, 80) // Green for active
             } else {
                 egui::Color32::from_rgb(55, 55, 70) // Dark blue-gray for clips
@@ -621,8 +664,15 @@ impl ClipGridPanel {
 
                     draw_texture(ui, texture_id, image_rect);
 
-                    // Draw active indicator border
-                    if is_active {
+                    // Draw active/pending indicator border
+                    if is_pending && blink_on {
+                        ui.painter().rect_stroke(
+                            rect,
+                            3.0,
+                            egui::Stroke::new(3.0, pending_color),
+                            egui::StrokeKind::Outside,
+                        );
+                    } else if is_active {
                         ui.painter().rect_stroke(
                             rect,
                             3.0,
@@ -733,7 +783,13 @@ impl ClipGridPanel {
 
         // Tooltip with full name and source info
         if let Some(clip) = cell {
-            let active_text = if is_active { " (playing)" } else { "" };
+            let active_text = if is_pending {
+                " (waiting for beat)"
+            } else if is_active {
+                " (playing)"
+            } else {
+                ""
+            };
             let source_info = match &clip.source {
                 crate::compositor::ClipSource::File { path } => format!("📁 {}", path.display()),
                 crate::compositor::ClipSource::Omt { address, .. } => format!("📡 OMT: {}", address),
@@ -0,0 +1,438 @@
+//! Curve Editor Window
+//!
+//! A floating window for editing keyframed automation curves. Shows the
+//! curve over its loop length with a playhead; keyframes can be dragged,
+//! added with a double-click and removed with a right-click.
+
+use crate::compositor::Environment;
+use crate::effects::{
+    AutomationSource, BpmClock, CurveSource, CurveTimeBase, Keyframe, KeyframeEasing, Parameter,
+};
+use crate::ui::properties_panel::{EffectContext, PropertiesAction};
+
+/// Keyframe handle radius in points
+const HANDLE_RADIUS: f32 = 4.0;
+/// Pick distance for keyframe handles in points
+const PICK_RADIUS: f32 = 8.0;
+/// Curve color (matches the Curve modulation gear in the properties panel)
+const CURVE_COLOR: egui::Color32 = egui::Color32::from_rgb(170, 130, 255);
+
+/// A curve-automated effect parameter
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CurveTarget {
+    /// Which effect stack the effect lives in
+    pub context: EffectContext,
+    /// Effect instance ID
+    pub effect_id: u32,
+    /// Parameter name
+    pub param_name: String,
+}
+
+impl CurveTarget {
+    /// Create a new curve target
+    pub fn new(context: EffectContext, effect_id: u32, param_name: &str) -> Self {
+        Self {
+            context,
+            effect_id,
+            param_name: param_name.to_string(),
+        }
+    }
+
+    /// Layer owning the target's effect stack (None for environment effects)
+    pub fn layer_id(&self) -> Option<u32> {
+        match self.context {
+            EffectContext::Layer { layer_id } | EffectContext::Clip { layer_id, .. } => Some(layer_id),
+            EffectContext::Environment => None,
+        }
+    }
+
+    /// Look up the target parameter
+    pub fn parameter<'a>(&self, environment: &'a Environment) -> Option<&'a Parameter> {
+        let stack = match self.context {
+            EffectContext::Layer { layer_id } => &environment.get_layer(layer_id)?.effects,
+            EffectContext::Clip { layer_id, slot } => &environment.get_layer(layer_id)?.get_clip(slot)?.effects,
+            EffectContext::Environment => environment.effects(),
+        };
+        stack.get(self.effect_id)?.get_parameter(&self.param_name)
+    }
+
+    /// Look up the target parameter for editing
+    pub fn parameter_mut<'a>(&self, environment: &'a mut Environment) -> Option<&'a mut Parameter> {
+        let stack = match self.context {
+            EffectContext::Layer { layer_id } => &mut environment.get_layer_mut(layer_id)?.effects,
+            EffectContext::Clip { layer_id, slot } => &mut environment.get_layer_mut(layer_id)?.get_clip_mut(slot)?.effects,
+            EffectContext::Environment => environment.effects_mut(),
+        };
+        stack.get_mut(self.effect_id)?.get_parameter_mut(&self.param_name)
+    }
+
+    /// Build the action that replaces the target's automation
+    pub fn automation_action(&self, automation: Option<AutomationSource>) -> PropertiesAction {
+        let effect_id = self.effect_id;
+        let param_name = self.param_name.clone();
+        match self.context {
+            EffectContext::Layer { layer_id } => {
+                PropertiesAction::SetLayerEffectParameterAutomation { layer_id, effect_id, param_name, automation }
+            }
+            EffectContext::Clip { layer_id, slot } => {
+                PropertiesAction::SetClipEffectParameterAutomation { layer_id, slot, effect_id, param_name, automation }
+            }
+            EffectContext::Environment => {
+                PropertiesAction::SetEnvironmentEffectParameterAutomation { effect_id, param_name, automation }
+            }
+        }
+    }
+}
+
+/// Curve editor window state
+pub struct CurveEditorWindow {
+    /// Whether the window is open
+    pub open: bool,
+    /// Parameter whose curve is being edited
+    target: Option<CurveTarget>,
+    /// Selected keyframe index
+    selected: Option<usize>,
+    /// Keyframe index being dragged
+    dragging: Option<usize>,
+}
+
+impl Default for CurveEditorWindow {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CurveEditorWindow {
+    /// Create a new curve editor window (closed by default)
+    pub fn new() -> Self {
+        Self {
+            open: false,
+            target: None,
+            selected: None,
+            dragging: None,
+        }
+    }
+
+    /// Open the editor for a parameter
+    pub fn open_for(&mut self, target: CurveTarget) {
+        if self.target.as_ref() != Some(&target) {
+            self.selected = None;
+            self.dragging = None;
+        }
+        self.target = Some(target);
+        self.open = true;
+    }
+
+    /// Parameter currently shown in the editor
+    pub fn target(&self) -> Option<&CurveTarget> {
+        self.target.as_ref()
+    }
+
+    /// Render the curve editor window
+    ///
+    /// `clip_time` is the playback position of the target's clip in seconds
+    /// (or the effect time when there is no clip position).
+    ///
+    /// Returns a list of actions to be processed by the app.
+    pub fn render(
+        &mut self,
+        ctx: &egui::Context,
+        environment: &Environment,
+        bpm_clock: &BpmClock,
+        clip_time: f32,
+    ) -> Vec<PropertiesAction> {
+        let mut actions = Vec::new();
+
+        if !self.open {
+            return actions;
+        }
+        let Some(target) = self.target.clone() else {
+            self.open = false;
+            return actions;
+        };
+
+        let mut open = self.open;
+        egui::Window::new("Curve Editor")
+            .id(egui::Id::new("curve_editor_window"))
+            .open(&mut open)
+            .default_size([520.0, 300.0])
+            .resizable(true)
+            .collapsible(true)
+            .show(ctx, |ui| {
+                let curve = match target.parameter(environment).map(|p| (p, &p.automation)) {
+                    Some((param, Some(AutomationSource::Curve(curve)))) => {
+                        ui.label(egui::RichText::new(&param.meta.label).strong());
+                        curve
+                    }
+                    _ => {
+                        ui.label(
+                            egui::RichText::new("The parameter no longer has curve automation.")
+                                .color(egui::Color32::from_gray(140)),
+                        );
+                        return;
+                    }
+                };
+
+                let mut edited = curve.clone();
+                let mut changed = self.render_header(ui, &target, &mut edited, &mut actions);
+                ui.separator();
+                let playhead = edited.playhead(clip_time, bpm_clock);
+                changed |= self.render_curve(ui, &mut edited, playhead);
+                ui.separator();
+                changed |= self.render_keyframe_details(ui, &mut edited);
+
+                if changed {
+                    actions.push(target.automation_action(Some(AutomationSource::Curve(edited))));
+                }
+            });
+        self.open = open;
+
+        actions
+    }
+
+    /// Render arm button, time base and length controls
+    fn render_header(
+        &mut self,
+        ui: &mut egui::Ui,
+        target: &CurveTarget,
+        curve: &mut CurveSource,
+        actions: &mut Vec<PropertiesAction>,
+    ) -> bool {
+        let mut changed = false;
+        ui.horizontal(|ui| {
+            let arm_text = if curve.armed {
+                egui::RichText::new("Recording").color(egui::Color32::from_rgb(255, 70, 70))
+            } else {
+                egui::RichText::new("Arm")
+            };
+            if ui.selectable_label(curve.armed, arm_text)
+                .on_hover_text("Record parameter moves into the curve while the clip plays")
+                .clicked()
+            {
+                actions.push(PropertiesAction::SetCurveRecordArm { target: target.clone(), armed: !curve.armed });
+            }
+
+            ui.separator();
+
+            egui::ComboBox::from_id_salt("curve_editor_time_base")
+                .selected_text(curve.time_base.name())
+                .width(80.0)
+                .show_ui(ui, |ui| {
+                    for base in CurveTimeBase::all() {
+                        if ui.selectable_value(&mut curve.time_base, *base, base.name()).changed() {
+                            changed = true;
+                        }
+                    }
+                });
+
+            ui.label("Length:");
+            if ui
+                .add(
+                    egui::DragValue::new(&mut curve.length)
+                        .speed(0.1)
+                        .range(0.25..=600.0)
+                        .suffix(curve.time_base.unit()),
+                )
+                .changed()
+            {
+                changed = true;
+            }
+
+            ui.separator();
+
+            if ui.button("Clear").on_hover_text("Remove all keyframes").clicked() {
+                curve.keyframes.clear();
+                self.selected = None;
+                changed = true;
+            }
+        });
+        changed
+    }
+
+    /// Render the curve graph and handle keyframe editing
+    fn render_curve(&mut self, ui: &mut egui::Ui, curve: &mut CurveSource, playhead: f32) -> bool {
+        let mut changed = false;
+        let height = (ui.available_height() - 60.0).max(120.0);
+        let (response, painter) =
+            ui.allocate_painter(egui::vec2(ui.available_width(), height), egui::Sense::click_and_drag());
+        let rect = response.rect.shrink(HANDLE_RADIUS + 2.0);
+        let length = curve.length.max(f32::EPSILON);
+
+        let to_screen = |position: f32, value: f32| {
+            egui::pos2(
+                rect.left() + position / length * rect.width(),
+                rect.bottom() - value * rect.height(),
+            )
+        };
+        let from_screen = |pos: egui::Pos2| {
+            (
+                ((pos.x - rect.left()) / rect.width() * length).clamp(0.0, length),
+                ((rect.bottom() - pos.y) / rect.height()).clamp(0.0, 1.0),
+            )
+        };
+
+        // Background and grid (one line per second or bar)
+        painter.rect_filled(response.rect, 2.0, egui::Color32::from_gray(24));
+        if length <= 128.0 {
+            let grid_stroke = egui::Stroke::new(1.0, egui::Color32::from_gray(45));
+            for unit in 1..length.ceil() as i32 {
+                let x = to_screen(unit as f32, 0.0).x;
+                painter.line_segment([egui::pos2(x, rect.top()), egui::pos2(x, rect.bottom())], grid_stroke);
+            }
+        }
+        let mid_y = to_screen(0.0, 0.5).y;
+        painter.line_segment(
+            [egui::pos2(rect.left(), mid_y), egui::pos2(rect.right(), mid_y)],
+            egui::Stroke::new(1.0, egui::Color32::from_gray(40)),
+        );
+
+        // Curve
+        if !curve.keyframes.is_empty() {
+            let samples = (rect.width() as usize / 2).max(2);
+            let points: Vec<egui::Pos2> = (0..=samples)
+                .map(|i| {
+                    let position = i as f32 / samples as f32 * length;
+                    // Evaluate just inside the loop so the last sample doesn't wrap to the start
+                    let value = curve.evaluate(position.min(length - 1e-4)).unwrap_or(0.0);
+                    to_screen(position, value)
+                })
+                .collect();
+            painter.add(egui::Shape::line(points, egui::Stroke::new(1.5, CURVE_COLOR)));
+        }
+
+        // Playhead
+        let playhead_x = to_screen(playhead, 0.0).x;
+        let playhead_color = if curve.armed {
+            egui::Color32::from_rgb(255, 70, 70)
+        } else {
+            egui::Color32::from_gray(200)
+        };
+        painter.line_segment(
+            [egui::pos2(playhead_x, response.rect.top()), egui::pos2(playhead_x, response.rect.bottom())],
+            egui::Stroke::new(1.0, playhead_color),
+        );
+
+        // Keyframe handles
+        for (index, keyframe) in curve.keyframes.iter().enumerate() {
+            let center = to_screen(keyframe.position, keyframe.value);
+            if self.selected == Some(index) {
+                painter.circle_filled(center, HANDLE_RADIUS + 1.0, egui::Color32::WHITE);
+            } else {
+                painter.circle_filled(center, HANDLE_RADIUS, CURVE_COLOR);
+            }
+        }
+
+        // Find the keyframe under the pointer
+        let hovered = response.hover_pos().and_then(|pos| {
+            curve
+                .keyframes
+                .iter()
+                .enumerate()
+                .map(|(index, k)| (index, to_screen(k.position, k.value).distance(pos)))
+                .filter(|(_, distance)| *distance <= PICK_RADIUS)
+                .min_by(|a, b| a.1.total_cmp(&b.1))
+                .map(|(index, _)| index)
+        });
+
+        if response.drag_started() {
+            self.dragging = hovered;
+            self.selected = hovered;
+        }
+        if let (Some(index), Some(pos)) = (self.dragging, response.interact_pointer_pos()) {
+            if response.dragged() {
+                let (position, value) = from_screen(pos);
+                let new_index = curve.move_keyframe(index, position, value);
+                self.dragging = Some(new_index);
+                self.selected = Some(new_index);
+                changed = true;
+            }
+        }
+        if response.drag_stopped() {
+            self.dragging = None;
+        }
+
+        if response.clicked() {
+            self.selected = hovered;
+        }
+        if response.double_clicked() && hovered.is_none() {
+            if let Some(pos) = response.interact_pointer_pos() {
+                let (position, value) = from_screen(pos);
+                self.selected = Some(curve.insert_keyframe(Keyframe::new(position, value)));
+                changed = true;
+            }
+        }
+        if response.secondary_clicked() {
+            if let Some(index) = hovered {
+                curve.keyframes.remove(index);
+                self.selected = None;
+                changed = true;
+            }
+        }
+
+        response.on_hover_text("Double-click to add a keyframe, drag to move, right-click to delete");
+        changed
+    }
+
+    /// Render position, value and easing of the selected keyframe
+    fn render_keyframe_details(&mut self, ui: &mut egui::Ui, curve: &mut CurveSource) -> bool {
+        let mut changed = false;
+        let Some(index) = self.selected.filter(|&i| i < curve.keyframes.len()) else {
+            ui.label(
+                egui::RichText::new(format!("{} keyframes", curve.keyframes.len()))
+                    .small()
+                    .color(egui::Color32::from_gray(140)),
+            );
+            return false;
+        };
+
+        ui.horizontal(|ui| {
+            let unit = curve.time_base.unit();
+            let mut keyframe = curve.keyframes[index];
+
+            ui.label("Position:");
+            let position_changed = ui
+                .add(egui::DragValue::new(&mut keyframe.position).speed(0.01).range(0.0..=curve.length).suffix(unit))
+                .changed();
+
+            ui.label("Value:");
+            let value_changed = ui
+                .add(egui::DragValue::new(&mut keyframe.value).speed(0.005).range(0.0..=1.0))
+                .changed();
+
+            if position_changed || value_changed {
+                self.selected = Some(curve.move_keyframe(index, keyframe.position, keyframe.value));
+                changed = true;
+            }
+
+            ui.separator();
+
+            // Easing applies to the segment from this keyframe to the next
+            let index = self.selected.unwrap_or(index);
+            let mut easing = curve.keyframes[index].easing;
+            egui::ComboBox::from_id_salt("curve_editor_easing")
+                .selected_text(easing.name())
+                .width(90.0)
+                .show_ui(ui, |ui| {
+                    for (name, preset) in KeyframeEasing::presets() {
+                        if ui.selectable_value(&mut easing, *preset, *name).changed() {
+                            changed = true;
+                        }
+                    }
+                })
+                .response
+                .on_hover_text("Interpolation to the next keyframe");
+
+            // Bezier control points (x stays within 0-1 so the curve doesn't fold back)
+            if let KeyframeEasing::Bezier { x1, y1, x2, y2 } = &mut easing {
+                for (value, is_x) in [(x1, true), (y1, false), (x2, true), (y2, false)] {
+                    let range = if is_x { 0.0..=1.0 } else { -1.0..=2.0 };
+                    if ui.add(egui::DragValue::new(value).speed(0.01).range(range).max_decimals(2)).changed() {
+                        changed = true;
+                    }
+                }
+            }
+            curve.keyframes[index].easing = easing;
+        });
+        changed
+    }
+}
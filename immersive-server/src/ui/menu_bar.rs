@@ -61,6 +61,8 @@ pub enum MenuAction {
     TapTempo,
     /// Resync to bar start
     ResyncBpm,
+    /// Toggle Ableton Link session sync
+    ToggleLink,
     /// Breakout environment viewport to separate window
     BreakoutEnvironment,
     /// Redock environment viewport back to main window
@@ -89,6 +91,23 @@ pub struct BpmInfo {
     pub beat_phase: f32,
     /// Current beat within bar (0 to beats_per_bar-1)
     pub current_beat_in_bar: u32,
+    /// Number of Link peers in the session (None when Link is disabled)
+    pub link_peers: Option<usize>,
+}
+
+/// Ableton Link toggle button for the BPM area (highlighted while Link is active)
+pub fn link_button(ui: &mut egui::Ui, link_peers: Option<usize>) -> egui::Response {
+    let (text, hover) = match link_peers {
+        Some(peers) => (
+            egui::RichText::new(format!("LINK {}", peers)).color(egui::Color32::from_rgb(255, 180, 50)),
+            format!("Ableton Link active ({} peer{}). Click to disable.", peers, if peers == 1 { "" } else { "s" }),
+        ),
+        None => (
+            egui::RichText::new("LINK").weak(),
+            "Sync tempo and beat phase with an Ableton Link session".to_string(),
+        ),
+    };
+    ui.add(egui::Button::new(text).small()).on_hover_text(hover)
 }
 
 impl MenuBar {
@@ -478,6 +497,11 @@ impl MenuBar {
                 {
                     self.pending_menu_action = Some(MenuAction::ResyncBpm);
                 }
+
+                // Ableton Link toggle
+                if link_button(ui, info.link_peers).clicked() {
+                    self.pending_menu_action = Some(MenuAction::ToggleLink);
+                }
             }
             ui.separator();
         }
@@ -4,6 +4,7 @@
 
 pub mod advanced_output_window;
 pub mod clip_grid_panel;
+pub mod curve_editor_window;
 pub mod dock;
 pub mod icons;
 pub mod effects_browser_panel;
@@ -26,6 +27,7 @@ pub mod window_registry;
 
 pub use advanced_output_window::{AdvancedOutputAction, AdvancedOutputWindow};
 pub use clip_grid_panel::{ClipGridAction, ClipGridPanel};
+pub use curve_editor_window::{CurveEditorWindow, CurveTarget};
 pub use dock::{DockAction, DockManager, DockZone, DockablePanel};
 pub use effects_browser_panel::{DraggableEffect, EffectsBrowserAction, EffectsBrowserPanel, DRAG_EFFECT_PAYLOAD};
 pub use tiled_layout::{
@@ -40,7 +42,7 @@ pub use performance_panel::PerformancePanel;
 pub use preferences_window::PreferencesWindow;
 pub use previs_panel::{PrevisAction, PrevisPanel, WallId};
 pub use preview_monitor_panel::{PreviewClipInfo, PreviewLayerInfo, PreviewMode, PreviewMonitorAction, PreviewMonitorPanel, PreviewSourceInfo};
-pub use properties_panel::{PropertiesPanel, PropertiesAction, PropertiesTab};
+pub use properties_panel::{EffectContext, PropertiesPanel, PropertiesAction, PropertiesTab};
 pub use sources_panel::{DraggableSource, SourcesAction, SourcesPanel};
 pub use thumbnail_cache::ThumbnailCache;
 pub use viewport_widget::{ViewportConfig, ViewportResponse, UvRenderInfo, handle_viewport_input, compute_uv_rect, compute_uv_and_dest_rect, draw_zoom_indicator};
@@ -3,7 +3,7 @@
 //! A floating window for editing application-wide environment settings.
 //! Accessible via Immersive Server → Preferences (macOS) or Edit → Preferences (Windows).
 
-use crate::audio::AudioManager;
+use crate::audio::{format_frequency, log_band_range, AudioManager};
 use crate::compositor::Environment;
 use crate::network::discovery::{DiscoveredSource, SourceType};
 use crate::settings::{AudioSourceType, EnvironmentSettings, ThumbnailMode};
@@ -26,6 +26,8 @@ pub struct PreferencesWindow {
     cached_audio_devices: Vec<String>,
     /// Whether audio devices need to be refreshed
     audio_devices_dirty: bool,
+    /// Log-spaced FFT band whose attack/release is being edited
+    selected_fft_band: usize,
 }
 
 impl Default for PreferencesWindow {
@@ -46,6 +48,7 @@ impl PreferencesWindow {
             temp_fps: 60,
             cached_audio_devices: Vec::new(),
             audio_devices_dirty: true,
+            selected_fft_band: 0,
         }
     }
 
@@ -451,6 +454,49 @@ impl PreferencesWindow {
 
         ui.add_space(8.0);
 
+        // Global clip trigger quantization (layers may override)
+        ui.horizontal(|ui| {
+            ui.label("Trigger Quantize:");
+            let current = settings.trigger_quantize;
+            egui::ComboBox::from_id_salt("global_trigger_quantize")
+                .selected_text(current.name())
+                .width(100.0)
+                .show_ui(ui, |ui| {
+                    for quantize in crate::compositor::TriggerQuantize::PRESETS {
+                        if ui.selectable_label(current == quantize, quantize.name()).clicked() && current != quantize {
+                            actions.push(PropertiesAction::SetTriggerQuantize { quantize });
+                        }
+                    }
+                })
+                .response
+                .on_hover_text("Clip triggers wait for the next beat or bar on the BPM clock. Layers can override this.");
+        });
+
+        ui.add_space(8.0);
+
+        // Ableton Link
+        let mut link_enabled = settings.link_enabled;
+        if ui
+            .checkbox(&mut link_enabled, "Ableton Link")
+            .on_hover_text("Follow tempo and beat phase of an Ableton Link session on the local network")
+            .changed()
+        {
+            actions.push(PropertiesAction::SetLinkEnabled { enabled: link_enabled });
+        }
+
+        ui.add_enabled_ui(link_enabled, |ui| {
+            let mut start_stop_sync = settings.link_start_stop_sync;
+            if ui
+                .checkbox(&mut start_stop_sync, "Start/Stop Sync")
+                .on_hover_text("Pause the BPM clock when the Link session transport is stopped")
+                .changed()
+            {
+                actions.push(PropertiesAction::SetLinkStartStopSync { enabled: start_stop_sync });
+            }
+        });
+
+        ui.add_space(8.0);
+
         // Low Latency Mode toggle
         let mut low_latency = settings.low_latency_mode;
         if ui
@@ -825,6 +871,113 @@ impl PreferencesWindow {
                 }
             });
 
+            ui.add_space(8.0);
+
+            // Log-spaced band count
+            let band_count = manager.band_count();
+            ui.horizontal(|ui| {
+                ui.label("FFT Bands:");
+                egui::ComboBox::from_id_salt("fft_band_count")
+                    .selected_text(band_count.to_string())
+                    .width(60.0)
+                    .show_ui(ui, |ui| {
+                        for count in [8, 16, 32] {
+                            if ui.selectable_label(band_count == count, count.to_string()).clicked() {
+                                actions.push(PropertiesAction::SetFftBandCount { count });
+                            }
+                        }
+                    });
+            });
+
+            // Band meter (click a band to edit its envelope)
+            let levels = manager
+                .with_primary_fft_data(|fft| fft.bands.clone())
+                .unwrap_or_default();
+            self.selected_fft_band = self.selected_fft_band.min(band_count.saturating_sub(1));
+            let meter_width = ui.available_width().min(260.0);
+            let meter_height = 40.0;
+            let (rect, response) =
+                ui.allocate_exact_size(egui::vec2(meter_width, meter_height), egui::Sense::click());
+            let bar_width = rect.width() / band_count.max(1) as f32;
+            let hovered_band = response
+                .hover_pos()
+                .map(|pos| (((pos.x - rect.left()) / bar_width) as usize).min(band_count.saturating_sub(1)));
+
+            if ui.is_rect_visible(rect) {
+                let painter = ui.painter();
+                painter.rect_filled(rect, 2.0, egui::Color32::from_gray(30));
+                for index in 0..band_count {
+                    let level = (levels.get(index).copied().unwrap_or(0.0) * settings.fft_gain).clamp(0.0, 1.0);
+                    let left = rect.left() + index as f32 * bar_width;
+                    let bar_rect = egui::Rect::from_min_max(
+                        egui::pos2(left + 1.0, rect.bottom() - rect.height() * level),
+                        egui::pos2(left + bar_width - 1.0, rect.bottom()),
+                    );
+                    let color = if index == self.selected_fft_band {
+                        egui::Color32::from_rgb(255, 80, 200)
+                    } else {
+                        egui::Color32::from_rgb(50, 180, 220)
+                    };
+                    painter.rect_filled(bar_rect, 0.0, color);
+                }
+            }
+            if let Some(index) = hovered_band {
+                let (min_hz, max_hz) = log_band_range(index, band_count);
+                if response.clicked() {
+                    self.selected_fft_band = index;
+                }
+                response.on_hover_text(format!(
+                    "Band {}: {} - {}",
+                    index + 1,
+                    format_frequency(min_hz),
+                    format_frequency(max_hz)
+                ));
+            }
+
+            // Attack/release of the selected band
+            let index = self.selected_fft_band;
+            let mut envelope = settings.fft_band_envelopes.get(index).copied().unwrap_or_default();
+            ui.horizontal(|ui| {
+                ui.label(format!("Band {}:", index + 1));
+                let attack = ui.add(
+                    egui::DragValue::new(&mut envelope.attack_ms)
+                        .speed(1.0)
+                        .range(0.0..=500.0)
+                        .prefix("Atk ")
+                        .suffix("ms"),
+                );
+                let release = ui.add(
+                    egui::DragValue::new(&mut envelope.release_ms)
+                        .speed(10.0)
+                        .range(0.0..=2000.0)
+                        .prefix("Rel ")
+                        .suffix("ms"),
+                );
+                if attack.changed() || release.changed() {
+                    actions.push(PropertiesAction::SetFftBandEnvelope { index, envelope });
+                }
+            });
+
+            ui.add_space(8.0);
+
+            // Tempo detection driving the BPM clock
+            ui.horizontal(|ui| {
+                let mut beat_sync = settings.audio_beat_sync;
+                if ui
+                    .checkbox(&mut beat_sync, "Detect BPM")
+                    .on_hover_text("Drive the BPM clock from tempo and beats detected in this audio source (Ableton Link takes precedence)")
+                    .changed()
+                {
+                    actions.push(PropertiesAction::SetAudioBeatSync { enabled: beat_sync });
+                }
+
+                let detected = match manager.tempo_estimate() {
+                    Some(estimate) => format!("{:.1} BPM ({:.0}%)", estimate.bpm, estimate.confidence * 100.0),
+                    None => "listening...".to_string(),
+                };
+                ui.label(egui::RichText::new(detected).small().weak());
+            });
+
             // Request repaint for animation
             if settings.audio_source.is_enabled() {
                 ui.ctx().request_repaint();
@@ -7,11 +7,12 @@ use std::collections::HashMap;
 
 use egui::PointerButton;
 
-use crate::audio::AudioBand;
-use crate::compositor::{BlendMode, ClipSource, ClipTransition, Environment, Layer, LoopMode};
-use crate::effects::{AutomationSource, AutomationRange, EffectManager, EffectRegistry, EffectStack, FftSource, LfoSource, LfoShape, BeatSource, BeatTrigger, TimelineSource, TimelineDirection, TimelineMode, TimelineEasing, ParameterValue};
+use crate::audio::{format_frequency, log_band_range, AudioBand, ANALYSIS_MAX_HZ, ANALYSIS_MIN_HZ, DEFAULT_BAND_COUNT};
+use crate::compositor::{BlendMode, ClipSource, ClipTransition, Environment, Layer, LoopMode, TriggerQuantize};
+use crate::effects::{AutomationSource, AutomationRange, EffectManager, EffectRegistry, EffectStack, FftSource, LfoSource, LfoShape, BeatSource, BeatTrigger, OnsetSource, TimelineSource, TimelineDirection, TimelineMode, TimelineEasing, CurveSource, CurveTimeBase, ParameterValue};
 use crate::layer_runtime::LayerVideoInfo;
 use crate::settings::{EnvironmentSettings, ThumbnailMode};
+use crate::ui::curve_editor_window::CurveTarget;
 use crate::ui::effects_browser_panel::DraggableEffect;
 use crate::ui::CrossWindowDragState;
 use egui_widgets::{video_scrubber, ScrubberAction, ScrubberState};
@@ -60,6 +61,8 @@ pub enum PropertiesAction {
     SetLayerRotation { layer_id: u32, degrees: f32 },
     /// Layer transition changed
     SetLayerTransition { layer_id: u32, transition: ClipTransition },
+    /// Layer trigger quantization changed (None = use global setting)
+    SetLayerTriggerQuantize { layer_id: u32, quantize: Option<TriggerQuantize> },
     /// OMT broadcast toggle changed
     SetOmtBroadcast { enabled: bool },
     /// OMT capture FPS changed
@@ -175,12 +178,20 @@ pub enum PropertiesAction {
     PreviewClip { layer_id: u32, slot: usize },
     /// Set clip loop mode
     SetClipLoopMode { layer_id: u32, slot: usize, mode: LoopMode },
+    /// Toggle time-stretching the clip loop to a whole number of bars
+    SetClipBpmSync { layer_id: u32, slot: usize, enabled: bool },
 
     // Performance mode actions
     /// Floor sync enabled changed
     SetFloorSyncEnabled { enabled: bool },
     /// Floor layer index changed
     SetFloorLayerIndex { index: usize },
+    /// Global clip trigger quantization changed
+    SetTriggerQuantize { quantize: TriggerQuantize },
+    /// Ableton Link sync toggled
+    SetLinkEnabled { enabled: bool },
+    /// Ableton Link start/stop sync toggled
+    SetLinkStartStopSync { enabled: bool },
     /// Low latency mode changed (trades stability for reduced input lag)
     SetLowLatencyMode { enabled: bool },
     /// Test pattern mode changed (replaces composition with calibration pattern)
@@ -193,11 +204,21 @@ pub enum PropertiesAction {
     SetAudioSource { source_type: crate::settings::AudioSourceType },
     /// FFT gain (sensitivity) changed
     SetFftGain { gain: f32 },
+    /// BPM detection from audio toggled
+    SetAudioBeatSync { enabled: bool },
+    /// Number of log-spaced FFT bands changed
+    SetFftBandCount { count: usize },
+    /// Attack/release of a log-spaced FFT band changed
+    SetFftBandEnvelope { index: usize, envelope: crate::audio::BandEnvelope },
+    /// Open the curve editor for a curve-automated parameter
+    OpenCurveEditor { target: CurveTarget },
+    /// Arm or disarm live recording into a curve-automated parameter
+    SetCurveRecordArm { target: CurveTarget, armed: bool },
 }
 
 /// Context for rendering effect stacks (determines which PropertiesAction variants to emit)
-#[derive(Debug, Clone, Copy, PartialEq)]
-enum EffectContext {
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EffectContext {
     /// Effects on a layer
     Layer { layer_id: u32 },
     /// Effects on a clip within a layer
@@ -489,6 +510,28 @@ impl PropertiesPanel {
             }
         });
 
+        // Trigger quantization (wait for beat/bar before switching clips)
+        ui.horizontal(|ui| {
+            ui.label("Quantize:");
+            let current = layer.trigger_quantize;
+            egui::ComboBox::from_id_salt(format!("layer_quantize_{}", layer_id))
+                .selected_text(current.map(|q| q.name()).unwrap_or_else(|| "Global".to_string()))
+                .width(100.0)
+                .show_ui(ui, |ui| {
+                    if ui.selectable_label(current.is_none(), "Global").clicked() && current.is_some() {
+                        actions.push(PropertiesAction::SetLayerTriggerQuantize { layer_id, quantize: None });
+                    }
+                    for quantize in TriggerQuantize::PRESETS {
+                        let selected = current == Some(quantize);
+                        if ui.selectable_label(selected, quantize.name()).clicked() && !selected {
+                            actions.push(PropertiesAction::SetLayerTriggerQuantize { layer_id, quantize: Some(quantize) });
+                        }
+                    }
+                })
+                .response
+                .on_hover_text("Wait for the next beat or bar on the BPM clock before switching clips");
+        });
+
         ui.add_space(16.0);
         ui.separator();
 
@@ -935,6 +978,8 @@ impl PropertiesPanel {
                     Some(AutomationSource::Beat(_)) => egui::Color32::from_rgb(50, 200, 255), // Cyan for Beat
                     Some(AutomationSource::Fft(_)) => egui::Color32::from_rgb(255, 80, 200), // Magenta for FFT
                     Some(AutomationSource::Timeline(_)) => egui::Color32::from_rgb(100, 255, 100), // Green for Timeline
+                    Some(AutomationSource::Onset(_)) => egui::Color32::from_rgb(255, 140, 40), // Orange for Onset
+                    Some(AutomationSource::Curve(_)) => egui::Color32::from_rgb(170, 130, 255), // Violet for Curve
                     None => egui::Color32::from_gray(100), // Gray when inactive
                 };
 
@@ -962,6 +1007,8 @@ impl PropertiesPanel {
                             Some(AutomationSource::Beat(_)) => "Beat modulation active (click to edit)",
                             Some(AutomationSource::Fft(_)) => "FFT modulation active (click to edit)",
                             Some(AutomationSource::Timeline(_)) => "Timeline modulation active (click to edit)",
+                            Some(AutomationSource::Onset(_)) => "Onset modulation active (click to edit)",
+                            Some(AutomationSource::Curve(_)) => "Curve automation active (click to edit)",
                             None => "Click to add modulation",
                         };
                         response.clone().on_hover_text(tooltip);
@@ -1033,7 +1080,7 @@ impl PropertiesPanel {
                                     min + env_val * range
                                 } else if let Some(am) = audio_manager {
                                     // Fallback: raw value if no envelope state yet
-                                    let raw = am.get_band_value(fft.band) * fft.gain;
+                                    let raw = fft.sample(am) * fft.gain;
                                     min + raw.min(1.0) * range
                                 } else {
                                     val
@@ -1047,6 +1094,22 @@ impl PropertiesPanel {
                                     val // Fallback to base value if no envelope state yet
                                 }
                             }
+                            AutomationSource::Onset(_onset) => {
+                                // Onset: use envelope value from effect_manager (0-1 mapped to range)
+                                if let Some(env_val) = effect_manager.get_onset_envelope_value(layer_id, clip_slot, effect_id, &param.meta.name) {
+                                    min + env_val * range
+                                } else {
+                                    val // Fallback to base value if no envelope state yet
+                                }
+                            }
+                            AutomationSource::Curve(curve) => {
+                                // Curve: evaluate at the same playhead the renderer uses
+                                let clip_time = effect_manager.get_clip_time(layer_id, clip_slot).unwrap_or(effect_time);
+                                curve
+                                    .evaluate(curve.playhead(clip_time, bpm_clock))
+                                    .map(|v| min + curve.range.remap(v) * range)
+                                    .unwrap_or(val)
+                            }
                         }
                     } else {
                         val
@@ -1066,6 +1129,8 @@ impl PropertiesPanel {
                         AutomationSource::Beat(beat) => beat.range,
                         AutomationSource::Fft(fft) => fft.range,
                         AutomationSource::Timeline(timeline) => timeline.range,
+                        AutomationSource::Onset(onset) => onset.range,
+                        AutomationSource::Curve(curve) => curve.range,
                     });
 
                     // Custom slider with visible track and range handles
@@ -1217,6 +1282,16 @@ impl PropertiesPanel {
                                                 new_timeline.range = new_range;
                                                 AutomationSource::Timeline(new_timeline)
                                             }
+                                            AutomationSource::Onset(onset) => {
+                                                let mut new_onset = onset.clone();
+                                                new_onset.range = new_range;
+                                                AutomationSource::Onset(new_onset)
+                                            }
+                                            AutomationSource::Curve(curve) => {
+                                                let mut new_curve = curve.clone();
+                                                new_curve.range = new_range;
+                                                AutomationSource::Curve(new_curve)
+                                            }
                                         };
                                         self.push_automation_action(actions, context, effect_id, param.meta.name.clone(), Some(new_automation));
                                     }
@@ -1268,6 +1343,16 @@ impl PropertiesPanel {
                                             new_timeline.range = AutomationRange::default();
                                             AutomationSource::Timeline(new_timeline)
                                         }
+                                        AutomationSource::Onset(onset) => {
+                                            let mut new_onset = onset.clone();
+                                            new_onset.range = AutomationRange::default();
+                                            AutomationSource::Onset(new_onset)
+                                        }
+                                        AutomationSource::Curve(curve) => {
+                                            let mut new_curve = curve.clone();
+                                            new_curve.range = AutomationRange::default();
+                                            AutomationSource::Curve(new_curve)
+                                        }
                                     };
                                     self.push_automation_action(actions, context, effect_id, param.meta.name.clone(), Some(new_automation));
                                 }
@@ -1410,7 +1495,8 @@ impl PropertiesPanel {
         });
 
         // Inline modulation controls when automation is active
-        self.render_inline_modulation_controls(ui, context, effect_id, param, actions, GEAR_WIDTH + LABEL_WIDTH + 8.0);
+        let band_count = audio_manager.map_or(DEFAULT_BAND_COUNT, |am| am.band_count());
+        self.render_inline_modulation_controls(ui, context, effect_id, param, actions, GEAR_WIDTH + LABEL_WIDTH + 8.0, band_count);
     }
 
     // Helper methods for generating context-specific actions
@@ -1545,6 +1631,8 @@ impl PropertiesPanel {
         let is_beat = matches!(&param.automation, Some(AutomationSource::Beat(_)));
         let is_fft = matches!(&param.automation, Some(AutomationSource::Fft(_)));
         let is_timeline = matches!(&param.automation, Some(AutomationSource::Timeline(_)));
+        let is_onset = matches!(&param.automation, Some(AutomationSource::Onset(_)));
+        let is_curve = matches!(&param.automation, Some(AutomationSource::Curve(_)));
 
         // None option
         if ui.selectable_label(param.automation.is_none(), "None").clicked() {
@@ -1584,6 +1672,22 @@ impl PropertiesPanel {
                 self.push_automation_action(actions, context, effect_id, param.meta.name.clone(), Some(AutomationSource::Timeline(TimelineSource::default())));
             }
         }
+
+        // Onset option
+        let onset_label = egui::RichText::new("Onset").color(egui::Color32::from_rgb(255, 140, 40));
+        if ui.selectable_label(is_onset, onset_label).clicked() {
+            if !is_onset {
+                self.push_automation_action(actions, context, effect_id, param.meta.name.clone(), Some(AutomationSource::Onset(OnsetSource::default())));
+            }
+        }
+
+        // Curve option
+        let curve_label = egui::RichText::new("Curve").color(egui::Color32::from_rgb(170, 130, 255));
+        if ui.selectable_label(is_curve, curve_label).clicked() {
+            if !is_curve {
+                self.push_automation_action(actions, context, effect_id, param.meta.name.clone(), Some(AutomationSource::Curve(CurveSource::default())));
+            }
+        }
     }
 
     /// Render inline modulation controls below a parameter slider
@@ -1595,6 +1699,7 @@ impl PropertiesPanel {
         param: &crate::effects::Parameter,
         actions: &mut Vec<PropertiesAction>,
         indent: f32,
+        band_count: usize,
     ) {
         let Some(automation) = &param.automation else { return };
 
@@ -1806,28 +1911,44 @@ impl PropertiesPanel {
 
                     // Band
                     let band_response = egui::ComboBox::from_id_salt(format!("fft_band_{}_{}", effect_id, &param.meta.name))
-                        .selected_text(match fft.band {
-                            AudioBand::Low => "Low",
-                            AudioBand::Mid => "Mid",
-                            AudioBand::High => "High",
-                            AudioBand::Full => "Full",
-                        })
-                        .width(50.0)
+                        .selected_text(fft.selection_name())
+                        .width(70.0)
                         .show_ui(ui, |ui| {
-                            for (band, name) in [
-                                (AudioBand::Low, "Low"),
-                                (AudioBand::Mid, "Mid"),
-                                (AudioBand::High, "High"),
-                                (AudioBand::Full, "Full"),
-                            ] {
-                                if ui.selectable_value(&mut fft.band, band, name).changed() {
+                            let is_preset = fft.band_index.is_none() && fft.frequency_range.is_none();
+                            for band in AudioBand::all() {
+                                if ui.selectable_label(is_preset && fft.band == *band, band.name_with_range()).clicked() {
+                                    fft.band = *band;
+                                    fft.band_index = None;
+                                    fft.frequency_range = None;
                                     changed = true;
                                 }
                             }
+                            ui.separator();
+                            for index in 0..band_count {
+                                let (min_hz, max_hz) = log_band_range(index, band_count);
+                                let selected = fft.frequency_range.is_none() && fft.band_index == Some(index);
+                                let label = format!("Band {} ({} - {})", index + 1, format_frequency(min_hz), format_frequency(max_hz));
+                                if ui.selectable_label(selected, label).clicked() {
+                                    fft.band_index = Some(index);
+                                    fft.frequency_range = None;
+                                    changed = true;
+                                }
+                            }
+                            ui.separator();
+                            if ui.selectable_label(fft.frequency_range.is_some(), "Custom Range").clicked() && fft.frequency_range.is_none() {
+                                // Start from the currently tracked band
+                                fft.frequency_range = Some(match fft.band_index {
+                                    Some(index) => log_band_range(index, band_count),
+                                    None => (200.0, 2000.0),
+                                });
+                                changed = true;
+                            }
                         });
                     // Right-click instantly resets to default
                     if band_response.response.clicked_by(PointerButton::Secondary) {
                         fft.band = default_fft.band;
+                        fft.band_index = default_fft.band_index;
+                        fft.frequency_range = default_fft.frequency_range;
                         changed = true;
                     }
 
@@ -1861,6 +1982,24 @@ impl PropertiesPanel {
 
                 });
 
+                // Custom frequency range row
+                if let Some((mut min_hz, mut max_hz)) = fft.frequency_range {
+                    ui.horizontal(|ui| {
+                        ui.add_space(indent + 40.0);
+
+                        ui.label(egui::RichText::new("From:").small());
+                        let min_response = ui.add(egui::DragValue::new(&mut min_hz).speed(5.0).range(ANALYSIS_MIN_HZ..=max_hz).suffix(" Hz").max_decimals(0));
+
+                        ui.label(egui::RichText::new("To:").small());
+                        let max_response = ui.add(egui::DragValue::new(&mut max_hz).speed(20.0).range(min_hz..=ANALYSIS_MAX_HZ).suffix(" Hz").max_decimals(0));
+
+                        if min_response.changed() || max_response.changed() {
+                            fft.frequency_range = Some((min_hz, max_hz));
+                            changed = true;
+                        }
+                    });
+                }
+
                 // Row 2: Attack and Release (envelope controls)
                 ui.horizontal(|ui| {
                     ui.add_space(indent + 40.0);
@@ -1970,6 +2109,136 @@ impl PropertiesPanel {
                     self.push_automation_action(actions, context, effect_id, param.meta.name.clone(), Some(AutomationSource::Timeline(timeline)));
                 }
             }
+
+            AutomationSource::Onset(onset) => {
+                let mut onset = onset.clone();
+                let default_onset = OnsetSource::default();
+                let mut changed = false;
+
+                ui.horizontal(|ui| {
+                    ui.add_space(indent);
+                    ui.label(egui::RichText::new("Onset").small().color(egui::Color32::from_rgb(255, 140, 40)));
+                    ui.separator();
+
+                    // Threshold
+                    ui.label(egui::RichText::new("Thr:").small());
+                    let thr_response = ui.add(egui::DragValue::new(&mut onset.threshold).speed(0.01).range(0.0..=1.0))
+                        .on_hover_text("Minimum onset strength that fires the envelope");
+                    if thr_response.changed() {
+                        changed = true;
+                    }
+                    // Right-click instantly resets to default
+                    if thr_response.clicked_by(PointerButton::Secondary) {
+                        onset.threshold = default_onset.threshold;
+                        changed = true;
+                    }
+
+                    ui.separator();
+
+                    // Attack
+                    ui.label(egui::RichText::new("Atk:").small());
+                    let atk_response = ui.add(egui::DragValue::new(&mut onset.attack_ms).speed(1.0).range(0.0..=500.0).suffix("ms"));
+                    if atk_response.changed() {
+                        changed = true;
+                    }
+                    // Right-click instantly resets to default
+                    if atk_response.clicked_by(PointerButton::Secondary) {
+                        onset.attack_ms = default_onset.attack_ms;
+                        changed = true;
+                    }
+
+                    ui.separator();
+
+                    // Release
+                    ui.label(egui::RichText::new("Rel:").small());
+                    let rel_response = ui.add(egui::DragValue::new(&mut onset.release_ms).speed(10.0).range(0.0..=2000.0).suffix("ms"));
+                    if rel_response.changed() {
+                        changed = true;
+                    }
+                    // Right-click instantly resets to default
+                    if rel_response.clicked_by(PointerButton::Secondary) {
+                        onset.release_ms = default_onset.release_ms;
+                        changed = true;
+                    }
+                });
+
+                if changed {
+                    self.push_automation_action(actions, context, effect_id, param.meta.name.clone(), Some(AutomationSource::Onset(onset)));
+                }
+            }
+
+            AutomationSource::Curve(curve) => {
+                let mut curve = curve.clone();
+                let default_curve = CurveSource::default();
+                let mut changed = false;
+                let target = CurveTarget::new(context, effect_id, &param.meta.name);
+
+                ui.horizontal(|ui| {
+                    ui.add_space(indent);
+                    ui.label(egui::RichText::new("Curve").small().color(egui::Color32::from_rgb(170, 130, 255)));
+                    ui.separator();
+
+                    // Record arm
+                    let arm_text = if curve.armed {
+                        egui::RichText::new("Rec").small().color(egui::Color32::from_rgb(255, 70, 70))
+                    } else {
+                        egui::RichText::new("Arm").small()
+                    };
+                    if ui.selectable_label(curve.armed, arm_text)
+                        .on_hover_text("Record parameter moves into the curve while the clip plays")
+                        .clicked()
+                    {
+                        actions.push(PropertiesAction::SetCurveRecordArm { target: target.clone(), armed: !curve.armed });
+                    }
+
+                    // Time base
+                    let base_response = egui::ComboBox::from_id_salt(format!("curve_base_{}_{}", effect_id, &param.meta.name))
+                        .selected_text(curve.time_base.name())
+                        .width(70.0)
+                        .show_ui(ui, |ui| {
+                            for base in CurveTimeBase::all() {
+                                if ui.selectable_value(&mut curve.time_base, *base, base.name()).changed() {
+                                    changed = true;
+                                }
+                            }
+                        });
+                    // Right-click instantly resets to default
+                    if base_response.response.clicked_by(PointerButton::Secondary) {
+                        curve.time_base = default_curve.time_base;
+                        changed = true;
+                    }
+
+                    // Loop length
+                    let len_response = ui.add(
+                        egui::DragValue::new(&mut curve.length)
+                            .speed(0.1)
+                            .range(0.25..=600.0)
+                            .suffix(curve.time_base.unit()),
+                    ).on_hover_text("Loop length");
+                    if len_response.changed() {
+                        changed = true;
+                    }
+                    // Right-click instantly resets to default
+                    if len_response.clicked_by(PointerButton::Secondary) {
+                        curve.length = default_curve.length;
+                        changed = true;
+                    }
+
+                    ui.separator();
+
+                    // Open the curve editor
+                    if ui.small_button(format!("Edit ({})", curve.keyframes.len()))
+                        .on_hover_text("Open the curve editor")
+                        .clicked()
+                    {
+                        actions.push(PropertiesAction::OpenCurveEditor { target: target.clone() });
+                    }
+                });
+
+                if changed {
+                    self.push_automation_action(actions, context, effect_id, param.meta.name.clone(), Some(AutomationSource::Curve(curve)));
+                }
+            }
         }
     }
 
@@ -2172,6 +2441,18 @@ impl PropertiesPanel {
                 });
         });
 
+        // BPM sync (time-stretch loop to whole bars)
+        if clip.is_file() {
+            let mut bpm_sync = clip.bpm_sync;
+            if ui
+                .checkbox(&mut bpm_sync, "Sync to BPM")
+                .on_hover_text("Time-stretch playback so one loop lasts a whole number of bars")
+                .changed()
+            {
+                actions.push(PropertiesAction::SetClipBpmSync { layer_id, slot, enabled: bpm_sync });
+            }
+        }
+
         // ========== CLIP TRANSFORM ==========
         ui.add_space(16.0);
         ui.separator();
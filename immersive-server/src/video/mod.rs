@@ -20,7 +20,7 @@ mod texture;
 pub use decoder::{HwAccelMethod, VideoDecoder, VideoDecoderError};
 pub use frame::DecodedFrame;
 pub use hap::{HapDecoder, HapFormat, HapFrame};
-pub use player::{VideoInfo, VideoPlayer};
+pub use player::{VideoInfo, VideoPlayer, MAX_PLAYBACK_SPEED, MIN_PLAYBACK_SPEED};
 pub use renderer::{LayerParams, VideoParams, VideoRenderer};
 pub use texture::VideoTexture;
 
@@ -12,6 +12,11 @@ use std::time::{Duration, Instant};
 
 use super::{DecodedFrame, VideoDecoder, VideoDecoderError};
 
+/// Slowest supported playback speed multiplier
+pub const MIN_PLAYBACK_SPEED: f64 = 0.1;
+/// Fastest supported playback speed multiplier
+pub const MAX_PLAYBACK_SPEED: f64 = 10.0;
+
 /// Shared state between decode thread and main thread
 struct SharedState {
     /// The latest decoded frame (if any)
@@ -32,6 +37,8 @@ struct SharedState {
     frame_index: AtomicU64,
     /// Loop mode: 0=Loop, 1=PlayOnce
     loop_mode: AtomicU8,
+    /// Playback speed multiplier (stored as f64 bits for atomic ops)
+    speed_bits: AtomicU64,
 }
 
 impl SharedState {
@@ -46,6 +53,7 @@ impl SharedState {
             seek_target_bits: AtomicU64::new(0),
             frame_index: AtomicU64::new(0),
             loop_mode: AtomicU8::new(0), // Default: Loop
+            speed_bits: AtomicU64::new(1.0f64.to_bits()),
         }
     }
 }
@@ -253,8 +261,9 @@ impl VideoPlayer {
                 }
             }
 
-            // Schedule next frame
-            next_frame_time += frame_duration;
+            // Schedule next frame (scaled by playback speed)
+            let speed = f64::from_bits(state.speed_bits.load(Ordering::Acquire));
+            next_frame_time += frame_duration.div_f64(speed);
 
             // If we fell behind, reset to now (don't try to catch up)
             let now = Instant::now();
@@ -385,6 +394,17 @@ impl VideoPlayer {
     pub fn loop_mode(&self) -> u8 {
         self.state.loop_mode.load(Ordering::Acquire)
     }
+
+    /// Set the playback speed multiplier (1.0 = native frame rate)
+    pub fn set_speed(&self, speed: f64) {
+        let speed = speed.clamp(MIN_PLAYBACK_SPEED, MAX_PLAYBACK_SPEED);
+        self.state.speed_bits.store(speed.to_bits(), Ordering::Release);
+    }
+
+    /// Get the playback speed multiplier
+    pub fn speed(&self) -> f64 {
+        f64::from_bits(self.state.speed_bits.load(Ordering::Acquire))
+    }
 }
 
 impl Drop for VideoPlayer {
@@ -0,0 +1,82 @@
+"""Synthesize the drum loop fixtures for the beat detection tests.
+
+Usage: python3 generate.py <output dir>
+"""
+import math, random, struct, wave, sys
+SR = 22050
+def render(path, bpm, seconds, offset, pattern, seed):
+    rnd = random.Random(seed)
+    n = int(SR * seconds)
+    buf = [0.0] * n
+    beat = 60.0 / bpm
+    def add(t0, fn, length):
+        s0 = int(t0 * SR)
+        for i in range(int(length * SR)):
+            j = s0 + i
+            if 0 <= j < n:
+                buf[j] += fn(i / SR)
+    def kick(vel):
+        def f(t):
+            return vel * 0.8 * math.sin(2 * math.pi * (50 * t + 100 * (1 - math.exp(-t * 30)) / 30)) * math.exp(-t * 9)
+        return f
+    def snare(vel):
+        def f(t):
+            return vel * (0.35 * rnd.uniform(-1, 1) + 0.2 * math.sin(2 * math.pi * 190 * t)) * math.exp(-t * 18)
+        return f
+    def hat(vel):
+        prev = [0.0]
+        def f(t):
+            x = rnd.uniform(-1, 1)
+            y = x - prev[0]
+            prev[0] = x
+            return vel * 0.12 * y * math.exp(-t * 60)
+        return f
+    def bass(freq, vel):
+        def f(t):
+            return vel * 0.15 * math.sin(2 * math.pi * freq * t) * min(1.0, t * 200) * math.exp(-t * 3)
+        return f
+    steps = int((seconds - offset) / (beat / 4)) + 1
+    for s in range(steps):
+        t = offset + s * beat / 4
+        jitter = rnd.uniform(-0.003, 0.003)
+        for kind in pattern(s):
+            vel = rnd.uniform(0.8, 1.0)
+            if kind == 'k': add(t + jitter, kick(vel), 0.5)
+            elif kind == 's': add(t + jitter, snare(vel), 0.3)
+            elif kind == 'h': add(t + jitter, hat(vel), 0.08)
+            elif kind == 'H': add(t + jitter, hat(vel * 0.5), 0.08)
+            elif kind == 'b': add(t + jitter, bass(rnd.choice([55, 65.4, 73.4]), vel), 0.4)
+    for i in range(n):
+        buf[i] += rnd.gauss(0, 0.004)
+    peak = max(abs(v) for v in buf)
+    with wave.open(path, 'wb') as w:
+        w.setnchannels(1); w.setsampwidth(2); w.setframerate(SR)
+        w.writeframes(b''.join(struct.pack('<h', int(max(-1, min(1, v / peak * 0.9)) * 32767)) for v in buf))
+
+# Four-on-the-floor with off-beat open hats
+def four(s):
+    out = []
+    if s % 4 == 0: out.append('k')
+    if s % 4 == 2: out.append('h')
+    return out
+# Backbeat: kick on 1 and 3 plus syncopated kicks, snare on 2 and 4, 16th hats
+def backbeat(s):
+    out = []
+    p = s % 16
+    if p in (0, 8, 10): out.append('k')
+    if p in (4, 12): out.append('s')
+    out.append('h' if p % 2 == 0 else 'H')
+    if p in (3, 11): out.append('b')
+    return out
+# Boom-bap: kick on 1, the and of 2 and 3, snare on 2 and 4, 8th hats
+def boombap(s):
+    out = []
+    p = s % 16
+    if p in (0, 6, 8): out.append('k')
+    if p in (4, 12): out.append('s')
+    if p % 2 == 0: out.append('h')
+    return out
+d = sys.argv[1]
+render(d + '/four_on_floor_120bpm.wav', 120.0, 8.0, 0.250, four, 1)
+render(d + '/backbeat_128bpm.wav', 128.0, 8.0, 0.100, backbeat, 2)
+render(d + '/boombap_90bpm.wav', 90.0, 8.0, 0.400, boombap, 3)
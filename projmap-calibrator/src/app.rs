@@ -1,7 +1,8 @@
 //! Main application state and event handling.
 
+use std::collections::BTreeMap;
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 /// Helper function to render egui pass, working around lifetime issues in egui-wgpu.
 fn render_egui_pass(
@@ -34,6 +35,34 @@ fn render_egui_pass(
     renderer.render(render_pass_static, paint_jobs, screen_descriptor);
 }
 
+/// Camera source combo box. A folder entry asks for the folder to watch.
+fn pick_camera_source(
+    ui: &mut egui::Ui,
+    id: &str,
+    selected: &str,
+    sources: &[CameraSourceKind],
+) -> Option<CameraSourceKind> {
+    let mut picked = None;
+    egui::ComboBox::from_id_salt(id).selected_text(selected).show_ui(ui, |ui| {
+        for source in sources {
+            let label = match source {
+                CameraSourceKind::Folder { .. } => "Watch Folder...".to_string(),
+                _ => source.label(),
+            };
+            if ui.selectable_label(false, label).clicked() {
+                picked = Some(source.clone());
+            }
+        }
+    });
+    match picked? {
+        CameraSourceKind::Folder { settle_time, .. } => rfd::FileDialog::new()
+            .set_title("Watch Folder for Camera Images")
+            .pick_folder()
+            .map(|path| CameraSourceKind::Folder { path, settle_time }),
+        source => Some(source),
+    }
+}
+
 use winit::application::ApplicationHandler;
 use winit::dpi::PhysicalSize;
 use winit::event::{ElementState, KeyEvent, WindowEvent};
@@ -41,10 +70,18 @@ use winit::event_loop::ActiveEventLoop;
 use winit::keyboard::{KeyCode, PhysicalKey};
 use winit::window::{Window, WindowId};
 
-use crate::camera::{NdiFinder, NdiReceiver};
-use crate::calibration::{CalibrationConfig, CalibrationSession, CalibrationState};
+use crate::blending::{footprint_blend_masks, BlendSpace};
+use crate::camera::{list_webcams, CameraSource, CameraSourceKind, CaptureTiming, NdiFinder};
+use crate::calibration::{
+    load_session, merge_cameras, reconstruct_rig, record_session, save_session, CalibrationConfig, CalibrationSession,
+    CalibrationState, CameraMerge, CaptureRun, CheckerboardSet, CheckerboardSpec, ContentMapping, ContentSpace,
+    MeshSurface, PhaseShiftConfig, PhotometricConfig, PhotometricSession, PhotometricState,
+    RigReconstruction, TouchUp, VerificationConfig, MIN_CHECKERBOARD_VIEWS, TOUCH_UP_COLUMNS, TOUCH_UP_ROWS,
+};
+use crate::config::{UvMap, WarpCorrection};
+use crate::output::{PatternOutputKind, PatternPresenter};
 use crate::render::{PatternRenderer, PreviewRenderer, RenderPipeline};
-use crate::ui::UiState;
+use crate::ui::{correction_overlay, UiState};
 
 /// Main application state.
 pub struct CalibrationApp {
@@ -54,8 +91,12 @@ pub struct CalibrationApp {
     render: Option<RenderPipeline>,
     /// Pattern renderer for calibration
     pattern_renderer: Option<PatternRenderer>,
+    /// Remote pattern output (patterns are shown locally when `None`)
+    pattern_presenter: Option<PatternPresenter>,
     /// Camera preview renderer
     preview_renderer: Option<PreviewRenderer>,
+    /// Camera preview registered with egui, and the frame size it was registered at
+    preview_texture: Option<(egui::TextureId, (u32, u32))>,
     /// egui context
     egui_ctx: egui::Context,
     /// egui-winit state
@@ -64,14 +105,30 @@ pub struct CalibrationApp {
     egui_renderer: Option<egui_wgpu::Renderer>,
     /// NDI source finder
     ndi_finder: Option<NdiFinder>,
-    /// NDI camera receiver
-    ndi_receiver: Option<NdiReceiver>,
+    /// Primary camera (NDI, webcam or watched folder)
+    camera: Option<Box<dyn CameraSource>>,
+    /// Additional cameras (multi-camera calibration)
+    extra_cameras: Vec<Box<dyn CameraSource>>,
     /// Available NDI sources
     ndi_sources: Vec<String>,
+    /// Local webcams found at startup
+    webcam_sources: Vec<CameraSourceKind>,
     /// Calibration session
     calibration_session: CalibrationSession,
     /// Calibration state (for display)
     calibration_state: CalibrationState,
+    /// Photometric (color matching) session
+    photometric_session: PhotometricSession,
+    /// Checkerboard views for camera intrinsics, per camera
+    checkerboards: Vec<CheckerboardSet>,
+    /// Projector geometry and surface points (non-planar calibration)
+    surface: Option<RigReconstruction>,
+    /// Content space fitted to the surface
+    content_mapping: Option<ContentMapping>,
+    /// Manual warp corrections by projector ID, kept across recalibration
+    warp_corrections: BTreeMap<u32, WarpCorrection>,
+    /// Touch-up in progress (test grids projected through the corrected mapping)
+    touch_up: Option<TouchUp>,
     /// UI state
     ui_state: UiState,
     /// Last frame time for FPS calculation
@@ -84,15 +141,25 @@ impl CalibrationApp {
             window: None,
             render: None,
             pattern_renderer: None,
+            pattern_presenter: None,
             preview_renderer: None,
+            preview_texture: None,
             egui_ctx: egui::Context::default(),
             egui_state: None,
             egui_renderer: None,
             ndi_finder: None,
-            ndi_receiver: None,
+            camera: None,
+            extra_cameras: Vec::new(),
             ndi_sources: Vec::new(),
+            webcam_sources: list_webcams(),
             calibration_session: CalibrationSession::new(CalibrationConfig::default()),
             calibration_state: CalibrationState::Idle,
+            photometric_session: PhotometricSession::new(PhotometricConfig::default()),
+            checkerboards: vec![CheckerboardSet::new(CheckerboardSpec::default())],
+            surface: None,
+            content_mapping: None,
+            warp_corrections: BTreeMap::new(),
+            touch_up: None,
             ui_state: UiState::default(),
             last_frame_time: Instant::now(),
         }
@@ -215,7 +282,7 @@ impl CalibrationApp {
                     // Toggle calibration
                     match &self.calibration_state {
                         CalibrationState::Idle => {
-                            if self.ndi_receiver.is_some() {
+                            if self.camera.is_some() {
                                 log::info!("Starting calibration...");
                                 // Would start calibration here
                             }
@@ -237,6 +304,24 @@ impl CalibrationApp {
         }
     }
 
+    /// Every source the camera pickers offer (folders are chosen separately).
+    fn camera_source_options(&self) -> Vec<CameraSourceKind> {
+        self.ndi_sources
+            .iter()
+            .map(|name| CameraSourceKind::Ndi(name.clone()))
+            .chain(self.webcam_sources.iter().cloned())
+            .chain(std::iter::once(CameraSourceKind::Folder {
+                path: Default::default(),
+                settle_time: Duration::from_secs_f32(self.ui_state.folder_capture_delay),
+            }))
+            .collect()
+    }
+
+    /// Capture timing that suits every connected camera.
+    fn capture_timing(&self) -> Option<CaptureTiming> {
+        CaptureTiming::combine(self.camera.iter().chain(&self.extra_cameras).map(|c| c.timing()))
+    }
+
     fn render_frame(&mut self) {
         // Update NDI sources periodically
         self.update_ndi_sources();
@@ -244,6 +329,20 @@ impl CalibrationApp {
         // Update calibration session state machine
         self.calibration_session.update();
 
+        // Send the current pattern to the remote output
+        if let Some(presenter) = &mut self.pattern_presenter {
+            if let Err(e) = presenter.update(&self.calibration_session) {
+                log::error!("Pattern output failed, stopping calibration: {}", e);
+                self.calibration_session.cancel();
+                self.pattern_presenter = None;
+            }
+        }
+
+        // The mapping being touched up is gone once the session restarts
+        if self.touch_up.is_some() && !self.calibration_session.state.is_complete() {
+            self.stop_touch_up();
+        }
+
         // Process calibration if in decoding/homography state
         if matches!(
             self.calibration_session.state,
@@ -252,9 +351,12 @@ impl CalibrationApp {
             self.calibration_session.process_calibration();
         }
 
+        // Update color matching state machine
+        self.photometric_session.update();
+
         // Get camera frame if available
-        let camera_frame = if let Some(receiver) = &mut self.ndi_receiver {
-            receiver.take_frame()
+        let camera_frame = if let Some(camera) = &mut self.camera {
+            camera.take_frame()
         } else {
             None
         };
@@ -266,7 +368,22 @@ impl CalibrationApp {
                 .chunks(4)
                 .map(|bgra| bgra[1]) // Green channel
                 .collect();
+            self.capture_checkerboard(0, &grayscale, frame.width, frame.height);
             self.calibration_session.submit_frame(grayscale, frame.width, frame.height);
+
+            // Color matching needs all three channels
+            self.photometric_session.submit_frame(&frame.data, frame.width, frame.height);
+        }
+
+        // Additional cameras capture the same patterns
+        for camera in 1..=self.extra_cameras.len() {
+            let Some(frame) = self.extra_cameras[camera - 1].take_frame() else {
+                continue;
+            };
+            let grayscale: Vec<u8> = frame.data.chunks(4).map(|bgra| bgra[1]).collect();
+            self.capture_checkerboard(camera, &grayscale, frame.width, frame.height);
+            self.calibration_session
+                .submit_camera_frame(camera, grayscale, frame.width, frame.height);
         }
 
         // Upload camera frame for preview
@@ -276,6 +393,27 @@ impl CalibrationApp {
             &mut self.preview_renderer,
         ) {
             preview.upload_frame(render.device(), render.queue(), &frame.data, frame.width, frame.height);
+
+            // The texture is recreated when the frame size changes
+            if let (Some(view), Some(egui_renderer)) = (preview.texture_view(), &mut self.egui_renderer) {
+                let size = preview.dimensions();
+                match self.preview_texture {
+                    Some((_, registered)) if registered == size => {}
+                    Some((id, _)) => {
+                        egui_renderer.update_egui_texture_from_wgpu_texture(
+                            render.device(),
+                            view,
+                            wgpu::FilterMode::Linear,
+                            id,
+                        );
+                        self.preview_texture = Some((id, size));
+                    }
+                    None => {
+                        let id = egui_renderer.register_native_texture(render.device(), view, wgpu::FilterMode::Linear);
+                        self.preview_texture = Some((id, size));
+                    }
+                }
+            }
         }
 
         // Get window reference for egui input
@@ -384,7 +522,291 @@ impl CalibrationApp {
         output.present();
     }
 
-    fn draw_ui(&mut self, _camera_frame: &Option<crate::camera::NdiFrame>) {
+    /// Add a frame to the selected camera's checkerboard views, if one was requested.
+    fn capture_checkerboard(&mut self, camera: usize, grayscale: &[u8], width: u32, height: u32) {
+        if !self.ui_state.capture_checkerboard || self.ui_state.checkerboard_camera != camera {
+            return;
+        }
+        self.ui_state.capture_checkerboard = false;
+        let Some(checkerboard) = self.checkerboards.get_mut(camera) else {
+            return;
+        };
+        match checkerboard.add_image(grayscale, width, height) {
+            Ok(true) => log::info!("Captured checkerboard view {}", checkerboard.views.len()),
+            Ok(false) => log::warn!("No checkerboard found in the camera frame"),
+            Err(e) => log::error!("Failed to capture checkerboard view: {}", e),
+        }
+    }
+
+    /// Save the calibration session, captures included.
+    fn save_session_file(&mut self) {
+        let Some(path) = rfd::FileDialog::new()
+            .set_title("Save Session")
+            .add_filter("Calibration Session", &["pmsession"])
+            .set_file_name("calibration.pmsession")
+            .save_file()
+        else {
+            return;
+        };
+        if let Err(e) = save_session(&self.calibration_session, &self.checkerboards, &self.warp_corrections, &path) {
+            log::error!("Failed to save session: {}", e);
+        }
+    }
+
+    /// Replace the calibration session with a saved one.
+    fn open_session_file(&mut self) {
+        let Some(path) = rfd::FileDialog::new()
+            .set_title("Open Session")
+            .add_filter("Calibration Session", &["pmsession"])
+            .pick_file()
+        else {
+            return;
+        };
+        let mut file = match load_session(&path) {
+            Ok(file) => file,
+            Err(e) => {
+                log::error!("Failed to open session: {}", e);
+                return;
+            }
+        };
+        self.stop_touch_up();
+        self.warp_corrections = std::mem::take(&mut file.warp_corrections);
+        let (session, mut checkerboards) = file.into_session();
+
+        // One checkerboard set per camera, primary first
+        checkerboards.resize_with(1 + session.extra_cameras.len(), || {
+            CheckerboardSet::new(CheckerboardSpec::default())
+        });
+        if let Some(projector) = session.projectors.first() {
+            self.ui_state.projector_width = projector.projector_width;
+            self.ui_state.projector_height = projector.projector_height;
+        }
+        self.ui_state.projector_count = session.projectors.len() as u32;
+        self.ui_state.phase_shift = session.config.phase_shift.is_some();
+        self.ui_state.overlap_result = None;
+        self.surface = None;
+        self.content_mapping = None;
+        self.checkerboards = checkerboards;
+        self.calibration_session = session;
+    }
+
+    /// Merge the cameras into one content space when there are several.
+    fn camera_merge(&self) -> Option<CameraMerge> {
+        if self.calibration_session.camera_count() < 2 {
+            return None;
+        }
+        match merge_cameras(&self.calibration_session) {
+            Ok(merge) => Some(merge),
+            Err(e) => {
+                log::error!("Failed to merge cameras, using the primary camera only: {}", e);
+                None
+            }
+        }
+    }
+
+    /// Per-pixel UV maps of the automatic solution, one per decoded projector.
+    fn automatic_uv_maps(&self, merge: Option<&CameraMerge>) -> Vec<UvMap> {
+        use crate::export::{CalibrationExporter, UV_MAP_GRID_SPACING};
+
+        let session = &self.calibration_session;
+        session
+            .projectors
+            .iter()
+            .filter_map(|p| {
+                // Non-planar calibration maps into the fitted content space
+                if let (Some(surface), Some(mapping)) = (&self.surface, &self.content_mapping) {
+                    return surface.uv_map(session, p.projector_id, mapping, UV_MAP_GRID_SPACING);
+                }
+                let columns = p.projector_width / UV_MAP_GRID_SPACING + 1;
+                let rows = p.projector_height / UV_MAP_GRID_SPACING + 1;
+                let dense = match merge {
+                    Some(merge) => merge.correspondence_map(session, p.projector_id, columns, rows)?,
+                    None => p.correspondences.as_ref()?.to_correspondence_map(p.projector_id, columns, rows),
+                };
+                Some(CalibrationExporter::uv_map_from_correspondences(&dense))
+            })
+            .collect()
+    }
+
+    /// Content space the UV maps point into.
+    fn blend_space(&self, merge: Option<&CameraMerge>) -> BlendSpace {
+        let config = &self.calibration_session.config;
+        match (&self.content_mapping, merge) {
+            (Some(mapping), _) if self.surface.is_some() => BlendSpace {
+                aspect: mapping.aspect() as f32,
+                wraps_u: mapping.wraps_u(),
+            },
+            (_, Some(merge)) => BlendSpace {
+                aspect: merge.content_width as f32 / merge.content_height.max(1) as f32,
+                wraps_u: false,
+            },
+            _ => BlendSpace {
+                aspect: config.camera_width as f32 / config.camera_height.max(1) as f32,
+                wraps_u: false,
+            },
+        }
+    }
+
+    /// Project test grids through the current mapping on every calibrated projector.
+    fn start_touch_up(&mut self) {
+        let merge = self.camera_merge();
+        let mut touch_up = TouchUp::new(self.automatic_uv_maps(merge.as_ref()), self.blend_space(merge.as_ref()).aspect);
+        touch_up.grid_divisions = self.ui_state.touch_up_grid_divisions;
+        if touch_up.maps.is_empty() {
+            log::error!("Nothing to touch up: no projector has a mapping");
+            return;
+        }
+        // Handles can only be drawn over the camera image when it is the content space
+        self.ui_state.touch_up_over_camera = merge.is_none() && self.surface.is_none();
+        if touch_up.map(self.ui_state.touch_up_projector).is_none() {
+            self.ui_state.touch_up_projector = touch_up.maps[0].projector_id;
+        }
+        let ids: Vec<u32> = touch_up.maps.iter().map(|m| m.projector_id).collect();
+        self.touch_up = Some(touch_up);
+        for id in ids {
+            self.send_touch_up_grid(id);
+        }
+    }
+
+    /// Re-project one projector's test grid after its correction changed.
+    fn send_touch_up_grid(&mut self, projector_id: u32) {
+        let (Some(touch_up), Some(presenter)) = (&self.touch_up, &mut self.pattern_presenter) else {
+            return;
+        };
+        let Some(grid) = touch_up.test_grid(projector_id, self.warp_corrections.get(&projector_id)) else {
+            return;
+        };
+        if let Err(e) = presenter.show_image(projector_id, &grid) {
+            log::error!("Failed to show test grid on projector {}: {}", projector_id, e);
+        }
+    }
+
+    /// Return the projectors to their content.
+    fn stop_touch_up(&mut self) {
+        if self.touch_up.take().is_none() {
+            return;
+        }
+        if let Some(presenter) = &mut self.pattern_presenter {
+            if let Err(e) = presenter.clear_all() {
+                log::error!("Failed to clear test grids: {}", e);
+            }
+        }
+    }
+
+    /// Export the finished calibration (project file plus correspondence maps).
+    fn export_calibration(&mut self) {
+        use crate::config::BlendCurve;
+        use crate::export::{CalibrationExporter, CORRESPONDENCE_GRID_COLUMNS, CORRESPONDENCE_GRID_ROWS};
+
+        let Some(path) = rfd::FileDialog::new()
+            .set_title("Export Calibration")
+            .add_filter("Calibration JSON", &["json"])
+            .add_filter("Calibration XML", &["projmap", "xml"])
+            .set_file_name("calibration.json")
+            .save_file()
+        else {
+            return;
+        };
+
+        let curve = match self.ui_state.blend_curve.as_str() {
+            "Linear" => BlendCurve::Linear,
+            "Gamma" => BlendCurve::Gamma,
+            "Cosine" => BlendCurve::Cosine,
+            _ => BlendCurve::Smoothstep,
+        };
+        let name = path
+            .file_stem()
+            .map(|s| s.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "Calibration".to_string());
+        let mut project = CalibrationExporter::project_from_session(
+            &name,
+            &self.calibration_session,
+            self.ui_state.overlap_result.as_ref(),
+            curve,
+        );
+        project.camera_source = self
+            .camera
+            .as_ref()
+            .map(|c| c.source_name().to_string());
+        project.extra_camera_sources = self
+            .calibration_session
+            .extra_cameras
+            .iter()
+            .map(|c| c.name.clone())
+            .collect();
+
+        // Several cameras are merged into one content space first
+        let session = &self.calibration_session;
+        let merge = self.camera_merge();
+
+        let maps: Vec<_> = session
+            .projectors
+            .iter()
+            .filter_map(|p| match &merge {
+                Some(merge) => merge.correspondence_map(
+                    session,
+                    p.projector_id,
+                    CORRESPONDENCE_GRID_COLUMNS,
+                    CORRESPONDENCE_GRID_ROWS,
+                ),
+                None => p.correspondences.as_ref().map(|c| {
+                    c.to_correspondence_map(
+                        p.projector_id,
+                        CORRESPONDENCE_GRID_COLUMNS,
+                        CORRESPONDENCE_GRID_ROWS,
+                    )
+                }),
+            })
+            .collect();
+
+        if let Some(merge) = &merge {
+            project.camera_width = merge.content_width;
+            project.camera_height = merge.content_height;
+            for projector in &mut project.projectors {
+                projector.homography = maps
+                    .iter()
+                    .find(|m| m.projector_id == projector.id)
+                    .and_then(CameraMerge::homography)
+                    .map(|m| [m[0][0], m[0][1], m[0][2], m[1][0], m[1][1], m[1][2], m[2][0], m[2][1], m[2][2]]);
+            }
+        }
+
+        // Manual touch-up goes on top of the automatic solution
+        let uv_maps: Vec<_> = self
+            .automatic_uv_maps(merge.as_ref())
+            .into_iter()
+            .map(|map| match self.warp_corrections.get(&map.projector_id) {
+                Some(correction) => correction.apply(&map),
+                None => map,
+            })
+            .collect();
+        for projector in &mut project.projectors {
+            projector.warp_correction = self.warp_corrections.get(&projector.id).cloned();
+        }
+
+        // Blend weights from where each projector lands in the same content space
+        let blend_masks = footprint_blend_masks(&uv_maps, self.blend_space(merge.as_ref()), curve);
+
+        match CalibrationExporter::export_calibration(
+            &mut project,
+            &maps,
+            &uv_maps,
+            &blend_masks,
+            &self.photometric_session.matches,
+            &path,
+        ) {
+            Ok(()) => log::info!("Calibration exported to {:?}", path),
+            Err(e) => log::error!("Failed to export calibration: {}", e),
+        }
+    }
+
+    fn draw_ui(&mut self, _camera_frame: &Option<crate::camera::CameraFrame>) {
+        let mut export_requested = false;
+        let mut save_session_requested = false;
+        let mut open_session_requested = false;
+        let mut start_touch_up_requested = false;
+        let mut stop_touch_up_requested = false;
+        let mut resend_grids: Vec<u32> = Vec::new();
         egui::TopBottomPanel::top("menu_bar").show(&self.egui_ctx, |ui| {
             egui::menu::bar(ui, |ui| {
                 ui.menu_button("File", |ui| {
@@ -395,8 +817,23 @@ impl CalibrationApp {
                         ui.close_menu();
                     }
                     ui.separator();
-                    if ui.button("Export Calibration...").clicked() {
+                    let can_export = self.calibration_session.state.is_complete();
+                    if ui.add_enabled(can_export, egui::Button::new("Export Calibration...")).clicked() {
                         ui.close_menu();
+                        export_requested = true;
+                    }
+                    ui.separator();
+                    let has_projectors = !self.calibration_session.projectors.is_empty();
+                    if ui.add_enabled(has_projectors, egui::Button::new("Save Session...")).clicked() {
+                        ui.close_menu();
+                        save_session_requested = true;
+                    }
+                    let can_open = self.calibration_session.state.is_idle()
+                        || self.calibration_session.state.is_complete()
+                        || self.calibration_session.state.is_error();
+                    if ui.add_enabled(can_open, egui::Button::new("Open Session...")).clicked() {
+                        ui.close_menu();
+                        open_session_requested = true;
                     }
                     ui.separator();
                     if ui.button("Exit").clicked() {
@@ -411,49 +848,99 @@ impl CalibrationApp {
             });
         });
 
+        if export_requested {
+            self.export_calibration();
+        }
+        if save_session_requested {
+            self.save_session_file();
+        }
+        if open_session_requested {
+            self.open_session_file();
+        }
+
+        let sources = self.camera_source_options();
+        let capture_timing = self.capture_timing();
         egui::SidePanel::left("settings_panel")
             .min_width(300.0)
             .show(&self.egui_ctx, |ui| {
                 ui.heading("Camera");
                 ui.separator();
 
-                // NDI source selection
+                // Camera source selection
                 ui.horizontal(|ui| {
-                    ui.label("NDI Source:");
-                    egui::ComboBox::from_id_salt("ndi_source")
-                        .selected_text(
-                            self.ndi_receiver
-                                .as_ref()
-                                .map(|r| r.source_name())
-                                .unwrap_or("Select..."),
-                        )
-                        .show_ui(ui, |ui| {
-                            for source in &self.ndi_sources {
-                                if ui.selectable_label(false, source).clicked() {
-                                    match NdiReceiver::connect(source) {
-                                        Ok(receiver) => {
-                                            self.ndi_receiver = Some(receiver);
-                                            log::info!("Connected to NDI source: {}", source);
-                                        }
-                                        Err(e) => {
-                                            log::error!("Failed to connect to NDI source: {}", e);
-                                        }
-                                    }
-                                }
+                    ui.label("Source:");
+                    let selected = self.camera.as_ref().map(|c| c.source_name()).unwrap_or("Select...");
+                    if let Some(source) = pick_camera_source(ui, "camera_source", selected, &sources) {
+                        match source.connect() {
+                            Ok(camera) => {
+                                self.camera = Some(camera);
+                                log::info!("Connected to camera: {}", source.label());
                             }
-                        });
+                            Err(e) => {
+                                log::error!("Failed to connect to camera: {}", e);
+                            }
+                        }
+                    }
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Folder Capture Delay:");
+                    ui.add(
+                        egui::DragValue::new(&mut self.ui_state.folder_capture_delay)
+                            .range(0.0..=30.0)
+                            .speed(0.1)
+                            .suffix(" s"),
+                    );
                 });
 
-                if let Some(receiver) = &self.ndi_receiver {
+                if let Some(camera) = &self.camera {
                     ui.label(format!(
                         "Status: {} ({}x{} @ {:.1} fps)",
-                        if receiver.is_connected() { "Connected" } else { "Connecting..." },
-                        receiver.width(),
-                        receiver.height(),
-                        receiver.average_fps()
+                        if camera.is_connected() { "Connected" } else { "Connecting..." },
+                        camera.width(),
+                        camera.height(),
+                        camera.average_fps()
                     ));
                 }
 
+                // Additional cameras capture alongside the primary one
+                ui.label("Additional Cameras:");
+                let can_edit_cameras = self.calibration_session.state.is_idle();
+                let mut remove_camera = None;
+                for (i, camera) in self.extra_cameras.iter().enumerate() {
+                    ui.horizontal(|ui| {
+                        ui.label(format!(
+                            "Camera {}: {} ({})",
+                            i + 2,
+                            camera.source_name(),
+                            if camera.is_connected() { "Connected" } else { "Connecting..." }
+                        ));
+                        if ui.add_enabled(can_edit_cameras, egui::Button::new("x").small()).clicked() {
+                            remove_camera = Some(i);
+                        }
+                    });
+                }
+                if let Some(i) = remove_camera {
+                    self.extra_cameras.remove(i);
+                    self.checkerboards.remove(i + 1);
+                    self.ui_state.checkerboard_camera = 0;
+                    self.surface = None;
+                    self.content_mapping = None;
+                }
+                ui.add_enabled_ui(can_edit_cameras, |ui| {
+                    if let Some(source) = pick_camera_source(ui, "extra_camera_source", "Add Camera...", &sources) {
+                        match source.connect() {
+                            Ok(camera) => {
+                                self.extra_cameras.push(camera);
+                                self.checkerboards.push(CheckerboardSet::new(CheckerboardSpec::default()));
+                                log::info!("Added camera: {}", source.label());
+                            }
+                            Err(e) => {
+                                log::error!("Failed to connect to camera: {}", e);
+                            }
+                        }
+                    }
+                });
+
                 ui.add_space(20.0);
                 ui.heading("Projectors");
                 ui.separator();
@@ -483,6 +970,57 @@ impl CalibrationApp {
                     });
                 }
 
+                ui.add_space(20.0);
+                ui.heading("Pattern Output");
+                ui.separator();
+
+                let can_change_output =
+                    self.calibration_session.current_pattern().is_none() && self.touch_up.is_none();
+                if let Some(presenter) = &self.pattern_presenter {
+                    let name = presenter.output_name();
+                    ui.horizontal(|ui| {
+                        ui.label(format!("Output: {}", name));
+                        if ui.add_enabled(can_change_output, egui::Button::new("Disconnect")).clicked() {
+                            self.pattern_presenter = None;
+                        }
+                    });
+                } else {
+                    ui.label("Output: Local projectors");
+                    let mut output_kind = None;
+                    ui.horizontal(|ui| {
+                        ui.label("NDI Name:");
+                        ui.text_edit_singleline(&mut self.ui_state.ndi_output_prefix);
+                        if ui.add_enabled(can_change_output, egui::Button::new("Send via NDI")).clicked() {
+                            output_kind = Some(PatternOutputKind::Ndi {
+                                prefix: self.ui_state.ndi_output_prefix.clone(),
+                            });
+                        }
+                    });
+                    ui.horizontal(|ui| {
+                        ui.label("Server:");
+                        ui.text_edit_singleline(&mut self.ui_state.server_address);
+                    });
+                    ui.horizontal(|ui| {
+                        ui.label("First Screen:");
+                        ui.add(egui::DragValue::new(&mut self.ui_state.server_first_screen));
+                        if ui.add_enabled(can_change_output, egui::Button::new("Connect")).clicked() {
+                            output_kind = Some(PatternOutputKind::Server {
+                                address: self.ui_state.server_address.clone(),
+                                first_screen: self.ui_state.server_first_screen,
+                            });
+                        }
+                    });
+                    if let Some(kind) = output_kind {
+                        match kind.connect() {
+                            Ok(output) => {
+                                log::info!("Sending patterns to {}", kind.label());
+                                self.pattern_presenter = Some(PatternPresenter::new(output));
+                            }
+                            Err(e) => log::error!("Failed to open pattern output: {}", e),
+                        }
+                    }
+                }
+
                 ui.add_space(20.0);
                 ui.heading("Calibration");
                 ui.separator();
@@ -497,13 +1035,33 @@ impl CalibrationApp {
                     ui.add(egui::ProgressBar::new(progress).show_percentage());
                 }
 
+                ui.checkbox(&mut self.ui_state.phase_shift, "Sub-pixel (phase shift)");
+
                 ui.horizontal(|ui| {
-                    let can_start = self.ndi_receiver.is_some()
+                    let can_start = self.camera.is_some()
                         && self.calibration_session.state.is_idle()
                         && self.ui_state.projector_count > 0;
 
                     if ui.add_enabled(can_start, egui::Button::new("Start Calibration")).clicked() {
                         log::info!("Starting calibration");
+                        self.calibration_session.projectors.clear();
+                        self.calibration_session.extra_cameras.clear();
+                        for camera in &self.extra_cameras {
+                            self.calibration_session.add_camera(
+                                camera.source_name(),
+                                camera.width(),
+                                camera.height(),
+                            );
+                        }
+                        if let Some(timing) = capture_timing {
+                            self.calibration_session.config.settle_time = timing.settle_time;
+                            self.calibration_session.config.frames_to_average = timing.frames_to_average;
+                        }
+                        if let Some(presenter) = &self.pattern_presenter {
+                            self.calibration_session.config.settle_time += presenter.latency();
+                        }
+                        self.calibration_session.config.phase_shift =
+                            self.ui_state.phase_shift.then(PhaseShiftConfig::default);
                         // Add projectors to session
                         for i in 0..self.ui_state.projector_count {
                             self.calibration_session.add_projector(
@@ -515,6 +1073,8 @@ impl CalibrationApp {
                         if let Err(e) = self.calibration_session.start() {
                             log::error!("Failed to start calibration: {}", e);
                         }
+                        self.surface = None;
+                        self.content_mapping = None;
                     }
 
                     let can_stop = !self.calibration_session.state.is_idle();
@@ -523,6 +1083,449 @@ impl CalibrationApp {
                     }
                 });
 
+                // Continue or re-decode a stopped or loaded session
+                ui.horizontal(|ui| {
+                    let session = &self.calibration_session;
+                    let can_resume = self.camera.is_some()
+                        && session.state.is_idle()
+                        && self.extra_cameras.len() == session.extra_cameras.len()
+                        && session.projectors.iter().any(|p| p.correspondences.is_none());
+                    if ui.add_enabled(can_resume, egui::Button::new("Resume")).clicked() {
+                        if let Some(timing) = capture_timing {
+                            self.calibration_session.config.settle_time = timing.settle_time;
+                            self.calibration_session.config.frames_to_average = timing.frames_to_average;
+                        }
+                        if let Some(presenter) = &self.pattern_presenter {
+                            self.calibration_session.config.settle_time += presenter.latency();
+                        }
+                        if let Err(e) = self.calibration_session.resume() {
+                            log::error!("Failed to resume calibration: {}", e);
+                        }
+                        self.surface = None;
+                        self.content_mapping = None;
+                    }
+
+                    let session = &self.calibration_session;
+                    let can_resolve = (session.state.is_idle() || session.state.is_complete())
+                        && !session.projectors.is_empty()
+                        && session.projectors.iter().all(|p| p.white_reference.is_some());
+                    if ui.add_enabled(can_resolve, egui::Button::new("Re-solve")).clicked() {
+                        match self.calibration_session.resolve() {
+                            Ok(()) => {
+                                self.ui_state.overlap_result = None;
+                                self.surface = None;
+                                self.content_mapping = None;
+                            }
+                            Err(e) => log::error!("Failed to re-solve calibration: {}", e),
+                        }
+                    }
+                });
+
+                ui.horizontal(|ui| {
+                    ui.label("Contrast Threshold:");
+                    ui.add(egui::Slider::new(&mut self.calibration_session.config.contrast_threshold, 0.01..=0.5));
+                });
+
+                // Offline calibration from recorded captures
+                ui.horizontal(|ui| {
+                    let has_captures = self
+                        .calibration_session
+                        .projectors
+                        .iter()
+                        .any(|p| p.white_reference.is_some());
+                    if ui.add_enabled(has_captures, egui::Button::new("Save Capture Run...")).clicked() {
+                        if let Some(dir) = rfd::FileDialog::new()
+                            .set_title("Save Capture Run")
+                            .pick_folder()
+                        {
+                            if let Err(e) = record_session(&self.calibration_session, &dir) {
+                                log::error!("Failed to save capture run: {}", e);
+                            }
+                        }
+                    }
+
+                    let can_load = self.calibration_session.state.is_idle()
+                        || self.calibration_session.state.is_complete()
+                        || self.calibration_session.state.is_error();
+                    if ui.add_enabled(can_load, egui::Button::new("Load Capture Run...")).clicked() {
+                        if let Some(dir) = rfd::FileDialog::new()
+                            .set_title("Load Capture Run")
+                            .pick_folder()
+                        {
+                            let result = CaptureRun::load(&dir)
+                                .map_err(|e| e.to_string())
+                                .and_then(|run| run.replay(&self.calibration_session.config));
+                            match result {
+                                Ok(session) => {
+                                    log::info!("Replayed capture run from {:?}", dir);
+                                    self.ui_state.projector_count = session.projectors.len() as u32;
+                                    self.ui_state.overlap_result = None;
+                                    self.surface = None;
+                                    self.content_mapping = None;
+                                    self.calibration_session = session;
+                                }
+                                Err(e) => log::error!("Failed to replay capture run: {}", e),
+                            }
+                        }
+                    }
+                });
+
+                // Crosshairs through the computed mapping, to measure residual error
+                ui.horizontal(|ui| {
+                    let can_verify = self.camera.is_some()
+                        && matches!(self.calibration_session.state, CalibrationState::Complete);
+                    if ui.add_enabled(can_verify, egui::Button::new("Verify")).clicked() {
+                        if let Some(timing) = capture_timing {
+                            self.calibration_session.config.settle_time = timing.settle_time;
+                            self.calibration_session.config.frames_to_average = timing.frames_to_average;
+                        }
+                        if let Some(presenter) = &self.pattern_presenter {
+                            self.calibration_session.config.settle_time += presenter.latency();
+                        }
+                        if let Err(e) = self.calibration_session.start_verification(VerificationConfig::default()) {
+                            log::error!("Failed to start verification: {}", e);
+                        }
+                    }
+
+                    let has_report = self.calibration_session.verification_report.is_some();
+                    if ui.add_enabled(has_report, egui::Button::new("Save Report...")).clicked() {
+                        if let Some(dir) = rfd::FileDialog::new()
+                            .set_title("Save Verification Report")
+                            .pick_folder()
+                        {
+                            if let Some(report) = &self.calibration_session.verification_report {
+                                if let Err(e) = report.save(&dir) {
+                                    log::error!("Failed to save verification report: {}", e);
+                                }
+                            }
+                        }
+                    }
+                });
+
+                if let Some(report) = &self.calibration_session.verification_report {
+                    for projector in &report.projectors {
+                        ui.label(format!(
+                            "Projector {}: {:.2}px RMS, {:.2}px max ({}/{} crosshairs, {:.0}% decoded)",
+                            projector.projector_id,
+                            projector.rms_error,
+                            projector.max_error,
+                            projector.targets_found,
+                            projector.targets_expected,
+                            projector.coverage * 100.0
+                        ));
+                    }
+                    for overlap in &report.overlaps {
+                        ui.label(format!(
+                            "Overlap {}-{}: {:.2}px RMS, {:.2}px max",
+                            overlap.projectors[0], overlap.projectors[1], overlap.rms_error, overlap.max_error
+                        ));
+                    }
+                }
+
+                ui.add_space(20.0);
+                ui.heading("Touch-Up");
+                ui.separator();
+
+                // Manual nudges on top of the decoded mapping, checked on a projected grid
+                if let Some(touch_up) = &mut self.touch_up {
+                    let selected = self.ui_state.touch_up_projector;
+                    egui::ComboBox::from_label("Projector")
+                        .selected_text(format!("Projector {}", selected))
+                        .show_ui(ui, |ui| {
+                            for map in &touch_up.maps {
+                                ui.selectable_value(
+                                    &mut self.ui_state.touch_up_projector,
+                                    map.projector_id,
+                                    format!("Projector {}", map.projector_id),
+                                );
+                            }
+                        });
+                    ui.horizontal(|ui| {
+                        ui.label("Grid Cells:");
+                        let cells = ui.add(
+                            egui::DragValue::new(&mut self.ui_state.touch_up_grid_divisions).range(4..=128),
+                        );
+                        if cells.changed() {
+                            touch_up.grid_divisions = self.ui_state.touch_up_grid_divisions;
+                            resend_grids.extend(touch_up.maps.iter().map(|m| m.projector_id));
+                        }
+                    });
+                    ui.label("Drag control points in the preview; the grid updates on release.");
+                    ui.horizontal(|ui| {
+                        if ui.button("Reset Projector").clicked() {
+                            self.warp_corrections.remove(&selected);
+                            resend_grids.push(selected);
+                        }
+                        if ui.button("Done").clicked() {
+                            stop_touch_up_requested = true;
+                        }
+                    });
+                } else {
+                    let can_touch_up = self.pattern_presenter.is_some()
+                        && matches!(self.calibration_session.state, CalibrationState::Complete);
+                    ui.horizontal(|ui| {
+                        if ui
+                            .add_enabled(can_touch_up, egui::Button::new("Start Touch-Up"))
+                            .on_disabled_hover_text("Needs a finished calibration and a pattern output")
+                            .clicked()
+                        {
+                            start_touch_up_requested = true;
+                        }
+                        let corrected = self.warp_corrections.values().filter(|c| !c.is_identity()).count();
+                        if ui.add_enabled(corrected > 0, egui::Button::new("Clear All")).clicked() {
+                            self.warp_corrections.clear();
+                        }
+                    });
+                }
+                let corrected = self.warp_corrections.values().filter(|c| !c.is_identity()).count();
+                if corrected > 0 {
+                    ui.label(format!("{} projector(s) corrected", corrected));
+                }
+
+                ui.add_space(20.0);
+                ui.heading("3D Calibration");
+                ui.separator();
+
+                // Camera intrinsics from checkerboard views, one set per camera
+                let camera_count = self.checkerboards.len();
+                if self.ui_state.checkerboard_camera >= camera_count {
+                    self.ui_state.checkerboard_camera = 0;
+                }
+                if camera_count > 1 {
+                    ui.horizontal(|ui| {
+                        ui.label("Camera:");
+                        egui::ComboBox::from_id_salt("checkerboard_camera")
+                            .selected_text(format!("Camera {}", self.ui_state.checkerboard_camera + 1))
+                            .show_ui(ui, |ui| {
+                                for camera in 0..camera_count {
+                                    ui.selectable_value(
+                                        &mut self.ui_state.checkerboard_camera,
+                                        camera,
+                                        format!("Camera {}", camera + 1),
+                                    );
+                                }
+                            });
+                    });
+                }
+                let board = self.ui_state.checkerboard_camera;
+                ui.add_enabled_ui(self.checkerboards[board].views.is_empty(), |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label("Checkerboard:");
+                        ui.add(egui::DragValue::new(&mut self.checkerboards[board].spec.columns).range(3..=40));
+                        ui.label("x");
+                        ui.add(egui::DragValue::new(&mut self.checkerboards[board].spec.rows).range(3..=40));
+                        ui.add(
+                            egui::DragValue::new(&mut self.checkerboards[board].spec.square_size)
+                                .range(1.0..=500.0)
+                                .suffix(" mm"),
+                        );
+                    });
+                });
+
+                ui.horizontal(|ui| {
+                    let has_camera = match board {
+                        0 => self.camera.is_some(),
+                        _ => board <= self.extra_cameras.len(),
+                    };
+                    let can_capture = has_camera && !self.ui_state.capture_checkerboard;
+                    if ui.add_enabled(can_capture, egui::Button::new("Add View")).clicked() {
+                        self.ui_state.capture_checkerboard = true;
+                    }
+                    if ui.button("Load Images...").clicked() {
+                        if let Some(paths) = rfd::FileDialog::new()
+                            .set_title("Load Checkerboard Images")
+                            .add_filter("Images", &["png", "jpg", "jpeg", "tif", "tiff", "bmp"])
+                            .pick_files()
+                        {
+                            for path in paths {
+                                let result = image::open(&path).map_err(|e| e.to_string()).and_then(|img| {
+                                    let gray = img.to_luma8();
+                                    self.checkerboards[board].add_image(gray.as_raw(), gray.width(), gray.height())
+                                });
+                                match result {
+                                    Ok(true) => {}
+                                    Ok(false) => log::warn!("No checkerboard found in {:?}", path),
+                                    Err(e) => log::error!("Failed to load {:?}: {}", path, e),
+                                }
+                            }
+                        }
+                    }
+                    if ui.button("Clear").clicked() {
+                        self.checkerboards[board].clear();
+                    }
+                });
+
+                ui.horizontal(|ui| {
+                    ui.label(format!("{} views", self.checkerboards[board].views.len()));
+                    let can_calibrate = self.checkerboards[board].views.len() >= MIN_CHECKERBOARD_VIEWS;
+                    if ui.add_enabled(can_calibrate, egui::Button::new("Calibrate Camera")).clicked() {
+                        match self.checkerboards[board].calibrate() {
+                            Ok(calibration) => log::info!(
+                                "Camera calibrated: f = {:.1}, error {:.2}px",
+                                calibration.intrinsics.fx,
+                                calibration.reprojection_error
+                            ),
+                            Err(e) => log::error!("Camera calibration failed: {}", e),
+                        }
+                        self.surface = None;
+                        self.content_mapping = None;
+                    }
+                });
+                if let Some(calibration) = &self.checkerboards[board].calibration {
+                    ui.label(format!(
+                        "Camera: f = {:.1}, FOV {:.1} deg, error {:.2} px",
+                        calibration.intrinsics.fx,
+                        calibration.intrinsics.horizontal_fov(),
+                        calibration.reprojection_error
+                    ));
+                }
+
+                // Projector lens guess and content space
+                ui.horizontal(|ui| {
+                    ui.label("Throw Ratio:");
+                    ui.add(
+                        egui::DragValue::new(&mut self.ui_state.reconstruction.throw_ratio)
+                            .range(0.2..=5.0)
+                            .speed(0.01),
+                    );
+                    ui.label("Lens Shift:");
+                    ui.add(
+                        egui::DragValue::new(&mut self.ui_state.reconstruction.lens_shift)
+                            .range(-1.0..=1.0)
+                            .speed(0.01),
+                    );
+                });
+                ui.checkbox(&mut self.ui_state.reconstruction.refine_lens, "Refine projector lens");
+
+                ui.horizontal(|ui| {
+                    ui.label("Content Space:");
+                    egui::ComboBox::from_id_salt("content_space")
+                        .selected_text(self.ui_state.content_space.name())
+                        .show_ui(ui, |ui| {
+                            for space in [ContentSpace::Equirectangular, ContentSpace::Cylinder] {
+                                let selected = std::mem::discriminant(&space)
+                                    == std::mem::discriminant(&self.ui_state.content_space);
+                                if ui.selectable_label(selected, space.name()).clicked() {
+                                    self.ui_state.content_space = space;
+                                }
+                            }
+                        });
+                    if ui.button("Load Mesh...").clicked() {
+                        if let Some(path) = rfd::FileDialog::new()
+                            .set_title("Load Surface Mesh")
+                            .add_filter("Wavefront OBJ", &["obj"])
+                            .pick_file()
+                        {
+                            match MeshSurface::load_obj(&path) {
+                                Ok(mesh) => {
+                                    log::info!("Loaded mesh with {} triangles", mesh.triangle_count());
+                                    self.ui_state.content_space = ContentSpace::Mesh(mesh);
+                                }
+                                Err(e) => log::error!("Failed to load mesh: {}", e),
+                            }
+                        }
+                    }
+                });
+
+                let can_reconstruct = self.checkerboards.iter().any(|c| c.calibration.is_some())
+                    && self.calibration_session.state.is_complete();
+                if ui.add_enabled(can_reconstruct, egui::Button::new("Reconstruct Surface")).clicked() {
+                    let intrinsics: Vec<_> = self
+                        .checkerboards
+                        .iter()
+                        .map(|c| c.calibration.as_ref().map(|c| c.intrinsics))
+                        .collect();
+                    let result = reconstruct_rig(&self.calibration_session, &intrinsics, &self.ui_state.reconstruction)
+                        .and_then(|surface| {
+                            let mapping = surface.fit_content(&self.ui_state.content_space)?;
+                            Ok((surface, mapping))
+                        });
+                    match result {
+                        Ok((surface, mapping)) => {
+                            self.surface = Some(surface);
+                            self.content_mapping = Some(mapping);
+                        }
+                        Err(e) => {
+                            log::error!("Surface reconstruction failed: {}", e);
+                            self.surface = None;
+                            self.content_mapping = None;
+                        }
+                    }
+                }
+
+                if let (Some(surface), Some(mapping)) = (&self.surface, &self.content_mapping) {
+                    for (camera, projector) in surface.projectors() {
+                        ui.label(format!(
+                            "Projector {} (camera {}): throw {:.2}, {} points, error {:.2} px",
+                            projector.projector_id + 1,
+                            camera + 1,
+                            projector.intrinsics.fx / projector.intrinsics.width as f64,
+                            projector.inlier_count,
+                            projector.reprojection_error
+                        ));
+                    }
+                    ui.label(format!("Content: {}", mapping.describe()));
+                }
+
+                ui.add_space(20.0);
+                ui.heading("Color Matching");
+                ui.separator();
+
+                ui.label(format!("State: {}", self.photometric_session.state));
+
+                let progress = self.photometric_session.progress();
+                if progress > 0.0 && progress < 1.0 {
+                    ui.add(egui::ProgressBar::new(progress).show_percentage());
+                }
+
+                ui.horizontal(|ui| {
+                    ui.label("Ramp Steps:");
+                    ui.add(egui::Slider::new(&mut self.ui_state.color_ramp_levels, 3..=17));
+                });
+
+                ui.horizontal(|ui| {
+                    // Color matching uses the camera directly, so it can run before
+                    // or after geometric calibration, but not at the same time
+                    let geometry_busy = !self.calibration_session.state.is_idle()
+                        && !self.calibration_session.state.is_complete();
+                    let can_start = self.camera.is_some()
+                        && !geometry_busy
+                        && !matches!(self.photometric_session.state, PhotometricState::Measuring { .. })
+                        && self.ui_state.projector_count > 0;
+
+                    if ui.add_enabled(can_start, egui::Button::new("Start Color Match")).clicked() {
+                        self.photometric_session.clear();
+                        self.photometric_session.config.levels = self.ui_state.color_ramp_levels;
+                        if let Some(timing) = capture_timing {
+                            self.photometric_session.config.settle_time = timing.settle_time;
+                            self.photometric_session.config.frames_to_average = timing.frames_to_average;
+                        }
+                        for i in 0..self.ui_state.projector_count {
+                            self.photometric_session.add_projector(i);
+                        }
+                        if let Err(e) = self.photometric_session.start() {
+                            log::error!("Failed to start color matching: {}", e);
+                        }
+                    }
+
+                    let can_stop = !self.photometric_session.state.is_idle();
+                    if ui.add_enabled(can_stop, egui::Button::new("Stop")).clicked() {
+                        self.photometric_session.cancel();
+                    }
+                });
+
+                for color in &self.photometric_session.matches {
+                    let c = &color.correction;
+                    ui.label(format!(
+                        "Projector {}: RGB {:.2}/{:.2}/{:.2}, gamma {:.2}",
+                        color.projector_id + 1,
+                        c.red,
+                        c.green,
+                        c.blue,
+                        c.gamma
+                    ));
+                }
+
                 ui.add_space(20.0);
                 ui.heading("Edge Blending");
                 ui.separator();
@@ -611,8 +1614,52 @@ impl CalibrationApp {
 
         egui::CentralPanel::default().show(&self.egui_ctx, |ui| {
             ui.heading("Camera Preview");
-            // Camera preview would go here
-            ui.label("Connect to an NDI camera to see preview");
+
+            // Touch-up draws on blank content space when it is not the camera image
+            let over_camera = self.touch_up.is_none() || self.ui_state.touch_up_over_camera;
+            let aspect = match (&self.touch_up, self.preview_texture) {
+                (Some(touch_up), _) if !over_camera => touch_up.aspect,
+                (_, Some((_, (width, height)))) => width as f32 / height.max(1) as f32,
+                (Some(touch_up), None) => touch_up.aspect,
+                (None, None) => {
+                    ui.label("Connect a camera to see preview");
+                    return;
+                }
+            };
+            let available = ui.available_size();
+            let size = if available.x / available.y.max(1.0) > aspect {
+                egui::vec2(available.y * aspect, available.y)
+            } else {
+                egui::vec2(available.x, available.x / aspect)
+            };
+            let (rect, _) = ui.allocate_exact_size(size, egui::Sense::hover());
+
+            match self.preview_texture {
+                Some((texture, _)) if over_camera => {
+                    let uv = egui::Rect::from_min_max(egui::pos2(0.0, 0.0), egui::pos2(1.0, 1.0));
+                    ui.painter().image(texture, rect, uv, egui::Color32::WHITE);
+                }
+                _ => {
+                    ui.painter().rect_filled(rect, 0.0, egui::Color32::from_gray(24));
+                }
+            }
+
+            if let Some(touch_up) = &self.touch_up {
+                let projector_id = self.ui_state.touch_up_projector;
+                let mut correction = self
+                    .warp_corrections
+                    .get(&projector_id)
+                    .cloned()
+                    .unwrap_or_else(|| WarpCorrection::new(TOUCH_UP_COLUMNS, TOUCH_UP_ROWS));
+                let handles = touch_up.handles(projector_id, &correction);
+                let response = correction_overlay(ui, rect, &handles, &mut correction);
+                if response.changed {
+                    self.warp_corrections.insert(projector_id, correction);
+                }
+                if response.released {
+                    resend_grids.push(projector_id);
+                }
+            }
         });
 
         egui::TopBottomPanel::bottom("status_bar").show(&self.egui_ctx, |ui| {
@@ -627,6 +1674,17 @@ impl CalibrationApp {
                 ui.label(format!("NDI Sources: {}", self.ndi_sources.len()));
             });
         });
+
+        if start_touch_up_requested {
+            self.start_touch_up();
+        }
+        if stop_touch_up_requested {
+            self.stop_touch_up();
+        }
+        resend_grids.dedup();
+        for projector_id in resend_grids {
+            self.send_touch_up_grid(projector_id);
+        }
     }
 }
 
@@ -0,0 +1,380 @@
+//! Blend masks from projector footprints in content space.
+//!
+//! Each projector's footprint is rasterized into a content-space grid from its
+//! UV map, and a Euclidean distance transform gives every content cell its
+//! distance to the footprint boundary. A projector pixel's weight is its own
+//! distance relative to the other projectors' distances at the same content
+//! point, shaped by the blend curve and normalized so the weights of all
+//! projectors sum to 1. Unlike the edge ramps this follows keystoned and
+//! curved overlaps exactly.
+
+use super::BlendMask;
+use crate::config::{BlendCurve, UvMap};
+
+/// Content grid cells along the longer content axis.
+const CONTENT_RESOLUTION: u32 = 1024;
+
+/// Projector pixels between rasterized footprint vertices.
+const FOOTPRINT_STEP: u32 = 4;
+
+/// Content space the footprints are compared in.
+#[derive(Debug, Clone, Copy)]
+pub struct BlendSpace {
+    /// Content width over height, so distances are isotropic on the surface.
+    pub aspect: f32,
+    /// u = 0 and u = 1 are the same place (equirectangular domes).
+    pub wraps_u: bool,
+}
+
+/// Distance from each content cell to a footprint's boundary (0 outside).
+struct Footprint {
+    columns: usize,
+    rows: usize,
+    wraps_u: bool,
+    distance: Vec<f32>,
+}
+
+impl Footprint {
+    fn rasterize(uv_map: &UvMap, columns: usize, rows: usize, wraps_u: bool) -> Self {
+        let mut inside = vec![false; columns * rows];
+        let step = FOOTPRINT_STEP.min(uv_map.width.max(2) - 1).max(1);
+        let uv_at = |x: u32, y: u32| uv_map.data[(y * uv_map.width + x) as usize];
+        let to_cell = |uv: [f32; 2]| [uv[0] * columns as f32, uv[1] * rows as f32];
+
+        let mut y = 0;
+        while y + 1 < uv_map.height {
+            let y1 = (y + step).min(uv_map.height - 1);
+            let mut x = 0;
+            while x + 1 < uv_map.width {
+                let x1 = (x + step).min(uv_map.width - 1);
+                let mut quad = [uv_at(x, y), uv_at(x1, y), uv_at(x1, y1), uv_at(x, y1)];
+                if quad.iter().all(|uv| uv[0].is_finite() && uv[1].is_finite()) {
+                    // A quad straddling the seam is drawn past u = 1 and wrapped
+                    if wraps_u {
+                        let max_u = quad.iter().map(|uv| uv[0]).fold(f32::MIN, f32::max);
+                        for uv in &mut quad {
+                            if max_u - uv[0] > 0.5 {
+                                uv[0] += 1.0;
+                            }
+                        }
+                    }
+                    let cells = quad.map(to_cell);
+                    fill_triangle(&mut inside, columns, rows, wraps_u, [cells[0], cells[1], cells[2]]);
+                    fill_triangle(&mut inside, columns, rows, wraps_u, [cells[0], cells[2], cells[3]]);
+                }
+                x = x1;
+            }
+            y = y1;
+        }
+
+        // A footprint covering the whole grid has no boundary at all
+        let limit = (columns + rows) as f32;
+        let distance = distance_transform(&inside, columns, rows, wraps_u)
+            .into_iter()
+            .map(|d| d.min(limit))
+            .collect();
+        Self {
+            columns,
+            rows,
+            wraps_u,
+            distance,
+        }
+    }
+
+    /// Bilinearly sampled boundary distance at a content UV.
+    fn sample(&self, uv: [f32; 2]) -> f32 {
+        let x = uv[0] * self.columns as f32 - 0.5;
+        let y = (uv[1] * self.rows as f32 - 0.5).clamp(0.0, (self.rows - 1) as f32);
+        let x = if self.wraps_u {
+            x.rem_euclid(self.columns as f32)
+        } else {
+            x.clamp(0.0, (self.columns - 1) as f32)
+        };
+        let (x0, y0) = (x.floor() as usize, y.floor() as usize);
+        let x1 = if self.wraps_u {
+            (x0 + 1) % self.columns
+        } else {
+            (x0 + 1).min(self.columns - 1)
+        };
+        let y1 = (y0 + 1).min(self.rows - 1);
+        let (fx, fy) = (x - x0 as f32, y - y0 as f32);
+        let d = |cx: usize, cy: usize| self.distance[cy * self.columns + cx];
+        let top = d(x0, y0) + (d(x1, y0) - d(x0, y0)) * fx;
+        let bottom = d(x0, y1) + (d(x1, y1) - d(x0, y1)) * fx;
+        top + (bottom - top) * fy
+    }
+}
+
+/// Mark the cells whose centers fall inside a triangle (cell coordinates).
+fn fill_triangle(inside: &mut [bool], columns: usize, rows: usize, wraps_u: bool, t: [[f32; 2]; 3]) {
+    let edge = |a: [f32; 2], b: [f32; 2], p: [f32; 2]| (b[0] - a[0]) * (p[1] - a[1]) - (b[1] - a[1]) * (p[0] - a[0]);
+    let area = edge(t[0], t[1], t[2]);
+    if area == 0.0 {
+        return;
+    }
+
+    let min_x = t.iter().map(|p| p[0]).fold(f32::MAX, f32::min).floor() as i64;
+    let max_x = t.iter().map(|p| p[0]).fold(f32::MIN, f32::max).ceil() as i64;
+    let min_y = t.iter().map(|p| p[1]).fold(f32::MAX, f32::min).floor().max(0.0) as i64;
+    let max_y = (t.iter().map(|p| p[1]).fold(f32::MIN, f32::max).ceil() as i64).min(rows as i64 - 1);
+
+    for cy in min_y..=max_y {
+        for cx in min_x..=max_x {
+            let p = [cx as f32 + 0.5, cy as f32 + 0.5];
+            let w = [edge(t[1], t[2], p), edge(t[2], t[0], p), edge(t[0], t[1], p)];
+            if !w.iter().all(|&w| w * area >= 0.0) {
+                continue;
+            }
+            let column = if wraps_u {
+                cx.rem_euclid(columns as i64)
+            } else if (0..columns as i64).contains(&cx) {
+                cx
+            } else {
+                continue;
+            };
+            inside[cy as usize * columns + column as usize] = true;
+        }
+    }
+}
+
+/// Euclidean distance (in cells) from each inside cell to the nearest outside cell.
+///
+/// Felzenszwalb-Huttenlocher squared distance transform, columns then rows.
+/// The grid border is not a boundary: a footprint clipped by the content
+/// edge has no real projector edge there.
+fn distance_transform(inside: &[bool], columns: usize, rows: usize, wraps_u: bool) -> Vec<f32> {
+    let mut squared: Vec<f32> = inside.iter().map(|&i| if i { f32::INFINITY } else { 0.0 }).collect();
+
+    let mut line = Vec::new();
+    for x in 0..columns {
+        line.clear();
+        line.extend((0..rows).map(|y| squared[y * columns + x]));
+        let transformed = transform_1d(&line);
+        for (y, value) in transformed.into_iter().enumerate() {
+            squared[y * columns + x] = value;
+        }
+    }
+
+    for y in 0..rows {
+        let row = &mut squared[y * columns..(y + 1) * columns];
+        if wraps_u {
+            // Three copies side by side so distances cross the seam
+            line.clear();
+            for _ in 0..3 {
+                line.extend_from_slice(row);
+            }
+            let transformed = transform_1d(&line);
+            row.copy_from_slice(&transformed[columns..2 * columns]);
+        } else {
+            let transformed = transform_1d(row);
+            row.copy_from_slice(&transformed);
+        }
+    }
+
+    squared.into_iter().map(f32::sqrt).collect()
+}
+
+/// 1D squared distance transform of a sampled function (lower envelope of parabolas).
+fn transform_1d(f: &[f32]) -> Vec<f32> {
+    let n = f.len();
+    let mut result = vec![f32::INFINITY; n];
+    let sources: Vec<usize> = (0..n).filter(|&q| f[q].is_finite()).collect();
+    if sources.is_empty() {
+        return result;
+    }
+
+    let mut vertices: Vec<usize> = Vec::with_capacity(sources.len());
+    let mut bounds: Vec<f32> = Vec::with_capacity(sources.len() + 1);
+    let intersect = |p: usize, q: usize| {
+        ((f[q] + (q * q) as f32) - (f[p] + (p * p) as f32)) / (2.0 * (q as f32 - p as f32))
+    };
+
+    for &q in &sources {
+        while let Some(&p) = vertices.last() {
+            if intersect(p, q) <= *bounds.last().unwrap() {
+                vertices.pop();
+                bounds.pop();
+            } else {
+                break;
+            }
+        }
+        let start = vertices.last().map_or(f32::NEG_INFINITY, |&p| intersect(p, q));
+        vertices.push(q);
+        bounds.push(start);
+    }
+
+    let mut k = 0;
+    for (q, value) in result.iter_mut().enumerate() {
+        while k + 1 < vertices.len() && bounds[k + 1] < q as f32 {
+            k += 1;
+        }
+        let p = vertices[k];
+        let d = q as f32 - p as f32;
+        *value = d * d + f[p];
+    }
+    result
+}
+
+/// Blend masks for every projector from the footprints of their UV maps.
+///
+/// UV maps hold each projector pixel's content UV (NaN where unmapped); the
+/// masks have the UV maps' resolution and are 0 on unmapped pixels.
+pub fn footprint_blend_masks(uv_maps: &[UvMap], space: BlendSpace, curve: BlendCurve) -> Vec<BlendMask> {
+    let aspect = if space.aspect.is_finite() && space.aspect > 0.0 { space.aspect } else { 1.0 };
+    let (columns, rows) = if aspect >= 1.0 {
+        (CONTENT_RESOLUTION, ((CONTENT_RESOLUTION as f32 / aspect).round() as u32).max(1))
+    } else {
+        (((CONTENT_RESOLUTION as f32 * aspect).round() as u32).max(1), CONTENT_RESOLUTION)
+    };
+
+    let footprints: Vec<Footprint> = uv_maps
+        .iter()
+        .map(|m| Footprint::rasterize(m, columns as usize, rows as usize, space.wraps_u))
+        .collect();
+
+    uv_maps
+        .iter()
+        .enumerate()
+        .map(|(own, uv_map)| {
+            let mut mask = BlendMask::new(uv_map.width, uv_map.height);
+            mask.curve = curve;
+            for (value, &uv) in mask.data.iter_mut().zip(&uv_map.data) {
+                if !(uv[0].is_finite() && uv[1].is_finite()) {
+                    *value = 0.0;
+                    continue;
+                }
+                let distances: Vec<f32> = footprints.iter().map(|f| f.sample(uv)).collect();
+                *value = blend_weight(&distances, own, curve);
+            }
+            mask
+        })
+        .collect()
+}
+
+/// Weight of projector `own` given every projector's boundary distance at a point.
+fn blend_weight(distances: &[f32], own: usize, curve: BlendCurve) -> f32 {
+    let total: f32 = distances.iter().sum();
+    if total <= 0.0 {
+        // On the projector's own edge with no one else around
+        return 1.0;
+    }
+    let weights: Vec<f32> = distances
+        .iter()
+        .map(|d| BlendMask::apply_curve(d / total, curve))
+        .collect();
+    let sum: f32 = weights.iter().sum();
+    if sum > 0.0 {
+        weights[own] / sum
+    } else {
+        1.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SIZE: (u32, u32) = (160, 90);
+
+    /// Content UV of a normalized projector position on a keystoned quad.
+    fn bilerp(corners: &[[f32; 2]; 4], fx: f32, fy: f32) -> [f32; 2] {
+        let lerp = |a: [f32; 2], b: [f32; 2], t: f32| [a[0] + (b[0] - a[0]) * t, a[1] + (b[1] - a[1]) * t];
+        lerp(lerp(corners[0], corners[1], fx), lerp(corners[3], corners[2], fx), fy)
+    }
+
+    /// UV map of a projector whose corners land on the given content UVs.
+    fn keystoned(projector_id: u32, corners: [[f32; 2]; 4]) -> UvMap {
+        let (width, height) = SIZE;
+        let mut data = Vec::with_capacity((width * height) as usize);
+        for y in 0..height {
+            for x in 0..width {
+                data.push(bilerp(&corners, x as f32 / (width - 1) as f32, y as f32 / (height - 1) as f32));
+            }
+        }
+        UvMap {
+            projector_id,
+            width,
+            height,
+            data,
+        }
+    }
+
+    /// Mask value at the projector position that sees a content UV (Newton on the quad).
+    fn mask_at(mask: &BlendMask, corners: &[[f32; 2]; 4], uv: [f32; 2]) -> Option<f32> {
+        let (mut fx, mut fy) = (0.5f32, 0.5f32);
+        for _ in 0..20 {
+            let p = bilerp(corners, fx, fy);
+            let (h, px, py) = (1e-3, bilerp(corners, fx + 1e-3, fy), bilerp(corners, fx, fy + 1e-3));
+            let j = [[(px[0] - p[0]) / h, (py[0] - p[0]) / h], [(px[1] - p[1]) / h, (py[1] - p[1]) / h]];
+            let det = j[0][0] * j[1][1] - j[0][1] * j[1][0];
+            let r = [uv[0] - p[0], uv[1] - p[1]];
+            fx += (j[1][1] * r[0] - j[0][1] * r[1]) / det;
+            fy += (j[0][0] * r[1] - j[1][0] * r[0]) / det;
+        }
+        if !(0.0..=1.0).contains(&fx) || !(0.0..=1.0).contains(&fy) {
+            return None;
+        }
+        let x = fx * (mask.width - 1) as f32;
+        let y = fy * (mask.height - 1) as f32;
+        let (x0, y0) = (x.floor() as u32, y.floor() as u32);
+        let (x1, y1) = ((x0 + 1).min(mask.width - 1), (y0 + 1).min(mask.height - 1));
+        let (tx, ty) = (x - x0 as f32, y - y0 as f32);
+        let m = |x: u32, y: u32| mask.data[(y * mask.width + x) as usize];
+        let top = m(x0, y0) + (m(x1, y0) - m(x0, y0)) * tx;
+        let bottom = m(x0, y1) + (m(x1, y1) - m(x0, y1)) * tx;
+        Some(top + (bottom - top) * ty)
+    }
+
+    #[test]
+    fn test_distance_transform_matches_brute_force() {
+        let (columns, rows) = (23, 17);
+        let inside: Vec<bool> = (0..columns * rows)
+            .map(|i| {
+                let (x, y) = ((i % columns) as f32, (i / columns) as f32);
+                (x - 11.0).powi(2) / 60.0 + (y - 8.0).powi(2) / 30.0 < 1.0 && (x as usize, y as usize) != (12, 8)
+            })
+            .collect();
+        let distance = distance_transform(&inside, columns, rows, false);
+        for (i, &actual) in distance.iter().enumerate() {
+            let (x, y) = ((i % columns) as f32, (i / columns) as f32);
+            let expected = (0..columns * rows)
+                .filter(|&j| !inside[j])
+                .map(|j| ((x - (j % columns) as f32).powi(2) + (y - (j / columns) as f32).powi(2)).sqrt())
+                .fold(f32::INFINITY, f32::min);
+            assert!((actual - expected).abs() < 1e-4, "cell {}: {} vs {}", i, actual, expected);
+        }
+    }
+
+    #[test]
+    fn test_keystoned_overlap_weights_sum_to_one() {
+        // Two keystoned projectors whose overlap is a slanted quadrilateral
+        let left_corners = [[0.05, 0.1], [0.6, 0.05], [0.5, 0.95], [0.05, 0.9]];
+        let right_corners = [[0.4, 0.08], [0.95, 0.1], [0.95, 0.9], [0.55, 0.92]];
+        let uv_maps = [keystoned(0, left_corners), keystoned(1, right_corners)];
+        let space = BlendSpace {
+            aspect: 16.0 / 9.0,
+            wraps_u: false,
+        };
+        let masks = footprint_blend_masks(&uv_maps, space, BlendCurve::Gamma);
+
+        // Every content point the left projector lights gets full weight in total
+        let mut overlapping = 0;
+        for y in (2..88).step_by(4) {
+            for x in (2..158).step_by(4) {
+                let uv = uv_maps[0].data[(y * SIZE.0 + x) as usize];
+                let other = mask_at(&masks[1], &right_corners, uv);
+                let sum = masks[0].data[(y * SIZE.0 + x) as usize] + other.unwrap_or(0.0);
+                assert!((sum - 1.0).abs() < 0.02, "weights sum to {} at {:?}", sum, uv);
+                overlapping += other.is_some() as usize;
+            }
+        }
+        assert!(overlapping > 50, "too few overlap samples: {}", overlapping);
+
+        // The ramp follows the slanted edge: full weight outside the overlap,
+        // and falling toward the projector's own edge inside it
+        let row = 45 * SIZE.0 as usize;
+        assert!((masks[0].data[row + 10] - 1.0).abs() < 1e-6);
+        assert!(masks[0].data[row + 155] < 0.05);
+        assert!(masks[1].data[row + 3] < 0.05);
+    }
+}
@@ -1,9 +1,11 @@
 //! Edge blending module for multi-projector setups.
 
+mod footprint;
 mod overlap;
 
 use crate::config::BlendCurve;
 
+pub use footprint::{footprint_blend_masks, BlendSpace};
 pub use overlap::{OverlapConfig, OverlapDetectionResult, OverlapDetector};
 
 /// Overlap region between two projectors.
@@ -1,11 +1,17 @@
 //! Automatic overlap detection from calibration data.
 //!
 //! Detects overlapping regions between projectors by finding camera pixels
-//! that map to valid coordinates in multiple projectors.
+//! that map to valid coordinates in multiple projectors. Blend masks come
+//! from the projectors' footprints in the camera image, so keystoned overlaps
+//! blend along their actual shape.
 
+use super::footprint::{footprint_blend_masks, BlendSpace};
 use super::{BlendMask, OverlapEdge, OverlapRegion};
 use crate::calibration::ProjectorCalibration;
-use crate::config::BlendCurve;
+use crate::config::{BlendCurve, UvMap};
+
+/// Projector pixels between correspondence grid nodes for the footprint UV maps.
+const FOOTPRINT_GRID_SPACING: u32 = 8;
 
 /// Result of overlap detection.
 #[derive(Debug, Clone)]
@@ -50,18 +56,11 @@ impl OverlapDetector {
     /// Detect overlaps between all projector pairs.
     pub fn detect(&self, projectors: &[ProjectorCalibration]) -> OverlapDetectionResult {
         let mut overlaps = Vec::new();
-        let mut blend_masks: Vec<BlendMask> = projectors
-            .iter()
-            .map(|p| BlendMask::new(p.projector_width, p.projector_height))
-            .collect();
 
         // Compare each pair of projectors
         for i in 0..projectors.len() {
             for j in (i + 1)..projectors.len() {
                 if let Some(overlap) = self.detect_pair(&projectors[i], &projectors[j]) {
-                    // Apply blend to both projectors using split_at_mut to avoid double borrow
-                    let (left, right) = blend_masks.split_at_mut(j);
-                    self.apply_overlap_blend(&mut left[i], &mut right[0], &overlap);
                     overlaps.push(overlap);
                 }
             }
@@ -69,10 +68,46 @@ impl OverlapDetector {
 
         OverlapDetectionResult {
             overlaps,
-            blend_masks,
+            blend_masks: self.blend_masks(projectors),
         }
     }
 
+    /// Per-pixel blend masks from the projectors' footprints in the camera image.
+    ///
+    /// Projectors without correspondences are left unblended.
+    fn blend_masks(&self, projectors: &[ProjectorCalibration]) -> Vec<BlendMask> {
+        let (indices, uv_maps): (Vec<usize>, Vec<UvMap>) = projectors
+            .iter()
+            .enumerate()
+            .filter_map(|(index, p)| {
+                let corr = p.correspondences.as_ref()?;
+                let map = corr.to_correspondence_map(
+                    p.projector_id,
+                    p.projector_width / FOOTPRINT_GRID_SPACING + 1,
+                    p.projector_height / FOOTPRINT_GRID_SPACING + 1,
+                );
+                Some((index, UvMap::from_correspondence_map(&map, p.projector_width, p.projector_height)))
+            })
+            .unzip();
+
+        let mut blend_masks: Vec<BlendMask> = projectors
+            .iter()
+            .map(|p| BlendMask::new(p.projector_width, p.projector_height))
+            .collect();
+        let Some(corr) = indices.first().and_then(|&i| projectors[i].correspondences.as_ref()) else {
+            return blend_masks;
+        };
+        let space = BlendSpace {
+            aspect: corr.camera_width as f32 / corr.camera_height.max(1) as f32,
+            wraps_u: false,
+        };
+        let masks = footprint_blend_masks(&uv_maps, space, self.config.blend_curve);
+        for (index, mask) in indices.into_iter().zip(masks) {
+            blend_masks[index] = mask;
+        }
+        blend_masks
+    }
+
     /// Detect overlap between two projectors using their correspondences.
     fn detect_pair(
         &self,
@@ -221,74 +256,6 @@ impl OverlapDetector {
             }
         }
     }
-
-    /// Apply overlap blend to both projectors' masks.
-    fn apply_overlap_blend(
-        &self,
-        mask_a: &mut BlendMask,
-        mask_b: &mut BlendMask,
-        overlap: &OverlapRegion,
-    ) {
-        let curve = self.config.blend_curve;
-        let width = overlap.overlap_width;
-
-        // Apply complementary blends to ensure sum = 1.0 in overlap
-        match overlap.edge {
-            OverlapEdge::Right => {
-                mask_a.apply_right_blend(width, curve);
-                mask_b.apply_left_blend(width, curve);
-            }
-            OverlapEdge::Left => {
-                mask_a.apply_left_blend(width, curve);
-                mask_b.apply_right_blend(width, curve);
-            }
-            OverlapEdge::Top => {
-                self.apply_top_blend(mask_a, width, curve);
-                self.apply_bottom_blend(mask_b, width, curve);
-            }
-            OverlapEdge::Bottom => {
-                self.apply_bottom_blend(mask_a, width, curve);
-                self.apply_top_blend(mask_b, width, curve);
-            }
-        }
-    }
-
-    /// Apply blend falloff on the top edge.
-    fn apply_top_blend(&self, mask: &mut BlendMask, blend_height: u32, curve: BlendCurve) {
-        if blend_height == 0 {
-            return;
-        }
-
-        mask.curve = curve;
-
-        for y in 0..blend_height.min(mask.height) {
-            let t = 1.0 - (y as f32 / blend_height as f32);
-            let blend = 1.0 - BlendMask::apply_curve(t, curve);
-            for x in 0..mask.width {
-                let idx = (y * mask.width + x) as usize;
-                mask.data[idx] *= blend;
-            }
-        }
-    }
-
-    /// Apply blend falloff on the bottom edge.
-    fn apply_bottom_blend(&self, mask: &mut BlendMask, blend_height: u32, curve: BlendCurve) {
-        if blend_height == 0 {
-            return;
-        }
-
-        mask.curve = curve;
-        let start_y = mask.height.saturating_sub(blend_height);
-
-        for y in start_y..mask.height {
-            let t = (y - start_y) as f32 / blend_height as f32;
-            let blend = 1.0 - BlendMask::apply_curve(t, curve);
-            for x in 0..mask.width {
-                let idx = (y * mask.width + x) as usize;
-                mask.data[idx] *= blend;
-            }
-        }
-    }
 }
 
 /// Bounding box helper.
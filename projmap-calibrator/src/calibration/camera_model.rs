@@ -0,0 +1,581 @@
+//! Pinhole camera model and checkerboard intrinsic calibration.
+//!
+//! Cameras and projectors share the same model: a pinhole with focal
+//! lengths, principal point and two radial distortion terms. Camera
+//! intrinsics come from a set of checkerboard views using Zhang's method
+//! (closed-form initialization from per-view homographies, then
+//! Levenberg-Marquardt refinement of the full reprojection error).
+
+use super::homography::fit_point_homography;
+use super::linalg::{self, Mat3, Vec3};
+use serde::{Deserialize, Serialize};
+
+/// Minimum checkerboard views for intrinsic calibration.
+pub const MIN_CHECKERBOARD_VIEWS: usize = 3;
+
+/// Maximum Levenberg-Marquardt iterations for the intrinsic refinement.
+const LM_MAX_ITERS: usize = 50;
+
+/// Pinhole intrinsics with radial distortion.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct CameraIntrinsics {
+    /// Image width in pixels.
+    pub width: u32,
+    /// Image height in pixels.
+    pub height: u32,
+    /// Focal lengths in pixels.
+    pub fx: f64,
+    pub fy: f64,
+    /// Principal point in pixels.
+    pub cx: f64,
+    pub cy: f64,
+    /// Radial distortion coefficients.
+    pub k1: f64,
+    pub k2: f64,
+}
+
+impl CameraIntrinsics {
+    /// Distortion-free intrinsics with a centered principal point.
+    pub fn new(width: u32, height: u32, focal: f64) -> Self {
+        Self {
+            width,
+            height,
+            fx: focal,
+            fy: focal,
+            cx: width as f64 / 2.0,
+            cy: height as f64 / 2.0,
+            k1: 0.0,
+            k2: 0.0,
+        }
+    }
+
+    /// Projector intrinsics from its throw ratio and vertical lens shift.
+    ///
+    /// `lens_shift` moves the principal point by a fraction of the image
+    /// height (0.5 puts it on the bottom edge, typical for table-mounted
+    /// projectors).
+    pub fn from_throw_ratio(width: u32, height: u32, throw_ratio: f64, lens_shift: f64) -> Self {
+        let mut intrinsics = Self::new(width, height, throw_ratio * width as f64);
+        intrinsics.cy = height as f64 * (0.5 + lens_shift);
+        intrinsics
+    }
+
+    fn distortion_factor(&self, x: f64, y: f64) -> f64 {
+        let r2 = x * x + y * y;
+        1.0 + self.k1 * r2 + self.k2 * r2 * r2
+    }
+
+    /// Project a point in this device's frame to pixels.
+    ///
+    /// Returns `None` for points behind the device.
+    pub fn project(&self, point: Vec3) -> Option<[f64; 2]> {
+        if point[2] <= 1e-9 {
+            return None;
+        }
+        let x = point[0] / point[2];
+        let y = point[1] / point[2];
+        let d = self.distortion_factor(x, y);
+        Some([self.fx * x * d + self.cx, self.fy * y * d + self.cy])
+    }
+
+    /// Undistorted normalized image coordinates of a pixel.
+    pub fn normalize(&self, pixel: [f64; 2]) -> [f64; 2] {
+        let xd = (pixel[0] - self.cx) / self.fx;
+        let yd = (pixel[1] - self.cy) / self.fy;
+        if self.k1 == 0.0 && self.k2 == 0.0 {
+            return [xd, yd];
+        }
+        // Fixed-point inversion of the radial model
+        let (mut x, mut y) = (xd, yd);
+        for _ in 0..20 {
+            let d = self.distortion_factor(x, y);
+            x = xd / d;
+            y = yd / d;
+        }
+        [x, y]
+    }
+
+    /// Pixel in the ideal (undistorted) image.
+    pub fn undistort(&self, pixel: [f64; 2]) -> [f64; 2] {
+        let [x, y] = self.normalize(pixel);
+        [self.fx * x + self.cx, self.fy * y + self.cy]
+    }
+
+    /// Unit viewing ray through a pixel, in this device's frame.
+    pub fn ray(&self, pixel: [f64; 2]) -> Vec3 {
+        let [x, y] = self.normalize(pixel);
+        linalg::normalize([x, y, 1.0])
+    }
+
+    /// 3x3 camera matrix (without distortion).
+    pub fn matrix(&self) -> Mat3 {
+        [[self.fx, 0.0, self.cx], [0.0, self.fy, self.cy], [0.0, 0.0, 1.0]]
+    }
+
+    /// Horizontal field of view in degrees.
+    pub fn horizontal_fov(&self) -> f64 {
+        2.0 * (self.width as f64 / (2.0 * self.fx)).atan().to_degrees()
+    }
+
+    fn with_parameters(&self, p: &[f64]) -> Self {
+        Self {
+            fx: p[0],
+            fy: p[1],
+            cx: p[2],
+            cy: p[3],
+            k1: p[4],
+            k2: p[5],
+            ..*self
+        }
+    }
+}
+
+/// Rigid transform from the reference (camera) frame into a device frame.
+///
+/// A reference point `p` is at `rotation * p + translation` in the device frame.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Pose {
+    pub rotation: Mat3,
+    pub translation: Vec3,
+}
+
+impl Default for Pose {
+    fn default() -> Self {
+        Self {
+            rotation: linalg::IDENTITY,
+            translation: [0.0; 3],
+        }
+    }
+}
+
+impl Pose {
+    /// Pose from an axis-angle rotation and a translation.
+    pub fn from_parameters(rotation: Vec3, translation: Vec3) -> Self {
+        Self {
+            rotation: linalg::rodrigues(rotation),
+            translation,
+        }
+    }
+
+    /// Axis-angle rotation and translation, the inverse of [`Pose::from_parameters`].
+    pub fn parameters(&self) -> [f64; 6] {
+        let r = linalg::rotation_vector(&self.rotation);
+        [r[0], r[1], r[2], self.translation[0], self.translation[1], self.translation[2]]
+    }
+
+    /// Transform a reference frame point into the device frame.
+    pub fn transform(&self, point: Vec3) -> Vec3 {
+        linalg::add(linalg::mat_vec(&self.rotation, point), self.translation)
+    }
+
+    /// Rotate a reference frame direction into the device frame.
+    pub fn rotate(&self, direction: Vec3) -> Vec3 {
+        linalg::mat_vec(&self.rotation, direction)
+    }
+
+    /// Device position in the reference frame.
+    pub fn center(&self) -> Vec3 {
+        linalg::scale(linalg::mat_vec(&linalg::transpose(&self.rotation), self.translation), -1.0)
+    }
+}
+
+/// Checkerboard geometry (inner corners).
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct CheckerboardSpec {
+    /// Inner corners per row.
+    pub columns: u32,
+    /// Inner corners per column.
+    pub rows: u32,
+    /// Square edge length (any unit; only affects the reported poses).
+    pub square_size: f64,
+}
+
+impl Default for CheckerboardSpec {
+    fn default() -> Self {
+        Self {
+            columns: 9,
+            rows: 6,
+            square_size: 25.0,
+        }
+    }
+}
+
+impl CheckerboardSpec {
+    pub fn corner_count(&self) -> usize {
+        (self.columns * self.rows) as usize
+    }
+
+    /// Corner positions on the board plane, row-major.
+    pub fn object_points(&self) -> Vec<[f64; 2]> {
+        (0..self.rows)
+            .flat_map(|row| {
+                (0..self.columns).map(move |col| {
+                    [col as f64 * self.square_size, row as f64 * self.square_size]
+                })
+            })
+            .collect()
+    }
+}
+
+/// Result of intrinsic calibration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CameraCalibration {
+    /// Refined camera intrinsics.
+    pub intrinsics: CameraIntrinsics,
+    /// Board pose per view (board plane -> camera).
+    pub view_poses: Vec<Pose>,
+    /// RMS reprojection error in pixels.
+    pub reprojection_error: f64,
+}
+
+/// Calibrate camera intrinsics from detected checkerboard corners.
+///
+/// Each view lists the corners in [`CheckerboardSpec::object_points`] order.
+pub fn calibrate_camera(
+    spec: &CheckerboardSpec,
+    views: &[Vec<[f64; 2]>],
+    width: u32,
+    height: u32,
+) -> Result<CameraCalibration, String> {
+    if views.len() < MIN_CHECKERBOARD_VIEWS {
+        return Err(format!(
+            "Need at least {} checkerboard views, got {}",
+            MIN_CHECKERBOARD_VIEWS,
+            views.len()
+        ));
+    }
+    let object = spec.object_points();
+    if let Some(view) = views.iter().find(|v| v.len() != object.len()) {
+        return Err(format!(
+            "Checkerboard view has {} corners, expected {}",
+            view.len(),
+            object.len()
+        ));
+    }
+
+    // Work in normalized pixel coordinates for a well-conditioned closed form
+    let s = 2.0 / width.max(height).max(1) as f64;
+    let (ox, oy) = (width as f64 / 2.0, height as f64 / 2.0);
+    let homographies = views
+        .iter()
+        .map(|view| {
+            let pairs: Vec<_> = object
+                .iter()
+                .zip(view)
+                .map(|(o, p)| (*o, [(p[0] - ox) * s, (p[1] - oy) * s]))
+                .collect();
+            fit_point_homography(&pairs).ok_or_else(|| "Degenerate checkerboard view".to_string())
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let (alpha, beta, u0, v0) = closed_form_intrinsics(&homographies)?;
+    let k_inv: Mat3 = [
+        [1.0 / alpha, 0.0, -u0 / alpha],
+        [0.0, 1.0 / beta, -v0 / beta],
+        [0.0, 0.0, 1.0],
+    ];
+    // Normalizing the image plane leaves the board poses unchanged
+    let initial_poses: Vec<Pose> = homographies.iter().map(|h| board_pose(&k_inv, h)).collect();
+
+    let base = CameraIntrinsics {
+        width,
+        height,
+        fx: alpha / s,
+        fy: beta / s,
+        cx: u0 / s + ox,
+        cy: v0 / s + oy,
+        k1: 0.0,
+        k2: 0.0,
+    };
+
+    // Joint refinement: 6 intrinsic parameters, then 6 per view
+    let mut params = vec![base.fx, base.fy, base.cx, base.cy, 0.0, 0.0];
+    for pose in &initial_poses {
+        params.extend_from_slice(&pose.parameters());
+    }
+    let residuals = |p: &[f64]| {
+        let intrinsics = base.with_parameters(p);
+        let mut out = Vec::with_capacity(views.len() * object.len() * 2);
+        for (v, view) in views.iter().enumerate() {
+            let q = &p[6 + v * 6..12 + v * 6];
+            let pose = Pose::from_parameters([q[0], q[1], q[2]], [q[3], q[4], q[5]]);
+            for (o, observed) in object.iter().zip(view) {
+                match intrinsics.project(pose.transform([o[0], o[1], 0.0])) {
+                    Some(pixel) => {
+                        out.push(pixel[0] - observed[0]);
+                        out.push(pixel[1] - observed[1]);
+                    }
+                    None => {
+                        out.push(1e3);
+                        out.push(1e3);
+                    }
+                }
+            }
+        }
+        out
+    };
+    let (params, cost) = linalg::levenberg_marquardt(params, LM_MAX_ITERS, residuals);
+
+    let intrinsics = base.with_parameters(&params);
+    let view_poses = (0..views.len())
+        .map(|v| {
+            let q = &params[6 + v * 6..12 + v * 6];
+            Pose::from_parameters([q[0], q[1], q[2]], [q[3], q[4], q[5]])
+        })
+        .collect();
+    let reprojection_error = (cost / (views.len() * object.len()) as f64).sqrt();
+
+    log::info!(
+        "Camera calibrated from {} views: f = {:.1}/{:.1}, c = ({:.1}, {:.1}), k = ({:.4}, {:.4}), RMS {:.3}px",
+        views.len(),
+        intrinsics.fx,
+        intrinsics.fy,
+        intrinsics.cx,
+        intrinsics.cy,
+        intrinsics.k1,
+        intrinsics.k2,
+        reprojection_error
+    );
+
+    Ok(CameraCalibration {
+        intrinsics,
+        view_poses,
+        reprojection_error,
+    })
+}
+
+/// Zhang's closed-form solution (zero skew) from board homographies.
+///
+/// Returns `(alpha, beta, u0, v0)` in the homographies' pixel units.
+fn closed_form_intrinsics(homographies: &[Mat3]) -> Result<(f64, f64, f64, f64), String> {
+    // v_ij . b = h_i^T B h_j for columns h_i, with b = [B11, B12, B22, B13, B23, B33]
+    let v = |h: &Mat3, i: usize, j: usize| {
+        vec![
+            h[0][i] * h[0][j],
+            h[0][i] * h[1][j] + h[1][i] * h[0][j],
+            h[1][i] * h[1][j],
+            h[2][i] * h[0][j] + h[0][i] * h[2][j],
+            h[2][i] * h[1][j] + h[1][i] * h[2][j],
+            h[2][i] * h[2][j],
+        ]
+    };
+    let mut rows = Vec::with_capacity(homographies.len() * 2 + 1);
+    for h in homographies {
+        rows.push(v(h, 0, 1));
+        rows.push(v(h, 0, 0).iter().zip(v(h, 1, 1)).map(|(a, b)| a - b).collect());
+    }
+    // Zero skew: B12 = 0
+    rows.push(vec![0.0, 1.0, 0.0, 0.0, 0.0, 0.0]);
+
+    let mut b = linalg::null_vector(&rows);
+    if b[0] < 0.0 {
+        b.iter_mut().for_each(|x| *x = -*x);
+    }
+    let (b11, b12, b22, b13, b23, b33) = (b[0], b[1], b[2], b[3], b[4], b[5]);
+
+    let denom = b11 * b22 - b12 * b12;
+    if denom <= 0.0 || b11 <= 0.0 {
+        return Err("Checkerboard views do not constrain the intrinsics (vary the board angle)".to_string());
+    }
+    let v0 = (b12 * b13 - b11 * b23) / denom;
+    let lambda = b33 - (b13 * b13 + v0 * (b12 * b13 - b11 * b23)) / b11;
+    if lambda / b11 <= 0.0 {
+        return Err("Checkerboard views do not constrain the intrinsics (vary the board angle)".to_string());
+    }
+    let alpha = (lambda / b11).sqrt();
+    let beta = (lambda * b11 / denom).sqrt();
+    let u0 = -b13 * alpha * alpha / lambda;
+    Ok((alpha, beta, u0, v0))
+}
+
+/// Board pose from its homography and the inverse camera matrix.
+fn board_pose(k_inv: &Mat3, h: &Mat3) -> Pose {
+    let column = |i: usize| linalg::mat_vec(k_inv, [h[0][i], h[1][i], h[2][i]]);
+    let (c1, c2, c3) = (column(0), column(1), column(2));
+    let mut lambda = 1.0 / linalg::norm(c1).max(1e-300);
+    // Keep the board in front of the camera
+    if c3[2] * lambda < 0.0 {
+        lambda = -lambda;
+    }
+    let r1 = linalg::scale(c1, lambda);
+    let r2 = linalg::scale(c2, lambda);
+    let r3 = linalg::cross(r1, r2);
+    let rotation = linalg::orthonormalize(&std::array::from_fn(|i| [r1[i], r2[i], r3[i]]));
+    Pose {
+        rotation,
+        translation: linalg::scale(c3, lambda),
+    }
+}
+
+/// Collected checkerboard views for one camera.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CheckerboardSet {
+    /// Board geometry.
+    pub spec: CheckerboardSpec,
+    /// Image size of the views.
+    pub image_width: u32,
+    pub image_height: u32,
+    /// Detected corners per view.
+    pub views: Vec<Vec<[f64; 2]>>,
+    /// Result of the last successful calibration.
+    pub calibration: Option<CameraCalibration>,
+}
+
+impl CheckerboardSet {
+    pub fn new(spec: CheckerboardSpec) -> Self {
+        Self {
+            spec,
+            ..Self::default()
+        }
+    }
+
+    /// Add detected corners. All views must share one image size.
+    pub fn add_view(&mut self, corners: Vec<[f64; 2]>, width: u32, height: u32) -> Result<(), String> {
+        if corners.len() != self.spec.corner_count() {
+            return Err(format!(
+                "Checkerboard view has {} corners, expected {}",
+                corners.len(),
+                self.spec.corner_count()
+            ));
+        }
+        if !self.views.is_empty() && (width, height) != (self.image_width, self.image_height) {
+            return Err(format!(
+                "View is {}x{}, earlier views are {}x{}",
+                width, height, self.image_width, self.image_height
+            ));
+        }
+        self.image_width = width;
+        self.image_height = height;
+        self.views.push(corners);
+        Ok(())
+    }
+
+    /// Detect the board in a grayscale image and add it.
+    ///
+    /// Returns `false` when the board was not found.
+    pub fn add_image(&mut self, gray: &[u8], width: u32, height: u32) -> Result<bool, String> {
+        match detect_checkerboard(gray, width, height, &self.spec)? {
+            Some(corners) => {
+                self.add_view(corners, width, height)?;
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    /// Drop all views and the calibration.
+    pub fn clear(&mut self) {
+        self.views.clear();
+        self.calibration = None;
+    }
+
+    /// Calibrate from the collected views, keeping the result.
+    pub fn calibrate(&mut self) -> Result<&CameraCalibration, String> {
+        let calibration = calibrate_camera(&self.spec, &self.views, self.image_width, self.image_height)?;
+        Ok(self.calibration.insert(calibration))
+    }
+}
+
+/// Find checkerboard inner corners in a grayscale image.
+#[cfg(feature = "opencv")]
+pub fn detect_checkerboard(
+    gray: &[u8],
+    width: u32,
+    height: u32,
+    spec: &CheckerboardSpec,
+) -> Result<Option<Vec<[f64; 2]>>, String> {
+    use opencv::calib3d;
+    use opencv::core::{Mat, Point2f, Size, Vector};
+    use opencv::prelude::*;
+
+    if gray.len() != (width * height) as usize {
+        return Err(format!("Image buffer does not match {}x{}", width, height));
+    }
+    let image = Mat::new_rows_cols_with_data(height as i32, width as i32, gray)
+        .map_err(|e| format!("OpenCV error: {}", e))?;
+    let mut corners = Vector::<Point2f>::new();
+    let found = calib3d::find_chessboard_corners_sb(
+        &image,
+        Size::new(spec.columns as i32, spec.rows as i32),
+        &mut corners,
+        calib3d::CALIB_CB_NORMALIZE_IMAGE | calib3d::CALIB_CB_EXHAUSTIVE | calib3d::CALIB_CB_ACCURACY,
+    )
+    .map_err(|e| format!("OpenCV error: {}", e))?;
+
+    if !found || corners.len() != spec.corner_count() {
+        return Ok(None);
+    }
+    Ok(Some(corners.iter().map(|p| [p.x as f64, p.y as f64]).collect()))
+}
+
+/// Find checkerboard inner corners in a grayscale image.
+#[cfg(not(feature = "opencv"))]
+pub fn detect_checkerboard(
+    _gray: &[u8],
+    _width: u32,
+    _height: u32,
+    _spec: &CheckerboardSpec,
+) -> Result<Option<Vec<[f64; 2]>>, String> {
+    Err("Checkerboard detection requires the opencv feature".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_distortion_round_trip() {
+        let mut camera = CameraIntrinsics::new(640, 480, 500.0);
+        camera.k1 = -0.12;
+        camera.k2 = 0.03;
+        for point in [[0.3, -0.2, 1.0], [-0.5, 0.35, 2.0], [0.0, 0.0, 1.0]] {
+            let pixel = camera.project(point).unwrap();
+            let ray = camera.ray(pixel);
+            let expected = linalg::normalize(point);
+            assert!(linalg::norm(linalg::sub(ray, expected)) < 1e-9);
+        }
+        assert!(camera.project([0.0, 0.0, -1.0]).is_none());
+    }
+
+    #[test]
+    fn test_calibrate_camera_recovers_intrinsics() {
+        let truth = CameraIntrinsics {
+            width: 1280,
+            height: 720,
+            fx: 1050.0,
+            fy: 1040.0,
+            cx: 655.0,
+            cy: 350.0,
+            k1: -0.08,
+            k2: 0.02,
+        };
+        let spec = CheckerboardSpec::default();
+        let object = spec.object_points();
+        let poses = [
+            ([0.3, -0.2, 0.05], [-100.0, -60.0, 600.0]),
+            ([-0.35, 0.1, -0.1], [-80.0, -70.0, 550.0]),
+            ([0.1, 0.4, 0.2], [-120.0, -50.0, 650.0]),
+            ([-0.2, -0.35, 0.0], [-60.0, -40.0, 500.0]),
+            ([0.45, 0.25, -0.15], [-110.0, -80.0, 700.0]),
+        ];
+        let views: Vec<Vec<[f64; 2]>> = poses
+            .iter()
+            .map(|&(r, t)| {
+                let pose = Pose::from_parameters(r, t);
+                object
+                    .iter()
+                    .map(|o| truth.project(pose.transform([o[0], o[1], 0.0])).unwrap())
+                    .collect()
+            })
+            .collect();
+
+        let result = calibrate_camera(&spec, &views, 1280, 720).unwrap();
+        let c = result.intrinsics;
+        assert!(result.reprojection_error < 1e-3, "RMS {}", result.reprojection_error);
+        assert!((c.fx - truth.fx).abs() < 0.5 && (c.fy - truth.fy).abs() < 0.5, "{:?}", c);
+        assert!((c.cx - truth.cx).abs() < 0.5 && (c.cy - truth.cy).abs() < 0.5, "{:?}", c);
+        assert!((c.k1 - truth.k1).abs() < 1e-3, "{:?}", c);
+
+        assert!(calibrate_camera(&spec, &views[..2], 1280, 720).is_err());
+    }
+}
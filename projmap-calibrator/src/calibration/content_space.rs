@@ -0,0 +1,752 @@
+//! Shared content spaces for non-planar surfaces.
+//!
+//! A content space turns a reconstructed surface point (camera frame) into
+//! the content UV that should appear there. Spheres (domes) use an
+//! equirectangular layout around the fitted center, cylinders are unwrapped
+//! around their fitted axis, and arbitrary screens use the UVs of a
+//! user-supplied OBJ mesh registered to the point cloud.
+//!
+//! The camera frame has X right, Y down and Z forward; "up" for the
+//! equirectangular and cylinder layouts is the camera's -Y axis.
+
+use super::linalg::{self, Mat3, Vec3};
+use std::f64::consts::{PI, TAU};
+use std::path::Path;
+
+/// Camera "up" direction.
+const UP: Vec3 = [0.0, -1.0, 0.0];
+
+/// Camera frame -> Y-up mesh frame facing the camera (-Z forward).
+const CAMERA_TO_MESH: Mat3 = [[1.0, 0.0, 0.0], [0.0, -1.0, 0.0], [0.0, 0.0, -1.0]];
+
+/// Fraction of extreme samples ignored when measuring cylinder extents.
+const EXTENT_TRIM: f64 = 0.005;
+
+/// Iterations for mesh registration.
+const ICP_ITERS: usize = 40;
+
+/// Fraction of the worst matches dropped in each registration step.
+const ICP_TRIM: f64 = 0.2;
+
+/// Maximum surface samples used to index a mesh.
+const MAX_MESH_SAMPLES: usize = 200_000;
+
+/// Target content layout.
+#[derive(Debug, Clone, Default)]
+pub enum ContentSpace {
+    /// Equirectangular around a fitted sphere (domes).
+    #[default]
+    Equirectangular,
+    /// Unwrapped around a fitted cylinder.
+    Cylinder,
+    /// UVs of a mesh registered to the surface.
+    Mesh(MeshSurface),
+}
+
+impl ContentSpace {
+    pub fn name(&self) -> &'static str {
+        match self {
+            ContentSpace::Equirectangular => "Dome (Equirectangular)",
+            ContentSpace::Cylinder => "Cylinder",
+            ContentSpace::Mesh(_) => "Mesh UVs",
+        }
+    }
+}
+
+/// Content space fitted to a reconstructed surface.
+#[derive(Debug, Clone)]
+pub enum ContentMapping {
+    Equirectangular {
+        /// Sphere center (camera frame).
+        center: Vec3,
+        /// Sphere radius.
+        radius: f64,
+        /// Rows: right, up and forward axes.
+        basis: Mat3,
+    },
+    Cylinder {
+        /// Point on the axis level with the surface centroid.
+        origin: Vec3,
+        /// Cylinder radius.
+        radius: f64,
+        /// Rows: right, up (the axis) and forward axes.
+        basis: Mat3,
+        /// Angle range mapped to u = 0..1 (radians from forward).
+        angles: [f64; 2],
+        /// Height range mapped to v = 1..0 (along the axis).
+        heights: [f64; 2],
+    },
+    Mesh {
+        surface: MeshSurface,
+        /// Camera frame -> mesh frame.
+        alignment: Similarity,
+        /// Mean registration distance (mesh units).
+        residual: f64,
+    },
+}
+
+impl ContentMapping {
+    /// Fit a content space to surface points.
+    pub fn fit(space: &ContentSpace, points: &[Vec3]) -> Result<Self, String> {
+        match space {
+            ContentSpace::Equirectangular => {
+                let (center, radius) = fit_sphere(points)?;
+                Ok(ContentMapping::Equirectangular {
+                    center,
+                    radius,
+                    basis: basis_from(UP, [0.0, 0.0, 1.0]),
+                })
+            }
+            ContentSpace::Cylinder => fit_cylinder(points),
+            ContentSpace::Mesh(surface) => {
+                let (alignment, residual) = surface.register(points)?;
+                Ok(ContentMapping::Mesh {
+                    surface: surface.clone(),
+                    alignment,
+                    residual,
+                })
+            }
+        }
+    }
+
+    /// Content width over height on the surface, for isotropic distances in UV.
+    pub fn aspect(&self) -> f64 {
+        match self {
+            // Longitude spans twice the latitude range
+            ContentMapping::Equirectangular { .. } => 2.0,
+            ContentMapping::Cylinder {
+                radius, angles, heights, ..
+            } => radius * (angles[1] - angles[0]) / (heights[1] - heights[0]).max(f64::EPSILON),
+            ContentMapping::Mesh { .. } => 1.0,
+        }
+    }
+
+    /// Whether u wraps around (0 and 1 are the same place).
+    pub fn wraps_u(&self) -> bool {
+        matches!(self, ContentMapping::Equirectangular { .. })
+    }
+
+    /// Content UV (0-1, top-left origin) of a surface point.
+    pub fn uv(&self, point: Vec3) -> Option<[f64; 2]> {
+        match self {
+            ContentMapping::Equirectangular { center, basis, .. } => {
+                let d = linalg::normalize(linalg::mat_vec(basis, linalg::sub(point, *center)));
+                let u = 0.5 + d[0].atan2(d[2]) / TAU;
+                let v = d[1].clamp(-1.0, 1.0).acos() / PI;
+                Some([u.rem_euclid(1.0), v])
+            }
+            ContentMapping::Cylinder {
+                origin,
+                basis,
+                angles,
+                heights,
+                ..
+            } => {
+                let local = linalg::mat_vec(basis, linalg::sub(point, *origin));
+                let angle = local[0].atan2(local[2]);
+                let u = (angle - angles[0]) / (angles[1] - angles[0]);
+                let v = (heights[1] - local[1]) / (heights[1] - heights[0]);
+                Some([u.clamp(0.0, 1.0), v.clamp(0.0, 1.0)])
+            }
+            ContentMapping::Mesh {
+                surface, alignment, ..
+            } => surface.uv_near(alignment.apply(point)),
+        }
+    }
+
+    /// Short description for logs and the UI.
+    pub fn describe(&self) -> String {
+        match self {
+            ContentMapping::Equirectangular { radius, .. } => format!("sphere, radius {:.3}", radius),
+            ContentMapping::Cylinder { radius, angles, .. } => format!(
+                "cylinder, radius {:.3}, {:.0} degree arc",
+                radius,
+                (angles[1] - angles[0]).to_degrees()
+            ),
+            ContentMapping::Mesh { residual, .. } => format!("mesh, registration error {:.4}", residual),
+        }
+    }
+}
+
+/// Rows right, up, forward from an up vector and a forward hint.
+fn basis_from(up: Vec3, forward_hint: Vec3) -> Mat3 {
+    let up = linalg::normalize(up);
+    let forward = linalg::normalize(linalg::sub(forward_hint, linalg::scale(up, linalg::dot(forward_hint, up))));
+    let right = linalg::cross(forward, up);
+    [right, up, forward]
+}
+
+/// Least-squares sphere through the points (algebraic fit).
+pub fn fit_sphere(points: &[Vec3]) -> Result<(Vec3, f64), String> {
+    if points.len() < 4 {
+        return Err("Not enough surface points to fit a sphere".to_string());
+    }
+    // |p|^2 = 2 c.p + d, with d = r^2 - |c|^2
+    let mut ata = vec![vec![0.0; 4]; 4];
+    let mut atb = vec![0.0; 4];
+    for p in points {
+        let row = [2.0 * p[0], 2.0 * p[1], 2.0 * p[2], 1.0];
+        let rhs = linalg::dot(*p, *p);
+        for i in 0..4 {
+            atb[i] += row[i] * rhs;
+            for j in 0..4 {
+                ata[i][j] += row[i] * row[j];
+            }
+        }
+    }
+    let x = linalg::solve(ata, atb).ok_or("Surface points are degenerate (coplanar?)")?;
+    let center = [x[0], x[1], x[2]];
+    let r2 = x[3] + linalg::dot(center, center);
+    if r2 <= 0.0 {
+        return Err("Sphere fit failed".to_string());
+    }
+    Ok((center, r2.sqrt()))
+}
+
+/// Cylinder fit: circle in the plane across `UP`, then a free-axis refinement.
+fn fit_cylinder(points: &[Vec3]) -> Result<ContentMapping, String> {
+    if points.len() < 6 {
+        return Err("Not enough surface points to fit a cylinder".to_string());
+    }
+    let centroid = linalg::scale(
+        points.iter().fold([0.0; 3], |acc, p| linalg::add(acc, *p)),
+        1.0 / points.len() as f64,
+    );
+
+    // Initial guess: vertical axis, algebraic circle fit in the horizontal plane
+    let [side, _, depth] = basis_from(UP, [0.0, 0.0, 1.0]);
+    let mut ata = vec![vec![0.0; 3]; 3];
+    let mut atb = vec![0.0; 3];
+    for p in points {
+        let d = linalg::sub(*p, centroid);
+        let (x, z) = (linalg::dot(d, side), linalg::dot(d, depth));
+        let row = [2.0 * x, 2.0 * z, 1.0];
+        for i in 0..3 {
+            atb[i] += row[i] * (x * x + z * z);
+            for j in 0..3 {
+                ata[i][j] += row[i] * row[j];
+            }
+        }
+    }
+    let circle = linalg::solve(ata, atb).ok_or("Surface points are degenerate")?;
+    let start_origin = linalg::add(
+        centroid,
+        linalg::add(linalg::scale(side, circle[0]), linalg::scale(depth, circle[1])),
+    );
+    let start_radius = (circle[2] + circle[0] * circle[0] + circle[1] * circle[1]).max(1e-12).sqrt();
+
+    // Refine: axis as polar angles around UP, origin offset, radius
+    let axis_of = |p: &[f64]| {
+        let (st, ct) = p[0].sin_cos();
+        let (sp, cp) = p[1].sin_cos();
+        linalg::add(
+            linalg::scale(UP, ct),
+            linalg::add(linalg::scale(side, st * cp), linalg::scale(depth, st * sp)),
+        )
+    };
+    let origin_of = |p: &[f64]| linalg::add(start_origin, [p[2], p[3], p[4]]);
+    let step = (points.len() / 5000).max(1);
+    let subset: Vec<Vec3> = points.iter().step_by(step).copied().collect();
+    let (params, _) = linalg::levenberg_marquardt(
+        vec![0.0, 0.0, 0.0, 0.0, 0.0, start_radius],
+        50,
+        |p| {
+            let axis = axis_of(p);
+            let origin = origin_of(p);
+            subset
+                .iter()
+                .map(|q| {
+                    let d = linalg::sub(*q, origin);
+                    let radial = linalg::sub(d, linalg::scale(axis, linalg::dot(d, axis)));
+                    linalg::norm(radial) - p[5]
+                })
+                .collect()
+        },
+    );
+
+    let mut axis = axis_of(&params);
+    if linalg::dot(axis, UP) < 0.0 {
+        axis = linalg::scale(axis, -1.0);
+    }
+    let radius = params[5].abs();
+    // Move the origin along the axis to the centroid's height
+    let raw_origin = origin_of(&params);
+    let origin = linalg::add(
+        raw_origin,
+        linalg::scale(axis, linalg::dot(linalg::sub(centroid, raw_origin), axis)),
+    );
+
+    // Forward points at the middle of the surface; u grows with camera X
+    let mut basis = basis_from(axis, linalg::sub(centroid, origin));
+    if linalg::dot(basis[0], [1.0, 0.0, 0.0]) < 0.0 {
+        basis[0] = linalg::scale(basis[0], -1.0);
+    }
+
+    let (mut angles, mut heights): (Vec<f64>, Vec<f64>) = points
+        .iter()
+        .map(|p| {
+            let local = linalg::mat_vec(&basis, linalg::sub(*p, origin));
+            (local[0].atan2(local[2]), local[1])
+        })
+        .unzip();
+    let angle_range = [
+        linalg::percentile(&mut angles, EXTENT_TRIM),
+        linalg::percentile(&mut angles, 1.0 - EXTENT_TRIM),
+    ];
+    let height_range = [
+        linalg::percentile(&mut heights, EXTENT_TRIM),
+        linalg::percentile(&mut heights, 1.0 - EXTENT_TRIM),
+    ];
+    if angle_range[1] - angle_range[0] < 1e-6 || height_range[1] - height_range[0] < 1e-9 {
+        return Err("Cylinder fit failed: surface has no extent".to_string());
+    }
+
+    Ok(ContentMapping::Cylinder {
+        origin,
+        radius,
+        basis,
+        angles: angle_range,
+        heights: height_range,
+    })
+}
+
+/// Uniform scale, rotation and translation: `scale * rotation * p + translation`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Similarity {
+    pub scale: f64,
+    pub rotation: Mat3,
+    pub translation: Vec3,
+}
+
+impl Similarity {
+    pub fn apply(&self, p: Vec3) -> Vec3 {
+        linalg::add(
+            linalg::scale(linalg::mat_vec(&self.rotation, p), self.scale),
+            self.translation,
+        )
+    }
+
+    /// Least-squares similarity mapping `source` onto `target` (Umeyama).
+    pub(super) fn fit(source: &[Vec3], target: &[Vec3]) -> Option<Self> {
+        let n = source.len().min(target.len());
+        if n < 3 {
+            return None;
+        }
+        let mean = |points: &[Vec3]| {
+            linalg::scale(points[..n].iter().fold([0.0; 3], |acc, p| linalg::add(acc, *p)), 1.0 / n as f64)
+        };
+        let (mu_s, mu_t) = (mean(source), mean(target));
+        let mut covariance = [[0.0; 3]; 3];
+        let mut variance = 0.0;
+        for (s, t) in source.iter().zip(target).take(n) {
+            let ds = linalg::sub(*s, mu_s);
+            let dt = linalg::sub(*t, mu_t);
+            variance += linalg::dot(ds, ds);
+            for (i, row) in covariance.iter_mut().enumerate() {
+                for (j, value) in row.iter_mut().enumerate() {
+                    *value += dt[i] * ds[j];
+                }
+            }
+        }
+        if variance < 1e-300 {
+            return None;
+        }
+        let (u, s, v) = linalg::svd3(&covariance);
+        let sign = if linalg::det(&u) * linalg::det(&v) < 0.0 { -1.0 } else { 1.0 };
+        let d = [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, sign]];
+        let rotation = linalg::mat_mul(&linalg::mat_mul(&u, &d), &linalg::transpose(&v));
+        let scale = (s[0] + s[1] + sign * s[2]) / variance;
+        let translation = linalg::sub(mu_t, linalg::scale(linalg::mat_vec(&rotation, mu_s), scale));
+        Some(Self {
+            scale,
+            rotation,
+            translation,
+        })
+    }
+}
+
+/// Triangle mesh with texture coordinates, indexed for closest-point queries.
+#[derive(Debug, Clone)]
+pub struct MeshSurface {
+    positions: Vec<Vec3>,
+    uvs: Vec<[f64; 2]>,
+    /// Position and UV index per corner.
+    triangles: Vec<[(usize, usize); 3]>,
+    /// Surface samples (position, triangle) in kd-tree order.
+    samples: Vec<(Vec3, usize)>,
+    /// Bounding box diagonal.
+    extent: f64,
+}
+
+impl MeshSurface {
+    /// Load a Wavefront OBJ file. Faces must reference texture coordinates.
+    pub fn load_obj(path: &Path) -> std::io::Result<Self> {
+        let text = std::fs::read_to_string(path)?;
+        Self::parse_obj(&text).map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+    }
+
+    /// Parse Wavefront OBJ text (positions, texture coordinates and faces).
+    pub fn parse_obj(text: &str) -> Result<Self, String> {
+        let mut positions = Vec::new();
+        let mut uvs = Vec::new();
+        let mut triangles = Vec::new();
+
+        let index = |token: &str, count: usize, line: usize| -> Result<usize, String> {
+            let value: i64 = token
+                .parse()
+                .map_err(|_| format!("line {}: bad index '{}'", line, token))?;
+            // OBJ indices are 1-based; negative ones count from the end
+            let resolved = if value < 0 { count as i64 + value } else { value - 1 };
+            if resolved < 0 || resolved as usize >= count {
+                return Err(format!("line {}: index {} out of range", line, value));
+            }
+            Ok(resolved as usize)
+        };
+
+        for (number, line) in text.lines().enumerate() {
+            let number = number + 1;
+            let mut tokens = line.split_whitespace();
+            match tokens.next() {
+                Some("v") => {
+                    let coords: Vec<f64> = tokens.take(3).filter_map(|t| t.parse().ok()).collect();
+                    if coords.len() != 3 {
+                        return Err(format!("line {}: vertex needs three coordinates", number));
+                    }
+                    positions.push([coords[0], coords[1], coords[2]]);
+                }
+                Some("vt") => {
+                    let coords: Vec<f64> = tokens.take(2).filter_map(|t| t.parse().ok()).collect();
+                    if coords.len() != 2 {
+                        return Err(format!("line {}: texture coordinate needs u and v", number));
+                    }
+                    uvs.push([coords[0], coords[1]]);
+                }
+                Some("f") => {
+                    let corners = tokens
+                        .map(|corner| {
+                            let mut parts = corner.split('/');
+                            let v = index(parts.next().unwrap_or(""), positions.len(), number)?;
+                            let vt = match parts.next() {
+                                Some(t) if !t.is_empty() => index(t, uvs.len(), number)?,
+                                _ => return Err(format!("line {}: face has no texture coordinates", number)),
+                            };
+                            Ok((v, vt))
+                        })
+                        .collect::<Result<Vec<_>, String>>()?;
+                    if corners.len() < 3 {
+                        return Err(format!("line {}: face needs three corners", number));
+                    }
+                    // Fan triangulation
+                    for i in 1..corners.len() - 1 {
+                        triangles.push([corners[0], corners[i], corners[i + 1]]);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        if triangles.is_empty() {
+            return Err("mesh has no faces".to_string());
+        }
+        Ok(Self::new(positions, uvs, triangles))
+    }
+
+    fn new(positions: Vec<Vec3>, uvs: Vec<[f64; 2]>, triangles: Vec<[(usize, usize); 3]>) -> Self {
+        let (min, max) = positions.iter().fold(
+            ([f64::INFINITY; 3], [f64::NEG_INFINITY; 3]),
+            |(lo, hi), p| (std::array::from_fn(|i| lo[i].min(p[i])), std::array::from_fn(|i| hi[i].max(p[i]))),
+        );
+        let extent = linalg::norm(linalg::sub(max, min)).max(1e-12);
+
+        // Sample every triangle on a barycentric grid of roughly equal spacing
+        let edge = |t: &[(usize, usize); 3]| {
+            (0..3)
+                .map(|i| linalg::norm(linalg::sub(positions[t[i].0], positions[t[(i + 1) % 3].0])))
+                .fold(0.0, f64::max)
+        };
+        let mut spacing = extent / 256.0;
+        let count = |spacing: f64| -> usize {
+            triangles
+                .iter()
+                .map(|t| {
+                    let n = (edge(t) / spacing).ceil().max(1.0) as usize;
+                    (n + 1) * (n + 2) / 2
+                })
+                .sum()
+        };
+        while count(spacing) > MAX_MESH_SAMPLES && spacing < extent {
+            spacing *= 1.5;
+        }
+        let mut samples = Vec::new();
+        for (index, t) in triangles.iter().enumerate() {
+            let [a, b, c] = t.map(|(v, _)| positions[v]);
+            let n = (edge(t) / spacing).ceil().max(1.0) as usize;
+            for i in 0..=n {
+                for j in 0..=n - i {
+                    let (s, r) = (i as f64 / n as f64, j as f64 / n as f64);
+                    let p = linalg::add(
+                        a,
+                        linalg::add(linalg::scale(linalg::sub(b, a), s), linalg::scale(linalg::sub(c, a), r)),
+                    );
+                    samples.push((p, index));
+                }
+            }
+        }
+        build_kd_tree(&mut samples, 0);
+
+        Self {
+            positions,
+            uvs,
+            triangles,
+            samples,
+            extent,
+        }
+    }
+
+    pub fn triangle_count(&self) -> usize {
+        self.triangles.len()
+    }
+
+    /// Closest surface point and its content UV (top-left origin).
+    fn closest(&self, point: Vec3) -> Option<(Vec3, [f64; 2])> {
+        let mut best = (f64::INFINITY, usize::MAX);
+        nearest_in_kd_tree(&self.samples, 0, point, &mut best);
+        let triangle = self.samples.get(best.1)?.1;
+        let t = &self.triangles[triangle];
+        let [a, b, c] = t.map(|(v, _)| self.positions[v]);
+        let (closest, [wa, wb, wc]) = closest_on_triangle(point, a, b, c);
+        let [ta, tb, tc] = t.map(|(_, vt)| self.uvs[vt]);
+        let u = wa * ta[0] + wb * tb[0] + wc * tc[0];
+        let v = wa * ta[1] + wb * tb[1] + wc * tc[1];
+        // OBJ texture coordinates have their origin at the bottom left
+        Some((closest, [u, 1.0 - v]))
+    }
+
+    /// UV of the closest surface point, if the point lies on the mesh.
+    fn uv_near(&self, point: Vec3) -> Option<[f64; 2]> {
+        let (closest, uv) = self.closest(point)?;
+        (linalg::norm(linalg::sub(closest, point)) < 0.05 * self.extent).then_some(uv)
+    }
+
+    /// Register surface points (camera frame) to the mesh with scaled ICP.
+    ///
+    /// The mesh is assumed to be modeled Y-up and facing the camera; the
+    /// initial guess matches centroids and sizes.
+    fn register(&self, points: &[Vec3]) -> Result<(Similarity, f64), String> {
+        if points.len() < 10 {
+            return Err("Not enough surface points to register the mesh".to_string());
+        }
+        let step = (points.len() / 4000).max(1);
+        let source: Vec<Vec3> = points.iter().step_by(step).copied().collect();
+
+        let centroid = |ps: &[Vec3]| {
+            linalg::scale(ps.iter().fold([0.0; 3], |acc, p| linalg::add(acc, *p)), 1.0 / ps.len() as f64)
+        };
+        let spread = |ps: &[Vec3], c: Vec3| {
+            (ps.iter().map(|p| linalg::dot(linalg::sub(*p, c), linalg::sub(*p, c))).sum::<f64>() / ps.len() as f64)
+                .sqrt()
+        };
+        let rotated: Vec<Vec3> = source.iter().map(|p| linalg::mat_vec(&CAMERA_TO_MESH, *p)).collect();
+        let (c_src, c_mesh) = (centroid(&rotated), centroid(&self.positions));
+        let scale = spread(&self.positions, c_mesh) / spread(&rotated, c_src).max(1e-12);
+        let mut alignment = Similarity {
+            scale,
+            rotation: CAMERA_TO_MESH,
+            translation: linalg::sub(c_mesh, linalg::scale(c_src, scale)),
+        };
+
+        let mut residual = f64::INFINITY;
+        for _ in 0..ICP_ITERS {
+            let mut matches: Vec<(f64, Vec3, Vec3)> = source
+                .iter()
+                .filter_map(|p| {
+                    let moved = alignment.apply(*p);
+                    let (closest, _) = self.closest(moved)?;
+                    Some((linalg::norm(linalg::sub(closest, moved)), *p, closest))
+                })
+                .collect();
+            matches.sort_by(|a, b| a.0.total_cmp(&b.0));
+            matches.truncate(((matches.len() as f64) * (1.0 - ICP_TRIM)).ceil() as usize);
+            let mean = matches.iter().map(|m| m.0).sum::<f64>() / matches.len().max(1) as f64;
+
+            let (src, dst): (Vec<Vec3>, Vec<Vec3>) = matches.iter().map(|m| (m.1, m.2)).unzip();
+            let Some(next) = Similarity::fit(&src, &dst) else {
+                break;
+            };
+            alignment = next;
+            let converged = (residual - mean).abs() < 1e-6 * self.extent;
+            residual = mean;
+            if converged {
+                break;
+            }
+        }
+
+        log::info!("Mesh registered: scale {:.4}, mean distance {:.4}", alignment.scale, residual);
+        Ok((alignment, residual))
+    }
+}
+
+fn build_kd_tree(items: &mut [(Vec3, usize)], axis: usize) {
+    if items.len() <= 1 {
+        return;
+    }
+    let mid = items.len() / 2;
+    items.select_nth_unstable_by(mid, |a, b| a.0[axis].total_cmp(&b.0[axis]));
+    let (left, right) = items.split_at_mut(mid);
+    build_kd_tree(left, (axis + 1) % 3);
+    build_kd_tree(&mut right[1..], (axis + 1) % 3);
+}
+
+/// Nearest sample; `best` holds (squared distance, index into the full slice).
+fn nearest_in_kd_tree(items: &[(Vec3, usize)], offset: usize, query: Vec3, best: &mut (f64, usize)) {
+    fn search(items: &[(Vec3, usize)], offset: usize, axis: usize, query: Vec3, best: &mut (f64, usize)) {
+        if items.is_empty() {
+            return;
+        }
+        let mid = items.len() / 2;
+        let d = linalg::sub(items[mid].0, query);
+        let dist = linalg::dot(d, d);
+        if dist < best.0 {
+            *best = (dist, offset + mid);
+        }
+        let delta = query[axis] - items[mid].0[axis];
+        let next = (axis + 1) % 3;
+        let (near, near_offset, far, far_offset) = if delta < 0.0 {
+            (&items[..mid], offset, &items[mid + 1..], offset + mid + 1)
+        } else {
+            (&items[mid + 1..], offset + mid + 1, &items[..mid], offset)
+        };
+        search(near, near_offset, next, query, best);
+        if delta * delta < best.0 {
+            search(far, far_offset, next, query, best);
+        }
+    }
+    search(items, offset, 0, query, best);
+}
+
+/// Closest point on a triangle and its barycentric weights (Ericson).
+fn closest_on_triangle(p: Vec3, a: Vec3, b: Vec3, c: Vec3) -> (Vec3, [f64; 3]) {
+    let ab = linalg::sub(b, a);
+    let ac = linalg::sub(c, a);
+    let ap = linalg::sub(p, a);
+    let d1 = linalg::dot(ab, ap);
+    let d2 = linalg::dot(ac, ap);
+    if d1 <= 0.0 && d2 <= 0.0 {
+        return (a, [1.0, 0.0, 0.0]);
+    }
+    let bp = linalg::sub(p, b);
+    let d3 = linalg::dot(ab, bp);
+    let d4 = linalg::dot(ac, bp);
+    if d3 >= 0.0 && d4 <= d3 {
+        return (b, [0.0, 1.0, 0.0]);
+    }
+    let vc = d1 * d4 - d3 * d2;
+    if vc <= 0.0 && d1 >= 0.0 && d3 <= 0.0 {
+        let v = d1 / (d1 - d3);
+        return (linalg::add(a, linalg::scale(ab, v)), [1.0 - v, v, 0.0]);
+    }
+    let cp = linalg::sub(p, c);
+    let d5 = linalg::dot(ab, cp);
+    let d6 = linalg::dot(ac, cp);
+    if d6 >= 0.0 && d5 <= d6 {
+        return (c, [0.0, 0.0, 1.0]);
+    }
+    let vb = d5 * d2 - d1 * d6;
+    if vb <= 0.0 && d2 >= 0.0 && d6 <= 0.0 {
+        let w = d2 / (d2 - d6);
+        return (linalg::add(a, linalg::scale(ac, w)), [1.0 - w, 0.0, w]);
+    }
+    let va = d3 * d6 - d5 * d4;
+    if va <= 0.0 && (d4 - d3) >= 0.0 && (d5 - d6) >= 0.0 {
+        let w = (d4 - d3) / ((d4 - d3) + (d5 - d6));
+        return (linalg::add(b, linalg::scale(linalg::sub(c, b), w)), [0.0, 1.0 - w, w]);
+    }
+    let denom = 1.0 / (va + vb + vc);
+    let v = vb * denom;
+    let w = vc * denom;
+    (
+        linalg::add(a, linalg::add(linalg::scale(ab, v), linalg::scale(ac, w))),
+        [1.0 - v - w, v, w],
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cylinder_fit_unwraps_surface() {
+        // Tilted cylinder arc seen from inside, axis through (0.2, 0, 3)
+        let axis = linalg::normalize([0.1, -1.0, 0.05]);
+        let basis = basis_from(axis, [0.0, 0.0, 1.0]);
+        let center = [0.2, 0.0, 3.0];
+        let mut points = Vec::new();
+        for i in 0..60 {
+            for j in 0..20 {
+                let angle = -1.0 + 2.0 * i as f64 / 59.0;
+                let height = -0.8 + 1.6 * j as f64 / 19.0;
+                let local = [2.5 * angle.sin(), height, 2.5 * angle.cos()];
+                let offset = linalg::mat_vec(&linalg::transpose(&basis), local);
+                points.push(linalg::add(center, offset));
+            }
+        }
+
+        let mapping = ContentMapping::fit(&ContentSpace::Cylinder, &points).unwrap();
+        let ContentMapping::Cylinder { radius, .. } = &mapping else {
+            panic!("expected a cylinder");
+        };
+        assert!((radius - 2.5).abs() < 1e-3, "radius {}", radius);
+
+        // Left/top corner to right/bottom corner
+        let first = mapping.uv(points[19]).unwrap();
+        let last = mapping.uv(points[points.len() - 20]).unwrap();
+        assert!(first[0] < 0.02 && first[1] < 0.02, "{:?}", first);
+        assert!(last[0] > 0.98 && last[1] > 0.98, "{:?}", last);
+    }
+
+    #[test]
+    fn test_mesh_registration_and_uv_lookup() {
+        // Curved strip in the Y-up mesh frame with u along X and v along Y
+        let mut obj = String::new();
+        let (cols, rows) = (24, 8);
+        for j in 0..=rows {
+            for i in 0..=cols {
+                let (u, v) = (i as f64 / cols as f64, j as f64 / rows as f64);
+                let angle = (u - 0.5) * 2.0;
+                obj += &format!("v {} {} {}\n", 3.0 * angle.sin(), 2.0 * v - 1.0, -3.0 * angle.cos());
+                obj += &format!("vt {} {}\n", u, v);
+            }
+        }
+        for j in 0..rows {
+            for i in 0..cols {
+                let a = j * (cols + 1) + i + 1;
+                let (b, c, d) = (a + 1, a + cols + 2, a + cols + 1);
+                obj += &format!("f {a}/{a} {b}/{b} {c}/{c} {d}/{d}\n");
+            }
+        }
+        let mesh = MeshSurface::parse_obj(&obj).unwrap();
+        assert_eq!(mesh.triangle_count(), cols * rows * 2);
+
+        // The same surface in the camera frame at half scale, shifted
+        let to_camera = |p: Vec3| {
+            let c = linalg::mat_vec(&CAMERA_TO_MESH, p);
+            linalg::add(linalg::scale(c, 0.5), [0.1, 0.05, 0.0])
+        };
+        let mut points = Vec::new();
+        for j in 0..40 {
+            for i in 0..80 {
+                let angle = (i as f64 / 79.0 - 0.5) * 2.0;
+                let y = 2.0 * j as f64 / 39.0 - 1.0;
+                points.push(to_camera([3.0 * angle.sin(), y, -3.0 * angle.cos()]));
+            }
+        }
+
+        let mapping = ContentMapping::fit(&ContentSpace::Mesh(mesh), &points).unwrap();
+        // Mesh u = 0.25, v = 0.75 (OBJ) -> content v = 0.25
+        let probe = to_camera([3.0 * (-0.5f64).sin(), 0.5, -3.0 * (-0.5f64).cos()]);
+        let uv = mapping.uv(probe).unwrap();
+        assert!((uv[0] - 0.25).abs() < 0.01 && (uv[1] - 0.25).abs() < 0.01, "{:?}", uv);
+
+        assert!(MeshSurface::parse_obj("v 0 0 0\nv 1 0 0\nv 0 1 0\nf 1 2 3\n").is_err());
+    }
+}
@@ -1,7 +1,10 @@
 //! Gray code pattern decoder.
 
+use crate::config::CorrespondenceMap;
+use serde::{Deserialize, Serialize};
+
 /// Result of decoding Gray code patterns.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DecodedCorrespondences {
     /// Camera image dimensions.
     pub camera_width: u32,
@@ -17,6 +20,10 @@ pub struct DecodedCorrespondences {
     pub confidence: Vec<f32>,
     /// Shadow/occlusion mask (true = valid pixel).
     pub valid_mask: Vec<bool>,
+    /// Sub-pixel X coordinate (phase-shift decoding only).
+    pub subpixel_x: Option<Vec<f32>>,
+    /// Sub-pixel Y coordinate (phase-shift decoding only).
+    pub subpixel_y: Option<Vec<f32>>,
 }
 
 impl DecodedCorrespondences {
@@ -31,21 +38,199 @@ impl DecodedCorrespondences {
             projector_y: vec![-1; size],
             confidence: vec![0.0; size],
             valid_mask: vec![false; size],
+            subpixel_x: None,
+            subpixel_y: None,
         }
     }
 
+    /// Projector position decoded at a camera pixel index, sub-pixel when available.
+    pub fn projector_position(&self, idx: usize) -> Option<(f64, f64)> {
+        if !self.valid_mask[idx] || self.projector_x[idx] < 0 || self.projector_y[idx] < 0 {
+            return None;
+        }
+        let x = match &self.subpixel_x {
+            Some(sub) => sub[idx] as f64,
+            None => self.projector_x[idx] as f64,
+        };
+        let y = match &self.subpixel_y {
+            Some(sub) => sub[idx] as f64,
+            None => self.projector_y[idx] as f64,
+        };
+        Some((x, y))
+    }
+
     /// Get correspondence at camera pixel (x, y).
     pub fn get(&self, x: u32, y: u32) -> Option<(f32, f32)> {
         let idx = (y * self.camera_width + x) as usize;
-        if self.valid_mask[idx] {
-            Some((self.projector_x[idx] as f32, self.projector_y[idx] as f32))
-        } else {
-            None
-        }
+        self.projector_position(idx).map(|(px, py)| (px as f32, py as f32))
     }
 
     /// Count valid correspondences.
     pub fn valid_count(&self) -> usize {
         self.valid_mask.iter().filter(|&&v| v).count()
     }
+
+    /// Resample the per-camera-pixel decode into a projector-space grid.
+    ///
+    /// Every decoded camera pixel is assigned to its nearest grid node. Each
+    /// node then fits a local affine map from projector offset to camera
+    /// position over its samples and evaluates it at the node, so nodes on
+    /// the projector border are not pulled inward by one-sided neighborhoods.
+    pub fn to_correspondence_map(&self, projector_id: u32, columns: u32, rows: u32) -> CorrespondenceMap {
+        let mut map = CorrespondenceMap::new(
+            projector_id,
+            (self.projector_width, self.projector_height),
+            (self.camera_width, self.camera_height),
+            columns,
+            rows,
+        );
+        let columns = map.columns;
+        let rows = map.rows;
+        let step_x = self.projector_width.saturating_sub(1).max(1) as f64 / (columns - 1) as f64;
+        let step_y = self.projector_height.saturating_sub(1).max(1) as f64 / (rows - 1) as f64;
+
+        // Per node: normal equation sums for camera = a + b*dx + c*dy
+        let mut sums = vec![NodeSums::default(); (columns * rows) as usize];
+
+        for cy in 0..self.camera_height {
+            for cx in 0..self.camera_width {
+                let idx = (cy * self.camera_width + cx) as usize;
+                let Some((px, py)) = self.projector_position(idx) else {
+                    continue;
+                };
+                let col = (px / step_x).round().clamp(0.0, (columns - 1) as f64) as u32;
+                let row = (py / step_y).round().clamp(0.0, (rows - 1) as f64) as u32;
+                // Offsets in cell units keep the normal equations well conditioned
+                let dx = px / step_x - col as f64;
+                let dy = py / step_y - row as f64;
+                sums[(row * columns + col) as usize].add(dx, dy, cx as f64, cy as f64);
+            }
+        }
+
+        for row in 0..rows {
+            for col in 0..columns {
+                let camera = sums[(row * columns + col) as usize].solve().and_then(|(x, y)| {
+                    let in_bounds = x >= -0.5
+                        && y >= -0.5
+                        && x <= self.camera_width as f64 - 0.5
+                        && y <= self.camera_height as f64 - 0.5;
+                    in_bounds.then_some([x as f32, y as f32])
+                });
+                map.set(col, row, camera);
+            }
+        }
+
+        map
+    }
+}
+
+/// Least-squares accumulator for one correspondence grid node.
+#[derive(Debug, Clone, Copy, Default)]
+struct NodeSums {
+    n: f64,
+    dx: f64,
+    dy: f64,
+    dxdx: f64,
+    dxdy: f64,
+    dydy: f64,
+    cx: f64,
+    cy: f64,
+    cx_dx: f64,
+    cx_dy: f64,
+    cy_dx: f64,
+    cy_dy: f64,
+}
+
+impl NodeSums {
+    /// Minimum samples before a node is considered decoded.
+    const MIN_SAMPLES: f64 = 4.0;
+
+    fn add(&mut self, dx: f64, dy: f64, cx: f64, cy: f64) {
+        self.n += 1.0;
+        self.dx += dx;
+        self.dy += dy;
+        self.dxdx += dx * dx;
+        self.dxdy += dx * dy;
+        self.dydy += dy * dy;
+        self.cx += cx;
+        self.cy += cy;
+        self.cx_dx += cx * dx;
+        self.cx_dy += cx * dy;
+        self.cy_dx += cy * dx;
+        self.cy_dy += cy * dy;
+    }
+
+    /// Camera position at the node (zero offset).
+    ///
+    /// Falls back to the sample mean when the samples are degenerate
+    /// (e.g. all on one projector row).
+    fn solve(&self) -> Option<(f64, f64)> {
+        if self.n < Self::MIN_SAMPLES {
+            return None;
+        }
+        let m = [
+            [self.n, self.dx, self.dy],
+            [self.dx, self.dxdx, self.dxdy],
+            [self.dy, self.dxdy, self.dydy],
+        ];
+        let det = det3(&m);
+        if det.abs() < 1e-9 * self.n * self.n * self.n {
+            return Some((self.cx / self.n, self.cy / self.n));
+        }
+        // Cramer's rule for the constant term only
+        let intercept = |b: [f64; 3]| {
+            let mut mb = m;
+            for (r, value) in b.iter().enumerate() {
+                mb[r][0] = *value;
+            }
+            det3(&mb) / det
+        };
+        Some((
+            intercept([self.cx, self.cx_dx, self.cx_dy]),
+            intercept([self.cy, self.cy_dx, self.cy_dy]),
+        ))
+    }
+}
+
+fn det3(m: &[[f64; 3]; 3]) -> f64 {
+    m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+        - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+        + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_correspondence_map_recovers_affine_mapping() {
+        // Camera sees the 64x48 projector scaled by 2 and offset by (10, 6)
+        let mut decoded = DecodedCorrespondences::new(160, 120, 64, 48);
+        for cy in 0..120u32 {
+            for cx in 0..160u32 {
+                let px = (cx as i32 - 10) / 2;
+                let py = (cy as i32 - 6) / 2;
+                if (0..64).contains(&px) && (0..48).contains(&py) && cx >= 10 && cy >= 6 {
+                    let idx = (cy * 160 + cx) as usize;
+                    decoded.projector_x[idx] = px;
+                    decoded.projector_y[idx] = py;
+                    decoded.valid_mask[idx] = true;
+                }
+            }
+        }
+
+        let map = decoded.to_correspondence_map(7, 8, 4);
+        assert_eq!(map.projector_id, 7);
+        assert_eq!(map.valid_count(), 32);
+
+        for row in 0..4 {
+            for col in 0..8 {
+                let [px, py] = map.node_position(col, row);
+                let [cx, cy] = map.get(col, row).unwrap();
+                // Each projector pixel covers 2x2 camera pixels; expect their center
+                assert!((cx - (10.5 + px * 2.0)).abs() < 0.75, "x at ({col},{row}): {cx}");
+                assert!((cy - (6.5 + py * 2.0)).abs() < 0.75, "y at ({col},{row}): {cy}");
+            }
+        }
+    }
 }
@@ -1,7 +1,10 @@
 //! Gray code pattern generation for structured light calibration.
 
+use super::phase_shift::{PhaseShiftConfig, PhaseStep};
+use serde::{Deserialize, Serialize};
+
 /// Direction of pattern stripes.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum PatternDirection {
     /// Stripes run horizontally, decode Y coordinate.
     Horizontal,
@@ -10,7 +13,7 @@ pub enum PatternDirection {
 }
 
 /// A single Gray code pattern specification.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct PatternSpec {
     /// Which bit of the Gray code this pattern encodes.
     pub bit_index: u32,
@@ -18,10 +21,12 @@ pub struct PatternSpec {
     pub direction: PatternDirection,
     /// Whether this is the inverted version.
     pub inverted: bool,
+    /// Sinusoidal fringe image instead of a Gray code bit.
+    pub phase: Option<PhaseStep>,
 }
 
 /// Configuration for pattern generation.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PatternConfig {
     /// Projector resolution width.
     pub projector_width: u32,
@@ -31,6 +36,8 @@ pub struct PatternConfig {
     pub horizontal_bits: u32,
     /// Number of bits for vertical patterns.
     pub vertical_bits: u32,
+    /// Sub-pixel phase-shift mode (Gray code only resolves half periods).
+    pub phase_shift: Option<PhaseShiftConfig>,
 }
 
 impl PatternConfig {
@@ -43,45 +50,66 @@ impl PatternConfig {
             projector_height: height,
             horizontal_bits,
             vertical_bits,
+            phase_shift: None,
+        }
+    }
+
+    /// Enable phase-shift patterns for sub-pixel decoding.
+    pub fn with_phase_shift(mut self, phase_shift: PhaseShiftConfig) -> Self {
+        self.phase_shift = Some(phase_shift);
+        self
+    }
+
+    /// Number of Gray code bits projected for a direction.
+    ///
+    /// With phase shifting only the most significant bits are needed, down to
+    /// half-period resolution.
+    pub fn gray_bits(&self, direction: PatternDirection) -> u32 {
+        let bits = match direction {
+            PatternDirection::Horizontal => self.vertical_bits,
+            PatternDirection::Vertical => self.horizontal_bits,
+        };
+        match &self.phase_shift {
+            Some(phase) => bits.saturating_sub(phase.dropped_bits()).max(1),
+            None => bits,
         }
     }
 
     /// Total number of patterns needed (including inverted pairs).
     pub fn total_patterns(&self) -> usize {
-        // Each direction: bits * 2 (positive + inverted) + 2 (white/black reference)
-        ((self.horizontal_bits + self.vertical_bits) * 2 + 2) as usize
+        // Each direction: bits * 2 (positive + inverted) + phase images, plus 2 (white/black reference)
+        let gray = self.gray_bits(PatternDirection::Horizontal) + self.gray_bits(PatternDirection::Vertical);
+        let phase = self.phase_shift.map_or(0, |p| p.steps * 2);
+        (gray * 2 + phase + 2) as usize
     }
 
     /// Generate the sequence of all patterns to project.
     pub fn pattern_sequence(&self) -> Vec<PatternSpec> {
         let mut patterns = Vec::with_capacity(self.total_patterns());
 
-        // Horizontal patterns (decode Y coordinate)
-        for bit in 0..self.vertical_bits {
-            patterns.push(PatternSpec {
-                bit_index: bit,
-                direction: PatternDirection::Horizontal,
-                inverted: false,
-            });
-            patterns.push(PatternSpec {
-                bit_index: bit,
-                direction: PatternDirection::Horizontal,
-                inverted: true,
-            });
-        }
+        // Horizontal patterns (decode Y coordinate), then vertical (decode X)
+        for direction in [PatternDirection::Horizontal, PatternDirection::Vertical] {
+            for bit in 0..self.gray_bits(direction) {
+                for inverted in [false, true] {
+                    patterns.push(PatternSpec {
+                        bit_index: bit,
+                        direction,
+                        inverted,
+                        phase: None,
+                    });
+                }
+            }
 
-        // Vertical patterns (decode X coordinate)
-        for bit in 0..self.horizontal_bits {
-            patterns.push(PatternSpec {
-                bit_index: bit,
-                direction: PatternDirection::Vertical,
-                inverted: false,
-            });
-            patterns.push(PatternSpec {
-                bit_index: bit,
-                direction: PatternDirection::Vertical,
-                inverted: true,
-            });
+            if let Some(config) = self.phase_shift {
+                for step in 0..config.steps {
+                    patterns.push(PatternSpec {
+                        bit_index: 0,
+                        direction,
+                        inverted: false,
+                        phase: Some(PhaseStep { step, config }),
+                    });
+                }
+            }
         }
 
         patterns
@@ -100,6 +128,11 @@ impl GrayCodeGenerator {
         }
     }
 
+    /// Generator for an existing pattern configuration (e.g. with phase shift).
+    pub fn from_config(config: PatternConfig) -> Self {
+        Self { config }
+    }
+
     pub fn config(&self) -> &PatternConfig {
         &self.config
     }
@@ -138,6 +171,12 @@ impl GrayCodeGenerator {
                     PatternDirection::Vertical => x,
                 };
 
+                if let Some(phase) = &spec.phase {
+                    let intensity = phase.config.intensity(coord as f32, phase.step);
+                    data[(y * width + x) as usize] = (intensity * 255.0).round() as u8;
+                    continue;
+                }
+
                 let gray = Self::binary_to_gray(coord);
                 let bit_position = total_bits - 1 - spec.bit_index;
                 let bit_value = (gray >> bit_position) & 1;
@@ -189,4 +228,20 @@ mod tests {
         assert_eq!(config.vertical_bits, 11);
         assert_eq!(config.total_patterns(), 46);
     }
+
+    #[test]
+    fn test_phase_shift_pattern_sequence() {
+        let config = PatternConfig::new(1920, 1080).with_phase_shift(PhaseShiftConfig::new(4, 16));
+        // Half-period (8 px) resolution drops 3 of the 11 bits per direction
+        assert_eq!(config.gray_bits(PatternDirection::Vertical), 8);
+        assert_eq!(config.gray_bits(PatternDirection::Horizontal), 8);
+        assert_eq!(config.total_patterns(), 8 * 2 * 2 + 4 * 2 + 2);
+
+        let sequence = config.pattern_sequence();
+        assert_eq!(sequence.len(), config.total_patterns() - 2);
+        let fringes: Vec<_> = sequence.iter().filter(|s| s.phase.is_some()).collect();
+        assert_eq!(fringes.len(), 8);
+        assert_eq!(fringes[3].phase.unwrap().step, 3);
+        assert_eq!(fringes[4].direction, PatternDirection::Vertical);
+    }
 }
@@ -1,12 +1,15 @@
-//! Homography computation using OpenCV.
+//! Homography computation.
 //!
 //! Computes camera-to-projector homography from decoded correspondences
-//! using RANSAC for robust outlier rejection.
+//! using RANSAC for robust outlier rejection. OpenCV is used when the `opencv`
+//! feature is enabled, otherwise a pure-Rust implementation with the same
+//! interface and result statistics.
 
 use super::decoder::DecodedCorrespondences;
+use serde::{Deserialize, Serialize};
 
 /// Result of homography computation.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HomographyResult {
     /// 3x3 homography matrix (row-major).
     pub matrix: [[f64; 3]; 3],
@@ -144,14 +147,11 @@ mod opencv_impl {
                 for x in (0..corr.camera_width).step_by(stride as usize) {
                     let idx = (y * corr.camera_width + x) as usize;
 
-                    if corr.valid_mask[idx] && corr.confidence[idx] > 0.5 {
-                        let proj_x = corr.projector_x[idx] as f32;
-                        let proj_y = corr.projector_y[idx] as f32;
-
+                    if corr.confidence[idx] > 0.5 {
                         // Skip invalid projector coordinates
-                        if proj_x >= 0.0 && proj_y >= 0.0 {
+                        if let Some((proj_x, proj_y)) = corr.projector_position(idx) {
                             src_points.push(Point2f::new(x as f32, y as f32));
-                            dst_points.push(Point2f::new(proj_x, proj_y));
+                            dst_points.push(Point2f::new(proj_x as f32, proj_y as f32));
                         }
                     }
                 }
@@ -239,69 +239,501 @@ mod opencv_impl {
                 [data[6], data[7], data[8]],
             ])
         }
+    }
+}
+
+#[cfg(feature = "opencv")]
+pub use opencv_impl::HomographyComputer;
+
+#[cfg(not(feature = "opencv"))]
+pub use software_impl::HomographyComputer;
+
+impl HomographyComputer {
+    /// Apply homography to a point (camera -> projector).
+    pub fn transform_point(matrix: &[[f64; 3]; 3], x: f64, y: f64) -> (f64, f64) {
+        let h = matrix;
+        let w = h[2][0] * x + h[2][1] * y + h[2][2];
+        if w.abs() < 1e-10 {
+            return (0.0, 0.0);
+        }
+        let tx = (h[0][0] * x + h[0][1] * y + h[0][2]) / w;
+        let ty = (h[1][0] * x + h[1][1] * y + h[1][2]) / w;
+        (tx, ty)
+    }
+
+    /// Compute inverse homography matrix.
+    pub fn invert(matrix: &[[f64; 3]; 3]) -> Result<[[f64; 3]; 3], String> {
+        let h = matrix;
+
+        // Compute determinant
+        let det = h[0][0] * (h[1][1] * h[2][2] - h[1][2] * h[2][1])
+                - h[0][1] * (h[1][0] * h[2][2] - h[1][2] * h[2][0])
+                + h[0][2] * (h[1][0] * h[2][1] - h[1][1] * h[2][0]);
+
+        if det.abs() < 1e-10 {
+            return Err("Matrix is singular, cannot invert".to_string());
+        }
+
+        let inv_det = 1.0 / det;
+
+        Ok([
+            [
+                (h[1][1] * h[2][2] - h[1][2] * h[2][1]) * inv_det,
+                (h[0][2] * h[2][1] - h[0][1] * h[2][2]) * inv_det,
+                (h[0][1] * h[1][2] - h[0][2] * h[1][1]) * inv_det,
+            ],
+            [
+                (h[1][2] * h[2][0] - h[1][0] * h[2][2]) * inv_det,
+                (h[0][0] * h[2][2] - h[0][2] * h[2][0]) * inv_det,
+                (h[0][2] * h[1][0] - h[0][0] * h[1][2]) * inv_det,
+            ],
+            [
+                (h[1][0] * h[2][1] - h[1][1] * h[2][0]) * inv_det,
+                (h[0][1] * h[2][0] - h[0][0] * h[2][1]) * inv_det,
+                (h[0][0] * h[1][1] - h[0][1] * h[1][0]) * inv_det,
+            ],
+        ])
+    }
+}
+
+/// Fit a homography to outlier-free point pairs (source -> destination).
+///
+/// Normalized DLT followed by Levenberg-Marquardt refinement; used for
+/// checkerboard corners, which need no RANSAC.
+pub(super) fn fit_point_homography(pairs: &[([f64; 2], [f64; 2])]) -> Option<[[f64; 3]; 3]> {
+    let linear = software_impl::fit_dlt(pairs)?;
+    Some(software_impl::refine_lm(&linear, pairs))
+}
+
+/// Pure-Rust homography estimation, used when OpenCV is not available.
+///
+/// Follows the same pipeline as `cv::findHomography` with RANSAC: normalized
+/// DLT on random minimal samples, a refit on the best inlier set, then
+/// Levenberg-Marquardt refinement of the reprojection error.
+#[cfg_attr(feature = "opencv", allow(dead_code))]
+mod software_impl {
+    use super::*;
+
+    /// Point pair: camera pixel and decoded projector pixel.
+    type PointPair = ([f64; 2], [f64; 2]);
+
+    /// Maximum Levenberg-Marquardt iterations.
+    const LM_MAX_ITERS: usize = 30;
+
+    /// Homography computer (pure Rust).
+    pub struct HomographyComputer {
+        /// RANSAC reprojection threshold in pixels.
+        pub ransac_threshold: f64,
+        /// Maximum RANSAC iterations.
+        pub max_iters: usize,
+        /// Confidence level for RANSAC.
+        pub confidence: f64,
+        /// Minimum points required for homography.
+        pub min_points: usize,
+        /// Sampling stride for correspondences.
+        pub sample_stride: u32,
+        /// Seed for RANSAC sampling (results are reproducible).
+        pub seed: u64,
+    }
+
+    impl Default for HomographyComputer {
+        fn default() -> Self {
+            Self {
+                ransac_threshold: 3.0,
+                max_iters: 2000,
+                confidence: 0.995,
+                min_points: 100,
+                sample_stride: 4,
+                seed: 0x9E37_79B9_7F4A_7C15,
+            }
+        }
+    }
+
+    impl HomographyComputer {
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        /// Compute homography from decoded correspondences.
+        pub fn compute(&self, correspondences: &DecodedCorrespondences) -> Result<HomographyResult, String> {
+            let pairs = self.extract_points(correspondences);
 
-        /// Apply homography to a point (camera -> projector).
-        pub fn transform_point(matrix: &[[f64; 3]; 3], x: f64, y: f64) -> (f64, f64) {
-            let h = matrix;
-            let w = h[2][0] * x + h[2][1] * y + h[2][2];
-            if w.abs() < 1e-10 {
-                return (0.0, 0.0);
+            if pairs.len() < self.min_points.max(4) {
+                return Err(format!(
+                    "Not enough valid correspondences: {} (need at least {})",
+                    pairs.len(),
+                    self.min_points.max(4)
+                ));
             }
-            let tx = (h[0][0] * x + h[0][1] * y + h[0][2]) / w;
-            let ty = (h[1][0] * x + h[1][1] * y + h[1][2]) / w;
-            (tx, ty)
+
+            log::info!("Computing homography from {} point pairs", pairs.len());
+
+            let best = self.ransac(&pairs).ok_or("Failed to compute homography")?;
+            let inliers: Vec<PointPair> = self.inliers(&best, &pairs).map(|i| pairs[i]).collect();
+
+            // Refit on all inliers, then refine the geometric error
+            let linear = fit_dlt(&inliers).unwrap_or(best);
+            let refined = refine_lm(&linear, &inliers);
+
+            let inlier_indices: Vec<usize> = self.inliers(&refined, &pairs).collect();
+            let inlier_count = inlier_indices.len();
+            let inlier_ratio = inlier_count as f32 / pairs.len() as f32;
+            let reprojection_error = if inlier_count > 0 {
+                inlier_indices
+                    .iter()
+                    .map(|&i| reprojection_error(&refined, &pairs[i]))
+                    .sum::<f64>()
+                    / inlier_count as f64
+            } else {
+                0.0
+            };
+
+            log::info!(
+                "Homography computed: {} inliers ({:.1}%), error: {:.2}px",
+                inlier_count,
+                inlier_ratio * 100.0,
+                reprojection_error
+            );
+
+            Ok(HomographyResult {
+                matrix: refined,
+                inlier_count,
+                inlier_ratio,
+                reprojection_error,
+            })
         }
 
-        /// Compute inverse homography matrix.
-        pub fn invert(matrix: &[[f64; 3]; 3]) -> Result<[[f64; 3]; 3], String> {
-            let h = matrix;
+        /// Extract point pairs from correspondences with sampling.
+        fn extract_points(&self, corr: &DecodedCorrespondences) -> Vec<PointPair> {
+            let stride = self.sample_stride.max(1) as usize;
+            let mut pairs = Vec::new();
 
-            // Compute determinant
-            let det = h[0][0] * (h[1][1] * h[2][2] - h[1][2] * h[2][1])
-                    - h[0][1] * (h[1][0] * h[2][2] - h[1][2] * h[2][0])
-                    + h[0][2] * (h[1][0] * h[2][1] - h[1][1] * h[2][0]);
+            for y in (0..corr.camera_height).step_by(stride) {
+                for x in (0..corr.camera_width).step_by(stride) {
+                    let idx = (y * corr.camera_width + x) as usize;
 
-            if det.abs() < 1e-10 {
-                return Err("Matrix is singular, cannot invert".to_string());
+                    if corr.confidence[idx] > 0.5 {
+                        // Skip invalid projector coordinates
+                        if let Some((proj_x, proj_y)) = corr.projector_position(idx) {
+                            pairs.push(([x as f64, y as f64], [proj_x, proj_y]));
+                        }
+                    }
+                }
             }
 
-            let inv_det = 1.0 / det;
+            pairs
+        }
 
-            Ok([
-                [
-                    (h[1][1] * h[2][2] - h[1][2] * h[2][1]) * inv_det,
-                    (h[0][2] * h[2][1] - h[0][1] * h[2][2]) * inv_det,
-                    (h[0][1] * h[1][2] - h[0][2] * h[1][1]) * inv_det,
-                ],
-                [
-                    (h[1][2] * h[2][0] - h[1][0] * h[2][2]) * inv_det,
-                    (h[0][0] * h[2][2] - h[0][2] * h[2][0]) * inv_det,
-                    (h[0][2] * h[1][0] - h[0][0] * h[1][2]) * inv_det,
-                ],
-                [
-                    (h[1][0] * h[2][1] - h[1][1] * h[2][0]) * inv_det,
-                    (h[0][1] * h[2][0] - h[0][0] * h[2][1]) * inv_det,
-                    (h[0][0] * h[1][1] - h[0][1] * h[1][0]) * inv_det,
-                ],
-            ])
+        /// Indices of the pairs within the RANSAC threshold.
+        fn inliers<'a>(
+            &'a self,
+            matrix: &'a [[f64; 3]; 3],
+            pairs: &'a [PointPair],
+        ) -> impl Iterator<Item = usize> + 'a {
+            (0..pairs.len()).filter(move |&i| reprojection_error(matrix, &pairs[i]) <= self.ransac_threshold)
+        }
+
+        /// Best model over random minimal samples, with adaptive termination.
+        fn ransac(&self, pairs: &[PointPair]) -> Option<[[f64; 3]; 3]> {
+            let mut rng = XorShift64::new(self.seed);
+            let mut best: Option<([[f64; 3]; 3], usize)> = None;
+            let mut needed_iters = self.max_iters;
+            let mut iter = 0;
+
+            while iter < needed_iters {
+                iter += 1;
+                let sample = rng.sample4(pairs.len());
+                let points = sample.map(|i| pairs[i]);
+                if is_degenerate(&points) {
+                    continue;
+                }
+                let Some(matrix) = fit_dlt(&points) else {
+                    continue;
+                };
+
+                let count = self.inliers(&matrix, pairs).count();
+                if best.as_ref().is_none_or(|(_, best_count)| count > *best_count) {
+                    best = Some((matrix, count));
+
+                    // Iterations needed to draw one all-inlier sample with the given confidence
+                    let ratio = count as f64 / pairs.len() as f64;
+                    let all_inliers = ratio.powi(4);
+                    if all_inliers >= 1.0 {
+                        break;
+                    }
+                    if all_inliers > 0.0 {
+                        let iters = (1.0 - self.confidence).ln() / (1.0 - all_inliers).ln();
+                        needed_iters = needed_iters.min(iters.ceil().max(1.0) as usize);
+                    }
+                }
+            }
+
+            best.filter(|(_, count)| *count >= 4).map(|(matrix, _)| matrix)
         }
     }
-}
 
-#[cfg(feature = "opencv")]
-pub use opencv_impl::HomographyComputer;
+    /// Small deterministic PRNG for sampling.
+    struct XorShift64(u64);
 
-#[cfg(not(feature = "opencv"))]
-pub struct HomographyComputer;
+    impl XorShift64 {
+        fn new(seed: u64) -> Self {
+            Self(seed.max(1))
+        }
 
-#[cfg(not(feature = "opencv"))]
-impl HomographyComputer {
-    pub fn new() -> Self {
-        Self
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0
+        }
+
+        /// Four distinct indices below `len` (len >= 4).
+        fn sample4(&mut self, len: usize) -> [usize; 4] {
+            let mut picked = [usize::MAX; 4];
+            let mut n = 0;
+            while n < 4 {
+                let i = (self.next() % len as u64) as usize;
+                if !picked[..n].contains(&i) {
+                    picked[n] = i;
+                    n += 1;
+                }
+            }
+            picked
+        }
+    }
+
+    /// Forward reprojection error (projector pixels).
+    pub(super) fn reprojection_error(matrix: &[[f64; 3]; 3], (src, dst): &PointPair) -> f64 {
+        let h = matrix;
+        let w = h[2][0] * src[0] + h[2][1] * src[1] + h[2][2];
+        if w.abs() < 1e-10 {
+            return f64::INFINITY;
+        }
+        let tx = (h[0][0] * src[0] + h[0][1] * src[1] + h[0][2]) / w;
+        let ty = (h[1][0] * src[0] + h[1][1] * src[1] + h[1][2]) / w;
+        ((tx - dst[0]).powi(2) + (ty - dst[1]).powi(2)).sqrt()
+    }
+
+    /// True if three of the four points are (nearly) collinear on either side.
+    fn is_degenerate(points: &[PointPair; 4]) -> bool {
+        let collinear = |a: [f64; 2], b: [f64; 2], c: [f64; 2]| {
+            let area = (b[0] - a[0]) * (c[1] - a[1]) - (b[1] - a[1]) * (c[0] - a[0]);
+            let scale = (b[0] - a[0]).hypot(b[1] - a[1]) * (c[0] - a[0]).hypot(c[1] - a[1]);
+            area.abs() <= 1e-6 * scale.max(f64::MIN_POSITIVE)
+        };
+        [(0, 1, 2), (0, 1, 3), (0, 2, 3), (1, 2, 3)].iter().any(|&(i, j, k)| {
+            collinear(points[i].0, points[j].0, points[k].0)
+                || collinear(points[i].1, points[j].1, points[k].1)
+        })
     }
 
-    pub fn compute(&self, _correspondences: &DecodedCorrespondences) -> Result<HomographyResult, String> {
-        Err("OpenCV feature not enabled. Build with --features opencv".to_string())
+    /// Similarity that moves the centroid to the origin with mean distance sqrt(2).
+    fn normalization(points: impl Iterator<Item = [f64; 2]> + Clone) -> [[f64; 3]; 3] {
+        let count = points.clone().count().max(1) as f64;
+        let (sx, sy) = points.clone().fold((0.0, 0.0), |(sx, sy), p| (sx + p[0], sy + p[1]));
+        let (cx, cy) = (sx / count, sy / count);
+        let mean_dist = points.map(|p| (p[0] - cx).hypot(p[1] - cy)).sum::<f64>() / count;
+        let s = if mean_dist > 1e-12 { std::f64::consts::SQRT_2 / mean_dist } else { 1.0 };
+        [[s, 0.0, -s * cx], [0.0, s, -s * cy], [0.0, 0.0, 1.0]]
+    }
+
+    /// Normalized direct linear transform; needs at least four pairs.
+    pub(super) fn fit_dlt(pairs: &[PointPair]) -> Option<[[f64; 3]; 3]> {
+        if pairs.len() < 4 {
+            return None;
+        }
+        let t_src = normalization(pairs.iter().map(|p| p.0));
+        let t_dst = normalization(pairs.iter().map(|p| p.1));
+        let apply = |t: &[[f64; 3]; 3], p: [f64; 2]| [t[0][0] * p[0] + t[0][2], t[1][1] * p[1] + t[1][2]];
+
+        // Accumulate A^T A for the 2n x 9 DLT system
+        let mut ata = [[0.0f64; 9]; 9];
+        for (src, dst) in pairs {
+            let [x, y] = apply(&t_src, *src);
+            let [u, v] = apply(&t_dst, *dst);
+            let rows = [
+                [-x, -y, -1.0, 0.0, 0.0, 0.0, u * x, u * y, u],
+                [0.0, 0.0, 0.0, -x, -y, -1.0, v * x, v * y, v],
+            ];
+            for row in &rows {
+                for (i, ata_row) in ata.iter_mut().enumerate() {
+                    for (j, value) in ata_row.iter_mut().enumerate() {
+                        *value += row[i] * row[j];
+                    }
+                }
+            }
+        }
+
+        let h = smallest_eigenvector(ata);
+        let normalized = [[h[0], h[1], h[2]], [h[3], h[4], h[5]], [h[6], h[7], h[8]]];
+
+        // Denormalize: H = T_dst^-1 * Hn * T_src
+        let t_dst_inv = super::HomographyComputer::invert(&t_dst).ok()?;
+        let matrix = mat_mul(&mat_mul(&t_dst_inv, &normalized), &t_src);
+        normalize_scale(matrix)
+    }
+
+    /// Scale so that h22 = 1 (the parameterization used for refinement).
+    fn normalize_scale(matrix: [[f64; 3]; 3]) -> Option<[[f64; 3]; 3]> {
+        let scale = matrix[2][2];
+        if scale.abs() < 1e-12 || !scale.is_finite() {
+            return None;
+        }
+        Some(matrix.map(|row| row.map(|v| v / scale)))
+    }
+
+    fn mat_mul(a: &[[f64; 3]; 3], b: &[[f64; 3]; 3]) -> [[f64; 3]; 3] {
+        let mut out = [[0.0; 3]; 3];
+        for (i, row) in out.iter_mut().enumerate() {
+            for (j, value) in row.iter_mut().enumerate() {
+                *value = (0..3).map(|k| a[i][k] * b[k][j]).sum();
+            }
+        }
+        out
+    }
+
+    /// Eigenvector of the smallest eigenvalue of a symmetric matrix (Jacobi rotations).
+    fn smallest_eigenvector<const N: usize>(mut a: [[f64; N]; N]) -> [f64; N] {
+        let mut v = [[0.0f64; N]; N];
+        for (i, row) in v.iter_mut().enumerate() {
+            row[i] = 1.0;
+        }
+
+        for _sweep in 0..100 {
+            let off: f64 = (0..N)
+                .flat_map(|i| (0..N).filter(move |&j| j != i).map(move |j| (i, j)))
+                .map(|(i, j)| a[i][j] * a[i][j])
+                .sum();
+            if off < 1e-30 {
+                break;
+            }
+            for p in 0..N {
+                for q in p + 1..N {
+                    if a[p][q].abs() < 1e-300 {
+                        continue;
+                    }
+                    let theta = (a[q][q] - a[p][p]) / (2.0 * a[p][q]);
+                    let t = theta.signum() / (theta.abs() + (theta * theta + 1.0).sqrt());
+                    let t = if theta == 0.0 { 1.0 } else { t };
+                    let c = 1.0 / (t * t + 1.0).sqrt();
+                    let s = t * c;
+                    for row in a.iter_mut() {
+                        let (akp, akq) = (row[p], row[q]);
+                        row[p] = c * akp - s * akq;
+                        row[q] = s * akp + c * akq;
+                    }
+                    let (row_p, row_q) = (a[p], a[q]);
+                    a[p] = std::array::from_fn(|k| c * row_p[k] - s * row_q[k]);
+                    a[q] = std::array::from_fn(|k| s * row_p[k] + c * row_q[k]);
+                    for row in v.iter_mut() {
+                        let (vkp, vkq) = (row[p], row[q]);
+                        row[p] = c * vkp - s * vkq;
+                        row[q] = s * vkp + c * vkq;
+                    }
+                }
+            }
+        }
+
+        let smallest = (0..N)
+            .min_by(|&i, &j| a[i][i].total_cmp(&a[j][j]))
+            .unwrap_or(0);
+        std::array::from_fn(|k| v[k][smallest])
+    }
+
+    /// Levenberg-Marquardt refinement of the forward reprojection error (h22 = 1).
+    pub(super) fn refine_lm(matrix: &[[f64; 3]; 3], pairs: &[PointPair]) -> [[f64; 3]; 3] {
+        let to_params = |m: &[[f64; 3]; 3]| [m[0][0], m[0][1], m[0][2], m[1][0], m[1][1], m[1][2], m[2][0], m[2][1]];
+        let to_matrix = |h: &[f64; 8]| [[h[0], h[1], h[2]], [h[3], h[4], h[5]], [h[6], h[7], 1.0]];
+        let cost = |h: &[f64; 8]| -> f64 {
+            let m = to_matrix(h);
+            pairs.iter().map(|p| reprojection_error(&m, p).powi(2)).sum()
+        };
+
+        let Some(start) = normalize_scale(*matrix) else {
+            return *matrix;
+        };
+        let mut h = to_params(&start);
+        let mut current = cost(&h);
+        let mut lambda = 1e-3;
+
+        for _ in 0..LM_MAX_ITERS {
+            // Normal equations J^T J and J^T r
+            let mut jtj = [[0.0f64; 8]; 8];
+            let mut jtr = [0.0f64; 8];
+            for (src, dst) in pairs {
+                let [x, y] = *src;
+                let w = h[6] * x + h[7] * y + 1.0;
+                if w.abs() < 1e-10 {
+                    continue;
+                }
+                let u = (h[0] * x + h[1] * y + h[2]) / w;
+                let v = (h[3] * x + h[4] * y + h[5]) / w;
+                let ju = [x / w, y / w, 1.0 / w, 0.0, 0.0, 0.0, -u * x / w, -u * y / w];
+                let jv = [0.0, 0.0, 0.0, x / w, y / w, 1.0 / w, -v * x / w, -v * y / w];
+                for (jac, residual) in [(ju, u - dst[0]), (jv, v - dst[1])] {
+                    for i in 0..8 {
+                        jtr[i] += jac[i] * residual;
+                        for j in 0..8 {
+                            jtj[i][j] += jac[i] * jac[j];
+                        }
+                    }
+                }
+            }
+
+            let mut improved = false;
+            while lambda < 1e12 {
+                let mut damped = jtj;
+                for (i, row) in damped.iter_mut().enumerate() {
+                    row[i] += lambda * jtj[i][i].max(1e-12);
+                }
+                let Some(step) = solve_linear(damped, jtr.map(|v| -v)) else {
+                    lambda *= 10.0;
+                    continue;
+                };
+                let candidate: [f64; 8] = std::array::from_fn(|i| h[i] + step[i]);
+                let candidate_cost = cost(&candidate);
+                if candidate_cost < current {
+                    let converged = current - candidate_cost < 1e-12 * current.max(1e-12);
+                    h = candidate;
+                    current = candidate_cost;
+                    lambda = (lambda * 0.1).max(1e-12);
+                    improved = !converged;
+                    break;
+                }
+                lambda *= 10.0;
+            }
+            if !improved {
+                break;
+            }
+        }
+
+        to_matrix(&h)
+    }
+
+    /// Solve `a x = b` by Gaussian elimination with partial pivoting.
+    fn solve_linear<const N: usize>(mut a: [[f64; N]; N], mut b: [f64; N]) -> Option<[f64; N]> {
+        for col in 0..N {
+            let pivot = (col..N).max_by(|&i, &j| a[i][col].abs().total_cmp(&a[j][col].abs()))?;
+            if a[pivot][col].abs() < 1e-300 {
+                return None;
+            }
+            a.swap(col, pivot);
+            b.swap(col, pivot);
+            for row in col + 1..N {
+                let factor = a[row][col] / a[col][col];
+                let pivot_row = a[col];
+                for (value, pivot_value) in a[row][col..].iter_mut().zip(&pivot_row[col..]) {
+                    *value -= factor * pivot_value;
+                }
+                b[row] -= factor * b[col];
+            }
+        }
+        let mut x = [0.0; N];
+        for row in (0..N).rev() {
+            let sum: f64 = (row + 1..N).map(|k| a[row][k] * x[k]).sum();
+            x[row] = (b[row] - sum) / a[row][row];
+        }
+        Some(x)
     }
 }
 
@@ -309,28 +741,90 @@ impl HomographyComputer {
 mod tests {
     use super::*;
 
+    /// Camera -> projector mapping with a perspective component
+    const TRUTH: [[f64; 3]; 3] = [
+        [1.6, 0.12, 30.0],
+        [-0.05, 1.5, 12.0],
+        [0.0002, 0.0001, 1.0],
+    ];
+
+    fn synthetic_correspondences(outlier_every: usize) -> DecodedCorrespondences {
+        let (width, height) = (320, 240);
+        let mut corr = DecodedCorrespondences::new(width, height, 800, 600);
+        for y in 0..height {
+            for x in 0..width {
+                let idx = (y * width + x) as usize;
+                let (px, py) = HomographyComputer::transform_point(&TRUTH, x as f64, y as f64);
+                let (px, py) = if outlier_every > 0 && idx.is_multiple_of(outlier_every) {
+                    // Wildly wrong decode, as from a reflection or shadow edge
+                    ((idx * 37 % 800) as f64, (idx * 91 % 600) as f64)
+                } else {
+                    (px, py)
+                };
+                corr.projector_x[idx] = px.round() as i32;
+                corr.projector_y[idx] = py.round() as i32;
+                corr.confidence[idx] = 1.0;
+                corr.valid_mask[idx] = true;
+            }
+        }
+        corr
+    }
+
     #[test]
     fn test_identity_transform() {
         let identity = [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]];
-
-        #[cfg(feature = "opencv")]
-        {
-            let (tx, ty) = HomographyComputer::transform_point(&identity, 100.0, 200.0);
-            assert!((tx - 100.0).abs() < 1e-6);
-            assert!((ty - 200.0).abs() < 1e-6);
-        }
+        let (tx, ty) = HomographyComputer::transform_point(&identity, 100.0, 200.0);
+        assert!((tx - 100.0).abs() < 1e-6);
+        assert!((ty - 200.0).abs() < 1e-6);
     }
 
     #[test]
     fn test_matrix_invert() {
-        #[cfg(feature = "opencv")]
-        {
-            let matrix = [[2.0, 0.0, 0.0], [0.0, 3.0, 0.0], [0.0, 0.0, 1.0]];
-            let inv = HomographyComputer::invert(&matrix).unwrap();
+        let matrix = [[2.0, 0.0, 0.0], [0.0, 3.0, 0.0], [0.0, 0.0, 1.0]];
+        let inv = HomographyComputer::invert(&matrix).unwrap();
 
-            // Inverse should be [0.5, 0, 0], [0, 0.333, 0], [0, 0, 1]
-            assert!((inv[0][0] - 0.5).abs() < 1e-6);
-            assert!((inv[1][1] - 1.0/3.0).abs() < 1e-6);
+        // Inverse should be [0.5, 0, 0], [0, 0.333, 0], [0, 0, 1]
+        assert!((inv[0][0] - 0.5).abs() < 1e-6);
+        assert!((inv[1][1] - 1.0/3.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_dlt_exact_points() {
+        let pairs: Vec<_> = [(0.0, 0.0), (300.0, 10.0), (280.0, 230.0), (15.0, 220.0), (150.0, 120.0)]
+            .iter()
+            .map(|&(x, y)| {
+                let (u, v) = HomographyComputer::transform_point(&TRUTH, x, y);
+                ([x, y], [u, v])
+            })
+            .collect();
+        let matrix = software_impl::fit_dlt(&pairs).unwrap();
+        for (i, row) in matrix.iter().enumerate() {
+            for (j, value) in row.iter().enumerate() {
+                assert!((value - TRUTH[i][j]).abs() < 1e-6 * TRUTH[i][j].abs().max(1.0));
+            }
         }
     }
+
+    #[test]
+    fn test_software_ransac_rejects_outliers() {
+        // Every 5th correspondence is garbage
+        let corr = synthetic_correspondences(5);
+        let computer = software_impl::HomographyComputer::new();
+        let result = computer.compute(&corr).unwrap();
+
+        assert!(result.inlier_ratio > 0.75 && result.inlier_ratio < 0.85, "{}", result.inlier_ratio);
+        // Decoded coordinates are rounded to whole projector pixels
+        assert!(result.reprojection_error < 0.5, "{}", result.reprojection_error);
+        for (x, y) in [(0.0, 0.0), (319.0, 0.0), (160.0, 120.0), (0.0, 239.0)] {
+            let expected = HomographyComputer::transform_point(&TRUTH, x, y);
+            let actual = HomographyComputer::transform_point(&result.matrix, x, y);
+            assert!((expected.0 - actual.0).abs() < 0.5 && (expected.1 - actual.1).abs() < 0.5);
+        }
+    }
+
+    #[test]
+    fn test_software_needs_enough_points() {
+        let corr = DecodedCorrespondences::new(64, 48, 800, 600);
+        assert!(software_impl::HomographyComputer::new().compute(&corr).is_err());
+    }
 }
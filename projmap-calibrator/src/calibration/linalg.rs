@@ -0,0 +1,381 @@
+//! Small dense linear algebra helpers for 3D calibration.
+//!
+//! Fixed-size 3-vectors and 3x3 matrices for geometry, plus dynamically
+//! sized routines (symmetric eigen decomposition, linear solves and a
+//! Levenberg-Marquardt driver) for the least-squares problems.
+
+pub type Vec3 = [f64; 3];
+pub type Mat3 = [[f64; 3]; 3];
+
+pub const IDENTITY: Mat3 = [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]];
+
+pub fn add(a: Vec3, b: Vec3) -> Vec3 {
+    [a[0] + b[0], a[1] + b[1], a[2] + b[2]]
+}
+
+pub fn sub(a: Vec3, b: Vec3) -> Vec3 {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+pub fn scale(a: Vec3, s: f64) -> Vec3 {
+    [a[0] * s, a[1] * s, a[2] * s]
+}
+
+pub fn dot(a: Vec3, b: Vec3) -> f64 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+pub fn cross(a: Vec3, b: Vec3) -> Vec3 {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+pub fn norm(a: Vec3) -> f64 {
+    dot(a, a).sqrt()
+}
+
+/// Unit vector in the direction of `a` (zero stays zero).
+pub fn normalize(a: Vec3) -> Vec3 {
+    let n = norm(a);
+    if n > 1e-300 {
+        scale(a, 1.0 / n)
+    } else {
+        a
+    }
+}
+
+pub fn mat_mul(a: &Mat3, b: &Mat3) -> Mat3 {
+    std::array::from_fn(|i| std::array::from_fn(|j| (0..3).map(|k| a[i][k] * b[k][j]).sum()))
+}
+
+pub fn mat_vec(m: &Mat3, v: Vec3) -> Vec3 {
+    [dot(m[0], v), dot(m[1], v), dot(m[2], v)]
+}
+
+pub fn transpose(m: &Mat3) -> Mat3 {
+    std::array::from_fn(|i| std::array::from_fn(|j| m[j][i]))
+}
+
+pub fn det(m: &Mat3) -> f64 {
+    dot(m[0], cross(m[1], m[2]))
+}
+
+/// Skew-symmetric cross product matrix: `skew(a) * b == cross(a, b)`.
+pub fn skew(a: Vec3) -> Mat3 {
+    [[0.0, -a[2], a[1]], [a[2], 0.0, -a[0]], [-a[1], a[0], 0.0]]
+}
+
+/// Rotation matrix from an axis-angle (Rodrigues) vector.
+pub fn rodrigues(r: Vec3) -> Mat3 {
+    let theta = norm(r);
+    if theta < 1e-12 {
+        // First order: I + [r]x
+        let k = skew(r);
+        return std::array::from_fn(|i| std::array::from_fn(|j| IDENTITY[i][j] + k[i][j]));
+    }
+    let k = skew(scale(r, 1.0 / theta));
+    let k2 = mat_mul(&k, &k);
+    let (s, c) = theta.sin_cos();
+    std::array::from_fn(|i| std::array::from_fn(|j| IDENTITY[i][j] + s * k[i][j] + (1.0 - c) * k2[i][j]))
+}
+
+/// Axis-angle (Rodrigues) vector of a rotation matrix.
+pub fn rotation_vector(m: &Mat3) -> Vec3 {
+    let cos = ((m[0][0] + m[1][1] + m[2][2] - 1.0) / 2.0).clamp(-1.0, 1.0);
+    let theta = cos.acos();
+    let axis = [m[2][1] - m[1][2], m[0][2] - m[2][0], m[1][0] - m[0][1]];
+    if theta < 1e-6 {
+        return scale(axis, 0.5);
+    }
+    if std::f64::consts::PI - theta < 1e-6 {
+        // Near 180 degrees: axis from the largest diagonal of (R + I) / 2
+        let i = (0..3).max_by(|&a, &b| m[a][a].total_cmp(&m[b][b])).unwrap_or(0);
+        let column: Vec3 = std::array::from_fn(|k| (m[k][i] + IDENTITY[k][i]) / 2.0);
+        return scale(normalize(column), theta);
+    }
+    scale(axis, theta / (2.0 * theta.sin()))
+}
+
+/// Closest rotation to a 3x3 matrix (in the Frobenius sense).
+pub fn orthonormalize(m: &Mat3) -> Mat3 {
+    let (u, _, v) = svd3(m);
+    let mut r = mat_mul(&u, &transpose(&v));
+    if det(&r) < 0.0 {
+        let mut u = u;
+        for row in u.iter_mut() {
+            row[2] = -row[2];
+        }
+        r = mat_mul(&u, &transpose(&v));
+    }
+    r
+}
+
+/// Singular value decomposition `m = u * diag(s) * v^T`, singular values descending.
+///
+/// Computed from the eigen decomposition of `m^T m`; the third left singular
+/// vector is completed with a cross product.
+pub fn svd3(m: &Mat3) -> (Mat3, Vec3, Mat3) {
+    let mtm = mat_mul(&transpose(m), m);
+    let (values, vectors) = symmetric_eigen(mtm.iter().map(|row| row.to_vec()).collect());
+
+    // Eigenvalues come back ascending
+    let order = [2, 1, 0];
+    let mut s: Vec3 = order.map(|i| values[i].max(0.0).sqrt());
+    let v_cols: [Vec3; 3] = order.map(|i| [vectors[i][0], vectors[i][1], vectors[i][2]]);
+
+    let mut u_cols = [[0.0; 3]; 3];
+    let largest = s[0].max(1e-300);
+    for k in 0..2 {
+        u_cols[k] = if s[k] > 1e-12 * largest {
+            scale(mat_vec(m, v_cols[k]), 1.0 / s[k])
+        } else {
+            orthogonal_to(if k == 0 { [1.0, 0.0, 0.0] } else { u_cols[0] })
+        };
+    }
+    // The third vector is fixed up to sign by the first two; deriving it from
+    // m * v3 loses orthogonality when s[2] is at rounding level
+    u_cols[2] = normalize(cross(u_cols[0], u_cols[1]));
+    let projected = dot(u_cols[2], mat_vec(m, v_cols[2]));
+    if projected < 0.0 {
+        u_cols[2] = scale(u_cols[2], -1.0);
+    }
+    // More accurate than the square root of a rounding-level eigenvalue
+    s[2] = projected.abs();
+
+    let u = std::array::from_fn(|i| std::array::from_fn(|j| u_cols[j][i]));
+    let v = std::array::from_fn(|i| std::array::from_fn(|j| v_cols[j][i]));
+    (u, s, v)
+}
+
+/// Some unit vector perpendicular to `a`.
+fn orthogonal_to(a: Vec3) -> Vec3 {
+    let helper = if a[0].abs() < 0.9 { [1.0, 0.0, 0.0] } else { [0.0, 1.0, 0.0] };
+    normalize(cross(a, helper))
+}
+
+/// Eigen decomposition of a symmetric matrix by cyclic Jacobi rotations.
+///
+/// Returns the eigenvalues in ascending order and the matching unit
+/// eigenvectors.
+pub fn symmetric_eigen(mut a: Vec<Vec<f64>>) -> (Vec<f64>, Vec<Vec<f64>>) {
+    let n = a.len();
+    let mut v: Vec<Vec<f64>> = (0..n).map(|i| (0..n).map(|j| if i == j { 1.0 } else { 0.0 }).collect()).collect();
+
+    for _sweep in 0..100 {
+        let off: f64 = (0..n)
+            .flat_map(|i| (0..n).filter(move |&j| j != i).map(move |j| (i, j)))
+            .map(|(i, j)| a[i][j] * a[i][j])
+            .sum();
+        let diagonal: f64 = (0..n).map(|i| a[i][i] * a[i][i]).sum();
+        if off <= 1e-30 * diagonal.max(1e-300) {
+            break;
+        }
+        for p in 0..n {
+            for q in p + 1..n {
+                if a[p][q].abs() < 1e-300 {
+                    continue;
+                }
+                let theta = (a[q][q] - a[p][p]) / (2.0 * a[p][q]);
+                let t = if theta == 0.0 {
+                    1.0
+                } else {
+                    theta.signum() / (theta.abs() + (theta * theta + 1.0).sqrt())
+                };
+                let c = 1.0 / (t * t + 1.0).sqrt();
+                let s = t * c;
+                for row in a.iter_mut() {
+                    let (akp, akq) = (row[p], row[q]);
+                    row[p] = c * akp - s * akq;
+                    row[q] = s * akp + c * akq;
+                }
+                let (upper, lower) = a.split_at_mut(q);
+                for (apk, aqk) in upper[p].iter_mut().zip(lower[0].iter_mut()) {
+                    (*apk, *aqk) = (c * *apk - s * *aqk, s * *apk + c * *aqk);
+                }
+                for row in v.iter_mut() {
+                    let (vkp, vkq) = (row[p], row[q]);
+                    row[p] = c * vkp - s * vkq;
+                    row[q] = s * vkp + c * vkq;
+                }
+            }
+        }
+    }
+
+    let mut order: Vec<usize> = (0..n).collect();
+    order.sort_by(|&i, &j| a[i][i].total_cmp(&a[j][j]));
+    let values = order.iter().map(|&i| a[i][i]).collect();
+    let vectors = order.iter().map(|&i| (0..n).map(|k| v[k][i]).collect()).collect();
+    (values, vectors)
+}
+
+/// Eigenvector of the smallest eigenvalue of `a^T a` for the rows of `a`.
+pub fn null_vector(rows: &[Vec<f64>]) -> Vec<f64> {
+    let n = rows.first().map_or(0, |r| r.len());
+    let mut ata = vec![vec![0.0; n]; n];
+    for row in rows {
+        for i in 0..n {
+            for j in 0..n {
+                ata[i][j] += row[i] * row[j];
+            }
+        }
+    }
+    let (_, vectors) = symmetric_eigen(ata);
+    vectors.into_iter().next().unwrap_or_default()
+}
+
+/// Solve `a x = b` by Gaussian elimination with partial pivoting.
+pub fn solve(mut a: Vec<Vec<f64>>, mut b: Vec<f64>) -> Option<Vec<f64>> {
+    let n = b.len();
+    for col in 0..n {
+        let pivot = (col..n).max_by(|&i, &j| a[i][col].abs().total_cmp(&a[j][col].abs()))?;
+        if a[pivot][col].abs() < 1e-300 {
+            return None;
+        }
+        a.swap(col, pivot);
+        b.swap(col, pivot);
+        for row in col + 1..n {
+            let factor = a[row][col] / a[col][col];
+            let (upper, lower) = a.split_at_mut(row);
+            for (x, pivot_x) in lower[0][col..].iter_mut().zip(&upper[col][col..]) {
+                *x -= factor * pivot_x;
+            }
+            b[row] -= factor * b[col];
+        }
+    }
+    let mut x = vec![0.0; n];
+    for row in (0..n).rev() {
+        let sum: f64 = (row + 1..n).map(|k| a[row][k] * x[k]).sum();
+        x[row] = (b[row] - sum) / a[row][row];
+    }
+    x.iter().all(|v| v.is_finite()).then_some(x)
+}
+
+/// Minimize the sum of squared residuals with Levenberg-Marquardt.
+///
+/// The Jacobian is estimated with forward differences, so `residuals` must
+/// always return the same number of values. Returns the refined parameters
+/// and the final sum of squares.
+pub fn levenberg_marquardt(
+    initial: Vec<f64>,
+    max_iters: usize,
+    residuals: impl Fn(&[f64]) -> Vec<f64>,
+) -> (Vec<f64>, f64) {
+    let cost = |r: &[f64]| r.iter().map(|v| v * v).sum::<f64>();
+    let n = initial.len();
+    let mut params = initial;
+    let mut r = residuals(&params);
+    let mut current = cost(&r);
+    let mut lambda = 1e-3;
+
+    for _ in 0..max_iters {
+        // Forward difference Jacobian, one column per parameter
+        let columns: Vec<Vec<f64>> = (0..n)
+            .map(|j| {
+                let h = 1e-6 * params[j].abs().max(1.0);
+                let mut shifted = params.clone();
+                shifted[j] += h;
+                residuals(&shifted)
+                    .iter()
+                    .zip(&r)
+                    .map(|(a, b)| (a - b) / h)
+                    .collect()
+            })
+            .collect();
+
+        let mut jtj = vec![vec![0.0; n]; n];
+        let mut jtr = vec![0.0; n];
+        for i in 0..n {
+            jtr[i] = columns[i].iter().zip(&r).map(|(j, r)| j * r).sum();
+            for k in i..n {
+                let value: f64 = columns[i].iter().zip(&columns[k]).map(|(a, b)| a * b).sum();
+                jtj[i][k] = value;
+                jtj[k][i] = value;
+            }
+        }
+
+        let mut improved = false;
+        while lambda < 1e12 {
+            let mut damped = jtj.clone();
+            for (i, row) in damped.iter_mut().enumerate() {
+                row[i] += lambda * jtj[i][i].max(1e-12);
+            }
+            let Some(step) = solve(damped, jtr.iter().map(|v| -v).collect()) else {
+                lambda *= 10.0;
+                continue;
+            };
+            let candidate: Vec<f64> = params.iter().zip(&step).map(|(p, s)| p + s).collect();
+            let candidate_r = residuals(&candidate);
+            let candidate_cost = cost(&candidate_r);
+            if candidate_cost < current {
+                let converged = current - candidate_cost < 1e-12 * current.max(1e-12);
+                params = candidate;
+                r = candidate_r;
+                current = candidate_cost;
+                lambda = (lambda * 0.1).max(1e-12);
+                improved = !converged;
+                break;
+            }
+            lambda *= 10.0;
+        }
+        if !improved {
+            break;
+        }
+    }
+
+    (params, current)
+}
+
+/// Value at a fraction (0-1) of the sorted data, by nearest rank.
+pub fn percentile(values: &mut [f64], fraction: f64) -> f64 {
+    if values.is_empty() {
+        return 0.0;
+    }
+    values.sort_by(f64::total_cmp);
+    let index = ((values.len() - 1) as f64 * fraction.clamp(0.0, 1.0)).round() as usize;
+    values[index]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rotation_vector_round_trip() {
+        for r in [[0.1, -0.2, 0.3], [0.0, 0.0, 0.0], [0.0, 3.1, 0.0], [1e-8, 0.0, 2e-8]] {
+            let back = rotation_vector(&rodrigues(r));
+            assert!(norm(sub(back, r)) < 1e-6, "{:?} -> {:?}", r, back);
+        }
+    }
+
+    #[test]
+    fn test_svd3_reconstructs_rank_deficient_matrix() {
+        // Essential-like matrix: rank two
+        let m = mat_mul(&skew([0.3, -0.1, 0.9]), &rodrigues([0.2, 0.1, -0.3]));
+        let (u, s, v) = svd3(&m);
+        assert!(s[0] >= s[1] && s[1] >= s[2] && s[2] < 1e-6);
+        let diag = [[s[0], 0.0, 0.0], [0.0, s[1], 0.0], [0.0, 0.0, s[2]]];
+        let back = mat_mul(&mat_mul(&u, &diag), &transpose(&v));
+        for i in 0..3 {
+            for j in 0..3 {
+                assert!((back[i][j] - m[i][j]).abs() < 1e-9);
+            }
+        }
+        assert!((det(&u).abs() - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_levenberg_marquardt_fits_exponential() {
+        let samples: Vec<(f64, f64)> = (0..20).map(|i| {
+            let x = i as f64 * 0.1;
+            (x, 2.5 * (-1.3 * x).exp())
+        }).collect();
+        let (params, cost) = levenberg_marquardt(vec![1.0, -0.5], 100, |p| {
+            samples.iter().map(|(x, y)| p[0] * (p[1] * x).exp() - y).collect()
+        });
+        assert!(cost < 1e-12);
+        assert!((params[0] - 2.5).abs() < 1e-5 && (params[1] + 1.3).abs() < 1e-5);
+    }
+}
@@ -2,13 +2,48 @@
 
 mod gray_code;
 mod session;
+mod session_file;
 mod decoder;
 mod homography;
+mod phase_shift;
+mod recording;
+mod photometric;
+mod linalg;
+mod camera_model;
+mod reconstruction;
+mod content_space;
+mod multi_camera;
+mod verification;
+mod touch_up;
 
 pub use gray_code::{GrayCodeGenerator, PatternConfig, PatternDirection, PatternSpec};
 pub use session::{
     CalibrationConfig, CalibrationSession, CalibrationState, CapturePhase,
-    CapturedPair, CurrentPattern, ProjectorCalibration,
+    CameraCaptures, CapturedPair, CurrentPattern, ProjectorCalibration,
 };
+pub use session_file::{load_session, save_session, SessionFile, SESSION_VERSION};
 pub use decoder::DecodedCorrespondences;
 pub use homography::{HomographyResult, HomographyComputer};
+pub use phase_shift::{PhaseShiftConfig, PhaseStep};
+pub use recording::{
+    record_session, write_pattern_set, CaptureManifest, CaptureRun, RecordedCapture, RecordedPattern,
+    RecordedProjector, MANIFEST_FILE,
+};
+pub use photometric::{
+    match_projectors, ColorMatch, PhotometricConfig, PhotometricSession, PhotometricState,
+    ProjectorResponse, ResponseCurve,
+};
+pub use camera_model::{
+    calibrate_camera, detect_checkerboard, CameraCalibration, CameraIntrinsics, CheckerboardSet,
+    CheckerboardSpec, Pose, MIN_CHECKERBOARD_VIEWS,
+};
+pub use reconstruction::{
+    reconstruct_surface, ProjectorGeometry, ReconstructionConfig, SurfaceReconstruction,
+};
+pub use content_space::{ContentMapping, ContentSpace, MeshSurface, Similarity};
+pub use multi_camera::{merge_cameras, reconstruct_rig, CameraMerge, RigReconstruction};
+pub use touch_up::{TouchUp, TOUCH_UP_COLUMNS, TOUCH_UP_ROWS};
+pub use verification::{
+    OverlapVerification, ProjectorVerification, TargetResidual, VerificationConfig,
+    VerificationReport,
+};
@@ -0,0 +1,578 @@
+//! Merging the views of several cameras into one content mapping.
+//!
+//! Each camera decodes the projectors it can see. Cameras are tied together
+//! through projector pixels that two of them observe: in 2D by a homography
+//! from each camera image into the primary camera's image plane (extended to
+//! cover every camera), in 3D by a similarity between the surfaces
+//! reconstructed from each calibrated camera. For every projector pixel, the
+//! camera with the most confident decode supplies the observation.
+//!
+//! The 2D merge suits cameras side by side on a roughly planar screen. Rooms,
+//! domes and other wrap-around surfaces need the 3D merge.
+
+use super::camera_model::CameraIntrinsics;
+use super::content_space::{ContentMapping, ContentSpace, Similarity};
+use super::decoder::DecodedCorrespondences;
+use super::homography::{fit_point_homography, HomographyComputer};
+use super::linalg::{self, Mat3, Vec3};
+use super::reconstruction::{
+    reconstruct_projectors, upsample_uv_grid, ProjectorGeometry, ReconstructionConfig, SurfaceReconstruction,
+};
+use super::session::{CalibrationSession, ProjectorCalibration};
+use crate::config::{CorrespondenceMap, UvMap};
+use std::collections::HashMap;
+
+/// Projector grid used to find pixels shared between cameras.
+const LINK_GRID_COLUMNS: u32 = 65;
+const LINK_GRID_ROWS: u32 = 37;
+
+/// Minimum shared grid nodes to link two cameras.
+const MIN_SHARED_NODES: usize = 12;
+
+/// Minimum decoded camera pixels for a camera to count as seeing a projector.
+const MIN_VISIBLE_PIXELS: usize = 500;
+
+/// Cameras tied into the primary camera's image plane.
+#[derive(Debug, Clone)]
+pub struct CameraMerge {
+    /// Per camera: camera pixel -> content pixel (`None` where not linked).
+    pub alignments: Vec<Option<Mat3>>,
+    /// Content width in pixels (covers every linked camera's decode).
+    pub content_width: u32,
+    /// Content height in pixels.
+    pub content_height: u32,
+}
+
+/// Surfaces reconstructed from several calibrated cameras, in one frame.
+#[derive(Debug, Clone)]
+pub struct RigReconstruction {
+    /// Per camera: its reconstruction (`None` where uncalibrated or blind).
+    pub surfaces: Vec<Option<SurfaceReconstruction>>,
+    /// Per camera: its frame -> the reference camera's frame.
+    pub alignments: Vec<Option<Similarity>>,
+    /// Surface samples of every linked camera in the reference frame.
+    pub points: Vec<Vec3>,
+}
+
+/// Camera pixels of a projector grid node decoded by two cameras.
+#[derive(Debug, Clone, Copy)]
+struct SharedNode {
+    projector_id: u32,
+    projector: [f64; 2],
+    a: [f64; 2],
+    b: [f64; 2],
+}
+
+/// Camera with the most confident decode of a projector grid node.
+#[derive(Debug, Clone, Copy)]
+struct Observation {
+    camera: usize,
+    pixel: [f64; 2],
+    confidence: f32,
+}
+
+/// Projector data per camera, primary camera first.
+fn camera_views(session: &CalibrationSession) -> Vec<&[ProjectorCalibration]> {
+    (0..session.camera_count())
+        .filter_map(|camera| session.camera_projectors(camera))
+        .collect()
+}
+
+/// Correspondences of a projector, if the camera sees enough of it.
+fn visible(projectors: &[ProjectorCalibration], projector_id: u32) -> Option<&DecodedCorrespondences> {
+    projectors
+        .iter()
+        .find(|p| p.projector_id == projector_id)?
+        .correspondences
+        .as_ref()
+        .filter(|c| c.valid_count() >= MIN_VISIBLE_PIXELS)
+}
+
+/// Link grids of every visible projector, per camera.
+fn link_grids(views: &[&[ProjectorCalibration]]) -> Vec<HashMap<u32, CorrespondenceMap>> {
+    views
+        .iter()
+        .map(|projectors| {
+            projectors
+                .iter()
+                .filter_map(|p| {
+                    let corr = visible(projectors, p.projector_id)?;
+                    Some((
+                        p.projector_id,
+                        corr.to_correspondence_map(p.projector_id, LINK_GRID_COLUMNS, LINK_GRID_ROWS),
+                    ))
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Grid nodes decoded by both cameras, over every projector they share.
+fn shared_nodes(a: &HashMap<u32, CorrespondenceMap>, b: &HashMap<u32, CorrespondenceMap>) -> Vec<SharedNode> {
+    let mut nodes = Vec::new();
+    for (projector_id, grid_a) in a {
+        let Some(grid_b) = b.get(projector_id) else {
+            continue;
+        };
+        for row in 0..grid_a.rows {
+            for col in 0..grid_a.columns {
+                if let (Some(pa), Some(pb)) = (grid_a.get(col, row), grid_b.get(col, row)) {
+                    let node = grid_a.node_position(col, row);
+                    nodes.push(SharedNode {
+                        projector_id: *projector_id,
+                        projector: [node[0] as f64, node[1] as f64],
+                        a: [pa[0] as f64, pa[1] as f64],
+                        b: [pb[0] as f64, pb[1] as f64],
+                    });
+                }
+            }
+        }
+    }
+    nodes
+}
+
+/// Tie cameras to `reference` one at a time, always through the strongest
+/// link to an already tied camera.
+///
+/// `link(k, j, transform_j)` returns camera `k`'s transform and the number of
+/// shared observations it was fitted to.
+fn chain_cameras<T: Copy>(
+    count: usize,
+    reference: usize,
+    identity: T,
+    mut link: impl FnMut(usize, usize, &T) -> Option<(T, usize)>,
+) -> Vec<Option<T>> {
+    let mut tied: Vec<Option<T>> = vec![None; count];
+    tied[reference] = Some(identity);
+    loop {
+        let mut best: Option<(usize, T, usize)> = None;
+        for k in (0..count).filter(|&k| tied[k].is_none()) {
+            for (j, transform) in tied.iter().enumerate() {
+                let Some(transform) = transform else {
+                    continue;
+                };
+                if let Some((fitted, shared)) = link(k, j, transform) {
+                    if best.is_none_or(|b| shared > b.2) {
+                        best = Some((k, fitted, shared));
+                    }
+                }
+            }
+        }
+        let Some((k, fitted, shared)) = best else {
+            break;
+        };
+        log::info!("Camera {} linked through {} shared projector pixels", k + 1, shared);
+        tied[k] = Some(fitted);
+    }
+    tied
+}
+
+/// Inliers within three times the median residual (and at least `floor`).
+fn inlier_mask(residuals: &[f64], floor: f64) -> Vec<bool> {
+    let mut sorted = residuals.to_vec();
+    let threshold = (3.0 * linalg::percentile(&mut sorted, 0.5)).max(floor);
+    residuals.iter().map(|r| *r <= threshold).collect()
+}
+
+/// Homography fitted to point pairs, refitted without gross outliers.
+fn fit_robust_homography(pairs: &[([f64; 2], [f64; 2])]) -> Option<Mat3> {
+    let transfer = |h: &Mat3, (source, target): &([f64; 2], [f64; 2])| {
+        let (x, y) = HomographyComputer::transform_point(h, source[0], source[1]);
+        (x - target[0]).hypot(y - target[1])
+    };
+    let initial = fit_point_homography(pairs)?;
+    let residuals: Vec<f64> = pairs.iter().map(|p| transfer(&initial, p)).collect();
+    let inliers: Vec<_> = pairs
+        .iter()
+        .zip(inlier_mask(&residuals, 2.0))
+        .filter_map(|(p, keep)| keep.then_some(*p))
+        .collect();
+    fit_point_homography(&inliers)
+}
+
+/// Similarity fitted to point pairs, refitted without gross outliers.
+fn fit_robust_similarity(source: &[Vec3], target: &[Vec3]) -> Option<Similarity> {
+    let initial = Similarity::fit(source, target)?;
+    let residuals: Vec<f64> = source
+        .iter()
+        .zip(target)
+        .map(|(s, t)| linalg::norm(linalg::sub(initial.apply(*s), *t)))
+        .collect();
+    let scale = target.iter().map(|t| linalg::norm(*t)).sum::<f64>() / target.len().max(1) as f64;
+    let mask = inlier_mask(&residuals, 1e-3 * scale);
+    let keep = |points: &[Vec3]| -> Vec<Vec3> {
+        points.iter().zip(&mask).filter_map(|(p, keep)| keep.then_some(*p)).collect()
+    };
+    Similarity::fit(&keep(source), &keep(target))
+}
+
+/// Per node of a projector grid, the linked camera with the most confident decode.
+fn best_observations(
+    views: &[&[ProjectorCalibration]],
+    linked: impl Fn(usize) -> bool,
+    projector_id: u32,
+    columns: u32,
+    rows: u32,
+) -> Vec<Option<Observation>> {
+    let (columns, rows) = (columns.max(2), rows.max(2));
+    let mut best: Vec<Option<Observation>> = vec![None; (columns * rows) as usize];
+    for (camera, projectors) in views.iter().enumerate() {
+        if !linked(camera) {
+            continue;
+        }
+        let Some(corr) = visible(projectors, projector_id) else {
+            continue;
+        };
+        let grid = corr.to_correspondence_map(projector_id, columns, rows);
+        for (node, slot) in best.iter_mut().enumerate() {
+            let Some([x, y]) = grid.points[node] else {
+                continue;
+            };
+            let px = (x.round().max(0.0) as u32).min(corr.camera_width - 1);
+            let py = (y.round().max(0.0) as u32).min(corr.camera_height - 1);
+            let confidence = corr.confidence[(py * corr.camera_width + px) as usize];
+            if slot.is_none_or(|o| confidence > o.confidence) {
+                *slot = Some(Observation {
+                    camera,
+                    pixel: [x as f64, y as f64],
+                    confidence,
+                });
+            }
+        }
+    }
+    best
+}
+
+/// Size of a projector, from whichever camera holds it.
+fn projector_size(views: &[&[ProjectorCalibration]], projector_id: u32) -> Option<(u32, u32)> {
+    views
+        .iter()
+        .flat_map(|projectors| projectors.iter())
+        .find(|p| p.projector_id == projector_id)
+        .map(|p| (p.projector_width, p.projector_height))
+}
+
+/// Tie every camera of the session into the primary camera's image plane.
+pub fn merge_cameras(session: &CalibrationSession) -> Result<CameraMerge, String> {
+    let views = camera_views(session);
+    let grids = link_grids(&views);
+    if grids[0].is_empty() {
+        return Err("The primary camera does not see any projector".to_string());
+    }
+
+    let alignments = chain_cameras(views.len(), 0, linalg::IDENTITY, |k, j, to_reference| {
+        let pairs: Vec<([f64; 2], [f64; 2])> = shared_nodes(&grids[k], &grids[j])
+            .iter()
+            .map(|n| {
+                let (x, y) = HomographyComputer::transform_point(to_reference, n.b[0], n.b[1]);
+                (n.a, [x, y])
+            })
+            .collect();
+        if pairs.len() < MIN_SHARED_NODES {
+            return None;
+        }
+        Some((fit_robust_homography(&pairs)?, pairs.len()))
+    });
+    for (camera, alignment) in alignments.iter().enumerate() {
+        if alignment.is_none() {
+            log::warn!("Camera {} shares no projector pixels with the others; it is ignored", camera + 1);
+        }
+    }
+
+    // Content covers every decoded grid node of every linked camera
+    let (mut min, mut max) = ([f64::INFINITY; 2], [f64::NEG_INFINITY; 2]);
+    for (camera, alignment) in alignments.iter().enumerate() {
+        let Some(h) = alignment else {
+            continue;
+        };
+        for point in grids[camera].values().flat_map(|g| g.points.iter().flatten()) {
+            let (x, y) = HomographyComputer::transform_point(h, point[0] as f64, point[1] as f64);
+            min = [min[0].min(x), min[1].min(y)];
+            max = [max[0].max(x), max[1].max(y)];
+        }
+    }
+    let origin = [min[0].floor(), min[1].floor()];
+    let shift: Mat3 = [[1.0, 0.0, -origin[0]], [0.0, 1.0, -origin[1]], [0.0, 0.0, 1.0]];
+    let alignments = alignments
+        .into_iter()
+        .map(|a| a.map(|h| linalg::mat_mul(&shift, &h)))
+        .collect();
+
+    Ok(CameraMerge {
+        alignments,
+        content_width: (max[0] - origin[0]).ceil().max(1.0) as u32 + 1,
+        content_height: (max[1] - origin[1]).ceil().max(1.0) as u32 + 1,
+    })
+}
+
+impl CameraMerge {
+    /// Projector -> content correspondence grid from the best camera per node.
+    pub fn correspondence_map(
+        &self,
+        session: &CalibrationSession,
+        projector_id: u32,
+        columns: u32,
+        rows: u32,
+    ) -> Option<CorrespondenceMap> {
+        let views = camera_views(session);
+        let size = projector_size(&views, projector_id)?;
+        let mut map = CorrespondenceMap::new(
+            projector_id,
+            size,
+            (self.content_width, self.content_height),
+            columns,
+            rows,
+        );
+        let observations = best_observations(
+            &views,
+            |camera| self.alignments.get(camera).is_some_and(|a| a.is_some()),
+            projector_id,
+            map.columns,
+            map.rows,
+        );
+        for (node, observation) in observations.iter().enumerate() {
+            let Some(o) = observation else {
+                continue;
+            };
+            let Some(h) = &self.alignments[o.camera] else {
+                continue;
+            };
+            let (x, y) = HomographyComputer::transform_point(h, o.pixel[0], o.pixel[1]);
+            map.points[node] = Some([x as f32, y as f32]);
+        }
+        (map.valid_count() > 0).then_some(map)
+    }
+
+    /// Content -> projector homography fitted to a merged correspondence grid.
+    pub fn homography(map: &CorrespondenceMap) -> Option<Mat3> {
+        let pairs: Vec<([f64; 2], [f64; 2])> = (0..map.rows)
+            .flat_map(|row| (0..map.columns).map(move |col| (col, row)))
+            .filter_map(|(col, row)| {
+                let content = map.get(col, row)?;
+                let node = map.node_position(col, row);
+                Some((
+                    [content[0] as f64, content[1] as f64],
+                    [node[0] as f64, node[1] as f64],
+                ))
+            })
+            .collect();
+        if pairs.len() < 4 {
+            return None;
+        }
+        fit_robust_homography(&pairs)
+    }
+}
+
+/// Reconstruct the surface from every calibrated camera and tie the results together.
+///
+/// `intrinsics` holds one entry per session camera (primary first); cameras
+/// without intrinsics are skipped. The first reconstructed camera is the
+/// reference frame.
+pub fn reconstruct_rig(
+    session: &CalibrationSession,
+    intrinsics: &[Option<CameraIntrinsics>],
+    config: &ReconstructionConfig,
+) -> Result<RigReconstruction, String> {
+    let views = camera_views(session);
+    let surfaces: Vec<Option<SurfaceReconstruction>> = views
+        .iter()
+        .enumerate()
+        .map(|(camera, projectors)| {
+            let lens = intrinsics.get(camera).copied().flatten()?;
+            let seen = projectors.iter().filter(|p| visible(projectors, p.projector_id).is_some());
+            match reconstruct_projectors(&lens, seen, config) {
+                Ok(surface) => Some(surface),
+                Err(e) => {
+                    log::warn!("Camera {}: {}", camera + 1, e);
+                    None
+                }
+            }
+        })
+        .collect();
+    let reference = surfaces
+        .iter()
+        .position(|s| s.is_some())
+        .ok_or("No calibrated camera could reconstruct the surface")?;
+
+    let grids = link_grids(&views);
+    let identity = Similarity {
+        scale: 1.0,
+        rotation: linalg::IDENTITY,
+        translation: [0.0; 3],
+    };
+    let alignments = chain_cameras(views.len(), reference, identity, |k, j, to_reference| {
+        let (Some(surface_k), Some(surface_j)) = (&surfaces[k], &surfaces[j]) else {
+            return None;
+        };
+        let (mut source, mut target) = (Vec::new(), Vec::new());
+        for node in shared_nodes(&grids[k], &grids[j]) {
+            let a = surface_k.triangulate(node.projector_id, node.a, node.projector);
+            let b = surface_j.triangulate(node.projector_id, node.b, node.projector);
+            if let (Some(a), Some(b)) = (a, b) {
+                source.push(a);
+                target.push(to_reference.apply(b));
+            }
+        }
+        if source.len() < MIN_SHARED_NODES {
+            return None;
+        }
+        Some((fit_robust_similarity(&source, &target)?, source.len()))
+    });
+
+    let mut points = Vec::new();
+    for (surface, alignment) in surfaces.iter().zip(&alignments) {
+        match (surface, alignment) {
+            (Some(surface), Some(alignment)) => points.extend(surface.points.iter().map(|p| alignment.apply(*p))),
+            (Some(_), None) => log::warn!("A reconstructed camera shares no projector pixels with the others"),
+            _ => {}
+        }
+    }
+
+    Ok(RigReconstruction {
+        surfaces,
+        alignments,
+        points,
+    })
+}
+
+impl RigReconstruction {
+    /// Calibrated projectors with the camera they were solved from.
+    ///
+    /// A projector seen by several cameras is listed once per camera.
+    pub fn projectors(&self) -> impl Iterator<Item = (usize, &ProjectorGeometry)> {
+        self.surfaces
+            .iter()
+            .enumerate()
+            .filter_map(|(camera, s)| s.as_ref().map(|s| (camera, s)))
+            .flat_map(|(camera, s)| s.projectors.iter().map(move |p| (camera, p)))
+    }
+
+    /// Fit a content space to the merged surface.
+    pub fn fit_content(&self, space: &ContentSpace) -> Result<ContentMapping, String> {
+        let mapping = ContentMapping::fit(space, &self.points)?;
+        log::info!("Content space fitted: {}", mapping.describe());
+        Ok(mapping)
+    }
+
+    /// Per-pixel content UV map for a projector.
+    ///
+    /// Like [`SurfaceReconstruction::uv_map`], but each grid node is
+    /// triangulated from the camera with the most confident decode and moved
+    /// into the reference frame.
+    pub fn uv_map(
+        &self,
+        session: &CalibrationSession,
+        projector_id: u32,
+        mapping: &ContentMapping,
+        spacing: u32,
+    ) -> Option<UvMap> {
+        let views = camera_views(session);
+        let (width, height) = projector_size(&views, projector_id)?;
+        let spacing = spacing.max(1);
+        let template = CorrespondenceMap::new(projector_id, (width, height), (1, 1), width / spacing + 1, height / spacing + 1);
+        let usable = |camera: usize| {
+            self.alignments.get(camera).is_some_and(|a| a.is_some())
+                && self.surfaces[camera]
+                    .as_ref()
+                    .is_some_and(|s| s.projector(projector_id).is_some())
+        };
+        let observations = best_observations(&views, usable, projector_id, template.columns, template.rows);
+        let nodes: Vec<Option<[f64; 2]>> = observations
+            .iter()
+            .enumerate()
+            .map(|(node, observation)| {
+                let o = observation.as_ref()?;
+                let col = node as u32 % template.columns;
+                let row = node as u32 / template.columns;
+                let projector = template.node_position(col, row);
+                let surface = self.surfaces[o.camera].as_ref()?;
+                let point = surface.triangulate(projector_id, o.pixel, [projector[0] as f64, projector[1] as f64])?;
+                mapping.uv(self.alignments[o.camera]?.apply(point))
+            })
+            .collect();
+        if nodes.iter().all(|n| n.is_none()) {
+            return None;
+        }
+
+        Some(UvMap {
+            projector_id,
+            width,
+            height,
+            data: upsample_uv_grid(&nodes, template.columns, template.rows, width, height, mapping.wraps_u()),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Two cameras side by side on a 400 x 200 wall, two projectors across it.
+    ///
+    /// Camera 0 sees wall x in [0, 240), camera 1 sees x in [160, 400), both
+    /// at 1.5 wall units per pixel. Projector 0 lights x in [0, 200],
+    /// projector 1 lights x in [180, 380].
+    fn wall_session() -> CalibrationSession {
+        let mut session = CalibrationSession::new(Default::default());
+        session.add_projector(0, 256, 128);
+        session.add_projector(1, 256, 128);
+        session.add_camera("Camera 2", 160, 134);
+
+        let projector_origin = [0.0, 180.0];
+        let camera_origin = [0.0, 160.0];
+        let scale = 200.0 / 255.0;
+        for (camera, camera_x) in camera_origin.iter().enumerate() {
+            for (index, origin) in projector_origin.iter().enumerate() {
+                let mut corr = DecodedCorrespondences::new(160, 134, 256, 128);
+                for y in 0..134u32 {
+                    for x in 0..160u32 {
+                        let wall = [camera_x + x as f64 * 1.5, y as f64 * 1.5];
+                        let (px, py) = ((wall[0] - origin) / scale, wall[1] / scale);
+                        if !(0.0..=255.0).contains(&px) || !(0.0..=127.0).contains(&py) {
+                            continue;
+                        }
+                        let i = (y * 160 + x) as usize;
+                        corr.projector_x[i] = px.round() as i32;
+                        corr.projector_y[i] = py.round() as i32;
+                        // Camera 1 decodes projector 1 more reliably
+                        corr.confidence[i] = if camera == index { 0.9 } else { 0.6 };
+                        corr.valid_mask[i] = true;
+                    }
+                }
+                let projector = match camera {
+                    0 => &mut session.projectors[index],
+                    _ => &mut session.extra_cameras[0].projectors[index],
+                };
+                projector.correspondences = Some(corr);
+            }
+        }
+        session
+    }
+
+    #[test]
+    fn test_merge_extends_primary_camera_plane() {
+        let session = wall_session();
+        let merge = merge_cameras(&session).unwrap();
+        assert!(merge.alignments.iter().all(|a| a.is_some()));
+        // The lit wall spans 380 units = 253 primary camera pixels
+        assert!((merge.content_width as i32 - 253).abs() <= 3, "{}", merge.content_width);
+
+        let map = merge.correspondence_map(&session, 1, 33, 17).unwrap();
+        let to_content = merge.alignments[0].unwrap();
+        let mut checked = 0;
+        for (col, row) in [(4, 4), (16, 8), (30, 12)] {
+            let Some(content) = map.get(col, row) else {
+                continue;
+            };
+            // Primary camera pixel of the wall point, even beyond its image
+            let node = map.node_position(col, row);
+            let wall = [180.0 + node[0] as f64 * 200.0 / 255.0, node[1] as f64 * 200.0 / 255.0];
+            let (x, y) = HomographyComputer::transform_point(&to_content, wall[0] / 1.5, wall[1] / 1.5);
+            assert!((content[0] as f64 - x).abs() < 1.0, "{:?} vs {:?}", content, (x, y));
+            assert!((content[1] as f64 - y).abs() < 1.0, "{:?} vs {:?}", content, (x, y));
+            checked += 1;
+        }
+        assert_eq!(checked, 3);
+        assert!(CameraMerge::homography(&map).is_some());
+    }
+}
@@ -0,0 +1,187 @@
+//! Phase-shift structured light for sub-pixel correspondences.
+//!
+//! Each direction projects N sinusoidal fringe images, shifted by 2π/N, with a
+//! period of `period` projector pixels. The wrapped phase gives the position
+//! within a period to a fraction of a pixel; the period itself comes from a
+//! coarse Gray code at half-period resolution (the top bits of the regular
+//! Gray code sequence). Using half periods keeps the unwrapping robust when a
+//! Gray code boundary lands a pixel off from the phase wrap.
+
+use serde::{Deserialize, Serialize};
+use std::f32::consts::TAU;
+
+/// Phase-shift pattern configuration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PhaseShiftConfig {
+    /// Number of phase steps per direction (at least 3).
+    pub steps: u32,
+    /// Fringe period in projector pixels (power of two, at least 4).
+    pub period: u32,
+}
+
+impl Default for PhaseShiftConfig {
+    fn default() -> Self {
+        Self {
+            steps: 4,
+            period: 16,
+        }
+    }
+}
+
+impl PhaseShiftConfig {
+    pub fn new(steps: u32, period: u32) -> Self {
+        Self { steps, period }
+    }
+
+    /// Check that the configuration can be decoded.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.steps < 3 {
+            return Err(format!("Phase shift needs at least 3 steps, got {}", self.steps));
+        }
+        if self.period < 4 || !self.period.is_power_of_two() {
+            return Err(format!(
+                "Fringe period must be a power of two of at least 4 pixels, got {}",
+                self.period
+            ));
+        }
+        Ok(())
+    }
+
+    /// Gray code bits dropped from the full code (half-period resolution).
+    pub fn dropped_bits(&self) -> u32 {
+        (self.period / 2).max(1).trailing_zeros()
+    }
+
+    /// Fringe intensity (0-1) at a projector coordinate for a phase step.
+    pub fn intensity(&self, coord: f32, step: u32) -> f32 {
+        let phase = TAU * (coord / self.period as f32 - step as f32 / self.steps as f32);
+        0.5 + 0.5 * phase.cos()
+    }
+
+    /// Wrapped phase (0..2π) and fringe amplitude from one pixel's samples.
+    ///
+    /// Samples follow `I_n = A + B cos(φ - 2πn/N)`; returns `(φ, B)`.
+    pub fn wrapped_phase(&self, samples: impl Iterator<Item = f32>) -> (f32, f32) {
+        let (mut sin_sum, mut cos_sum) = (0.0f32, 0.0f32);
+        for (n, value) in samples.enumerate() {
+            let delta = TAU * n as f32 / self.steps as f32;
+            sin_sum += value * delta.sin();
+            cos_sum += value * delta.cos();
+        }
+        let phase = sin_sum.atan2(cos_sum).rem_euclid(TAU);
+        let amplitude = 2.0 / self.steps as f32 * sin_sum.hypot(cos_sum);
+        (phase, amplitude)
+    }
+
+    /// Absolute projector coordinate from the half-period index and wrapped phase.
+    pub fn unwrap(&self, half_period_index: u32, phase: f32) -> f32 {
+        let period = self.period as f32;
+        let fraction = phase / TAU;
+        // Center of the half period the Gray code landed in
+        let coarse = (half_period_index as f32 + 0.5) * period / 2.0;
+        let cycle = ((coarse - fraction * period) / period).round();
+        (cycle + fraction) * period
+    }
+}
+
+/// One fringe image of a phase-shift sequence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PhaseStep {
+    /// Step index (0..steps).
+    pub step: u32,
+    /// Sequence configuration.
+    pub config: PhaseShiftConfig,
+}
+
+/// Refine a Gray-decoded coordinate plane with captured fringe images.
+///
+/// `coords` holds the half-period indices decoded from the coarse Gray code
+/// and is replaced by the nearest whole projector pixel; `subpixel` receives
+/// the fractional coordinate. Pixels whose fringe amplitude is below
+/// `threshold` (as a fraction of the white/black range) are invalidated.
+#[allow(clippy::too_many_arguments)]
+pub fn refine_coordinate(
+    config: &PhaseShiftConfig,
+    threshold: f32,
+    coords: &mut [i32],
+    subpixel: &mut [f32],
+    confidence: &mut [f32],
+    valid: &mut [bool],
+    frames: &[Vec<u8>],
+    white: &[u8],
+    black: &[u8],
+    max_coord: u32,
+) -> Result<(), String> {
+    if frames.len() != config.steps as usize {
+        return Err(format!("Expected {} phase images, got {}", config.steps, frames.len()));
+    }
+
+    for i in 0..coords.len() {
+        if !valid[i] || coords[i] < 0 {
+            continue;
+        }
+        let range = white.get(i).copied().unwrap_or(0) as f32 - black.get(i).copied().unwrap_or(0) as f32;
+        let samples = frames.iter().map(|f| f.get(i).copied().unwrap_or(0) as f32);
+        let (phase, amplitude) = config.wrapped_phase(samples);
+
+        // A full-swing fringe has an amplitude of half the white/black range
+        let modulation = if range > 0.0 { (2.0 * amplitude / range).min(1.0) } else { 0.0 };
+        if modulation < threshold {
+            valid[i] = false;
+            continue;
+        }
+
+        let coord = config.unwrap(coords[i] as u32, phase);
+        if coord < -0.5 || coord > max_coord as f32 - 0.5 {
+            valid[i] = false;
+            continue;
+        }
+        subpixel[i] = coord.max(0.0);
+        coords[i] = coord.round().max(0.0) as i32;
+        confidence[i] = confidence[i].min(modulation);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wrapped_phase_recovers_position() {
+        let config = PhaseShiftConfig::default();
+        for coord in [0.0, 3.25, 7.5, 15.9, 100.4] {
+            // Camera gain and offset do not change the phase
+            let samples = (0..config.steps).map(|n| 20.0 + 180.0 * config.intensity(coord, n));
+            let (phase, amplitude) = config.wrapped_phase(samples);
+            let expected = (coord / 16.0).fract() * TAU;
+            let error = (phase - expected).abs();
+            assert!(error.min(TAU - error) < 1e-3, "coord {}: {} vs {}", coord, phase, expected);
+            assert!((amplitude - 90.0).abs() < 1e-2);
+        }
+    }
+
+    #[test]
+    fn test_unwrap_tolerates_gray_code_boundary_errors() {
+        let config = PhaseShiftConfig::default();
+        for coord in [0.2f32, 7.9, 8.1, 15.95, 16.05, 300.5] {
+            let phase = (coord / 16.0).fract() * TAU;
+            let half_index = (coord / 8.0) as u32;
+            // Exact, and one half period off in either direction near its edge
+            assert!((config.unwrap(half_index, phase) - coord).abs() < 1e-3);
+            let early = (coord - 1.0).max(0.0) / 8.0;
+            let late = (coord + 1.0) / 8.0;
+            assert!((config.unwrap(early as u32, phase) - coord).abs() < 1e-3);
+            assert!((config.unwrap(late as u32, phase) - coord).abs() < 1e-3);
+        }
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(PhaseShiftConfig::default().validate().is_ok());
+        assert!(PhaseShiftConfig::new(2, 16).validate().is_err());
+        assert!(PhaseShiftConfig::new(4, 12).validate().is_err());
+        assert_eq!(PhaseShiftConfig::new(4, 16).dropped_bits(), 3);
+    }
+}
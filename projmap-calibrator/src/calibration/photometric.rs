@@ -0,0 +1,739 @@
+//! Photometric calibration: projector response measurement and color matching.
+//!
+//! Each projector shows black, white and a ramp of levels per primary while the
+//! camera averages the color it sees over the projector's footprint. The
+//! per-channel responses are then inverted so every projector reproduces a
+//! common target: the dimmest white and the brightest black of the set, per
+//! channel, which also gives all projectors the same white point.
+//!
+//! Matching happens in camera space, so the camera only needs a monotonic
+//! response and fixed exposure/white balance for the whole run.
+
+use super::session::CapturePhase;
+use crate::config::ColorCorrection;
+use std::time::{Duration, Instant};
+
+/// Index of the black patch in a projector's sequence.
+const BLACK_PATCH: usize = 0;
+/// Index of the white patch in a projector's sequence.
+const WHITE_PATCH: usize = 1;
+
+/// Configuration for photometric calibration.
+#[derive(Debug, Clone)]
+pub struct PhotometricConfig {
+    /// Ramp levels per channel, including black and full intensity.
+    pub levels: usize,
+    /// Time to wait for the projector (and camera exposure) to settle.
+    pub settle_time: Duration,
+    /// Number of frames to average for each patch.
+    pub frames_to_average: usize,
+    /// Minimum white/black difference for a camera pixel to be measured.
+    pub contrast_threshold: f32,
+}
+
+impl Default for PhotometricConfig {
+    fn default() -> Self {
+        Self {
+            levels: 9,
+            settle_time: Duration::from_millis(300),
+            frames_to_average: 5,
+            contrast_threshold: 0.1,
+        }
+    }
+}
+
+impl PhotometricConfig {
+    /// Solid colors shown for each projector: black, white, then a ramp per primary.
+    pub fn patch_sequence(&self) -> Vec<[f32; 3]> {
+        let steps = self.levels.max(2) - 1;
+        let mut patches = vec![[0.0; 3], [1.0; 3]];
+        for channel in 0..3 {
+            for step in 1..=steps {
+                let mut color = [0.0; 3];
+                color[channel] = step as f32 / steps as f32;
+                patches.push(color);
+            }
+        }
+        patches
+    }
+}
+
+/// State of the photometric calibration.
+#[derive(Debug, Clone)]
+pub enum PhotometricState {
+    /// Waiting to start.
+    Idle,
+    /// Projecting and capturing a solid color patch.
+    Measuring {
+        projector_id: u32,
+        patch_index: usize,
+        phase: CapturePhase,
+        start_time: Instant,
+    },
+    /// All projectors measured and matched.
+    Complete,
+    /// Error occurred.
+    Error(String),
+}
+
+impl PhotometricState {
+    pub fn is_idle(&self) -> bool {
+        matches!(self, PhotometricState::Idle)
+    }
+
+    pub fn is_complete(&self) -> bool {
+        matches!(self, PhotometricState::Complete)
+    }
+}
+
+impl std::fmt::Display for PhotometricState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PhotometricState::Idle => write!(f, "Idle"),
+            PhotometricState::Measuring { projector_id, patch_index, .. } => {
+                write!(f, "Projector {} patch {}", projector_id, patch_index)
+            }
+            PhotometricState::Complete => write!(f, "Complete"),
+            PhotometricState::Error(msg) => write!(f, "Error: {}", msg),
+        }
+    }
+}
+
+/// Measured response of one projector channel, as seen by the camera.
+///
+/// Values are non-decreasing camera readings (0-1) at increasing drive levels.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResponseCurve {
+    /// Drive levels (0-1), ascending, starting at 0 and ending at 1.
+    pub levels: Vec<f32>,
+    /// Camera reading at each level.
+    pub values: Vec<f32>,
+}
+
+impl ResponseCurve {
+    /// Build a curve, forcing the readings to be non-decreasing (sensor noise).
+    pub fn new(levels: Vec<f32>, mut values: Vec<f32>) -> Self {
+        for i in 1..values.len() {
+            values[i] = values[i].max(values[i - 1]);
+        }
+        Self { levels, values }
+    }
+
+    /// Reading with the channel off.
+    pub fn black(&self) -> f32 {
+        self.values.first().copied().unwrap_or(0.0)
+    }
+
+    /// Reading at full drive.
+    pub fn white(&self) -> f32 {
+        self.values.last().copied().unwrap_or(0.0)
+    }
+
+    /// Camera reading for a drive level (piecewise linear).
+    pub fn evaluate(&self, level: f32) -> f32 {
+        let level = level.clamp(0.0, 1.0);
+        for i in 1..self.levels.len() {
+            if level <= self.levels[i] {
+                let span = self.levels[i] - self.levels[i - 1];
+                let t = if span > 0.0 { (level - self.levels[i - 1]) / span } else { 1.0 };
+                return self.values[i - 1] + (self.values[i] - self.values[i - 1]) * t;
+            }
+        }
+        self.white()
+    }
+
+    /// Lowest drive level that reaches a camera reading (clamped to 0-1).
+    pub fn inverse(&self, value: f32) -> f32 {
+        if value <= self.black() {
+            return 0.0;
+        }
+        for i in 1..self.values.len() {
+            if value <= self.values[i] {
+                let rise = self.values[i] - self.values[i - 1];
+                let t = if rise > 0.0 { (value - self.values[i - 1]) / rise } else { 0.0 };
+                return self.levels[i - 1] + (self.levels[i] - self.levels[i - 1]) * t;
+            }
+        }
+        1.0
+    }
+
+    /// Fit `reading = black + (white - black) * level^gamma` and return gamma.
+    pub fn fit_gamma(&self) -> f32 {
+        let range = self.white() - self.black();
+        if range <= f32::EPSILON {
+            return 1.0;
+        }
+        let samples = self.levels.iter().zip(&self.values).filter_map(|(level, value)| {
+            let normalized = (value - self.black()) / range;
+            (*level > 0.0 && *level < 1.0 && normalized > 1e-3).then_some((*level, normalized))
+        });
+        fit_exponent(samples).unwrap_or(1.0)
+    }
+}
+
+/// Least-squares exponent `e` of `y = x^e` through the origin in log space.
+fn fit_exponent(samples: impl Iterator<Item = (f32, f32)>) -> Option<f32> {
+    let (mut sum_xy, mut sum_xx) = (0.0f64, 0.0f64);
+    for (x, y) in samples {
+        let (lx, ly) = ((x as f64).ln(), (y as f64).ln());
+        sum_xy += lx * ly;
+        sum_xx += lx * lx;
+    }
+    (sum_xx > 0.0).then(|| (sum_xy / sum_xx) as f32)
+}
+
+/// Measured per-channel response of a projector.
+#[derive(Debug, Clone)]
+pub struct ProjectorResponse {
+    /// Projector ID.
+    pub projector_id: u32,
+    /// Red, green and blue responses, each read from the matching camera channel.
+    pub curves: [ResponseCurve; 3],
+    /// Camera reading of the projector's full white.
+    pub white: [f32; 3],
+}
+
+impl ProjectorResponse {
+    /// Build a response from patch measurements in [`PhotometricConfig::patch_sequence`] order.
+    pub fn from_measurements(projector_id: u32, levels: usize, measurements: &[[f32; 3]]) -> Option<Self> {
+        let steps = levels.max(2) - 1;
+        if measurements.len() != 2 + 3 * steps {
+            return None;
+        }
+        let black = measurements[BLACK_PATCH];
+        let ramp_levels: Vec<f32> = (0..=steps).map(|step| step as f32 / steps as f32).collect();
+        let curves = [0, 1, 2].map(|channel| {
+            let ramp = &measurements[2 + channel * steps..2 + (channel + 1) * steps];
+            let values = std::iter::once(black[channel])
+                .chain(ramp.iter().map(|rgb| rgb[channel]))
+                .collect();
+            ResponseCurve::new(ramp_levels.clone(), values)
+        });
+        Some(Self {
+            projector_id,
+            curves,
+            white: measurements[WHITE_PATCH],
+        })
+    }
+}
+
+/// Correction that makes one projector match the common target.
+#[derive(Debug, Clone)]
+pub struct ColorMatch {
+    /// Projector ID.
+    pub projector_id: u32,
+    /// Drive level per channel for evenly spaced input levels (0-1).
+    pub curves: [Vec<f32>; 3],
+    /// Closest brightness/gamma/RGB gain approximation of the curves.
+    pub correction: ColorCorrection,
+}
+
+impl ColorMatch {
+    /// Corrected drive for an input color.
+    pub fn apply(&self, rgb: [f32; 3]) -> [f32; 3] {
+        [0, 1, 2].map(|channel| {
+            let curve = &self.curves[channel];
+            let position = rgb[channel].clamp(0.0, 1.0) * (curve.len() - 1) as f32;
+            let index = (position.floor() as usize).min(curve.len() - 2);
+            let t = position - index as f32;
+            curve[index] + (curve[index + 1] - curve[index]) * t
+        })
+    }
+
+    /// Serialize the correction as a `.cube` 3D LUT.
+    pub fn to_cube_string(&self, size: u32) -> String {
+        let size = size.max(2);
+        let scale = 1.0 / (size - 1) as f32;
+        let mut out = format!(
+            "TITLE \"Projector {} color match\"\nLUT_3D_SIZE {}\n",
+            self.projector_id, size
+        );
+        for b in 0..size {
+            for g in 0..size {
+                for r in 0..size {
+                    let [cr, cg, cb] = self.apply([r as f32 * scale, g as f32 * scale, b as f32 * scale]);
+                    out.push_str(&format!("{:.6} {:.6} {:.6}\n", cr, cg, cb));
+                }
+            }
+        }
+        out
+    }
+}
+
+/// Compute corrections that make all projectors match.
+///
+/// Per channel the target reading at each level is the dimmest projector's
+/// reading, raised to the brightest black so every projector can reach it.
+/// `samples` is the number of input levels stored per curve.
+pub fn match_projectors(responses: &[ProjectorResponse], samples: usize) -> Vec<ColorMatch> {
+    let samples = samples.max(2);
+    let levels: Vec<f32> = (0..samples).map(|i| i as f32 / (samples - 1) as f32).collect();
+    let targets: [Vec<f32>; 3] = [0, 1, 2].map(|channel| {
+        let floor = responses
+            .iter()
+            .map(|r| r.curves[channel].black())
+            .fold(0.0f32, f32::max);
+        levels
+            .iter()
+            .map(|level| {
+                let lowest = responses
+                    .iter()
+                    .map(|r| r.curves[channel].evaluate(*level))
+                    .fold(f32::INFINITY, f32::min);
+                lowest.max(floor)
+            })
+            .collect()
+    });
+
+    responses
+        .iter()
+        .map(|response| {
+            let curves = [0, 1, 2].map(|channel| {
+                targets[channel]
+                    .iter()
+                    .map(|target| response.curves[channel].inverse(*target))
+                    .collect::<Vec<f32>>()
+            });
+            let correction = fit_correction(&levels, &curves);
+            ColorMatch {
+                projector_id: response.projector_id,
+                curves,
+                correction,
+            }
+        })
+        .collect()
+}
+
+/// Approximate drive curves with per-channel gains and a shared gamma.
+fn fit_correction(levels: &[f32], curves: &[Vec<f32>; 3]) -> ColorCorrection {
+    let gains = curves.clone().map(|curve| curve.last().copied().unwrap_or(1.0));
+    let exponents: Vec<f32> = curves
+        .iter()
+        .zip(gains)
+        .filter(|(_, gain)| *gain > f32::EPSILON)
+        .filter_map(|(curve, gain)| {
+            let samples = levels.iter().zip(curve).filter_map(|(level, drive)| {
+                let normalized = drive / gain;
+                (*level > 0.0 && *level < 1.0 && normalized > 1e-3).then_some((*level, normalized))
+            });
+            fit_exponent(samples)
+        })
+        .collect();
+    let exponent = if exponents.is_empty() {
+        1.0
+    } else {
+        exponents.iter().sum::<f32>() / exponents.len() as f32
+    };
+    ColorCorrection {
+        // The color correction applies pow(c, 1 / gamma)
+        gamma: (1.0 / exponent.max(0.01)).clamp(0.1, 4.0),
+        red: gains[0],
+        green: gains[1],
+        blue: gains[2],
+        ..ColorCorrection::default()
+    }
+}
+
+/// Per-projector capture data.
+struct PhotometricProjector {
+    projector_id: u32,
+    /// Camera pixels lit by this projector (from the black/white patches).
+    mask: Option<Vec<bool>>,
+    /// Averaged BGRA black frame, kept until the mask is known.
+    black_frame: Option<Vec<u8>>,
+    /// Mean camera RGB (0-1) per captured patch.
+    measurements: Vec<[f32; 3]>,
+}
+
+/// Manages the photometric calibration workflow.
+pub struct PhotometricSession {
+    /// Current state.
+    pub state: PhotometricState,
+    /// Configuration.
+    pub config: PhotometricConfig,
+    /// Measured responses (filled when complete).
+    pub responses: Vec<ProjectorResponse>,
+    /// Corrections (filled when complete).
+    pub matches: Vec<ColorMatch>,
+    projectors: Vec<PhotometricProjector>,
+    current_projector: usize,
+    /// Accumulated full BGRA frames (black/white patches).
+    accumulated_frames: Vec<Vec<u8>>,
+    /// Accumulated mean colors (ramp patches).
+    accumulated_means: Vec<[f32; 3]>,
+}
+
+impl PhotometricSession {
+    pub fn new(config: PhotometricConfig) -> Self {
+        Self {
+            state: PhotometricState::Idle,
+            config,
+            responses: Vec::new(),
+            matches: Vec::new(),
+            projectors: Vec::new(),
+            current_projector: 0,
+            accumulated_frames: Vec::new(),
+            accumulated_means: Vec::new(),
+        }
+    }
+
+    /// Add a projector to measure.
+    pub fn add_projector(&mut self, id: u32) {
+        self.projectors.push(PhotometricProjector {
+            projector_id: id,
+            mask: None,
+            black_frame: None,
+            measurements: Vec::new(),
+        });
+    }
+
+    /// Remove all projectors and results.
+    pub fn clear(&mut self) {
+        self.cancel();
+        self.projectors.clear();
+        self.responses.clear();
+        self.matches.clear();
+    }
+
+    /// Start measuring.
+    pub fn start(&mut self) -> Result<(), String> {
+        if self.projectors.is_empty() {
+            return Err("No projectors configured".to_string());
+        }
+        for projector in &mut self.projectors {
+            projector.mask = None;
+            projector.black_frame = None;
+            projector.measurements.clear();
+        }
+        self.responses.clear();
+        self.matches.clear();
+        self.current_projector = 0;
+        self.begin_patch(0);
+        log::info!("Starting photometric calibration for {} projector(s)", self.projectors.len());
+        Ok(())
+    }
+
+    /// Cancel the measurement.
+    pub fn cancel(&mut self) {
+        self.state = PhotometricState::Idle;
+        self.accumulated_frames.clear();
+        self.accumulated_means.clear();
+    }
+
+    /// Projector that should currently display [`Self::current_color`].
+    pub fn current_projector_id(&self) -> Option<u32> {
+        match &self.state {
+            PhotometricState::Measuring { projector_id, .. } => Some(*projector_id),
+            _ => None,
+        }
+    }
+
+    /// Solid color to display (None when not measuring).
+    pub fn current_color(&self) -> Option<[f32; 3]> {
+        match &self.state {
+            PhotometricState::Measuring { patch_index, .. } => {
+                self.config.patch_sequence().get(*patch_index).copied()
+            }
+            _ => None,
+        }
+    }
+
+    /// Get progress (0.0 to 1.0).
+    pub fn progress(&self) -> f32 {
+        if self.projectors.is_empty() {
+            return 0.0;
+        }
+        let per_projector = self.config.patch_sequence().len();
+        let done = match &self.state {
+            PhotometricState::Measuring { patch_index, .. } => {
+                self.current_projector * per_projector + patch_index
+            }
+            PhotometricState::Complete => self.projectors.len() * per_projector,
+            _ => 0,
+        };
+        done as f32 / (self.projectors.len() * per_projector) as f32
+    }
+
+    /// Update state machine (call once per frame).
+    pub fn update(&mut self) {
+        if let PhotometricState::Measuring {
+            projector_id,
+            patch_index,
+            phase: CapturePhase::DisplayingPattern,
+            start_time,
+        } = &self.state
+        {
+            if start_time.elapsed() >= self.config.settle_time {
+                self.state = PhotometricState::Measuring {
+                    projector_id: *projector_id,
+                    patch_index: *patch_index,
+                    phase: CapturePhase::WaitingForCapture,
+                    start_time: Instant::now(),
+                };
+            }
+        }
+    }
+
+    /// Submit a captured BGRA camera frame.
+    pub fn submit_frame(&mut self, bgra: &[u8], width: u32, height: u32) {
+        let PhotometricState::Measuring { patch_index, phase, .. } = &self.state else {
+            return;
+        };
+        if *phase == CapturePhase::DisplayingPattern {
+            return;
+        }
+        let patch_index = *patch_index;
+        let pixel_count = (width * height) as usize;
+        if bgra.len() < pixel_count * 4 {
+            return;
+        }
+
+        if patch_index == BLACK_PATCH || patch_index == WHITE_PATCH {
+            self.accumulated_frames.push(bgra[..pixel_count * 4].to_vec());
+            if self.accumulated_frames.len() < self.config.frames_to_average {
+                return;
+            }
+            let averaged = average_frames(&self.accumulated_frames);
+            self.accumulated_frames.clear();
+            if let Err(e) = self.store_reference(patch_index, averaged) {
+                self.state = PhotometricState::Error(e);
+                return;
+            }
+        } else {
+            let projector = &self.projectors[self.current_projector];
+            let Some(mask) = projector.mask.as_ref().filter(|m| m.len() == pixel_count) else {
+                self.state = PhotometricState::Error("Camera resolution changed during measurement".to_string());
+                return;
+            };
+            self.accumulated_means.push(masked_mean(bgra, mask));
+            if self.accumulated_means.len() < self.config.frames_to_average {
+                return;
+            }
+            let count = self.accumulated_means.len() as f32;
+            let mean = self
+                .accumulated_means
+                .drain(..)
+                .fold([0.0; 3], |sum, rgb| [sum[0] + rgb[0], sum[1] + rgb[1], sum[2] + rgb[2]])
+                .map(|c| c / count);
+            self.projectors[self.current_projector].measurements.push(mean);
+        }
+
+        self.advance(patch_index);
+    }
+
+    /// Store the black or white reference; the white one also builds the mask.
+    fn store_reference(&mut self, patch_index: usize, frame: Vec<u8>) -> Result<(), String> {
+        let threshold = self.config.contrast_threshold;
+        let projector = &mut self.projectors[self.current_projector];
+        if patch_index == BLACK_PATCH {
+            projector.black_frame = Some(frame);
+            return Ok(());
+        }
+
+        let black = projector.black_frame.take().ok_or("Missing black reference")?;
+        if black.len() != frame.len() {
+            return Err("Camera resolution changed during measurement".to_string());
+        }
+        let luma = |px: &[u8]| (px[0] as f32 + px[1] as f32 + px[2] as f32) / (3.0 * 255.0);
+        let mask: Vec<bool> = black
+            .chunks_exact(4)
+            .zip(frame.chunks_exact(4))
+            .map(|(b, w)| luma(w) - luma(b) >= threshold)
+            .collect();
+        let lit = mask.iter().filter(|&&m| m).count();
+        if lit == 0 {
+            return Err(format!("Projector {} is not visible to the camera", projector.projector_id));
+        }
+        log::info!("Projector {}: measuring {} camera pixels", projector.projector_id, lit);
+
+        projector.measurements = vec![masked_mean(&black, &mask), masked_mean(&frame, &mask)];
+        projector.mask = Some(mask);
+        Ok(())
+    }
+
+    /// Move to the next patch, projector, or finish.
+    fn advance(&mut self, patch_index: usize) {
+        let patch_count = self.config.patch_sequence().len();
+        if patch_index + 1 < patch_count {
+            self.begin_patch(patch_index + 1);
+            return;
+        }
+
+        // Free the mask; only the measurements are needed from here
+        self.projectors[self.current_projector].mask = None;
+        self.current_projector += 1;
+        if self.current_projector < self.projectors.len() {
+            self.begin_patch(0);
+        } else {
+            self.finish();
+        }
+    }
+
+    fn begin_patch(&mut self, patch_index: usize) {
+        self.state = PhotometricState::Measuring {
+            projector_id: self.projectors[self.current_projector].projector_id,
+            patch_index,
+            phase: CapturePhase::DisplayingPattern,
+            start_time: Instant::now(),
+        };
+    }
+
+    /// Fit responses and compute the matching corrections.
+    fn finish(&mut self) {
+        let levels = self.config.levels;
+        let responses: Option<Vec<_>> = self
+            .projectors
+            .iter()
+            .map(|p| ProjectorResponse::from_measurements(p.projector_id, levels, &p.measurements))
+            .collect();
+        let Some(responses) = responses else {
+            self.state = PhotometricState::Error("Incomplete measurements".to_string());
+            return;
+        };
+
+        for response in &responses {
+            log::info!(
+                "Projector {}: white {:.3}/{:.3}/{:.3}, gamma {:.2}/{:.2}/{:.2}",
+                response.projector_id,
+                response.white[0],
+                response.white[1],
+                response.white[2],
+                response.curves[0].fit_gamma(),
+                response.curves[1].fit_gamma(),
+                response.curves[2].fit_gamma()
+            );
+        }
+
+        self.matches = match_projectors(&responses, 33);
+        self.responses = responses;
+        self.state = PhotometricState::Complete;
+    }
+}
+
+/// Average BGRA frames per byte.
+fn average_frames(frames: &[Vec<u8>]) -> Vec<u8> {
+    let count = frames.len() as u32;
+    (0..frames[0].len())
+        .map(|i| (frames.iter().map(|f| f[i] as u32).sum::<u32>() / count) as u8)
+        .collect()
+}
+
+/// Mean RGB (0-1) of a BGRA frame over the masked pixels.
+fn masked_mean(bgra: &[u8], mask: &[bool]) -> [f32; 3] {
+    let mut sum = [0.0f64; 3];
+    let mut count = 0usize;
+    for (px, _) in bgra.chunks_exact(4).zip(mask).filter(|(_, m)| **m) {
+        sum[0] += px[2] as f64;
+        sum[1] += px[1] as f64;
+        sum[2] += px[0] as f64;
+        count += 1;
+    }
+    let scale = 1.0 / (count.max(1) as f64 * 255.0);
+    sum.map(|c| (c * scale) as f32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Synthetic projector: reading = black + gain * level^gamma per channel
+    fn simulate(black: f32, gains: [f32; 3], gamma: f32, color: [f32; 3]) -> [f32; 3] {
+        [0, 1, 2].map(|c| (black + gains[c] * color[c].powf(gamma)).min(1.0))
+    }
+
+    #[test]
+    fn test_response_curve() {
+        let curve = ResponseCurve::new(vec![0.0, 0.5, 1.0], vec![0.1, 0.05, 0.9]);
+        // Noise below the previous reading is flattened
+        assert_eq!(curve.values, vec![0.1, 0.1, 0.9]);
+        assert!((curve.evaluate(0.75) - 0.5).abs() < 1e-6);
+        assert!((curve.inverse(0.5) - 0.75).abs() < 1e-6);
+        assert_eq!(curve.inverse(0.0), 0.0);
+        assert_eq!(curve.inverse(2.0), 1.0);
+
+        let levels: Vec<f32> = (0..=8).map(|i| i as f32 / 8.0).collect();
+        let values = levels.iter().map(|l| 0.02 + 0.8 * l.powf(2.2)).collect();
+        assert!((ResponseCurve::new(levels, values).fit_gamma() - 2.2).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_match_projectors() {
+        let config = PhotometricConfig::default();
+        let patches = config.patch_sequence();
+        assert_eq!(patches.len(), 2 + 3 * 8);
+
+        let bright: Vec<_> = patches.iter().map(|c| simulate(0.05, [0.9, 0.85, 0.8], 2.2, *c)).collect();
+        let dim: Vec<_> = patches.iter().map(|c| simulate(0.02, [0.6, 0.7, 0.5], 2.2, *c)).collect();
+        let responses = vec![
+            ProjectorResponse::from_measurements(0, config.levels, &bright).unwrap(),
+            ProjectorResponse::from_measurements(1, config.levels, &dim).unwrap(),
+        ];
+        let matches = match_projectors(&responses, 33);
+
+        // Both projectors now produce the same reading for every input
+        for level in [0.0, 0.3, 0.6, 1.0] {
+            let [a, b] = [0, 1].map(|p| {
+                let drive = matches[p].apply([level; 3]);
+                [0, 1, 2].map(|c| responses[p].curves[c].evaluate(drive[c]))
+            });
+            for c in 0..3 {
+                assert!((a[c] - b[c]).abs() < 0.01, "level {} channel {}: {} vs {}", level, c, a[c], b[c]);
+            }
+        }
+
+        // The dim projector runs at full drive; the bright one is scaled down to match
+        assert!((matches[1].correction.red - 1.0).abs() < 1e-3);
+        assert!(matches[0].correction.red < 0.9);
+
+        // With equal blacks and gammas only the gains differ
+        let same_black: Vec<_> = patches.iter().map(|c| simulate(0.02, [0.9, 0.85, 0.8], 2.2, *c)).collect();
+        let responses = vec![
+            ProjectorResponse::from_measurements(0, config.levels, &same_black).unwrap(),
+            responses[1].clone(),
+        ];
+        let correction = match_projectors(&responses, 33)[0].correction;
+        assert!((correction.gamma - 1.0).abs() < 0.05, "gamma {}", correction.gamma);
+        assert!((correction.green - (0.7f32 / 0.85).powf(1.0 / 2.2)).abs() < 0.01);
+
+        let cube = matches[1].to_cube_string(5);
+        assert!(cube.contains("LUT_3D_SIZE 5"));
+        assert_eq!(cube.lines().count(), 2 + 125);
+    }
+
+    #[test]
+    fn test_session_measures_projectors() {
+        let config = PhotometricConfig {
+            levels: 3,
+            settle_time: Duration::ZERO,
+            frames_to_average: 2,
+            contrast_threshold: 0.1,
+        };
+        let mut session = PhotometricSession::new(config);
+        session.add_projector(7);
+        session.start().unwrap();
+
+        // 4x1 camera: the projector lights the two left pixels only
+        let mut frames = 0;
+        while let Some(color) = session.current_color() {
+            session.update();
+            let lit = simulate(0.1, [0.8, 0.7, 0.6], 2.0, color);
+            let frame: Vec<u8> = (0..4)
+                .flat_map(|x| {
+                    let rgb = if x < 2 { lit } else { [0.05; 3] };
+                    [rgb[2], rgb[1], rgb[0], 1.0].map(|c| (c * 255.0).round() as u8)
+                })
+                .collect();
+            session.submit_frame(&frame, 4, 1);
+            frames += 1;
+            assert!(frames < 100, "session did not finish");
+        }
+
+        assert!(session.state.is_complete(), "{}", session.state);
+        assert_eq!(frames, 2 * (2 + 3 * 2));
+        let response = &session.responses[0];
+        assert_eq!(response.projector_id, 7);
+        assert!((response.white[0] - 0.9).abs() < 0.01);
+        assert!((response.curves[1].evaluate(0.5) - (0.1 + 0.7 * 0.25)).abs() < 0.01);
+        assert_eq!(session.matches.len(), 1);
+    }
+}
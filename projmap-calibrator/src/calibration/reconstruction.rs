@@ -0,0 +1,784 @@
+//! Projector-camera geometric calibration and surface reconstruction.
+//!
+//! With calibrated camera intrinsics, each projector is treated as an
+//! inverse camera. Its pose relative to the camera is initialized by
+//! decomposing the homography of the best-fitting surface plane with an
+//! initial guess of the projector lens, then refined on the epipolar
+//! (Sampson) error of all correspondences together with the focal length and
+//! vertical principal point. Triangulating the correspondences gives the
+//! surface point cloud.
+//!
+//! Two-view reconstruction is only defined up to scale, so every projector
+//! is first solved with a unit baseline and then rescaled to agree with the
+//! depths of projectors it overlaps in the camera image. The lens can only
+//! be refined where the surface has depth variation; on a nearly flat
+//! screen it stays close to the initial guess.
+
+use super::camera_model::{CameraIntrinsics, Pose};
+use super::content_space::{ContentMapping, ContentSpace};
+use super::homography::{fit_point_homography, HomographyComputer};
+use super::decoder::DecodedCorrespondences;
+use super::linalg::{self, Mat3, Vec3};
+use super::session::ProjectorCalibration;
+use crate::config::UvMap;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Minimum correspondences per projector.
+const MIN_SAMPLES: usize = 50;
+
+/// Minimum shared camera pixels to chain the scale between two projectors.
+const MIN_SCALE_OVERLAP: usize = 20;
+
+/// Maximum Levenberg-Marquardt iterations for the pose refinement.
+const LM_MAX_ITERS: usize = 50;
+
+/// Weight of the prior that keeps the lens near its initial guess.
+const LENS_PRIOR_WEIGHT: f64 = 2.0;
+
+/// Projector calibration settings.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ReconstructionConfig {
+    /// Initial projector throw ratio (throw distance / image width).
+    pub throw_ratio: f64,
+    /// Initial vertical lens shift (fraction of the image height).
+    pub lens_shift: f64,
+    /// Refine the projector focal length and vertical principal point.
+    pub refine_lens: bool,
+    /// Camera pixel stride when sampling correspondences.
+    pub sample_stride: u32,
+    /// Maximum correspondences used per projector.
+    pub max_samples: usize,
+    /// Epipolar inlier threshold in pixels.
+    pub inlier_threshold: f64,
+}
+
+impl Default for ReconstructionConfig {
+    fn default() -> Self {
+        Self {
+            throw_ratio: 1.5,
+            lens_shift: 0.0,
+            refine_lens: true,
+            sample_stride: 4,
+            max_samples: 20_000,
+            inlier_threshold: 2.0,
+        }
+    }
+}
+
+/// Calibrated projector.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectorGeometry {
+    pub projector_id: u32,
+    /// Projector lens.
+    pub intrinsics: CameraIntrinsics,
+    /// Camera frame -> projector frame.
+    pub pose: Pose,
+    /// Correspondences consistent with the epipolar geometry.
+    pub inlier_count: usize,
+    /// Mean triangulation reprojection error in pixels (camera and projector).
+    pub reprojection_error: f64,
+}
+
+/// Calibrated projectors and the triangulated surface.
+#[derive(Debug, Clone)]
+pub struct SurfaceReconstruction {
+    /// Camera lens (the camera is the reference frame).
+    pub camera: CameraIntrinsics,
+    pub projectors: Vec<ProjectorGeometry>,
+    /// Surface samples in the camera frame, at a common scale.
+    pub points: Vec<Vec3>,
+}
+
+/// One camera pixel -> projector pixel correspondence.
+#[derive(Debug, Clone, Copy)]
+struct Sample {
+    /// Camera pixel index (for matching samples across projectors).
+    index: usize,
+    /// Distorted camera pixel.
+    camera: [f64; 2],
+    /// Undistorted camera pixel.
+    ideal: [f64; 2],
+    projector: [f64; 2],
+}
+
+/// Calibrate every decoded projector against the camera and triangulate the surface.
+pub fn reconstruct_surface(
+    camera: &CameraIntrinsics,
+    projectors: &[ProjectorCalibration],
+    config: &ReconstructionConfig,
+) -> Result<SurfaceReconstruction, String> {
+    reconstruct_projectors(camera, projectors, config)
+}
+
+/// [`reconstruct_surface`] over any selection of projectors.
+pub(super) fn reconstruct_projectors<'a>(
+    camera: &CameraIntrinsics,
+    projectors: impl IntoIterator<Item = &'a ProjectorCalibration>,
+    config: &ReconstructionConfig,
+) -> Result<SurfaceReconstruction, String> {
+    let mut solved = Vec::new();
+    for projector in projectors {
+        let Some(correspondences) = &projector.correspondences else {
+            continue;
+        };
+        if (correspondences.camera_width, correspondences.camera_height) != (camera.width, camera.height) {
+            return Err(format!(
+                "Camera calibration is {}x{} but captures are {}x{}",
+                camera.width, camera.height, correspondences.camera_width, correspondences.camera_height
+            ));
+        }
+        let (geometry, points) = calibrate_projector(camera, projector, correspondences, config)
+            .map_err(|e| format!("Projector {}: {}", projector.projector_id + 1, e))?;
+        solved.push((geometry, points));
+    }
+    if solved.is_empty() {
+        return Err("No decoded projectors to reconstruct".to_string());
+    }
+
+    let scales = chain_scales(&solved);
+    let mut reconstruction = SurfaceReconstruction {
+        camera: *camera,
+        projectors: Vec::new(),
+        points: Vec::new(),
+    };
+    for ((mut geometry, points), scale) in solved.into_iter().zip(scales) {
+        geometry.pose.translation = linalg::scale(geometry.pose.translation, scale);
+        reconstruction
+            .points
+            .extend(points.into_iter().map(|(_, p)| linalg::scale(p, scale)));
+        reconstruction.projectors.push(geometry);
+    }
+    Ok(reconstruction)
+}
+
+/// Scale per projector so overlapping projectors agree on depth.
+///
+/// The projector with the most points keeps unit scale; the others are
+/// chained through the camera pixels they share with already-scaled ones.
+fn chain_scales(solved: &[(ProjectorGeometry, Vec<(usize, Vec3)>)]) -> Vec<f64> {
+    let depths: Vec<HashMap<usize, f64>> = solved
+        .iter()
+        .map(|(_, points)| points.iter().map(|(i, p)| (*i, linalg::norm(*p))).collect())
+        .collect();
+    let mut scales: Vec<Option<f64>> = vec![None; solved.len()];
+    let reference = (0..solved.len()).max_by_key(|&i| solved[i].1.len()).unwrap_or(0);
+    scales[reference] = Some(1.0);
+
+    loop {
+        // Unscaled projector with the largest overlap to a scaled one
+        let mut best: Option<(usize, usize, usize)> = None;
+        for k in (0..solved.len()).filter(|&k| scales[k].is_none()) {
+            for j in (0..solved.len()).filter(|&j| scales[j].is_some()) {
+                let overlap = depths[k].keys().filter(|i| depths[j].contains_key(i)).count();
+                if overlap >= MIN_SCALE_OVERLAP && best.is_none_or(|b| overlap > b.2) {
+                    best = Some((k, j, overlap));
+                }
+            }
+        }
+        let Some((k, j, _)) = best else {
+            break;
+        };
+        let mut ratios: Vec<f64> = depths[k]
+            .iter()
+            .filter_map(|(i, d)| depths[j].get(i).map(|dj| dj / d))
+            .collect();
+        let scale = linalg::percentile(&mut ratios, 0.5) * scales[j].unwrap_or(1.0);
+        log::info!(
+            "Projector {} scaled by {:.4} to match projector {}",
+            solved[k].0.projector_id + 1,
+            scale,
+            solved[j].0.projector_id + 1
+        );
+        scales[k] = Some(scale);
+    }
+
+    scales
+        .iter()
+        .zip(solved)
+        .map(|(scale, (geometry, _))| {
+            scale.unwrap_or_else(|| {
+                log::warn!(
+                    "Projector {} does not overlap the others; its scale is unknown",
+                    geometry.projector_id + 1
+                );
+                1.0
+            })
+        })
+        .collect()
+}
+
+/// Pose and lens of one projector with a unit baseline, plus its triangulated samples.
+fn calibrate_projector(
+    camera: &CameraIntrinsics,
+    projector: &ProjectorCalibration,
+    correspondences: &DecodedCorrespondences,
+    config: &ReconstructionConfig,
+) -> Result<(ProjectorGeometry, Vec<(usize, Vec3)>), String> {
+    let samples = sample_correspondences(camera, correspondences, config);
+    if samples.len() < MIN_SAMPLES {
+        return Err(format!(
+            "Not enough valid correspondences: {} (need at least {})",
+            samples.len(),
+            MIN_SAMPLES
+        ));
+    }
+
+    let guess = CameraIntrinsics::from_throw_ratio(
+        correspondences.projector_width,
+        correspondences.projector_height,
+        config.throw_ratio,
+        config.lens_shift,
+    );
+
+    // Dominant plane: the better half of the samples under the session homography
+    let homography = match &projector.homography {
+        Some(result) => result.matrix,
+        None => HomographyComputer::new().compute(correspondences)?.matrix,
+    };
+    let mut transfer: Vec<(f64, Sample)> = samples
+        .iter()
+        .map(|s| {
+            let (x, y) = HomographyComputer::transform_point(&homography, s.camera[0], s.camera[1]);
+            ((x - s.projector[0]).hypot(y - s.projector[1]), *s)
+        })
+        .filter(|(e, _)| e.is_finite())
+        .collect();
+    transfer.sort_by(|a, b| a.0.total_cmp(&b.0));
+    transfer.truncate((transfer.len() / 2).max(MIN_SAMPLES));
+    let planar: Vec<Sample> = transfer.into_iter().map(|(_, s)| s).collect();
+    let pairs: Vec<([f64; 2], [f64; 2])> = planar.iter().map(|s| (s.ideal, s.projector)).collect();
+    let plane = fit_point_homography(&pairs).ok_or("Failed to fit the surface homography")?;
+
+    // Near-planar surfaces fit every decomposition to within the inlier
+    // threshold, so candidates are ranked by their median epipolar error
+    let mut best: Option<(CameraIntrinsics, Pose, f64)> = None;
+    for initial in poses_from_homography(&plane, camera, &guess, &planar)? {
+        let (lens, pose) = refine_projector(camera, &guess, &guess, &initial, &planar, config);
+        let f = fundamental_from(camera, &lens, &pose);
+        let inliers: Vec<Sample> = samples
+            .iter()
+            .filter(|s| sampson(&f, s).abs() < config.inlier_threshold)
+            .copied()
+            .collect();
+        if inliers.len() < MIN_SAMPLES {
+            continue;
+        }
+        let (lens, pose) = refine_projector(camera, &guess, &lens, &pose, &inliers, config);
+        let f = fundamental_from(camera, &lens, &pose);
+        let center = pose.center();
+        let mut errors: Vec<f64> = samples
+            .iter()
+            .map(|s| match triangulate(camera, &lens, &pose, center, s.camera, s.projector) {
+                Some(_) => sampson(&f, s).abs(),
+                None => f64::INFINITY,
+            })
+            .collect();
+        let score = linalg::percentile(&mut errors, 0.5);
+        if best.as_ref().is_none_or(|b| score < b.2) {
+            best = Some((lens, pose, score));
+        }
+    }
+    let (intrinsics, pose, _) = best.ok_or("No projector pose agrees with the correspondences")?;
+
+    // Final inliers and triangulation
+    let fundamental = fundamental_from(camera, &intrinsics, &pose);
+    let center = pose.center();
+    let mut points = Vec::new();
+    let mut error_sum = 0.0;
+    for sample in samples
+        .iter()
+        .filter(|s| sampson(&fundamental, s).abs() < config.inlier_threshold)
+    {
+        let Some(point) = triangulate(camera, &intrinsics, &pose, center, sample.camera, sample.projector) else {
+            continue;
+        };
+        let (Some(c), Some(p)) = (camera.project(point), intrinsics.project(pose.transform(point))) else {
+            continue;
+        };
+        let error = ((c[0] - sample.camera[0]).hypot(c[1] - sample.camera[1])
+            + (p[0] - sample.projector[0]).hypot(p[1] - sample.projector[1]))
+            / 2.0;
+        error_sum += error;
+        points.push((sample.index, point));
+    }
+    if points.len() < MIN_SAMPLES {
+        return Err("Too few correspondences agree with the recovered geometry".to_string());
+    }
+
+    let reprojection_error = error_sum / points.len() as f64;
+    log::info!(
+        "Projector {} calibrated: f = {:.1}, c = ({:.1}, {:.1}), {} inliers, error {:.2}px",
+        projector.projector_id + 1,
+        intrinsics.fx,
+        intrinsics.cx,
+        intrinsics.cy,
+        points.len(),
+        reprojection_error
+    );
+
+    Ok((
+        ProjectorGeometry {
+            projector_id: projector.projector_id,
+            intrinsics,
+            pose,
+            inlier_count: points.len(),
+            reprojection_error,
+        },
+        points,
+    ))
+}
+
+/// Confident correspondences on a stride grid, capped at `max_samples`.
+fn sample_correspondences(
+    camera: &CameraIntrinsics,
+    corr: &DecodedCorrespondences,
+    config: &ReconstructionConfig,
+) -> Vec<Sample> {
+    let valid = corr.valid_count().max(1);
+    let min_stride = config.sample_stride.max(1) as usize;
+    let cap_stride = (valid as f64 / config.max_samples.max(1) as f64).sqrt().ceil() as usize;
+    let stride = min_stride.max(cap_stride);
+
+    let mut samples = Vec::new();
+    for y in (0..corr.camera_height as usize).step_by(stride) {
+        for x in (0..corr.camera_width as usize).step_by(stride) {
+            let index = y * corr.camera_width as usize + x;
+            if corr.confidence[index] <= 0.5 {
+                continue;
+            }
+            if let Some((px, py)) = corr.projector_position(index) {
+                let pixel = [x as f64, y as f64];
+                samples.push(Sample {
+                    index,
+                    camera: pixel,
+                    ideal: camera.undistort(pixel),
+                    projector: [px, py],
+                });
+            }
+        }
+    }
+    samples
+}
+
+/// Signed Sampson distance of a sample to the epipolar geometry, in pixels.
+fn sampson(f: &Mat3, sample: &Sample) -> f64 {
+    let c = [sample.ideal[0], sample.ideal[1], 1.0];
+    let p = [sample.projector[0], sample.projector[1], 1.0];
+    let fc = linalg::mat_vec(f, c);
+    let ftp = linalg::mat_vec(&linalg::transpose(f), p);
+    let error = linalg::dot(p, fc);
+    let denom = fc[0] * fc[0] + fc[1] * fc[1] + ftp[0] * ftp[0] + ftp[1] * ftp[1];
+    if denom < 1e-300 {
+        return f64::INFINITY;
+    }
+    error / denom.sqrt()
+}
+
+/// Fundamental matrix of a projector pose: `K_p^-T [t]x R K_c^-1`.
+fn fundamental_from(camera: &CameraIntrinsics, projector: &CameraIntrinsics, pose: &Pose) -> Mat3 {
+    let inverse = |k: &CameraIntrinsics| -> Mat3 {
+        [
+            [1.0 / k.fx, 0.0, -k.cx / k.fx],
+            [0.0, 1.0 / k.fy, -k.cy / k.fy],
+            [0.0, 0.0, 1.0],
+        ]
+    };
+    let essential = linalg::mat_mul(&linalg::skew(pose.translation), &pose.rotation);
+    linalg::mat_mul(
+        &linalg::mat_mul(&linalg::transpose(&inverse(projector)), &essential),
+        &inverse(camera),
+    )
+}
+
+/// Candidate poses (unit baseline) from a camera -> projector plane homography.
+///
+/// Decomposes `K_p^-1 H K_c = R + t n^T` (Ma, Soatto, Kosecka and Sastry,
+/// algorithm 5.2) and keeps the solutions with the plane in front of the
+/// camera.
+fn poses_from_homography(
+    homography: &Mat3,
+    camera: &CameraIntrinsics,
+    projector: &CameraIntrinsics,
+    samples: &[Sample],
+) -> Result<Vec<Pose>, String> {
+    let inverse_projector: Mat3 = [
+        [1.0 / projector.fx, 0.0, -projector.cx / projector.fx],
+        [0.0, 1.0 / projector.fy, -projector.cy / projector.fy],
+        [0.0, 0.0, 1.0],
+    ];
+    let mut h = linalg::mat_mul(&linalg::mat_mul(&inverse_projector, homography), &camera.matrix());
+    let (_, s, v) = linalg::svd3(&h);
+    if s[1] < 1e-12 {
+        return Err("Degenerate surface homography".to_string());
+    }
+    let rays: Vec<Vec3> = samples.iter().map(|s| camera.ray(s.camera)).collect();
+    let projected: Vec<Vec3> = samples.iter().map(|s| projector.ray(s.projector)).collect();
+    let orientation: f64 = rays.iter().zip(&projected).map(|(c, p)| linalg::dot(*p, linalg::mat_vec(&h, *c))).sum();
+    let sign = orientation.signum() / s[1];
+    h = h.map(|row| row.map(|x| x * sign));
+
+    let (s1, s3) = ((s[0] / s[1]).powi(2), (s[2] / s[1]).powi(2));
+    if s1 - s3 < 1e-9 {
+        return Err("The projector and camera share a center; the baseline is too small".to_string());
+    }
+    let column = |k: usize| [v[0][k], v[1][k], v[2][k]];
+    let (v1, v2, v3) = (column(0), column(1), column(2));
+    let a = ((1.0 - s3).max(0.0) / (s1 - s3)).sqrt();
+    let b = ((s1 - 1.0).max(0.0) / (s1 - s3)).sqrt();
+
+    let mut poses = Vec::new();
+    for u in [linalg::add(linalg::scale(v1, a), linalg::scale(v3, b)), linalg::sub(linalg::scale(v1, a), linalg::scale(v3, b))] {
+        let u_basis = [v2, u, linalg::cross(v2, u)];
+        let (hv2, hu) = (linalg::mat_vec(&h, v2), linalg::mat_vec(&h, u));
+        let w_basis = [hv2, hu, linalg::cross(hv2, hu)];
+        let rotation: Mat3 = std::array::from_fn(|r| std::array::from_fn(|c| (0..3).map(|k| w_basis[k][r] * u_basis[k][c]).sum()));
+        let normal = linalg::cross(v2, u);
+        let h_minus_r: Mat3 = std::array::from_fn(|r| std::array::from_fn(|c| h[r][c] - rotation[r][c]));
+        let translation = linalg::mat_vec(&h_minus_r, normal);
+        if linalg::norm(translation) < 1e-12 {
+            continue;
+        }
+        for flip in [1.0, -1.0] {
+            let normal = linalg::scale(normal, flip);
+            let in_front = rays.iter().filter(|r| linalg::dot(normal, **r) > 0.0).count();
+            if in_front * 2 > rays.len() {
+                poses.push(Pose {
+                    rotation: linalg::orthonormalize(&rotation),
+                    translation: linalg::normalize(linalg::scale(translation, flip)),
+                });
+            }
+        }
+    }
+    if poses.is_empty() {
+        return Err("Surface homography has no physical decomposition".to_string());
+    }
+    Ok(poses)
+}
+
+/// Joint Levenberg-Marquardt refinement of pose (unit baseline) and lens.
+///
+/// Starts from `start` and `initial`; the lens prior pulls toward `guess`.
+fn refine_projector(
+    camera: &CameraIntrinsics,
+    guess: &CameraIntrinsics,
+    start: &CameraIntrinsics,
+    initial: &Pose,
+    samples: &[Sample],
+    config: &ReconstructionConfig,
+) -> (CameraIntrinsics, Pose) {
+    let step = (samples.len() / 5000).max(1);
+    let subset: Vec<Sample> = samples.iter().step_by(step).copied().collect();
+    let prior = LENS_PRIOR_WEIGHT * (subset.len() as f64).sqrt();
+
+    let t = linalg::normalize(initial.translation);
+    let r = linalg::rotation_vector(&initial.rotation);
+    let mut params = vec![r[0], r[1], r[2], t[2].clamp(-1.0, 1.0).acos(), t[1].atan2(t[0])];
+    if config.refine_lens {
+        params.extend_from_slice(&[start.fx, start.cy]);
+    }
+
+    let unpack = |p: &[f64]| {
+        let (st, ct) = p[3].sin_cos();
+        let (sp, cp) = p[4].sin_cos();
+        let pose = Pose::from_parameters([p[0], p[1], p[2]], [st * cp, st * sp, ct]);
+        let mut lens = *start;
+        if p.len() > 5 {
+            lens.fx = p[5];
+            lens.fy = p[5];
+            lens.cy = p[6];
+        }
+        (lens, pose)
+    };
+
+    let (params, _) = linalg::levenberg_marquardt(params, LM_MAX_ITERS, |p| {
+        let (lens, pose) = unpack(p);
+        let f = fundamental_from(camera, &lens, &pose);
+        let mut residuals: Vec<f64> = subset
+            .iter()
+            .map(|s| {
+                let e = sampson(&f, s);
+                if e.is_finite() { e } else { 1e3 }
+            })
+            .collect();
+        if p.len() > 5 {
+            residuals.push(prior * (p[5] - guess.fx) / guess.fx);
+            residuals.push(prior * (p[6] - guess.cy) / guess.height as f64);
+        }
+        residuals
+    });
+
+    unpack(&params)
+}
+
+/// Midpoint triangulation of a camera pixel and a projector pixel.
+///
+/// Returns `None` when the rays are parallel or meet behind either device.
+fn triangulate(
+    camera: &CameraIntrinsics,
+    projector: &CameraIntrinsics,
+    pose: &Pose,
+    projector_center: Vec3,
+    camera_pixel: [f64; 2],
+    projector_pixel: [f64; 2],
+) -> Option<Vec3> {
+    let d_cam = camera.ray(camera_pixel);
+    let d_proj = linalg::mat_vec(&linalg::transpose(&pose.rotation), projector.ray(projector_pixel));
+    // Closest points of origin + s * d_cam and center + t * d_proj
+    let w0 = linalg::scale(projector_center, -1.0);
+    let b = linalg::dot(d_cam, d_proj);
+    let d = linalg::dot(d_cam, w0);
+    let e = linalg::dot(d_proj, w0);
+    let denom = 1.0 - b * b;
+    if denom < 1e-12 {
+        return None;
+    }
+    let s = (b * e - d) / denom;
+    let t = (e - b * d) / denom;
+    if s <= 0.0 || t <= 0.0 {
+        return None;
+    }
+    let on_camera = linalg::scale(d_cam, s);
+    let on_projector = linalg::add(projector_center, linalg::scale(d_proj, t));
+    Some(linalg::scale(linalg::add(on_camera, on_projector), 0.5))
+}
+
+impl SurfaceReconstruction {
+    pub fn projector(&self, projector_id: u32) -> Option<&ProjectorGeometry> {
+        self.projectors.iter().find(|p| p.projector_id == projector_id)
+    }
+
+    /// Surface point seen at a camera pixel and lit by a projector pixel.
+    pub fn triangulate(&self, projector_id: u32, camera_pixel: [f64; 2], projector_pixel: [f64; 2]) -> Option<Vec3> {
+        let geometry = self.projector(projector_id)?;
+        triangulate(
+            &self.camera,
+            &geometry.intrinsics,
+            &geometry.pose,
+            geometry.pose.center(),
+            camera_pixel,
+            projector_pixel,
+        )
+    }
+
+    /// Fit a content space to the reconstructed surface.
+    pub fn fit_content(&self, space: &ContentSpace) -> Result<ContentMapping, String> {
+        let mapping = ContentMapping::fit(space, &self.points)?;
+        log::info!("Content space fitted: {}", mapping.describe());
+        Ok(mapping)
+    }
+
+    /// Per-pixel content UV map for a projector.
+    ///
+    /// The correspondences are resampled to a projector grid every `spacing`
+    /// pixels, each node is triangulated and mapped into the content space,
+    /// and the grid is bilinearly upsampled to the projector resolution.
+    pub fn uv_map(
+        &self,
+        projector_id: u32,
+        correspondences: &DecodedCorrespondences,
+        mapping: &ContentMapping,
+        spacing: u32,
+    ) -> Option<UvMap> {
+        self.projector(projector_id)?;
+        let width = correspondences.projector_width.max(1);
+        let height = correspondences.projector_height.max(1);
+        let spacing = spacing.max(1);
+        let grid = correspondences.to_correspondence_map(projector_id, width / spacing + 1, height / spacing + 1);
+
+        let nodes: Vec<Option<[f64; 2]>> = (0..grid.rows)
+            .flat_map(|row| (0..grid.columns).map(move |col| (col, row)))
+            .map(|(col, row)| {
+                let camera = grid.get(col, row)?;
+                let projector = grid.node_position(col, row);
+                let point = self.triangulate(
+                    projector_id,
+                    [camera[0] as f64, camera[1] as f64],
+                    [projector[0] as f64, projector[1] as f64],
+                )?;
+                mapping.uv(point)
+            })
+            .collect();
+
+        Some(UvMap {
+            projector_id,
+            width,
+            height,
+            data: upsample_uv_grid(&nodes, grid.columns, grid.rows, width, height, mapping.wraps_u()),
+        })
+    }
+}
+
+/// Bilinear upsampling of a corner-aligned UV grid; NaN where a corner is missing.
+///
+/// With `wrap_u`, u is interpolated the short way around the 0/1 seam.
+pub(super) fn upsample_uv_grid(
+    nodes: &[Option<[f64; 2]>],
+    columns: u32,
+    rows: u32,
+    width: u32,
+    height: u32,
+    wrap_u: bool,
+) -> Vec<[f32; 2]> {
+    let max_col = (columns - 1) as f64;
+    let max_row = (rows - 1) as f64;
+    let node = |col: u32, row: u32| nodes[(row * columns + col) as usize];
+    let mut data = Vec::with_capacity((width * height) as usize);
+
+    for y in 0..height {
+        let gy = if height > 1 { y as f64 / (height - 1) as f64 * max_row } else { 0.0 };
+        let row = (gy.floor() as u32).min(rows.saturating_sub(2));
+        let fy = gy - row as f64;
+        for x in 0..width {
+            let gx = if width > 1 { x as f64 / (width - 1) as f64 * max_col } else { 0.0 };
+            let col = (gx.floor() as u32).min(columns.saturating_sub(2));
+            let fx = gx - col as f64;
+            let corners = (
+                node(col, row),
+                node((col + 1).min(columns - 1), row),
+                node(col, (row + 1).min(rows - 1)),
+                node((col + 1).min(columns - 1), (row + 1).min(rows - 1)),
+            );
+            let uv = match corners {
+                (Some(a), Some(mut b), Some(mut c), Some(mut d)) => {
+                    if wrap_u {
+                        for corner in [&mut b, &mut c, &mut d] {
+                            corner[0] -= (corner[0] - a[0]).round();
+                        }
+                    }
+                    let lerp = |p: [f64; 2], q: [f64; 2], t: f64| [p[0] + (q[0] - p[0]) * t, p[1] + (q[1] - p[1]) * t];
+                    let [u, v] = lerp(lerp(a, b, fx), lerp(c, d, fx), fy);
+                    let u = if wrap_u { u.rem_euclid(1.0) } else { u };
+                    [u as f32, v as f32]
+                }
+                _ => [f32::NAN, f32::NAN],
+            };
+            data.push(uv);
+        }
+    }
+    data
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SPHERE_CENTER: Vec3 = [0.0, 0.3, 2.5];
+    const SPHERE_RADIUS: f64 = 4.0;
+
+    /// Point where a ray from `origin` leaves the sphere (origin inside).
+    fn hit_sphere(origin: Vec3, direction: Vec3) -> Vec3 {
+        let oc = linalg::sub(origin, SPHERE_CENTER);
+        let b = linalg::dot(oc, direction);
+        let c = linalg::dot(oc, oc) - SPHERE_RADIUS * SPHERE_RADIUS;
+        let t = -b + (b * b - c).sqrt();
+        linalg::add(origin, linalg::scale(direction, t))
+    }
+
+    /// Pose of a projector with the given rotation vector and center.
+    fn placed(rotation: Vec3, center: Vec3) -> Pose {
+        let translation = linalg::mat_vec(&linalg::rodrigues(rotation), center);
+        Pose::from_parameters(rotation, linalg::scale(translation, -1.0))
+    }
+
+    /// Dome seen by the camera and lit by a projector with the given pose.
+    fn synth(
+        id: u32,
+        camera: &CameraIntrinsics,
+        lens: &CameraIntrinsics,
+        pose: &Pose,
+    ) -> ProjectorCalibration {
+        let mut corr = DecodedCorrespondences::new(camera.width, camera.height, lens.width, lens.height);
+        let mut sub_x = vec![0.0f32; corr.projector_x.len()];
+        let mut sub_y = vec![0.0f32; corr.projector_x.len()];
+        for y in 0..camera.height {
+            for x in 0..camera.width {
+                let idx = (y * camera.width + x) as usize;
+                let point = hit_sphere([0.0; 3], camera.ray([x as f64, y as f64]));
+                let Some([px, py]) = lens.project(pose.transform(point)) else {
+                    continue;
+                };
+                if px < 0.0 || py < 0.0 || px > (lens.width - 1) as f64 || py > (lens.height - 1) as f64 {
+                    continue;
+                }
+                corr.projector_x[idx] = px.round() as i32;
+                corr.projector_y[idx] = py.round() as i32;
+                sub_x[idx] = px as f32;
+                sub_y[idx] = py as f32;
+                corr.confidence[idx] = 1.0;
+                corr.valid_mask[idx] = true;
+            }
+        }
+        // A sprinkling of decoding errors
+        for idx in (0..corr.projector_x.len()).step_by(53).filter(|&i| corr.valid_mask[i]) {
+            sub_x[idx] = (idx % lens.width as usize) as f32;
+            sub_y[idx] = ((idx / 7) % lens.height as usize) as f32;
+        }
+        corr.subpixel_x = Some(sub_x);
+        corr.subpixel_y = Some(sub_y);
+
+        let mut calibration = ProjectorCalibration::new(id, lens.width, lens.height);
+        calibration.correspondences = Some(corr);
+        calibration
+    }
+
+    #[test]
+    fn test_dome_reconstruction_maps_to_equirectangular() {
+        let mut camera = CameraIntrinsics::new(320, 240, 200.0);
+        camera.k1 = -0.05;
+        let lens = CameraIntrinsics::from_throw_ratio(256, 160, 1.2, 0.1);
+        // Two projectors beside the camera, each turned toward its half of the view
+        let poses = [
+            (0, placed([0.05, 0.3, 0.0], [0.6, -0.1, 0.1])),
+            (1, placed([-0.05, -0.3, 0.02], [-0.5, 0.15, 0.0])),
+        ];
+        let projectors: Vec<_> = poses
+            .iter()
+            .map(|(id, pose)| synth(*id, &camera, &lens, pose))
+            .collect();
+
+        let config = ReconstructionConfig {
+            throw_ratio: 1.25,
+            lens_shift: 0.1,
+            ..ReconstructionConfig::default()
+        };
+        let reconstruction = reconstruct_surface(&camera, &projectors, &config).unwrap();
+        assert_eq!(reconstruction.projectors.len(), 2);
+        for geometry in &reconstruction.projectors {
+            assert!(geometry.reprojection_error < 0.5, "{:?}", geometry);
+            assert!((geometry.intrinsics.fx - lens.fx).abs() / lens.fx < 0.05, "{:?}", geometry);
+        }
+
+        let mapping = reconstruction.fit_content(&ContentSpace::Equirectangular).unwrap();
+        let truth = ContentMapping::Equirectangular {
+            center: SPHERE_CENTER,
+            radius: SPHERE_RADIUS,
+            basis: basis_for_test(),
+        };
+        for (projector, (id, pose)) in projectors.iter().zip(&poses) {
+            let map = reconstruction
+                .uv_map(*id, projector.correspondences.as_ref().unwrap(), &mapping, 16)
+                .unwrap();
+            assert_eq!((map.width, map.height), (256, 160));
+
+            let mut checked = 0;
+            for (py, px) in [(40u32, 60u32), (80, 128), (120, 200), (30, 220)] {
+                let uv = map.data[(py * 256 + px) as usize];
+                if uv[0].is_nan() {
+                    continue;
+                }
+                let ray = linalg::mat_vec(&linalg::transpose(&pose.rotation), lens.ray([px as f64, py as f64]));
+                let expected = truth.uv(hit_sphere(pose.center(), ray)).unwrap();
+                assert!((uv[0] as f64 - expected[0]).abs() < 0.01, "u {:?} vs {:?}", uv, expected);
+                assert!((uv[1] as f64 - expected[1]).abs() < 0.01, "v {:?} vs {:?}", uv, expected);
+                checked += 1;
+            }
+            assert!(checked >= 2);
+        }
+    }
+
+    fn basis_for_test() -> Mat3 {
+        // right, up (-Y), forward (+Z) in camera coordinates
+        [[1.0, 0.0, 0.0], [0.0, -1.0, 0.0], [0.0, 0.0, 1.0]]
+    }
+}
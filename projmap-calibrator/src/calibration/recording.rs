@@ -0,0 +1,448 @@
+//! Recorded capture runs for offline calibration.
+//!
+//! A capture run is a folder with a `manifest.json` and one image per captured
+//! pattern. Runs recorded by the calibrator use lossless 8-bit PNGs, but any
+//! format the `image` crate reads works, so captures taken with a DSLR can be
+//! decoded by writing a manifest for them by hand. Color images are reduced to
+//! their green channel, as with live NDI frames.
+//!
+//! Replaying a run pushes the images through a regular [`CalibrationSession`],
+//! so thresholds can be changed and the whole decode pipeline re-run without
+//! the projectors or camera.
+//!
+//! [`write_pattern_set`] writes the pattern images themselves, for projecting
+//! them from other software and capturing with any camera.
+
+use super::gray_code::{GrayCodeGenerator, PatternConfig, PatternDirection};
+use super::phase_shift::PhaseShiftConfig;
+use super::session::{CalibrationConfig, CalibrationSession, CurrentPattern, ProjectorCalibration};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::time::Duration;
+
+/// Manifest file name inside a capture folder.
+pub const MANIFEST_FILE: &str = "manifest.json";
+
+/// What a captured image shows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RecordedPattern {
+    White,
+    Black,
+    GrayCode {
+        direction: PatternDirection,
+        bit: u32,
+        inverted: bool,
+    },
+    PhaseShift {
+        direction: PatternDirection,
+        step: u32,
+    },
+}
+
+impl RecordedPattern {
+    /// Pattern description for what the session wants displayed.
+    ///
+    /// `None` for verification crosshairs, which are not part of a capture run.
+    fn from_current(pattern: &CurrentPattern) -> Option<Self> {
+        let recorded = match pattern {
+            CurrentPattern::White => RecordedPattern::White,
+            CurrentPattern::Black => RecordedPattern::Black,
+            CurrentPattern::Verification(_) => return None,
+            CurrentPattern::GrayCode(spec) => match &spec.phase {
+                Some(phase) => RecordedPattern::PhaseShift {
+                    direction: spec.direction,
+                    step: phase.step,
+                },
+                None => RecordedPattern::GrayCode {
+                    direction: spec.direction,
+                    bit: spec.bit_index,
+                    inverted: spec.inverted,
+                },
+            },
+        };
+        Some(recorded)
+    }
+
+    /// File name used when recording.
+    fn file_name(&self, projector_id: u32) -> String {
+        let axis = |direction: &PatternDirection| match direction {
+            PatternDirection::Horizontal => "h",
+            PatternDirection::Vertical => "v",
+        };
+        match self {
+            RecordedPattern::White => format!("projector{}_white.png", projector_id),
+            RecordedPattern::Black => format!("projector{}_black.png", projector_id),
+            RecordedPattern::GrayCode { direction, bit, inverted } => format!(
+                "projector{}_{}_bit{:02}_{}.png",
+                projector_id,
+                axis(direction),
+                bit,
+                if *inverted { "inv" } else { "pos" }
+            ),
+            RecordedPattern::PhaseShift { direction, step } => {
+                format!("projector{}_{}_phase{}.png", projector_id, axis(direction), step)
+            }
+        }
+    }
+}
+
+/// One captured image.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordedCapture {
+    /// Pattern shown by the projector.
+    pub pattern: RecordedPattern,
+    /// Image file, relative to the manifest.
+    pub file: String,
+}
+
+/// Captures for one projector.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordedProjector {
+    /// Projector ID.
+    pub id: u32,
+    /// Projector resolution.
+    pub width: u32,
+    pub height: u32,
+    /// Phase-shift settings, if fringes were captured.
+    #[serde(default)]
+    pub phase_shift: Option<PhaseShiftConfig>,
+    /// Captured images.
+    pub captures: Vec<RecordedCapture>,
+}
+
+/// Capture folder manifest.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CaptureManifest {
+    /// Projectors in capture order.
+    pub projectors: Vec<RecordedProjector>,
+}
+
+/// A loaded capture run.
+pub struct CaptureRun {
+    /// Manifest describing the captures.
+    pub manifest: CaptureManifest,
+    /// Camera frame width.
+    pub camera_width: u32,
+    /// Camera frame height.
+    pub camera_height: u32,
+    /// Grayscale frames, per projector, in manifest order.
+    frames: Vec<Vec<Vec<u8>>>,
+}
+
+impl CaptureRun {
+    /// Load a capture folder (manifest plus images).
+    pub fn load(dir: &Path) -> std::io::Result<Self> {
+        let contents = std::fs::read_to_string(dir.join(MANIFEST_FILE))?;
+        let manifest: CaptureManifest = serde_json::from_str(&contents)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+
+        let mut size: Option<(u32, u32)> = None;
+        let mut frames = Vec::with_capacity(manifest.projectors.len());
+        for projector in &manifest.projectors {
+            let mut projector_frames = Vec::with_capacity(projector.captures.len());
+            for capture in &projector.captures {
+                let image = image::open(dir.join(&capture.file))
+                    .map_err(|e| invalid_data(format!("{}: {}", capture.file, e)))?;
+                let frame_size = (image.width(), image.height());
+                if *size.get_or_insert(frame_size) != frame_size {
+                    return Err(invalid_data(format!(
+                        "{}: image is {}x{}, expected all captures to match the first",
+                        capture.file, frame_size.0, frame_size.1
+                    )));
+                }
+                let frame = match image {
+                    image::DynamicImage::ImageLuma8(gray) => gray.into_raw(),
+                    other => other.to_rgb8().pixels().map(|p| p.0[1]).collect(),
+                };
+                projector_frames.push(frame);
+            }
+            frames.push(projector_frames);
+        }
+
+        let (camera_width, camera_height) = size.ok_or_else(|| invalid_data("Capture run contains no images"))?;
+        log::info!(
+            "Loaded capture run with {} projector(s) at {}x{}",
+            manifest.projectors.len(),
+            camera_width,
+            camera_height
+        );
+
+        Ok(Self {
+            manifest,
+            camera_width,
+            camera_height,
+            frames,
+        })
+    }
+
+    /// Decode the run with the given thresholds.
+    ///
+    /// Timing settings are ignored; each image is submitted once. The returned
+    /// session holds the decoded correspondences and homographies, exactly as
+    /// after a live run.
+    pub fn replay(&self, config: &CalibrationConfig) -> Result<CalibrationSession, String> {
+        let mut session = CalibrationSession::new(CalibrationConfig {
+            settle_time: Duration::ZERO,
+            frames_to_average: 1,
+            camera_width: self.camera_width,
+            camera_height: self.camera_height,
+            ..config.clone()
+        });
+
+        // Pattern settings come from the recording, not the config
+        for projector in &self.manifest.projectors {
+            session.config.phase_shift = projector.phase_shift;
+            session.add_projector(projector.id, projector.width, projector.height);
+        }
+        session.start()?;
+
+        let mut current_projector = None;
+        loop {
+            // Decode as soon as a projector's captures are complete
+            while !session.process_calibration() {}
+            if session.state.is_error() {
+                return Err(session.state.to_string());
+            }
+            let Some(pattern) = session.current_pattern() else {
+                break;
+            };
+            let projector_id = session.current_projector_id().ok_or("No projector being captured")?;
+            if current_projector != Some(projector_id) {
+                current_projector = Some(projector_id);
+                log::info!("Replaying captures for projector {}", projector_id);
+            }
+
+            let wanted = RecordedPattern::from_current(&pattern).ok_or("Capture runs cannot be verified")?;
+            let frame = self
+                .frame(projector_id, &wanted)
+                .ok_or_else(|| format!("Projector {}: no capture for {:?}", projector_id, wanted))?;
+            session.update();
+            session.submit_frame(frame.to_vec(), self.camera_width, self.camera_height);
+        }
+
+        Ok(session)
+    }
+
+    /// Frame captured for a projector and pattern.
+    fn frame(&self, projector_id: u32, pattern: &RecordedPattern) -> Option<&[u8]> {
+        let index = self.manifest.projectors.iter().position(|p| p.id == projector_id)?;
+        let capture = self.manifest.projectors[index]
+            .captures
+            .iter()
+            .position(|c| c.pattern == *pattern)?;
+        Some(&self.frames[index][capture])
+    }
+}
+
+/// Write every capture held by a session to a folder, with its manifest.
+///
+/// Projectors without a complete set of captures are recorded as far as they
+/// got, which is enough to debug a failed run.
+pub fn record_session(session: &CalibrationSession, dir: &Path) -> std::io::Result<CaptureManifest> {
+    std::fs::create_dir_all(dir)?;
+    let (width, height) = (session.config.camera_width, session.config.camera_height);
+
+    let mut manifest = CaptureManifest {
+        projectors: Vec::with_capacity(session.projectors.len()),
+    };
+    for projector in &session.projectors {
+        let mut captures = Vec::new();
+        for (pattern, frame) in projector_frames(projector) {
+            if frame.len() != (width * height) as usize {
+                continue;
+            }
+            let file = pattern.file_name(projector.projector_id);
+            image::GrayImage::from_raw(width, height, frame.to_vec())
+                .ok_or_else(|| invalid_data("Frame size mismatch"))?
+                .save(dir.join(&file))
+                .map_err(std::io::Error::other)?;
+            captures.push(RecordedCapture { pattern, file });
+        }
+        manifest.projectors.push(RecordedProjector {
+            id: projector.projector_id,
+            width: projector.projector_width,
+            height: projector.projector_height,
+            phase_shift: projector.pattern_config.phase_shift,
+            captures,
+        });
+    }
+
+    let json = serde_json::to_string_pretty(&manifest).map_err(std::io::Error::other)?;
+    std::fs::write(dir.join(MANIFEST_FILE), json)?;
+    log::info!("Recorded capture run to {:?}", dir);
+    Ok(manifest)
+}
+
+/// Write the pattern images for a set of projectors, with a manifest.
+///
+/// `projectors` holds `(id, width, height)`. The manifest names each image
+/// after the pattern it shows, so the folder is itself a capture run of a
+/// camera that sees the projectors 1:1. Copying the manifest next to real
+/// photos saved under the same names gives a run that can be decoded.
+pub fn write_pattern_set(
+    dir: &Path,
+    projectors: &[(u32, u32, u32)],
+    phase_shift: Option<PhaseShiftConfig>,
+) -> std::io::Result<CaptureManifest> {
+    std::fs::create_dir_all(dir)?;
+
+    let mut manifest = CaptureManifest {
+        projectors: Vec::with_capacity(projectors.len()),
+    };
+    for &(id, width, height) in projectors {
+        let mut config = PatternConfig::new(width, height);
+        config.phase_shift = phase_shift;
+        let generator = GrayCodeGenerator::from_config(config);
+
+        let mut images = vec![
+            (CurrentPattern::White, generator.generate_white()),
+            (CurrentPattern::Black, generator.generate_black()),
+        ];
+        for spec in generator.config().pattern_sequence() {
+            let data = generator.generate_pattern(&spec);
+            images.push((CurrentPattern::GrayCode(spec), data));
+        }
+
+        let mut captures = Vec::with_capacity(images.len());
+        for (current, data) in images {
+            let Some(pattern) = RecordedPattern::from_current(&current) else {
+                continue;
+            };
+            let file = pattern.file_name(id);
+            image::GrayImage::from_raw(width, height, data)
+                .ok_or_else(|| invalid_data("Pattern size mismatch"))?
+                .save(dir.join(&file))
+                .map_err(std::io::Error::other)?;
+            captures.push(RecordedCapture { pattern, file });
+        }
+        manifest.projectors.push(RecordedProjector {
+            id,
+            width,
+            height,
+            phase_shift,
+            captures,
+        });
+    }
+
+    let json = serde_json::to_string_pretty(&manifest).map_err(std::io::Error::other)?;
+    std::fs::write(dir.join(MANIFEST_FILE), json)?;
+    log::info!("Wrote pattern set for {} projector(s) to {:?}", projectors.len(), dir);
+    Ok(manifest)
+}
+
+/// All captured frames of a projector, in projection order.
+fn projector_frames(projector: &ProjectorCalibration) -> Vec<(RecordedPattern, &[u8])> {
+    let mut frames: Vec<(RecordedPattern, &[u8])> = Vec::new();
+    if let Some(white) = &projector.white_reference {
+        frames.push((RecordedPattern::White, white));
+    }
+    if let Some(black) = &projector.black_reference {
+        frames.push((RecordedPattern::Black, black));
+    }
+    for (direction, pairs, phase) in [
+        (PatternDirection::Horizontal, &projector.horizontal_pairs, &projector.horizontal_phase),
+        (PatternDirection::Vertical, &projector.vertical_pairs, &projector.vertical_phase),
+    ] {
+        for (bit, pair) in pairs.iter().enumerate() {
+            for (inverted, frame) in [(false, &pair.positive), (true, &pair.inverted)] {
+                let pattern = RecordedPattern::GrayCode {
+                    direction,
+                    bit: bit as u32,
+                    inverted,
+                };
+                frames.push((pattern, frame));
+            }
+        }
+        for (step, frame) in phase.iter().enumerate() {
+            frames.push((RecordedPattern::PhaseShift { direction, step: step as u32 }, frame));
+        }
+    }
+    frames
+}
+
+fn invalid_data(message: impl Into<String>) -> std::io::Error {
+    std::io::Error::new(std::io::ErrorKind::InvalidData, message.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calibration::GrayCodeGenerator;
+
+    /// Folder with a hand-written manifest: camera sees the 64x32 projector 1:1
+    fn write_fixture(dir: &Path) {
+        std::fs::create_dir_all(dir).unwrap();
+        let generator = GrayCodeGenerator::new(64, 32);
+        let config = generator.config().clone();
+        let save = |name: &str, data: Vec<u8>| {
+            image::GrayImage::from_raw(64, 32, data).unwrap().save(dir.join(name)).unwrap();
+        };
+
+        let mut captures = vec![
+            RecordedCapture { pattern: RecordedPattern::White, file: "white.png".into() },
+            RecordedCapture { pattern: RecordedPattern::Black, file: "black.png".into() },
+        ];
+        save("white.png", generator.generate_white());
+        save("black.png", generator.generate_black());
+        for spec in config.pattern_sequence() {
+            let pattern = RecordedPattern::from_current(&CurrentPattern::GrayCode(spec.clone())).unwrap();
+            let file = pattern.file_name(5);
+            save(&file, generator.generate_pattern(&spec));
+            captures.push(RecordedCapture { pattern, file });
+        }
+
+        let manifest = CaptureManifest {
+            projectors: vec![RecordedProjector {
+                id: 5,
+                width: 64,
+                height: 32,
+                phase_shift: None,
+                captures,
+            }],
+        };
+        std::fs::write(dir.join(MANIFEST_FILE), serde_json::to_string(&manifest).unwrap()).unwrap();
+    }
+
+    #[test]
+    fn test_replay_and_record_round_trip() {
+        let dir = std::env::temp_dir().join(format!("projmap_capture_run_{}", std::process::id()));
+        write_fixture(&dir);
+
+        let run = CaptureRun::load(&dir).unwrap();
+        assert_eq!((run.camera_width, run.camera_height), (64, 32));
+        let session = run.replay(&CalibrationConfig::default()).unwrap();
+        assert!(session.state.is_complete(), "{}", session.state);
+
+        let decoded = session.projectors[0].correspondences.as_ref().unwrap();
+        for (x, y) in [(0, 0), (17, 9), (63, 31)] {
+            assert_eq!(decoded.get(x, y), Some((x as f32, y as f32)));
+        }
+
+        // Recording the replayed session reproduces the same captures
+        let copy = dir.join("copy");
+        let manifest = record_session(&session, &copy).unwrap();
+        assert_eq!(manifest.projectors[0].captures.len(), run.manifest.projectors[0].captures.len());
+        let again = CaptureRun::load(&copy).unwrap().replay(&CalibrationConfig::default()).unwrap();
+        assert_eq!(
+            again.projectors[0].correspondences.as_ref().unwrap().projector_x,
+            decoded.projector_x
+        );
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_missing_capture_is_reported() {
+        let dir = std::env::temp_dir().join(format!("projmap_capture_missing_{}", std::process::id()));
+        write_fixture(&dir);
+        let mut manifest: CaptureManifest =
+            serde_json::from_str(&std::fs::read_to_string(dir.join(MANIFEST_FILE)).unwrap()).unwrap();
+        manifest.projectors[0].captures.pop();
+        std::fs::write(dir.join(MANIFEST_FILE), serde_json::to_string(&manifest).unwrap()).unwrap();
+
+        let error = CaptureRun::load(&dir).unwrap().replay(&CalibrationConfig::default()).err().unwrap();
+        assert!(error.contains("no capture"), "{}", error);
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
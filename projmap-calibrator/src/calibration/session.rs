@@ -2,7 +2,12 @@
 
 use super::decoder::DecodedCorrespondences;
 use super::gray_code::{GrayCodeGenerator, PatternConfig, PatternDirection, PatternSpec};
-use super::homography::HomographyResult;
+use super::homography::{HomographyComputer, HomographyResult};
+use super::phase_shift::{self, PhaseShiftConfig};
+use super::verification::{VerificationConfig, VerificationPass, VerificationReport};
+use image::GrayImage;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
 use std::time::{Duration, Instant};
 
 /// Phase of the pattern capture process.
@@ -48,6 +53,12 @@ pub enum CalibrationState {
     ProjectorComplete { projector_id: u32 },
     /// All calibrations complete.
     Complete,
+    /// Projecting and capturing the verification crosshairs.
+    Verifying {
+        projector_id: u32,
+        phase: CapturePhase,
+        start_time: Instant,
+    },
     /// Error occurred.
     Error(String),
 }
@@ -79,13 +90,16 @@ impl std::fmt::Display for CalibrationState {
             CalibrationState::ComputingHomography { .. } => write!(f, "Computing Homography"),
             CalibrationState::ProjectorComplete { .. } => write!(f, "Projector Complete"),
             CalibrationState::Complete => write!(f, "Complete"),
+            CalibrationState::Verifying { projector_id, .. } => {
+                write!(f, "Verifying Projector {}", projector_id)
+            }
             CalibrationState::Error(msg) => write!(f, "Error: {}", msg),
         }
     }
 }
 
 /// Captured frame pair (positive and inverted).
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct CapturedPair {
     /// Positive pattern (bit = 1 → white).
     pub positive: Vec<u8>,
@@ -98,6 +112,7 @@ pub struct CapturedPair {
 }
 
 /// Per-projector calibration data.
+#[derive(Serialize, Deserialize)]
 pub struct ProjectorCalibration {
     /// Projector ID.
     pub projector_id: u32,
@@ -114,6 +129,10 @@ pub struct ProjectorCalibration {
     pub horizontal_pairs: Vec<CapturedPair>,
     /// Captured vertical patterns (X coordinate).
     pub vertical_pairs: Vec<CapturedPair>,
+    /// Captured horizontal fringe images (phase-shift mode).
+    pub horizontal_phase: Vec<Vec<u8>>,
+    /// Captured vertical fringe images (phase-shift mode).
+    pub vertical_phase: Vec<Vec<u8>>,
     /// Decoded correspondences.
     pub correspondences: Option<DecodedCorrespondences>,
     /// Computed homography.
@@ -131,6 +150,8 @@ impl ProjectorCalibration {
             black_reference: None,
             horizontal_pairs: Vec::new(),
             vertical_pairs: Vec::new(),
+            horizontal_phase: Vec::new(),
+            vertical_phase: Vec::new(),
             correspondences: None,
             homography: None,
         }
@@ -143,10 +164,22 @@ impl ProjectorCalibration {
     pub fn pattern_sequence(&self) -> Vec<PatternSpec> {
         self.pattern_config.pattern_sequence()
     }
+
+    /// Drop captured frames and everything computed from them.
+    fn clear_captures(&mut self) {
+        self.white_reference = None;
+        self.black_reference = None;
+        self.horizontal_pairs.clear();
+        self.vertical_pairs.clear();
+        self.horizontal_phase.clear();
+        self.vertical_phase.clear();
+        self.correspondences = None;
+        self.homography = None;
+    }
 }
 
 /// Configuration for calibration timing.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CalibrationConfig {
     /// Time to wait for projector to display pattern.
     pub settle_time: Duration,
@@ -158,6 +191,8 @@ pub struct CalibrationConfig {
     pub camera_width: u32,
     /// Camera frame height.
     pub camera_height: u32,
+    /// Add phase-shift fringes for sub-pixel correspondences.
+    pub phase_shift: Option<PhaseShiftConfig>,
 }
 
 impl Default for CalibrationConfig {
@@ -168,24 +203,51 @@ impl Default for CalibrationConfig {
             contrast_threshold: 0.1,
             camera_width: 1920,
             camera_height: 1080,
+            phase_shift: None,
         }
     }
 }
 
+/// Captures of every projector from an additional camera.
+///
+/// Additional cameras see the same patterns as the primary camera, at the
+/// same time, and are decoded the same way. Projectors a camera cannot see
+/// simply end up with few or no valid correspondences.
+#[derive(Serialize, Deserialize)]
+pub struct CameraCaptures {
+    /// Camera source name.
+    pub name: String,
+    /// Camera frame width.
+    pub width: u32,
+    /// Camera frame height.
+    pub height: u32,
+    /// Per-projector captures, in the same order as the session's projectors.
+    pub projectors: Vec<ProjectorCalibration>,
+    /// Frames accumulated for the current pattern.
+    #[serde(skip)]
+    accumulated_frames: Vec<Vec<u8>>,
+}
+
 /// Manages the calibration session workflow.
 pub struct CalibrationSession {
     /// Current state.
     pub state: CalibrationState,
     /// Configuration.
     pub config: CalibrationConfig,
-    /// Per-projector calibration data.
+    /// Per-projector calibration data (primary camera).
     pub projectors: Vec<ProjectorCalibration>,
+    /// Additional cameras (camera 1 onward).
+    pub extra_cameras: Vec<CameraCaptures>,
     /// Current projector index.
     current_projector: usize,
     /// Accumulated frames for averaging.
     accumulated_frames: Vec<Vec<u8>>,
     /// Current pattern spec being captured.
     current_pattern: Option<PatternSpec>,
+    /// Verification pass in progress.
+    verification: Option<VerificationPass>,
+    /// Result of the last verification pass.
+    pub verification_report: Option<VerificationReport>,
 }
 
 impl CalibrationSession {
@@ -194,15 +256,80 @@ impl CalibrationSession {
             state: CalibrationState::Idle,
             config,
             projectors: Vec::new(),
+            extra_cameras: Vec::new(),
             current_projector: 0,
             accumulated_frames: Vec::new(),
             current_pattern: None,
+            verification: None,
+            verification_report: None,
         }
     }
 
+    /// Rebuild a session from saved calibration data.
+    ///
+    /// The session is complete when every projector has correspondences,
+    /// otherwise idle so the capture can be resumed.
+    pub fn restore(
+        config: CalibrationConfig,
+        projectors: Vec<ProjectorCalibration>,
+        extra_cameras: Vec<CameraCaptures>,
+    ) -> Self {
+        let mut session = Self::new(config);
+        session.projectors = projectors;
+        session.extra_cameras = extra_cameras;
+        if !session.projectors.is_empty() && session.projectors.iter().all(|p| p.correspondences.is_some()) {
+            session.current_projector = session.projectors.len();
+            session.state = CalibrationState::Complete;
+        }
+        session
+    }
+
     /// Add a projector to calibrate.
     pub fn add_projector(&mut self, id: u32, width: u32, height: u32) {
-        self.projectors.push(ProjectorCalibration::new(id, width, height));
+        let mut projector = ProjectorCalibration::new(id, width, height);
+        projector.pattern_config.phase_shift = self.config.phase_shift;
+        self.projectors.push(projector);
+        for camera in &mut self.extra_cameras {
+            let mut projector = ProjectorCalibration::new(id, width, height);
+            projector.pattern_config.phase_shift = self.config.phase_shift;
+            camera.projectors.push(projector);
+        }
+    }
+
+    /// Add a camera that captures alongside the primary one.
+    ///
+    /// Returns the camera index to submit its frames with.
+    pub fn add_camera(&mut self, name: &str, width: u32, height: u32) -> usize {
+        let projectors = self
+            .projectors
+            .iter()
+            .map(|p| {
+                let mut projector = ProjectorCalibration::new(p.projector_id, p.projector_width, p.projector_height);
+                projector.pattern_config.phase_shift = p.pattern_config.phase_shift;
+                projector
+            })
+            .collect();
+        self.extra_cameras.push(CameraCaptures {
+            name: name.to_string(),
+            width,
+            height,
+            projectors,
+            accumulated_frames: Vec::new(),
+        });
+        self.extra_cameras.len()
+    }
+
+    /// Number of cameras, including the primary one.
+    pub fn camera_count(&self) -> usize {
+        1 + self.extra_cameras.len()
+    }
+
+    /// Per-projector calibration data seen by a camera (0 is the primary camera).
+    pub fn camera_projectors(&self, camera: usize) -> Option<&[ProjectorCalibration]> {
+        match camera {
+            0 => Some(&self.projectors),
+            _ => self.extra_cameras.get(camera - 1).map(|c| c.projectors.as_slice()),
+        }
     }
 
     /// Start the calibration process.
@@ -210,8 +337,12 @@ impl CalibrationSession {
         if self.projectors.is_empty() {
             return Err("No projectors configured".to_string());
         }
+        if let Some(phase) = &self.config.phase_shift {
+            phase.validate()?;
+        }
 
         self.current_projector = 0;
+        self.verification_report = None;
         self.state = CalibrationState::WhiteReference {
             projector_id: self.projectors[0].projector_id,
             phase: CapturePhase::DisplayingPattern,
@@ -222,14 +353,129 @@ impl CalibrationSession {
         Ok(())
     }
 
+    /// Continue capturing at the first projector without correspondences.
+    ///
+    /// That projector's partial captures are discarded; projectors already
+    /// decoded keep their data. Additional cameras must be reconnected in the
+    /// order they were added.
+    pub fn resume(&mut self) -> Result<(), String> {
+        let index = self
+            .projectors
+            .iter()
+            .position(|p| p.correspondences.is_none())
+            .ok_or("Every projector is already calibrated")?;
+
+        self.projectors[index].clear_captures();
+        for camera in &mut self.extra_cameras {
+            camera.accumulated_frames.clear();
+            if let Some(projector) = camera.projectors.get_mut(index) {
+                projector.clear_captures();
+            }
+        }
+        self.accumulated_frames.clear();
+        self.current_projector = index;
+        self.verification_report = None;
+
+        let projector_id = self.projectors[index].projector_id;
+        self.state = CalibrationState::WhiteReference {
+            projector_id,
+            phase: CapturePhase::DisplayingPattern,
+            start_time: Instant::now(),
+        };
+        log::info!("Resuming calibration at projector {}", projector_id);
+        Ok(())
+    }
+
+    /// Decode every projector's captures again and recompute the homographies,
+    /// e.g. after changing the contrast threshold.
+    pub fn resolve(&mut self) -> Result<(), String> {
+        if self.projectors.is_empty() {
+            return Err("No projectors configured".to_string());
+        }
+        let decoded = self
+            .projectors
+            .iter()
+            .map(|p| {
+                Self::decode_patterns_for(&self.config, p).map_err(|e| format!("Projector {}: {}", p.projector_id, e))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        for (projector, correspondences) in self.projectors.iter_mut().zip(decoded) {
+            projector.homography = match HomographyComputer::new().compute(&correspondences) {
+                Ok(result) => Some(result),
+                Err(e) => {
+                    log::warn!("Projector {}: homography failed: {}", projector.projector_id, e);
+                    None
+                }
+            };
+            projector.correspondences = Some(correspondences);
+        }
+
+        // Additional cameras that fail to decode just don't see the projector
+        for camera in &mut self.extra_cameras {
+            let config = CalibrationConfig {
+                camera_width: camera.width,
+                camera_height: camera.height,
+                ..self.config.clone()
+            };
+            for projector in &mut camera.projectors {
+                projector.correspondences = Self::decode_patterns_for(&config, projector).ok();
+                projector.homography = projector
+                    .correspondences
+                    .as_ref()
+                    .and_then(|c| HomographyComputer::new().compute(c).ok());
+            }
+        }
+
+        self.current_projector = self.projectors.len();
+        self.verification_report = None;
+        self.state = CalibrationState::Complete;
+        log::info!("Re-solved {} projector(s)", self.projectors.len());
+        Ok(())
+    }
+
     /// Cancel the calibration.
     pub fn cancel(&mut self) {
         self.state = CalibrationState::Idle;
         self.accumulated_frames.clear();
+        for camera in &mut self.extra_cameras {
+            camera.accumulated_frames.clear();
+        }
         self.current_pattern = None;
+        self.verification = None;
         log::info!("Calibration cancelled");
     }
 
+    /// Project crosshairs through the computed mapping and measure where they land.
+    ///
+    /// Needs a completed calibration; the session returns to `Complete` with
+    /// a [`VerificationReport`] once every projector has been captured.
+    pub fn start_verification(&mut self, config: VerificationConfig) -> Result<(), String> {
+        if !matches!(self.state, CalibrationState::Complete) {
+            return Err("Verification needs a completed calibration".to_string());
+        }
+        let pass = VerificationPass::new(
+            config,
+            &self.projectors,
+            self.config.camera_width,
+            self.config.camera_height,
+        )?;
+
+        self.accumulated_frames.clear();
+        for camera in &mut self.extra_cameras {
+            camera.accumulated_frames.clear();
+        }
+        self.verification = Some(pass);
+        self.state = CalibrationState::Verifying {
+            projector_id: self.projectors[0].projector_id,
+            phase: CapturePhase::DisplayingPattern,
+            start_time: Instant::now(),
+        };
+
+        log::info!("Starting verification for {} projector(s)", self.projectors.len());
+        Ok(())
+    }
+
     /// Get current pattern to display (returns None if not in pattern projection state).
     pub fn current_pattern(&self) -> Option<CurrentPattern> {
         match &self.state {
@@ -243,6 +489,24 @@ impl CalibrationSession {
                     None
                 }
             }
+            CalibrationState::Verifying { .. } => {
+                let pass = self.verification.as_ref()?;
+                pass.pattern(pass.current()).map(CurrentPattern::Verification)
+            }
+            _ => None,
+        }
+    }
+
+    /// Projector currently being captured or processed.
+    pub fn current_projector_id(&self) -> Option<u32> {
+        match &self.state {
+            CalibrationState::WhiteReference { projector_id, .. }
+            | CalibrationState::BlackReference { projector_id, .. }
+            | CalibrationState::ProjectingPattern { projector_id, .. }
+            | CalibrationState::Decoding { projector_id }
+            | CalibrationState::ComputingHomography { projector_id }
+            | CalibrationState::ProjectorComplete { projector_id }
+            | CalibrationState::Verifying { projector_id, .. } => Some(*projector_id),
             _ => None,
         }
     }
@@ -252,6 +516,9 @@ impl CalibrationSession {
         if self.projectors.is_empty() {
             return 0.0;
         }
+        if let (CalibrationState::Verifying { .. }, Some(pass)) = (&self.state, &self.verification) {
+            return pass.current() as f32 / pass.len().max(1) as f32;
+        }
 
         let patterns_per_projector = self.projectors[0].total_patterns() + 2; // +2 for white/black
         let total = patterns_per_projector * self.projectors.len();
@@ -265,9 +532,9 @@ impl CalibrationSession {
             CalibrationState::Decoding { .. } | CalibrationState::ComputingHomography { .. } => {
                 patterns_per_projector
             }
-            CalibrationState::ProjectorComplete { .. } | CalibrationState::Complete => {
-                patterns_per_projector
-            }
+            CalibrationState::ProjectorComplete { .. }
+            | CalibrationState::Complete
+            | CalibrationState::Verifying { .. } => patterns_per_projector,
             CalibrationState::Error(_) => 0,
         };
 
@@ -279,6 +546,17 @@ impl CalibrationSession {
         let now = Instant::now();
 
         match &self.state {
+            CalibrationState::Verifying {
+                phase: CapturePhase::DisplayingPattern,
+                start_time,
+                projector_id,
+            } if now.duration_since(*start_time) >= self.config.settle_time => {
+                self.state = CalibrationState::Verifying {
+                    projector_id: *projector_id,
+                    phase: CapturePhase::WaitingForCapture,
+                    start_time: now,
+                };
+            }
             CalibrationState::WhiteReference { phase, start_time, projector_id }
             | CalibrationState::BlackReference { phase, start_time, projector_id } => {
                 let projector_id = *projector_id;
@@ -331,7 +609,7 @@ impl CalibrationSession {
         }
     }
 
-    /// Submit a captured camera frame.
+    /// Submit a captured frame from the primary camera.
     pub fn submit_frame(&mut self, frame: Vec<u8>, width: u32, height: u32) {
         // Update camera dimensions if different
         if width != self.config.camera_width || height != self.config.camera_height {
@@ -339,116 +617,197 @@ impl CalibrationSession {
             self.config.camera_height = height;
         }
 
+        if self.is_capturing() && self.accumulated_frames.len() < self.config.frames_to_average {
+            self.accumulated_frames.push(frame);
+        }
+        self.advance_capture();
+    }
+
+    /// Submit a captured frame from a camera (0 is the primary camera).
+    pub fn submit_camera_frame(&mut self, camera: usize, frame: Vec<u8>, width: u32, height: u32) {
+        if camera == 0 {
+            self.submit_frame(frame, width, height);
+            return;
+        }
+        let capturing = self.is_capturing();
+        let frames_to_average = self.config.frames_to_average;
+        let Some(captures) = self.extra_cameras.get_mut(camera - 1) else {
+            return;
+        };
+        captures.width = width;
+        captures.height = height;
+        if capturing && captures.accumulated_frames.len() < frames_to_average {
+            captures.accumulated_frames.push(frame);
+        }
+        self.advance_capture();
+    }
+
+    /// Whether the current state is waiting for camera frames.
+    fn is_capturing(&self) -> bool {
         match &self.state {
-            CalibrationState::WhiteReference {
-                phase,
-                projector_id,
-                ..
-            } if *phase != CapturePhase::DisplayingPattern => {
-                let projector_id = *projector_id;
-                self.accumulated_frames.push(frame);
+            CalibrationState::WhiteReference { phase, .. }
+            | CalibrationState::BlackReference { phase, .. }
+            | CalibrationState::ProjectingPattern { phase, .. }
+            | CalibrationState::Verifying { phase, .. } => *phase != CapturePhase::DisplayingPattern,
+            _ => false,
+        }
+    }
+
+    /// Store the averaged captures and move on once every camera has its frames.
+    fn advance_capture(&mut self) {
+        let frames_to_average = self.config.frames_to_average;
+        if !self.is_capturing()
+            || self.accumulated_frames.len() < frames_to_average
+            || self
+                .extra_cameras
+                .iter()
+                .any(|c| c.accumulated_frames.len() < frames_to_average)
+        {
+            return;
+        }
+        if matches!(self.state, CalibrationState::Verifying { .. }) {
+            self.advance_verification();
+            return;
+        }
 
-                if self.accumulated_frames.len() >= self.config.frames_to_average {
-                    let averaged = self.average_frames();
-                    if let Some(proj) = self.projectors.get_mut(self.current_projector) {
+        let index = self.current_projector;
+        let primary = (
+            Self::average_frames(&std::mem::take(&mut self.accumulated_frames)),
+            self.config.camera_width,
+            self.config.camera_height,
+        );
+        let mut captures = vec![(self.projectors.get_mut(index), primary)];
+        for camera in &mut self.extra_cameras {
+            let averaged = Self::average_frames(&std::mem::take(&mut camera.accumulated_frames));
+            captures.push((camera.projectors.get_mut(index), (averaged, camera.width, camera.height)));
+        }
+
+        match self.state.clone() {
+            CalibrationState::WhiteReference { projector_id, .. } => {
+                for (projector, (averaged, _, _)) in captures {
+                    if let Some(proj) = projector {
                         proj.white_reference = Some(averaged);
                     }
-                    self.accumulated_frames.clear();
-
-                    // Move to black reference
-                    self.state = CalibrationState::BlackReference {
-                        projector_id,
-                        phase: CapturePhase::DisplayingPattern,
-                        start_time: Instant::now(),
-                    };
                 }
-            }
-            CalibrationState::BlackReference {
-                phase,
-                projector_id,
-                ..
-            } if *phase != CapturePhase::DisplayingPattern => {
-                let projector_id = *projector_id;
-                self.accumulated_frames.push(frame);
 
-                if self.accumulated_frames.len() >= self.config.frames_to_average {
-                    let averaged = self.average_frames();
-                    if let Some(proj) = self.projectors.get_mut(self.current_projector) {
+                // Move to black reference
+                self.state = CalibrationState::BlackReference {
+                    projector_id,
+                    phase: CapturePhase::DisplayingPattern,
+                    start_time: Instant::now(),
+                };
+            }
+            CalibrationState::BlackReference { projector_id, .. } => {
+                for (projector, (averaged, _, _)) in captures {
+                    if let Some(proj) = projector {
                         proj.black_reference = Some(averaged);
                     }
-                    self.accumulated_frames.clear();
+                }
 
-                    // Start pattern projection
-                    let patterns = if let Some(proj) = self.projectors.get(self.current_projector) {
-                        proj.pattern_sequence()
-                    } else {
-                        Vec::new()
-                    };
+                // Start pattern projection
+                let patterns = if let Some(proj) = self.projectors.get(index) {
+                    proj.pattern_sequence()
+                } else {
+                    Vec::new()
+                };
 
-                    if let Some(first_pattern) = patterns.first() {
-                        self.current_pattern = Some(first_pattern.clone());
-                        self.state = CalibrationState::ProjectingPattern {
-                            projector_id,
-                            pattern_index: 0,
-                            phase: CapturePhase::DisplayingPattern,
-                            start_time: Instant::now(),
-                        };
-                    }
+                if let Some(first_pattern) = patterns.first() {
+                    self.current_pattern = Some(first_pattern.clone());
+                    self.state = CalibrationState::ProjectingPattern {
+                        projector_id,
+                        pattern_index: 0,
+                        phase: CapturePhase::DisplayingPattern,
+                        start_time: Instant::now(),
+                    };
                 }
             }
             CalibrationState::ProjectingPattern {
-                phase,
                 projector_id,
                 pattern_index,
                 ..
-            } if *phase != CapturePhase::DisplayingPattern => {
-                let projector_id = *projector_id;
-                let pattern_index = *pattern_index;
-                self.accumulated_frames.push(frame);
-
-                if self.accumulated_frames.len() >= self.config.frames_to_average {
-                    let averaged = self.average_frames();
-                    self.store_pattern_capture(pattern_index, averaged);
-                    self.accumulated_frames.clear();
-
-                    // Move to next pattern or complete
-                    let patterns = if let Some(proj) = self.projectors.get(self.current_projector) {
-                        proj.pattern_sequence()
-                    } else {
-                        Vec::new()
-                    };
-
-                    let next_index = pattern_index + 1;
-                    if next_index < patterns.len() {
-                        self.current_pattern = patterns.get(next_index).cloned();
-                        self.state = CalibrationState::ProjectingPattern {
-                            projector_id,
-                            pattern_index: next_index,
-                            phase: CapturePhase::DisplayingPattern,
-                            start_time: Instant::now(),
-                        };
-                    } else {
-                        // All patterns captured, start decoding
-                        self.state = CalibrationState::Decoding { projector_id };
+            } => {
+                for (projector, (averaged, width, height)) in captures {
+                    if let Some(proj) = projector {
+                        Self::store_pattern_capture(proj, pattern_index, averaged, width, height);
                     }
                 }
+
+                // Move to next pattern or complete
+                let patterns = if let Some(proj) = self.projectors.get(index) {
+                    proj.pattern_sequence()
+                } else {
+                    Vec::new()
+                };
+
+                let next_index = pattern_index + 1;
+                if next_index < patterns.len() {
+                    self.current_pattern = patterns.get(next_index).cloned();
+                    self.state = CalibrationState::ProjectingPattern {
+                        projector_id,
+                        pattern_index: next_index,
+                        phase: CapturePhase::DisplayingPattern,
+                        start_time: Instant::now(),
+                    };
+                } else {
+                    // All patterns captured, start decoding
+                    self.state = CalibrationState::Decoding { projector_id };
+                }
             }
             _ => {}
         }
     }
 
+    /// Store the verification capture and move to the next projector, or
+    /// measure all captures once the last one is in.
+    ///
+    /// Verification is measured in the primary camera only; frames from
+    /// additional cameras are dropped.
+    fn advance_verification(&mut self) {
+        let averaged = Self::average_frames(&std::mem::take(&mut self.accumulated_frames));
+        for camera in &mut self.extra_cameras {
+            camera.accumulated_frames.clear();
+        }
+        let Some(pass) = &mut self.verification else {
+            return;
+        };
+        pass.store(averaged);
+
+        if pass.is_done() {
+            let report = pass.report(&self.projectors, self.config.contrast_threshold);
+            for projector in &report.projectors {
+                log::info!(
+                    "Projector {}: {}/{} crosshairs, {:.2}px RMS, {:.2}px max",
+                    projector.projector_id,
+                    projector.targets_found,
+                    projector.targets_expected,
+                    projector.rms_error,
+                    projector.max_error
+                );
+            }
+            self.verification_report = Some(report);
+            self.verification = None;
+            self.state = CalibrationState::Complete;
+        } else if let Some(projector) = self.projectors.get(pass.current()) {
+            self.state = CalibrationState::Verifying {
+                projector_id: projector.projector_id,
+                phase: CapturePhase::DisplayingPattern,
+                start_time: Instant::now(),
+            };
+        }
+    }
+
     /// Average accumulated frames.
-    fn average_frames(&self) -> Vec<u8> {
-        if self.accumulated_frames.is_empty() {
+    fn average_frames(frames: &[Vec<u8>]) -> Vec<u8> {
+        if frames.is_empty() {
             return Vec::new();
         }
 
-        let len = self.accumulated_frames[0].len();
-        let count = self.accumulated_frames.len() as u32;
+        let len = frames[0].len();
+        let count = frames.len() as u32;
         let mut result = vec![0u8; len];
 
         for i in 0..len {
-            let sum: u32 = self.accumulated_frames.iter().map(|f| f[i] as u32).sum();
+            let sum: u32 = frames.iter().map(|f| f[i] as u32).sum();
             result[i] = (sum / count) as u8;
         }
 
@@ -456,20 +815,34 @@ impl CalibrationSession {
     }
 
     /// Store a captured pattern.
-    fn store_pattern_capture(&mut self, pattern_index: usize, data: Vec<u8>) {
-        let projector = match self.projectors.get_mut(self.current_projector) {
-            Some(p) => p,
-            None => return,
-        };
-
+    fn store_pattern_capture(
+        projector: &mut ProjectorCalibration,
+        pattern_index: usize,
+        data: Vec<u8>,
+        width: u32,
+        height: u32,
+    ) {
         let patterns = projector.pattern_sequence();
         let spec = match patterns.get(pattern_index) {
             Some(s) => s,
             None => return,
         };
 
-        let pair_index = pattern_index / 2;
-        let is_positive = pattern_index % 2 == 0;
+        if spec.phase.is_some() {
+            match spec.direction {
+                PatternDirection::Horizontal => projector.horizontal_phase.push(data),
+                PatternDirection::Vertical => projector.vertical_phase.push(data),
+            }
+            return;
+        }
+
+        // Pairs are numbered per direction
+        let pair_index = patterns[..pattern_index]
+            .iter()
+            .filter(|p| p.direction == spec.direction && p.phase.is_none())
+            .count()
+            / 2;
+        let is_positive = !spec.inverted;
 
         let pairs = match spec.direction {
             PatternDirection::Horizontal => &mut projector.horizontal_pairs,
@@ -481,8 +854,8 @@ impl CalibrationSession {
             pairs.push(CapturedPair {
                 positive: Vec::new(),
                 inverted: Vec::new(),
-                width: self.config.camera_width,
-                height: self.config.camera_height,
+                width,
+                height,
             });
         }
 
@@ -515,6 +888,21 @@ impl CalibrationSession {
                         }
                     }
                 }
+
+                // Additional cameras that fail to decode just don't see this projector
+                for camera in &mut self.extra_cameras {
+                    let config = CalibrationConfig {
+                        camera_width: camera.width,
+                        camera_height: camera.height,
+                        ..config.clone()
+                    };
+                    if let Some(projector) = camera.projectors.get_mut(self.current_projector) {
+                        match Self::decode_patterns_for(&config, projector) {
+                            Ok(correspondences) => projector.correspondences = Some(correspondences),
+                            Err(e) => log::warn!("Camera {}: decoding failed: {}", camera.name, e),
+                        }
+                    }
+                }
                 false
             }
             CalibrationState::ComputingHomography { projector_id } => {
@@ -522,32 +910,29 @@ impl CalibrationSession {
                 log::info!("Computing homography for projector {}", projector_id);
 
                 if let Some(projector) = self.projectors.get_mut(self.current_projector) {
-                    #[cfg(feature = "opencv")]
-                    {
-                        use super::homography::HomographyComputer;
-
-                        if let Some(ref correspondences) = projector.correspondences {
-                            let computer = HomographyComputer::new();
-                            match computer.compute(correspondences) {
-                                Ok(result) => {
-                                    log::info!(
-                                        "Homography computed: {} inliers, {:.2}px error",
-                                        result.inlier_count,
-                                        result.reprojection_error
-                                    );
-                                    projector.homography = Some(result);
-                                }
-                                Err(e) => {
-                                    log::error!("Homography computation failed: {}", e);
-                                    // Continue anyway, can still use correspondences for mesh warp
-                                }
+                    if let Some(ref correspondences) = projector.correspondences {
+                        let computer = HomographyComputer::new();
+                        match computer.compute(correspondences) {
+                            Ok(result) => {
+                                log::info!(
+                                    "Homography computed: {} inliers, {:.2}px error",
+                                    result.inlier_count,
+                                    result.reprojection_error
+                                );
+                                projector.homography = Some(result);
+                            }
+                            Err(e) => {
+                                log::error!("Homography computation failed: {}", e);
+                                // Continue anyway, can still use correspondences for mesh warp
                             }
                         }
                     }
-
-                    #[cfg(not(feature = "opencv"))]
-                    {
-                        log::warn!("OpenCV not available, skipping homography computation");
+                }
+                for camera in &mut self.extra_cameras {
+                    if let Some(projector) = camera.projectors.get_mut(self.current_projector) {
+                        if let Some(ref correspondences) = projector.correspondences {
+                            projector.homography = HomographyComputer::new().compute(correspondences).ok();
+                        }
                     }
                 }
 
@@ -578,16 +963,13 @@ impl CalibrationSession {
         let camera_height = config.camera_height;
         let pixel_count = (camera_width * camera_height) as usize;
 
-        let mut correspondences = DecodedCorrespondences {
+        let mut correspondences = DecodedCorrespondences::new(
             camera_width,
             camera_height,
-            projector_width: projector.projector_width,
-            projector_height: projector.projector_height,
-            projector_x: vec![-1; pixel_count],
-            projector_y: vec![-1; pixel_count],
-            confidence: vec![0.0; pixel_count],
-            valid_mask: vec![false; pixel_count],
-        };
+            projector.projector_width,
+            projector.projector_height,
+        );
+        let pattern_config = &projector.pattern_config;
 
         let white = projector.white_reference.as_ref()
             .ok_or("Missing white reference")?;
@@ -603,7 +985,7 @@ impl CalibrationSession {
             &projector.vertical_pairs,
             white,
             black,
-            projector.pattern_config.horizontal_bits,
+            pattern_config.gray_bits(PatternDirection::Vertical),
         )?;
 
         // Decode Y coordinates from horizontal patterns
@@ -615,9 +997,41 @@ impl CalibrationSession {
             &projector.horizontal_pairs,
             white,
             black,
-            projector.pattern_config.vertical_bits,
+            pattern_config.gray_bits(PatternDirection::Horizontal),
         )?;
 
+        // Gray code gave half-period indices; the fringes give the position within
+        if let Some(phase) = &pattern_config.phase_shift {
+            let mut subpixel_x = vec![0.0; pixel_count];
+            let mut subpixel_y = vec![0.0; pixel_count];
+            phase_shift::refine_coordinate(
+                phase,
+                config.contrast_threshold,
+                &mut correspondences.projector_x,
+                &mut subpixel_x,
+                &mut correspondences.confidence,
+                &mut correspondences.valid_mask,
+                &projector.vertical_phase,
+                white,
+                black,
+                projector.projector_width,
+            )?;
+            phase_shift::refine_coordinate(
+                phase,
+                config.contrast_threshold,
+                &mut correspondences.projector_y,
+                &mut subpixel_y,
+                &mut correspondences.confidence,
+                &mut correspondences.valid_mask,
+                &projector.horizontal_phase,
+                white,
+                black,
+                projector.projector_height,
+            )?;
+            correspondences.subpixel_x = Some(subpixel_x);
+            correspondences.subpixel_y = Some(subpixel_y);
+        }
+
         // Count valid pixels
         let valid_count = correspondences.valid_mask.iter().filter(|&&v| v).count();
         log::info!("Decoded {} valid correspondences", valid_count);
@@ -683,9 +1097,164 @@ impl CalibrationSession {
 }
 
 /// Current pattern to display.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum CurrentPattern {
     White,
     Black,
     GrayCode(PatternSpec),
+    /// Verification crosshairs, pre-rendered at projector resolution.
+    Verification(Arc<GrayImage>),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Camera pixel -> continuous projector position (camera sees 2.7 x 1.9 projector pixels per pixel)
+    fn projector_position(cx: u32, cy: u32) -> (f32, f32) {
+        (40.0 + cx as f32 * 2.7, 20.0 + cy as f32 * 1.9)
+    }
+
+    /// Render what an ideal camera sees for the current pattern
+    ///
+    /// `shift` moves the camera right by that many pixels after calibration.
+    fn capture(pattern: &CurrentPattern, config: &PatternConfig, width: u32, height: u32, shift: f32) -> Vec<u8> {
+        let mut frame = vec![0u8; (width * height) as usize];
+        for cy in 0..height {
+            for cx in 0..width {
+                let (px, py) = projector_position(cx, cy);
+                let px = px + shift * 2.7;
+                let inside = px >= -0.5
+                    && py >= -0.5
+                    && px < config.projector_width as f32 - 0.5
+                    && py < config.projector_height as f32 - 0.5;
+                let value = match pattern {
+                    _ if !inside => 0.0,
+                    CurrentPattern::White => 1.0,
+                    CurrentPattern::Black => 0.0,
+                    CurrentPattern::Verification(image) => {
+                        image.get_pixel(px.round() as u32, py.round() as u32).0[0] as f32 / 255.0
+                    }
+                    CurrentPattern::GrayCode(spec) => {
+                        let coord = match spec.direction {
+                            PatternDirection::Horizontal => py,
+                            PatternDirection::Vertical => px,
+                        };
+                        match &spec.phase {
+                            Some(phase) => phase.config.intensity(coord, phase.step),
+                            None => {
+                                let bits = match spec.direction {
+                                    PatternDirection::Horizontal => config.vertical_bits,
+                                    PatternDirection::Vertical => config.horizontal_bits,
+                                };
+                                let gray = GrayCodeGenerator::binary_to_gray(coord.round() as u32);
+                                let bit = (gray >> (bits - 1 - spec.bit_index)) & 1;
+                                (bit != spec.inverted as u32) as u32 as f32
+                            }
+                        }
+                    }
+                };
+                frame[(cy * width + cx) as usize] = (value * 255.0).round() as u8;
+            }
+        }
+        frame
+    }
+
+    fn calibrated_session(phase_shift: Option<PhaseShiftConfig>) -> CalibrationSession {
+        let (width, height) = (160, 120);
+        let mut session = CalibrationSession::new(CalibrationConfig {
+            settle_time: Duration::ZERO,
+            frames_to_average: 1,
+            camera_width: width,
+            camera_height: height,
+            phase_shift,
+            ..Default::default()
+        });
+        session.add_projector(0, 512, 256);
+        session.start().unwrap();
+
+        while let Some(pattern) = session.current_pattern() {
+            session.update();
+            let frame = capture(&pattern, &session.projectors[0].pattern_config, width, height, 0.0);
+            session.submit_frame(frame, width, height);
+        }
+        while !session.process_calibration() {}
+
+        assert!(session.state.is_complete(), "{}", session.state);
+        assert!(session.projectors[0].homography.is_some());
+        session
+    }
+
+    fn run_session(phase_shift: Option<PhaseShiftConfig>) -> DecodedCorrespondences {
+        calibrated_session(phase_shift).projectors.remove(0).correspondences.unwrap()
+    }
+
+    /// Run the verification pass with the camera shifted by `shift` pixels
+    fn verify(session: &mut CalibrationSession, shift: f32) -> VerificationReport {
+        let config = VerificationConfig {
+            grid_spacing: 32,
+            arm_length: 6.0,
+            ..Default::default()
+        };
+        session.start_verification(config).unwrap();
+        while let Some(pattern) = session.current_pattern() {
+            session.update();
+            let frame = capture(&pattern, &session.projectors[0].pattern_config, 160, 120, shift);
+            session.submit_frame(frame, 160, 120);
+        }
+        assert!(matches!(session.state, CalibrationState::Complete), "{}", session.state);
+        session.verification_report.take().unwrap()
+    }
+
+    /// Mean absolute decode error over valid pixels
+    fn mean_error(decoded: &DecodedCorrespondences) -> f32 {
+        let mut total = 0.0;
+        let mut count = 0;
+        for cy in 0..decoded.camera_height {
+            for cx in 0..decoded.camera_width {
+                if let Some((x, y)) = decoded.get(cx, cy) {
+                    let (px, py) = projector_position(cx, cy);
+                    total += (x - px).abs() + (y - py).abs();
+                    count += 2;
+                }
+            }
+        }
+        assert!(count > 1000, "only {} valid coordinates", count);
+        total / count as f32
+    }
+
+    #[test]
+    fn test_gray_code_decode_is_pixel_accurate() {
+        let decoded = run_session(None);
+        assert!(decoded.subpixel_x.is_none());
+        let error = mean_error(&decoded);
+        assert!(error < 0.5 && error > 0.1, "{}", error);
+    }
+
+    #[test]
+    fn test_phase_shift_decode_is_subpixel_accurate() {
+        let decoded = run_session(Some(PhaseShiftConfig::default()));
+        assert!(decoded.subpixel_x.is_some());
+        let error = mean_error(&decoded);
+        assert!(error < 0.05, "{}", error);
+    }
+
+    #[test]
+    fn test_verification_measures_residual() {
+        let mut session = calibrated_session(Some(PhaseShiftConfig::default()));
+
+        let report = verify(&mut session, 0.0);
+        let projector = &report.projectors[0];
+        assert_eq!(projector.targets_expected, 15);
+        assert_eq!(projector.targets_found, 15);
+        assert!(projector.rms_error < 0.3, "{}", projector.rms_error);
+        assert!(projector.coverage > 0.5 && projector.camera_coverage > 0.9);
+        assert!(report.overlaps.is_empty());
+
+        // A camera bumped after calibration shows up as a 2px residual
+        let report = verify(&mut session, 2.0);
+        let projector = &report.projectors[0];
+        assert!(projector.targets_found >= 12, "{}", projector.targets_found);
+        assert!((projector.rms_error - 2.0).abs() < 0.3, "{}", projector.rms_error);
+    }
 }
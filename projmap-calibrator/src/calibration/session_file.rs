@@ -0,0 +1,213 @@
+//! Saved calibration sessions.
+//!
+//! A session file keeps everything captured and computed during a calibration
+//! run (reference frames, pattern captures, decoded correspondences with their
+//! confidence maps, homographies, checkerboard views and manual warp
+//! corrections) so a session can be resumed, re-solved or re-exported without
+//! recapturing.
+//!
+//! Layout: 8-byte magic, little-endian `u32` format version, then the
+//! bincode-encoded [`SessionFile`] compressed with LZ4 (size-prepended).
+//!
+//! When the layout of any stored type changes, bump [`SESSION_VERSION`], keep
+//! the previous layout as a legacy struct in this module and convert it in
+//! `decode_payload`, so older files keep loading.
+
+use super::camera_model::CheckerboardSet;
+use super::session::{CalibrationConfig, CalibrationSession, CameraCaptures, ProjectorCalibration};
+use crate::config::WarpCorrection;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Identifies a session file.
+const MAGIC: &[u8; 8] = b"PMCALSES";
+
+/// Current session file format version.
+pub const SESSION_VERSION: u32 = 2;
+
+/// Contents of a session file.
+#[derive(Serialize, Deserialize)]
+pub struct SessionFile {
+    /// Capture timing and decoding settings.
+    pub config: CalibrationConfig,
+    /// Primary camera captures, one per projector.
+    pub projectors: Vec<ProjectorCalibration>,
+    /// Additional cameras, in the order they were added.
+    pub extra_cameras: Vec<CameraCaptures>,
+    /// Checkerboard views per camera (primary first).
+    pub checkerboards: Vec<CheckerboardSet>,
+    /// Manual warp corrections by projector ID.
+    pub warp_corrections: BTreeMap<u32, WarpCorrection>,
+}
+
+/// Version 1 layout, before warp corrections were stored.
+#[derive(Deserialize)]
+struct SessionFileV1 {
+    config: CalibrationConfig,
+    projectors: Vec<ProjectorCalibration>,
+    extra_cameras: Vec<CameraCaptures>,
+    checkerboards: Vec<CheckerboardSet>,
+}
+
+impl From<SessionFileV1> for SessionFile {
+    fn from(file: SessionFileV1) -> Self {
+        Self {
+            config: file.config,
+            projectors: file.projectors,
+            extra_cameras: file.extra_cameras,
+            checkerboards: file.checkerboards,
+            warp_corrections: BTreeMap::new(),
+        }
+    }
+}
+
+impl SessionFile {
+    /// Rebuild the calibration session.
+    pub fn into_session(self) -> (CalibrationSession, Vec<CheckerboardSet>) {
+        let session = CalibrationSession::restore(self.config, self.projectors, self.extra_cameras);
+        (session, self.checkerboards)
+    }
+}
+
+/// Save a session, its checkerboard views and warp corrections.
+pub fn save_session(
+    session: &CalibrationSession,
+    checkerboards: &[CheckerboardSet],
+    warp_corrections: &BTreeMap<u32, WarpCorrection>,
+    path: &Path,
+) -> std::io::Result<()> {
+    // Borrowing mirror of `SessionFile`, so nothing is cloned to save
+    #[derive(Serialize)]
+    struct SessionRef<'a> {
+        config: &'a CalibrationConfig,
+        projectors: &'a [ProjectorCalibration],
+        extra_cameras: &'a [CameraCaptures],
+        checkerboards: &'a [CheckerboardSet],
+        warp_corrections: &'a BTreeMap<u32, WarpCorrection>,
+    }
+
+    let payload = bincode::serialize(&SessionRef {
+        config: &session.config,
+        projectors: &session.projectors,
+        extra_cameras: &session.extra_cameras,
+        checkerboards,
+        warp_corrections,
+    })
+    .map_err(std::io::Error::other)?;
+
+    let mut data = Vec::with_capacity(MAGIC.len() + 4 + payload.len() / 2);
+    data.extend_from_slice(MAGIC);
+    data.extend_from_slice(&SESSION_VERSION.to_le_bytes());
+    data.extend_from_slice(&lz4_flex::compress_prepend_size(&payload));
+    std::fs::write(path, data)?;
+
+    log::info!(
+        "Saved session with {} projector(s) to {}",
+        session.projectors.len(),
+        path.display()
+    );
+    Ok(())
+}
+
+/// Load a session file, migrating older format versions.
+pub fn load_session(path: &Path) -> std::io::Result<SessionFile> {
+    let data = std::fs::read(path)?;
+    if data.len() < MAGIC.len() + 4 || &data[..MAGIC.len()] != MAGIC {
+        return Err(invalid_data("Not a calibration session file"));
+    }
+    let version = u32::from_le_bytes(data[MAGIC.len()..MAGIC.len() + 4].try_into().unwrap());
+    let payload = lz4_flex::decompress_size_prepended(&data[MAGIC.len() + 4..])
+        .map_err(|e| invalid_data(format!("Corrupt session file: {}", e)))?;
+    let file = decode_payload(version, &payload)?;
+
+    log::info!(
+        "Loaded session (format {}) with {} projector(s) from {}",
+        version,
+        file.projectors.len(),
+        path.display()
+    );
+    Ok(file)
+}
+
+/// Decode a payload written by any supported format version.
+fn decode_payload(version: u32, payload: &[u8]) -> std::io::Result<SessionFile> {
+    let corrupt = |e: bincode::Error| invalid_data(format!("Corrupt session file: {}", e));
+    match version {
+        SESSION_VERSION => bincode::deserialize(payload).map_err(corrupt),
+        1 => bincode::deserialize::<SessionFileV1>(payload)
+            .map(SessionFile::from)
+            .map_err(corrupt),
+        v if v > SESSION_VERSION => Err(invalid_data(format!(
+            "Session format {} is newer than this calibrator supports ({})",
+            v, SESSION_VERSION
+        ))),
+        v => Err(invalid_data(format!("Unsupported session format {}", v))),
+    }
+}
+
+fn invalid_data(message: impl Into<String>) -> std::io::Error {
+    std::io::Error::new(std::io::ErrorKind::InvalidData, message.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calibration::CalibrationState;
+
+    #[test]
+    fn test_session_round_trip_migration_and_version_check() {
+        let dir = std::env::temp_dir().join(format!("projmap_session_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("test.pmsession");
+
+        let mut session = CalibrationSession::new(CalibrationConfig::default());
+        session.add_projector(0, 64, 32);
+        session.add_projector(1, 48, 24);
+        session.projectors[0].white_reference = Some(vec![200; 16]);
+        session.projectors[1].black_reference = Some(vec![10; 16]);
+        let mut corrections = BTreeMap::new();
+        let mut correction = WarpCorrection::new(3, 3);
+        correction.set(1, 1, [0.01, -0.02]);
+        corrections.insert(1, correction.clone());
+        save_session(&session, &[CheckerboardSet::default()], &corrections, &path).unwrap();
+
+        let mut file = load_session(&path).unwrap();
+        assert_eq!(file.warp_corrections.get(&1), Some(&correction));
+        file.warp_corrections.clear();
+        let (loaded, checkerboards) = file.into_session();
+        assert_eq!(loaded.projectors.len(), 2);
+        assert_eq!(loaded.projectors[1].projector_width, 48);
+        assert_eq!(loaded.projectors[0].white_reference.as_deref(), Some(&[200u8; 16][..]));
+        assert_eq!(loaded.projectors[1].black_reference.as_deref(), Some(&[10u8; 16][..]));
+        assert_eq!(checkerboards.len(), 1);
+        // Nothing decoded yet, so the session waits to be resumed
+        assert!(matches!(loaded.state, CalibrationState::Idle));
+
+        // Version 1 files load without corrections
+        #[derive(Serialize)]
+        struct V1<'a>(
+            &'a CalibrationConfig,
+            &'a [ProjectorCalibration],
+            &'a [CameraCaptures],
+            &'a [CheckerboardSet],
+        );
+        let payload = bincode::serialize(&V1(&session.config, &session.projectors, &[], &[])).unwrap();
+        let mut data = MAGIC.to_vec();
+        data.extend_from_slice(&1u32.to_le_bytes());
+        data.extend_from_slice(&lz4_flex::compress_prepend_size(&payload));
+        std::fs::write(&path, data).unwrap();
+        let migrated = load_session(&path).unwrap();
+        assert_eq!(migrated.projectors.len(), 2);
+        assert!(migrated.warp_corrections.is_empty());
+
+        // A file from a newer calibrator is rejected rather than misread
+        let mut data = std::fs::read(&path).unwrap();
+        data[MAGIC.len()..MAGIC.len() + 4].copy_from_slice(&(SESSION_VERSION + 1).to_le_bytes());
+        std::fs::write(&path, data).unwrap();
+        let err = load_session(&path).err().unwrap();
+        assert!(err.to_string().contains("newer"));
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
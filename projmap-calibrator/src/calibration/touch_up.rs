@@ -0,0 +1,150 @@
+//! Manual warp touch-up on top of the automatic mapping.
+//!
+//! Decoding gets the mapping most of the way; the rest is nudged by hand. A
+//! [`WarpCorrection`] per projector moves content at a coarse grid of control
+//! points, and a test grid drawn in content space is projected through the
+//! corrected mapping so every nudge can be checked on the surface. The
+//! corrections are a separate layer over the decoded UV maps, so they still
+//! apply after recalibrating.
+
+use crate::config::{UvMap, WarpCorrection};
+use image::{GrayImage, Luma};
+
+/// Default control points across and down a projector.
+pub const TOUCH_UP_COLUMNS: u32 = 9;
+pub const TOUCH_UP_ROWS: u32 = 6;
+
+/// Automatic mappings being touched up, and the test grid drawn through them.
+pub struct TouchUp {
+    /// Automatic UV map per projector.
+    pub maps: Vec<UvMap>,
+    /// Content width / height, so test grid cells come out square.
+    pub aspect: f32,
+    /// Test grid cells across the content width.
+    pub grid_divisions: u32,
+}
+
+impl TouchUp {
+    pub fn new(maps: Vec<UvMap>, aspect: f32) -> Self {
+        Self {
+            maps,
+            aspect: aspect.max(1e-3),
+            grid_divisions: 32,
+        }
+    }
+
+    /// Automatic mapping of a projector.
+    pub fn map(&self, projector_id: u32) -> Option<&UvMap> {
+        self.maps.iter().find(|m| m.projector_id == projector_id)
+    }
+
+    /// Mapping of a projector with its correction applied.
+    pub fn corrected(&self, projector_id: u32, correction: Option<&WarpCorrection>) -> Option<UvMap> {
+        let map = self.map(projector_id)?;
+        Some(match correction {
+            Some(correction) => correction.apply(map),
+            None => map.clone(),
+        })
+    }
+
+    /// Test grid for a projector at its resolution: one-pixel lines wherever
+    /// the corrected mapping crosses a grid line in content space.
+    pub fn test_grid(&self, projector_id: u32, correction: Option<&WarpCorrection>) -> Option<GrayImage> {
+        let map = self.corrected(projector_id, correction)?;
+        let (width, height) = (map.width, map.height);
+        let divisions = self.grid_divisions.max(1) as f32;
+        let cell = |x: u32, y: u32| -> Option<(i32, i32)> {
+            let uv = map.data[(y * width + x) as usize];
+            if uv[0].is_nan() || uv[1].is_nan() {
+                return None;
+            }
+            let u = (uv[0] * divisions).floor() as i32;
+            let v = (uv[1] * divisions / self.aspect).floor() as i32;
+            Some((u, v))
+        };
+
+        let mut image = GrayImage::new(width, height);
+        for y in 0..height {
+            for x in 0..width {
+                let Some(here) = cell(x, y) else {
+                    continue;
+                };
+                let right = (x + 1 < width).then(|| cell(x + 1, y)).flatten();
+                let below = (y + 1 < height).then(|| cell(x, y + 1)).flatten();
+                let crosses = right.is_some_and(|r| r != here) || below.is_some_and(|b| b != here);
+                if crosses {
+                    image.put_pixel(x, y, Luma([255]));
+                }
+            }
+        }
+        Some(image)
+    }
+
+    /// Where the content at each control point lands now, in normalized
+    /// content coordinates, row-major like the correction's offsets; `None`
+    /// where the automatic mapping has no data.
+    pub fn handles(&self, projector_id: u32, correction: &WarpCorrection) -> Vec<Option<[f32; 2]>> {
+        let Some(map) = self.map(projector_id) else {
+            return vec![None; correction.offsets.len()];
+        };
+        let max_x = map.width.saturating_sub(1) as f32;
+        let max_y = map.height.saturating_sub(1) as f32;
+        let mut handles = Vec::with_capacity(correction.offsets.len());
+        for row in 0..correction.rows {
+            for column in 0..correction.columns {
+                let node = correction.node_position(column, row);
+                let x = (node[0] * max_x).round() as u32;
+                let y = (node[1] * max_y).round() as u32;
+                let uv = map.data[(y * map.width + x) as usize];
+                let offset = correction.get(column, row);
+                handles.push((!uv[0].is_nan() && !uv[1].is_nan()).then(|| [uv[0] + offset[0], uv[1] + offset[1]]));
+            }
+        }
+        handles
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Projector showing the whole content space, one pixel per 1/`size`.
+    fn identity_map(size: u32) -> UvMap {
+        let max = (size - 1) as f32;
+        UvMap {
+            projector_id: 3,
+            width: size,
+            height: size,
+            data: (0..size * size)
+                .map(|i| [(i % size) as f32 / max, (i / size) as f32 / max])
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_touch_up_moves_handles_and_grid_together() {
+        let mut touch_up = TouchUp::new(vec![identity_map(65)], 1.0);
+        touch_up.grid_divisions = 4;
+        let mut correction = WarpCorrection::new(3, 3);
+
+        // Untouched: the center handle sits on the content center, grid lines
+        // fall every quarter of the projector
+        assert_eq!(touch_up.handles(3, &correction)[4], Some([0.5, 0.5]));
+        let grid = touch_up.test_grid(3, Some(&correction)).unwrap();
+        assert_eq!(grid.get_pixel(15, 40)[0], 255);
+        assert_eq!(grid.get_pixel(20, 40)[0], 0);
+
+        // Moving every control point right moves the handles and the lines right
+        for row in 0..3 {
+            for column in 0..3 {
+                correction.set(column, row, [0.0625, 0.0]);
+            }
+        }
+        assert_eq!(touch_up.handles(3, &correction)[4], Some([0.5625, 0.5]));
+        let grid = touch_up.test_grid(3, Some(&correction)).unwrap();
+        assert_eq!(grid.get_pixel(15, 40)[0], 0);
+        assert_eq!(grid.get_pixel(19, 40)[0], 255);
+
+        assert!(touch_up.test_grid(7, None).is_none());
+    }
+}
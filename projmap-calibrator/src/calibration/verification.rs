@@ -0,0 +1,687 @@
+//! Verification pass: measure how well the computed mapping lines up.
+//!
+//! After decoding, each projector shows crosshairs placed on a regular grid in
+//! camera space, drawn through its own projector -> camera mapping (the same
+//! per-pixel lookup the exported UV map gives the media server). With a
+//! perfect calibration every crosshair lands exactly on its grid point in the
+//! camera image; the offset of the captured crosshair's centroid is the
+//! residual. Where projectors overlap, the same grid point is drawn by each of
+//! them and their detected positions are compared directly.
+
+use super::session::ProjectorCalibration;
+use crate::config::UvMap;
+use image::{GrayImage, Luma, Rgb, RgbImage};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::Arc;
+
+/// Report file name inside a verification folder.
+pub const REPORT_FILE: &str = "verification_report.json";
+
+/// Verification pattern and measurement settings.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VerificationConfig {
+    /// Distance between crosshairs, in camera pixels.
+    pub grid_spacing: u32,
+    /// Crosshair arm length from the center, in camera pixels.
+    pub arm_length: f32,
+    /// Crosshair line width, in camera pixels.
+    pub line_width: f32,
+    /// Projector pixels between correspondence grid nodes when sampling the mapping.
+    pub map_spacing: u32,
+    /// Error shown as full red in the heatmaps, in camera pixels.
+    pub heatmap_scale: f32,
+}
+
+impl Default for VerificationConfig {
+    fn default() -> Self {
+        Self {
+            grid_spacing: 48,
+            arm_length: 8.0,
+            line_width: 3.0,
+            map_spacing: 8,
+            heatmap_scale: 4.0,
+        }
+    }
+}
+
+impl VerificationConfig {
+    /// Check that neighbouring crosshairs can be told apart.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.line_width <= 0.0 || self.arm_length < self.line_width {
+            return Err(format!(
+                "Crosshair arms ({}) must be at least as long as the line width ({})",
+                self.arm_length, self.line_width
+            ));
+        }
+        if self.search_radius() + self.arm_length >= self.grid_spacing as f32 {
+            return Err(format!(
+                "Grid spacing {} is too tight for {}px crosshair arms",
+                self.grid_spacing, self.arm_length
+            ));
+        }
+        if self.map_spacing == 0 {
+            return Err("Mapping grid spacing must be at least 1 pixel".to_string());
+        }
+        Ok(())
+    }
+
+    /// Half size of the window searched around each grid point.
+    fn search_radius(&self) -> f32 {
+        self.arm_length + self.grid_spacing as f32 / 4.0
+    }
+
+    /// Camera pixels a crosshair covers.
+    fn crosshair_area(&self) -> f32 {
+        4.0 * self.arm_length * self.line_width - self.line_width * self.line_width
+    }
+}
+
+/// Crosshair positions, centered in the camera frame.
+#[derive(Debug, Clone, Copy)]
+struct TargetGrid {
+    origin: [f32; 2],
+    spacing: f32,
+    columns: u32,
+    rows: u32,
+}
+
+impl TargetGrid {
+    fn new(width: u32, height: u32, spacing: u32) -> Self {
+        let columns = (width / spacing).max(1);
+        let rows = (height / spacing).max(1);
+        let spacing = spacing as f32;
+        Self {
+            origin: [
+                (width as f32 - 1.0 - (columns - 1) as f32 * spacing) / 2.0,
+                (height as f32 - 1.0 - (rows - 1) as f32 * spacing) / 2.0,
+            ],
+            spacing,
+            columns,
+            rows,
+        }
+    }
+
+    fn position(&self, column: u32, row: u32) -> [f32; 2] {
+        [
+            self.origin[0] + column as f32 * self.spacing,
+            self.origin[1] + row as f32 * self.spacing,
+        ]
+    }
+
+    /// Grid point closest to a camera position.
+    fn nearest(&self, point: [f32; 2]) -> [f32; 2] {
+        let column = ((point[0] - self.origin[0]) / self.spacing)
+            .round()
+            .clamp(0.0, (self.columns - 1) as f32);
+        let row = ((point[1] - self.origin[1]) / self.spacing)
+            .round()
+            .clamp(0.0, (self.rows - 1) as f32);
+        self.position(column as u32, row as u32)
+    }
+
+    /// All grid points, row-major.
+    fn targets(&self) -> Vec<[f32; 2]> {
+        (0..self.rows)
+            .flat_map(|row| (0..self.columns).map(move |column| (column, row)))
+            .map(|(column, row)| self.position(column, row))
+            .collect()
+    }
+}
+
+/// Crosshair patterns and captures of an ongoing verification pass.
+pub(super) struct VerificationPass {
+    config: VerificationConfig,
+    grid: TargetGrid,
+    camera_width: u32,
+    camera_height: u32,
+    /// Pattern per projector, in session order.
+    patterns: Vec<Arc<GrayImage>>,
+    /// Averaged captures, in session order.
+    captures: Vec<Vec<u8>>,
+}
+
+impl VerificationPass {
+    /// Render the crosshair pattern of every projector.
+    pub(super) fn new(
+        config: VerificationConfig,
+        projectors: &[ProjectorCalibration],
+        camera_width: u32,
+        camera_height: u32,
+    ) -> Result<Self, String> {
+        config.validate()?;
+        let grid = TargetGrid::new(camera_width, camera_height, config.grid_spacing);
+        let patterns = projectors
+            .iter()
+            .map(|p| {
+                let decoded = p
+                    .correspondences
+                    .as_ref()
+                    .ok_or_else(|| format!("Projector {} has no decoded correspondences", p.projector_id))?;
+                let map = decoded.to_correspondence_map(
+                    p.projector_id,
+                    p.projector_width / config.map_spacing + 1,
+                    p.projector_height / config.map_spacing + 1,
+                );
+                let uv_map = UvMap::from_correspondence_map(&map, p.projector_width, p.projector_height);
+                Ok(Arc::new(render_crosshairs(&uv_map, camera_width, camera_height, &grid, &config)))
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+
+        Ok(Self {
+            config,
+            grid,
+            camera_width,
+            camera_height,
+            patterns,
+            captures: Vec::new(),
+        })
+    }
+
+    /// Pattern for the projector at a session index.
+    pub(super) fn pattern(&self, index: usize) -> Option<Arc<GrayImage>> {
+        self.patterns.get(index).cloned()
+    }
+
+    /// Index of the projector being captured.
+    pub(super) fn current(&self) -> usize {
+        self.captures.len()
+    }
+
+    /// Number of projectors in the pass.
+    pub(super) fn len(&self) -> usize {
+        self.patterns.len()
+    }
+
+    /// Store the capture of the current projector.
+    pub(super) fn store(&mut self, frame: Vec<u8>) {
+        self.captures.push(frame);
+    }
+
+    /// Whether every projector has been captured.
+    pub(super) fn is_done(&self) -> bool {
+        self.captures.len() >= self.patterns.len()
+    }
+
+    /// Measure every capture against the grid.
+    pub(super) fn report(&self, projectors: &[ProjectorCalibration], contrast_threshold: f32) -> VerificationReport {
+        let frame_len = (self.camera_width * self.camera_height) as usize;
+        let targets = self.grid.targets();
+
+        let results: Vec<ProjectorVerification> = projectors
+            .iter()
+            .zip(&self.captures)
+            .map(|(projector, capture)| {
+                let decoded = projector.correspondences.as_ref();
+                let shadow_mask = decoded
+                    .map(|d| d.valid_mask.clone())
+                    .unwrap_or_else(|| vec![false; frame_len]);
+                let white = projector.white_reference.as_deref().unwrap_or(&[]);
+                let black = projector.black_reference.as_deref().unwrap_or(&[]);
+
+                let residuals: Vec<TargetResidual> = targets
+                    .iter()
+                    .filter(|t| self.crosshair_visible(&shadow_mask, **t))
+                    .map(|&expected| {
+                        let detected = self.measure(capture, white, black, contrast_threshold, expected);
+                        TargetResidual {
+                            expected,
+                            detected,
+                            error: detected.map(|d| distance(d, expected)),
+                        }
+                    })
+                    .collect();
+                let errors: Vec<f32> = residuals.iter().filter_map(|r| r.error).collect();
+
+                let coverage = decoded
+                    .map(|d| {
+                        let map = d.to_correspondence_map(
+                            projector.projector_id,
+                            projector.projector_width / self.config.map_spacing + 1,
+                            projector.projector_height / self.config.map_spacing + 1,
+                        );
+                        map.valid_count() as f32 / map.points.len() as f32
+                    })
+                    .unwrap_or(0.0);
+                let camera_coverage =
+                    shadow_mask.iter().filter(|&&v| v).count() as f32 / frame_len.max(1) as f32;
+
+                ProjectorVerification {
+                    projector_id: projector.projector_id,
+                    targets_expected: residuals.len(),
+                    targets_found: errors.len(),
+                    rms_error: rms(&errors),
+                    max_error: errors.iter().copied().fold(0.0, f32::max),
+                    coverage,
+                    camera_coverage,
+                    targets: residuals,
+                    shadow_mask,
+                }
+            })
+            .collect();
+
+        let mut overlaps = Vec::new();
+        for (i, a) in results.iter().enumerate() {
+            for b in &results[i + 1..] {
+                let differences: Vec<f32> = a
+                    .targets
+                    .iter()
+                    .filter_map(|ta| {
+                        let tb = b.targets.iter().find(|tb| tb.expected == ta.expected)?;
+                        Some(distance(ta.detected?, tb.detected?))
+                    })
+                    .collect();
+                if !differences.is_empty() {
+                    overlaps.push(OverlapVerification {
+                        projectors: [a.projector_id, b.projector_id],
+                        shared_targets: differences.len(),
+                        rms_error: rms(&differences),
+                        max_error: differences.iter().copied().fold(0.0, f32::max),
+                    });
+                }
+            }
+        }
+
+        VerificationReport {
+            camera_width: self.camera_width,
+            camera_height: self.camera_height,
+            grid_spacing: self.config.grid_spacing,
+            heatmap_scale: self.config.heatmap_scale,
+            projectors: results,
+            overlaps,
+        }
+    }
+
+    /// Whether a crosshair lies entirely inside the decoded footprint.
+    fn crosshair_visible(&self, valid_mask: &[bool], target: [f32; 2]) -> bool {
+        let arm = self.config.arm_length;
+        [[0.0, 0.0], [-arm, 0.0], [arm, 0.0], [0.0, -arm], [0.0, arm]]
+            .iter()
+            .all(|offset| {
+                let x = (target[0] + offset[0]).round();
+                let y = (target[1] + offset[1]).round();
+                x >= 0.0
+                    && y >= 0.0
+                    && x < self.camera_width as f32
+                    && y < self.camera_height as f32
+                    && valid_mask
+                        .get(y as usize * self.camera_width as usize + x as usize)
+                        .copied()
+                        .unwrap_or(false)
+            })
+    }
+
+    /// Centroid of the crosshair captured around a grid point.
+    ///
+    /// Pixels are normalized by the projector's white and black references so
+    /// surface albedo and vignetting do not pull the centroid.
+    fn measure(
+        &self,
+        capture: &[u8],
+        white: &[u8],
+        black: &[u8],
+        contrast_threshold: f32,
+        target: [f32; 2],
+    ) -> Option<[f32; 2]> {
+        let radius = self.config.search_radius();
+        let x0 = (target[0] - radius).floor().max(0.0) as u32;
+        let y0 = (target[1] - radius).floor().max(0.0) as u32;
+        let x1 = ((target[0] + radius).ceil() as u32).min(self.camera_width.saturating_sub(1));
+        let y1 = ((target[1] + radius).ceil() as u32).min(self.camera_height.saturating_sub(1));
+
+        let (mut sum, mut sum_x, mut sum_y) = (0.0f32, 0.0f32, 0.0f32);
+        let mut lit = 0.0f32;
+        for y in y0..=y1 {
+            for x in x0..=x1 {
+                let i = (y * self.camera_width + x) as usize;
+                let (Some(&value), Some(&w), Some(&b)) = (capture.get(i), white.get(i), black.get(i)) else {
+                    continue;
+                };
+                let contrast = (w as f32 - b as f32) / 255.0;
+                if contrast < contrast_threshold {
+                    continue;
+                }
+                let level = ((value as f32 - b as f32) / 255.0 / contrast).clamp(0.0, 1.0);
+                if level > 0.5 {
+                    lit += 1.0;
+                }
+                // Ignore the faint spill between lines
+                let weight = (level - 0.25).max(0.0);
+                sum += weight;
+                sum_x += weight * x as f32;
+                sum_y += weight * y as f32;
+            }
+        }
+
+        (lit >= 0.5 * self.config.crosshair_area() && sum > 0.0).then(|| [sum_x / sum, sum_y / sum])
+    }
+}
+
+/// Draw crosshairs at the grid points through a projector's mapping.
+fn render_crosshairs(
+    uv_map: &UvMap,
+    camera_width: u32,
+    camera_height: u32,
+    grid: &TargetGrid,
+    config: &VerificationConfig,
+) -> GrayImage {
+    let half_width = config.line_width / 2.0;
+    let arm = config.arm_length;
+    // Soft one-pixel edges keep the centroid from snapping to projector pixels
+    let bar = |along: f32, across: f32| {
+        (half_width + 0.5 - across).clamp(0.0, 1.0) * (arm + 0.5 - along).clamp(0.0, 1.0)
+    };
+
+    let mut image = GrayImage::new(uv_map.width, uv_map.height);
+    for (pixel, uv) in image.pixels_mut().zip(&uv_map.data) {
+        if uv[0].is_nan() || uv[1].is_nan() {
+            continue;
+        }
+        let camera = [uv[0] * camera_width as f32, uv[1] * camera_height as f32];
+        let target = grid.nearest(camera);
+        let dx = (camera[0] - target[0]).abs();
+        let dy = (camera[1] - target[1]).abs();
+        let value = bar(dx, dy).max(bar(dy, dx));
+        *pixel = Luma([(value * 255.0).round() as u8]);
+    }
+    image
+}
+
+/// Residual of one crosshair.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TargetResidual {
+    /// Grid point the crosshair was drawn at (camera pixels).
+    pub expected: [f32; 2],
+    /// Captured crosshair centroid, if it was found.
+    pub detected: Option<[f32; 2]>,
+    /// Distance between the two (camera pixels).
+    pub error: Option<f32>,
+}
+
+/// Verification result of one projector.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectorVerification {
+    /// Projector ID.
+    pub projector_id: u32,
+    /// Crosshairs inside the decoded footprint.
+    pub targets_expected: usize,
+    /// Crosshairs found in the capture.
+    pub targets_found: usize,
+    /// RMS residual over found crosshairs (camera pixels).
+    pub rms_error: f32,
+    /// Largest residual (camera pixels).
+    pub max_error: f32,
+    /// Fraction of the projector's correspondence grid that decoded.
+    pub coverage: f32,
+    /// Fraction of the camera frame the projector decoded in.
+    pub camera_coverage: f32,
+    /// Per-crosshair residuals.
+    pub targets: Vec<TargetResidual>,
+    /// Shadow mask: camera pixels with a valid decode.
+    #[serde(skip)]
+    pub shadow_mask: Vec<bool>,
+}
+
+/// Disagreement between two overlapping projectors.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OverlapVerification {
+    /// Projector IDs.
+    pub projectors: [u32; 2],
+    /// Crosshairs found for both projectors.
+    pub shared_targets: usize,
+    /// RMS distance between their detected positions (camera pixels).
+    pub rms_error: f32,
+    /// Largest distance (camera pixels).
+    pub max_error: f32,
+}
+
+/// Result of a verification pass.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VerificationReport {
+    /// Camera frame width.
+    pub camera_width: u32,
+    /// Camera frame height.
+    pub camera_height: u32,
+    /// Distance between crosshairs (camera pixels).
+    pub grid_spacing: u32,
+    /// Error shown as full red in the heatmaps (camera pixels).
+    pub heatmap_scale: f32,
+    /// Per-projector results, in calibration order.
+    pub projectors: Vec<ProjectorVerification>,
+    /// Per overlapping pair results.
+    pub overlaps: Vec<OverlapVerification>,
+}
+
+impl VerificationReport {
+    /// Write the JSON report, per-projector heatmaps and shadow masks, and
+    /// the overlap heatmap to a folder.
+    pub fn save(&self, dir: &Path) -> std::io::Result<()> {
+        std::fs::create_dir_all(dir)?;
+        let json = serde_json::to_string_pretty(self).map_err(std::io::Error::other)?;
+        std::fs::write(dir.join(REPORT_FILE), json)?;
+
+        for projector in &self.projectors {
+            let id = projector.projector_id;
+            self.projector_heatmap(projector)
+                .save(dir.join(format!("projector_{}_verification.png", id)))
+                .map_err(std::io::Error::other)?;
+            self.shadow_image(projector)
+                .save(dir.join(format!("projector_{}_shadow.png", id)))
+                .map_err(std::io::Error::other)?;
+        }
+        if !self.overlaps.is_empty() {
+            self.overlap_heatmap()
+                .save(dir.join("overlap_verification.png"))
+                .map_err(std::io::Error::other)?;
+        }
+
+        log::info!("Saved verification report to {:?}", dir);
+        Ok(())
+    }
+
+    /// Shadow mask as an 8-bit image (white = decoded).
+    fn shadow_image(&self, projector: &ProjectorVerification) -> GrayImage {
+        let mut image = GrayImage::new(self.camera_width, self.camera_height);
+        for (pixel, valid) in image.pixels_mut().zip(&projector.shadow_mask) {
+            *pixel = Luma([if *valid { 255 } else { 0 }]);
+        }
+        image
+    }
+
+    /// Residual heatmap over the projector's footprint, annotated with the
+    /// grid points (white), residual vectors scaled 4x (black) and missing
+    /// crosshairs (magenta).
+    fn projector_heatmap(&self, projector: &ProjectorVerification) -> RgbImage {
+        let found: Vec<&TargetResidual> = projector.targets.iter().filter(|t| t.error.is_some()).collect();
+        let reach = self.grid_spacing as f32 * 1.5;
+
+        let mut image = RgbImage::from_pixel(self.camera_width, self.camera_height, SHADOW_COLOR);
+        for (i, pixel) in image.pixels_mut().enumerate() {
+            if !projector.shadow_mask.get(i).copied().unwrap_or(false) {
+                continue;
+            }
+            let point = [
+                (i as u32 % self.camera_width) as f32,
+                (i as u32 / self.camera_width) as f32,
+            ];
+            // Inverse-distance weighting of the nearby residuals
+            let (mut sum, mut weights) = (0.0, 0.0);
+            for target in &found {
+                let d = distance(point, target.expected);
+                if d < reach {
+                    let w = 1.0 / (d * d + 1.0);
+                    sum += w * target.error.unwrap_or(0.0);
+                    weights += w;
+                }
+            }
+            *pixel = if weights > 0.0 {
+                dim(self.error_color(sum / weights))
+            } else {
+                UNMEASURED_COLOR
+            };
+        }
+
+        for target in &projector.targets {
+            draw_cross(&mut image, target.expected, 3.0, Rgb([255, 255, 255]));
+            match target.detected {
+                Some(detected) => {
+                    let tip = [
+                        target.expected[0] + (detected[0] - target.expected[0]) * 4.0,
+                        target.expected[1] + (detected[1] - target.expected[1]) * 4.0,
+                    ];
+                    draw_line(&mut image, target.expected, tip, Rgb([0, 0, 0]));
+                }
+                None => {
+                    let [x, y] = target.expected;
+                    let magenta = Rgb([255, 0, 255]);
+                    draw_line(&mut image, [x - 4.0, y - 4.0], [x + 4.0, y + 4.0], magenta);
+                    draw_line(&mut image, [x - 4.0, y + 4.0], [x + 4.0, y - 4.0], magenta);
+                }
+            }
+        }
+        image
+    }
+
+    /// Projector count per camera pixel (darker gray = fewer), with a square
+    /// at every shared crosshair colored by the largest disagreement there.
+    fn overlap_heatmap(&self) -> RgbImage {
+        let mut image = RgbImage::from_pixel(self.camera_width, self.camera_height, SHADOW_COLOR);
+        for (i, pixel) in image.pixels_mut().enumerate() {
+            let count = self
+                .projectors
+                .iter()
+                .filter(|p| p.shadow_mask.get(i).copied().unwrap_or(false))
+                .count();
+            if count > 0 {
+                let level = (40 + 30 * count).min(160) as u8;
+                *pixel = Rgb([level, level, level]);
+            }
+        }
+
+        let half = self.grid_spacing as f32 / 4.0;
+        for (i, a) in self.projectors.iter().enumerate() {
+            for b in &self.projectors[i + 1..] {
+                for ta in &a.targets {
+                    let Some(tb) = b.targets.iter().find(|tb| tb.expected == ta.expected) else {
+                        continue;
+                    };
+                    let (Some(da), Some(db)) = (ta.detected, tb.detected) else {
+                        continue;
+                    };
+                    let color = self.error_color(distance(da, db));
+                    let [x, y] = ta.expected;
+                    for py in (y - half).max(0.0) as u32..((y + half) as u32).min(self.camera_height) {
+                        for px in (x - half).max(0.0) as u32..((x + half) as u32).min(self.camera_width) {
+                            image.put_pixel(px, py, color);
+                        }
+                    }
+                    draw_cross(&mut image, ta.expected, 3.0, Rgb([255, 255, 255]));
+                }
+            }
+        }
+        image
+    }
+
+    /// Green (no error) through yellow to red (heatmap scale and beyond).
+    fn error_color(&self, error: f32) -> Rgb<u8> {
+        let t = (error / self.heatmap_scale.max(f32::EPSILON)).clamp(0.0, 1.0);
+        let red = (t * 2.0).min(1.0);
+        let green = (2.0 - t * 2.0).min(1.0);
+        Rgb([(red * 255.0) as u8, (green * 255.0) as u8, 0])
+    }
+}
+
+/// Camera pixels the projector did not decode.
+const SHADOW_COLOR: Rgb<u8> = Rgb([24, 24, 24]);
+
+/// Decoded pixels too far from any found crosshair to estimate an error.
+const UNMEASURED_COLOR: Rgb<u8> = Rgb([80, 80, 96]);
+
+/// Darken a heatmap color so annotations stand out.
+fn dim(color: Rgb<u8>) -> Rgb<u8> {
+    Rgb(color.0.map(|c| (c as f32 * 0.7) as u8))
+}
+
+fn draw_cross(image: &mut RgbImage, center: [f32; 2], size: f32, color: Rgb<u8>) {
+    let [x, y] = center;
+    draw_line(image, [x - size, y], [x + size, y], color);
+    draw_line(image, [x, y - size], [x, y + size], color);
+}
+
+fn draw_line(image: &mut RgbImage, from: [f32; 2], to: [f32; 2], color: Rgb<u8>) {
+    let steps = (to[0] - from[0]).abs().max((to[1] - from[1]).abs()).ceil().max(1.0) as u32;
+    for i in 0..=steps {
+        let t = i as f32 / steps as f32;
+        let x = (from[0] + (to[0] - from[0]) * t).round();
+        let y = (from[1] + (to[1] - from[1]) * t).round();
+        if x >= 0.0 && y >= 0.0 && (x as u32) < image.width() && (y as u32) < image.height() {
+            image.put_pixel(x as u32, y as u32, color);
+        }
+    }
+}
+
+fn distance(a: [f32; 2], b: [f32; 2]) -> f32 {
+    ((a[0] - b[0]).powi(2) + (a[1] - b[1]).powi(2)).sqrt()
+}
+
+fn rms(values: &[f32]) -> f32 {
+    if values.is_empty() {
+        return 0.0;
+    }
+    (values.iter().map(|v| v * v).sum::<f32>() / values.len() as f32).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_report_writes_json_and_images() {
+        let target = |x: f32, detected: Option<[f32; 2]>| TargetResidual {
+            expected: [x, 16.0],
+            detected,
+            error: detected.map(|d| distance(d, [x, 16.0])),
+        };
+        let projector = |id: u32, detected: [f32; 2]| ProjectorVerification {
+            projector_id: id,
+            targets_expected: 2,
+            targets_found: 1,
+            rms_error: distance(detected, [16.0, 16.0]),
+            max_error: distance(detected, [16.0, 16.0]),
+            coverage: 0.9,
+            camera_coverage: 0.5,
+            targets: vec![target(16.0, Some(detected)), target(48.0, None)],
+            shadow_mask: vec![true; 64 * 32],
+        };
+        let report = VerificationReport {
+            camera_width: 64,
+            camera_height: 32,
+            grid_spacing: 32,
+            heatmap_scale: 4.0,
+            projectors: vec![projector(0, [16.5, 16.0]), projector(1, [18.0, 16.0])],
+            overlaps: vec![OverlapVerification {
+                projectors: [0, 1],
+                shared_targets: 1,
+                rms_error: 1.5,
+                max_error: 1.5,
+            }],
+        };
+
+        let dir = std::env::temp_dir().join(format!("projmap_verification_{}", std::process::id()));
+        report.save(&dir).unwrap();
+
+        let json: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(dir.join(REPORT_FILE)).unwrap()).unwrap();
+        assert_eq!(json["projectors"][1]["targets"][1]["detected"], serde_json::Value::Null);
+        assert!(json["projectors"][0].get("shadow_mask").is_none());
+
+        let heatmap = image::open(dir.join("projector_1_verification.png")).unwrap().to_rgb8();
+        assert_eq!(heatmap.dimensions(), (64, 32));
+        // Crosshair 2px off with a 4px scale: yellow, dimmed
+        assert_eq!(*heatmap.get_pixel(12, 24), Rgb([178, 178, 0]));
+        assert!(dir.join("projector_0_shadow.png").exists());
+        assert!(dir.join("overlap_verification.png").exists());
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
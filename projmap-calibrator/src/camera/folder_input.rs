@@ -0,0 +1,259 @@
+//! Folder watcher camera input.
+//!
+//! Tethering software for DSLRs and mirrorless cameras saves every shot into a
+//! directory. FolderWatcher picks up each new image once the file stops
+//! growing and hands it out as a camera frame.
+
+use super::source::{rgba_to_bgra, CameraError, CameraFrame, CameraSource, CaptureTiming};
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+/// Image extensions picked up from the watched folder.
+const IMAGE_EXTENSIONS: &[&str] = &["jpg", "jpeg", "png", "tif", "tiff", "bmp"];
+
+/// How long a file's size must stay unchanged before it is read.
+const WRITE_SETTLE: Duration = Duration::from_millis(200);
+
+/// How often pending files are checked.
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Shared state between the loader thread and main thread.
+struct FolderWatcherState {
+    current_frame: Mutex<Option<CameraFrame>>,
+    new_frame_available: AtomicBool,
+    running: AtomicBool,
+    frame_count: AtomicU64,
+}
+
+/// A new file waiting for the tethering software to finish writing it.
+struct PendingImage {
+    path: PathBuf,
+    size: Option<u64>,
+    stable_since: Instant,
+}
+
+/// Camera input from images saved into a folder.
+pub struct FolderWatcher {
+    state: Arc<FolderWatcherState>,
+    thread_handle: Option<JoinHandle<()>>,
+    watcher: Option<RecommendedWatcher>,
+    source_name: String,
+    settle_time: Duration,
+    width: u32,
+    height: u32,
+    start_time: Instant,
+}
+
+impl FolderWatcher {
+    /// Watch a folder for new images.
+    ///
+    /// Images already in the folder are ignored. `settle_time` is the delay
+    /// between a pattern appearing and a photo of it landing in the folder
+    /// (trigger interval plus exposure plus transfer).
+    pub fn watch(dir: &Path, settle_time: Duration) -> Result<Self, CameraError> {
+        if !dir.is_dir() {
+            return Err(CameraError::Folder(format!("{} is not a directory", dir.display())));
+        }
+
+        let (sender, events) = mpsc::channel();
+        let mut watcher = notify::recommended_watcher(move |result: notify::Result<Event>| match result {
+            Ok(event) if matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_)) => {
+                for path in event.paths {
+                    let _ = sender.send(path);
+                }
+            }
+            Ok(_) => {}
+            Err(e) => log::warn!("Folder watcher: {}", e),
+        })
+        .map_err(|e| CameraError::Folder(e.to_string()))?;
+        watcher
+            .watch(dir, RecursiveMode::NonRecursive)
+            .map_err(|e| CameraError::Folder(e.to_string()))?;
+
+        let state = Arc::new(FolderWatcherState {
+            current_frame: Mutex::new(None),
+            new_frame_available: AtomicBool::new(false),
+            running: AtomicBool::new(true),
+            frame_count: AtomicU64::new(0),
+        });
+        let start_time = Instant::now();
+        let state_clone = Arc::clone(&state);
+        let thread_handle = thread::spawn(move || {
+            Self::load_loop(state_clone, events, start_time);
+        });
+
+        log::info!("Folder Watcher: Watching {:?}", dir);
+
+        Ok(Self {
+            state,
+            thread_handle: Some(thread_handle),
+            watcher: Some(watcher),
+            source_name: dir.display().to_string(),
+            settle_time,
+            width: 0,
+            height: 0,
+            start_time,
+        })
+    }
+
+    fn load_loop(state: Arc<FolderWatcherState>, events: Receiver<PathBuf>, start_time: Instant) {
+        let mut pending: Vec<PendingImage> = Vec::new();
+
+        while state.running.load(Ordering::Acquire) {
+            match events.recv_timeout(POLL_INTERVAL) {
+                Ok(path) => {
+                    if is_image(&path) && !pending.iter().any(|p| p.path == path) {
+                        pending.push(PendingImage {
+                            path,
+                            size: None,
+                            stable_since: Instant::now(),
+                        });
+                    }
+                    continue;
+                }
+                Err(RecvTimeoutError::Timeout) => {}
+                Err(RecvTimeoutError::Disconnected) => break,
+            }
+
+            pending.retain_mut(|image| {
+                let Ok(size) = std::fs::metadata(&image.path).map(|m| m.len()) else {
+                    // Deleted or renamed before we got to it
+                    return false;
+                };
+                if image.size != Some(size) || size == 0 {
+                    image.size = Some(size);
+                    image.stable_since = Instant::now();
+                    return true;
+                }
+                if image.stable_since.elapsed() < WRITE_SETTLE {
+                    return true;
+                }
+
+                match image::open(&image.path) {
+                    Ok(img) => {
+                        let rgba = img.to_rgba8();
+                        let frame = CameraFrame {
+                            width: rgba.width(),
+                            height: rgba.height(),
+                            data: rgba_to_bgra(rgba.into_raw()),
+                            timestamp: start_time.elapsed(),
+                            frame_rate: 0.0,
+                        };
+                        log::info!("Folder Watcher: Loaded {:?}", image.path);
+                        if let Ok(mut current) = state.current_frame.lock() {
+                            *current = Some(frame);
+                            state.new_frame_available.store(true, Ordering::Release);
+                            state.frame_count.fetch_add(1, Ordering::Relaxed);
+                        }
+                    }
+                    Err(e) => log::warn!("Folder Watcher: Failed to load {:?}: {}", image.path, e),
+                }
+                false
+            });
+        }
+    }
+}
+
+/// Whether a path has one of the supported image extensions.
+fn is_image(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| IMAGE_EXTENSIONS.contains(&e.to_ascii_lowercase().as_str()))
+}
+
+impl CameraSource for FolderWatcher {
+    fn source_name(&self) -> &str {
+        &self.source_name
+    }
+
+    fn take_frame(&mut self) -> Option<CameraFrame> {
+        if self.state.new_frame_available.swap(false, Ordering::AcqRel) {
+            if let Ok(mut current) = self.state.current_frame.lock() {
+                if let Some(frame) = current.take() {
+                    self.width = frame.width;
+                    self.height = frame.height;
+                    return Some(frame);
+                }
+            }
+        }
+        None
+    }
+
+    fn is_connected(&self) -> bool {
+        self.thread_handle.as_ref().is_some_and(|h| !h.is_finished())
+    }
+
+    fn width(&self) -> u32 {
+        self.width
+    }
+
+    fn height(&self) -> u32 {
+        self.height
+    }
+
+    fn average_fps(&self) -> f64 {
+        let elapsed = self.start_time.elapsed().as_secs_f64();
+        if elapsed > 0.0 {
+            self.state.frame_count.load(Ordering::Acquire) as f64 / elapsed
+        } else {
+            0.0
+        }
+    }
+
+    fn timing(&self) -> CaptureTiming {
+        // Each file is a separate exposure, so there is nothing to average
+        CaptureTiming {
+            settle_time: self.settle_time,
+            frames_to_average: 1,
+        }
+    }
+}
+
+impl Drop for FolderWatcher {
+    fn drop(&mut self) {
+        self.state.running.store(false, Ordering::Release);
+        // Dropping the watcher closes the event channel
+        self.watcher.take();
+        if let Some(handle) = self.thread_handle.take() {
+            let _ = handle.join();
+        }
+        log::info!("Folder Watcher: Stopped watching {}", self.source_name);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_image_becomes_frame() {
+        let dir = std::env::temp_dir().join(format!("projmap_folder_watch_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("notes.txt"), "not an image").unwrap();
+
+        let mut watcher = FolderWatcher::watch(&dir, Duration::from_secs(1)).unwrap();
+        let image = image::RgbaImage::from_pixel(8, 4, image::Rgba([10, 20, 30, 255]));
+        image.save(dir.join("shot_0001.png")).unwrap();
+
+        let deadline = Instant::now() + Duration::from_secs(5);
+        let frame = loop {
+            if let Some(frame) = watcher.take_frame() {
+                break frame;
+            }
+            assert!(Instant::now() < deadline, "no frame from the watched folder");
+            thread::sleep(POLL_INTERVAL);
+        };
+        assert_eq!((frame.width, frame.height), (8, 4));
+        assert_eq!(&frame.data[..4], &[30, 20, 10, 255]);
+        assert_eq!(watcher.timing().frames_to_average, 1);
+
+        drop(watcher);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
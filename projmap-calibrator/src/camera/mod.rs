@@ -1,9 +1,18 @@
 //! Camera input module for projection mapping calibration.
 //!
-//! Provides NDI camera input for capturing projected patterns.
+//! Provides camera backends for capturing projected patterns: NDI sources,
+//! local webcams (`webcam` feature) and a folder watcher for tethered DSLRs.
 
-mod ndi_ffi;
+mod folder_input;
+pub(crate) mod ndi_ffi;
 mod ndi_input;
+mod source;
+#[cfg(feature = "webcam")]
+mod webcam_input;
 
+pub use folder_input::FolderWatcher;
 pub use ndi_ffi::{destroy as ndi_destroy, initialize as ndi_initialize, version as ndi_version};
-pub use ndi_input::{NdiError, NdiFinder, NdiFrame, NdiReceiver};
+pub use ndi_input::{NdiError, NdiFinder, NdiReceiver};
+pub use source::{list_webcams, CameraError, CameraFrame, CameraSource, CameraSourceKind, CaptureTiming};
+#[cfg(feature = "webcam")]
+pub use webcam_input::WebcamCapture;
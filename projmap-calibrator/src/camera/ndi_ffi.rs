@@ -1,4 +1,4 @@
-//! FFI bindings to the NDI SDK (libndi).
+//! FFI bindings to the NDI SDK (libndi): finder, receiver and sender.
 //!
 //! SDK Location: /Library/NDI SDK for Apple/
 //! Library: /Library/NDI SDK for Apple/lib/macOS/libndi.dylib
@@ -16,12 +16,14 @@ use std::ptr;
 
 pub type NDIlib_find_instance_t = *mut c_void;
 pub type NDIlib_recv_instance_t = *mut c_void;
+pub type NDIlib_send_instance_t = *mut c_void;
 
 // =============================================================================
 // Constants
 // =============================================================================
 
 pub const NDILIB_RECV_TIMESTAMP_UNDEFINED: i64 = i64::MAX;
+pub const NDILIB_SEND_TIMECODE_SYNTHESIZE: i64 = i64::MAX;
 
 // =============================================================================
 // Enumerations
@@ -138,6 +140,26 @@ impl Default for NDIlib_recv_create_v3_t {
     }
 }
 
+#[repr(C)]
+#[derive(Debug)]
+pub struct NDIlib_send_create_t {
+    pub p_ndi_name: *const c_char,
+    pub p_groups: *const c_char,
+    pub clock_video: bool,
+    pub clock_audio: bool,
+}
+
+impl Default for NDIlib_send_create_t {
+    fn default() -> Self {
+        Self {
+            p_ndi_name: ptr::null(),
+            p_groups: ptr::null(),
+            clock_video: false,
+            clock_audio: false,
+        }
+    }
+}
+
 #[repr(C)]
 #[derive(Debug)]
 pub struct NDIlib_video_frame_v2_t {
@@ -266,6 +288,20 @@ extern "C" {
         p_video_data: *const NDIlib_video_frame_v2_t,
     );
     pub fn NDIlib_recv_get_no_connections(p_instance: NDIlib_recv_instance_t) -> c_int;
+
+    // Sender
+    pub fn NDIlib_send_create(
+        p_create_settings: *const NDIlib_send_create_t,
+    ) -> NDIlib_send_instance_t;
+    pub fn NDIlib_send_destroy(p_instance: NDIlib_send_instance_t);
+    pub fn NDIlib_send_send_video_v2(
+        p_instance: NDIlib_send_instance_t,
+        p_video_data: *const NDIlib_video_frame_v2_t,
+    );
+    pub fn NDIlib_send_get_no_connections(
+        p_instance: NDIlib_send_instance_t,
+        timeout_in_ms: u32,
+    ) -> c_int;
 }
 
 // =============================================================================
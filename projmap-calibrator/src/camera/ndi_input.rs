@@ -3,6 +3,7 @@
 //! Provides NdiReceiver for receiving camera feeds over NDI.
 
 use super::ndi_ffi::*;
+use super::source::{CameraFrame, CameraSource, CaptureTiming};
 use bytes::Bytes;
 use std::ffi::CString;
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
@@ -10,21 +11,6 @@ use std::sync::{Arc, Mutex};
 use std::thread::{self, JoinHandle};
 use std::time::{Duration, Instant};
 
-/// Video frame received from NDI camera.
-#[derive(Debug, Clone)]
-pub struct NdiFrame {
-    /// Frame width in pixels.
-    pub width: u32,
-    /// Frame height in pixels.
-    pub height: u32,
-    /// Raw pixel data (BGRA format).
-    pub data: Bytes,
-    /// Timestamp from stream start.
-    pub timestamp: Duration,
-    /// Frame rate (frames per second).
-    pub frame_rate: f64,
-}
-
 /// NDI error type.
 #[derive(Debug, Clone)]
 pub enum NdiError {
@@ -49,7 +35,7 @@ impl std::error::Error for NdiError {}
 
 /// Shared state between receive thread and main thread.
 struct NdiReceiverState {
-    current_frame: Mutex<Option<NdiFrame>>,
+    current_frame: Mutex<Option<CameraFrame>>,
     new_frame_available: AtomicBool,
     running: AtomicBool,
     connected: AtomicBool,
@@ -193,7 +179,7 @@ impl NdiReceiver {
                         60.0
                     };
 
-                    let ndi_frame = NdiFrame {
+                    let ndi_frame = CameraFrame {
                         width: video_frame.xres as u32,
                         height: video_frame.yres as u32,
                         data,
@@ -228,7 +214,7 @@ impl NdiReceiver {
     }
 
     /// Take the latest frame (non-blocking).
-    pub fn take_frame(&mut self) -> Option<NdiFrame> {
+    pub fn take_frame(&mut self) -> Option<CameraFrame> {
         if self.state.new_frame_available.swap(false, Ordering::AcqRel) {
             if let Ok(mut current) = self.state.current_frame.lock() {
                 if let Some(frame) = current.take() {
@@ -277,6 +263,40 @@ impl NdiReceiver {
     }
 }
 
+impl CameraSource for NdiReceiver {
+    fn source_name(&self) -> &str {
+        NdiReceiver::source_name(self)
+    }
+
+    fn take_frame(&mut self) -> Option<CameraFrame> {
+        NdiReceiver::take_frame(self)
+    }
+
+    fn is_connected(&self) -> bool {
+        NdiReceiver::is_connected(self)
+    }
+
+    fn width(&self) -> u32 {
+        NdiReceiver::width(self)
+    }
+
+    fn height(&self) -> u32 {
+        NdiReceiver::height(self)
+    }
+
+    fn average_fps(&self) -> f64 {
+        NdiReceiver::average_fps(self)
+    }
+
+    fn timing(&self) -> CaptureTiming {
+        // NDI adds about a frame of network latency on top of the projector's
+        CaptureTiming {
+            settle_time: Duration::from_millis(100),
+            frames_to_average: 3,
+        }
+    }
+}
+
 impl Drop for NdiReceiver {
     fn drop(&mut self) {
         self.state.running.store(false, Ordering::Release);
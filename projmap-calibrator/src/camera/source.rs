@@ -0,0 +1,181 @@
+//! Camera backend abstraction.
+//!
+//! Every backend delivers BGRA frames through [`CameraSource`], so the
+//! calibration sessions do not care where the pixels come from.
+
+use bytes::Bytes;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use super::folder_input::FolderWatcher;
+use super::ndi_input::{NdiError, NdiReceiver};
+
+/// Video frame received from a camera.
+#[derive(Debug, Clone)]
+pub struct CameraFrame {
+    /// Frame width in pixels.
+    pub width: u32,
+    /// Frame height in pixels.
+    pub height: u32,
+    /// Raw pixel data (BGRA format).
+    pub data: Bytes,
+    /// Timestamp from stream start.
+    pub timestamp: Duration,
+    /// Frame rate (frames per second).
+    pub frame_rate: f64,
+}
+
+/// How long to wait after a pattern change, and how many frames to average.
+///
+/// Streaming cameras deliver a fresh frame every few milliseconds, while a
+/// tethered DSLR needs seconds for exposure and file transfer and yields one
+/// image per trigger.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CaptureTiming {
+    /// Delay between displaying a pattern and accepting frames.
+    pub settle_time: Duration,
+    /// Frames to average per pattern.
+    pub frames_to_average: usize,
+}
+
+impl CaptureTiming {
+    /// Timing that suits every camera in a rig: the longest settle time and
+    /// the fewest frames per pattern.
+    pub fn combine(timings: impl IntoIterator<Item = CaptureTiming>) -> Option<CaptureTiming> {
+        timings.into_iter().reduce(|a, b| CaptureTiming {
+            settle_time: a.settle_time.max(b.settle_time),
+            frames_to_average: a.frames_to_average.min(b.frames_to_average),
+        })
+    }
+}
+
+/// A camera backend.
+pub trait CameraSource: Send {
+    /// Human-readable source name.
+    fn source_name(&self) -> &str;
+
+    /// Take the latest frame (non-blocking).
+    fn take_frame(&mut self) -> Option<CameraFrame>;
+
+    /// Check if the source is delivering frames.
+    fn is_connected(&self) -> bool;
+
+    /// Get video width (0 until the first frame).
+    fn width(&self) -> u32;
+
+    /// Get video height (0 until the first frame).
+    fn height(&self) -> u32;
+
+    /// Get the average FPS since connection.
+    fn average_fps(&self) -> f64;
+
+    /// Settle time and frame averaging suited to this backend.
+    fn timing(&self) -> CaptureTiming;
+}
+
+/// Camera error type.
+#[derive(Debug, Clone)]
+pub enum CameraError {
+    Ndi(NdiError),
+    Webcam(String),
+    Folder(String),
+}
+
+impl std::fmt::Display for CameraError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CameraError::Ndi(e) => write!(f, "{}", e),
+            CameraError::Webcam(msg) => write!(f, "Webcam error: {}", msg),
+            CameraError::Folder(msg) => write!(f, "Folder watcher error: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for CameraError {}
+
+impl From<NdiError> for CameraError {
+    fn from(e: NdiError) -> Self {
+        CameraError::Ndi(e)
+    }
+}
+
+/// A camera that can be connected to.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CameraSourceKind {
+    /// NDI source by name.
+    Ndi(String),
+    /// Local V4L2/UVC/AVFoundation webcam.
+    Webcam { index: u32, name: String },
+    /// Directory that tethering software saves new images into.
+    Folder { path: PathBuf, settle_time: Duration },
+}
+
+impl CameraSourceKind {
+    /// Label for source pickers.
+    pub fn label(&self) -> String {
+        match self {
+            CameraSourceKind::Ndi(name) => format!("NDI: {}", name),
+            CameraSourceKind::Webcam { name, .. } => format!("Webcam: {}", name),
+            CameraSourceKind::Folder { path, .. } => format!("Folder: {}", path.display()),
+        }
+    }
+
+    /// Connect to the source.
+    pub fn connect(&self) -> Result<Box<dyn CameraSource>, CameraError> {
+        match self {
+            CameraSourceKind::Ndi(name) => Ok(Box::new(NdiReceiver::connect(name)?)),
+            #[cfg(feature = "webcam")]
+            CameraSourceKind::Webcam { index, name } => {
+                Ok(Box::new(super::webcam_input::WebcamCapture::open(*index, name)?))
+            }
+            #[cfg(not(feature = "webcam"))]
+            CameraSourceKind::Webcam { .. } => Err(CameraError::Webcam(
+                "built without the `webcam` feature".to_string(),
+            )),
+            CameraSourceKind::Folder { path, settle_time } => {
+                Ok(Box::new(FolderWatcher::watch(path, *settle_time)?))
+            }
+        }
+    }
+}
+
+/// Swap RGBA pixels to the BGRA order the calibrator works in.
+pub(super) fn rgba_to_bgra(mut data: Vec<u8>) -> Bytes {
+    for pixel in data.chunks_exact_mut(4) {
+        pixel.swap(0, 2);
+    }
+    Bytes::from(data)
+}
+
+/// Local webcams, or none when built without the `webcam` feature.
+pub fn list_webcams() -> Vec<CameraSourceKind> {
+    #[cfg(feature = "webcam")]
+    {
+        super::webcam_input::list_webcams()
+    }
+    #[cfg(not(feature = "webcam"))]
+    {
+        Vec::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_combined_timing_suits_slowest_camera() {
+        let ndi = CaptureTiming {
+            settle_time: Duration::from_millis(100),
+            frames_to_average: 3,
+        };
+        let dslr = CaptureTiming {
+            settle_time: Duration::from_secs(2),
+            frames_to_average: 1,
+        };
+        let combined = CaptureTiming::combine([ndi, dslr]).unwrap();
+        assert_eq!(combined.settle_time, Duration::from_secs(2));
+        assert_eq!(combined.frames_to_average, 1);
+        assert!(CaptureTiming::combine([]).is_none());
+    }
+}
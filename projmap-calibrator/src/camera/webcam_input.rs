@@ -0,0 +1,208 @@
+//! Local webcam input (V4L2, UVC, AVFoundation) via nokhwa.
+//!
+//! Only built with the `webcam` feature.
+
+use super::source::{rgba_to_bgra, CameraError, CameraFrame, CameraSource, CameraSourceKind, CaptureTiming};
+use nokhwa::pixel_format::RgbAFormat;
+use nokhwa::utils::{ApiBackend, CameraIndex, RequestedFormat, RequestedFormatType};
+use nokhwa::Camera;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::mpsc;
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+/// List local webcams.
+pub fn list_webcams() -> Vec<CameraSourceKind> {
+    match nokhwa::query(ApiBackend::Auto) {
+        Ok(cameras) => cameras
+            .iter()
+            .enumerate()
+            .map(|(index, info)| CameraSourceKind::Webcam {
+                index: index as u32,
+                name: info.human_name().to_string(),
+            })
+            .collect(),
+        Err(e) => {
+            log::warn!("Failed to enumerate webcams: {:?}", e);
+            Vec::new()
+        }
+    }
+}
+
+/// Shared state between capture thread and main thread.
+struct WebcamState {
+    current_frame: Mutex<Option<CameraFrame>>,
+    new_frame_available: AtomicBool,
+    running: AtomicBool,
+    frame_count: AtomicU64,
+}
+
+/// Webcam capture on a background thread.
+pub struct WebcamCapture {
+    state: Arc<WebcamState>,
+    thread_handle: Option<JoinHandle<()>>,
+    source_name: String,
+    width: u32,
+    height: u32,
+    start_time: Instant,
+}
+
+impl WebcamCapture {
+    /// Open a webcam by index at its highest resolution.
+    pub fn open(index: u32, name: &str) -> Result<Self, CameraError> {
+        let state = Arc::new(WebcamState {
+            current_frame: Mutex::new(None),
+            new_frame_available: AtomicBool::new(false),
+            running: AtomicBool::new(true),
+            frame_count: AtomicU64::new(0),
+        });
+        let start_time = Instant::now();
+
+        // The camera handle is not Send on every platform, so it is opened on
+        // the capture thread and the result reported back.
+        let (opened, open_result) = mpsc::channel();
+        let state_clone = Arc::clone(&state);
+        let thread_handle = thread::Builder::new()
+            .name("webcam-capture".to_string())
+            .spawn(move || {
+                let mut camera = match Self::open_camera(index) {
+                    Ok(camera) => {
+                        let _ = opened.send(Ok(()));
+                        camera
+                    }
+                    Err(e) => {
+                        let _ = opened.send(Err(e));
+                        return;
+                    }
+                };
+                Self::capture_loop(&mut camera, state_clone, start_time);
+            })
+            .map_err(|e| CameraError::Webcam(format!("Failed to spawn capture thread: {}", e)))?;
+
+        open_result
+            .recv()
+            .map_err(|_| CameraError::Webcam("capture thread exited".to_string()))??;
+        log::info!("Webcam: Opened '{}'", name);
+
+        Ok(Self {
+            state,
+            thread_handle: Some(thread_handle),
+            source_name: name.to_string(),
+            width: 0,
+            height: 0,
+            start_time,
+        })
+    }
+
+    fn open_camera(index: u32) -> Result<Camera, CameraError> {
+        let requested = RequestedFormat::new::<RgbAFormat>(RequestedFormatType::AbsoluteHighestResolution);
+        let mut camera = Camera::new(CameraIndex::Index(index), requested)
+            .or_else(|_| {
+                Camera::new(
+                    CameraIndex::Index(index),
+                    RequestedFormat::new::<RgbAFormat>(RequestedFormatType::None),
+                )
+            })
+            .map_err(|e| CameraError::Webcam(format!("{:?}", e)))?;
+        camera
+            .open_stream()
+            .map_err(|e| CameraError::Webcam(format!("Failed to open stream: {:?}", e)))?;
+        Ok(camera)
+    }
+
+    fn capture_loop(camera: &mut Camera, state: Arc<WebcamState>, start_time: Instant) {
+        let frame_rate = camera.frame_rate() as f64;
+
+        while state.running.load(Ordering::Acquire) {
+            let buffer = match camera.frame() {
+                Ok(buffer) => buffer,
+                Err(e) => {
+                    log::warn!("Webcam: Failed to capture frame: {:?}", e);
+                    thread::sleep(Duration::from_millis(10));
+                    continue;
+                }
+            };
+            let image = match buffer.decode_image::<RgbAFormat>() {
+                Ok(image) => image,
+                Err(e) => {
+                    log::warn!("Webcam: Failed to decode frame: {:?}", e);
+                    continue;
+                }
+            };
+
+            let frame = CameraFrame {
+                width: image.width(),
+                height: image.height(),
+                data: rgba_to_bgra(image.into_raw()),
+                timestamp: start_time.elapsed(),
+                frame_rate,
+            };
+            if let Ok(mut current) = state.current_frame.lock() {
+                *current = Some(frame);
+                state.new_frame_available.store(true, Ordering::Release);
+                state.frame_count.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+
+        let _ = camera.stop_stream();
+    }
+}
+
+impl CameraSource for WebcamCapture {
+    fn source_name(&self) -> &str {
+        &self.source_name
+    }
+
+    fn take_frame(&mut self) -> Option<CameraFrame> {
+        if self.state.new_frame_available.swap(false, Ordering::AcqRel) {
+            if let Ok(mut current) = self.state.current_frame.lock() {
+                if let Some(frame) = current.take() {
+                    self.width = frame.width;
+                    self.height = frame.height;
+                    return Some(frame);
+                }
+            }
+        }
+        None
+    }
+
+    fn is_connected(&self) -> bool {
+        self.state.frame_count.load(Ordering::Acquire) > 0
+    }
+
+    fn width(&self) -> u32 {
+        self.width
+    }
+
+    fn height(&self) -> u32 {
+        self.height
+    }
+
+    fn average_fps(&self) -> f64 {
+        let elapsed = self.start_time.elapsed().as_secs_f64();
+        if elapsed > 0.0 {
+            self.state.frame_count.load(Ordering::Acquire) as f64 / elapsed
+        } else {
+            0.0
+        }
+    }
+
+    fn timing(&self) -> CaptureTiming {
+        // Webcam drivers queue a few frames and auto-exposure needs time to react
+        CaptureTiming {
+            settle_time: Duration::from_millis(300),
+            frames_to_average: 3,
+        }
+    }
+}
+
+impl Drop for WebcamCapture {
+    fn drop(&mut self) {
+        self.state.running.store(false, Ordering::Release);
+        if let Some(handle) = self.thread_handle.take() {
+            let _ = handle.join();
+        }
+        log::info!("Webcam: Closed '{}'", self.source_name);
+    }
+}
@@ -0,0 +1,433 @@
+//! Command-line interface for scripted calibration runs.
+//!
+//! Every subcommand reads and updates the same project file, so a run on site
+//! is `generate-patterns`, capture, `decode`, `solve`, `blend`, `export`.
+//! Intermediate files (correspondence maps, UV maps, blend masks) are written
+//! next to the project file and named after it, exactly as an export would
+//! name them. No window or GPU is needed.
+
+use crate::blending::{footprint_blend_masks, BlendSpace};
+use crate::calibration::{write_pattern_set, CalibrationConfig, CameraMerge, CaptureRun, PhaseShiftConfig};
+use crate::config::{BlendCurve, CorrespondenceMap, ProjectConfig, ProjectorConfig, UvMap, SCHEMA_VERSION};
+use crate::export::{
+    load_blend_mask, load_correspondence_map, load_project, save_project, CalibrationExporter,
+    UV_MAP_GRID_SPACING,
+};
+use clap::{Parser, Subcommand};
+use std::path::{Path, PathBuf};
+
+/// Projection mapping calibration. Starts the GUI without a subcommand.
+#[derive(Debug, Parser)]
+#[command(name = "projmap-calibrator", version)]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+/// Calibration steps, in the order they are run.
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Write the Gray code pattern images and a capture manifest.
+    ///
+    /// Creates the project file if it does not exist yet.
+    GeneratePatterns {
+        /// Project file (`.json`, XML otherwise).
+        #[arg(long)]
+        project: PathBuf,
+        /// Folder for the pattern images and manifest.
+        #[arg(long)]
+        out: PathBuf,
+        /// Number of projectors in a new project.
+        #[arg(long, default_value_t = 1)]
+        projectors: u32,
+        /// Projector width in a new project.
+        #[arg(long, default_value_t = 1920)]
+        width: u32,
+        /// Projector height in a new project.
+        #[arg(long, default_value_t = 1080)]
+        height: u32,
+        /// Add phase-shift fringes for sub-pixel correspondences.
+        #[arg(long)]
+        phase_shift: bool,
+    },
+    /// Decode a folder of captures into correspondence maps.
+    Decode {
+        /// Project file.
+        #[arg(long)]
+        project: PathBuf,
+        /// Capture folder with a `manifest.json`.
+        #[arg(long)]
+        captures: PathBuf,
+        /// Minimum white/black difference for a camera pixel to decode (0-1).
+        #[arg(long, default_value_t = 0.1)]
+        contrast_threshold: f32,
+    },
+    /// Fit homographies and build per-pixel UV maps from the correspondences,
+    /// keeping any warp correction stored in the project.
+    Solve {
+        /// Project file.
+        #[arg(long)]
+        project: PathBuf,
+    },
+    /// Compute blend masks from the projector footprints.
+    Blend {
+        /// Project file.
+        #[arg(long)]
+        project: PathBuf,
+        /// Blend curve (linear, gamma, cosine, smoothstep); defaults to the project's.
+        #[arg(long, value_parser = parse_curve)]
+        curve: Option<BlendCurve>,
+    },
+    /// Write the calibration for immersive-server.
+    Export {
+        /// Project file.
+        #[arg(long)]
+        project: PathBuf,
+        /// Exported project file (`.json`, XML otherwise); sidecar files are written next to it.
+        #[arg(long)]
+        out: PathBuf,
+    },
+}
+
+/// Run one calibration step.
+pub fn run(command: Command) -> Result<(), String> {
+    match command {
+        Command::GeneratePatterns {
+            project,
+            out,
+            projectors,
+            width,
+            height,
+            phase_shift,
+        } => generate_patterns(&project, &out, projectors, width, height, phase_shift),
+        Command::Decode {
+            project,
+            captures,
+            contrast_threshold,
+        } => decode(&project, &captures, contrast_threshold),
+        Command::Solve { project } => solve(&project),
+        Command::Blend { project, curve } => blend(&project, curve),
+        Command::Export { project, out } => export(&project, &out),
+    }
+}
+
+fn parse_curve(name: &str) -> Result<BlendCurve, String> {
+    match name.to_ascii_lowercase().as_str() {
+        "linear" => Ok(BlendCurve::Linear),
+        "gamma" => Ok(BlendCurve::Gamma),
+        "cosine" => Ok(BlendCurve::Cosine),
+        "smoothstep" => Ok(BlendCurve::Smoothstep),
+        _ => Err(format!("unknown blend curve '{}'", name)),
+    }
+}
+
+fn generate_patterns(
+    project_path: &Path,
+    out: &Path,
+    count: u32,
+    width: u32,
+    height: u32,
+    phase_shift: bool,
+) -> Result<(), String> {
+    let project = if project_path.exists() {
+        open_project(project_path)?
+    } else {
+        let project = new_project(project_path, count, width, height);
+        let (dir, _) = sidecar_location(project_path);
+        std::fs::create_dir_all(&dir).map_err(|e| format!("{}: {}", dir.display(), e))?;
+        write_project(&project, project_path)?;
+        log::info!("Created project {:?} with {} projector(s)", project_path, count);
+        project
+    };
+
+    let projectors: Vec<_> = project.projectors.iter().map(|p| (p.id, p.width, p.height)).collect();
+    let manifest = write_pattern_set(out, &projectors, phase_shift.then(PhaseShiftConfig::default))
+        .map_err(|e| format!("{}: {}", out.display(), e))?;
+    let images: usize = manifest.projectors.iter().map(|p| p.captures.len()).sum();
+    log::info!("Wrote {} pattern images to {:?}", images, out);
+    Ok(())
+}
+
+/// Project with projectors laid out left to right on the canvas.
+fn new_project(path: &Path, count: u32, width: u32, height: u32) -> ProjectConfig {
+    let projectors = (0..count)
+        .map(|i| ProjectorConfig {
+            id: i,
+            name: format!("Projector {}", i + 1),
+            width,
+            height,
+            display_index: i as usize,
+            canvas_x: (i * width) as i32,
+            ..ProjectorConfig::default()
+        })
+        .collect();
+    ProjectConfig {
+        schema_version: SCHEMA_VERSION,
+        name: sidecar_stem(path),
+        canvas_width: (count * width).max(1),
+        canvas_height: height.max(1),
+        projectors,
+        ..ProjectConfig::default()
+    }
+}
+
+fn decode(project_path: &Path, captures: &Path, contrast_threshold: f32) -> Result<(), String> {
+    let mut project = open_project(project_path)?;
+    let run = CaptureRun::load(captures).map_err(|e| format!("{}: {}", captures.display(), e))?;
+    let session = run.replay(&CalibrationConfig {
+        contrast_threshold,
+        ..Default::default()
+    })?;
+
+    project.camera_width = run.camera_width;
+    project.camera_height = run.camera_height;
+    let (dir, stem) = sidecar_location(project_path);
+    for calibration in &session.projectors {
+        let id = calibration.projector_id;
+        let projector = project
+            .projectors
+            .iter_mut()
+            .find(|p| p.id == id)
+            .ok_or_else(|| format!("Capture run has projector {}, which is not in the project", id))?;
+        if (projector.width, projector.height) != (calibration.projector_width, calibration.projector_height) {
+            return Err(format!(
+                "Projector {} was captured at {}x{} but the project says {}x{}",
+                id, calibration.projector_width, calibration.projector_height, projector.width, projector.height
+            ));
+        }
+        let Some(decoded) = &calibration.correspondences else {
+            continue;
+        };
+
+        // Dense enough to rebuild the per-pixel UV map from
+        let map = decoded.to_correspondence_map(
+            id,
+            projector.width / UV_MAP_GRID_SPACING + 1,
+            projector.height / UV_MAP_GRID_SPACING + 1,
+        );
+        let filename = format!("{}_projector_{}.json", stem, id);
+        let json = serde_json::to_string(&map).map_err(|e| e.to_string())?;
+        std::fs::write(dir.join(&filename), json).map_err(|e| format!("{}: {}", filename, e))?;
+        log::info!(
+            "Projector {}: {} decoded pixels, {}/{} grid nodes",
+            id,
+            decoded.valid_count(),
+            map.valid_count(),
+            map.points.len()
+        );
+
+        // Anything solved from older correspondences is stale now
+        projector.correspondence_file = Some(filename);
+        projector.homography = None;
+        projector.uv_map_file = None;
+        projector.blend_mask_file = None;
+    }
+
+    write_project(&project, project_path)
+}
+
+fn solve(project_path: &Path) -> Result<(), String> {
+    let mut project = open_project(project_path)?;
+    let (dir, stem) = sidecar_location(project_path);
+
+    for projector in &mut project.projectors {
+        let Some(map) = projector_map(&dir, projector)? else {
+            log::warn!("Projector {}: not decoded, skipping", projector.id);
+            continue;
+        };
+
+        projector.homography = CameraMerge::homography(&map)
+            .map(|m| [m[0][0], m[0][1], m[0][2], m[1][0], m[1][1], m[1][2], m[2][0], m[2][1], m[2][2]]);
+        if projector.homography.is_none() {
+            log::warn!("Projector {}: homography fit failed", projector.id);
+        }
+
+        // A touch-up made in the app is kept on top of the new solution
+        let mut uv_map = CalibrationExporter::uv_map_from_correspondences(&map);
+        if let Some(correction) = &projector.warp_correction {
+            correction
+                .validate()
+                .map_err(|e| format!("Projector {} warp correction: {}", projector.id, e))?;
+            uv_map = correction.apply(&uv_map);
+        }
+        let filename = format!("{}_projector_{}.pmuv", stem, projector.id);
+        std::fs::write(dir.join(&filename), uv_map.to_bytes()).map_err(|e| format!("{}: {}", filename, e))?;
+        log::info!("Projector {}: wrote UV map {}", projector.id, filename);
+        projector.uv_map_file = Some(filename);
+        projector.blend_mask_file = None;
+    }
+
+    write_project(&project, project_path)
+}
+
+fn blend(project_path: &Path, curve: Option<BlendCurve>) -> Result<(), String> {
+    let mut project = open_project(project_path)?;
+    let (dir, stem) = sidecar_location(project_path);
+    let curve = curve
+        .or_else(|| project.projectors.first().map(|p| p.blend.curve))
+        .unwrap_or_default();
+
+    let mut ids = Vec::new();
+    let mut uv_maps = Vec::new();
+    for projector in &project.projectors {
+        if let Some(uv_map) = projector_uv_map(&dir, projector)? {
+            ids.push(projector.id);
+            uv_maps.push(uv_map);
+        }
+    }
+    if uv_maps.is_empty() {
+        return Err("No UV maps in the project; run `solve` first".to_string());
+    }
+
+    let (content_width, content_height) = project.content_size();
+    let space = BlendSpace {
+        aspect: content_width as f32 / content_height.max(1) as f32,
+        wraps_u: false,
+    };
+    let masks = footprint_blend_masks(&uv_maps, space, curve);
+
+    for (id, mask) in ids.iter().zip(&masks) {
+        let filename = format!("{}_projector_{}_blend.png", stem, id);
+        CalibrationExporter::export_blend_mask_16bit(mask, &dir.join(&filename))
+            .map_err(|e| format!("{}: {}", filename, e))?;
+        log::info!("Projector {}: wrote blend mask {}", id, filename);
+        if let Some(projector) = project.projectors.iter_mut().find(|p| p.id == *id) {
+            projector.blend.curve = curve;
+            projector.blend_mask_file = Some(filename);
+        }
+    }
+
+    write_project(&project, project_path)
+}
+
+fn export(project_path: &Path, out: &Path) -> Result<(), String> {
+    let mut project = open_project(project_path)?;
+    let (dir, _) = sidecar_location(project_path);
+
+    let mut maps = Vec::new();
+    let mut uv_maps = Vec::new();
+    let mut blend_masks = Vec::new();
+    let mut masks_complete = true;
+    for projector in &project.projectors {
+        if let Some(map) = projector_map(&dir, projector)? {
+            maps.push(map);
+        }
+        let Some(uv_map) = projector_uv_map(&dir, projector)? else {
+            continue;
+        };
+        uv_maps.push(uv_map);
+        match &projector.blend_mask_file {
+            Some(file) => blend_masks.push(
+                load_blend_mask(&dir.join(file), projector.blend.curve).map_err(|e| format!("{}: {}", file, e))?,
+            ),
+            None => masks_complete = false,
+        }
+    }
+    // Masks are matched to UV maps by position
+    if !masks_complete {
+        log::warn!("Not every projector has a blend mask; run `blend` to include them");
+        blend_masks.clear();
+    }
+
+    if let Some(parent) = out.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent).map_err(|e| format!("{}: {}", parent.display(), e))?;
+    }
+    CalibrationExporter::export_calibration(&mut project, &maps, &uv_maps, &blend_masks, &[], out)
+        .map_err(|e| format!("{}: {}", out.display(), e))?;
+    log::info!("Exported calibration to {:?}", out);
+    Ok(())
+}
+
+fn open_project(path: &Path) -> Result<ProjectConfig, String> {
+    load_project(path).map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+fn write_project(project: &ProjectConfig, path: &Path) -> Result<(), String> {
+    save_project(project, path).map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+/// Folder and file stem sidecar files are written with.
+fn sidecar_location(project_path: &Path) -> (PathBuf, String) {
+    let dir = project_path
+        .parent()
+        .filter(|p| !p.as_os_str().is_empty())
+        .unwrap_or_else(|| Path::new("."));
+    (dir.to_path_buf(), sidecar_stem(project_path))
+}
+
+fn sidecar_stem(project_path: &Path) -> String {
+    project_path
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "calibration".to_string())
+}
+
+fn projector_map(dir: &Path, projector: &ProjectorConfig) -> Result<Option<CorrespondenceMap>, String> {
+    let Some(file) = &projector.correspondence_file else {
+        return Ok(None);
+    };
+    load_correspondence_map(&dir.join(file))
+        .map(Some)
+        .map_err(|e| format!("{}: {}", file, e))
+}
+
+fn projector_uv_map(dir: &Path, projector: &ProjectorConfig) -> Result<Option<UvMap>, String> {
+    let Some(file) = &projector.uv_map_file else {
+        return Ok(None);
+    };
+    let bytes = std::fs::read(dir.join(file)).map_err(|e| format!("{}: {}", file, e))?;
+    UvMap::from_bytes(&bytes)
+        .map(Some)
+        .map_err(|e| format!("{}: {}", file, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scripted_run_from_patterns_to_export() {
+        let dir = std::env::temp_dir().join(format!("projmap_cli_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let project = dir.join("site.json");
+        let patterns = dir.join("patterns");
+        let parse = |args: &[&str]| {
+            let mut argv = vec!["projmap-calibrator"];
+            argv.extend_from_slice(args);
+            Cli::try_parse_from(argv).unwrap().command.unwrap()
+        };
+        let project_arg = project.to_str().unwrap();
+
+        run(parse(&[
+            "generate-patterns", "--project", project_arg, "--out", patterns.to_str().unwrap(),
+            "--projectors", "2", "--width", "64", "--height", "32",
+        ]))
+        .unwrap();
+        // The pattern folder doubles as the captures of a camera seeing each projector 1:1
+        run(parse(&["decode", "--project", project_arg, "--captures", patterns.to_str().unwrap()])).unwrap();
+        run(parse(&["solve", "--project", project_arg])).unwrap();
+        run(parse(&["blend", "--project", project_arg, "--curve", "linear"])).unwrap();
+
+        let solved = load_project(&project).unwrap();
+        assert_eq!((solved.camera_width, solved.camera_height), (64, 32));
+        let h = solved.projectors[1].homography.unwrap();
+        let (x, y) = crate::config::project_point(&h, 40.0, 20.0).unwrap();
+        assert!((x - 40.0).abs() < 0.5 && (y - 20.0).abs() < 0.5, "({}, {})", x, y);
+
+        // Both projectors cover the whole camera frame, so they share it evenly
+        let mask = load_blend_mask(&dir.join("site_projector_0_blend.png"), BlendCurve::Linear).unwrap();
+        let center = mask.data[(16 * mask.width + 32) as usize];
+        assert!((center - 0.5).abs() < 0.05, "{}", center);
+
+        let out = dir.join("export").join("show.json");
+        run(parse(&["export", "--project", project_arg, "--out", out.to_str().unwrap()])).unwrap();
+        let exported = load_project(&out).unwrap();
+        for (projector, id) in exported.projectors.iter().zip(0..) {
+            assert_eq!(projector.uv_map_file.as_deref(), Some(format!("show_projector_{}.pmuv", id).as_str()));
+            assert!(dir.join("export").join(projector.blend_mask_file.as_ref().unwrap()).exists());
+            assert!(dir.join("export").join(projector.correspondence_file.as_ref().unwrap()).exists());
+        }
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
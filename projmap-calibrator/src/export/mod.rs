@@ -1,9 +1,23 @@
 //! Export module for calibration data.
 
-use crate::blending::BlendMask;
-use crate::config::ProjectConfig;
+use crate::blending::{BlendMask, OverlapDetectionResult, OverlapEdge};
+use crate::calibration::{CalibrationSession, ColorMatch};
+use crate::config::{
+    BlendConfig, BlendCurve, CorrespondenceMap, ProjectConfig, ProjectorConfig, UvMap,
+    SCHEMA_VERSION,
+};
 use std::path::Path;
 
+/// Default correspondence grid density exported per projector.
+pub const CORRESPONDENCE_GRID_COLUMNS: u32 = 33;
+pub const CORRESPONDENCE_GRID_ROWS: u32 = 19;
+
+/// Projector pixels between grid nodes when building per-pixel UV maps.
+pub const UV_MAP_GRID_SPACING: u32 = 8;
+
+/// Edge length of the exported color matching LUTs.
+pub const COLOR_LUT_SIZE: u32 = 33;
+
 /// Export calibration data to various formats.
 pub struct CalibrationExporter;
 
@@ -24,6 +38,167 @@ impl CalibrationExporter {
         std::fs::write(path, json)
     }
 
+    /// Build a project description from a finished calibration session.
+    ///
+    /// Projectors are laid out left to right on the canvas. Edge blend widths
+    /// come from the detected overlaps: the overlapping edge of projector A and
+    /// the opposite edge of projector B both receive the overlap width.
+    pub fn project_from_session(
+        name: &str,
+        session: &CalibrationSession,
+        overlaps: Option<&OverlapDetectionResult>,
+        curve: BlendCurve,
+    ) -> ProjectConfig {
+        let mut canvas_x = 0i32;
+        let mut canvas_height = 0u32;
+        let mut projectors: Vec<ProjectorConfig> = session
+            .projectors
+            .iter()
+            .enumerate()
+            .map(|(index, calibration)| {
+                let homography = calibration.homography.as_ref().map(|h| {
+                    let m = h.matrix;
+                    [
+                        m[0][0], m[0][1], m[0][2], m[1][0], m[1][1], m[1][2], m[2][0], m[2][1],
+                        m[2][2],
+                    ]
+                });
+                let config = ProjectorConfig {
+                    id: calibration.projector_id,
+                    name: format!("Projector {}", index + 1),
+                    width: calibration.projector_width,
+                    height: calibration.projector_height,
+                    display_index: index,
+                    canvas_x,
+                    canvas_y: 0,
+                    homography,
+                    blend: BlendConfig {
+                        curve,
+                        ..BlendConfig::default()
+                    },
+                    correspondence_file: None,
+                    uv_map_file: None,
+                    blend_mask_file: None,
+                    color_correction: None,
+                    lut_file: None,
+                    warp_correction: None,
+                };
+                canvas_x += calibration.projector_width as i32;
+                canvas_height = canvas_height.max(calibration.projector_height);
+                config
+            })
+            .collect();
+
+        if let Some(result) = overlaps {
+            for overlap in &result.overlaps {
+                let opposite = match overlap.edge {
+                    OverlapEdge::Left => OverlapEdge::Right,
+                    OverlapEdge::Right => OverlapEdge::Left,
+                    OverlapEdge::Top => OverlapEdge::Bottom,
+                    OverlapEdge::Bottom => OverlapEdge::Top,
+                };
+                for (id, edge) in [(overlap.projector_a, overlap.edge), (overlap.projector_b, opposite)] {
+                    if let Some(projector) = projectors.iter_mut().find(|p| p.id == id) {
+                        let width = match edge {
+                            OverlapEdge::Left => &mut projector.blend.left_width,
+                            OverlapEdge::Right => &mut projector.blend.right_width,
+                            OverlapEdge::Top => &mut projector.blend.top_width,
+                            OverlapEdge::Bottom => &mut projector.blend.bottom_width,
+                        };
+                        *width = (*width).max(overlap.overlap_width);
+                    }
+                }
+            }
+        }
+
+        ProjectConfig {
+            schema_version: SCHEMA_VERSION,
+            name: name.to_string(),
+            canvas_width: (canvas_x.max(0) as u32).max(1),
+            canvas_height: canvas_height.max(1),
+            camera_width: session.config.camera_width,
+            camera_height: session.config.camera_height,
+            projectors,
+            camera_source: None,
+            extra_camera_sources: Vec::new(),
+        }
+    }
+
+    /// Build a per-pixel UV map from a decoded correspondence grid.
+    ///
+    /// The grid should be dense (see [`UV_MAP_GRID_SPACING`]); it is
+    /// bilinearly upsampled to the projector's full resolution.
+    pub fn uv_map_from_correspondences(map: &CorrespondenceMap) -> UvMap {
+        UvMap::from_correspondence_map(map, map.projector_width, map.projector_height)
+    }
+
+    /// Export a calibration for immersive-server.
+    ///
+    /// Writes one `<stem>_projector_<id>.json` correspondence map, one
+    /// `<stem>_projector_<id>.pmuv` UV map, one `<stem>_projector_<id>_blend.png`
+    /// 16-bit blend mask and one `<stem>_projector_<id>.cube` color matching
+    /// LUT next to the project file for every projector that has them, records
+    /// the relative file names (and the fitted color correction) in the
+    /// project, then writes the project itself (JSON when the extension is
+    /// `.json`, XML otherwise).
+    ///
+    /// `blend_masks` lines up with `uv_maps` (see
+    /// [`footprint_blend_masks`](crate::blending::footprint_blend_masks)).
+    pub fn export_calibration(
+        project: &mut ProjectConfig,
+        maps: &[CorrespondenceMap],
+        uv_maps: &[UvMap],
+        blend_masks: &[BlendMask],
+        color_matches: &[ColorMatch],
+        path: &Path,
+    ) -> std::io::Result<()> {
+        let dir = path.parent().unwrap_or_else(|| Path::new("."));
+        let stem = path
+            .file_stem()
+            .map(|s| s.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "calibration".to_string());
+
+        for projector in &mut project.projectors {
+            projector.correspondence_file = None;
+            if let Some(map) = maps.iter().find(|m| m.projector_id == projector.id) {
+                let filename = format!("{}_projector_{}.json", stem, projector.id);
+                let json = serde_json::to_string(map)
+                    .map_err(std::io::Error::other)?;
+                std::fs::write(dir.join(&filename), json)?;
+                log::info!("Exported correspondence map: {}", filename);
+                projector.correspondence_file = Some(filename);
+            }
+
+            projector.uv_map_file = None;
+            projector.blend_mask_file = None;
+            if let Some(index) = uv_maps.iter().position(|m| m.projector_id == projector.id) {
+                let filename = format!("{}_projector_{}.pmuv", stem, projector.id);
+                std::fs::write(dir.join(&filename), uv_maps[index].to_bytes())?;
+                log::info!("Exported UV map: {}", filename);
+                projector.uv_map_file = Some(filename);
+
+                if let Some(mask) = blend_masks.get(index) {
+                    let filename = format!("{}_projector_{}_blend.png", stem, projector.id);
+                    Self::export_blend_mask_16bit(mask, &dir.join(&filename))?;
+                    log::info!("Exported blend mask: {}", filename);
+                    projector.blend_mask_file = Some(filename);
+                }
+            }
+
+            projector.color_correction = None;
+            projector.lut_file = None;
+            if let Some(color) = color_matches.iter().find(|m| m.projector_id == projector.id) {
+                let filename = format!("{}_projector_{}.cube", stem, projector.id);
+                std::fs::write(dir.join(&filename), color.to_cube_string(COLOR_LUT_SIZE))?;
+                log::info!("Exported color LUT: {}", filename);
+                projector.color_correction = Some(color.correction);
+                projector.lut_file = Some(filename);
+            }
+        }
+
+        save_project(project, path)
+    }
+
     /// Export blend mask as 8-bit grayscale PNG image.
     pub fn export_blend_mask(mask: &BlendMask, path: &Path) -> std::io::Result<()> {
         let img = image::GrayImage::from_fn(mask.width, mask.height, |x, y| {
@@ -82,11 +257,46 @@ pub fn load_project(path: &Path) -> std::io::Result<ProjectConfig> {
     let contents = std::fs::read_to_string(path)?;
 
     // Try JSON first, then XML
-    if path.extension().map(|e| e == "json").unwrap_or(false) {
+    let project: ProjectConfig = if path.extension().map(|e| e == "json").unwrap_or(false) {
         serde_json::from_str(&contents)
-            .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))?
     } else {
         quick_xml::de::from_str(&contents)
-            .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))?
+    };
+
+    project
+        .check_version()
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+    Ok(project)
+}
+
+/// Save project configuration (JSON when the extension is `.json`, XML otherwise).
+pub fn save_project(project: &ProjectConfig, path: &Path) -> std::io::Result<()> {
+    if path.extension().map(|e| e == "json").unwrap_or(false) {
+        CalibrationExporter::export_json(project, path)
+    } else {
+        CalibrationExporter::export_xml(project, path)
     }
 }
+
+/// Load a grayscale blend mask PNG (8 or 16-bit).
+pub fn load_blend_mask(path: &Path, curve: BlendCurve) -> std::io::Result<BlendMask> {
+    let image = image::open(path).map_err(std::io::Error::other)?.to_luma16();
+    Ok(BlendMask {
+        width: image.width(),
+        height: image.height(),
+        data: image.pixels().map(|p| p.0[0] as f32 / 65535.0).collect(),
+        curve,
+    })
+}
+
+/// Load a dense correspondence map written by [`CalibrationExporter::export_calibration`].
+pub fn load_correspondence_map(path: &Path) -> std::io::Result<CorrespondenceMap> {
+    let contents = std::fs::read_to_string(path)?;
+    let map: CorrespondenceMap = serde_json::from_str(&contents)
+        .map_err(std::io::Error::other)?;
+    map.validate()
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+    Ok(map)
+}
@@ -5,9 +5,12 @@
 //! - Structured light (Gray code) patterns
 //! - Multi-projector edge blending
 //! - OpenCV for homography computation
+//! - A command-line interface for scripted, headless runs
+//! - Remote pattern output over NDI or the immersive-server REST API
 
 pub mod app;
 pub mod calibration;
+pub mod cli;
 pub mod camera;
 pub mod config;
 pub mod blending;
@@ -15,3 +18,4 @@ pub mod projector;
 pub mod render;
 pub mod ui;
 pub mod export;
+pub mod output;
@@ -2,15 +2,28 @@
 //!
 //! Entry point for the projmap-calibrator application.
 
+use clap::Parser;
 use projmap_calibrator::app::CalibrationApp;
+use projmap_calibrator::cli::Cli;
 use winit::event_loop::EventLoop;
 
 fn main() {
+    let cli = Cli::parse();
+
     // Initialize logging
     env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info"))
         .format_timestamp_millis()
         .init();
 
+    // Scripted steps run without a window or NDI
+    if let Some(command) = cli.command {
+        if let Err(e) = projmap_calibrator::cli::run(command) {
+            log::error!("{}", e);
+            std::process::exit(1);
+        }
+        return;
+    }
+
     log::info!("ProjMap Calibrator starting...");
 
     // Initialize NDI
@@ -0,0 +1,15 @@
+//! Pattern output module for remote calibration.
+//!
+//! Sends calibration patterns to the machine the projectors are attached to:
+//! as NDI streams, or as immersive-server calibration patterns over its REST
+//! API. Without an output the patterns are shown locally.
+
+mod ndi_output;
+mod presenter;
+mod server_output;
+mod sink;
+
+pub use ndi_output::NdiPatternSender;
+pub use presenter::{render_pattern, PatternPresenter};
+pub use server_output::{RemoteScreen, ServerOutput};
+pub use sink::{OutputError, PatternOutput, PatternOutputKind};
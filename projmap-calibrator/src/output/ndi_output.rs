@@ -0,0 +1,139 @@
+//! NDI pattern output.
+//!
+//! Publishes one NDI stream per projector. The machine driving the projectors
+//! (e.g. immersive-server with an NDI clip on the projector's screen) receives
+//! the stream and displays it through its real output path.
+
+use crate::camera::ndi_ffi::*;
+use image::GrayImage;
+use std::collections::HashMap;
+use std::ffi::CString;
+use std::time::{Duration, Instant};
+
+use super::sink::{OutputError, PatternOutput};
+
+/// How often an unchanged pattern is re-sent, so late receivers pick it up.
+const REPEAT_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Frame rate advertised to receivers.
+const FRAME_RATE: i32 = 30;
+
+/// Network transfer plus a receiver's frame buffering.
+const OUTPUT_LATENCY: Duration = Duration::from_millis(150);
+
+/// One projector's NDI stream and the frame it shows.
+struct ProjectorStream {
+    sender: NDIlib_send_instance_t,
+    width: u32,
+    height: u32,
+    /// BGRA pixels of the current pattern.
+    frame: Vec<u8>,
+    last_sent: Instant,
+}
+
+impl ProjectorStream {
+    fn create(name: &str) -> Result<Self, OutputError> {
+        let c_name = CString::new(name).map_err(|_| OutputError::Ndi(format!("Invalid stream name '{}'", name)))?;
+        let settings = NDIlib_send_create_t {
+            p_ndi_name: c_name.as_ptr(),
+            ..Default::default()
+        };
+        let sender = unsafe { NDIlib_send_create(&settings) };
+        if sender.is_null() {
+            return Err(OutputError::Ndi(format!("Failed to create sender '{}'", name)));
+        }
+        log::info!("NDI Output: Created stream '{}'", name);
+        Ok(Self {
+            sender,
+            width: 0,
+            height: 0,
+            frame: Vec::new(),
+            last_sent: Instant::now(),
+        })
+    }
+
+    fn send(&mut self) {
+        let frame = NDIlib_video_frame_v2_t {
+            xres: self.width as i32,
+            yres: self.height as i32,
+            FourCC: NDIlib_FourCC_video_type_e::BGRA,
+            frame_rate_N: FRAME_RATE,
+            frame_rate_D: 1,
+            picture_aspect_ratio: self.width as f32 / self.height.max(1) as f32,
+            timecode: NDILIB_SEND_TIMECODE_SYNTHESIZE,
+            p_data: self.frame.as_ptr() as *mut u8,
+            line_stride_in_bytes: (self.width * 4) as i32,
+            ..Default::default()
+        };
+        unsafe { NDIlib_send_send_video_v2(self.sender, &frame) };
+        self.last_sent = Instant::now();
+    }
+}
+
+impl Drop for ProjectorStream {
+    fn drop(&mut self) {
+        unsafe { NDIlib_send_destroy(self.sender) };
+    }
+}
+
+/// NDI sender for calibration patterns.
+pub struct NdiPatternSender {
+    prefix: String,
+    streams: HashMap<u32, ProjectorStream>,
+}
+
+unsafe impl Send for NdiPatternSender {}
+
+impl NdiPatternSender {
+    /// Streams are created on first use, named `<prefix> Projector <id>`.
+    pub fn new(prefix: &str) -> Self {
+        Self {
+            prefix: prefix.to_string(),
+            streams: HashMap::new(),
+        }
+    }
+
+    /// Stream name for a projector.
+    pub fn stream_name(&self, projector_id: u32) -> String {
+        format!("{} Projector {}", self.prefix, projector_id)
+    }
+}
+
+impl PatternOutput for NdiPatternSender {
+    fn output_name(&self) -> String {
+        format!("NDI: {}", self.prefix)
+    }
+
+    fn show(&mut self, projector_id: u32, pattern: &GrayImage) -> Result<(), OutputError> {
+        if !self.streams.contains_key(&projector_id) {
+            let stream = ProjectorStream::create(&self.stream_name(projector_id))?;
+            self.streams.insert(projector_id, stream);
+        }
+        let stream = self.streams.get_mut(&projector_id).expect("stream created above");
+        stream.width = pattern.width();
+        stream.height = pattern.height();
+        stream.frame = pattern.as_raw().iter().flat_map(|&v| [v, v, v, 255]).collect();
+        stream.send();
+        Ok(())
+    }
+
+    fn clear(&mut self, projector_id: u32) -> Result<(), OutputError> {
+        // Receivers fall back to their own content once the stream is gone
+        if self.streams.remove(&projector_id).is_some() {
+            log::info!("NDI Output: Closed stream '{}'", self.stream_name(projector_id));
+        }
+        Ok(())
+    }
+
+    fn latency(&self) -> Duration {
+        OUTPUT_LATENCY
+    }
+
+    fn keep_alive(&mut self) {
+        for stream in self.streams.values_mut() {
+            if stream.last_sent.elapsed() >= REPEAT_INTERVAL {
+                stream.send();
+            }
+        }
+    }
+}
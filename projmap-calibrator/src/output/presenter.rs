@@ -0,0 +1,170 @@
+//! Drives a pattern output from a calibration session.
+
+use image::GrayImage;
+use std::time::Duration;
+
+use super::sink::{OutputError, PatternOutput};
+use crate::calibration::{CalibrationSession, CurrentPattern, GrayCodeGenerator, PatternConfig};
+
+/// Render a session pattern at projector resolution.
+pub fn render_pattern(pattern: &CurrentPattern, config: &PatternConfig) -> GrayImage {
+    let generator = GrayCodeGenerator::from_config(config.clone());
+    let data = match pattern {
+        CurrentPattern::White => generator.generate_white(),
+        CurrentPattern::Black => generator.generate_black(),
+        CurrentPattern::GrayCode(spec) => generator.generate_pattern(spec),
+        CurrentPattern::Verification(image) => return (**image).clone(),
+    };
+    GrayImage::from_raw(config.projector_width, config.projector_height, data)
+        .expect("pattern generator matches its configuration")
+}
+
+/// Sends whatever the session wants displayed to a [`PatternOutput`].
+///
+/// The projector being captured shows the pattern and every other projector
+/// in the session shows black; when the run ends they all return to their
+/// normal content. Patterns are only sent when they change.
+pub struct PatternPresenter {
+    output: Box<dyn PatternOutput>,
+    /// Projector and pattern currently displayed.
+    shown: Option<(u32, CurrentPattern)>,
+    /// Projectors showing a pattern or black.
+    active: Vec<u32>,
+}
+
+impl PatternPresenter {
+    pub fn new(output: Box<dyn PatternOutput>) -> Self {
+        Self {
+            output,
+            shown: None,
+            active: Vec::new(),
+        }
+    }
+
+    /// Human-readable output name.
+    pub fn output_name(&self) -> String {
+        self.output.output_name()
+    }
+
+    /// Extra settle time the output needs.
+    pub fn latency(&self) -> Duration {
+        self.output.latency()
+    }
+
+    /// Bring the output in line with the session. Call once per frame, after
+    /// [`CalibrationSession::update`].
+    pub fn update(&mut self, session: &CalibrationSession) -> Result<(), OutputError> {
+        let wanted = session.current_projector_id().zip(session.current_pattern());
+        if wanted == self.shown {
+            self.output.keep_alive();
+            return Ok(());
+        }
+
+        match &wanted {
+            Some((projector_id, pattern)) => {
+                let Some(projector) = session.projectors.iter().find(|p| p.projector_id == *projector_id) else {
+                    return Ok(());
+                };
+                let switched = self.shown.as_ref().map(|(id, _)| id) != Some(projector_id);
+                if switched {
+                    for other in session.projectors.iter().filter(|p| p.projector_id != *projector_id) {
+                        let black = GrayImage::new(other.projector_width, other.projector_height);
+                        self.output.show(other.projector_id, &black)?;
+                        self.mark_active(other.projector_id);
+                    }
+                }
+                self.output.show(*projector_id, &render_pattern(pattern, &projector.pattern_config))?;
+                self.mark_active(*projector_id);
+            }
+            None => self.clear_all()?,
+        }
+
+        self.shown = wanted;
+        Ok(())
+    }
+
+    /// Show an image outside a calibration run, e.g. a touch-up test grid.
+    /// It stays up until [`clear_all`](Self::clear_all).
+    pub fn show_image(&mut self, projector_id: u32, image: &GrayImage) -> Result<(), OutputError> {
+        self.output.show(projector_id, image)?;
+        self.mark_active(projector_id);
+        Ok(())
+    }
+
+    /// Return every projector touched so far to its normal content.
+    pub fn clear_all(&mut self) -> Result<(), OutputError> {
+        self.shown = None;
+        while let Some(projector_id) = self.active.pop() {
+            self.output.clear(projector_id)?;
+        }
+        Ok(())
+    }
+
+    fn mark_active(&mut self, projector_id: u32) {
+        if !self.active.contains(&projector_id) {
+            self.active.push(projector_id);
+        }
+    }
+}
+
+impl Drop for PatternPresenter {
+    fn drop(&mut self) {
+        if let Err(e) = self.clear_all() {
+            log::warn!("Failed to clear patterns on {}: {}", self.output.output_name(), e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calibration::CalibrationConfig;
+    use std::sync::{Arc, Mutex};
+
+    /// Records calls as (projector, mean level) for shows and (projector, -1) for clears.
+    struct RecordingOutput(Arc<Mutex<Vec<(u32, i32)>>>);
+
+    impl PatternOutput for RecordingOutput {
+        fn output_name(&self) -> String {
+            "recording".to_string()
+        }
+
+        fn show(&mut self, projector_id: u32, pattern: &GrayImage) -> Result<(), OutputError> {
+            let sum: u64 = pattern.as_raw().iter().map(|&v| v as u64).sum();
+            let mean = (sum / pattern.as_raw().len() as u64) as i32;
+            self.0.lock().unwrap().push((projector_id, mean));
+            Ok(())
+        }
+
+        fn clear(&mut self, projector_id: u32) -> Result<(), OutputError> {
+            self.0.lock().unwrap().push((projector_id, -1));
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_presenter_blacks_out_other_projectors_and_clears() {
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let mut presenter = PatternPresenter::new(Box::new(RecordingOutput(Arc::clone(&calls))));
+
+        let mut session = CalibrationSession::new(CalibrationConfig::default());
+        session.add_projector(0, 64, 32);
+        session.add_projector(1, 48, 24);
+        session.start().unwrap();
+
+        // White reference on projector 0, black on projector 1
+        presenter.update(&session).unwrap();
+        assert_eq!(*calls.lock().unwrap(), vec![(1, 0), (0, 255)]);
+
+        // Unchanged pattern is not sent again
+        presenter.update(&session).unwrap();
+        assert_eq!(calls.lock().unwrap().len(), 2);
+
+        // Ending the run returns both projectors to their content
+        session.cancel();
+        presenter.update(&session).unwrap();
+        let calls = calls.lock().unwrap();
+        assert_eq!(calls.len(), 4);
+        assert!(calls[2..].contains(&(0, -1)) && calls[2..].contains(&(1, -1)));
+    }
+}
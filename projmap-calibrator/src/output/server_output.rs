@@ -0,0 +1,157 @@
+//! immersive-server pattern output.
+//!
+//! Shows patterns on immersive-server screens through its REST API
+//! (`PUT`/`DELETE /api/screens/:id/calibration-pattern`), so calibration runs
+//! against the machine the projectors are actually connected to.
+
+use image::GrayImage;
+use serde::Deserialize;
+use std::io::{Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::time::Duration;
+
+use super::sink::{OutputError, PatternOutput};
+
+/// Connect, read and write timeout for API requests.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// The server applies a pattern on its next frame; allow for a few frames of
+/// output and projector delay on top (the screen's own delay is not included).
+const OUTPUT_LATENCY: Duration = Duration::from_millis(100);
+
+/// Screen as listed by `GET /api/screens`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct RemoteScreen {
+    pub id: u32,
+    pub name: String,
+    pub width: u32,
+    pub height: u32,
+}
+
+#[derive(Deserialize)]
+struct ScreensResponse {
+    screens: Vec<RemoteScreen>,
+}
+
+/// REST client that shows patterns on immersive-server screens.
+pub struct ServerOutput {
+    /// `host:port` of the API server.
+    address: String,
+    /// Screen shown by projector 0; projector `n` uses `first_screen + n`.
+    first_screen: u32,
+    /// Screens reported when connecting.
+    screens: Vec<RemoteScreen>,
+}
+
+impl ServerOutput {
+    /// Connect to an API server (`host:port`, optionally with `http://`).
+    pub fn connect(address: &str, first_screen: u32) -> Result<Self, OutputError> {
+        let address = address
+            .trim()
+            .trim_start_matches("http://")
+            .trim_end_matches('/')
+            .to_string();
+        let mut output = Self {
+            address,
+            first_screen,
+            screens: Vec::new(),
+        };
+
+        let body = output.request("GET", "/api/screens", None)?;
+        let response: ScreensResponse = serde_json::from_slice(&body)
+            .map_err(|e| OutputError::Server(format!("Unexpected screen list: {}", e)))?;
+        output.screens = response.screens;
+        log::info!(
+            "immersive-server Output: Connected to {} ({} screens)",
+            output.address,
+            output.screens.len()
+        );
+        Ok(output)
+    }
+
+    /// Screens reported by the server.
+    pub fn screens(&self) -> &[RemoteScreen] {
+        &self.screens
+    }
+
+    /// Screen a projector is shown on.
+    pub fn screen_for(&self, projector_id: u32) -> u32 {
+        self.first_screen + projector_id
+    }
+
+    /// Send one HTTP/1.1 request and return the response body.
+    fn request(&self, method: &str, path: &str, body: Option<(&str, &[u8])>) -> Result<Vec<u8>, OutputError> {
+        let error = |e: std::io::Error| OutputError::Server(format!("{} {}: {}", method, path, e));
+
+        let addr = self
+            .address
+            .to_socket_addrs()
+            .map_err(error)?
+            .next()
+            .ok_or_else(|| OutputError::Server(format!("Cannot resolve '{}'", self.address)))?;
+        let mut stream = TcpStream::connect_timeout(&addr, REQUEST_TIMEOUT).map_err(error)?;
+        stream.set_read_timeout(Some(REQUEST_TIMEOUT)).map_err(error)?;
+        stream.set_write_timeout(Some(REQUEST_TIMEOUT)).map_err(error)?;
+
+        let (content_type, payload) = body.unwrap_or(("application/octet-stream", &[]));
+        let head = format!(
+            "{} {} HTTP/1.1\r\nHost: {}\r\nConnection: close\r\nContent-Type: {}\r\nContent-Length: {}\r\n\r\n",
+            method,
+            path,
+            self.address,
+            content_type,
+            payload.len()
+        );
+        stream.write_all(head.as_bytes()).map_err(error)?;
+        stream.write_all(payload).map_err(error)?;
+
+        let mut response = Vec::new();
+        stream.read_to_end(&mut response).map_err(error)?;
+        let (status, body) = parse_response(&response)
+            .ok_or_else(|| OutputError::Server(format!("{} {}: malformed response", method, path)))?;
+        if !(200..300).contains(&status) {
+            return Err(OutputError::Server(format!(
+                "{} {} returned {}: {}",
+                method,
+                path,
+                status,
+                String::from_utf8_lossy(body).trim()
+            )));
+        }
+        Ok(body.to_vec())
+    }
+}
+
+/// Status code and body of an HTTP/1.1 response read until the connection closed.
+fn parse_response(response: &[u8]) -> Option<(u16, &[u8])> {
+    let split = response.windows(4).position(|w| w == b"\r\n\r\n")?;
+    let head = std::str::from_utf8(&response[..split]).ok()?;
+    let status = head.lines().next()?.split_whitespace().nth(1)?.parse().ok()?;
+    Some((status, &response[split + 4..]))
+}
+
+impl PatternOutput for ServerOutput {
+    fn output_name(&self) -> String {
+        format!("immersive-server: {}", self.address)
+    }
+
+    fn show(&mut self, projector_id: u32, pattern: &GrayImage) -> Result<(), OutputError> {
+        let mut png = Vec::new();
+        pattern
+            .write_to(&mut std::io::Cursor::new(&mut png), image::ImageFormat::Png)
+            .map_err(|e| OutputError::Server(format!("Failed to encode pattern: {}", e)))?;
+        let path = format!("/api/screens/{}/calibration-pattern", self.screen_for(projector_id));
+        self.request("PUT", &path, Some(("image/png", &png)))?;
+        Ok(())
+    }
+
+    fn latency(&self) -> Duration {
+        OUTPUT_LATENCY
+    }
+
+    fn clear(&mut self, projector_id: u32) -> Result<(), OutputError> {
+        let path = format!("/api/screens/{}/calibration-pattern", self.screen_for(projector_id));
+        self.request("DELETE", &path, None)?;
+        Ok(())
+    }
+}
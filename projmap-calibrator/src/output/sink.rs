@@ -0,0 +1,79 @@
+//! Pattern output abstraction.
+//!
+//! Every backend receives grayscale patterns at projector resolution through
+//! [`PatternOutput`], so the presenter does not care how they reach the
+//! projector.
+
+use image::GrayImage;
+use std::time::Duration;
+
+use super::ndi_output::NdiPatternSender;
+use super::server_output::ServerOutput;
+
+/// A destination for calibration patterns.
+pub trait PatternOutput: Send {
+    /// Human-readable output name.
+    fn output_name(&self) -> String;
+
+    /// Display a pattern on a projector, replacing what it showed before.
+    fn show(&mut self, projector_id: u32, pattern: &GrayImage) -> Result<(), OutputError>;
+
+    /// Return a projector to its normal content.
+    fn clear(&mut self, projector_id: u32) -> Result<(), OutputError>;
+
+    /// Called every frame; streaming backends repeat their last frames here.
+    fn keep_alive(&mut self) {}
+
+    /// Extra time a pattern needs to reach the projector, added to the settle time.
+    fn latency(&self) -> Duration {
+        Duration::ZERO
+    }
+}
+
+/// Pattern output error type.
+#[derive(Debug, Clone)]
+pub enum OutputError {
+    Ndi(String),
+    Server(String),
+}
+
+impl std::fmt::Display for OutputError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OutputError::Ndi(msg) => write!(f, "NDI output error: {}", msg),
+            OutputError::Server(msg) => write!(f, "immersive-server error: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for OutputError {}
+
+/// Where calibration patterns are sent.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PatternOutputKind {
+    /// One NDI stream per projector, named `<prefix> Projector <id>`.
+    Ndi { prefix: String },
+    /// immersive-server REST API at `host:port`; projector `n` maps to
+    /// screen `first_screen + n`.
+    Server { address: String, first_screen: u32 },
+}
+
+impl PatternOutputKind {
+    /// Label for output pickers.
+    pub fn label(&self) -> String {
+        match self {
+            PatternOutputKind::Ndi { prefix } => format!("NDI: {}", prefix),
+            PatternOutputKind::Server { address, .. } => format!("immersive-server: {}", address),
+        }
+    }
+
+    /// Open the output.
+    pub fn connect(&self) -> Result<Box<dyn PatternOutput>, OutputError> {
+        match self {
+            PatternOutputKind::Ndi { prefix } => Ok(Box::new(NdiPatternSender::new(prefix))),
+            PatternOutputKind::Server { address, first_screen } => {
+                Ok(Box::new(ServerOutput::connect(address, *first_screen)?))
+            }
+        }
+    }
+}
@@ -11,6 +11,8 @@ pub enum PatternType {
     GrayCode = 0,
     White = 1,
     Black = 2,
+    Solid = 3,
+    PhaseShift = 4,
 }
 
 /// Uniform buffer for pattern parameters.
@@ -24,7 +26,9 @@ struct PatternParams {
     proj_width: f32,
     proj_height: f32,
     pattern_type: u32,
-    _padding: u32,
+    /// Fringe period in pixels (phase shift only).
+    period: u32,
+    color: [f32; 4],
 }
 
 /// GPU-accelerated Gray code pattern renderer.
@@ -112,7 +116,8 @@ impl PatternRenderer {
             proj_width: width as f32,
             proj_height: height as f32,
             pattern_type: PatternType::GrayCode as u32,
-            _padding: 0,
+            period: 0,
+            color: [0.0; 4],
         };
 
         let params_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
@@ -158,7 +163,8 @@ impl PatternRenderer {
             proj_width: width as f32,
             proj_height: height as f32,
             pattern_type: PatternType::GrayCode as u32,
-            _padding: 0,
+            period: 0,
+            color: [0.0; 4],
         };
 
         self.params_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
@@ -177,7 +183,7 @@ impl PatternRenderer {
         });
     }
 
-    /// Render a Gray code pattern.
+    /// Render a Gray code pattern or phase-shift fringe.
     pub fn render_pattern<'a>(
         &'a self,
         render_pass: &mut wgpu::RenderPass<'a>,
@@ -189,18 +195,35 @@ impl PatternRenderer {
             PatternDirection::Vertical => self.horizontal_bits,
         };
 
-        let params = PatternParams {
-            bit_index: spec.bit_index,
-            total_bits,
-            direction: match spec.direction {
-                PatternDirection::Horizontal => 0,
-                PatternDirection::Vertical => 1,
+        let direction = match spec.direction {
+            PatternDirection::Horizontal => 0,
+            PatternDirection::Vertical => 1,
+        };
+
+        // Fringe images reuse bit_index/total_bits as step/steps
+        let params = match &spec.phase {
+            Some(phase) => PatternParams {
+                bit_index: phase.step,
+                total_bits: phase.config.steps,
+                direction,
+                inverted: 0,
+                proj_width: self.proj_width as f32,
+                proj_height: self.proj_height as f32,
+                pattern_type: PatternType::PhaseShift as u32,
+                period: phase.config.period,
+                color: [0.0; 4],
+            },
+            None => PatternParams {
+                bit_index: spec.bit_index,
+                total_bits,
+                direction,
+                inverted: if spec.inverted { 1 } else { 0 },
+                proj_width: self.proj_width as f32,
+                proj_height: self.proj_height as f32,
+                pattern_type: PatternType::GrayCode as u32,
+                period: 0,
+                color: [0.0; 4],
             },
-            inverted: if spec.inverted { 1 } else { 0 },
-            proj_width: self.proj_width as f32,
-            proj_height: self.proj_height as f32,
-            pattern_type: PatternType::GrayCode as u32,
-            _padding: 0,
         };
 
         queue.write_buffer(&self.params_buffer, 0, bytemuck::cast_slice(&[params]));
@@ -220,7 +243,8 @@ impl PatternRenderer {
             proj_width: self.proj_width as f32,
             proj_height: self.proj_height as f32,
             pattern_type: PatternType::White as u32,
-            _padding: 0,
+            period: 0,
+            color: [0.0; 4],
         };
 
         queue.write_buffer(&self.params_buffer, 0, bytemuck::cast_slice(&[params]));
@@ -240,7 +264,34 @@ impl PatternRenderer {
             proj_width: self.proj_width as f32,
             proj_height: self.proj_height as f32,
             pattern_type: PatternType::Black as u32,
-            _padding: 0,
+            period: 0,
+            color: [0.0; 4],
+        };
+
+        queue.write_buffer(&self.params_buffer, 0, bytemuck::cast_slice(&[params]));
+
+        render_pass.set_pipeline(&self.pipeline);
+        render_pass.set_bind_group(0, &self.bind_group, &[]);
+        render_pass.draw(0..3, 0..1);
+    }
+
+    /// Render a solid color (photometric calibration).
+    pub fn render_solid<'a>(
+        &'a self,
+        render_pass: &mut wgpu::RenderPass<'a>,
+        queue: &wgpu::Queue,
+        color: [f32; 3],
+    ) {
+        let params = PatternParams {
+            bit_index: 0,
+            total_bits: 0,
+            direction: 0,
+            inverted: 0,
+            proj_width: self.proj_width as f32,
+            proj_height: self.proj_height as f32,
+            pattern_type: PatternType::Solid as u32,
+            period: 0,
+            color: [color[0], color[1], color[2], 1.0],
         };
 
         queue.write_buffer(&self.params_buffer, 0, bytemuck::cast_slice(&[params]));
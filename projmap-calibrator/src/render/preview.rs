@@ -193,6 +193,11 @@ impl PreviewRenderer {
         }
     }
 
+    /// View of the frame texture, for showing it in the UI.
+    pub fn texture_view(&self) -> Option<&wgpu::TextureView> {
+        self.texture_view.as_ref()
+    }
+
     /// Check if a frame has been uploaded.
     pub fn has_frame(&self) -> bool {
         self.texture.is_some()
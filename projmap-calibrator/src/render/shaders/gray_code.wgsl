@@ -17,9 +17,12 @@ struct PatternParams {
     // Projector dimensions
     proj_width: f32,
     proj_height: f32,
-    // Pattern type: 0 = gray code, 1 = white, 2 = black
+    // Pattern type: 0 = gray code, 1 = white, 2 = black, 3 = solid color, 4 = phase shift
+    // (phase shift uses bit_index as the step and total_bits as the step count)
     pattern_type: u32,
-    _padding: u32,
+    period: u32,           // Fringe period in pixels (phase shift)
+    // Solid color (pattern type 3)
+    color: vec4<f32>,
 };
 
 @group(0) @binding(0)
@@ -54,6 +57,9 @@ fn fs_main(input: VertexOutput) -> @location(0) vec4<f32> {
     if (params.pattern_type == 2u) {
         return vec4<f32>(0.0, 0.0, 0.0, 1.0); // Black
     }
+    if (params.pattern_type == 3u) {
+        return params.color;
+    }
 
     // Calculate pixel coordinate in projector space
     let px = u32(input.tex_coord.x * params.proj_width);
@@ -67,6 +73,13 @@ fn fs_main(input: VertexOutput) -> @location(0) vec4<f32> {
         coord = px; // Vertical stripes encode X coordinate
     }
 
+    // Sinusoidal fringe, shifted by step / steps of a period
+    if (params.pattern_type == 4u) {
+        let cycles = f32(coord) / f32(params.period) - f32(params.bit_index) / f32(params.total_bits);
+        let fringe = 0.5 + 0.5 * cos(6.28318530718 * cycles);
+        return vec4<f32>(fringe, fringe, fringe, 1.0);
+    }
+
     // Convert to Gray code
     let gray = binary_to_gray(coord);
 
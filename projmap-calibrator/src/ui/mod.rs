@@ -1,6 +1,11 @@
 //! UI module for calibration workflow.
 
+mod touch_up;
+
+pub use touch_up::{correction_overlay, OverlayResponse};
+
 use crate::blending::OverlapDetectionResult;
+use crate::calibration::{ContentSpace, ReconstructionConfig};
 
 /// UI state for the calibration application.
 pub struct UiState {
@@ -18,6 +23,33 @@ pub struct UiState {
     pub show_preview: bool,
     /// Detected overlap result.
     pub overlap_result: Option<OverlapDetectionResult>,
+    /// Ramp levels per channel for color matching.
+    pub color_ramp_levels: usize,
+    /// Add phase-shift fringes for sub-pixel calibration.
+    pub phase_shift: bool,
+    /// Grab a checkerboard view from the next camera frame.
+    pub capture_checkerboard: bool,
+    /// Delay before a watched folder's images count for a pattern, in seconds.
+    pub folder_capture_delay: f32,
+    /// Camera that checkerboard views are captured from (0 is the primary camera).
+    pub checkerboard_camera: usize,
+    /// Content space for non-planar calibration.
+    pub content_space: ContentSpace,
+    /// Projector lens guess and solver settings for non-planar calibration.
+    pub reconstruction: ReconstructionConfig,
+    /// Stream name prefix for NDI pattern output.
+    pub ndi_output_prefix: String,
+    /// immersive-server API address (`host:port`) for pattern output.
+    pub server_address: String,
+    /// immersive-server screen that shows projector 1's patterns.
+    pub server_first_screen: u32,
+    /// Projector whose control points are edited during touch-up.
+    pub touch_up_projector: u32,
+    /// Touch-up test grid cells across the content width.
+    pub touch_up_grid_divisions: u32,
+    /// Touch-up handles are drawn over the camera preview (content space is
+    /// the primary camera image) rather than on a blank content view.
+    pub touch_up_over_camera: bool,
 }
 
 impl Default for UiState {
@@ -30,6 +62,19 @@ impl Default for UiState {
             blend_curve: "Gamma".to_string(),
             show_preview: true,
             overlap_result: None,
+            color_ramp_levels: 9,
+            phase_shift: false,
+            capture_checkerboard: false,
+            folder_capture_delay: 2.0,
+            checkerboard_camera: 0,
+            content_space: ContentSpace::default(),
+            reconstruction: ReconstructionConfig::default(),
+            ndi_output_prefix: "Calibrator".to_string(),
+            server_address: "localhost:8080".to_string(),
+            server_first_screen: 1,
+            touch_up_projector: 0,
+            touch_up_grid_divisions: 32,
+            touch_up_over_camera: true,
         }
     }
 }
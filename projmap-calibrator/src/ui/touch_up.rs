@@ -0,0 +1,78 @@
+//! Draggable correction mesh drawn over the preview.
+
+use crate::config::WarpCorrection;
+
+/// What happened to the mesh this frame.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct OverlayResponse {
+    /// A control point moved.
+    pub changed: bool,
+    /// A drag ended, so the projected test grid should be refreshed.
+    pub released: bool,
+}
+
+const HANDLE_RADIUS: f32 = 5.0;
+
+/// Draw a projector's control points and let them be dragged.
+///
+/// `handles` holds where each control point's content lands, in normalized
+/// content coordinates (see [`TouchUp::handles`](crate::calibration::TouchUp::handles));
+/// `rect` is the content space on screen. Dragging a handle moves its
+/// content by the same amount.
+pub fn correction_overlay(
+    ui: &mut egui::Ui,
+    rect: egui::Rect,
+    handles: &[Option<[f32; 2]>],
+    correction: &mut WarpCorrection,
+) -> OverlayResponse {
+    let to_screen = |p: [f32; 2]| rect.min + egui::vec2(p[0] * rect.width(), p[1] * rect.height());
+    let columns = correction.columns as usize;
+    let painter = ui.painter_at(rect);
+    let line = egui::Stroke::new(1.0, egui::Color32::from_rgb(255, 200, 0));
+
+    // Mesh lines between neighbouring control points
+    for (i, handle) in handles.iter().enumerate() {
+        let Some(p) = handle else {
+            continue;
+        };
+        let right = (i % columns + 1 < columns).then(|| handles.get(i + 1)).flatten();
+        let below = handles.get(i + columns);
+        for neighbour in [right, below].into_iter().flatten().flatten() {
+            painter.line_segment([to_screen(*p), to_screen(*neighbour)], line);
+        }
+    }
+
+    let mut response = OverlayResponse::default();
+    for (i, handle) in handles.iter().enumerate() {
+        let Some(p) = handle else {
+            continue;
+        };
+        let center = to_screen(*p);
+        let hit = egui::Rect::from_center_size(center, egui::Vec2::splat(HANDLE_RADIUS * 3.0));
+        let handle_response = ui.interact(hit, ui.id().with(("touch_up_handle", i)), egui::Sense::drag());
+
+        let delta = handle_response.drag_delta();
+        if delta != egui::Vec2::ZERO {
+            let (column, row) = ((i % columns) as u32, (i / columns) as u32);
+            let offset = correction.get(column, row);
+            correction.set(
+                column,
+                row,
+                [offset[0] + delta.x / rect.width(), offset[1] + delta.y / rect.height()],
+            );
+            response.changed = true;
+        }
+        response.released |= handle_response.drag_stopped();
+
+        let moved = correction.offsets.get(i).is_some_and(|o| o[0] != 0.0 || o[1] != 0.0);
+        let fill = if handle_response.dragged() || handle_response.hovered() {
+            egui::Color32::WHITE
+        } else if moved {
+            egui::Color32::from_rgb(255, 120, 0)
+        } else {
+            egui::Color32::from_rgb(255, 200, 0)
+        };
+        painter.circle(center, HANDLE_RADIUS, fill, egui::Stroke::new(1.0, egui::Color32::BLACK));
+    }
+    response
+}
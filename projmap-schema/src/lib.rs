@@ -0,0 +1,770 @@
+//! Calibration exchange format for projection mapping.
+//!
+//! Shared between `projmap-calibrator`, which writes calibration results, and
+//! `immersive-server`, which imports them into output screens and slices.
+//!
+//! All coordinates follow the calibrator's conventions:
+//! - Camera space is the camera image in pixels and acts as the content space.
+//! - Projector space is the projector's native framebuffer in pixels.
+//! - Homographies map camera pixels to projector pixels and are stored row-major.
+//! - UV maps store normalized camera coordinates (0-1) per projector pixel.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Current version of the exchange format.
+///
+/// Bump this when a change would make older readers misinterpret a file.
+/// Purely additive fields with serde defaults do not need a bump.
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// Version assumed for files written before the format was versioned.
+fn legacy_schema_version() -> u32 {
+    1
+}
+
+/// Error raised when a file cannot be interpreted by this schema version.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SchemaError {
+    /// The file was written by a newer (or invalid) schema version.
+    UnsupportedVersion { found: u32, supported: u32 },
+    /// A correspondence map's point count does not match its grid size.
+    InvalidGrid { expected: usize, found: usize },
+    /// A binary UV map is truncated or has the wrong magic bytes.
+    InvalidUvMap(&'static str),
+}
+
+impl fmt::Display for SchemaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SchemaError::UnsupportedVersion { found, supported } => write!(
+                f,
+                "unsupported calibration schema version {} (this build reads up to {})",
+                found, supported
+            ),
+            SchemaError::InvalidGrid { expected, found } => write!(
+                f,
+                "correspondence map has {} points, expected {}",
+                found, expected
+            ),
+            SchemaError::InvalidUvMap(reason) => write!(f, "invalid UV map: {}", reason),
+        }
+    }
+}
+
+impl std::error::Error for SchemaError {}
+
+/// Check a file's schema version against what this build can read.
+pub fn check_version(version: u32) -> Result<(), SchemaError> {
+    if version == 0 || version > SCHEMA_VERSION {
+        Err(SchemaError::UnsupportedVersion {
+            found: version,
+            supported: SCHEMA_VERSION,
+        })
+    } else {
+        Ok(())
+    }
+}
+
+/// Project configuration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectConfig {
+    /// Exchange format version this file was written with.
+    #[serde(default = "legacy_schema_version")]
+    pub schema_version: u32,
+    /// Project name.
+    pub name: String,
+    /// Canvas width (combined projector output).
+    pub canvas_width: u32,
+    /// Canvas height.
+    pub canvas_height: u32,
+    /// Camera image width the calibration was captured at (0 = unknown).
+    #[serde(default)]
+    pub camera_width: u32,
+    /// Camera image height the calibration was captured at (0 = unknown).
+    #[serde(default)]
+    pub camera_height: u32,
+    /// List of projectors.
+    pub projectors: Vec<ProjectorConfig>,
+    /// NDI camera source name.
+    pub camera_source: Option<String>,
+    /// Additional NDI cameras of a multi-camera calibration.
+    ///
+    /// Their views are merged with the primary camera's, so `camera_width`
+    /// and `camera_height` describe the merged content space.
+    #[serde(default)]
+    pub extra_camera_sources: Vec<String>,
+}
+
+impl Default for ProjectConfig {
+    fn default() -> Self {
+        Self {
+            schema_version: SCHEMA_VERSION,
+            name: "New Project".to_string(),
+            canvas_width: 1920,
+            canvas_height: 1080,
+            camera_width: 0,
+            camera_height: 0,
+            projectors: vec![ProjectorConfig::default()],
+            camera_source: None,
+            extra_camera_sources: Vec::new(),
+        }
+    }
+}
+
+impl ProjectConfig {
+    /// Check that this file can be read by this build.
+    pub fn check_version(&self) -> Result<(), SchemaError> {
+        check_version(self.schema_version)
+    }
+
+    /// Size of the space homographies map from.
+    ///
+    /// This is the camera image when known, otherwise the canvas.
+    pub fn content_size(&self) -> (u32, u32) {
+        if self.camera_width > 0 && self.camera_height > 0 {
+            (self.camera_width, self.camera_height)
+        } else {
+            (self.canvas_width, self.canvas_height)
+        }
+    }
+}
+
+/// Per-projector configuration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectorConfig {
+    /// Unique ID.
+    pub id: u32,
+    /// Display name.
+    pub name: String,
+    /// Native resolution width.
+    pub width: u32,
+    /// Native resolution height.
+    pub height: u32,
+    /// Display adapter index.
+    pub display_index: usize,
+    /// Position in canvas (top-left X).
+    pub canvas_x: i32,
+    /// Position in canvas (top-left Y).
+    pub canvas_y: i32,
+    /// Computed homography (3x3 matrix, row-major, camera -> projector).
+    pub homography: Option<[f64; 9]>,
+    /// Edge blend settings.
+    pub blend: BlendConfig,
+    /// Dense correspondence map file, relative to the project file.
+    #[serde(default)]
+    pub correspondence_file: Option<String>,
+    /// Per-pixel binary UV map file (`.pmuv`), relative to the project file.
+    #[serde(default)]
+    pub uv_map_file: Option<String>,
+    /// Per-pixel blend weights (16-bit grayscale PNG, projector resolution),
+    /// relative to the project file. Replaces the edge blend widths.
+    #[serde(default)]
+    pub blend_mask_file: Option<String>,
+    /// Photometric correction matching this projector to the others.
+    #[serde(default)]
+    pub color_correction: Option<ColorCorrection>,
+    /// Per-projector 3D LUT (`.cube`), relative to the project file.
+    #[serde(default)]
+    pub lut_file: Option<String>,
+    /// Manual touch-up on top of the decoded mapping. Already applied to
+    /// `uv_map_file`; the correspondence map holds the automatic solution.
+    #[serde(default)]
+    pub warp_correction: Option<WarpCorrection>,
+}
+
+impl Default for ProjectorConfig {
+    fn default() -> Self {
+        Self {
+            id: 1,
+            name: "Projector 1".to_string(),
+            width: 1920,
+            height: 1080,
+            display_index: 0,
+            canvas_x: 0,
+            canvas_y: 0,
+            homography: None,
+            blend: BlendConfig::default(),
+            correspondence_file: None,
+            uv_map_file: None,
+            blend_mask_file: None,
+            color_correction: None,
+            lut_file: None,
+            warp_correction: None,
+        }
+    }
+}
+
+/// Edge blend configuration for a projector.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlendConfig {
+    /// Left edge blend width.
+    pub left_width: u32,
+    /// Right edge blend width.
+    pub right_width: u32,
+    /// Top edge blend width.
+    pub top_width: u32,
+    /// Bottom edge blend width.
+    pub bottom_width: u32,
+    /// Gamma correction.
+    pub gamma: f32,
+    /// Blend curve type.
+    pub curve: BlendCurve,
+}
+
+impl Default for BlendConfig {
+    fn default() -> Self {
+        Self {
+            left_width: 0,
+            right_width: 0,
+            top_width: 0,
+            bottom_width: 0,
+            gamma: 2.2,
+            curve: BlendCurve::Gamma,
+        }
+    }
+}
+
+/// Blend curve types.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BlendCurve {
+    Linear,
+    #[default]
+    Gamma,
+    Cosine,
+    Smoothstep,
+}
+
+/// Per-projector color correction from photometric calibration.
+///
+/// Mirrors immersive-server's screen color correction: brightness is added,
+/// contrast scales around 0.5, the signal is raised to `1 / gamma`, then the
+/// channel multipliers are applied.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ColorCorrection {
+    /// Brightness offset (-1.0 to 1.0).
+    pub brightness: f32,
+    /// Contrast multiplier (0.0 to 2.0).
+    pub contrast: f32,
+    /// Gamma (0.1 to 4.0).
+    pub gamma: f32,
+    /// Saturation multiplier (0.0 to 2.0).
+    pub saturation: f32,
+    /// Red channel multiplier.
+    pub red: f32,
+    /// Green channel multiplier.
+    pub green: f32,
+    /// Blue channel multiplier.
+    pub blue: f32,
+}
+
+impl Default for ColorCorrection {
+    fn default() -> Self {
+        Self {
+            brightness: 0.0,
+            contrast: 1.0,
+            gamma: 1.0,
+            saturation: 1.0,
+            red: 1.0,
+            green: 1.0,
+            blue: 1.0,
+        }
+    }
+}
+
+/// Dense projector -> camera correspondences sampled on a regular grid.
+///
+/// Grid nodes are spread evenly over the projector framebuffer, including its
+/// edges. Each node stores the camera pixel it lands on, or `None` where the
+/// structured light could not be decoded.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CorrespondenceMap {
+    /// Exchange format version this file was written with.
+    #[serde(default = "legacy_schema_version")]
+    pub schema_version: u32,
+    /// Projector this map belongs to.
+    pub projector_id: u32,
+    /// Projector resolution width.
+    pub projector_width: u32,
+    /// Projector resolution height.
+    pub projector_height: u32,
+    /// Camera image width.
+    pub camera_width: u32,
+    /// Camera image height.
+    pub camera_height: u32,
+    /// Number of grid columns (>= 2).
+    pub columns: u32,
+    /// Number of grid rows (>= 2).
+    pub rows: u32,
+    /// Camera pixel per grid node, row-major (`row * columns + column`).
+    pub points: Vec<Option<[f32; 2]>>,
+}
+
+impl CorrespondenceMap {
+    /// Create an empty map with every node undecoded.
+    pub fn new(
+        projector_id: u32,
+        projector_size: (u32, u32),
+        camera_size: (u32, u32),
+        columns: u32,
+        rows: u32,
+    ) -> Self {
+        let columns = columns.max(2);
+        let rows = rows.max(2);
+        Self {
+            schema_version: SCHEMA_VERSION,
+            projector_id,
+            projector_width: projector_size.0,
+            projector_height: projector_size.1,
+            camera_width: camera_size.0,
+            camera_height: camera_size.1,
+            columns,
+            rows,
+            points: vec![None; (columns * rows) as usize],
+        }
+    }
+
+    /// Check version and grid consistency.
+    pub fn validate(&self) -> Result<(), SchemaError> {
+        check_version(self.schema_version)?;
+        let expected = (self.columns * self.rows) as usize;
+        if self.columns < 2 || self.rows < 2 || self.points.len() != expected {
+            return Err(SchemaError::InvalidGrid {
+                expected,
+                found: self.points.len(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Camera pixel at a grid node.
+    pub fn get(&self, column: u32, row: u32) -> Option<[f32; 2]> {
+        if column >= self.columns || row >= self.rows {
+            return None;
+        }
+        self.points
+            .get((row * self.columns + column) as usize)
+            .copied()
+            .flatten()
+    }
+
+    /// Set the camera pixel at a grid node.
+    pub fn set(&mut self, column: u32, row: u32, camera: Option<[f32; 2]>) {
+        if column < self.columns && row < self.rows {
+            self.points[(row * self.columns + column) as usize] = camera;
+        }
+    }
+
+    /// Projector pixel a grid node sits on.
+    pub fn node_position(&self, column: u32, row: u32) -> [f32; 2] {
+        let max_x = self.projector_width.saturating_sub(1) as f32;
+        let max_y = self.projector_height.saturating_sub(1) as f32;
+        [
+            column as f32 / (self.columns - 1) as f32 * max_x,
+            row as f32 / (self.rows - 1) as f32 * max_y,
+        ]
+    }
+
+    /// Number of decoded nodes.
+    pub fn valid_count(&self) -> usize {
+        self.points.iter().filter(|p| p.is_some()).count()
+    }
+}
+
+/// Per-pixel projector -> camera lookup table.
+///
+/// Stores, for every projector pixel, the normalized camera UV (0-1) it shows.
+/// Unmapped pixels hold NaN. Serialized as a compact little-endian binary:
+/// `b"PMUV"`, then `u32` schema version, projector id, width and height,
+/// followed by `width * height` pairs of `f32` in row-major order.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UvMap {
+    /// Projector this map belongs to.
+    pub projector_id: u32,
+    /// Map width (projector pixels).
+    pub width: u32,
+    /// Map height (projector pixels).
+    pub height: u32,
+    /// Camera UV per pixel, row-major; NaN where unmapped.
+    pub data: Vec<[f32; 2]>,
+}
+
+impl UvMap {
+    /// Magic bytes at the start of a binary UV map.
+    pub const MAGIC: [u8; 4] = *b"PMUV";
+
+    const HEADER_LEN: usize = 20;
+
+    /// Upsample a correspondence grid to a dense per-pixel map.
+    ///
+    /// Pixels in a grid cell with any undecoded corner are left unmapped.
+    pub fn from_correspondence_map(map: &CorrespondenceMap, width: u32, height: u32) -> Self {
+        let width = width.max(1);
+        let height = height.max(1);
+        let camera_w = map.camera_width.max(1) as f32;
+        let camera_h = map.camera_height.max(1) as f32;
+        let max_col = (map.columns - 1) as f32;
+        let max_row = (map.rows - 1) as f32;
+        let mut data = Vec::with_capacity((width * height) as usize);
+
+        for y in 0..height {
+            let gy = if height > 1 { y as f32 / (height - 1) as f32 * max_row } else { 0.0 };
+            let row = (gy.floor() as u32).min(map.rows - 2);
+            let fy = gy - row as f32;
+            for x in 0..width {
+                let gx = if width > 1 { x as f32 / (width - 1) as f32 * max_col } else { 0.0 };
+                let col = (gx.floor() as u32).min(map.columns - 2);
+                let fx = gx - col as f32;
+                let corners = (
+                    map.get(col, row),
+                    map.get(col + 1, row),
+                    map.get(col, row + 1),
+                    map.get(col + 1, row + 1),
+                );
+                let uv = match corners {
+                    (Some(a), Some(b), Some(c), Some(d)) => {
+                        let top = [a[0] + (b[0] - a[0]) * fx, a[1] + (b[1] - a[1]) * fx];
+                        let bottom = [c[0] + (d[0] - c[0]) * fx, c[1] + (d[1] - c[1]) * fx];
+                        [
+                            (top[0] + (bottom[0] - top[0]) * fy) / camera_w,
+                            (top[1] + (bottom[1] - top[1]) * fy) / camera_h,
+                        ]
+                    }
+                    _ => [f32::NAN, f32::NAN],
+                };
+                data.push(uv);
+            }
+        }
+
+        Self {
+            projector_id: map.projector_id,
+            width,
+            height,
+            data,
+        }
+    }
+
+    /// Encode as the binary `.pmuv` format.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(Self::HEADER_LEN + self.data.len() * 8);
+        bytes.extend_from_slice(&Self::MAGIC);
+        for value in [SCHEMA_VERSION, self.projector_id, self.width, self.height] {
+            bytes.extend_from_slice(&value.to_le_bytes());
+        }
+        for uv in &self.data {
+            bytes.extend_from_slice(&uv[0].to_le_bytes());
+            bytes.extend_from_slice(&uv[1].to_le_bytes());
+        }
+        bytes
+    }
+
+    /// Decode the binary `.pmuv` format.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, SchemaError> {
+        if bytes.len() < Self::HEADER_LEN || bytes[..4] != Self::MAGIC {
+            return Err(SchemaError::InvalidUvMap("missing PMUV header"));
+        }
+        let read_u32 = |offset: usize| {
+            u32::from_le_bytes([
+                bytes[offset],
+                bytes[offset + 1],
+                bytes[offset + 2],
+                bytes[offset + 3],
+            ])
+        };
+        check_version(read_u32(4))?;
+        let projector_id = read_u32(8);
+        let width = read_u32(12);
+        let height = read_u32(16);
+
+        let count = width as usize * height as usize;
+        let payload = &bytes[Self::HEADER_LEN..];
+        if payload.len() != count * 8 {
+            return Err(SchemaError::InvalidUvMap("payload size does not match dimensions"));
+        }
+        let data = payload
+            .chunks_exact(8)
+            .map(|c| {
+                [
+                    f32::from_le_bytes([c[0], c[1], c[2], c[3]]),
+                    f32::from_le_bytes([c[4], c[5], c[6], c[7]]),
+                ]
+            })
+            .collect();
+
+        Ok(Self {
+            projector_id,
+            width,
+            height,
+            data,
+        })
+    }
+}
+
+/// Manual warp correction layered over a projector's automatic mapping.
+///
+/// Control points sit on a regular grid spread over the projector
+/// framebuffer, edges included. Each stores how far the content at that point
+/// is moved, in normalized content coordinates (the UV map's units), and the
+/// offsets are bilinearly interpolated in between. The correction is kept
+/// apart from the decoded mapping so it can be re-applied after recalibrating.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WarpCorrection {
+    /// Number of grid columns (>= 2).
+    pub columns: u32,
+    /// Number of grid rows (>= 2).
+    pub rows: u32,
+    /// Content offset per control point, row-major (`row * columns + column`).
+    pub offsets: Vec<[f32; 2]>,
+}
+
+impl WarpCorrection {
+    /// Create a correction that leaves the mapping unchanged.
+    pub fn new(columns: u32, rows: u32) -> Self {
+        let columns = columns.max(2);
+        let rows = rows.max(2);
+        Self {
+            columns,
+            rows,
+            offsets: vec![[0.0, 0.0]; (columns * rows) as usize],
+        }
+    }
+
+    /// Check grid consistency.
+    pub fn validate(&self) -> Result<(), SchemaError> {
+        let expected = (self.columns * self.rows) as usize;
+        if self.columns < 2 || self.rows < 2 || self.offsets.len() != expected {
+            return Err(SchemaError::InvalidGrid {
+                expected,
+                found: self.offsets.len(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Offset of a control point.
+    pub fn get(&self, column: u32, row: u32) -> [f32; 2] {
+        if column >= self.columns || row >= self.rows {
+            return [0.0, 0.0];
+        }
+        self.offsets
+            .get((row * self.columns + column) as usize)
+            .copied()
+            .unwrap_or([0.0, 0.0])
+    }
+
+    /// Set the offset of a control point.
+    pub fn set(&mut self, column: u32, row: u32, offset: [f32; 2]) {
+        if column < self.columns && row < self.rows {
+            self.offsets[(row * self.columns + column) as usize] = offset;
+        }
+    }
+
+    /// Normalized projector position (0-1) of a control point.
+    pub fn node_position(&self, column: u32, row: u32) -> [f32; 2] {
+        [
+            column as f32 / (self.columns - 1) as f32,
+            row as f32 / (self.rows - 1) as f32,
+        ]
+    }
+
+    /// Whether every offset is zero.
+    pub fn is_identity(&self) -> bool {
+        self.offsets.iter().all(|o| o[0] == 0.0 && o[1] == 0.0)
+    }
+
+    /// Interpolated offset at a normalized projector position.
+    pub fn offset_at(&self, u: f32, v: f32) -> [f32; 2] {
+        let gx = u.clamp(0.0, 1.0) * (self.columns - 1) as f32;
+        let gy = v.clamp(0.0, 1.0) * (self.rows - 1) as f32;
+        let col = (gx.floor() as u32).min(self.columns - 2);
+        let row = (gy.floor() as u32).min(self.rows - 2);
+        let fx = gx - col as f32;
+        let fy = gy - row as f32;
+        let (a, b) = (self.get(col, row), self.get(col + 1, row));
+        let (c, d) = (self.get(col, row + 1), self.get(col + 1, row + 1));
+        let mut offset = [0.0; 2];
+        for (i, value) in offset.iter_mut().enumerate() {
+            let top = a[i] + (b[i] - a[i]) * fx;
+            let bottom = c[i] + (d[i] - c[i]) * fx;
+            *value = top + (bottom - top) * fy;
+        }
+        offset
+    }
+
+    /// Apply the correction to a per-pixel UV map.
+    ///
+    /// Moving content by an offset means each pixel shows the content that
+    /// many units back. Unmapped pixels stay unmapped.
+    pub fn apply(&self, map: &UvMap) -> UvMap {
+        let max_x = map.width.saturating_sub(1).max(1) as f32;
+        let max_y = map.height.saturating_sub(1).max(1) as f32;
+        let mut data = map.data.clone();
+        for (i, uv) in data.iter_mut().enumerate() {
+            if uv[0].is_nan() || uv[1].is_nan() {
+                continue;
+            }
+            let x = (i as u32 % map.width.max(1)) as f32;
+            let y = (i as u32 / map.width.max(1)) as f32;
+            let offset = self.offset_at(x / max_x, y / max_y);
+            uv[0] -= offset[0];
+            uv[1] -= offset[1];
+        }
+        UvMap { data, ..map.clone() }
+    }
+}
+
+/// Apply a row-major homography to a point.
+///
+/// Returns `None` when the point maps to infinity.
+pub fn project_point(h: &[f64; 9], x: f64, y: f64) -> Option<(f64, f64)> {
+    let w = h[6] * x + h[7] * y + h[8];
+    if w.abs() < 1e-12 {
+        return None;
+    }
+    Some((
+        (h[0] * x + h[1] * y + h[2]) / w,
+        (h[3] * x + h[4] * y + h[5]) / w,
+    ))
+}
+
+/// Invert a row-major homography. Returns `None` if it is singular.
+pub fn invert_homography(h: &[f64; 9]) -> Option<[f64; 9]> {
+    let det = h[0] * (h[4] * h[8] - h[5] * h[7]) - h[1] * (h[3] * h[8] - h[5] * h[6])
+        + h[2] * (h[3] * h[7] - h[4] * h[6]);
+    if det.abs() < 1e-12 {
+        return None;
+    }
+    let inv_det = 1.0 / det;
+    Some([
+        (h[4] * h[8] - h[5] * h[7]) * inv_det,
+        (h[2] * h[7] - h[1] * h[8]) * inv_det,
+        (h[1] * h[5] - h[2] * h[4]) * inv_det,
+        (h[5] * h[6] - h[3] * h[8]) * inv_det,
+        (h[0] * h[8] - h[2] * h[6]) * inv_det,
+        (h[2] * h[3] - h[0] * h[5]) * inv_det,
+        (h[3] * h[7] - h[4] * h[6]) * inv_det,
+        (h[1] * h[6] - h[0] * h[7]) * inv_det,
+        (h[0] * h[4] - h[1] * h[3]) * inv_det,
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_legacy_project_defaults_to_version_one() {
+        let json = r#"{
+            "name": "Old",
+            "canvas_width": 3840,
+            "canvas_height": 1080,
+            "projectors": [{
+                "id": 0, "name": "P0", "width": 1920, "height": 1080,
+                "display_index": 0, "canvas_x": 0, "canvas_y": 0,
+                "homography": null,
+                "blend": {"left_width": 0, "right_width": 200, "top_width": 0,
+                          "bottom_width": 0, "gamma": 2.2, "curve": "Gamma"}
+            }],
+            "camera_source": null
+        }"#;
+        let project: ProjectConfig = serde_json::from_str(json).unwrap();
+        assert_eq!(project.schema_version, 1);
+        assert!(project.check_version().is_ok());
+        assert_eq!(project.content_size(), (3840, 1080));
+        assert!(project.projectors[0].correspondence_file.is_none());
+        assert!(project.projectors[0].color_correction.is_none());
+        assert!(project.projectors[0].lut_file.is_none());
+        assert!(project.projectors[0].blend_mask_file.is_none());
+        assert!(project.projectors[0].warp_correction.is_none());
+        assert!(project.extra_camera_sources.is_empty());
+    }
+
+    #[test]
+    fn test_newer_version_rejected() {
+        let project = ProjectConfig {
+            schema_version: SCHEMA_VERSION + 1,
+            ..Default::default()
+        };
+        assert!(matches!(
+            project.check_version(),
+            Err(SchemaError::UnsupportedVersion { .. })
+        ));
+    }
+
+    #[test]
+    fn test_homography_round_trip() {
+        let h = [1.2, 0.1, 30.0, -0.05, 0.9, 12.0, 0.0001, 0.0002, 1.0];
+        let inv = invert_homography(&h).unwrap();
+        let (px, py) = project_point(&h, 100.0, 200.0).unwrap();
+        let (x, y) = project_point(&inv, px, py).unwrap();
+        assert!((x - 100.0).abs() < 1e-9);
+        assert!((y - 200.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_correspondence_map_grid() {
+        let mut map = CorrespondenceMap::new(2, (1921, 1081), (1280, 720), 5, 3);
+        assert!(map.validate().is_ok());
+        assert_eq!(map.node_position(4, 2), [1920.0, 1080.0]);
+        assert_eq!(map.node_position(2, 1), [960.0, 540.0]);
+
+        map.set(1, 2, Some([10.0, 20.0]));
+        assert_eq!(map.get(1, 2), Some([10.0, 20.0]));
+        assert_eq!(map.get(5, 0), None);
+        assert_eq!(map.valid_count(), 1);
+
+        map.points.pop();
+        assert!(map.validate().is_err());
+    }
+
+    #[test]
+    fn test_uv_map_from_grid_and_binary_round_trip() {
+        let mut map = CorrespondenceMap::new(4, (5, 3), (100, 50), 2, 2);
+        map.set(0, 0, Some([0.0, 0.0]));
+        map.set(1, 0, Some([100.0, 0.0]));
+        map.set(0, 1, Some([0.0, 50.0]));
+        map.set(1, 1, Some([100.0, 50.0]));
+
+        let uv = UvMap::from_correspondence_map(&map, 5, 3);
+        assert_eq!(uv.data.len(), 15);
+        assert_eq!(uv.data[0], [0.0, 0.0]);
+        assert_eq!(uv.data[7], [0.5, 0.5]);
+        assert_eq!(uv.data[14], [1.0, 1.0]);
+
+        let decoded = UvMap::from_bytes(&uv.to_bytes()).unwrap();
+        assert_eq!(decoded, uv);
+        assert!(UvMap::from_bytes(&uv.to_bytes()[..30]).is_err());
+
+        map.set(1, 1, None);
+        let holes = UvMap::from_correspondence_map(&map, 5, 3);
+        assert!(holes.data[7][0].is_nan());
+    }
+
+    #[test]
+    fn test_warp_correction_interpolates_and_moves_content() {
+        let mut correction = WarpCorrection::new(3, 2);
+        assert!(correction.validate().is_ok());
+        assert!(correction.is_identity());
+        assert_eq!(correction.node_position(1, 1), [0.5, 1.0]);
+
+        correction.set(1, 0, [0.2, 0.0]);
+        correction.set(1, 1, [0.2, 0.0]);
+        assert_eq!(correction.offset_at(0.5, 0.3), [0.2, 0.0]);
+        assert!((correction.offset_at(0.25, 0.0)[0] - 0.1).abs() < 1e-6);
+        assert_eq!(correction.offset_at(1.0, 1.0), [0.0, 0.0]);
+
+        let map = UvMap {
+            projector_id: 0,
+            width: 3,
+            height: 1,
+            data: vec![[0.0, 0.0], [0.5, 0.5], [f32::NAN, f32::NAN]],
+        };
+        let corrected = correction.apply(&map);
+        assert_eq!(corrected.data[0], [0.0, 0.0]);
+        assert!((corrected.data[1][0] - 0.3).abs() < 1e-6);
+        assert!(corrected.data[2][0].is_nan());
+
+        correction.offsets.pop();
+        assert!(correction.validate().is_err());
+    }
+}